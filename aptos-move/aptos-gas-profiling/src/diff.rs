@@ -0,0 +1,134 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::BTreeMap, fmt};
+
+/// How the gas usage of a single entry changed relative to the baseline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GasChange {
+    /// The gas usage increased by more than the threshold.
+    Regressed,
+    /// The gas usage decreased by more than the threshold.
+    Improved,
+    /// The entry is not present in the baseline.
+    Added,
+    /// The entry is present in the baseline, but was not measured this time.
+    Removed,
+    /// The gas usage did not change by more than the threshold.
+    Unchanged,
+}
+
+impl fmt::Display for GasChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", match self {
+            GasChange::Regressed => "regressed",
+            GasChange::Improved => "improved",
+            GasChange::Added => "added",
+            GasChange::Removed => "removed",
+            GasChange::Unchanged => "unchanged",
+        })
+    }
+}
+
+/// The gas usage of a single entry (e.g., a unit test), before and after.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GasDiffEntry {
+    pub name: String,
+    pub baseline: Option<u64>,
+    pub current: Option<u64>,
+    pub change: GasChange,
+}
+
+impl GasDiffEntry {
+    /// Returns the change relative to the baseline in percent, if both measurements exist.
+    pub fn percentage(&self) -> Option<f64> {
+        match (self.baseline, self.current) {
+            (Some(baseline), Some(current)) => Some(relative_change(baseline, current)),
+            _ => None,
+        }
+    }
+}
+
+/// A comparison of gas usages against a baseline, which can be rendered as a report.
+#[derive(Debug, Clone)]
+pub struct GasDiff {
+    pub threshold_percentage: f64,
+    pub entries: Vec<GasDiffEntry>,
+}
+
+fn relative_change(baseline: u64, current: u64) -> f64 {
+    if baseline == 0 {
+        if current == 0 {
+            0.0
+        } else {
+            f64::INFINITY
+        }
+    } else {
+        (current as f64 - baseline as f64) / baseline as f64 * 100.0
+    }
+}
+
+impl GasDiff {
+    /// Compares the current gas usages against the baseline. A change is only considered
+    /// a regression (or an improvement) if it exceeds `threshold_percentage`.
+    pub fn new(
+        baseline: &BTreeMap<String, u64>,
+        current: &BTreeMap<String, u64>,
+        threshold_percentage: f64,
+    ) -> Self {
+        let mut names = baseline.keys().chain(current.keys()).collect::<Vec<_>>();
+        names.sort();
+        names.dedup();
+
+        let entries = names
+            .into_iter()
+            .map(|name| {
+                let baseline = baseline.get(name).copied();
+                let current = current.get(name).copied();
+                let change = match (baseline, current) {
+                    (None, _) => GasChange::Added,
+                    (_, None) => GasChange::Removed,
+                    (Some(baseline), Some(current)) => {
+                        let percentage = relative_change(baseline, current);
+                        if percentage > threshold_percentage {
+                            GasChange::Regressed
+                        } else if percentage < -threshold_percentage {
+                            GasChange::Improved
+                        } else {
+                            GasChange::Unchanged
+                        }
+                    },
+                };
+                GasDiffEntry {
+                    name: name.clone(),
+                    baseline,
+                    current,
+                    change,
+                }
+            })
+            .collect();
+
+        Self {
+            threshold_percentage,
+            entries,
+        }
+    }
+
+    /// Returns all entries with the given kind of change.
+    pub fn entries_with(&self, change: GasChange) -> impl Iterator<Item = &GasDiffEntry> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.change == change)
+    }
+
+    pub fn has_regressions(&self) -> bool {
+        self.entries_with(GasChange::Regressed).next().is_some()
+    }
+
+    /// Returns `true` if any entry changed beyond the threshold, or was added or removed.
+    pub fn has_changes(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.change != GasChange::Unchanged)
+    }
+}
@@ -1,7 +1,11 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{log::TransactionGasLog, render::Render};
+use crate::{
+    diff::{GasChange, GasDiff, GasDiffEntry},
+    log::TransactionGasLog,
+    render::Render,
+};
 use anyhow::Result;
 use aptos_gas_algebra::{Fee, InternalGas};
 use handlebars::Handlebars;
@@ -13,6 +17,7 @@ use std::{
 };
 
 const TEMPLATE: &str = include_str!("../templates/index.html");
+const DIFF_TEMPLATE: &str = include_str!("../templates/diff.html");
 
 fn ensure_dirs_exist(path: impl AsRef<Path>) -> Result<()> {
     if let Err(err) = fs::create_dir_all(&path) {
@@ -351,3 +356,96 @@ impl TransactionGasLog {
         Ok(())
     }
 }
+
+fn render_gas(gas: Option<u64>) -> String {
+    gas.map(|gas| gas.to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn render_percentage(entry: &GasDiffEntry) -> String {
+    match entry.percentage() {
+        Some(percentage) if percentage.is_infinite() => "+inf%".to_string(),
+        Some(percentage) => format!("{:+.2}%", percentage),
+        None => "-".to_string(),
+    }
+}
+
+impl GasDiff {
+    fn render_summary(&self) -> String {
+        let count = |change| self.entries_with(change).count();
+        format!(
+            "{} regressed, {} improved, {} added, {} removed, {} unchanged (threshold: {}%)",
+            count(GasChange::Regressed),
+            count(GasChange::Improved),
+            count(GasChange::Added),
+            count(GasChange::Removed),
+            count(GasChange::Unchanged),
+            self.threshold_percentage,
+        )
+    }
+
+    /// Renders the entries that changed beyond the threshold as a markdown document,
+    /// e.g. to be posted as a comment on a pull request.
+    pub fn generate_markdown_report(&self, header: &str) -> String {
+        let mut output = format!("# {}\n\n{}\n", header, self.render_summary());
+
+        let changed = self
+            .entries
+            .iter()
+            .filter(|entry| entry.change != GasChange::Unchanged)
+            .collect::<Vec<_>>();
+        if !changed.is_empty() {
+            output.push_str("\n| Name | Baseline | Current | Change | Status |\n");
+            output.push_str("|:-----|---------:|--------:|-------:|:-------|\n");
+            for entry in changed {
+                output.push_str(&format!(
+                    "| `{}` | {} | {} | {} | {} |\n",
+                    entry.name,
+                    render_gas(entry.baseline),
+                    render_gas(entry.current),
+                    render_percentage(entry),
+                    entry.change,
+                ));
+            }
+        }
+
+        output
+    }
+
+    /// Renders all entries as an html document, saved as `index.html` in the given directory.
+    pub fn generate_html_report(&self, path: impl AsRef<Path>, header: String) -> Result<()> {
+        let mut data = Map::new();
+        data.insert("title".to_string(), Value::String(header));
+        data.insert("summary".to_string(), Value::String(self.render_summary()));
+
+        let mut entries = self.entries.iter().collect::<Vec<_>>();
+        entries.sort_by_key(|entry| entry.change);
+        data.insert(
+            "entries".to_string(),
+            Value::Array(
+                entries
+                    .into_iter()
+                    .map(|entry| {
+                        json!({
+                            "name": entry.name,
+                            "baseline": render_gas(entry.baseline),
+                            "current": render_gas(entry.current),
+                            "percentage": render_percentage(entry),
+                            "change": entry.change.to_string(),
+                        })
+                    })
+                    .collect(),
+            ),
+        );
+
+        let mut handlebars = Handlebars::new();
+        handlebars.register_template_string("diff", DIFF_TEMPLATE)?;
+        let html = handlebars.render("diff", &data)?;
+
+        let path_root = path.as_ref();
+        ensure_dirs_exist(path_root)?;
+        fs::write(path_root.join("index.html"), html)?;
+
+        Ok(())
+    }
+}
@@ -4,7 +4,7 @@
 //! This module defines the gas parameters for Aptos Framework & Stdlib.
 
 use crate::{
-    gas_feature_versions::{RELEASE_V1_14, RELEASE_V1_8, RELEASE_V1_9_SKIPPED},
+    gas_feature_versions::{RELEASE_V1_14, RELEASE_V1_19, RELEASE_V1_8, RELEASE_V1_9_SKIPPED},
     gas_schedule::NativeGasParameters,
     ver::gas_feature_versions::{RELEASE_V1_12, RELEASE_V1_13},
 };
@@ -190,6 +190,10 @@ crate::gas_schedule::macros::define_gas_parameters!(
         [secp256k1_base: InternalGas, "secp256k1.base", 551],
         [secp256k1_ecdsa_recover: InternalGasPerArg, "secp256k1.ecdsa_recover", 5918360],
 
+        [secp256r1_base: InternalGas, { RELEASE_V1_19.. => "secp256r1.base" }, 551],
+        [secp256r1_ecdsa_verify: InternalGasPerArg, { RELEASE_V1_19.. => "secp256r1.ecdsa_verify" }, 6350000],
+        [secp256r1_ecdsa_recover: InternalGasPerArg, { RELEASE_V1_19.. => "secp256r1.ecdsa_recover" }, 6900000],
+
         [ristretto255_basepoint_mul: InternalGasPerArg, "ristretto255.basepoint_mul", 470528],
         [ristretto255_basepoint_double_mul: InternalGasPerArg, "ristretto255.basepoint_double_mul", 1617440],
 
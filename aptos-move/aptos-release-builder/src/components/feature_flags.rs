@@ -128,6 +128,7 @@ pub enum FeatureFlag {
     EnableResourceAccessControl,
     RejectUnstableBytecodeForScript,
     FederatedKeyless,
+    Secp256r1Natives,
 }
 
 fn generate_features_blob(writer: &CodeWriter, data: &[u64]) {
@@ -338,6 +339,7 @@ impl From<FeatureFlag> for AptosFeatureFlag {
                 AptosFeatureFlag::REJECT_UNSTABLE_BYTECODE_FOR_SCRIPT
             },
             FeatureFlag::FederatedKeyless => AptosFeatureFlag::FEDERATED_KEYLESS,
+            FeatureFlag::Secp256r1Natives => AptosFeatureFlag::SECP256R1_NATIVES,
         }
     }
 }
@@ -477,6 +479,7 @@ impl From<AptosFeatureFlag> for FeatureFlag {
                 FeatureFlag::RejectUnstableBytecodeForScript
             },
             AptosFeatureFlag::FEDERATED_KEYLESS => FeatureFlag::FederatedKeyless,
+            AptosFeatureFlag::SECP256R1_NATIVES => FeatureFlag::Secp256r1Natives,
         }
     }
 }
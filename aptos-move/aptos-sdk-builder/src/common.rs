@@ -2,11 +2,15 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::view_function::ViewFunctionABI;
 use aptos_types::transaction::{
     ArgumentABI, EntryABI, EntryFunctionABI, TransactionScriptABI, TypeArgumentABI,
 };
 use heck::ToUpperCamelCase;
-use move_core_types::language_storage::{StructTag, TypeTag};
+use move_core_types::{
+    account_address::AccountAddress,
+    language_storage::{ModuleId, StructTag, TypeTag},
+};
 use once_cell::sync::Lazy;
 use serde_reflection::{ContainerFormat, Format, Named, VariantFormat};
 use std::{
@@ -181,3 +185,115 @@ pub(crate) fn entry_function_abis(abis: &[EntryABI]) -> Vec<EntryFunctionABI> {
         })
         .collect::<Vec<_>>()
 }
+
+/// Structs which can be passed as arguments to entry and view functions, see
+/// `aptos_vm::verifier::transaction_arg_validation`.
+pub(crate) enum ArgumentStruct<'a> {
+    /// `0x1::string::String`, encoded like a `vector<u8>` of UTF-8 bytes.
+    String,
+    /// `0x1::object::Object<T>`, encoded like the address of the object.
+    Object,
+    /// `0x1::option::Option<T>`, encoded like a `vector<T>` of at most one element.
+    Option(&'a TypeTag),
+    /// `0x1::fixed_point32::FixedPoint32`, encoded like its raw `u64` value.
+    FixedPoint32,
+    /// `0x1::fixed_point64::FixedPoint64`, encoded like its raw `u128` value.
+    FixedPoint64,
+}
+
+pub(crate) fn argument_struct(tag: &StructTag) -> Option<ArgumentStruct<'_>> {
+    if tag.address != AccountAddress::ONE {
+        return None;
+    }
+    match (
+        tag.module.as_str(),
+        tag.name.as_str(),
+        tag.type_args.as_slice(),
+    ) {
+        ("string", "String", []) => Some(ArgumentStruct::String),
+        ("object", "Object", [_]) => Some(ArgumentStruct::Object),
+        ("option", "Option", [type_arg]) => Some(ArgumentStruct::Option(type_arg)),
+        ("fixed_point32", "FixedPoint32", []) => Some(ArgumentStruct::FixedPoint32),
+        ("fixed_point64", "FixedPoint64", []) => Some(ArgumentStruct::FixedPoint64),
+        _ => None,
+    }
+}
+
+/// Whether arguments of the given type can be encoded by the generators which support
+/// struct arguments (TypeScript and Python).
+pub(crate) fn is_supported_argument_type(type_tag: &TypeTag) -> bool {
+    match type_tag {
+        TypeTag::Signer => false,
+        TypeTag::Vector(type_tag) => is_supported_argument_type(type_tag),
+        TypeTag::Struct(tag) => match argument_struct(tag) {
+            Some(ArgumentStruct::Option(type_tag)) => is_supported_argument_type(type_tag),
+            Some(_) => true,
+            None => false,
+        },
+        _ => true,
+    }
+}
+
+/// A function for which the generators which support struct arguments (TypeScript and
+/// Python) output a builder, if all of its arguments are supported.
+pub(crate) struct FunctionCall<'a> {
+    pub module_name: &'a ModuleId,
+    pub name: &'a str,
+    pub doc: String,
+    pub ty_args: &'a [TypeArgumentABI],
+    pub args: &'a [ArgumentABI],
+}
+
+impl<'a> FunctionCall<'a> {
+    pub fn entry_functions(abis: &'a [EntryABI]) -> Vec<Self> {
+        abis.iter()
+            .filter_map(|abi| match abi {
+                EntryABI::EntryFunction(abi) => Some(Self {
+                    module_name: abi.module_name(),
+                    name: abi.name(),
+                    doc: prepare_doc_string(abi.doc()),
+                    ty_args: abi.ty_args(),
+                    args: abi.args(),
+                }),
+                EntryABI::TransactionScript(_) => None,
+            })
+            .filter(Self::is_supported)
+            .collect()
+    }
+
+    pub fn view_functions(abis: &'a [ViewFunctionABI]) -> Vec<Self> {
+        abis.iter()
+            .map(|abi| {
+                let returns = abi
+                    .returns()
+                    .iter()
+                    .map(|type_tag| type_tag.to_string())
+                    .collect::<Vec<_>>();
+                let returns = if returns.len() == 1 {
+                    returns[0].clone()
+                } else {
+                    format!("({})", returns.join(", "))
+                };
+                Self {
+                    module_name: abi.module_name(),
+                    name: abi.name(),
+                    doc: format!(
+                        "Request to call the view function `{}::{}`, which returns `{}`.",
+                        abi.module_name().short_str_lossless(),
+                        abi.name(),
+                        returns
+                    ),
+                    ty_args: abi.ty_args(),
+                    args: abi.args(),
+                }
+            })
+            .filter(Self::is_supported)
+            .collect()
+    }
+
+    fn is_supported(&self) -> bool {
+        self.args
+            .iter()
+            .all(|arg| is_supported_argument_type(arg.type_tag()))
+    }
+}
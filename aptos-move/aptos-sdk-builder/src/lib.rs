@@ -3,10 +3,25 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_types::transaction::EntryABI;
+use move_binary_format::CompiledModule;
+use move_bytecode_source_map::source_map::SourceMap;
+pub use move_struct::{
+    struct_abis, FieldABI, FieldType, MoveStructABI, StructLayoutABI, StructTypeParameterABI,
+    VariantABI,
+};
 use std::{ffi::OsStr, fs, io::Read, path::Path};
+pub use view_function::{view_function_abis, ViewFunctionABI};
 
 pub mod golang;
+pub mod python;
 pub mod rust;
+pub mod rust_bindings;
+pub mod typescript;
+
+/// Extraction of structs from compiled modules.
+mod move_struct;
+/// Extraction of view functions from compiled modules.
+mod view_function;
 
 /// Internals shared between languages.
 mod common;
@@ -56,6 +71,52 @@ pub fn read_abis(dir_paths: &[impl AsRef<Path>]) -> anyhow::Result<Vec<EntryABI>
     Ok(abis)
 }
 
+/// Read all compiled modules (`.mv` files) in the specified directories, along with their
+/// source maps when they can be found next to the modules, or in the `source_maps` directory
+/// of the package build.
+fn read_modules(
+    dir_paths: &[impl AsRef<Path>],
+) -> anyhow::Result<Vec<(CompiledModule, Option<SourceMap>)>> {
+    let mut modules = Vec::new();
+    for dir in dir_paths.iter() {
+        for entry in fs::read_dir(dir.as_ref())? {
+            let path = entry?.path();
+            if let Some("mv") = path.extension().and_then(OsStr::to_str) {
+                modules.push(view_function::read_module(&path)?);
+            }
+        }
+    }
+    Ok(modules)
+}
+
+/// Read the view functions of all compiled modules in the specified directories.
+/// Source maps are used for parameter names, if available.
+pub fn read_view_function_abis(
+    dir_paths: &[impl AsRef<Path>],
+) -> anyhow::Result<Vec<ViewFunctionABI>> {
+    let mut abis = Vec::new();
+    for (module, source_map) in read_modules(dir_paths)? {
+        abis.append(&mut view_function_abis(&module, source_map.as_ref()));
+    }
+
+    // Sort functions by (module, function) lexicographical order
+    abis.sort_by(|a, b| {
+        (a.module_name().to_string(), a.name()).cmp(&(b.module_name().to_string(), b.name()))
+    });
+    Ok(abis)
+}
+
+/// Read the structs of all compiled modules in the specified directories, in declaration order
+/// within each module. Source maps are used for type parameter names, if available.
+pub fn read_struct_abis(dir_paths: &[impl AsRef<Path>]) -> anyhow::Result<Vec<MoveStructABI>> {
+    let mut modules = read_modules(dir_paths)?;
+    modules.sort_by_key(|(module, _)| module.self_id());
+    Ok(modules
+        .iter()
+        .flat_map(|(module, source_map)| struct_abis(module, source_map.as_ref()))
+        .collect())
+}
+
 /// How to copy ABI-generated source code for a given language.
 pub trait SourceInstaller {
     type Error;
@@ -16,6 +16,9 @@ use std::path::PathBuf;
 enum Language {
     Rust,
     Go,
+    #[value(name = "typescript")]
+    TypeScript,
+    Python,
 }
 
 #[derive(Debug, Parser)]
@@ -24,6 +27,17 @@ struct Options {
     /// Path to the directory containing ABI files in BCS encoding.
     abi_directories: Vec<PathBuf>,
 
+    /// Paths to directories containing compiled modules (`.mv` files), for which wrappers of
+    /// view functions are generated (TypeScript, Python and Rust bindings only).
+    #[clap(long)]
+    module_directories: Vec<PathBuf>,
+
+    /// Generate Rust bindings for the structs and view functions of the modules in
+    /// `--module-directories`, instead of transaction builders. They are written to
+    /// `<module_name>.rs` in the `target_source_dir`, if given.
+    #[clap(long)]
+    rust_bindings: bool,
+
     /// Language for code generation.
     #[clap(long, value_enum, ignore_case = true, default_value_t = Language::Rust)]
     language: Language,
@@ -41,10 +55,11 @@ struct Options {
     /// * In Java, this is expected to be a package name, e.g. "com.test" to create Java files in `com/test`.
     /// * In Go, this is expected to be of the format "go_module/path/go_package_name",
     /// and `aptos_types` is assumed to be in "go_module/path/aptos_types".
+    /// * In TypeScript and Python, this is the name of the directory created for the builders.
     #[clap(long)]
     module_name: Option<String>,
 
-    /// Optional package name (Python) or module path (Go) of the Serde and BCS runtime dependencies.
+    /// Optional package name (Python) or module path (Go, TypeScript) of the Serde and BCS runtime dependencies.
     #[clap(long)]
     serde_package_name: Option<String>,
 
@@ -53,7 +68,7 @@ struct Options {
     #[clap(long, default_value = "0.1.0")]
     aptos_version_number: String,
 
-    /// Optional package name (Python) or module path (Go) of the `aptos_types` dependency.
+    /// Optional package name (Python) or module path (Go, TypeScript) of the `aptos_types` dependency.
     #[clap(long)]
     package_name: Option<String>,
 }
@@ -62,6 +77,23 @@ fn main() {
     let options = Options::parse();
     let abis = aptos_sdk_builder::read_abis(&options.abi_directories)
         .expect("Failed to read ABI in directory");
+    let view_functions = aptos_sdk_builder::read_view_function_abis(&options.module_directories)
+        .expect("Failed to read compiled modules in directory");
+
+    if options.rust_bindings {
+        let structs = aptos_sdk_builder::read_struct_abis(&options.module_directories)
+            .expect("Failed to read compiled modules in directory");
+        let mut out: Box<dyn std::io::Write> = match options.target_source_dir {
+            None => Box::new(std::io::stdout()),
+            Some(dir) => {
+                let name = options.module_name.as_deref().unwrap_or("bindings");
+                std::fs::create_dir_all(&dir).unwrap();
+                Box::new(std::fs::File::create(dir.join(name).with_extension("rs")).unwrap())
+            },
+        };
+        aptos_sdk_builder::rust_bindings::output(&mut out, &structs, &view_functions).unwrap();
+        return;
+    }
 
     let install_dir = match options.target_source_dir {
         None => {
@@ -83,6 +115,25 @@ fn main() {
                     )
                     .unwrap();
                 },
+                Language::TypeScript => {
+                    aptos_sdk_builder::typescript::output(
+                        &mut out,
+                        options.serde_package_name.clone(),
+                        options.package_name.clone(),
+                        &abis,
+                        &view_functions,
+                    )
+                    .unwrap();
+                },
+                Language::Python => {
+                    aptos_sdk_builder::python::output(
+                        &mut out,
+                        options.package_name.clone(),
+                        &abis,
+                        &view_functions,
+                    )
+                    .unwrap();
+                },
             }
             return;
         },
@@ -98,6 +149,13 @@ fn main() {
                     install_dir.clone(),
                     options.serde_package_name.clone(),
                 )),
+                Language::TypeScript => {
+                    Box::new(serdegen::typescript::Installer::new(install_dir.clone()))
+                },
+                Language::Python => Box::new(serdegen::python3::Installer::new(
+                    install_dir.clone(),
+                    options.serde_package_name.clone(),
+                )),
             };
 
         let content =
@@ -118,12 +176,20 @@ fn main() {
                 vec!["aptos-types"],
             ),
             Language::Go => ("aptostypes".to_string(), vec!["aptostypes"]),
+            Language::TypeScript | Language::Python => {
+                ("aptos_types".to_string(), vec!["aptos_types"])
+            },
         };
 
         let config = serdegen::CodeGeneratorConfig::new(package_name)
             .with_encodings(vec![serdegen::Encoding::Bcs]);
 
         installer.install_module(&config, &registry).unwrap();
+        // Unlike Rust crates and Go modules, the runtimes are not published as packages.
+        if let Language::TypeScript | Language::Python = options.language {
+            installer.install_serde_runtime().unwrap();
+            installer.install_bcs_runtime().unwrap();
+        }
     }
 
     // Transaction builders
@@ -138,6 +204,17 @@ fn main() {
                 options.serde_package_name,
                 options.package_name,
             )),
+            Language::TypeScript => Box::new(aptos_sdk_builder::typescript::Installer::new(
+                install_dir,
+                options.serde_package_name,
+                options.package_name,
+                view_functions,
+            )),
+            Language::Python => Box::new(aptos_sdk_builder::python::Installer::new(
+                install_dir,
+                options.package_name,
+                view_functions,
+            )),
         };
 
     if let Some(ref name) = options.module_name {
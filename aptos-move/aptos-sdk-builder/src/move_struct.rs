@@ -0,0 +1,252 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::view_function::module_attributes;
+use heck::ToUpperCamelCase;
+use move_binary_format::{
+    access::ModuleAccess,
+    file_format::{
+        FieldDefinition, SignatureToken, StructDefinitionIndex, StructFieldInformation,
+        StructHandleIndex,
+    },
+    CompiledModule,
+};
+use move_bytecode_source_map::source_map::SourceMap;
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::{ModuleId, TypeTag},
+};
+
+/// Kind of the attribute marking event structs, see `aptos_framework::KnownAttributeKind`.
+const EVENT_ATTRIBUTE_KIND: u8 = 4;
+
+/// The type of a field, which may depend on the type parameters of the enclosing struct.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FieldType {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    U256,
+    Address,
+    Signer,
+    Vector(Box<FieldType>),
+    Struct {
+        address: AccountAddress,
+        module: Identifier,
+        name: Identifier,
+        type_args: Vec<FieldType>,
+    },
+    /// The type parameter of the enclosing struct at the given index.
+    TypeParameter(u16),
+}
+
+impl From<&TypeTag> for FieldType {
+    fn from(type_tag: &TypeTag) -> Self {
+        match type_tag {
+            TypeTag::Bool => FieldType::Bool,
+            TypeTag::U8 => FieldType::U8,
+            TypeTag::U16 => FieldType::U16,
+            TypeTag::U32 => FieldType::U32,
+            TypeTag::U64 => FieldType::U64,
+            TypeTag::U128 => FieldType::U128,
+            TypeTag::U256 => FieldType::U256,
+            TypeTag::Address => FieldType::Address,
+            TypeTag::Signer => FieldType::Signer,
+            TypeTag::Vector(type_tag) => FieldType::Vector(Box::new(type_tag.as_ref().into())),
+            TypeTag::Struct(tag) => FieldType::Struct {
+                address: tag.address,
+                module: tag.module.clone(),
+                name: tag.name.clone(),
+                type_args: tag.type_args.iter().map(FieldType::from).collect(),
+            },
+        }
+    }
+}
+
+/// A named field of a struct or of a variant of an enum.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FieldABI {
+    pub name: String,
+    pub type_: FieldType,
+}
+
+/// A variant of an enum.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VariantABI {
+    pub name: String,
+    pub fields: Vec<FieldABI>,
+}
+
+/// The layout of a struct, in declaration order, which is the order of its BCS encoding.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StructLayoutABI {
+    Fields(Vec<FieldABI>),
+    Variants(Vec<VariantABI>),
+}
+
+/// A type parameter of a struct.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StructTypeParameterABI {
+    pub name: String,
+    pub is_phantom: bool,
+}
+
+/// The description of a struct (or enum) declared in a module.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MoveStructABI {
+    /// The name of the struct.
+    pub name: String,
+    /// The module the struct is declared in.
+    pub module_name: ModuleId,
+    pub ty_params: Vec<StructTypeParameterABI>,
+    pub layout: StructLayoutABI,
+    /// Whether the struct has the `key` ability, i.e. can be stored as a resource.
+    pub is_resource: bool,
+    /// Whether the struct is declared as an event with `#[event]`.
+    pub is_event: bool,
+}
+
+impl MoveStructABI {
+    /// Whether the struct is the one referred to by the given address, module and name.
+    pub fn is(&self, address: &AccountAddress, module: &Identifier, name: &Identifier) -> bool {
+        self.module_name.address() == address
+            && self.module_name.name() == module.as_ident_str()
+            && self.name == name.as_str()
+    }
+
+    /// The types of all fields, of all variants for an enum.
+    pub fn field_types(&self) -> Vec<&FieldType> {
+        match &self.layout {
+            StructLayoutABI::Fields(fields) => fields.iter().map(|field| &field.type_).collect(),
+            StructLayoutABI::Variants(variants) => variants
+                .iter()
+                .flat_map(|variant| variant.fields.iter().map(|field| &field.type_))
+                .collect(),
+        }
+    }
+}
+
+/// Extracts the structs declared in a compiled module, skipping native structs.
+///
+/// Type parameter names are taken from the source map, if given, and default to `T<i>`
+/// otherwise.
+pub fn struct_abis(module: &CompiledModule, source_map: Option<&SourceMap>) -> Vec<MoveStructABI> {
+    let events = module_attributes(module)
+        .map(|(struct_attributes, _)| {
+            struct_attributes
+                .into_iter()
+                .filter(|(_, attributes)| {
+                    attributes
+                        .iter()
+                        .any(|(kind, _)| *kind == EVENT_ATTRIBUTE_KIND)
+                })
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    let mut abis = vec![];
+    for (index, def) in module.struct_defs().iter().enumerate() {
+        let handle = module.struct_handle_at(def.struct_handle);
+        let name = module.identifier_at(handle.name).to_string();
+        let layout = match &def.field_information {
+            StructFieldInformation::Native => continue,
+            StructFieldInformation::Declared(fields) => {
+                StructLayoutABI::Fields(field_abis(module, fields))
+            },
+            StructFieldInformation::DeclaredVariants(variants) => StructLayoutABI::Variants(
+                variants
+                    .iter()
+                    .map(|variant| VariantABI {
+                        name: module.identifier_at(variant.name).to_string(),
+                        fields: field_abis(module, &variant.fields),
+                    })
+                    .collect(),
+            ),
+        };
+        let struct_source_map = source_map.and_then(|source_map| {
+            source_map
+                .get_struct_source_map(StructDefinitionIndex(index as u16))
+                .ok()
+        });
+        let ty_params = handle
+            .type_parameters
+            .iter()
+            .enumerate()
+            .map(|(i, param)| StructTypeParameterABI {
+                name: struct_source_map
+                    .and_then(|source_map| source_map.type_parameters.get(i))
+                    .map(|(name, _)| name.to_upper_camel_case())
+                    .unwrap_or_else(|| format!("T{}", i)),
+                is_phantom: param.is_phantom,
+            })
+            .collect();
+
+        abis.push(MoveStructABI {
+            is_resource: handle.abilities.has_key(),
+            is_event: events.contains(&name),
+            name,
+            module_name: module.self_id(),
+            ty_params,
+            layout,
+        });
+    }
+    abis
+}
+
+fn field_abis(module: &CompiledModule, fields: &[FieldDefinition]) -> Vec<FieldABI> {
+    fields
+        .iter()
+        .map(|field| FieldABI {
+            name: module.identifier_at(field.name).to_string(),
+            type_: field_type(module, &field.signature.0),
+        })
+        .collect()
+}
+
+fn field_type(module: &CompiledModule, token: &SignatureToken) -> FieldType {
+    use SignatureToken::*;
+    match token {
+        Bool => FieldType::Bool,
+        U8 => FieldType::U8,
+        U16 => FieldType::U16,
+        U32 => FieldType::U32,
+        U64 => FieldType::U64,
+        U128 => FieldType::U128,
+        U256 => FieldType::U256,
+        Address => FieldType::Address,
+        Signer => FieldType::Signer,
+        Vector(token) => FieldType::Vector(Box::new(field_type(module, token))),
+        Struct(idx) => struct_type(module, *idx, vec![]),
+        StructInstantiation(idx, tokens) => struct_type(
+            module,
+            *idx,
+            tokens
+                .iter()
+                .map(|token| field_type(module, token))
+                .collect(),
+        ),
+        TypeParameter(idx) => FieldType::TypeParameter(*idx),
+        // Fields cannot be references.
+        Reference(_) | MutableReference(_) => unreachable!("reference in struct field"),
+    }
+}
+
+fn struct_type(
+    module: &CompiledModule,
+    idx: StructHandleIndex,
+    type_args: Vec<FieldType>,
+) -> FieldType {
+    let handle = module.struct_handle_at(idx);
+    let module_handle = module.module_handle_at(handle.module);
+    FieldType::Struct {
+        address: *module.address_identifier_at(module_handle.address),
+        module: module.identifier_at(module_handle.name).to_owned(),
+        name: module.identifier_at(handle.name).to_owned(),
+        type_args,
+    }
+}
@@ -0,0 +1,322 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    common::{self, ArgumentStruct, FunctionCall},
+    view_function::ViewFunctionABI,
+};
+use aptos_types::transaction::EntryABI;
+use move_core_types::language_storage::TypeTag;
+use std::{
+    io::{Result, Write},
+    path::PathBuf,
+};
+
+/// Output builders in Python for the entry functions of the given ABIs, and for the given
+/// view functions. Transaction scripts, as well as functions with arguments of unsupported
+/// types, are skipped.
+pub fn output(
+    out: &mut dyn Write,
+    aptos_package_name: Option<String>,
+    abis: &[EntryABI],
+    view_functions: &[ViewFunctionABI],
+) -> Result<()> {
+    let mut emitter = PythonEmitter {
+        out,
+        aptos_package_name,
+    };
+
+    emitter.output_preamble()?;
+    for function in FunctionCall::entry_functions(abis) {
+        emitter.output_entry_function_builder(&function)?;
+    }
+    for function in FunctionCall::view_functions(view_functions) {
+        emitter.output_view_function_builder(&function)?;
+    }
+    Ok(())
+}
+
+/// Shared state for the Python code generator.
+struct PythonEmitter<T> {
+    /// Writer.
+    out: T,
+    /// Name of the package containing the `aptos_types` module.
+    /// `None` to import `aptos_types` as a top-level module.
+    aptos_package_name: Option<String>,
+}
+
+impl<T> PythonEmitter<T>
+where
+    T: Write,
+{
+    fn output_preamble(&mut self) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"# This file was generated by `aptos-sdk-builder`. Do not modify!
+#
+# Builders for the payloads of known Move entry functions, and for requests to known Move
+# view functions. The BCS encoding of a view function request is the body of a BCS request
+# to the `/view` endpoint of the REST API.
+
+import typing
+
+from {}aptos_types import (
+    AccountAddress,
+    EntryFunction,
+    Identifier,
+    ModuleId,
+    TransactionPayload,
+    TransactionPayload__EntryFunction,
+    TypeTag,
+)
+
+
+def _module_id(address: str, name: str) -> ModuleId:
+    return ModuleId(
+        address=AccountAddress.bcs_deserialize(bytes.fromhex(address)),
+        name=Identifier(value=name),
+    )
+
+
+def _encode_uleb128(value: int) -> bytes:
+    output = bytearray()
+    while value >= 0x80:
+        output.append((value & 0x7F) | 0x80)
+        value >>= 7
+    output.append(value)
+    return bytes(output)
+
+
+def _encode_bytes(value: bytes) -> bytes:
+    return _encode_uleb128(len(value)) + value"#,
+            self.aptos_package_name
+                .as_ref()
+                .map(|name| format!("{}.", name))
+                .unwrap_or_default(),
+        )
+    }
+
+    fn output_entry_function_builder(&mut self, function: &FunctionCall) -> Result<()> {
+        write!(
+            self.out,
+            "\n\ndef encode_{}_{}({}) -> TransactionPayload:\n{}",
+            function.module_name.name(),
+            function.name,
+            Self::quote_parameters(function),
+            Self::quote_doc(&function.doc),
+        )?;
+        writeln!(
+            self.out,
+            "    return TransactionPayload__EntryFunction(\n        value={},\n    )",
+            Self::quote_entry_function(function, 8),
+        )
+    }
+
+    fn output_view_function_builder(&mut self, function: &FunctionCall) -> Result<()> {
+        write!(
+            self.out,
+            "\n\ndef view_{}_{}({}) -> EntryFunction:\n{}",
+            function.module_name.name(),
+            function.name,
+            Self::quote_parameters(function),
+            Self::quote_doc(&function.doc),
+        )?;
+        writeln!(
+            self.out,
+            "    return {}",
+            Self::quote_entry_function(function, 4),
+        )
+    }
+
+    /// Quotes the construction of an `EntryFunction`, which also is the layout of a view
+    /// function request. All but the first line are indented by `indent` spaces.
+    fn quote_entry_function(function: &FunctionCall, indent: usize) -> String {
+        let indent = " ".repeat(indent);
+        let args = if function.args.is_empty() {
+            "[]".to_string()
+        } else {
+            format!(
+                "[\n{}{}    ]",
+                function
+                    .args
+                    .iter()
+                    .map(|arg| {
+                        format!(
+                            "{}        {},\n",
+                            indent,
+                            Self::quote_encoding(arg.type_tag(), &Self::quote_name(arg.name()), 0)
+                        )
+                    })
+                    .collect::<String>(),
+                indent,
+            )
+        };
+        format!(
+            "EntryFunction(\n{0}    module=_module_id(\"{1}\", \"{2}\"),\n{0}    function=Identifier(value=\"{3}\"),\n{0}    ty_args=[{4}],\n{0}    args={5},\n{0})",
+            indent,
+            function.module_name.address().to_hex(),
+            function.module_name.name(),
+            function.name,
+            function
+                .ty_args
+                .iter()
+                .map(|ty_arg| Self::quote_name(ty_arg.name()))
+                .collect::<Vec<_>>()
+                .join(", "),
+            args,
+        )
+    }
+
+    fn quote_doc(doc: &str) -> String {
+        if doc.is_empty() {
+            return String::new();
+        }
+        let lines = doc
+            .replace('\\', "\\\\")
+            .replace("\"\"\"", "\\\"\\\"\\\"")
+            .lines()
+            .map(|line| {
+                if line.is_empty() {
+                    "\n".to_string()
+                } else {
+                    format!("    {}\n", line)
+                }
+            })
+            .collect::<String>();
+        format!("    \"\"\"\n{}    \"\"\"\n", lines)
+    }
+
+    fn quote_parameters(function: &FunctionCall) -> String {
+        function
+            .ty_args
+            .iter()
+            .map(|ty_arg| format!("{}: TypeTag", Self::quote_name(ty_arg.name())))
+            .chain(function.args.iter().map(|arg| {
+                format!(
+                    "{}: {}",
+                    Self::quote_name(arg.name()),
+                    Self::quote_type(arg.type_tag())
+                )
+            }))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Appends an underscore to names which are keywords in Python.
+    fn quote_name(name: &str) -> String {
+        const KEYWORDS: &[&str] = &[
+            "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class",
+            "continue", "def", "del", "elif", "else", "except", "finally", "for", "from", "global",
+            "if", "import", "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise",
+            "return", "try", "while", "with", "yield",
+        ];
+        if KEYWORDS.contains(&name) {
+            format!("{}_", name)
+        } else {
+            name.to_string()
+        }
+    }
+
+    fn quote_type(type_tag: &TypeTag) -> String {
+        use TypeTag::*;
+        match type_tag {
+            Bool => "bool".into(),
+            U8 | U16 | U32 | U64 | U128 | U256 => "int".into(),
+            Address => "AccountAddress".into(),
+            Vector(type_tag) => match type_tag.as_ref() {
+                U8 => "bytes".into(),
+                type_tag => format!("typing.Sequence[{}]", Self::quote_type(type_tag)),
+            },
+            Struct(tag) => match common::argument_struct(tag) {
+                Some(ArgumentStruct::String) => "str".into(),
+                Some(ArgumentStruct::Object) => "AccountAddress".into(),
+                Some(ArgumentStruct::Option(type_tag)) => {
+                    format!("typing.Optional[{}]", Self::quote_type(type_tag))
+                },
+                Some(ArgumentStruct::FixedPoint32 | ArgumentStruct::FixedPoint64) => "int".into(),
+                None => common::type_not_allowed(type_tag),
+            },
+            Signer => common::type_not_allowed(type_tag),
+        }
+    }
+
+    /// Quotes an expression evaluating to the BCS encoding of `value`. Generator variables
+    /// are suffixed by `depth`, to support nested vectors.
+    fn quote_encoding(type_tag: &TypeTag, value: &str, depth: usize) -> String {
+        use TypeTag::*;
+        let int_encoding = |size: usize| format!("{}.to_bytes({}, \"little\")", value, size);
+        match type_tag {
+            Bool => format!("(b\"\\x01\" if {} else b\"\\x00\")", value),
+            U8 => int_encoding(1),
+            U16 => int_encoding(2),
+            U32 => int_encoding(4),
+            U64 => int_encoding(8),
+            U128 => int_encoding(16),
+            U256 => int_encoding(32),
+            Address => format!("{}.bcs_serialize()", value),
+            Vector(type_tag) => match type_tag.as_ref() {
+                U8 => format!("_encode_bytes(bytes({}))", value),
+                type_tag => format!(
+                    "_encode_uleb128(len({0})) + b\"\".join({2} for item{1} in {0})",
+                    value,
+                    depth,
+                    Self::quote_encoding(type_tag, &format!("item{}", depth), depth + 1)
+                ),
+            },
+            Struct(tag) => match common::argument_struct(tag) {
+                Some(ArgumentStruct::String) => format!("_encode_bytes({}.encode())", value),
+                Some(ArgumentStruct::Object) => format!("{}.bcs_serialize()", value),
+                Some(ArgumentStruct::Option(type_tag)) => format!(
+                    "(b\"\\x00\" if {0} is None else b\"\\x01\" + {1})",
+                    value,
+                    Self::quote_encoding(type_tag, value, depth)
+                ),
+                Some(ArgumentStruct::FixedPoint32) => int_encoding(8),
+                Some(ArgumentStruct::FixedPoint64) => int_encoding(16),
+                None => common::type_not_allowed(type_tag),
+            },
+            Signer => common::type_not_allowed(type_tag),
+        }
+    }
+}
+
+pub struct Installer {
+    install_dir: PathBuf,
+    aptos_package_name: Option<String>,
+    view_functions: Vec<ViewFunctionABI>,
+}
+
+impl Installer {
+    pub fn new(
+        install_dir: PathBuf,
+        aptos_package_name: Option<String>,
+        view_functions: Vec<ViewFunctionABI>,
+    ) -> Self {
+        Installer {
+            install_dir,
+            aptos_package_name,
+            view_functions,
+        }
+    }
+}
+
+impl crate::SourceInstaller for Installer {
+    type Error = Box<dyn std::error::Error>;
+
+    fn install_transaction_builders(
+        &self,
+        name: &str,
+        abis: &[EntryABI],
+    ) -> std::result::Result<(), Self::Error> {
+        let dir_path = self.install_dir.join(name);
+        std::fs::create_dir_all(&dir_path)?;
+        let mut file = std::fs::File::create(dir_path.join("__init__.py"))?;
+        output(
+            &mut file,
+            self.aptos_package_name.clone(),
+            abis,
+            &self.view_functions,
+        )?;
+        Ok(())
+    }
+}
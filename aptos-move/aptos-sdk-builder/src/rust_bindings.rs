@@ -0,0 +1,537 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    move_struct::{FieldABI, FieldType, MoveStructABI, StructLayoutABI},
+    view_function::ViewFunctionABI,
+};
+use move_core_types::{
+    account_address::AccountAddress,
+    language_storage::{ModuleId, TypeTag},
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{Result, Write},
+};
+
+const MOVE_TYPES: &str = "::aptos_sdk::move_types";
+
+/// Output Rust bindings for the given structs and view functions, as one Rust module per Move
+/// module. Modules refer to each other by `super::`, so the output is meant to be included
+/// with `include!` in a module of its own.
+///
+/// Structs are skipped if they refer to structs which are not given (e.g. the framework
+/// structs when only the modules of a package are given), and view functions are skipped if
+/// their arguments or returned values are of such a type.
+pub fn output(
+    out: &mut dyn Write,
+    structs: &[MoveStructABI],
+    view_functions: &[ViewFunctionABI],
+) -> Result<()> {
+    let structs = available_structs(structs);
+    let view_functions = view_functions
+        .iter()
+        .filter(|abi| {
+            abi.args()
+                .iter()
+                .map(|arg| arg.type_tag())
+                .chain(abi.returns())
+                .all(|type_tag| {
+                    let type_ = FieldType::from(type_tag);
+                    type_ != FieldType::Signer && is_available(&structs, &type_)
+                })
+        })
+        .collect::<Vec<_>>();
+
+    let mut modules = BTreeMap::<String, (&ModuleId, Vec<_>, Vec<_>)>::new();
+    for abi in &structs {
+        modules
+            .entry(abi.module_name.name().to_string())
+            .or_insert_with(|| (&abi.module_name, vec![], vec![]))
+            .1
+            .push(*abi);
+    }
+    for abi in &view_functions {
+        modules
+            .entry(abi.module_name().name().to_string())
+            .or_insert_with(|| (abi.module_name(), vec![], vec![]))
+            .2
+            .push(*abi);
+    }
+
+    let mut emitter = RustBindingsEmitter { out };
+    emitter.output_preamble()?;
+    for (module_name, structs, view_functions) in modules.values() {
+        emitter.output_module(module_name, structs, view_functions)?;
+    }
+    Ok(())
+}
+
+/// Returns the structs whose fields only refer to structs which are available as well.
+fn available_structs(structs: &[MoveStructABI]) -> Vec<&MoveStructABI> {
+    let mut available = structs.iter().collect::<Vec<_>>();
+    loop {
+        let previous = available.clone();
+        available.retain(|abi| {
+            abi.field_types()
+                .into_iter()
+                .all(|type_| is_available(&previous, type_))
+        });
+        if available.len() == previous.len() {
+            return available;
+        }
+    }
+}
+
+fn is_available(structs: &[&MoveStructABI], type_: &FieldType) -> bool {
+    match type_ {
+        FieldType::Vector(type_) => is_available(structs, type_),
+        FieldType::Struct {
+            address,
+            module,
+            name,
+            type_args,
+        } => {
+            (is_std_struct(address, module.as_str(), name.as_str())
+                || structs.iter().any(|abi| abi.is(address, module, name)))
+                && type_args.iter().all(|type_| is_available(structs, type_))
+        },
+        _ => true,
+    }
+}
+
+/// Whether the struct is represented by a type of the Rust standard library with the same BCS
+/// encoding.
+fn is_std_struct(address: &AccountAddress, module: &str, name: &str) -> bool {
+    *address == AccountAddress::ONE
+        && matches!((module, name), ("string", "String") | ("option", "Option"))
+}
+
+/// Shared state for the Rust bindings generator.
+struct RustBindingsEmitter<T> {
+    /// Writer.
+    out: T,
+}
+
+impl<T> RustBindingsEmitter<T>
+where
+    T: Write,
+{
+    fn output_preamble(&mut self) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"// This file was generated by `aptos-sdk-builder`. Do not modify!
+//
+// Rust types for the structs of Move modules, which match the BCS encoding of resources and
+// events, and typed wrappers calling view functions through the REST API.
+
+/// The result of a call to a view function.
+pub type ViewFunctionResult<T> = ::std::result::Result<
+    ::aptos_sdk::rest_client::Response<T>,
+    ::aptos_sdk::rest_client::error::RestError,
+>;
+
+/// Calls a view function through the BCS API. The response is the number of returned values,
+/// as a single-byte ULEB128, followed by the values.
+#[allow(dead_code)]
+async fn call_view_function<T: ::serde::de::DeserializeOwned>(
+    client: &::aptos_sdk::rest_client::Client,
+    address: &str,
+    module: &str,
+    function: &str,
+    ty_args: ::std::vec::Vec<{0}::language_storage::TypeTag>,
+    args: ::std::vec::Vec<::std::vec::Vec<u8>>,
+    version: ::std::option::Option<u64>,
+) -> ViewFunctionResult<T> {{
+    let request = ::aptos_sdk::rest_client::aptos_api_types::ViewFunction {{
+        module: {0}::language_storage::ModuleId::new(
+            {0}::account_address::AccountAddress::from_hex_literal(address).unwrap(),
+            {0}::identifier::Identifier::new(module).unwrap(),
+        ),
+        function: {0}::identifier::Identifier::new(function).unwrap(),
+        ty_args,
+        args,
+    }};
+    let response = client.view_bcs::<(u8, T)>(&request, version).await?;
+    Ok(response.map(|(_, values)| values))
+}}
+
+#[allow(dead_code)]
+fn encode_argument<T: ::serde::Serialize>(value: &T) -> ::std::vec::Vec<u8> {{
+    ::aptos_sdk::bcs::to_bytes(value).expect("arguments can be serialized")
+}}
+
+#[allow(dead_code)]
+fn type_tag<T: {0}::move_resource::MoveStructType>() -> {0}::language_storage::TypeTag {{
+    {0}::language_storage::TypeTag::Struct(::std::boxed::Box::new(T::struct_tag()))
+}}
+
+#[allow(dead_code)]
+fn decode_event<T: {0}::move_resource::MoveStructType + ::serde::de::DeserializeOwned>(
+    event: &::aptos_sdk::types::contract_event::ContractEvent,
+) -> ::std::result::Result<::std::option::Option<T>, ::aptos_sdk::bcs::Error> {{
+    if event.type_tag() != &type_tag::<T>() {{
+        return Ok(None);
+    }}
+    ::aptos_sdk::bcs::from_bytes(event.event_data()).map(Some)
+}}"#,
+            MOVE_TYPES
+        )
+    }
+
+    fn output_module(
+        &mut self,
+        module_name: &ModuleId,
+        structs: &[&MoveStructABI],
+        view_functions: &[&ViewFunctionABI],
+    ) -> Result<()> {
+        writeln!(
+            self.out,
+            "\npub mod {} {{\n    //! Bindings for the Move module `{}`.\n    #![allow(clippy::all, non_camel_case_types, non_snake_case)]",
+            quote_ident(module_name.name().as_str()),
+            module_name.short_str_lossless(),
+        )?;
+        for abi in structs {
+            self.output_struct(abi)?;
+            self.output_struct_impls(abi)?;
+        }
+        for abi in view_functions {
+            self.output_view_function(abi)?;
+        }
+        writeln!(self.out, "}}")
+    }
+
+    fn output_struct(&mut self, abi: &MoveStructABI) -> Result<()> {
+        let ty_params = ty_param_names(abi);
+        let phantom_params = phantom_params(abi);
+        writeln!(
+            self.out,
+            "\n    /// The Move struct `{}::{}`.\n    #[derive(Clone, Debug, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]",
+            abi.module_name.short_str_lossless(),
+            abi.name,
+        )?;
+        match &abi.layout {
+            StructLayoutABI::Fields(fields) => {
+                writeln!(
+                    self.out,
+                    "    pub struct {}{} {{",
+                    abi.name,
+                    quote_generics(&ty_params)
+                )?;
+                self.output_fields(abi, fields, "        ", "pub ")?;
+                if let Some(phantom_params) = &phantom_params {
+                    writeln!(
+                        self.out,
+                        "        #[serde(skip)]\n        pub phantom: ::std::marker::PhantomData<{}>,",
+                        phantom_params
+                    )?;
+                }
+            },
+            StructLayoutABI::Variants(variants) => {
+                writeln!(
+                    self.out,
+                    "    pub enum {}{} {{",
+                    abi.name,
+                    quote_generics(&ty_params)
+                )?;
+                for variant in variants {
+                    if variant.fields.is_empty() {
+                        writeln!(self.out, "        {},", variant.name)?;
+                    } else {
+                        writeln!(self.out, "        {} {{", variant.name)?;
+                        self.output_fields(abi, &variant.fields, "            ", "")?;
+                        writeln!(self.out, "        }},")?;
+                    }
+                }
+                // Skipped variants must come last, not to change the index of the others.
+                if let Some(phantom_params) = &phantom_params {
+                    writeln!(
+                        self.out,
+                        "        #[doc(hidden)]\n        #[serde(skip)]\n        Phantom(::std::marker::PhantomData<{}>),",
+                        phantom_params
+                    )?;
+                }
+            },
+        }
+        writeln!(self.out, "    }}")
+    }
+
+    fn output_fields(
+        &mut self,
+        abi: &MoveStructABI,
+        fields: &[FieldABI],
+        indent: &str,
+        visibility: &str,
+    ) -> Result<()> {
+        let ty_params = ty_param_names(abi);
+        for field in fields {
+            writeln!(
+                self.out,
+                "{}{}{}: {},",
+                indent,
+                visibility,
+                quote_ident(&field.name),
+                quote_type(&abi.module_name, &ty_params, &field.type_)
+            )?;
+        }
+        Ok(())
+    }
+
+    fn output_struct_impls(&mut self, abi: &MoveStructABI) -> Result<()> {
+        let ty_params = ty_param_names(abi);
+        let bounded_generics = if ty_params.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "<{}>",
+                ty_params
+                    .iter()
+                    .map(|name| format!("{}: {}::move_resource::MoveStructType", name, MOVE_TYPES))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        };
+        let self_type = format!("{}{}", abi.name, quote_generics(&ty_params));
+        let address = abi
+            .module_name
+            .address()
+            .into_bytes()
+            .iter()
+            .map(|byte| byte.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        writeln!(
+            self.out,
+            "\n    impl{0} {1}::move_resource::MoveStructType for {2} {{\n        const ADDRESS: {1}::account_address::AccountAddress =\n            {1}::account_address::AccountAddress::new([{3}]);\n        const MODULE_NAME: &'static {1}::identifier::IdentStr = {1}::ident_str!(\"{4}\");\n        const STRUCT_NAME: &'static {1}::identifier::IdentStr = {1}::ident_str!(\"{5}\");",
+            bounded_generics,
+            MOVE_TYPES,
+            self_type,
+            address,
+            abi.module_name.name(),
+            abi.name,
+        )?;
+        if !ty_params.is_empty() {
+            writeln!(
+                self.out,
+                "\n        fn type_args() -> ::std::vec::Vec<{}::language_storage::TypeTag> {{\n            vec![{}]\n        }}",
+                MOVE_TYPES,
+                ty_params
+                    .iter()
+                    .map(|name| format!("super::type_tag::<{}>()", name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )?;
+        }
+        writeln!(self.out, "    }}")?;
+
+        if abi.is_resource {
+            writeln!(
+                self.out,
+                "\n    impl{} {}::move_resource::MoveResource for {} where Self: ::serde::de::DeserializeOwned {{}}",
+                bounded_generics, MOVE_TYPES, self_type,
+            )?;
+        }
+        if abi.is_event {
+            writeln!(
+                self.out,
+                r#"
+    impl{} {} where Self: ::serde::de::DeserializeOwned {{
+        /// Decodes the event, if it is a `{}::{}`.
+        pub fn try_from_event(
+            event: &::aptos_sdk::types::contract_event::ContractEvent,
+        ) -> ::std::result::Result<::std::option::Option<Self>, ::aptos_sdk::bcs::Error> {{
+            super::decode_event(event)
+        }}
+    }}"#,
+                bounded_generics,
+                self_type,
+                abi.module_name.short_str_lossless(),
+                abi.name,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn output_view_function(&mut self, abi: &ViewFunctionABI) -> Result<()> {
+        let module_name = abi.module_name();
+        let quote_type_tag =
+            |type_tag: &TypeTag| quote_type(module_name, &[], &FieldType::from(type_tag));
+        let returns = abi.returns().iter().map(quote_type_tag).collect::<Vec<_>>();
+        let returns = if returns.len() == 1 {
+            returns[0].clone()
+        } else {
+            format!("({})", returns.join(", "))
+        };
+
+        writeln!(
+            self.out,
+            "\n    /// Calls the view function `{}::{}`.\n    pub async fn {}(\n        client: &::aptos_sdk::rest_client::Client,",
+            module_name.short_str_lossless(),
+            abi.name(),
+            quote_ident(abi.name()),
+        )?;
+        for ty_arg in abi.ty_args() {
+            writeln!(
+                self.out,
+                "        {}: {}::language_storage::TypeTag,",
+                quote_parameter_name(ty_arg.name()),
+                MOVE_TYPES
+            )?;
+        }
+        for arg in abi.args() {
+            writeln!(
+                self.out,
+                "        {}: {},",
+                quote_parameter_name(arg.name()),
+                quote_type_tag(arg.type_tag())
+            )?;
+        }
+        writeln!(
+            self.out,
+            "        version: ::std::option::Option<u64>,\n    ) -> super::ViewFunctionResult<{}> {{",
+            returns
+        )?;
+        writeln!(
+            self.out,
+            "        super::call_view_function(\n            client,\n            \"{}\",\n            \"{}\",\n            \"{}\",\n            vec![{}],\n            vec![{}],\n            version,\n        )\n        .await\n    }}",
+            module_name.address().to_hex_literal(),
+            module_name.name(),
+            abi.name(),
+            abi.ty_args()
+                .iter()
+                .map(|ty_arg| quote_parameter_name(ty_arg.name()))
+                .collect::<Vec<_>>()
+                .join(", "),
+            abi.args()
+                .iter()
+                .map(|arg| format!("super::encode_argument(&{})", quote_parameter_name(arg.name())))
+                .collect::<Vec<_>>()
+                .join(", "),
+        )
+    }
+}
+
+fn ty_param_names(abi: &MoveStructABI) -> Vec<String> {
+    abi.ty_params
+        .iter()
+        .map(|param| quote_ident(&param.name))
+        .collect()
+}
+
+/// Returns the type parameters which are not used by any field, as the type argument of
+/// `PhantomData`, if any: Rust does not allow unused type parameters.
+fn phantom_params(abi: &MoveStructABI) -> Option<String> {
+    let mut used = BTreeSet::new();
+    for type_ in abi.field_types() {
+        collect_type_parameters(type_, &mut used);
+    }
+    let unused = abi
+        .ty_params
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !used.contains(&(*index as u16)))
+        .map(|(_, param)| quote_ident(&param.name))
+        .collect::<Vec<_>>();
+    match unused.len() {
+        0 => None,
+        1 => Some(unused[0].clone()),
+        _ => Some(format!("({})", unused.join(", "))),
+    }
+}
+
+fn collect_type_parameters(type_: &FieldType, used: &mut BTreeSet<u16>) {
+    match type_ {
+        FieldType::Vector(type_) => collect_type_parameters(type_, used),
+        FieldType::Struct { type_args, .. } => {
+            for type_ in type_args {
+                collect_type_parameters(type_, used);
+            }
+        },
+        FieldType::TypeParameter(index) => {
+            used.insert(*index);
+        },
+        _ => (),
+    }
+}
+
+fn quote_generics(ty_params: &[String]) -> String {
+    if ty_params.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", ty_params.join(", "))
+    }
+}
+
+/// Quotes the Rust type of a field declared in `module_name`, or of an argument or returned
+/// value of a view function declared in `module_name`.
+fn quote_type(module_name: &ModuleId, ty_params: &[String], type_: &FieldType) -> String {
+    use FieldType::*;
+    match type_ {
+        Bool => "bool".into(),
+        U8 => "u8".into(),
+        U16 => "u16".into(),
+        U32 => "u32".into(),
+        U64 => "u64".into(),
+        U128 => "u128".into(),
+        U256 => format!("{}::u256::U256", MOVE_TYPES),
+        Address | Signer => format!("{}::account_address::AccountAddress", MOVE_TYPES),
+        Vector(type_) => format!(
+            "::std::vec::Vec<{}>",
+            quote_type(module_name, ty_params, type_)
+        ),
+        Struct {
+            address,
+            module,
+            name,
+            type_args,
+        } => {
+            let type_args = type_args
+                .iter()
+                .map(|type_| quote_type(module_name, ty_params, type_))
+                .collect::<Vec<_>>();
+            if is_std_struct(address, module.as_str(), name.as_str()) {
+                return match name.as_str() {
+                    "String" => "::std::string::String".into(),
+                    _ => format!("::std::option::Option<{}>", type_args.join(", ")),
+                };
+            }
+            let path = if module_name.name() == module.as_ident_str() {
+                name.to_string()
+            } else {
+                format!("super::{}::{}", quote_ident(module.as_str()), name)
+            };
+            format!("{}{}", path, quote_generics(&type_args))
+        },
+        TypeParameter(index) => ty_params[*index as usize].clone(),
+    }
+}
+
+/// Quotes a name which may be a keyword in Rust.
+fn quote_ident(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do",
+        "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let",
+        "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+        "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+        "virtual", "where", "while", "yield",
+    ];
+    // These keywords cannot be raw identifiers.
+    const PATH_KEYWORDS: &[&str] = &["crate", "self", "Self", "super"];
+    if KEYWORDS.contains(&name) {
+        format!("r#{}", name)
+    } else if PATH_KEYWORDS.contains(&name) {
+        format!("{}_", name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// Quotes the name of a parameter of a view function wrapper, which must not clash with the
+/// parameters added by the wrapper.
+fn quote_parameter_name(name: &str) -> String {
+    match name {
+        "client" | "version" => format!("{}_", name),
+        name => quote_ident(name),
+    }
+}
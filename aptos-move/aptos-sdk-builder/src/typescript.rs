@@ -0,0 +1,378 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    common::{self, ArgumentStruct, FunctionCall},
+    view_function::ViewFunctionABI,
+};
+use aptos_types::transaction::EntryABI;
+use heck::ToUpperCamelCase;
+use move_core_types::language_storage::{ModuleId, TypeTag};
+use std::{
+    io::{Result, Write},
+    path::PathBuf,
+};
+
+/// Output builders in TypeScript for the entry functions of the given ABIs, and for the given
+/// view functions. Transaction scripts, as well as functions with arguments of unsupported
+/// types, are skipped.
+pub fn output(
+    out: &mut dyn Write,
+    serde_module_path: Option<String>,
+    aptos_module_path: Option<String>,
+    abis: &[EntryABI],
+    view_functions: &[ViewFunctionABI],
+) -> Result<()> {
+    let mut emitter = TypeScriptEmitter {
+        out,
+        serde_module_path,
+        aptos_module_path,
+    };
+
+    emitter.output_preamble()?;
+    for function in FunctionCall::entry_functions(abis) {
+        emitter.output_entry_function_builder(&function)?;
+    }
+    for function in FunctionCall::view_functions(view_functions) {
+        emitter.output_view_function_builder(&function)?;
+    }
+    Ok(())
+}
+
+/// Shared state for the TypeScript code generator.
+struct TypeScriptEmitter<T> {
+    /// Writer.
+    out: T,
+    /// Path of the directory containing the Serde and BCS runtime modules.
+    /// `None` to use the parent directory.
+    serde_module_path: Option<String>,
+    /// Path of the directory containing the `aptos_types` module.
+    /// `None` to use the parent directory.
+    aptos_module_path: Option<String>,
+}
+
+impl<T> TypeScriptEmitter<T>
+where
+    T: Write,
+{
+    fn output_preamble(&mut self) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"// This file was generated by `aptos-sdk-builder`. Do not modify!
+//
+// Builders for the payloads of known Move entry functions, and for requests to known Move
+// view functions. The BCS encoding of a view function request is the body of a BCS request
+// to the `/view` endpoint of the REST API.
+
+import {{ BcsDeserializer, BcsSerializer }} from "{}/bcs/mod.ts";
+import {{
+  AccountAddress,
+  EntryFunction,
+  Identifier,
+  ModuleId,
+  TransactionPayload,
+  TransactionPayloadVariantEntryFunction,
+  TypeTag,
+}} from "{}/aptos_types/mod.ts";
+
+function moduleId(address: string, name: string): ModuleId {{
+  const bytes = new Uint8Array(32);
+  for (let i = 0; i < 32; i++) {{
+    bytes[i] = parseInt(address.substring(2 * i, 2 * i + 2), 16);
+  }}
+  return new ModuleId(
+    AccountAddress.deserialize(new BcsDeserializer(bytes)),
+    new Identifier(name),
+  );
+}}
+
+function encodeArgument(write: (serializer: BcsSerializer) => void): Uint8Array {{
+  const serializer = new BcsSerializer();
+  write(serializer);
+  return serializer.getBytes();
+}}"#,
+            self.serde_module_path.as_deref().unwrap_or(".."),
+            self.aptos_module_path.as_deref().unwrap_or(".."),
+        )
+    }
+
+    fn output_entry_function_builder(&mut self, function: &FunctionCall) -> Result<()> {
+        writeln!(
+            self.out,
+            "\n{}export function encode{}({}): TransactionPayload {{",
+            Self::quote_doc(&function.doc),
+            Self::quote_function_name(function.module_name, function.name),
+            Self::quote_parameters(function),
+        )?;
+        writeln!(
+            self.out,
+            "  return new TransactionPayloadVariantEntryFunction(\n    {},\n  );\n}}",
+            Self::quote_entry_function(function, 4),
+        )
+    }
+
+    fn output_view_function_builder(&mut self, function: &FunctionCall) -> Result<()> {
+        writeln!(
+            self.out,
+            "\n{}export function view{}({}): EntryFunction {{",
+            Self::quote_doc(&function.doc),
+            Self::quote_function_name(function.module_name, function.name),
+            Self::quote_parameters(function),
+        )?;
+        writeln!(
+            self.out,
+            "  return {};\n}}",
+            Self::quote_entry_function(function, 2),
+        )
+    }
+
+    /// Quotes the construction of an `EntryFunction`, which also is the layout of a view
+    /// function request. All but the first line are indented by `indent` spaces.
+    fn quote_entry_function(function: &FunctionCall, indent: usize) -> String {
+        let indent = " ".repeat(indent);
+        let args = if function.args.is_empty() {
+            "[]".to_string()
+        } else {
+            format!(
+                "[\n{}{}  ]",
+                function
+                    .args
+                    .iter()
+                    .map(|arg| {
+                        format!(
+                            "{}    encodeArgument((s) => {{ {} }}),\n",
+                            indent,
+                            Self::quote_serialization(
+                                arg.type_tag(),
+                                &Self::quote_name(arg.name()),
+                                0
+                            )
+                        )
+                    })
+                    .collect::<String>(),
+                indent,
+            )
+        };
+        format!(
+            "new EntryFunction(\n{0}  moduleId(\"{1}\", \"{2}\"),\n{0}  new Identifier(\"{3}\"),\n{0}  [{4}],\n{0}  {5},\n{0})",
+            indent,
+            function.module_name.address().to_hex(),
+            function.module_name.name(),
+            function.name,
+            function
+                .ty_args
+                .iter()
+                .map(|ty_arg| Self::quote_name(ty_arg.name()))
+                .collect::<Vec<_>>()
+                .join(", "),
+            args,
+        )
+    }
+
+    fn quote_function_name(module_name: &ModuleId, name: &str) -> String {
+        format!(
+            "{}{}",
+            module_name.name().as_str().to_upper_camel_case(),
+            name.to_upper_camel_case()
+        )
+    }
+
+    fn quote_doc(doc: &str) -> String {
+        if doc.is_empty() {
+            return String::new();
+        }
+        let lines = doc
+            .replace("*/", "*\\/")
+            .lines()
+            .map(|line| {
+                if line.is_empty() {
+                    " *\n".to_string()
+                } else {
+                    format!(" * {}\n", line)
+                }
+            })
+            .collect::<String>();
+        format!("/**\n{} */\n", lines)
+    }
+
+    fn quote_parameters(function: &FunctionCall) -> String {
+        function
+            .ty_args
+            .iter()
+            .map(|ty_arg| format!("{}: TypeTag", Self::quote_name(ty_arg.name())))
+            .chain(function.args.iter().map(|arg| {
+                format!(
+                    "{}: {}",
+                    Self::quote_name(arg.name()),
+                    Self::quote_type(arg.type_tag())
+                )
+            }))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Appends an underscore to names which are reserved words in TypeScript, or which would
+    /// shadow the serializer `s` in argument encoders.
+    fn quote_name(name: &str) -> String {
+        const RESERVED_WORDS: &[&str] = &[
+            "break",
+            "case",
+            "catch",
+            "class",
+            "const",
+            "continue",
+            "debugger",
+            "default",
+            "delete",
+            "do",
+            "else",
+            "enum",
+            "export",
+            "extends",
+            "false",
+            "finally",
+            "for",
+            "function",
+            "if",
+            "import",
+            "in",
+            "instanceof",
+            "new",
+            "null",
+            "return",
+            "super",
+            "switch",
+            "this",
+            "throw",
+            "true",
+            "try",
+            "typeof",
+            "var",
+            "void",
+            "while",
+            "with",
+            "let",
+            "static",
+            "yield",
+            "await",
+            "s",
+        ];
+        if RESERVED_WORDS.contains(&name) {
+            format!("{}_", name)
+        } else {
+            name.to_string()
+        }
+    }
+
+    fn quote_type(type_tag: &TypeTag) -> String {
+        use TypeTag::*;
+        match type_tag {
+            Bool => "boolean".into(),
+            U8 | U16 | U32 => "number".into(),
+            U64 | U128 | U256 => "bigint".into(),
+            Address => "AccountAddress".into(),
+            Vector(type_tag) => match type_tag.as_ref() {
+                U8 => "Uint8Array".into(),
+                type_tag => format!("Array<{}>", Self::quote_type(type_tag)),
+            },
+            Struct(tag) => match common::argument_struct(tag) {
+                Some(ArgumentStruct::String) => "string".into(),
+                Some(ArgumentStruct::Object) => "AccountAddress".into(),
+                Some(ArgumentStruct::Option(type_tag)) => {
+                    format!("{} | null", Self::quote_type(type_tag))
+                },
+                Some(ArgumentStruct::FixedPoint32 | ArgumentStruct::FixedPoint64) => {
+                    "bigint".into()
+                },
+                None => common::type_not_allowed(type_tag),
+            },
+            Signer => common::type_not_allowed(type_tag),
+        }
+    }
+
+    /// Quotes the statements writing `value` to the serializer `s`. Loop variables are
+    /// suffixed by `depth`, to support nested vectors.
+    fn quote_serialization(type_tag: &TypeTag, value: &str, depth: usize) -> String {
+        use TypeTag::*;
+        match type_tag {
+            Bool => format!("s.serializeBool({});", value),
+            U8 => format!("s.serializeU8({});", value),
+            U16 => format!("s.serializeU16({});", value),
+            U32 => format!("s.serializeU32({});", value),
+            U64 => format!("s.serializeU64({});", value),
+            U128 => format!("s.serializeU128({});", value),
+            U256 => format!(
+                "s.serializeU128({0} & ((1n << 128n) - 1n)); s.serializeU128({0} >> 128n);",
+                value
+            ),
+            Address => format!("{}.serialize(s);", value),
+            Vector(type_tag) => match type_tag.as_ref() {
+                U8 => format!("s.serializeBytes({});", value),
+                type_tag => format!(
+                    "s.serializeLen({0}.length); for (const item{1} of {0}) {{ {2} }}",
+                    value,
+                    depth,
+                    Self::quote_serialization(type_tag, &format!("item{}", depth), depth + 1)
+                ),
+            },
+            Struct(tag) => match common::argument_struct(tag) {
+                Some(ArgumentStruct::String) => format!("s.serializeStr({});", value),
+                Some(ArgumentStruct::Object) => format!("{}.serialize(s);", value),
+                Some(ArgumentStruct::Option(type_tag)) => format!(
+                    "if ({0} === null) {{ s.serializeLen(0); }} else {{ s.serializeLen(1); {1} }}",
+                    value,
+                    Self::quote_serialization(type_tag, value, depth)
+                ),
+                Some(ArgumentStruct::FixedPoint32) => format!("s.serializeU64({});", value),
+                Some(ArgumentStruct::FixedPoint64) => format!("s.serializeU128({});", value),
+                None => common::type_not_allowed(type_tag),
+            },
+            Signer => common::type_not_allowed(type_tag),
+        }
+    }
+}
+
+pub struct Installer {
+    install_dir: PathBuf,
+    serde_module_path: Option<String>,
+    aptos_module_path: Option<String>,
+    view_functions: Vec<ViewFunctionABI>,
+}
+
+impl Installer {
+    pub fn new(
+        install_dir: PathBuf,
+        serde_module_path: Option<String>,
+        aptos_module_path: Option<String>,
+        view_functions: Vec<ViewFunctionABI>,
+    ) -> Self {
+        Installer {
+            install_dir,
+            serde_module_path,
+            aptos_module_path,
+            view_functions,
+        }
+    }
+}
+
+impl crate::SourceInstaller for Installer {
+    type Error = Box<dyn std::error::Error>;
+
+    fn install_transaction_builders(
+        &self,
+        name: &str,
+        abis: &[EntryABI],
+    ) -> std::result::Result<(), Self::Error> {
+        let dir_path = self.install_dir.join(name);
+        std::fs::create_dir_all(&dir_path)?;
+        let mut file = std::fs::File::create(dir_path.join("mod.ts"))?;
+        output(
+            &mut file,
+            self.serde_module_path.clone(),
+            self.aptos_module_path.clone(),
+            abis,
+            &self.view_functions,
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,276 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_types::transaction::{ArgumentABI, TypeArgumentABI};
+use heck::ToSnakeCase;
+use move_binary_format::{
+    access::ModuleAccess,
+    file_format::{FunctionDefinitionIndex, SignatureToken, StructHandleIndex},
+    CompiledModule,
+};
+use move_bytecode_source_map::{source_map::SourceMap, utils::source_map_from_file};
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::{ModuleId, StructTag, TypeTag},
+};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// Key of the Aptos metadata in compiled modules, see `aptos_framework::APTOS_METADATA_KEY_V1`.
+const APTOS_METADATA_KEY_V1: &[u8] = b"aptos::metadata_v1";
+
+/// Kinds of the attribute marking view functions, see `aptos_framework::KnownAttributeKind`.
+const VIEW_FUNCTION_ATTRIBUTE_KINDS: [u8; 2] = [0, 1];
+
+/// The BCS layout of `aptos_framework::RuntimeModuleMetadataV1`, i.e. the error map, the
+/// struct attributes and the function attributes. `aptos-framework` depends on this crate,
+/// so it cannot be used here.
+type RuntimeModuleMetadataV1 = (
+    BTreeMap<u64, (String, String)>,
+    BTreeMap<String, Vec<(u8, Vec<String>)>>,
+    BTreeMap<String, Vec<(u8, Vec<String>)>>,
+);
+
+/// How to call a view function of a module.
+///
+/// View functions are not described by `EntryABI`s, so they are extracted from compiled
+/// modules instead, which also provides the types of the returned values.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ViewFunctionABI {
+    /// The name of the function.
+    name: String,
+    /// The module the function lives in.
+    module_name: ModuleId,
+    /// The names of the type arguments.
+    ty_args: Vec<TypeArgumentABI>,
+    /// The description of the regular arguments.
+    args: Vec<ArgumentABI>,
+    /// The types of the returned values.
+    returns: Vec<TypeTag>,
+}
+
+impl ViewFunctionABI {
+    pub fn new(
+        name: String,
+        module_name: ModuleId,
+        ty_args: Vec<TypeArgumentABI>,
+        args: Vec<ArgumentABI>,
+        returns: Vec<TypeTag>,
+    ) -> Self {
+        Self {
+            name,
+            module_name,
+            ty_args,
+            args,
+            returns,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn module_name(&self) -> &ModuleId {
+        &self.module_name
+    }
+
+    pub fn ty_args(&self) -> &[TypeArgumentABI] {
+        &self.ty_args
+    }
+
+    pub fn args(&self) -> &[ArgumentABI] {
+        &self.args
+    }
+
+    pub fn returns(&self) -> &[TypeTag] {
+        &self.returns
+    }
+}
+
+/// Extracts the view functions of a compiled module.
+///
+/// Parameter names are taken from the source map, if given, and default to `arg<i>` and
+/// `t<i>` otherwise. View functions with arguments or return values whose type depends on
+/// a type parameter (other than the type of an `Object<T>`) are skipped.
+pub fn view_function_abis(
+    module: &CompiledModule,
+    source_map: Option<&SourceMap>,
+) -> Vec<ViewFunctionABI> {
+    let view_functions = match view_function_names(module) {
+        Some(names) if !names.is_empty() => names,
+        _ => return vec![],
+    };
+
+    let mut abis = vec![];
+    for (index, def) in module.function_defs().iter().enumerate() {
+        let handle = module.function_handle_at(def.function);
+        let name = module.identifier_at(handle.name).to_string();
+        if !view_functions.contains(&name) {
+            continue;
+        }
+        let function_source_map = source_map.and_then(|source_map| {
+            source_map
+                .get_function_source_map(FunctionDefinitionIndex(index as u16))
+                .ok()
+        });
+
+        let ty_args = (0..handle.type_parameters.len())
+            .map(|i| {
+                let name = function_source_map
+                    .and_then(|source_map| source_map.type_parameters.get(i))
+                    .map(|(name, _)| name.to_snake_case())
+                    .unwrap_or_else(|| format!("t{}", i));
+                TypeArgumentABI::new(name)
+            })
+            .collect();
+        let args = module
+            .signature_at(handle.parameters)
+            .0
+            .iter()
+            .enumerate()
+            .map(|(i, token)| {
+                let name = function_source_map
+                    .and_then(|source_map| source_map.get_parameter_or_local_name(i as u64))
+                    .map(|(name, _)| name)
+                    .unwrap_or_else(|| format!("arg{}", i));
+                Some(ArgumentABI::new(name, type_tag(module, token)?))
+            })
+            .collect::<Option<Vec<_>>>();
+        let returns = module
+            .signature_at(handle.return_)
+            .0
+            .iter()
+            .map(|token| type_tag(module, token))
+            .collect::<Option<Vec<_>>>();
+
+        if let (Some(args), Some(returns)) = (args, returns) {
+            abis.push(ViewFunctionABI::new(
+                name,
+                module.self_id(),
+                ty_args,
+                args,
+                returns,
+            ));
+        }
+    }
+    abis
+}
+
+/// Reads a compiled module along with its source map, which is expected either next to
+/// the module or in the `source_maps` directory of the package build.
+pub(crate) fn read_module(path: &Path) -> anyhow::Result<(CompiledModule, Option<SourceMap>)> {
+    let module = CompiledModule::deserialize(&std::fs::read(path)?)?;
+    let source_map = source_map_paths(path)
+        .into_iter()
+        .find_map(|path| source_map_from_file(&path).ok());
+    Ok((module, source_map))
+}
+
+fn source_map_paths(module_path: &Path) -> Vec<PathBuf> {
+    let mut paths = vec![module_path.with_extension("mvsm")];
+    if let (Some(file_name), Some(build_dir)) = (
+        module_path.file_name(),
+        module_path.parent().and_then(Path::parent),
+    ) {
+        paths.push(
+            build_dir
+                .join("source_maps")
+                .join(file_name)
+                .with_extension("mvsm"),
+        );
+    }
+    paths
+}
+
+/// Returns the struct attributes and the function attributes of a module, as pairs of the
+/// attribute kind and its arguments by name of the struct or function.
+pub(crate) fn module_attributes(
+    module: &CompiledModule,
+) -> Option<(
+    BTreeMap<String, Vec<(u8, Vec<String>)>>,
+    BTreeMap<String, Vec<(u8, Vec<String>)>>,
+)> {
+    let metadata = module
+        .metadata
+        .iter()
+        .find(|metadata| metadata.key == APTOS_METADATA_KEY_V1)?;
+    let (_, struct_attributes, fun_attributes) =
+        bcs::from_bytes::<RuntimeModuleMetadataV1>(&metadata.value).ok()?;
+    Some((struct_attributes, fun_attributes))
+}
+
+fn view_function_names(module: &CompiledModule) -> Option<Vec<String>> {
+    let (_, fun_attributes) = module_attributes(module)?;
+    Some(
+        fun_attributes
+            .into_iter()
+            .filter(|(_, attributes)| {
+                attributes
+                    .iter()
+                    .any(|(kind, _)| VIEW_FUNCTION_ATTRIBUTE_KINDS.contains(kind))
+            })
+            .map(|(name, _)| name)
+            .collect(),
+    )
+}
+
+fn type_tag(module: &CompiledModule, token: &SignatureToken) -> Option<TypeTag> {
+    use SignatureToken::*;
+    Some(match token {
+        Bool => TypeTag::Bool,
+        U8 => TypeTag::U8,
+        U16 => TypeTag::U16,
+        U32 => TypeTag::U32,
+        U64 => TypeTag::U64,
+        U128 => TypeTag::U128,
+        U256 => TypeTag::U256,
+        Address => TypeTag::Address,
+        Signer => TypeTag::Signer,
+        Vector(token) => TypeTag::Vector(Box::new(type_tag(module, token)?)),
+        Struct(idx) => TypeTag::Struct(Box::new(struct_tag(module, *idx))),
+        StructInstantiation(idx, tokens) => {
+            let mut tag = struct_tag(module, *idx);
+            let type_args = tokens
+                .iter()
+                .map(|token| type_tag(module, token))
+                .collect::<Option<Vec<_>>>();
+            tag.type_args = match type_args {
+                Some(type_args) => type_args,
+                // The encoding of an object does not depend on its type.
+                None if is_object(&tag) => vec![object_core()],
+                None => return None,
+            };
+            TypeTag::Struct(Box::new(tag))
+        },
+        Reference(_) | MutableReference(_) | TypeParameter(_) => return None,
+    })
+}
+
+fn struct_tag(module: &CompiledModule, idx: StructHandleIndex) -> StructTag {
+    let handle = module.struct_handle_at(idx);
+    let module_handle = module.module_handle_at(handle.module);
+    StructTag {
+        address: *module.address_identifier_at(module_handle.address),
+        module: module.identifier_at(module_handle.name).to_owned(),
+        name: module.identifier_at(handle.name).to_owned(),
+        type_args: vec![],
+    }
+}
+
+fn is_object(tag: &StructTag) -> bool {
+    tag.address == AccountAddress::ONE
+        && tag.module.as_str() == "object"
+        && tag.name.as_str() == "Object"
+}
+
+fn object_core() -> TypeTag {
+    TypeTag::Struct(Box::new(StructTag {
+        address: AccountAddress::ONE,
+        module: Identifier::new("object").unwrap(),
+        name: Identifier::new("ObjectCore").unwrap(),
+        type_args: vec![],
+    }))
+}
@@ -0,0 +1,311 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_sdk_builder as buildgen;
+use aptos_sdk_builder::{
+    FieldABI, FieldType, MoveStructABI, StructLayoutABI, StructTypeParameterABI, VariantABI,
+    ViewFunctionABI,
+};
+use aptos_types::transaction::{ArgumentABI, EntryABI, EntryFunctionABI, TypeArgumentABI};
+use goldenfile::Mint;
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::{ModuleId, TypeTag},
+};
+use std::str::FromStr;
+
+// To update the golden files, run `UPDATE_GOLDENFILES=1 cargo test -p aptos-sdk-builder`.
+const GOLDENS_DIR: &str = "tests/goldens";
+
+fn module_id(address: &str, name: &str) -> ModuleId {
+    ModuleId::new(
+        AccountAddress::from_hex_literal(address).unwrap(),
+        Identifier::new(name).unwrap(),
+    )
+}
+
+fn args(args: &[(&str, &str)]) -> Vec<ArgumentABI> {
+    args.iter()
+        .map(|(name, type_tag)| {
+            ArgumentABI::new(name.to_string(), TypeTag::from_str(type_tag).unwrap())
+        })
+        .collect()
+}
+
+fn ty_args(names: &[&str]) -> Vec<TypeArgumentABI> {
+    names
+        .iter()
+        .map(|name| TypeArgumentABI::new(name.to_string()))
+        .collect()
+}
+
+fn entry_function_abis() -> Vec<EntryABI> {
+    vec![
+        EntryABI::EntryFunction(EntryFunctionABI::new(
+            "transfer".to_string(),
+            module_id("0x1", "coin"),
+            " Transfer `amount` of coins to `to`.".to_string(),
+            ty_args(&["coin_type"]),
+            args(&[("to", "address"), ("amount", "u64")]),
+        )),
+        EntryABI::EntryFunction(EntryFunctionABI::new(
+            "set_profile".to_string(),
+            module_id("0xcafe", "profile"),
+            " Sets the profile of the sender.\n\n The avatar is optional.".to_string(),
+            vec![],
+            args(&[
+                ("name", "0x1::string::String"),
+                (
+                    "avatar",
+                    "0x1::option::Option<0x1::object::Object<0xcafe::profile::Avatar>>",
+                ),
+                ("tags", "vector<0x1::string::String>"),
+                ("from", "address"),
+            ]),
+        )),
+        // Skipped, as the argument cannot be encoded.
+        EntryABI::EntryFunction(EntryFunctionABI::new(
+            "set_config".to_string(),
+            module_id("0xcafe", "profile"),
+            String::new(),
+            vec![],
+            args(&[("config", "0xcafe::profile::Config")]),
+        )),
+    ]
+}
+
+fn view_function_abis() -> Vec<ViewFunctionABI> {
+    vec![
+        ViewFunctionABI::new(
+            "balance".to_string(),
+            module_id("0x1", "coin"),
+            ty_args(&["coin_type"]),
+            args(&[("owner", "address")]),
+            vec![TypeTag::U64],
+        ),
+        ViewFunctionABI::new(
+            "count".to_string(),
+            module_id("0xcafe", "profile"),
+            vec![],
+            vec![],
+            vec![TypeTag::U64],
+        ),
+        ViewFunctionABI::new(
+            "stats".to_string(),
+            module_id("0xcafe", "profile"),
+            vec![],
+            args(&[
+                ("owners", "vector<address>"),
+                ("bonus", "0x1::fixed_point32::FixedPoint32"),
+                ("limit", "u256"),
+                ("data", "vector<vector<u8>>"),
+            ]),
+            vec![TypeTag::from_str("vector<u8>").unwrap(), TypeTag::Bool],
+        ),
+    ]
+}
+
+fn field(name: &str, type_tag: &str) -> FieldABI {
+    FieldABI {
+        name: name.to_string(),
+        type_: FieldType::from(&TypeTag::from_str(type_tag).unwrap()),
+    }
+}
+
+fn struct_abi(module: &str, name: &str, layout: StructLayoutABI) -> MoveStructABI {
+    MoveStructABI {
+        name: name.to_string(),
+        module_name: module_id("0xcafe", module),
+        ty_params: vec![],
+        layout,
+        is_resource: false,
+        is_event: false,
+    }
+}
+
+fn struct_abis() -> Vec<MoveStructABI> {
+    vec![
+        MoveStructABI {
+            ty_params: vec![StructTypeParameterABI {
+                name: "Kind".to_string(),
+                is_phantom: true,
+            }],
+            ..struct_abi(
+                "badge",
+                "Badge",
+                StructLayoutABI::Fields(vec![field("level", "u64")]),
+            )
+        },
+        MoveStructABI {
+            is_resource: true,
+            ..struct_abi(
+                "profile",
+                "Profile",
+                StructLayoutABI::Fields(vec![
+                    field("name", "0x1::string::String"),
+                    field("avatar", "0x1::option::Option<0xcafe::profile::Avatar>"),
+                    field("tags", "vector<0x1::string::String>"),
+                    field("type", "u8"),
+                ]),
+            )
+        },
+        struct_abi(
+            "profile",
+            "Avatar",
+            StructLayoutABI::Fields(vec![
+                field("uri", "0x1::string::String"),
+                field("size", "u256"),
+            ]),
+        ),
+        struct_abi(
+            "profile",
+            "Status",
+            StructLayoutABI::Variants(vec![
+                VariantABI {
+                    name: "Active".to_string(),
+                    fields: vec![],
+                },
+                VariantABI {
+                    name: "Suspended".to_string(),
+                    fields: vec![field("until", "u64")],
+                },
+            ]),
+        ),
+        MoveStructABI {
+            is_event: true,
+            ..struct_abi(
+                "profile",
+                "Updated",
+                StructLayoutABI::Fields(vec![
+                    field("owner", "address"),
+                    field("badge", "0xcafe::badge::Badge<0xcafe::profile::Profile>"),
+                ]),
+            )
+        },
+        // Skipped, as the `object` module is not given.
+        struct_abi(
+            "profile",
+            "Link",
+            StructLayoutABI::Fields(vec![field(
+                "target",
+                "0x1::object::Object<0xcafe::profile::Avatar>",
+            )]),
+        ),
+    ]
+}
+
+fn struct_view_function_abis() -> Vec<ViewFunctionABI> {
+    vec![
+        ViewFunctionABI::new(
+            "badge_level".to_string(),
+            module_id("0xcafe", "profile"),
+            ty_args(&["kind"]),
+            args(&[("client", "address")]),
+            vec![TypeTag::U64, TypeTag::Bool],
+        ),
+        // Skipped, as the returned struct is skipped.
+        ViewFunctionABI::new(
+            "link".to_string(),
+            module_id("0xcafe", "profile"),
+            vec![],
+            args(&[("owner", "address")]),
+            vec![TypeTag::from_str("0xcafe::profile::Link").unwrap()],
+        ),
+        ViewFunctionABI::new(
+            "profile".to_string(),
+            module_id("0xcafe", "profile"),
+            vec![],
+            args(&[("owner", "address")]),
+            vec![TypeTag::from_str("0x1::option::Option<0xcafe::profile::Profile>").unwrap()],
+        ),
+    ]
+}
+
+#[test]
+fn test_typescript_builders() {
+    let mut mint = Mint::new(GOLDENS_DIR);
+    let mut file = mint.new_goldenfile("builders.ts").unwrap();
+    buildgen::typescript::output(
+        &mut file,
+        None,
+        None,
+        &entry_function_abis(),
+        &view_function_abis(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_python_builders() {
+    let mut mint = Mint::new(GOLDENS_DIR);
+    let mut file = mint.new_goldenfile("builders.py").unwrap();
+    buildgen::python::output(
+        &mut file,
+        None,
+        &entry_function_abis(),
+        &view_function_abis(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_rust_bindings() {
+    let mut mint = Mint::new(GOLDENS_DIR);
+    let mut file = mint.new_goldenfile("bindings.rs").unwrap();
+    buildgen::rust_bindings::output(&mut file, &struct_abis(), &struct_view_function_abis())
+        .unwrap();
+}
+
+#[test]
+fn test_view_functions_of_framework() {
+    let coin = aptos_cached_packages::head_release_bundle()
+        .compiled_modules()
+        .into_iter()
+        .find(|module| module.self_id() == module_id("0x1", "coin"))
+        .unwrap();
+    let abis = buildgen::view_function_abis(&coin, None);
+
+    let balance = abis.iter().find(|abi| abi.name() == "balance").unwrap();
+    assert_eq!(balance.ty_args(), ty_args(&["t0"]).as_slice());
+    assert_eq!(balance.args(), args(&[("arg0", "address")]).as_slice());
+    assert_eq!(balance.returns(), &[TypeTag::U64]);
+
+    // Entry functions which are not view functions are not extracted.
+    assert!(abis.iter().all(|abi| abi.name() != "transfer"));
+}
+
+#[test]
+fn test_structs_of_framework() {
+    let coin = aptos_cached_packages::head_release_bundle()
+        .compiled_modules()
+        .into_iter()
+        .find(|module| module.self_id() == module_id("0x1", "coin"))
+        .unwrap();
+    let abis = buildgen::struct_abis(&coin, None);
+
+    let coin_store = abis.iter().find(|abi| abi.name == "CoinStore").unwrap();
+    assert!(coin_store.is_resource && !coin_store.is_event);
+    assert_eq!(coin_store.ty_params, vec![StructTypeParameterABI {
+        name: "T0".to_string(),
+        is_phantom: true,
+    }]);
+    match &coin_store.layout {
+        StructLayoutABI::Fields(fields) => {
+            assert_eq!(fields[0], FieldABI {
+                name: "coin".to_string(),
+                type_: FieldType::Struct {
+                    address: AccountAddress::ONE,
+                    module: Identifier::new("coin").unwrap(),
+                    name: Identifier::new("Coin").unwrap(),
+                    type_args: vec![FieldType::TypeParameter(0)],
+                },
+            });
+            assert_eq!(fields[1], field("frozen", "bool"));
+        },
+        StructLayoutABI::Variants(_) => panic!("`CoinStore` is not an enum"),
+    }
+
+    let coin_deposit = abis.iter().find(|abi| abi.name == "CoinDeposit").unwrap();
+    assert!(coin_deposit.is_event && !coin_deposit.is_resource);
+}
@@ -0,0 +1,192 @@
+// This file was generated by `aptos-sdk-builder`. Do not modify!
+//
+// Rust types for the structs of Move modules, which match the BCS encoding of resources and
+// events, and typed wrappers calling view functions through the REST API.
+
+/// The result of a call to a view function.
+pub type ViewFunctionResult<T> = ::std::result::Result<
+    ::aptos_sdk::rest_client::Response<T>,
+    ::aptos_sdk::rest_client::error::RestError,
+>;
+
+/// Calls a view function through the BCS API. The response is the number of returned values,
+/// as a single-byte ULEB128, followed by the values.
+#[allow(dead_code)]
+async fn call_view_function<T: ::serde::de::DeserializeOwned>(
+    client: &::aptos_sdk::rest_client::Client,
+    address: &str,
+    module: &str,
+    function: &str,
+    ty_args: ::std::vec::Vec<::aptos_sdk::move_types::language_storage::TypeTag>,
+    args: ::std::vec::Vec<::std::vec::Vec<u8>>,
+    version: ::std::option::Option<u64>,
+) -> ViewFunctionResult<T> {
+    let request = ::aptos_sdk::rest_client::aptos_api_types::ViewFunction {
+        module: ::aptos_sdk::move_types::language_storage::ModuleId::new(
+            ::aptos_sdk::move_types::account_address::AccountAddress::from_hex_literal(address).unwrap(),
+            ::aptos_sdk::move_types::identifier::Identifier::new(module).unwrap(),
+        ),
+        function: ::aptos_sdk::move_types::identifier::Identifier::new(function).unwrap(),
+        ty_args,
+        args,
+    };
+    let response = client.view_bcs::<(u8, T)>(&request, version).await?;
+    Ok(response.map(|(_, values)| values))
+}
+
+#[allow(dead_code)]
+fn encode_argument<T: ::serde::Serialize>(value: &T) -> ::std::vec::Vec<u8> {
+    ::aptos_sdk::bcs::to_bytes(value).expect("arguments can be serialized")
+}
+
+#[allow(dead_code)]
+fn type_tag<T: ::aptos_sdk::move_types::move_resource::MoveStructType>() -> ::aptos_sdk::move_types::language_storage::TypeTag {
+    ::aptos_sdk::move_types::language_storage::TypeTag::Struct(::std::boxed::Box::new(T::struct_tag()))
+}
+
+#[allow(dead_code)]
+fn decode_event<T: ::aptos_sdk::move_types::move_resource::MoveStructType + ::serde::de::DeserializeOwned>(
+    event: &::aptos_sdk::types::contract_event::ContractEvent,
+) -> ::std::result::Result<::std::option::Option<T>, ::aptos_sdk::bcs::Error> {
+    if event.type_tag() != &type_tag::<T>() {
+        return Ok(None);
+    }
+    ::aptos_sdk::bcs::from_bytes(event.event_data()).map(Some)
+}
+
+pub mod badge {
+    //! Bindings for the Move module `0xcafe::badge`.
+    #![allow(clippy::all, non_camel_case_types, non_snake_case)]
+
+    /// The Move struct `0xcafe::badge::Badge`.
+    #[derive(Clone, Debug, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    pub struct Badge<Kind> {
+        pub level: u64,
+        #[serde(skip)]
+        pub phantom: ::std::marker::PhantomData<Kind>,
+    }
+
+    impl<Kind: ::aptos_sdk::move_types::move_resource::MoveStructType> ::aptos_sdk::move_types::move_resource::MoveStructType for Badge<Kind> {
+        const ADDRESS: ::aptos_sdk::move_types::account_address::AccountAddress =
+            ::aptos_sdk::move_types::account_address::AccountAddress::new([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 202, 254]);
+        const MODULE_NAME: &'static ::aptos_sdk::move_types::identifier::IdentStr = ::aptos_sdk::move_types::ident_str!("badge");
+        const STRUCT_NAME: &'static ::aptos_sdk::move_types::identifier::IdentStr = ::aptos_sdk::move_types::ident_str!("Badge");
+
+        fn type_args() -> ::std::vec::Vec<::aptos_sdk::move_types::language_storage::TypeTag> {
+            vec![super::type_tag::<Kind>()]
+        }
+    }
+}
+
+pub mod profile {
+    //! Bindings for the Move module `0xcafe::profile`.
+    #![allow(clippy::all, non_camel_case_types, non_snake_case)]
+
+    /// The Move struct `0xcafe::profile::Profile`.
+    #[derive(Clone, Debug, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    pub struct Profile {
+        pub name: ::std::string::String,
+        pub avatar: ::std::option::Option<Avatar>,
+        pub tags: ::std::vec::Vec<::std::string::String>,
+        pub r#type: u8,
+    }
+
+    impl ::aptos_sdk::move_types::move_resource::MoveStructType for Profile {
+        const ADDRESS: ::aptos_sdk::move_types::account_address::AccountAddress =
+            ::aptos_sdk::move_types::account_address::AccountAddress::new([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 202, 254]);
+        const MODULE_NAME: &'static ::aptos_sdk::move_types::identifier::IdentStr = ::aptos_sdk::move_types::ident_str!("profile");
+        const STRUCT_NAME: &'static ::aptos_sdk::move_types::identifier::IdentStr = ::aptos_sdk::move_types::ident_str!("Profile");
+    }
+
+    impl ::aptos_sdk::move_types::move_resource::MoveResource for Profile where Self: ::serde::de::DeserializeOwned {}
+
+    /// The Move struct `0xcafe::profile::Avatar`.
+    #[derive(Clone, Debug, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    pub struct Avatar {
+        pub uri: ::std::string::String,
+        pub size: ::aptos_sdk::move_types::u256::U256,
+    }
+
+    impl ::aptos_sdk::move_types::move_resource::MoveStructType for Avatar {
+        const ADDRESS: ::aptos_sdk::move_types::account_address::AccountAddress =
+            ::aptos_sdk::move_types::account_address::AccountAddress::new([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 202, 254]);
+        const MODULE_NAME: &'static ::aptos_sdk::move_types::identifier::IdentStr = ::aptos_sdk::move_types::ident_str!("profile");
+        const STRUCT_NAME: &'static ::aptos_sdk::move_types::identifier::IdentStr = ::aptos_sdk::move_types::ident_str!("Avatar");
+    }
+
+    /// The Move struct `0xcafe::profile::Status`.
+    #[derive(Clone, Debug, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    pub enum Status {
+        Active,
+        Suspended {
+            until: u64,
+        },
+    }
+
+    impl ::aptos_sdk::move_types::move_resource::MoveStructType for Status {
+        const ADDRESS: ::aptos_sdk::move_types::account_address::AccountAddress =
+            ::aptos_sdk::move_types::account_address::AccountAddress::new([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 202, 254]);
+        const MODULE_NAME: &'static ::aptos_sdk::move_types::identifier::IdentStr = ::aptos_sdk::move_types::ident_str!("profile");
+        const STRUCT_NAME: &'static ::aptos_sdk::move_types::identifier::IdentStr = ::aptos_sdk::move_types::ident_str!("Status");
+    }
+
+    /// The Move struct `0xcafe::profile::Updated`.
+    #[derive(Clone, Debug, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    pub struct Updated {
+        pub owner: ::aptos_sdk::move_types::account_address::AccountAddress,
+        pub badge: super::badge::Badge<Profile>,
+    }
+
+    impl ::aptos_sdk::move_types::move_resource::MoveStructType for Updated {
+        const ADDRESS: ::aptos_sdk::move_types::account_address::AccountAddress =
+            ::aptos_sdk::move_types::account_address::AccountAddress::new([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 202, 254]);
+        const MODULE_NAME: &'static ::aptos_sdk::move_types::identifier::IdentStr = ::aptos_sdk::move_types::ident_str!("profile");
+        const STRUCT_NAME: &'static ::aptos_sdk::move_types::identifier::IdentStr = ::aptos_sdk::move_types::ident_str!("Updated");
+    }
+
+    impl Updated where Self: ::serde::de::DeserializeOwned {
+        /// Decodes the event, if it is a `0xcafe::profile::Updated`.
+        pub fn try_from_event(
+            event: &::aptos_sdk::types::contract_event::ContractEvent,
+        ) -> ::std::result::Result<::std::option::Option<Self>, ::aptos_sdk::bcs::Error> {
+            super::decode_event(event)
+        }
+    }
+
+    /// Calls the view function `0xcafe::profile::badge_level`.
+    pub async fn badge_level(
+        client: &::aptos_sdk::rest_client::Client,
+        kind: ::aptos_sdk::move_types::language_storage::TypeTag,
+        client_: ::aptos_sdk::move_types::account_address::AccountAddress,
+        version: ::std::option::Option<u64>,
+    ) -> super::ViewFunctionResult<(u64, bool)> {
+        super::call_view_function(
+            client,
+            "0xcafe",
+            "profile",
+            "badge_level",
+            vec![kind],
+            vec![super::encode_argument(&client_)],
+            version,
+        )
+        .await
+    }
+
+    /// Calls the view function `0xcafe::profile::profile`.
+    pub async fn profile(
+        client: &::aptos_sdk::rest_client::Client,
+        owner: ::aptos_sdk::move_types::account_address::AccountAddress,
+        version: ::std::option::Option<u64>,
+    ) -> super::ViewFunctionResult<::std::option::Option<Profile>> {
+        super::call_view_function(
+            client,
+            "0xcafe",
+            "profile",
+            "profile",
+            vec![],
+            vec![super::encode_argument(&owner)],
+            version,
+        )
+        .await
+    }
+}
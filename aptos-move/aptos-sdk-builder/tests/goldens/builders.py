@@ -0,0 +1,118 @@
+# This file was generated by `aptos-sdk-builder`. Do not modify!
+#
+# Builders for the payloads of known Move entry functions, and for requests to known Move
+# view functions. The BCS encoding of a view function request is the body of a BCS request
+# to the `/view` endpoint of the REST API.
+
+import typing
+
+from aptos_types import (
+    AccountAddress,
+    EntryFunction,
+    Identifier,
+    ModuleId,
+    TransactionPayload,
+    TransactionPayload__EntryFunction,
+    TypeTag,
+)
+
+
+def _module_id(address: str, name: str) -> ModuleId:
+    return ModuleId(
+        address=AccountAddress.bcs_deserialize(bytes.fromhex(address)),
+        name=Identifier(value=name),
+    )
+
+
+def _encode_uleb128(value: int) -> bytes:
+    output = bytearray()
+    while value >= 0x80:
+        output.append((value & 0x7F) | 0x80)
+        value >>= 7
+    output.append(value)
+    return bytes(output)
+
+
+def _encode_bytes(value: bytes) -> bytes:
+    return _encode_uleb128(len(value)) + value
+
+
+def encode_coin_transfer(coin_type: TypeTag, to: AccountAddress, amount: int) -> TransactionPayload:
+    """
+    Transfer `amount` of coins to `to`.
+    """
+    return TransactionPayload__EntryFunction(
+        value=EntryFunction(
+            module=_module_id("0000000000000000000000000000000000000000000000000000000000000001", "coin"),
+            function=Identifier(value="transfer"),
+            ty_args=[coin_type],
+            args=[
+                to.bcs_serialize(),
+                amount.to_bytes(8, "little"),
+            ],
+        ),
+    )
+
+
+def encode_profile_set_profile(name: str, avatar: typing.Optional[AccountAddress], tags: typing.Sequence[str], from_: AccountAddress) -> TransactionPayload:
+    """
+    Sets the profile of the sender.
+
+    The avatar is optional.
+    """
+    return TransactionPayload__EntryFunction(
+        value=EntryFunction(
+            module=_module_id("000000000000000000000000000000000000000000000000000000000000cafe", "profile"),
+            function=Identifier(value="set_profile"),
+            ty_args=[],
+            args=[
+                _encode_bytes(name.encode()),
+                (b"\x00" if avatar is None else b"\x01" + avatar.bcs_serialize()),
+                _encode_uleb128(len(tags)) + b"".join(_encode_bytes(item0.encode()) for item0 in tags),
+                from_.bcs_serialize(),
+            ],
+        ),
+    )
+
+
+def view_coin_balance(coin_type: TypeTag, owner: AccountAddress) -> EntryFunction:
+    """
+    Request to call the view function `0x1::coin::balance`, which returns `u64`.
+    """
+    return EntryFunction(
+        module=_module_id("0000000000000000000000000000000000000000000000000000000000000001", "coin"),
+        function=Identifier(value="balance"),
+        ty_args=[coin_type],
+        args=[
+            owner.bcs_serialize(),
+        ],
+    )
+
+
+def view_profile_count() -> EntryFunction:
+    """
+    Request to call the view function `0xcafe::profile::count`, which returns `u64`.
+    """
+    return EntryFunction(
+        module=_module_id("000000000000000000000000000000000000000000000000000000000000cafe", "profile"),
+        function=Identifier(value="count"),
+        ty_args=[],
+        args=[],
+    )
+
+
+def view_profile_stats(owners: typing.Sequence[AccountAddress], bonus: int, limit: int, data: typing.Sequence[bytes]) -> EntryFunction:
+    """
+    Request to call the view function `0xcafe::profile::stats`, which returns `(vector<u8>, bool)`.
+    """
+    return EntryFunction(
+        module=_module_id("000000000000000000000000000000000000000000000000000000000000cafe", "profile"),
+        function=Identifier(value="stats"),
+        ty_args=[],
+        args=[
+            _encode_uleb128(len(owners)) + b"".join(item0.bcs_serialize() for item0 in owners),
+            bonus.to_bytes(8, "little"),
+            limit.to_bytes(32, "little"),
+            _encode_uleb128(len(data)) + b"".join(_encode_bytes(bytes(item0)) for item0 in data),
+        ],
+    )
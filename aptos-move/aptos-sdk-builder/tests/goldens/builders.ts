@@ -0,0 +1,114 @@
+// This file was generated by `aptos-sdk-builder`. Do not modify!
+//
+// Builders for the payloads of known Move entry functions, and for requests to known Move
+// view functions. The BCS encoding of a view function request is the body of a BCS request
+// to the `/view` endpoint of the REST API.
+
+import { BcsDeserializer, BcsSerializer } from "../bcs/mod.ts";
+import {
+  AccountAddress,
+  EntryFunction,
+  Identifier,
+  ModuleId,
+  TransactionPayload,
+  TransactionPayloadVariantEntryFunction,
+  TypeTag,
+} from "../aptos_types/mod.ts";
+
+function moduleId(address: string, name: string): ModuleId {
+  const bytes = new Uint8Array(32);
+  for (let i = 0; i < 32; i++) {
+    bytes[i] = parseInt(address.substring(2 * i, 2 * i + 2), 16);
+  }
+  return new ModuleId(
+    AccountAddress.deserialize(new BcsDeserializer(bytes)),
+    new Identifier(name),
+  );
+}
+
+function encodeArgument(write: (serializer: BcsSerializer) => void): Uint8Array {
+  const serializer = new BcsSerializer();
+  write(serializer);
+  return serializer.getBytes();
+}
+
+/**
+ * Transfer `amount` of coins to `to`.
+ */
+export function encodeCoinTransfer(coin_type: TypeTag, to: AccountAddress, amount: bigint): TransactionPayload {
+  return new TransactionPayloadVariantEntryFunction(
+    new EntryFunction(
+      moduleId("0000000000000000000000000000000000000000000000000000000000000001", "coin"),
+      new Identifier("transfer"),
+      [coin_type],
+      [
+        encodeArgument((s) => { to.serialize(s); }),
+        encodeArgument((s) => { s.serializeU64(amount); }),
+      ],
+    ),
+  );
+}
+
+/**
+ * Sets the profile of the sender.
+ *
+ * The avatar is optional.
+ */
+export function encodeProfileSetProfile(name: string, avatar: AccountAddress | null, tags: Array<string>, from: AccountAddress): TransactionPayload {
+  return new TransactionPayloadVariantEntryFunction(
+    new EntryFunction(
+      moduleId("000000000000000000000000000000000000000000000000000000000000cafe", "profile"),
+      new Identifier("set_profile"),
+      [],
+      [
+        encodeArgument((s) => { s.serializeStr(name); }),
+        encodeArgument((s) => { if (avatar === null) { s.serializeLen(0); } else { s.serializeLen(1); avatar.serialize(s); } }),
+        encodeArgument((s) => { s.serializeLen(tags.length); for (const item0 of tags) { s.serializeStr(item0); } }),
+        encodeArgument((s) => { from.serialize(s); }),
+      ],
+    ),
+  );
+}
+
+/**
+ * Request to call the view function `0x1::coin::balance`, which returns `u64`.
+ */
+export function viewCoinBalance(coin_type: TypeTag, owner: AccountAddress): EntryFunction {
+  return new EntryFunction(
+    moduleId("0000000000000000000000000000000000000000000000000000000000000001", "coin"),
+    new Identifier("balance"),
+    [coin_type],
+    [
+      encodeArgument((s) => { owner.serialize(s); }),
+    ],
+  );
+}
+
+/**
+ * Request to call the view function `0xcafe::profile::count`, which returns `u64`.
+ */
+export function viewProfileCount(): EntryFunction {
+  return new EntryFunction(
+    moduleId("000000000000000000000000000000000000000000000000000000000000cafe", "profile"),
+    new Identifier("count"),
+    [],
+    [],
+  );
+}
+
+/**
+ * Request to call the view function `0xcafe::profile::stats`, which returns `(vector<u8>, bool)`.
+ */
+export function viewProfileStats(owners: Array<AccountAddress>, bonus: bigint, limit: bigint, data: Array<Uint8Array>): EntryFunction {
+  return new EntryFunction(
+    moduleId("000000000000000000000000000000000000000000000000000000000000cafe", "profile"),
+    new Identifier("stats"),
+    [],
+    [
+      encodeArgument((s) => { s.serializeLen(owners.length); for (const item0 of owners) { item0.serialize(s); } }),
+      encodeArgument((s) => { s.serializeU64(bonus); }),
+      encodeArgument((s) => { s.serializeU128(limit & ((1n << 128n) - 1n)); s.serializeU128(limit >> 128n); }),
+      encodeArgument((s) => { s.serializeLen(data.length); for (const item0 of data) { s.serializeBytes(item0); } }),
+    ],
+  );
+}
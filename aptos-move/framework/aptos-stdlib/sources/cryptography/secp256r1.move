@@ -0,0 +1,184 @@
+/// This module implements ECDSA signatures based on the NIST P-256 elliptic curve (a.k.a., secp256r1 or prime256v1).
+///
+/// P-256 is the curve used by WebAuthn passkeys and by most secure enclaves and hardware attestation schemes, so these
+/// functions can be used to verify passkey signatures and device attestations on chain.
+///
+/// Note that ECDSA signatures are malleable: if `(r, s)` is a valid signature then so is `(r, n - s)`, where `n` is the
+/// order of the curve. Therefore, signatures should never be used as unique identifiers (e.g., to prevent replays).
+module aptos_std::secp256r1 {
+    use std::features;
+    use std::option::Option;
+
+    /// An error occurred while deserializing, for example due to wrong input size.
+    const E_DESERIALIZE: u64 = 1;   // This code must be the same, if ever returned from the native Rust implementation.
+
+    /// The secp256r1 natives are not yet enabled.
+    const E_NATIVE_FUN_NOT_AVAILABLE: u64 = 2;
+
+    /// The size of a secp256r1-based ECDSA public key (the `x` and `y` coordinates of the point), in bytes.
+    const RAW_PUBLIC_KEY_NUM_BYTES: u64 = 64;
+
+    /// The size of a secp256r1-based ECDSA signature (the `r` and `s` scalars), in bytes.
+    const SIGNATURE_NUM_BYTES: u64 = 64;
+
+    /// The size of a message digest that is signed, in bytes.
+    const MESSAGE_DIGEST_NUM_BYTES: u64 = 32;
+
+    /// A 64-byte ECDSA public key.
+    struct ECDSARawPublicKey has copy, drop, store {
+        bytes: vector<u8>
+    }
+
+    /// A 64-byte ECDSA signature.
+    struct ECDSASignature has copy, drop, store {
+        bytes: vector<u8>
+    }
+
+    /// Constructs an ECDSASignature struct from the given 64 bytes.
+    public fun ecdsa_signature_from_bytes(bytes: vector<u8>): ECDSASignature {
+        assert!(std::vector::length(&bytes) == SIGNATURE_NUM_BYTES, std::error::invalid_argument(E_DESERIALIZE));
+        ECDSASignature { bytes }
+    }
+
+    /// Constructs an ECDSARawPublicKey struct, given a 64-byte raw representation (i.e., an uncompressed SEC1 public
+    /// key without the leading `0x04` byte).
+    public fun ecdsa_raw_public_key_from_64_bytes(bytes: vector<u8>): ECDSARawPublicKey {
+        assert!(std::vector::length(&bytes) == RAW_PUBLIC_KEY_NUM_BYTES, std::error::invalid_argument(E_DESERIALIZE));
+        ECDSARawPublicKey { bytes }
+    }
+
+    /// Serializes an ECDSARawPublicKey struct to 64-bytes.
+    public fun ecdsa_raw_public_key_to_bytes(pk: &ECDSARawPublicKey): vector<u8> {
+        pk.bytes
+    }
+
+    /// Serializes an ECDSASignature struct to 64-bytes.
+    public fun ecdsa_signature_to_bytes(sig: &ECDSASignature): vector<u8> {
+        sig.bytes
+    }
+
+    /// Returns `true` if `signature` verifies on the signed `message` (32 byte digest, e.g., the SHA2-256 hash of the
+    /// message) under `public_key`. Returns `false` otherwise, including if `public_key` is not a valid curve point.
+    public fun ecdsa_verify(
+        message: vector<u8>,
+        public_key: &ECDSARawPublicKey,
+        signature: &ECDSASignature,
+    ): bool {
+        assert!(features::secp256r1_natives_enabled(), std::error::invalid_state(E_NATIVE_FUN_NOT_AVAILABLE));
+        assert!(std::vector::length(&message) == MESSAGE_DIGEST_NUM_BYTES, std::error::invalid_argument(E_DESERIALIZE));
+        ecdsa_verify_internal(message, public_key.bytes, signature.bytes)
+    }
+
+    /// Recovers the signer's raw (64-byte) public key from a secp256r1 ECDSA `signature` given the `recovery_id` and the
+    /// signed `message` (32 byte digest).
+    ///
+    /// Note that an invalid signature, or a signature from a different message, will result in the recovery of an
+    /// incorrect public key. This recovery algorithm can only be used to check validity of a signature if the signer's
+    /// public key (or its hash) is known beforehand.
+    public fun ecdsa_recover(
+        message: vector<u8>,
+        recovery_id: u8,
+        signature: &ECDSASignature,
+    ): Option<ECDSARawPublicKey> {
+        assert!(features::secp256r1_natives_enabled(), std::error::invalid_state(E_NATIVE_FUN_NOT_AVAILABLE));
+        assert!(std::vector::length(&message) == MESSAGE_DIGEST_NUM_BYTES, std::error::invalid_argument(E_DESERIALIZE));
+        let (pk, success) = ecdsa_recover_internal(message, recovery_id, signature.bytes);
+        if (success) {
+            std::option::some(ecdsa_raw_public_key_from_64_bytes(pk))
+        } else {
+            std::option::none<ECDSARawPublicKey>()
+        }
+    }
+
+    //
+    // Native functions
+    //
+
+    /// Returns `true` if `signature` verifies on `message` under `public_key` and returns `false` otherwise.
+    native fun ecdsa_verify_internal(
+        message: vector<u8>,
+        public_key: vector<u8>,
+        signature: vector<u8>
+    ): bool;
+
+    /// Returns `(public_key, true)` if `signature` verifies on `message` under the recovered `public_key`
+    /// and returns `([], false)` otherwise.
+    native fun ecdsa_recover_internal(
+        message: vector<u8>,
+        recovery_id: u8,
+        signature: vector<u8>
+    ): (vector<u8>, bool);
+
+    //
+    // Tests
+    //
+
+    #[test_only]
+    /// The public key for sk = x"0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef"
+    const TEST_PUBLIC_KEY: vector<u8> = x"d8cd12ea5c67f2f8a00c1124893edcfa6754c4d6cede6be13bdf2295c810a97fa5a89d2d2a360c0ca9a4d6c7c9ed4b28d3e199d6627f2e696d689c310a5b0f48";
+
+    #[test_only]
+    /// A signature on the SHA2-256 hash of `b"test aptos secp256r1"` under `TEST_PUBLIC_KEY`
+    const TEST_SIGNATURE: vector<u8> = x"d9eae8f2b41af6e901de3ea3c2aa139f92c0b00a16e1ccb9e06ba037dcf8c0ab027bf0649a3a209387ecc33bde416fecda9614224f2ac43ea653a79bc4607f52";
+
+    #[test(fx = @aptos_std)]
+    fun test_ecdsa_verify(fx: signer) {
+        use std::hash;
+        features::change_feature_flags_for_testing(&fx, vector[features::get_secp256r1_natives_feature()], vector[]);
+
+        let pk = ecdsa_raw_public_key_from_64_bytes(TEST_PUBLIC_KEY);
+        let sig = ecdsa_signature_from_bytes(TEST_SIGNATURE);
+        assert!(ecdsa_verify(hash::sha2_256(b"test aptos secp256r1"), &pk, &sig), 1);
+
+        // Wrong message
+        assert!(!ecdsa_verify(hash::sha2_256(b"test aptos secp256k1"), &pk, &sig), 1);
+
+        // Flipped bits; Signature becomes invalid
+        let sig = ecdsa_signature_from_bytes(x"d9eae8f2b41af6e901de3ea3c2aa139f92c0b00a16e1ccb9e06ba037dcf8c0ab027bf0649a3a209387ecc33bde416fecda9614224f2ac43ea653a79bc4607f53");
+        assert!(!ecdsa_verify(hash::sha2_256(b"test aptos secp256r1"), &pk, &sig), 1);
+
+        // Invalid public key (not on the curve)
+        let pk = ecdsa_raw_public_key_from_64_bytes(x"d8cd12ea5c67f2f8a00c1124893edcfa6754c4d6cede6be13bdf2295c810a97fa5a89d2d2a360c0ca9a4d6c7c9ed4b28d3e199d6627f2e696d689c310a5b0f49");
+        assert!(!ecdsa_verify(hash::sha2_256(b"test aptos secp256r1"), &pk, &ecdsa_signature_from_bytes(TEST_SIGNATURE)), 1);
+    }
+
+    #[test(fx = @aptos_std)]
+    fun test_ecdsa_recover(fx: signer) {
+        use std::hash;
+        features::change_feature_flags_for_testing(&fx, vector[features::get_secp256r1_natives_feature()], vector[]);
+
+        let pk = ecdsa_recover(
+            hash::sha2_256(b"test aptos secp256r1"),
+            1,
+            &ECDSASignature { bytes: TEST_SIGNATURE },
+        );
+        assert!(std::option::is_some(&pk), 1);
+        assert!(std::option::extract(&mut pk).bytes == TEST_PUBLIC_KEY, 1);
+
+        // Wrong recovery id; a different public key is recovered
+        let pk = ecdsa_recover(
+            hash::sha2_256(b"test aptos secp256r1"),
+            0,
+            &ECDSASignature { bytes: TEST_SIGNATURE },
+        );
+        assert!(std::option::is_some(&pk), 1);
+        assert!(std::option::extract(&mut pk).bytes != TEST_PUBLIC_KEY, 1);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x30002, location = Self)]
+    fun test_ecdsa_verify_feature_disabled() {
+        let pk = ecdsa_raw_public_key_from_64_bytes(TEST_PUBLIC_KEY);
+        let sig = ecdsa_signature_from_bytes(TEST_SIGNATURE);
+        ecdsa_verify(std::hash::sha2_256(b"test aptos secp256r1"), &pk, &sig);
+    }
+
+    #[test(fx = @aptos_std)]
+    #[expected_failure(abort_code = 0x10001, location = Self)]
+    fun test_ecdsa_verify_invalid_digest(fx: signer) {
+        features::change_feature_flags_for_testing(&fx, vector[features::get_secp256r1_natives_feature()], vector[]);
+        let pk = ecdsa_raw_public_key_from_64_bytes(TEST_PUBLIC_KEY);
+        let sig = ecdsa_signature_from_bytes(TEST_SIGNATURE);
+        ecdsa_verify(b"test aptos secp256r1", &pk, &sig);
+    }
+}
@@ -582,6 +582,18 @@ module std::features {
         is_enabled(ABORT_IF_MULTISIG_PAYLOAD_MISMATCH)
     }
 
+    /// Whether the secp256r1 (P-256) ECDSA natives in `aptos_std::secp256r1` are enabled.
+    /// This is needed because of the introduction of new native functions.
+    ///
+    /// Lifetime: transient
+    const SECP256R1_NATIVES: u64 = 78;
+
+    public fun get_secp256r1_natives_feature(): u64 { SECP256R1_NATIVES }
+
+    public fun secp256r1_natives_enabled(): bool acquires Features {
+        is_enabled(SECP256R1_NATIVES)
+    }
+
     // ============================================================================================
     // Feature Flag Implementation
 
@@ -48,7 +48,15 @@ pub fn create_consensus_runtime(
     admin_service: &mut AdminService,
 ) -> Option<Runtime> {
     consensus_network_interfaces.map(|consensus_network_interfaces| {
-        let (consensus_runtime, consensus_db, quorum_store_db) = services::start_consensus_runtime(
+        let (
+            consensus_runtime,
+            consensus_db,
+            quorum_store_db,
+            batch_lifecycles,
+            block_traces,
+            dag_visualizer,
+            block_building_snapshots,
+        ) = services::start_consensus_runtime(
             node_config,
             db_rw.clone(),
             consensus_reconfig_subscription,
@@ -59,6 +67,16 @@ pub fn create_consensus_runtime(
             consensus_publisher.clone(),
         );
         admin_service.set_consensus_dbs(consensus_db, quorum_store_db);
+        if let Some(batch_lifecycles) = batch_lifecycles {
+            admin_service.set_batch_lifecycles(batch_lifecycles);
+        }
+        if let Some(block_traces) = block_traces {
+            admin_service.set_block_traces(block_traces);
+        }
+        admin_service.set_dag_visualizer(dag_visualizer);
+        if let Some(block_building_snapshots) = block_building_snapshots {
+            admin_service.set_block_building_snapshots(block_building_snapshots);
+        }
 
         consensus_runtime
     })
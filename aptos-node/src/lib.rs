@@ -679,6 +679,8 @@ pub fn setup_environment_and_start_node(
         &node_config,
         aptos_data_client,
         peers_and_metadata.clone(),
+        state_sync_runtimes.get_progress_tracker(),
+        state_sync_runtimes.get_light_client(),
     );
 
     // Bootstrap the API and indexer
@@ -6,8 +6,11 @@ use aptos_admin_service::AdminService;
 use aptos_build_info::build_information;
 use aptos_config::config::NodeConfig;
 use aptos_consensus::{
-    consensus_observer::publisher::ConsensusPublisher, network_interface::ConsensusMsg,
-    persistent_liveness_storage::StorageWriteProxy, quorum_store::quorum_store_db::QuorumStoreDB,
+    consensus_observer::publisher::ConsensusPublisher,
+    network_interface::ConsensusMsg,
+    persistent_liveness_storage::StorageWriteProxy,
+    quorum_store::{batch_lifecycle::BatchLifecycles, quorum_store_db::QuorumStoreDB},
+    BlockBuildingSnapshots, BlockTraces, DagVisualizer,
 };
 use aptos_consensus_notifications::ConsensusNotifier;
 use aptos_data_client::client::AptosDataClient;
@@ -27,6 +30,7 @@ use aptos_peer_monitoring_service_server::{
     PeerMonitoringServiceServer,
 };
 use aptos_peer_monitoring_service_types::PeerMonitoringServiceMessage;
+use aptos_state_sync_driver::{light_client::LightClient, progress::StateSyncProgressTracker};
 use aptos_storage_interface::{DbReader, DbReaderWriter};
 use aptos_time_service::TimeService;
 use aptos_types::{chain_id::ChainId, indexer::indexer_db_reader::IndexerReader};
@@ -130,7 +134,15 @@ pub fn start_consensus_runtime(
     consensus_to_mempool_sender: Sender<QuorumStoreRequest>,
     vtxn_pool: VTxnPoolState,
     consensus_publisher: Option<Arc<ConsensusPublisher>>,
-) -> (Runtime, Arc<StorageWriteProxy>, Arc<QuorumStoreDB>) {
+) -> (
+    Runtime,
+    Arc<StorageWriteProxy>,
+    Arc<QuorumStoreDB>,
+    Option<Arc<BatchLifecycles>>,
+    Option<Arc<BlockTraces>>,
+    Arc<DagVisualizer>,
+    Option<Arc<BlockBuildingSnapshots>>,
+) {
     let instant = Instant::now();
 
     let reconfig_subscription = consensus_reconfig_subscription
@@ -194,11 +206,15 @@ pub fn start_node_inspection_service(
     node_config: &NodeConfig,
     aptos_data_client: AptosDataClient,
     peers_and_metadata: Arc<PeersAndMetadata>,
+    progress_tracker: StateSyncProgressTracker,
+    light_client: Option<LightClient<AptosDataClient>>,
 ) {
     aptos_inspection_service::start_inspection_service(
         node_config.clone(),
         aptos_data_client,
         peers_and_metadata,
+        progress_tracker,
+        light_client,
     )
 }
 
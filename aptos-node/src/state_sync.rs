@@ -147,9 +147,9 @@ pub fn start_state_sync_and_get_notification_handles(
         setup_aptos_data_client(node_config, network_client, db_rw.reader.clone())?;
 
     // Start the data streaming service
-    let state_sync_config = node_config.state_sync;
+    let state_sync_config = node_config.state_sync.clone();
     let (streaming_service_client, streaming_service_runtime) =
-        setup_data_streaming_service(state_sync_config, aptos_data_client.clone())?;
+        setup_data_streaming_service(&state_sync_config, aptos_data_client.clone())?;
 
     // Create the chunk executor and persistent storage
     let chunk_executor = Arc::new(ChunkExecutor::<AptosVM>::new(db_rw.clone()));
@@ -215,7 +215,7 @@ pub fn start_state_sync_and_get_notification_handles(
 
 /// Sets up the data streaming service runtime
 fn setup_data_streaming_service(
-    state_sync_config: StateSyncConfig,
+    state_sync_config: &StateSyncConfig,
     aptos_data_client: AptosDataClient,
 ) -> anyhow::Result<(StreamingServiceClient, Runtime)> {
     // Create the data streaming service
@@ -1,32 +1,58 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, Result};
+use aptos_backup_cli::{
+    coordinators::restore::{RestoreCoordinator, RestoreCoordinatorOpt},
+    metadata::cache::MetadataCacheOpt,
+    storage::{
+        command_adapter::{config::CommandAdapterConfig, CommandAdapter},
+        local_fs::LocalFs,
+        BackupStorage,
+    },
+    utils::{GlobalRestoreOptions, RestoreRunMode, TrustedWaypointOpt},
+};
 use aptos_backup_service::start_backup_service;
-use aptos_config::{config::NodeConfig, utils::get_genesis_txn};
-use aptos_db::{fast_sync_storage_wrapper::FastSyncStorageWrapper, AptosDB};
+use aptos_config::{
+    config::{BackupRestoreConfig, BackupStorageLocation, NodeConfig, NO_OP_STORAGE_PRUNER_CONFIG},
+    utils::get_genesis_txn,
+};
+use aptos_db::{
+    fast_sync_storage_wrapper::FastSyncStorageWrapper, get_restore_handler::GetRestoreHandler,
+    AptosDB,
+};
 use aptos_db_indexer::db_indexer::InternalIndexerDB;
 use aptos_executor::db_bootstrapper::maybe_bootstrap;
 use aptos_indexer_grpc_table_info::internal_indexer_db_service::InternalIndexerDBService;
 use aptos_logger::{debug, info};
 use aptos_storage_interface::{DbReader, DbReaderWriter};
-use aptos_types::{ledger_info::LedgerInfoWithSignatures, waypoint::Waypoint};
+use aptos_types::{
+    ledger_info::LedgerInfoWithSignatures, transaction::Version, waypoint::Waypoint,
+};
 use aptos_vm::AptosVM;
 use either::Either;
 use std::{fs, path::Path, sync::Arc, time::Instant};
 use tokio::runtime::Runtime;
 
-pub(crate) fn maybe_apply_genesis(
-    db_rw: &DbReaderWriter,
-    node_config: &NodeConfig,
-) -> Result<Option<LedgerInfoWithSignatures>> {
-    // We read from the storage genesis waypoint and fallback to the node config one if it is none
-    let genesis_waypoint = node_config
+/// The file (in the storage directory) that marks a completed restore from backup
+pub(crate) const RESTORE_FROM_BACKUP_MARKER_FILE: &str = "restore_from_backup.completed";
+
+/// Returns the genesis waypoint of the node. We read from the storage genesis
+/// waypoint and fallback to the node config one if it is none.
+fn get_genesis_waypoint(node_config: &NodeConfig) -> Waypoint {
+    node_config
         .execution
         .genesis_waypoint
         .as_ref()
         .unwrap_or(&node_config.base.waypoint)
-        .genesis_waypoint();
+        .genesis_waypoint()
+}
+
+pub(crate) fn maybe_apply_genesis(
+    db_rw: &DbReaderWriter,
+    node_config: &NodeConfig,
+) -> Result<Option<LedgerInfoWithSignatures>> {
+    let genesis_waypoint = get_genesis_waypoint(node_config);
     if let Some(genesis) = get_genesis_txn(node_config) {
         let ledger_info_opt = maybe_bootstrap::<AptosVM>(db_rw, genesis, genesis_waypoint)
             .map_err(|err| anyhow!("DB failed to bootstrap {}", err))?;
@@ -47,6 +73,7 @@ pub(crate) fn bootstrap_db(
     Option<InternalIndexerDB>,
 )> {
     let internal_indexer_db = InternalIndexerDBService::get_indexer_db(node_config);
+    maybe_restore_from_backup(node_config, internal_indexer_db.clone())?;
     let (aptos_db_reader, db_rw, backup_service) =
         match FastSyncStorageWrapper::initialize_dbs(node_config, internal_indexer_db.clone())? {
             Either::Left(db) => {
@@ -87,6 +114,147 @@ pub(crate) fn bootstrap_db(
     Ok((aptos_db_reader, db_rw, backup_service, internal_indexer_db))
 }
 
+/// If the node is bootstrapping from a backup, restores the epoch ending ledger
+/// infos, the latest state snapshot and the subsequent transactions from the backup
+/// storage into the database (before it is opened by the node). State sync will then
+/// catch up to the latest version using peers. Restores are resumable, so an
+/// interrupted restore will continue the next time the node starts.
+#[cfg(not(feature = "consensus-only-perf-test"))]
+fn maybe_restore_from_backup(
+    node_config: &NodeConfig,
+    internal_indexer_db: Option<InternalIndexerDB>,
+) -> Result<()> {
+    // Only restore if the node is configured to do so
+    let bootstrapping_mode = node_config.state_sync.state_sync_driver.bootstrapping_mode;
+    let backup_restore_config = match &node_config.state_sync.backup_restore {
+        Some(backup_restore_config) if bootstrapping_mode.is_restore_from_backup() => {
+            backup_restore_config
+        },
+        _ => return Ok(()),
+    };
+
+    // Skip the restore if it has already completed
+    let restore_marker_file = node_config
+        .storage
+        .dir()
+        .join(RESTORE_FROM_BACKUP_MARKER_FILE);
+    if restore_marker_file.exists() {
+        info!("The database has already been restored from backup. Skipping the restore.");
+        return Ok(());
+    }
+
+    // Restore the database on a dedicated runtime
+    info!(
+        "Restoring the database from backup storage: {:?}",
+        backup_restore_config.backup_storage
+    );
+    let instant = Instant::now();
+    let restore_runtime = aptos_runtimes::spawn_named_runtime("db-restore".into(), None);
+    restore_runtime.block_on(restore_from_backup(
+        node_config,
+        backup_restore_config,
+        internal_indexer_db,
+    ))?;
+
+    // Mark the restore as complete
+    fs::write(&restore_marker_file, b"")?;
+    info!(
+        "Restored the database from backup in {} secs",
+        instant.elapsed().as_secs()
+    );
+
+    Ok(())
+}
+
+/// Runs the backup restore coordinator against the node's database
+#[cfg(not(feature = "consensus-only-perf-test"))]
+async fn restore_from_backup(
+    node_config: &NodeConfig,
+    backup_restore_config: &BackupRestoreConfig,
+    internal_indexer_db: Option<InternalIndexerDB>,
+) -> Result<()> {
+    // Open the backup storage
+    let backup_storage: Arc<dyn BackupStorage> = match &backup_restore_config.backup_storage {
+        BackupStorageLocation::LocalFs { dir } => Arc::new(LocalFs::new(dir.clone())),
+        BackupStorageLocation::CommandAdapter { config_path } => Arc::new(CommandAdapter::new(
+            CommandAdapterConfig::load_from_file(config_path).await?,
+        )),
+    };
+
+    // Trust the genesis waypoint, as well as any additional waypoints
+    let mut trusted_waypoints = backup_restore_config.trusted_waypoints.clone();
+    let genesis_waypoint = get_genesis_waypoint(node_config);
+    if !trusted_waypoints.contains(&genesis_waypoint) {
+        trusted_waypoints.push(genesis_waypoint);
+    }
+    let trusted_waypoints = TrustedWaypointOpt {
+        trust_waypoint: trusted_waypoints,
+    }
+    .verify()?;
+
+    // Open the database for restoring. The database is dropped
+    // (and closed) once the restore has completed.
+    let restore_handler = Arc::new(AptosDB::open_kv_only(
+        node_config.storage.get_dir_paths(),
+        false,                       /* readonly */
+        NO_OP_STORAGE_PRUNER_CONFIG, /* pruner config */
+        node_config.storage.rocksdb_configs,
+        false, /* indexer */
+        node_config.storage.buffered_state_target_items,
+        node_config.storage.max_num_nodes_per_lru_cache_shard,
+        internal_indexer_db,
+    )?)
+    .get_restore_handler();
+
+    // Restore the database
+    let global_restore_options = GlobalRestoreOptions {
+        target_version: backup_restore_config.target_version.unwrap_or(Version::MAX),
+        trusted_waypoints: Arc::new(trusted_waypoints),
+        run_mode: Arc::new(RestoreRunMode::Restore {
+            restore_handler: restore_handler.clone(),
+        }),
+        concurrent_downloads: backup_restore_config
+            .concurrent_downloads
+            .unwrap_or_else(num_cpus::get),
+        replay_concurrency_level: backup_restore_config
+            .replay_concurrency_level
+            .unwrap_or_else(num_cpus::get),
+    };
+    let restore_coordinator_options = RestoreCoordinatorOpt {
+        metadata_cache_opt: MetadataCacheOpt::new(
+            backup_restore_config.metadata_cache_dir.as_ref(),
+        ),
+        replay_all: false,
+        ledger_history_start_version: None,
+        skip_epoch_endings: false,
+    };
+    RestoreCoordinator::new(
+        restore_coordinator_options,
+        global_restore_options,
+        backup_storage,
+    )
+    .run()
+    .await?;
+
+    // Verify the restore reached all trusted waypoints. Waypoints beyond
+    // the restored version are never checked against the backup, so a
+    // backup older than a trusted waypoint is rejected.
+    let next_version = restore_handler.get_next_expected_transaction_version()?;
+    if let Some(waypoint) = backup_restore_config
+        .trusted_waypoints
+        .iter()
+        .find(|waypoint| waypoint.version() >= next_version)
+    {
+        bail!(
+            "The backup is older than the trusted waypoint: {}! Restored up to version: {}",
+            waypoint,
+            next_version.saturating_sub(1)
+        );
+    }
+
+    Ok(())
+}
+
 /// In consensus-only mode, return a in-memory based [FakeAptosDB] and
 /// do not run the backup service.
 #[cfg(feature = "consensus-only-perf-test")]
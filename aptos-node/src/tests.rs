@@ -114,3 +114,228 @@ fn test_create_single_node_test_config() {
             .bootstrapping_mode
     );
 }
+
+#[cfg(not(feature = "consensus-only-perf-test"))]
+mod restore_from_backup_tests {
+    use crate::storage;
+    use aptos_backup_cli::{
+        backup_types::{
+            epoch_ending::backup::{EpochEndingBackupController, EpochEndingBackupOpt},
+            state_snapshot::backup::{StateSnapshotBackupController, StateSnapshotBackupOpt},
+            transaction::backup::{TransactionBackupController, TransactionBackupOpt},
+        },
+        storage::{local_fs::LocalFs, BackupStorage},
+        utils::{
+            backup_service_client::BackupServiceClient, test_utils::start_local_backup_service,
+            GlobalBackupOpt,
+        },
+    };
+    use aptos_config::{
+        config::{
+            BackupRestoreConfig, BackupStorageLocation, BootstrappingMode, NodeConfig,
+            WaypointConfig,
+        },
+        utils::get_available_port,
+    };
+    use aptos_db::AptosDB;
+    use aptos_executor_test_helpers::integration_test_impl::test_execution_with_storage_impl;
+    use aptos_storage_interface::DbReader;
+    use aptos_temppath::TempPath;
+    use aptos_types::{
+        ledger_info::LedgerInfoWithSignatures, transaction::Version, waypoint::Waypoint,
+    };
+    use std::{
+        net::{IpAddr, Ipv4Addr, SocketAddr},
+        path::{Path, PathBuf},
+        sync::Arc,
+        time::Duration,
+    };
+
+    #[test]
+    fn test_bootstrap_db_from_backup() {
+        // Create a database and back it up to the local file system
+        let db = test_execution_with_storage_impl();
+        let latest_version = db.expect_synced_version();
+        let latest_epoch = db.get_latest_epoch_state().unwrap().epoch;
+        let epoch_ending_lis = get_epoch_ending_ledger_infos(&db, latest_epoch);
+        let backup_dir = TempPath::new();
+        backup_dir.create_as_dir().unwrap();
+        backup_db_to_local_fs(db.clone(), backup_dir.path(), latest_epoch, latest_version);
+
+        // Create a node config that restores from the backup (trusting the last epoch ending)
+        let data_dir = TempPath::new();
+        data_dir.create_as_dir().unwrap();
+        let trusted_waypoint = create_waypoint(epoch_ending_lis.last().unwrap());
+        let node_config = create_restore_node_config(
+            data_dir.path(),
+            backup_dir.path(),
+            create_waypoint(&epoch_ending_lis[0]),
+            vec![trusted_waypoint],
+        );
+
+        // Bootstrap the database and verify it was restored from the backup
+        let (db_reader, _, _backup_service, _) = storage::bootstrap_db(&node_config).unwrap();
+        assert_eq!(db_reader.expect_synced_version(), latest_version);
+        assert_eq!(
+            db_reader
+                .get_epoch_ending_ledger_info(trusted_waypoint.version())
+                .unwrap(),
+            *epoch_ending_lis.last().unwrap()
+        );
+        assert!(get_restore_marker_file(&node_config).exists());
+    }
+
+    #[test]
+    fn test_bootstrap_db_from_backup_older_than_waypoint() {
+        // Create a database and back it up to the local file system (excluding the last epoch)
+        let db = test_execution_with_storage_impl();
+        let latest_epoch = db.get_latest_epoch_state().unwrap().epoch;
+        let epoch_ending_lis = get_epoch_ending_ledger_infos(&db, latest_epoch);
+        let backup_end_epoch = latest_epoch - 1;
+        let backup_end_version = epoch_ending_lis[backup_end_epoch as usize - 1]
+            .ledger_info()
+            .version();
+        let backup_dir = TempPath::new();
+        backup_dir.create_as_dir().unwrap();
+        backup_db_to_local_fs(
+            db.clone(),
+            backup_dir.path(),
+            backup_end_epoch,
+            backup_end_version,
+        );
+
+        // Create a node config that trusts a waypoint newer than the backup
+        let data_dir = TempPath::new();
+        data_dir.create_as_dir().unwrap();
+        let node_config = create_restore_node_config(
+            data_dir.path(),
+            backup_dir.path(),
+            create_waypoint(&epoch_ending_lis[0]),
+            vec![create_waypoint(
+                &epoch_ending_lis[backup_end_epoch as usize],
+            )],
+        );
+
+        // Bootstrapping the database should fail and the restore should not be marked as complete
+        let error = storage::bootstrap_db(&node_config).err().unwrap();
+        assert!(error
+            .to_string()
+            .contains("The backup is older than the trusted waypoint"));
+        assert!(!get_restore_marker_file(&node_config).exists());
+    }
+
+    /// Backs up the epoch ending ledger infos (for all epochs before the given end epoch),
+    /// a state snapshot (at the end of the epoch before the end epoch) and all transactions
+    /// (up to and including the given end version) to the given local directory.
+    fn backup_db_to_local_fs(
+        db: Arc<AptosDB>,
+        backup_dir: &Path,
+        end_epoch: u64,
+        end_version: Version,
+    ) {
+        let (runtime, port) = start_local_backup_service(db);
+        let client = Arc::new(BackupServiceClient::new(format!(
+            "http://localhost:{}",
+            port
+        )));
+        let backup_storage: Arc<dyn BackupStorage> =
+            Arc::new(LocalFs::new(backup_dir.to_path_buf()));
+        let global_backup_opt = GlobalBackupOpt {
+            max_chunk_size: 2048,
+            concurrent_data_requests: 2,
+        };
+
+        runtime
+            .block_on(
+                EpochEndingBackupController::new(
+                    EpochEndingBackupOpt {
+                        start_epoch: 0,
+                        end_epoch,
+                    },
+                    global_backup_opt.clone(),
+                    client.clone(),
+                    backup_storage.clone(),
+                )
+                .run(),
+            )
+            .unwrap();
+        runtime
+            .block_on(
+                StateSnapshotBackupController::new(
+                    StateSnapshotBackupOpt {
+                        epoch: end_epoch - 1,
+                    },
+                    global_backup_opt.clone(),
+                    client.clone(),
+                    backup_storage.clone(),
+                )
+                .run(),
+            )
+            .unwrap();
+        runtime
+            .block_on(
+                TransactionBackupController::new(
+                    TransactionBackupOpt {
+                        start_version: 0,
+                        num_transactions: (end_version + 1) as usize,
+                    },
+                    global_backup_opt,
+                    client,
+                    backup_storage,
+                )
+                .run(),
+            )
+            .unwrap();
+
+        runtime.shutdown_timeout(Duration::from_secs(1));
+    }
+
+    /// Creates a node config that restores the database from the given backup directory
+    fn create_restore_node_config(
+        data_dir: &Path,
+        backup_dir: &Path,
+        genesis_waypoint: Waypoint,
+        trusted_waypoints: Vec<Waypoint>,
+    ) -> NodeConfig {
+        let mut node_config = NodeConfig::default();
+        node_config.set_data_dir(data_dir.to_path_buf());
+        node_config.base.waypoint = WaypointConfig::FromConfig(genesis_waypoint);
+        node_config.storage.backup_service_address =
+            SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), get_available_port());
+        node_config.state_sync.state_sync_driver.bootstrapping_mode =
+            BootstrappingMode::RestoreFromBackup;
+        node_config.state_sync.backup_restore = Some(BackupRestoreConfig {
+            backup_storage: BackupStorageLocation::LocalFs {
+                dir: backup_dir.to_path_buf(),
+            },
+            concurrent_downloads: Some(2),
+            replay_concurrency_level: Some(2),
+            trusted_waypoints,
+            ..Default::default()
+        });
+        node_config
+    }
+
+    /// Creates an epoch boundary waypoint for the given epoch ending ledger info
+    fn create_waypoint(ledger_info: &LedgerInfoWithSignatures) -> Waypoint {
+        Waypoint::new_epoch_boundary(ledger_info.ledger_info()).unwrap()
+    }
+
+    /// Returns the epoch ending ledger infos for all epochs before the given end epoch
+    fn get_epoch_ending_ledger_infos(
+        db: &AptosDB,
+        end_epoch: u64,
+    ) -> Vec<LedgerInfoWithSignatures> {
+        db.get_epoch_ending_ledger_infos(0, end_epoch)
+            .unwrap()
+            .ledger_info_with_sigs
+    }
+
+    /// Returns the marker file written once the restore from backup completes
+    fn get_restore_marker_file(node_config: &NodeConfig) -> PathBuf {
+        node_config
+            .storage
+            .dir()
+            .join(storage::RESTORE_FROM_BACKUP_MARKER_FILE)
+    }
+}
@@ -88,6 +88,15 @@ pub struct ConsensusConfig {
     pub proof_cache_capacity: u64,
     pub rand_rb_config: ReliableBroadcastConfig,
     pub num_bounded_executor_tasks: u64,
+    // The policy used by the proposal generator to compute the block targets
+    // (sizes and delay) and to select the payload for a proposed block
+    pub block_building_policy: BlockBuildingPolicyType,
+    // Whether to record the block building snapshots of the proposal generator (for export
+    // via the admin service, and replay by the block building simulator)
+    pub enable_block_building_snapshots: bool,
+    // Whether to record per-block traces through the consensus pipeline (for inspection via
+    // the admin service). Disabled by default, as tracing adds bookkeeping to the hot path.
+    pub enable_block_tracing: bool,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
@@ -104,6 +113,18 @@ impl QcAggregatorType {
     }
 }
 
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum BlockBuildingPolicyType {
+    /// Applies the most restrictive backpressure targets, but never reduces the
+    /// number of pulled transactions below `min_max_txns_in_block_after_filtering_from_backpressure`
+    /// (instead, only the number of transactions to execute is limited).
+    #[default]
+    Default,
+    /// Applies the most restrictive backpressure targets directly to the pulled
+    /// payload (i.e., the block is never larger than the backpressure targets).
+    StrictBackpressure,
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct DelayedQcAggregatorConfig {
     // Maximum Delay for a QC to be aggregated after round start (in milliseconds). This assumes that
@@ -352,6 +373,9 @@ impl Default for ConsensusConfig {
                 rpc_timeout_ms: 10000,
             },
             num_bounded_executor_tasks: 16,
+            block_building_policy: BlockBuildingPolicyType::default(),
+            enable_block_building_snapshots: false,
+            enable_block_tracing: false,
         }
     }
 }
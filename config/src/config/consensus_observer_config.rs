@@ -30,8 +30,16 @@ pub struct ConsensusObserverConfig {
 
     /// Interval (in milliseconds) to garbage collect peer state
     pub garbage_collection_interval_ms: u64,
+    /// Maximum number of concurrent subscriptions (i.e., publishers) for the observer
+    pub max_concurrent_subscriptions: u64,
     /// Maximum number of blocks to keep in memory (e.g., pending blocks, ordered blocks, etc.)
     pub max_num_pending_blocks: u64,
+    /// Maximum time (in milliseconds) a subscription can remain in an older epoch
+    /// than the most up-to-date subscription before it is considered stalled.
+    pub max_subscription_epoch_lag_ms: u64,
+    /// Maximum number of rounds a subscription can lag behind the most
+    /// up-to-date subscription before it is considered stalled.
+    pub max_subscription_round_lag: u64,
     /// Maximum timeout (in milliseconds) for active subscriptions
     pub max_subscription_timeout_ms: u64,
     /// Maximum timeout (in milliseconds) we'll wait for the synced version to
@@ -52,7 +60,10 @@ impl Default for ConsensusObserverConfig {
             max_parallel_serialization_tasks: num_cpus::get(), // Default to the number of CPUs
             network_request_timeout_ms: 10_000,                // 10 seconds
             garbage_collection_interval_ms: 60_000,            // 60 seconds
+            max_concurrent_subscriptions: 2,                   // 2 publishers
             max_num_pending_blocks: 100,                       // 100 blocks
+            max_subscription_epoch_lag_ms: 10_000,             // 10 seconds
+            max_subscription_round_lag: 20,                    // 20 rounds
             max_subscription_timeout_ms: 30_000,               // 30 seconds
             max_synced_version_timeout_ms: 60_000,             // 60 seconds
             peer_optimality_check_interval_ms: 60_000,         // 60 seconds
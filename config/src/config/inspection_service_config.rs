@@ -19,6 +19,7 @@ pub struct InspectionServiceConfig {
     pub port: u16,
     pub expose_configuration: bool,
     pub expose_peer_information: bool,
+    pub expose_state_sync_progress: bool,
     pub expose_system_information: bool,
 }
 
@@ -29,6 +30,7 @@ impl Default for InspectionServiceConfig {
             port: 9101,
             expose_configuration: false,
             expose_peer_information: true,
+            expose_state_sync_progress: true,
             expose_system_information: true,
         }
     }
@@ -90,6 +92,11 @@ impl ConfigOptimizer for InspectionServiceConfig {
                     modified_config = true;
                 }
 
+                if local_inspection_config_yaml["expose_state_sync_progress"].is_null() {
+                    inspection_service_config.expose_state_sync_progress = true;
+                    modified_config = true;
+                }
+
                 if local_inspection_config_yaml["expose_system_information"].is_null() {
                     inspection_service_config.expose_system_information = true;
                     modified_config = true;
@@ -112,6 +119,7 @@ mod tests {
             inspection_service: InspectionServiceConfig {
                 expose_configuration: false,
                 expose_peer_information: false,
+                expose_state_sync_progress: false,
                 expose_system_information: false,
                 ..Default::default()
             },
@@ -131,6 +139,7 @@ mod tests {
         // Verify all endpoints are still disabled
         assert!(!node_config.inspection_service.expose_configuration);
         assert!(!node_config.inspection_service.expose_peer_information);
+        assert!(!node_config.inspection_service.expose_state_sync_progress);
         assert!(!node_config.inspection_service.expose_system_information);
     }
 
@@ -141,6 +150,7 @@ mod tests {
             inspection_service: InspectionServiceConfig {
                 expose_configuration: false,
                 expose_peer_information: false,
+                expose_state_sync_progress: false,
                 expose_system_information: false,
                 ..Default::default()
             },
@@ -160,6 +170,7 @@ mod tests {
         // Verify all endpoints are now enabled
         assert!(node_config.inspection_service.expose_configuration);
         assert!(node_config.inspection_service.expose_peer_information);
+        assert!(node_config.inspection_service.expose_state_sync_progress);
         assert!(node_config.inspection_service.expose_system_information);
     }
 
@@ -170,6 +181,7 @@ mod tests {
             inspection_service: InspectionServiceConfig {
                 expose_configuration: false,
                 expose_peer_information: false,
+                expose_state_sync_progress: false,
                 expose_system_information: false,
                 ..Default::default()
             },
@@ -198,6 +210,7 @@ mod tests {
         // Verify only the system information endpoint is now enabled
         assert!(!node_config.inspection_service.expose_configuration);
         assert!(node_config.inspection_service.expose_peer_information);
+        assert!(node_config.inspection_service.expose_state_sync_progress);
         assert!(node_config.inspection_service.expose_system_information);
     }
 
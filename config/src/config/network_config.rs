@@ -124,6 +124,8 @@ pub struct NetworkConfig {
     pub max_parallel_deserialization_tasks: Option<usize>,
     /// Whether or not to enable latency aware peer dialing
     pub enable_latency_aware_dialing: bool,
+    /// Application message capture configuration, if not specified, no messages are captured
+    pub message_capture_config: Option<MessageCaptureConfig>,
 }
 
 impl Default for NetworkConfig {
@@ -165,6 +167,7 @@ impl NetworkConfig {
             outbound_tx_buffer_size_bytes: None,
             max_parallel_deserialization_tasks: None,
             enable_latency_aware_dialing: true,
+            message_capture_config: None,
         };
 
         // Configure the number of parallel deserialization tasks
@@ -388,6 +391,32 @@ impl Default for RateLimitConfig {
     }
 }
 
+/// Configuration for capturing the application messages exchanged with peers.
+/// Captured messages are written to a set of rotating files on local disk.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MessageCaptureConfig {
+    /// Directory in which the capture files are written
+    pub capture_dir: PathBuf,
+    /// Maximum size of a single capture file before it is rotated
+    pub max_file_size_bytes: u64,
+    /// Maximum number of capture files to keep (the oldest are deleted first)
+    pub max_num_files: usize,
+    /// Maximum number of messages pending a write before new messages are dropped
+    pub max_pending_messages: usize,
+}
+
+impl Default for MessageCaptureConfig {
+    fn default() -> Self {
+        Self {
+            capture_dir: PathBuf::from("/tmp/aptos_network_capture"),
+            max_file_size_bytes: 256 * 1024 * 1024, // 256 MiB
+            max_num_files: 8,
+            max_pending_messages: 10_000,
+        }
+    }
+}
+
 pub type PeerSet = HashMap<PeerId, Peer>;
 
 // TODO: Combine with RoleType?
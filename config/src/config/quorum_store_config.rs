@@ -97,6 +97,9 @@ pub struct QuorumStoreConfig {
     pub batch_buckets: Vec<u64>,
     pub allow_batches_without_pos_in_proposal: bool,
     pub enable_opt_quorum_store: bool,
+    /// Whether to track the lifecycles of batches (for inspection via the admin service).
+    /// Note: tracking adds bookkeeping to the quorum store hot path, so it is disabled by default.
+    pub enable_batch_lifecycle_tracking: bool,
 }
 
 impl Default for QuorumStoreConfig {
@@ -136,6 +139,7 @@ impl Default for QuorumStoreConfig {
             batch_buckets: DEFAULT_BUCKETS.to_vec(),
             allow_batches_without_pos_in_proposal: true,
             enable_opt_quorum_store: false,
+            enable_batch_lifecycle_tracking: false,
         }
     }
 }
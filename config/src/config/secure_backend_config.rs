@@ -3,7 +3,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::config::Error;
-use aptos_secure_storage::{InMemoryStorage, Namespaced, OnDiskStorage, Storage, VaultStorage};
+use aptos_secure_storage::{
+    EncryptedOnDiskStorage, InMemoryStorage, Namespaced, OnDiskStorage, Storage, VaultStorage,
+    WrappingKey,
+};
 use serde::{Deserialize, Serialize};
 use std::{
     fs::File,
@@ -14,21 +17,27 @@ use std::{
 // The default file name for the secure storage file
 pub const SECURE_STORAGE_FILENAME: &str = "secure_storage.json";
 
+// The default file name for the encrypted secure storage file
+pub const ENCRYPTED_SECURE_STORAGE_FILENAME: &str = "secure_storage.encrypted.json";
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "snake_case", tag = "type")]
 pub enum SecureBackend {
     InMemoryStorage,
     Vault(VaultConfig),
     OnDiskStorage(OnDiskStorageConfig),
+    EncryptedOnDiskStorage(EncryptedOnDiskStorageConfig),
 }
 
 impl SecureBackend {
     pub fn namespace(&self) -> Option<&str> {
         match self {
             SecureBackend::Vault(VaultConfig { namespace, .. })
-            | SecureBackend::OnDiskStorage(OnDiskStorageConfig { namespace, .. }) => {
-                namespace.as_deref()
-            },
+            | SecureBackend::OnDiskStorage(OnDiskStorageConfig { namespace, .. })
+            | SecureBackend::EncryptedOnDiskStorage(EncryptedOnDiskStorageConfig {
+                namespace,
+                ..
+            }) => namespace.as_deref(),
             SecureBackend::InMemoryStorage => None,
         }
     }
@@ -36,7 +45,11 @@ impl SecureBackend {
     pub fn clear_namespace(&mut self) {
         match self {
             SecureBackend::Vault(VaultConfig { namespace, .. })
-            | SecureBackend::OnDiskStorage(OnDiskStorageConfig { namespace, .. }) => {
+            | SecureBackend::OnDiskStorage(OnDiskStorageConfig { namespace, .. })
+            | SecureBackend::EncryptedOnDiskStorage(EncryptedOnDiskStorageConfig {
+                namespace,
+                ..
+            }) => {
                 *namespace = None;
             },
             SecureBackend::InMemoryStorage => {},
@@ -97,6 +110,94 @@ pub struct OnDiskStorageConfig {
     data_dir: PathBuf,
 }
 
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct EncryptedOnDiskStorageConfig {
+    // Required path for the encrypted on disk storage
+    pub path: PathBuf,
+    /// A namespace is an optional portion of the path to a key stored within the storage. For
+    /// example, a key, S, without a namespace would be available in S, with a namespace, N, it
+    /// would be in N/S.
+    pub namespace: Option<String>,
+    /// The source of the key used to wrap (i.e., encrypt) the storage data key
+    pub wrapping_key: WrappingKeySource,
+    /// The source of the previous wrapping key. To rotate the wrapping key, move the
+    /// current source here and set a new wrapping key. The storage is rotated to the
+    /// new wrapping key when it is next opened.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_wrapping_key: Option<WrappingKeySource>,
+    #[serde(skip)]
+    data_dir: PathBuf,
+}
+
+impl EncryptedOnDiskStorageConfig {
+    pub fn new(wrapping_key: WrappingKeySource) -> Self {
+        Self {
+            namespace: None,
+            path: PathBuf::from(ENCRYPTED_SECURE_STORAGE_FILENAME),
+            wrapping_key,
+            previous_wrapping_key: None,
+            data_dir: PathBuf::from("/opt/aptos/data"),
+        }
+    }
+
+    pub fn path(&self) -> PathBuf {
+        if self.path.is_relative() {
+            self.data_dir.join(&self.path)
+        } else {
+            self.path.clone()
+        }
+    }
+
+    pub fn set_data_dir(&mut self, data_dir: PathBuf) {
+        self.data_dir = data_dir;
+    }
+}
+
+/// The wrapping key of the encrypted on disk storage can either be derived from a
+/// passphrase (stored on disk or in an environment variable), or read from a key file.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WrappingKeySource {
+    /// A file containing the passphrase. This is an absolute path and not relative to data_dir
+    PassphraseFromDisk(PathBuf),
+    /// The name of an environment variable containing the passphrase
+    PassphraseFromEnv(String),
+    /// A file containing a hex encoded 32 byte key. This is an absolute path and not
+    /// relative to data_dir
+    KeyFromDisk(PathBuf),
+}
+
+impl WrappingKeySource {
+    pub fn read_wrapping_key(&self) -> Result<WrappingKey, Error> {
+        match self {
+            WrappingKeySource::PassphraseFromDisk(path) => {
+                let passphrase = read_file(path)?;
+                Ok(WrappingKey::Passphrase(
+                    passphrase.trim_end_matches(['\n', '\r']).to_string(),
+                ))
+            },
+            WrappingKeySource::PassphraseFromEnv(variable) => std::env::var(variable)
+                .map(WrappingKey::Passphrase)
+                .map_err(|error| {
+                    Error::Unexpected(format!(
+                        "Unable to read the passphrase from the environment variable {}: {}",
+                        variable, error
+                    ))
+                }),
+            WrappingKeySource::KeyFromDisk(path) => {
+                let key = hex::decode(read_file(path)?.trim()).map_err(|error| {
+                    Error::Unexpected(format!(
+                        "Unable to decode the hex encoded key in {:?}: {}",
+                        path, error
+                    ))
+                })?;
+                Ok(WrappingKey::Key(key))
+            },
+        }
+    }
+}
+
 /// Tokens can either be directly within this config or stored somewhere on disk.
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -172,6 +273,32 @@ impl From<&SecureBackend> for Storage {
                     storage
                 }
             },
+            SecureBackend::EncryptedOnDiskStorage(config) => {
+                let wrapping_key = config
+                    .wrapping_key
+                    .read_wrapping_key()
+                    .expect("Unable to read wrapping key");
+                let storage = match &config.previous_wrapping_key {
+                    Some(previous_wrapping_key) => {
+                        let previous_wrapping_key = previous_wrapping_key
+                            .read_wrapping_key()
+                            .expect("Unable to read previous wrapping key");
+                        EncryptedOnDiskStorage::new_with_previous_wrapping_key(
+                            config.path(),
+                            wrapping_key,
+                            previous_wrapping_key,
+                        )
+                    },
+                    None => EncryptedOnDiskStorage::new(config.path(), wrapping_key),
+                };
+                let storage =
+                    Storage::from(storage.expect("Unable to open encrypted on disk storage"));
+                if let Some(namespace) = &config.namespace {
+                    Storage::from(Namespaced::new(namespace, Box::new(storage)))
+                } else {
+                    storage
+                }
+            },
             SecureBackend::Vault(config) => {
                 let storage = Storage::from(VaultStorage::new(
                     config.server.clone(),
@@ -197,6 +324,7 @@ impl From<&SecureBackend> for Storage {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aptos_secure_storage::KVStorage;
     use std::io::Write;
 
     #[derive(Debug, Deserialize, PartialEq, Eq, Serialize)]
@@ -303,4 +431,95 @@ vault:
         let config = Token::FromConfig("config_token".to_string());
         assert_eq!("config_token", config.read_token().unwrap());
     }
+
+    #[test]
+    fn test_encrypted_on_disk_storage_parsing() {
+        let text_from_config = r#"
+type: "encrypted_on_disk_storage"
+path: "secure_storage.encrypted.json"
+namespace: ~
+wrapping_key:
+    passphrase_from_env: "APTOS_SECURE_STORAGE_PASSPHRASE"
+        "#;
+
+        let backend: SecureBackend = serde_yaml::from_str(text_from_config).unwrap();
+        assert_eq!(
+            backend,
+            SecureBackend::EncryptedOnDiskStorage(EncryptedOnDiskStorageConfig::new(
+                WrappingKeySource::PassphraseFromEnv("APTOS_SECURE_STORAGE_PASSPHRASE".into())
+            ))
+        );
+        // Just assert that it can be serialized, no need to do string comparison
+        serde_yaml::to_string(&backend).unwrap();
+
+        let text_with_previous_wrapping_key = r#"
+type: "encrypted_on_disk_storage"
+path: "secure_storage.encrypted.json"
+namespace: ~
+wrapping_key:
+    key_from_disk: "/new_key"
+previous_wrapping_key:
+    key_from_disk: "/old_key"
+        "#;
+
+        let backend: SecureBackend = serde_yaml::from_str(text_with_previous_wrapping_key).unwrap();
+        let mut config =
+            EncryptedOnDiskStorageConfig::new(WrappingKeySource::KeyFromDisk("/new_key".into()));
+        config.previous_wrapping_key = Some(WrappingKeySource::KeyFromDisk("/old_key".into()));
+        assert_eq!(backend, SecureBackend::EncryptedOnDiskStorage(config));
+    }
+
+    #[test]
+    fn test_encrypted_on_disk_storage_rotation() {
+        // Write the old and new wrapping keys to disk
+        let old_key_path = aptos_temppath::TempPath::new();
+        std::fs::write(old_key_path.path(), hex::encode([1u8; 32])).unwrap();
+        let new_key_path = aptos_temppath::TempPath::new();
+        std::fs::write(new_key_path.path(), hex::encode([2u8; 32])).unwrap();
+        let storage_path = aptos_temppath::TempPath::new();
+
+        // Create the storage with the old wrapping key
+        let mut config = EncryptedOnDiskStorageConfig::new(WrappingKeySource::KeyFromDisk(
+            old_key_path.path().to_path_buf(),
+        ));
+        config.path = storage_path.path().to_path_buf();
+        let mut storage = Storage::from(&SecureBackend::EncryptedOnDiskStorage(config.clone()));
+        storage.set("key", "value").unwrap();
+
+        // Rotate the storage to the new wrapping key (by moving the old key to the previous key)
+        config.previous_wrapping_key = Some(config.wrapping_key.clone());
+        config.wrapping_key = WrappingKeySource::KeyFromDisk(new_key_path.path().to_path_buf());
+        let storage = Storage::from(&SecureBackend::EncryptedOnDiskStorage(config.clone()));
+        assert_eq!(storage.get::<String>("key").unwrap().value, "value");
+
+        // Verify the storage can now be opened without the previous wrapping key
+        config.previous_wrapping_key = None;
+        let storage = Storage::from(&SecureBackend::EncryptedOnDiskStorage(config));
+        assert_eq!(storage.get::<String>("key").unwrap().value, "value");
+    }
+
+    #[test]
+    fn test_wrapping_key_reading() {
+        let temppath = aptos_temppath::TempPath::new();
+        temppath.create_as_file().unwrap();
+        let mut file = File::create(temppath.path()).unwrap();
+        file.write_all(b"passphrase\n").unwrap();
+        let passphrase = WrappingKeySource::PassphraseFromDisk(temppath.path().to_path_buf());
+        assert_eq!(
+            passphrase.read_wrapping_key().unwrap(),
+            WrappingKey::Passphrase("passphrase".into())
+        );
+
+        let mut file = File::create(temppath.path()).unwrap();
+        file.write_all(hex::encode([7u8; 32]).as_bytes()).unwrap();
+        let key = WrappingKeySource::KeyFromDisk(temppath.path().to_path_buf());
+        assert_eq!(
+            key.read_wrapping_key().unwrap(),
+            WrappingKey::Key(vec![7u8; 32])
+        );
+
+        let mut file = File::create(temppath.path()).unwrap();
+        file.write_all(b"not hex").unwrap();
+        assert!(key.read_wrapping_key().is_err());
+    }
 }
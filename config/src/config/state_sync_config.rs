@@ -6,9 +6,10 @@ use crate::config::{
     config_optimizer::ConfigOptimizer, config_sanitizer::ConfigSanitizer,
     node_config_loader::NodeType, Error, NodeConfig,
 };
-use aptos_types::chain_id::ChainId;
+use aptos_types::{chain_id::ChainId, transaction::Version, waypoint::Waypoint};
 use serde::{Deserialize, Serialize};
 use serde_yaml::Value;
+use std::path::PathBuf;
 
 // The maximum message size per state sync message
 const MAX_MESSAGE_SIZE: usize = 8 * 1024 * 1024; /* 8 MiB */
@@ -23,13 +24,15 @@ const MAX_TRANSACTION_OUTPUT_CHUNK_SIZE: u64 = 3000;
 const MAX_CONCURRENT_REQUESTS: u64 = 6;
 const MAX_CONCURRENT_STATE_REQUESTS: u64 = 6;
 
-#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct StateSyncConfig {
     pub data_streaming_service: DataStreamingServiceConfig,
     pub aptos_data_client: AptosDataClientConfig,
     pub state_sync_driver: StateSyncDriverConfig,
     pub storage_service: StorageServiceConfig,
+    /// The backup to restore from (only used by the `RestoreFromBackup` bootstrapping mode)
+    pub backup_restore: Option<BackupRestoreConfig>,
 }
 
 /// The bootstrapping mode determines how the node will bootstrap to the latest
@@ -44,6 +47,14 @@ pub enum BootstrappingMode {
     ExecuteTransactionsFromGenesis,
     /// Executes transactions or applies outputs from genesis (whichever is faster)
     ExecuteOrApplyFromGenesis,
+    /// Restores the epoch ending ledger infos, the latest state snapshot and the
+    /// subsequent transactions from a backup storage location (see `BackupRestoreConfig`),
+    /// and then applies transaction outputs (from peers) to catch up to the latest version
+    RestoreFromBackup,
+    /// Only syncs and verifies the epoch ending ledger infos and the latest ledger info
+    /// (without executing or storing any transactions or states). State values are then
+    /// fetched (and verified) from peers on demand. Note: this is only supported by fullnodes.
+    LightClient,
 }
 
 impl BootstrappingMode {
@@ -57,6 +68,8 @@ impl BootstrappingMode {
                 "execute_transactions_from_genesis"
             },
             BootstrappingMode::ExecuteOrApplyFromGenesis => "execute_or_apply_from_genesis",
+            BootstrappingMode::RestoreFromBackup => "restore_from_backup",
+            BootstrappingMode::LightClient => "light_client",
         }
     }
 
@@ -64,6 +77,59 @@ impl BootstrappingMode {
     pub fn is_fast_sync(&self) -> bool {
         *self == BootstrappingMode::DownloadLatestStates
     }
+
+    /// Returns true iff the bootstrapping mode restores from a backup
+    pub fn is_restore_from_backup(&self) -> bool {
+        *self == BootstrappingMode::RestoreFromBackup
+    }
+
+    /// Returns true iff the bootstrapping mode is the light client mode
+    pub fn is_light_client(&self) -> bool {
+        *self == BootstrappingMode::LightClient
+    }
+}
+
+/// The location of the backups to restore from
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum BackupStorageLocation {
+    /// A backup stored in a local (or mounted) directory
+    LocalFs { dir: PathBuf },
+    /// A backup stored in a remote location (e.g., an object store), accessed
+    /// via the command adapter config file at the given path
+    CommandAdapter { config_path: PathBuf },
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BackupRestoreConfig {
+    /// The location of the backups
+    pub backup_storage: BackupStorageLocation,
+    /// The number of concurrent downloads from the backup storage (defaults to the number of CPUs)
+    pub concurrent_downloads: Option<usize>,
+    /// The directory to cache the backup metadata files (defaults to a temporary directory)
+    pub metadata_cache_dir: Option<PathBuf>,
+    /// The number of threads used to replay transactions (defaults to the number of CPUs)
+    pub replay_concurrency_level: Option<usize>,
+    /// The version to restore up to (defaults to the latest version in the backups)
+    pub target_version: Option<Version>,
+    /// Additional waypoints to trust when restoring (the genesis waypoint is always trusted)
+    pub trusted_waypoints: Vec<Waypoint>,
+}
+
+impl Default for BackupRestoreConfig {
+    fn default() -> Self {
+        Self {
+            backup_storage: BackupStorageLocation::LocalFs {
+                dir: PathBuf::from("/opt/aptos/backup"),
+            },
+            concurrent_downloads: None,
+            metadata_cache_dir: None,
+            replay_concurrency_level: None,
+            target_version: None,
+            trusted_waypoints: vec![],
+        }
+    }
 }
 
 /// The continuous syncing mode determines how the node will stay up-to-date
@@ -389,6 +455,44 @@ impl Default for AptosLatencyFilteringConfig {
     }
 }
 
+/// The strategy used by the data client to select peers for specific data requests
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub enum PeerSelectionStrategy {
+    /// Selects peers randomly, weighted by their ping latencies
+    Latency,
+    /// Selects peers randomly, weighted by their measured throughput and
+    /// failure history (for each request type). A percentage of requests
+    /// are still sent to random peers to explore and discover faster peers.
+    AdaptiveThroughput,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AptosPeerSelectionConfig {
+    /// The percentage of requests (0-100) sent to random peers (to explore
+    /// peers that have not yet been measured or that may have improved).
+    pub exploration_percentage: u64,
+    /// The minimum number of measurements required before a peer's
+    /// throughput is trusted (otherwise, the peer is given an average weight)
+    pub min_samples_for_throughput: u64,
+    /// The weight (percentage, 0-100) given to each new measurement when
+    /// updating the moving averages of throughput and failure rates.
+    pub moving_average_weight_percentage: u64,
+    /// The strategy used to select peers for specific data requests
+    pub peer_selection_strategy: PeerSelectionStrategy,
+}
+
+impl Default for AptosPeerSelectionConfig {
+    fn default() -> Self {
+        Self {
+            exploration_percentage: 10, // Explore with 10% of requests
+            min_samples_for_throughput: 3,
+            moving_average_weight_percentage: 20, // Each new measurement has a 20% weight
+            peer_selection_strategy: PeerSelectionStrategy::Latency,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct AptosDataClientConfig {
@@ -420,6 +524,8 @@ pub struct AptosDataClientConfig {
     pub max_transaction_output_chunk_size: u64,
     /// Timeout (in ms) when waiting for an optimistic fetch response
     pub optimistic_fetch_timeout_ms: u64,
+    /// The peer selection config for the data client
+    pub peer_selection_config: AptosPeerSelectionConfig,
     /// First timeout (in ms) when waiting for a response
     pub response_timeout_ms: u64,
     /// Timeout (in ms) when waiting for a subscription response
@@ -444,7 +550,8 @@ impl Default for AptosDataClientConfig {
             max_subscription_lag_secs: 20, // 20 seconds
             max_transaction_chunk_size: MAX_TRANSACTION_CHUNK_SIZE,
             max_transaction_output_chunk_size: MAX_TRANSACTION_OUTPUT_CHUNK_SIZE,
-            optimistic_fetch_timeout_ms: 5000,        // 5 seconds
+            optimistic_fetch_timeout_ms: 5000, // 5 seconds
+            peer_selection_config: AptosPeerSelectionConfig::default(),
             response_timeout_ms: 10_000,              // 10 seconds
             subscription_response_timeout_ms: 15_000, // 15 seconds (longer than a regular timeout because of prefetching)
             use_compression: true,
@@ -466,7 +573,7 @@ impl ConfigSanitizer for StateSyncConfig {
 impl ConfigSanitizer for StateSyncDriverConfig {
     fn sanitize(
         node_config: &NodeConfig,
-        _node_type: NodeType,
+        node_type: NodeType,
         _chain_id: Option<ChainId>,
     ) -> Result<(), Error> {
         let sanitizer_name = Self::get_sanitizer_name();
@@ -483,6 +590,45 @@ impl ConfigSanitizer for StateSyncDriverConfig {
             ));
         }
 
+        // Verify that a backup is specified for nodes restoring from a backup
+        if state_sync_driver_config
+            .bootstrapping_mode
+            .is_restore_from_backup()
+            && node_config.state_sync.backup_restore.is_none()
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "The backup restore config must be set for nodes that are restoring from a backup!"
+                    .to_string(),
+            ));
+        }
+
+        // Verify that validators are not running in light client mode
+        if state_sync_driver_config
+            .bootstrapping_mode
+            .is_light_client()
+            && node_type.is_validator()
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "Validators cannot run in light client mode!".to_string(),
+            ));
+        }
+
+        // Verify that the REST API is disabled in light client mode. The API is
+        // served from the local storage, which is never synced by the light client.
+        if state_sync_driver_config
+            .bootstrapping_mode
+            .is_light_client()
+            && node_config.api.enabled
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "The REST API must be disabled in light client mode (the local storage is not synced)! \
+                Use the inspection service light client endpoint instead.".to_string(),
+            ));
+        }
+
         Ok(())
     }
 }
@@ -570,6 +716,7 @@ impl ConfigOptimizer for DataStreamingServiceConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::ApiConfig;
 
     #[test]
     fn test_optimize_bootstrapping_mode_devnet_vfn() {
@@ -788,6 +935,94 @@ mod tests {
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
 
+    #[test]
+    fn test_sanitize_restore_from_backup_missing_config() {
+        // Create a node config that restores from a backup,
+        // but doesn't specify the backup location.
+        let mut node_config = NodeConfig {
+            state_sync: StateSyncConfig {
+                state_sync_driver: StateSyncDriverConfig {
+                    bootstrapping_mode: BootstrappingMode::RestoreFromBackup,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that sanitization fails
+        let error =
+            StateSyncConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::testnet()))
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Add the backup restore config and verify that sanitization passes
+        node_config.state_sync.backup_restore = Some(BackupRestoreConfig::default());
+        StateSyncConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::testnet()))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_sanitize_light_client_validator() {
+        // Create a node config that runs in light client mode (without the API)
+        let node_config = NodeConfig {
+            api: ApiConfig {
+                enabled: false,
+                ..Default::default()
+            },
+            state_sync: StateSyncConfig {
+                state_sync_driver: StateSyncDriverConfig {
+                    bootstrapping_mode: BootstrappingMode::LightClient,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that sanitization fails for validators
+        let error =
+            StateSyncConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::testnet()))
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Verify that sanitization passes for fullnodes
+        StateSyncConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::testnet()),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_sanitize_light_client_api_enabled() {
+        // Create a fullnode config that runs in light client mode with the API enabled
+        let node_config = NodeConfig {
+            api: ApiConfig {
+                enabled: true,
+                ..Default::default()
+            },
+            state_sync: StateSyncConfig {
+                state_sync_driver: StateSyncDriverConfig {
+                    bootstrapping_mode: BootstrappingMode::LightClient,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that sanitization fails
+        let error = StateSyncConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::testnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
     /// Creates and returns a node config with the syncing modes set to execution
     fn create_execution_mode_config() -> NodeConfig {
         NodeConfig {
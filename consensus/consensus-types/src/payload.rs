@@ -253,6 +253,22 @@ impl OptQuorumStorePayloadV1 {
             .collect()
     }
 
+    pub fn into_parts(
+        self,
+    ) -> (
+        InlineBatches,
+        BatchPointer<BatchInfo>,
+        BatchPointer<ProofOfStore>,
+        PayloadExecutionLimit,
+    ) {
+        (
+            self.inline_batches,
+            self.opt_batches,
+            self.proofs,
+            self.execution_limits,
+        )
+    }
+
     pub fn max_txns_to_execute(&self) -> Option<u64> {
         match self.execution_limits {
             PayloadExecutionLimit::None => None,
@@ -27,6 +27,8 @@ pub enum Error {
     InvalidLedgerInfo,
     #[error("Invalid proposal: {0}")]
     InvalidProposal(String),
+    #[error("Invalid safety data import: {0}")]
+    InvalidSafetyDataImport(String),
     #[error("Invalid QC: {0}")]
     InvalidQuorumCertificate(String),
     #[error("{0} is not set, SafetyRules is not initialized")]
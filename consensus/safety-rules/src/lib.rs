@@ -12,6 +12,7 @@ mod logging;
 mod persistent_safety_storage;
 mod process;
 mod remote_service;
+pub mod safety_data_export;
 mod safety_rules;
 mod safety_rules_2chain;
 pub mod safety_rules_manager;
@@ -5,10 +5,14 @@
 use crate::{
     counters,
     logging::{self, LogEntry, LogEvent},
+    safety_data_export::{
+        check_safety_data_import, SafetyDataExport, SignedSafetyDataExport,
+        SAFETY_DATA_EXPORT_VERSION,
+    },
     Error,
 };
 use aptos_consensus_types::{common::Author, safety_data::SafetyData};
-use aptos_crypto::{bls12381, PrivateKey};
+use aptos_crypto::{bls12381, PrivateKey, SigningKey};
 use aptos_global_constants::{CONSENSUS_KEY, OWNER_ACCOUNT, SAFETY_DATA, WAYPOINT};
 use aptos_logger::prelude::*;
 use aptos_secure_storage::{KVStorage, Storage};
@@ -149,6 +153,66 @@ impl PersistentSafetyStorage {
         }
     }
 
+    /// Exports the safety data, signed by the consensus key, so that it can
+    /// be imported on a new host (see `import_safety_data`).
+    pub fn export_safety_data(&mut self) -> Result<SignedSafetyDataExport, Error> {
+        let export = SafetyDataExport {
+            version: SAFETY_DATA_EXPORT_VERSION,
+            author: self.author()?,
+            safety_data: self.safety_data()?,
+            timestamp_usecs: aptos_infallible::duration_since_epoch().as_micros() as u64,
+        };
+
+        let _timer = counters::start_timer("get", CONSENSUS_KEY);
+        let consensus_key: bls12381::PrivateKey =
+            self.internal_store.get(CONSENSUS_KEY).map(|v| v.value)?;
+        let signature = consensus_key
+            .sign(&export)
+            .map_err(|error| Error::SerializationError(error.to_string()))?;
+
+        Ok(SignedSafetyDataExport {
+            export,
+            public_key: consensus_key.public_key(),
+            signature,
+        })
+    }
+
+    /// Imports the safety data exported by `export_safety_data`. The export must be
+    /// signed by the consensus key of this validator, and the import is refused if it
+    /// would lower any of the existing safety watermarks.
+    pub fn import_safety_data(
+        &mut self,
+        signed_export: &SignedSafetyDataExport,
+    ) -> Result<(), Error> {
+        signed_export.verify()?;
+
+        let author = self.author()?;
+        if signed_export.export.author != author {
+            return Err(Error::InvalidSafetyDataImport(format!(
+                "Exported author {} does not match the local author {}",
+                signed_export.export.author, author
+            )));
+        }
+        self.consensus_key_for_version(signed_export.public_key.clone())
+            .map_err(|_| {
+                Error::InvalidSafetyDataImport(
+                    "Export was not signed by the local consensus key".into(),
+                )
+            })?;
+
+        let imported_safety_data = signed_export.export.safety_data.clone();
+        check_safety_data_import(&self.safety_data()?, &imported_safety_data)?;
+        info!(
+            logging::SafetyLogSchema::new(LogEntry::State, LogEvent::Update)
+                .author(author)
+                .epoch(imported_safety_data.epoch)
+                .last_voted_round(imported_safety_data.last_voted_round)
+                .preferred_round(imported_safety_data.preferred_round)
+                .highest_timeout_round(imported_safety_data.highest_timeout_round)
+        );
+        self.set_safety_data(imported_safety_data)
+    }
+
     pub fn waypoint(&self) -> Result<Waypoint, Error> {
         let _timer = counters::start_timer("get", WAYPOINT);
         Ok(self.internal_store.get(WAYPOINT).map(|v| v.value)?)
@@ -202,6 +266,100 @@ mod tests {
         }
     }
 
+    fn create_safety_storage(author: Author, validator_index: u8) -> PersistentSafetyStorage {
+        let consensus_private_key = ValidatorSigner::from_int(validator_index)
+            .private_key()
+            .clone();
+        PersistentSafetyStorage::initialize(
+            Storage::from(InMemoryStorage::new()),
+            author,
+            consensus_private_key,
+            Waypoint::default(),
+            true,
+        )
+    }
+
+    #[test]
+    fn test_export_import_safety_data() {
+        // Export the safety data from the old host
+        let author = Author::random();
+        let mut old_storage = create_safety_storage(author, 0);
+        let safety_data = SafetyData::new(5, 10, 8, 9, None, 7);
+        old_storage.set_safety_data(safety_data.clone()).unwrap();
+        let signed_export = old_storage.export_safety_data().unwrap();
+        assert_eq!(signed_export.export.author, author);
+        assert_eq!(signed_export.export.safety_data, safety_data);
+
+        // Import the safety data on the new host
+        let mut new_storage = create_safety_storage(author, 0);
+        new_storage.import_safety_data(&signed_export).unwrap();
+        assert_eq!(new_storage.safety_data().unwrap(), safety_data);
+
+        // Re-importing the same safety data is allowed
+        new_storage.import_safety_data(&signed_export).unwrap();
+
+        // Verify that tampered exports are rejected
+        let mut tampered_export = signed_export.clone();
+        tampered_export.export.safety_data.last_voted_round = 100;
+        assert!(matches!(
+            new_storage.import_safety_data(&tampered_export),
+            Err(Error::InvalidSafetyDataImport(_))
+        ));
+
+        // Verify that unsupported versions are rejected
+        let mut versioned_export = signed_export.clone();
+        versioned_export.export.version = SAFETY_DATA_EXPORT_VERSION + 1;
+        assert!(new_storage.import_safety_data(&versioned_export).is_err());
+
+        // Verify that exports from other validators are rejected
+        let mut other_author_storage = create_safety_storage(Author::random(), 0);
+        assert!(other_author_storage
+            .import_safety_data(&signed_export)
+            .is_err());
+        let mut other_key_storage = create_safety_storage(author, 1);
+        assert!(other_key_storage
+            .import_safety_data(&signed_export)
+            .is_err());
+        assert_eq!(new_storage.safety_data().unwrap(), safety_data);
+    }
+
+    #[test]
+    fn test_import_refuses_to_lower_watermarks() {
+        let author = Author::random();
+        let mut old_storage = create_safety_storage(author, 0);
+        let mut new_storage = create_safety_storage(author, 0);
+        let current_safety_data = SafetyData::new(5, 10, 8, 9, None, 7);
+        new_storage
+            .set_safety_data(current_safety_data.clone())
+            .unwrap();
+
+        let lower_safety_data = vec![
+            SafetyData::new(4, 20, 20, 20, None, 20),
+            SafetyData::new(5, 9, 8, 9, None, 7),
+            SafetyData::new(5, 10, 7, 9, None, 7),
+            SafetyData::new(5, 10, 8, 8, None, 7),
+            SafetyData::new(5, 10, 8, 9, None, 6),
+        ];
+        for safety_data in lower_safety_data {
+            old_storage.set_safety_data(safety_data).unwrap();
+            let signed_export = old_storage.export_safety_data().unwrap();
+            assert!(matches!(
+                new_storage.import_safety_data(&signed_export),
+                Err(Error::InvalidSafetyDataImport(_))
+            ));
+            assert_eq!(new_storage.safety_data().unwrap(), current_safety_data);
+        }
+
+        // Higher epochs are allowed to reset the rounds
+        let higher_safety_data = SafetyData::new(6, 1, 0, 0, None, 0);
+        old_storage
+            .set_safety_data(higher_safety_data.clone())
+            .unwrap();
+        let signed_export = old_storage.export_safety_data().unwrap();
+        new_storage.import_safety_data(&signed_export).unwrap();
+        assert_eq!(new_storage.safety_data().unwrap(), higher_safety_data);
+    }
+
     fn test_safety_data_counters(safety_storage: &mut PersistentSafetyStorage) {
         let safety_data = safety_storage.safety_data().unwrap();
         assert_eq!(safety_data.epoch, 1);
@@ -0,0 +1,114 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::Error;
+use aptos_consensus_types::{common::Author, safety_data::SafetyData};
+use aptos_crypto::{bls12381, Signature};
+use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
+use serde::{Deserialize, Serialize};
+
+/// The current version of the safety data interchange format
+pub const SAFETY_DATA_EXPORT_VERSION: u64 = 1;
+
+/// The safety data of a validator, exported so that it can be imported
+/// on a new host (e.g., when migrating a validator between machines).
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, CryptoHasher, BCSCryptoHash)]
+pub struct SafetyDataExport {
+    /// The version of the interchange format
+    pub version: u64,
+    /// The validator that owns the safety data
+    pub author: Author,
+    /// The exported safety data (epoch, last voted round, preferred round, etc.)
+    pub safety_data: SafetyData,
+    /// The time (in microseconds since the unix epoch) at which the data was exported
+    pub timestamp_usecs: u64,
+}
+
+/// A safety data export, signed by the consensus key of the validator
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct SignedSafetyDataExport {
+    /// The exported safety data
+    pub export: SafetyDataExport,
+    /// The consensus public key of the validator that signed the export
+    pub public_key: bls12381::PublicKey,
+    /// The signature over the export
+    pub signature: bls12381::Signature,
+}
+
+impl SignedSafetyDataExport {
+    /// Verifies the format version and the signature of the export
+    pub fn verify(&self) -> Result<(), Error> {
+        if self.export.version != SAFETY_DATA_EXPORT_VERSION {
+            return Err(Error::InvalidSafetyDataImport(format!(
+                "Unsupported export version: {}, expected: {}",
+                self.export.version, SAFETY_DATA_EXPORT_VERSION
+            )));
+        }
+        self.signature
+            .verify(&self.export, &self.public_key)
+            .map_err(|error| {
+                Error::InvalidSafetyDataImport(format!("Invalid signature: {}", error))
+            })
+    }
+}
+
+/// Verifies that replacing the current safety data with the imported safety
+/// data does not lower any watermark (otherwise, the validator could equivocate).
+pub fn check_safety_data_import(current: &SafetyData, imported: &SafetyData) -> Result<(), Error> {
+    if imported.epoch < current.epoch {
+        return Err(Error::InvalidSafetyDataImport(format!(
+            "Imported epoch {} is lower than the current epoch {}",
+            imported.epoch, current.epoch
+        )));
+    }
+    if imported.epoch > current.epoch {
+        return Ok(()); // The rounds are only comparable within the same epoch
+    }
+
+    let watermarks = [
+        (
+            "last_voted_round",
+            imported.last_voted_round,
+            current.last_voted_round,
+        ),
+        (
+            "preferred_round",
+            imported.preferred_round,
+            current.preferred_round,
+        ),
+        (
+            "one_chain_round",
+            imported.one_chain_round,
+            current.one_chain_round,
+        ),
+        (
+            "highest_timeout_round",
+            imported.highest_timeout_round,
+            current.highest_timeout_round,
+        ),
+    ];
+    for (name, imported_round, current_round) in watermarks {
+        if imported_round < current_round {
+            return Err(Error::InvalidSafetyDataImport(format!(
+                "Imported {} {} is lower than the current {} {}",
+                name, imported_round, name, current_round
+            )));
+        }
+    }
+
+    // If the last voted rounds are equal, the votes must not conflict
+    if imported.last_voted_round == current.last_voted_round {
+        if let Some(current_vote) = &current.last_vote {
+            if current_vote.vote_data().proposed().round() == current.last_voted_round
+                && imported.last_vote.as_ref() != Some(current_vote)
+            {
+                return Err(Error::InvalidSafetyDataImport(format!(
+                    "Imported last vote conflicts with the current last vote for round {}",
+                    current.last_voted_round
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -6,7 +6,7 @@ use crate::{
     block_storage::{
         block_tree::BlockTree,
         pending_blocks::PendingBlocks,
-        tracing::{observe_block, BlockStage},
+        tracing::{observe_block, trace_block, BlockStage, BlockTraces},
         BlockReader,
     },
     counters,
@@ -48,9 +48,19 @@ mod block_store_test;
 #[path = "sync_manager.rs"]
 pub mod sync_manager;
 
-fn update_counters_for_ordered_blocks(ordered_blocks: &[Arc<PipelinedBlock>]) {
+fn update_counters_for_ordered_blocks(
+    ordered_blocks: &[Arc<PipelinedBlock>],
+    block_traces: Option<&BlockTraces>,
+) {
     for block in ordered_blocks {
         observe_block(block.block().timestamp_usecs(), BlockStage::ORDERED);
+        trace_block(
+            block_traces,
+            block.id(),
+            block.epoch(),
+            block.round(),
+            BlockStage::ORDERED,
+        );
     }
 }
 
@@ -85,6 +95,7 @@ pub struct BlockStore {
     back_pressure_for_test: AtomicBool,
     order_vote_enabled: bool,
     pending_blocks: Arc<Mutex<PendingBlocks>>,
+    block_traces: Option<Arc<BlockTraces>>,
 }
 
 impl BlockStore {
@@ -98,6 +109,7 @@ impl BlockStore {
         payload_manager: Arc<dyn TPayloadManager>,
         order_vote_enabled: bool,
         pending_blocks: Arc<Mutex<PendingBlocks>>,
+        block_traces: Option<Arc<BlockTraces>>,
     ) -> Self {
         let highest_2chain_tc = initial_data.highest_2chain_timeout_certificate();
         let (root, root_metadata, blocks, quorum_certs) = initial_data.take();
@@ -115,6 +127,7 @@ impl BlockStore {
             payload_manager,
             order_vote_enabled,
             pending_blocks,
+            block_traces,
         ));
         block_on(block_store.try_send_for_execution());
         block_store
@@ -153,6 +166,7 @@ impl BlockStore {
         payload_manager: Arc<dyn TPayloadManager>,
         order_vote_enabled: bool,
         pending_blocks: Arc<Mutex<PendingBlocks>>,
+        block_traces: Option<Arc<BlockTraces>>,
     ) -> Self {
         let RootInfo(root_block, root_qc, root_ordered_cert, root_commit_cert) = root;
 
@@ -214,6 +228,7 @@ impl BlockStore {
             back_pressure_for_test: AtomicBool::new(false),
             order_vote_enabled,
             pending_blocks,
+            block_traces,
         };
 
         for block in blocks {
@@ -257,6 +272,7 @@ impl BlockStore {
 
         let block_tree = self.inner.clone();
         let storage = self.storage.clone();
+        let block_traces = self.block_traces.clone();
         let finality_proof_clone = finality_proof.clone();
         self.pending_blocks
             .lock()
@@ -269,6 +285,15 @@ impl BlockStore {
                 Box::new(
                     move |committed_blocks: &[Arc<PipelinedBlock>],
                           commit_decision: LedgerInfoWithSignatures| {
+                        for block in committed_blocks {
+                            trace_block(
+                                block_traces.as_deref(),
+                                block.id(),
+                                block.epoch(),
+                                block.round(),
+                                BlockStage::COMMITTED,
+                            );
+                        }
                         block_tree.write().commit_callback(
                             storage,
                             committed_blocks,
@@ -285,7 +310,7 @@ impl BlockStore {
         self.inner
             .write()
             .insert_ordered_cert(finality_proof_clone.clone());
-        update_counters_for_ordered_blocks(&blocks_to_commit);
+        update_counters_for_ordered_blocks(&blocks_to_commit, self.block_traces.as_deref());
 
         Ok(())
     }
@@ -325,6 +350,7 @@ impl BlockStore {
             self.payload_manager.clone(),
             self.order_vote_enabled,
             self.pending_blocks.clone(),
+            self.block_traces.clone(),
         )
         .await;
 
@@ -370,6 +396,8 @@ impl BlockStore {
             self.payload_manager
                 .prefetch_payload_data(payload, pipelined_block.block().timestamp_usecs());
         }
+        self.payload_manager
+            .notify_block_inserted(pipelined_block.block());
         self.storage
             .save_tree(vec![pipelined_block.block().clone()], vec![])
             .context("Insert block failed when saving block")?;
@@ -472,6 +500,12 @@ impl BlockStore {
         self.pending_blocks.clone()
     }
 
+    /// Records the time at which the given block reached the given stage
+    /// (if block tracing is enabled).
+    pub fn trace_block(&self, block_id: HashValue, epoch: u64, round: Round, stage: &'static str) {
+        trace_block(self.block_traces.as_deref(), block_id, epoch, round, stage);
+    }
+
     pub async fn wait_for_payload(&self, block: &Block) -> anyhow::Result<()> {
         tokio::time::timeout(
             Duration::from_secs(1),
@@ -3,8 +3,22 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::counters;
-use aptos_infallible::duration_since_epoch;
-use std::time::Duration;
+use aptos_consensus_types::common::Round;
+use aptos_crypto::HashValue;
+use aptos_infallible::{duration_since_epoch, Mutex};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Duration,
+};
+
+#[cfg(test)]
+#[path = "tracing_test.rs"]
+mod tracing_test;
+
+/// The maximum number of block traces to keep in memory
+pub const MAX_NUM_BLOCK_TRACES: usize = 1000;
 
 pub struct BlockStage;
 
@@ -14,10 +28,14 @@ impl BlockStage {
     pub const EPOCH_MANAGER_RECEIVED: &'static str = "epoch_manager_received";
     pub const EPOCH_MANAGER_VERIFIED: &'static str = "epoch_manager_verified";
     pub const EXECUTED: &'static str = "executed";
+    pub const EXECUTION_FINISHED: &'static str = "execution_finished";
+    pub const EXECUTION_SCHEDULED: &'static str = "execution_scheduled";
     pub const NETWORK_RECEIVED: &'static str = "network_received";
     pub const OC_ADDED: &'static str = "ordered_cert_created";
     pub const ORDERED: &'static str = "ordered";
     pub const ORDER_VOTED: &'static str = "order_voted";
+    pub const PERSISTED: &'static str = "persisted";
+    pub const PROPOSAL_RECEIVED: &'static str = "proposal_received";
     pub const QC_ADDED: &'static str = "qc_added";
     pub const QC_AGGREGATED: &'static str = "qc_aggregated";
     pub const RAND_ADD_DECISION: &'static str = "rand_add_decision";
@@ -27,6 +45,7 @@ impl BlockStage {
     pub const RAND_READY: &'static str = "rand_ready";
     pub const ROUND_MANAGER_RECEIVED: &'static str = "round_manager_received";
     pub const SIGNED: &'static str = "signed";
+    pub const SIGNED_COMMIT_VOTE: &'static str = "signed_commit_vote";
     pub const SYNCED: &'static str = "synced";
     pub const VOTED: &'static str = "voted";
 }
@@ -39,3 +58,173 @@ pub fn observe_block(timestamp: u64, stage: &'static str) {
             .observe(t.as_secs_f64());
     }
 }
+
+/// Record the time at which the given block reached the given stage
+/// in the per-block trace buffer (if block tracing is enabled).
+pub fn trace_block(
+    block_traces: Option<&BlockTraces>,
+    block_id: HashValue,
+    epoch: u64,
+    round: Round,
+    stage: &'static str,
+) {
+    if let Some(block_traces) = block_traces {
+        block_traces.record(
+            block_id,
+            epoch,
+            round,
+            stage,
+            duration_since_epoch().as_micros() as u64,
+        );
+    }
+}
+
+/// A single stage reached by a block (and the local time it was reached)
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BlockStageEvent {
+    pub stage: String,
+    pub timestamp_usecs: u64,
+}
+
+/// The trace of a single block through the consensus pipeline
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BlockTrace {
+    pub block_id: HashValue,
+    pub epoch: u64,
+    pub round: Round,
+    pub stages: Vec<BlockStageEvent>,
+}
+
+impl BlockTrace {
+    fn new(block_id: HashValue, epoch: u64, round: Round) -> Self {
+        Self {
+            block_id,
+            epoch,
+            round,
+            stages: vec![],
+        }
+    }
+
+    /// Returns the events of the trace, sorted by time
+    fn sorted_stages(&self) -> Vec<BlockStageEvent> {
+        let mut stages = self.stages.clone();
+        stages.sort_by_key(|event| event.timestamp_usecs);
+        stages
+    }
+}
+
+#[derive(Default)]
+struct BlockTracesInner {
+    block_order: VecDeque<HashValue>,
+    traces: HashMap<HashValue, BlockTrace>,
+}
+
+/// A bounded ring buffer of block traces. Once the buffer is full,
+/// the traces of the oldest blocks are evicted.
+pub struct BlockTraces {
+    max_num_traces: usize,
+    inner: Mutex<BlockTracesInner>,
+}
+
+impl BlockTraces {
+    pub fn new(max_num_traces: usize) -> Self {
+        Self {
+            max_num_traces,
+            inner: Mutex::new(BlockTracesInner::default()),
+        }
+    }
+
+    /// Records the stage for the given block. Only the first time
+    /// a block reaches a stage is recorded (e.g., blocks may be
+    /// re-executed, but only the first execution is traced).
+    pub fn record(
+        &self,
+        block_id: HashValue,
+        epoch: u64,
+        round: Round,
+        stage: &'static str,
+        timestamp_usecs: u64,
+    ) {
+        if self.max_num_traces == 0 {
+            return;
+        }
+
+        let mut inner = self.inner.lock();
+        if !inner.traces.contains_key(&block_id) {
+            // Evict the oldest traces if the buffer is full
+            while inner.block_order.len() >= self.max_num_traces {
+                if let Some(evicted_block_id) = inner.block_order.pop_front() {
+                    inner.traces.remove(&evicted_block_id);
+                }
+            }
+            inner.block_order.push_back(block_id);
+            inner
+                .traces
+                .insert(block_id, BlockTrace::new(block_id, epoch, round));
+        }
+
+        let trace = inner
+            .traces
+            .get_mut(&block_id)
+            .expect("The block trace must exist!");
+        if !trace.stages.iter().any(|event| event.stage == stage) {
+            trace.stages.push(BlockStageEvent {
+                stage: stage.to_string(),
+                timestamp_usecs,
+            });
+        }
+    }
+
+    /// Returns the trace for the given block (if one exists)
+    pub fn get_trace(&self, block_id: &HashValue) -> Option<BlockTrace> {
+        self.inner.lock().traces.get(block_id).cloned()
+    }
+
+    /// Returns all block traces (ordered from oldest to newest)
+    pub fn get_traces(&self) -> Vec<BlockTrace> {
+        let inner = self.inner.lock();
+        inner
+            .block_order
+            .iter()
+            .filter_map(|block_id| inner.traces.get(block_id))
+            .map(|trace| {
+                let mut trace = trace.clone();
+                trace.stages = trace.sorted_stages();
+                trace
+            })
+            .collect()
+    }
+
+    /// Returns all block traces in the Chrome trace event format (which
+    /// can be loaded into chrome://tracing or Perfetto). Each block is
+    /// displayed as a thread (keyed by round) in a process (keyed by epoch),
+    /// and each stage is displayed as a span that starts when the previous
+    /// stage was reached and ends when the stage itself was reached.
+    pub fn get_traces_chrome_format(&self) -> serde_json::Value {
+        let mut trace_events = vec![];
+        for trace in self.get_traces() {
+            let mut previous_timestamp_usecs = None;
+            for event in trace.stages {
+                let start_timestamp_usecs =
+                    previous_timestamp_usecs.unwrap_or(event.timestamp_usecs);
+                trace_events.push(json!({
+                    "name": event.stage,
+                    "cat": "consensus",
+                    "ph": "X",
+                    "ts": start_timestamp_usecs,
+                    "dur": event.timestamp_usecs - start_timestamp_usecs,
+                    "pid": trace.epoch,
+                    "tid": trace.round,
+                    "args": {
+                        "block_id": trace.block_id.to_hex(),
+                    },
+                }));
+                previous_timestamp_usecs = Some(event.timestamp_usecs);
+            }
+        }
+        json!({
+            "traceEvents": trace_events,
+            "displayTimeUnit": "ms",
+        })
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::block_storage::tracing::{BlockStage, BlockTraces};
+use aptos_crypto::HashValue;
+
+#[test]
+fn test_block_traces_eviction() {
+    // Create a buffer that holds at most two traces
+    let block_traces = BlockTraces::new(2);
+
+    // Record stages for three different blocks
+    let block_ids: Vec<_> = (0..3).map(|_| HashValue::random()).collect();
+    for (round, block_id) in block_ids.iter().enumerate() {
+        block_traces.record(*block_id, 1, round as u64, BlockStage::VOTED, 100);
+    }
+
+    // Verify the oldest trace was evicted
+    let traces = block_traces.get_traces();
+    assert_eq!(traces.len(), 2);
+    assert!(block_traces.get_trace(&block_ids[0]).is_none());
+    assert_eq!(traces[0].block_id, block_ids[1]);
+    assert_eq!(traces[1].block_id, block_ids[2]);
+}
+
+#[test]
+fn test_block_traces_stages() {
+    let block_traces = BlockTraces::new(10);
+    let block_id = HashValue::random();
+
+    // Record several stages (out of order, and with a duplicate stage)
+    block_traces.record(block_id, 1, 5, BlockStage::VOTED, 300);
+    block_traces.record(block_id, 1, 5, BlockStage::PROPOSAL_RECEIVED, 100);
+    block_traces.record(block_id, 1, 5, BlockStage::VOTED, 400);
+    block_traces.record(block_id, 1, 5, BlockStage::COMMITTED, 1000);
+
+    // Verify only the first occurrence of each stage is kept (sorted by time)
+    let trace = block_traces.get_traces().pop().unwrap();
+    let stages: Vec<_> = trace
+        .stages
+        .iter()
+        .map(|event| (event.stage.as_str(), event.timestamp_usecs))
+        .collect();
+    assert_eq!(stages, vec![
+        (BlockStage::PROPOSAL_RECEIVED, 100),
+        (BlockStage::VOTED, 300),
+        (BlockStage::COMMITTED, 1000),
+    ]);
+
+    // Verify the chrome trace spans cover the time between stages
+    let chrome_trace = block_traces.get_traces_chrome_format();
+    let trace_events = chrome_trace["traceEvents"].as_array().unwrap();
+    assert_eq!(trace_events.len(), 3);
+    assert_eq!(trace_events[1]["name"], BlockStage::VOTED);
+    assert_eq!(trace_events[1]["ts"], 100);
+    assert_eq!(trace_events[1]["dur"], 200);
+    assert_eq!(trace_events[2]["dur"], 700);
+    assert_eq!(trace_events[2]["pid"], 1);
+    assert_eq!(trace_events[2]["tid"], 5);
+}
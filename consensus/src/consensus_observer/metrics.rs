@@ -15,6 +15,7 @@ pub const BLOCK_PAYLOAD_LABEL: &str = "block_payload";
 pub const COMMIT_DECISION_LABEL: &str = "commit_decision";
 pub const CREATED_SUBSCRIPTION_LABEL: &str = "created_subscription";
 pub const ORDERED_BLOCK_ENTRIES_LABEL: &str = "ordered_block_entries";
+pub const ORDERED_BLOCK_LABEL: &str = "ordered_block";
 pub const ORDERED_BLOCKS_LABEL: &str = "ordered_blocks";
 pub const PENDING_BLOCK_ENTRIES_LABEL: &str = "pending_block_entries";
 pub const PENDING_BLOCKS_LABEL: &str = "pending_blocks";
@@ -30,6 +31,26 @@ pub static OBSERVER_CREATED_SUBSCRIPTIONS: Lazy<IntCounterVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Counter for tracking duplicate messages (e.g., from other publishers) dropped by the consensus observer
+pub static OBSERVER_DUPLICATE_MESSAGES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "consensus_observer_duplicate_messages",
+        "Counters for duplicate messages dropped by the consensus observer",
+        &["message_type", "network_id"]
+    )
+    .unwrap()
+});
+
+/// Counter for tracking conflicting messages (from publishers) that failed verification
+pub static OBSERVER_INVALID_CONFLICTING_MESSAGES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "consensus_observer_invalid_conflicting_messages",
+        "Counters for conflicting messages that failed verification",
+        &["message_type", "network_id"]
+    )
+    .unwrap()
+});
+
 /// Gauge for tracking the number of active subscriptions for the consensus observer
 pub static OBSERVER_NUM_ACTIVE_SUBSCRIPTIONS: Lazy<IntGaugeVec> = Lazy::new(|| {
     register_int_gauge_vec!(
@@ -60,6 +81,16 @@ pub static OBSERVER_PROCESSED_BLOCK_ROUNDS: Lazy<IntGaugeVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Counter for tracking disagreements between the publishers of the consensus observer
+pub static OBSERVER_PUBLISHER_DISAGREEMENTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "consensus_observer_publisher_disagreements",
+        "Counters for messages that disagree with messages from other publishers",
+        &["message_type", "network_id"]
+    )
+    .unwrap()
+});
+
 /// Counter for tracking successful RPC responses received by the consensus observer
 pub static OBSERVER_RECEIVED_MESSAGE_RESPONSES: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
@@ -13,4 +13,5 @@ pub mod ordered_blocks;
 pub mod payload_store;
 pub mod pending_blocks;
 pub mod publisher;
+mod publisher_cross_check;
 mod subscription;
@@ -16,6 +16,7 @@ use crate::{
         payload_store::BlockPayloadStore,
         pending_blocks::PendingBlockStore,
         publisher::ConsensusPublisher,
+        publisher_cross_check::{CrossCheckResult, PublisherCrossChecker},
         subscription::{self, ConsensusObserverSubscription},
     },
     dag::DagCommitSigner,
@@ -33,7 +34,7 @@ use aptos_consensus_types::{pipeline, pipelined_block::PipelinedBlock};
 use aptos_crypto::{bls12381, Genesis};
 use aptos_event_notifications::{DbBackedOnChainConfig, ReconfigNotificationListener};
 use aptos_infallible::Mutex;
-use aptos_logger::{debug, error, info, warn};
+use aptos_logger::{debug, error, info, sample, sample::SampleRate, warn};
 use aptos_network::{
     application::{interface::NetworkClient, metadata::PeerMetadata},
     protocols::wire::handshake::v1::ProtocolId,
@@ -57,13 +58,20 @@ use futures::{
 };
 use futures_channel::oneshot;
 use move_core_types::account_address::AccountAddress;
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
 use tokio::{sync::mpsc::UnboundedSender, time::interval};
 use tokio_stream::wrappers::IntervalStream;
 
 // Whether to log messages at the info level (useful for debugging)
 const LOG_MESSAGES_AT_INFO_LEVEL: bool = true;
 
+// The interval (in seconds) at which to log conflicting messages that fail verification
+const INVALID_CONFLICTING_MESSAGE_LOG_INTERVAL_SECS: u64 = 5;
+
 /// The consensus observer receives consensus updates and propagates them to the execution pipeline
 pub struct ConsensusObserver {
     // The configuration of the node
@@ -98,8 +106,10 @@ pub struct ConsensusObserver {
 
     // The consensus publisher to forward payload messages
     consensus_publisher: Option<Arc<ConsensusPublisher>>,
-    // The currently active consensus observer subscription
-    active_observer_subscription: Option<ConsensusObserverSubscription>,
+    // The currently active consensus observer subscriptions (one per publisher)
+    active_observer_subscriptions: HashMap<PeerNetworkId, ConsensusObserverSubscription>,
+    // The cross checker used to deduplicate messages and detect publisher disagreements
+    publisher_cross_checker: PublisherCrossChecker,
     // A handle to storage (used to read the latest state and check progress)
     db_reader: Arc<dyn DbReader>,
     // The time service (used to check progress)
@@ -142,7 +152,8 @@ impl ConsensusObserver {
             sync_notification_sender,
             reconfig_events,
             consensus_publisher,
-            active_observer_subscription: None,
+            active_observer_subscriptions: HashMap::new(),
+            publisher_cross_checker: PublisherCrossChecker::new(consensus_observer_config),
             db_reader,
             time_service,
         }
@@ -175,88 +186,92 @@ impl ConsensusObserver {
             return;
         }
 
-        // Get the peer ID of the currently active subscription (if any)
-        let active_subscription_peer = self
-            .active_observer_subscription
-            .as_ref()
-            .map(|subscription| subscription.get_peer_network_id());
-
-        // If we have an active subscription, verify that the subscription
-        // is still healthy. If not, the subscription should be terminated.
-        if let Some(active_subscription_peer) = active_subscription_peer {
-            if let Err(error) = self.check_active_subscription() {
-                // Log the subscription termination
-                warn!(
-                    LogSchema::new(LogEntry::ConsensusObserver).message(&format!(
-                        "Terminating subscription to peer: {:?}! Error: {:?}",
-                        active_subscription_peer, error
-                    ))
-                );
+        // Verify that the active subscriptions are still healthy. If
+        // not, the unhealthy subscriptions should be terminated.
+        let terminated_subscription_peers = self.check_active_subscriptions();
 
-                // Unsubscribe from the peer
-                self.unsubscribe_from_peer(active_subscription_peer);
-
-                // Update the subscription termination metrics
-                self.update_subscription_termination_metrics(active_subscription_peer, error);
-            }
-        }
-
-        // If we don't have a subscription, we should select a new peer to
-        // subscribe to. If we had a previous subscription, it should be
+        // If we have fewer subscriptions than the maximum, we should select new
+        // peers to subscribe to. Any terminated subscription peers should be
         // excluded from the selection process.
-        if self.active_observer_subscription.is_none() {
-            // Create a new observer subscription
-            self.create_new_observer_subscription(active_subscription_peer)
+        let max_concurrent_subscriptions = self
+            .node_config
+            .consensus_observer
+            .max_concurrent_subscriptions as usize;
+        let num_active_subscriptions = self.active_observer_subscriptions.len();
+        if num_active_subscriptions < max_concurrent_subscriptions {
+            // Create the new observer subscriptions
+            let num_subscriptions_to_create =
+                max_concurrent_subscriptions - num_active_subscriptions;
+            let new_subscription_peers = self
+                .create_new_observer_subscriptions(
+                    num_subscriptions_to_create,
+                    terminated_subscription_peers,
+                )
                 .await;
 
-            // If we successfully created a new subscription, clear the state and update the metrics
-            if let Some(active_subscription) = &self.active_observer_subscription {
-                // Clear the block state
+            // If we previously had no subscriptions, clear the block state
+            // (we might have fallen behind, so we should restart from the root).
+            if num_active_subscriptions == 0 && !new_subscription_peers.is_empty() {
                 self.clear_pending_block_state().await;
+            }
 
-                // Update the subscription creation metrics
-                self.update_subscription_creation_metrics(
-                    active_subscription.get_peer_network_id(),
-                );
+            // Update the subscription creation metrics
+            for peer_network_id in new_subscription_peers {
+                self.update_subscription_creation_metrics(peer_network_id);
             }
         }
     }
 
-    /// Checks if the active subscription is still healthy. If not, an error is returned.
-    fn check_active_subscription(&mut self) -> Result<(), Error> {
-        let active_observer_subscription = self.active_observer_subscription.take();
-        if let Some(mut active_subscription) = active_observer_subscription {
-            // Check if the peer for the subscription is still connected
-            let peer_network_id = active_subscription.get_peer_network_id();
-            let peer_still_connected = self
-                .get_connected_peers_and_metadata()
-                .map_or(false, |peers_and_metadata| {
-                    peers_and_metadata.contains_key(&peer_network_id)
-                });
-
-            // Verify the peer is still connected
-            if !peer_still_connected {
-                return Err(Error::SubscriptionDisconnected(
-                    "The peer is no longer connected!".to_string(),
-                ));
+    /// Checks if the active subscriptions are still healthy. Any unhealthy
+    /// subscriptions are terminated, and the terminated peers are returned.
+    fn check_active_subscriptions(&mut self) -> Vec<PeerNetworkId> {
+        // Get the connected peers and metadata
+        let connected_peers_and_metadata = self.get_connected_peers_and_metadata();
+
+        // Identify the highest ordered epoch and round across all subscriptions
+        let highest_ordered_epoch_and_round = self
+            .active_observer_subscriptions
+            .values()
+            .filter_map(|subscription| subscription.get_highest_ordered_epoch_and_round())
+            .max();
+
+        // Check the health of each subscription
+        let active_subscription_peers: HashSet<_> =
+            self.active_observer_subscriptions.keys().cloned().collect();
+        let mut unhealthy_subscriptions = vec![];
+        for (peer_network_id, active_subscription) in self.active_observer_subscriptions.iter_mut()
+        {
+            if let Err(error) = check_subscription_health(
+                active_subscription,
+                &connected_peers_and_metadata,
+                &active_subscription_peers,
+                highest_ordered_epoch_and_round,
+            ) {
+                unhealthy_subscriptions.push((*peer_network_id, error));
             }
+        }
 
-            // Verify the subscription has not timed out
-            active_subscription.check_subscription_timeout()?;
-
-            // Verify that the DB is continuing to sync and commit new data
-            active_subscription.check_syncing_progress()?;
+        // Terminate the unhealthy subscriptions
+        let mut terminated_subscription_peers = vec![];
+        for (peer_network_id, error) in unhealthy_subscriptions {
+            // Log the subscription termination
+            warn!(
+                LogSchema::new(LogEntry::ConsensusObserver).message(&format!(
+                    "Terminating subscription to peer: {:?}! Error: {:?}",
+                    peer_network_id, error
+                ))
+            );
 
-            // Verify that the subscription peer is optimal
-            if let Some(peers_and_metadata) = self.get_connected_peers_and_metadata() {
-                active_subscription.check_subscription_peer_optimality(peers_and_metadata)?;
-            }
+            // Remove the subscription and unsubscribe from the peer
+            self.active_observer_subscriptions.remove(&peer_network_id);
+            self.unsubscribe_from_peer(peer_network_id);
 
-            // The subscription seems healthy, we can keep it
-            self.active_observer_subscription = Some(active_subscription);
+            // Update the subscription termination metrics
+            self.update_subscription_termination_metrics(peer_network_id, error);
+            terminated_subscription_peers.push(peer_network_id);
         }
 
-        Ok(())
+        terminated_subscription_peers
     }
 
     /// Clears the pending block state (this is useful for changing
@@ -318,20 +333,22 @@ impl ConsensusObserver {
         })
     }
 
-    /// Creates a new observer subscription by sending subscription requests to
-    /// appropriate peers and waiting for a successful response. If `previous_subscription_peer`
-    /// is provided, it will be excluded from the selection process.
-    async fn create_new_observer_subscription(
+    /// Creates new observer subscriptions by sending subscription requests to
+    /// appropriate peers and waiting for successful responses. Any peers in
+    /// `excluded_peers` will be excluded from the selection process. Returns
+    /// the peers that were successfully subscribed to.
+    async fn create_new_observer_subscriptions(
         &mut self,
-        previous_subscription_peer: Option<PeerNetworkId>,
-    ) {
-        // Get a set of sorted peers to service our subscription request
-        let sorted_peers = match self.sort_peers_for_subscription(previous_subscription_peer) {
+        num_subscriptions_to_create: usize,
+        excluded_peers: Vec<PeerNetworkId>,
+    ) -> Vec<PeerNetworkId> {
+        // Get a set of sorted peers to service our subscription requests
+        let sorted_peers = match self.sort_peers_for_subscription(excluded_peers) {
             Some(sorted_peers) => sorted_peers,
             None => {
                 error!(LogSchema::new(LogEntry::ConsensusObserver)
                     .message("Failed to sort peers for subscription requests!"));
-                return;
+                return vec![];
             },
         };
 
@@ -339,12 +356,17 @@ impl ConsensusObserver {
         if sorted_peers.is_empty() {
             warn!(LogSchema::new(LogEntry::ConsensusObserver)
                 .message("There are no peers to subscribe to!"));
-            return;
+            return vec![];
         }
 
-        // Go through the sorted peers and attempt to subscribe to a single peer.
-        // The first peer that responds successfully will be the selected peer.
+        // Go through the sorted peers and attempt to subscribe to each peer
+        // until we have created the required number of subscriptions.
+        let mut new_subscription_peers = vec![];
         for selected_peer in &sorted_peers {
+            if new_subscription_peers.len() >= num_subscriptions_to_create {
+                break; // We've created enough subscriptions
+            }
+
             info!(
                 LogSchema::new(LogEntry::ConsensusObserver).message(&format!(
                     "Attempting to subscribe to peer: {}!",
@@ -353,7 +375,8 @@ impl ConsensusObserver {
             );
 
             // Send a subscription request to the peer and wait for the response.
-            // Note: it is fine to block here because we assume only a single active subscription.
+            // Note: it is fine to block here because subscriptions are only
+            // created periodically (and the number of subscriptions is small).
             let subscription_request = ConsensusObserverRequest::Subscribe;
             let request_timeout_ms = self
                 .node_config
@@ -364,7 +387,7 @@ impl ConsensusObserver {
                 .send_rpc_request_to_peer(selected_peer, subscription_request, request_timeout_ms)
                 .await;
 
-            // Process the response and update the active subscription
+            // Process the response and update the active subscriptions
             match response {
                 Ok(ConsensusObserverResponse::SubscribeAck) => {
                     info!(
@@ -374,16 +397,16 @@ impl ConsensusObserver {
                         ))
                     );
 
-                    // Update the active subscription
+                    // Add the new subscription
                     let subscription = ConsensusObserverSubscription::new(
                         self.node_config.consensus_observer,
                         self.db_reader.clone(),
                         *selected_peer,
                         self.time_service.clone(),
                     );
-                    self.active_observer_subscription = Some(subscription);
-
-                    return; // Return after successfully subscribing
+                    self.active_observer_subscriptions
+                        .insert(*selected_peer, subscription);
+                    new_subscription_peers.push(*selected_peer);
                 },
                 Ok(response) => {
                     // We received an invalid response
@@ -406,13 +429,19 @@ impl ConsensusObserver {
             }
         }
 
-        // We failed to connect to any peers
-        warn!(
-            LogSchema::new(LogEntry::ConsensusObserver).message(&format!(
-                "Failed to subscribe to any peers! Num peers attempted: {:?}",
-                sorted_peers.len()
-            ))
-        );
+        // Log if we failed to create enough subscriptions
+        if new_subscription_peers.len() < num_subscriptions_to_create {
+            warn!(
+                LogSchema::new(LogEntry::ConsensusObserver).message(&format!(
+                    "Failed to create enough subscriptions! Num created: {:?}, num required: {:?}, num peers attempted: {:?}",
+                    new_subscription_peers.len(),
+                    num_subscriptions_to_create,
+                    sorted_peers.len()
+                ))
+            );
+        }
+
+        new_subscription_peers
     }
 
     /// Finalizes the ordered block by sending it to the execution pipeline
@@ -611,6 +640,9 @@ impl ConsensusObserver {
 
             // Update the pending blocks with the commit decision
             if self.process_commit_decision_for_pending_block(&commit_decision) {
+                // Record the processed commit decision (to identify duplicates from other publishers)
+                self.publisher_cross_checker
+                    .record_commit_decision(commit_decision.proof_block_info());
                 return; // The commit decision was successfully processed
             }
         }
@@ -652,6 +684,14 @@ impl ConsensusObserver {
             self.ordered_block_store
                 .remove_blocks_for_commit(commit_decision.commit_proof());
 
+            // Record the commit decision if it was verified (to identify duplicates
+            // from other publishers). Commit decisions for future epochs can't be
+            // verified yet, but are dropped by the check above while we sync.
+            if commit_decision_epoch == epoch_state.epoch {
+                self.publisher_cross_checker
+                    .record_commit_decision(commit_decision.proof_block_info());
+            }
+
             // Start the state sync process
             let abort_handle = sync_to_commit_decision(
                 commit_decision,
@@ -709,8 +749,10 @@ impl ConsensusObserver {
         // Unpack the network message
         let (peer_network_id, message) = network_message.into_parts();
 
-        // Verify the message is from the peer we've subscribed to
-        if let Some(active_subscription) = &mut self.active_observer_subscription {
+        // Verify the message is from a peer we've subscribed to
+        if let Some(active_subscription) =
+            self.active_observer_subscriptions.get_mut(&peer_network_id)
+        {
             if let Err(error) = active_subscription.verify_message_sender(&peer_network_id) {
                 warn!(
                     LogSchema::new(LogEntry::ConsensusObserver).message(&format!(
@@ -726,7 +768,7 @@ impl ConsensusObserver {
         } else {
             warn!(
                 LogSchema::new(LogEntry::ConsensusObserver).message(&format!(
-                    "Received message from unexpected peer: {}! No active subscription found!",
+                    "Received message from unexpected peer: {}! No active subscription found for the peer!",
                     peer_network_id
                 ))
             );
@@ -754,8 +796,39 @@ impl ConsensusObserver {
                 );
                 log_received_message(log_message);
 
-                // Process the ordered block message
-                self.process_ordered_block_message(ordered_block).await;
+                // Cross-check the ordered block against the ordered blocks
+                // from other publishers, and process it if it's new.
+                let proof_block_info = ordered_block.proof_block_info().clone();
+                if self.cross_check_message(
+                    &peer_network_id,
+                    metrics::ORDERED_BLOCK_LABEL,
+                    self.publisher_cross_checker
+                        .check_ordered_block(&proof_block_info),
+                    &proof_block_info,
+                    |epoch_state| {
+                        ordered_block.verify_ordered_blocks()?;
+                        ordered_block.verify_ordered_proof(epoch_state)
+                    },
+                ) {
+                    self.process_ordered_block_message(ordered_block).await;
+                }
+
+                // If the ordered block matches a verified ordered block, the
+                // publisher is making progress (update the subscription).
+                if self
+                    .publisher_cross_checker
+                    .check_ordered_block(&proof_block_info)
+                    == CrossCheckResult::Duplicate
+                {
+                    if let Some(active_subscription) =
+                        self.active_observer_subscriptions.get_mut(&peer_network_id)
+                    {
+                        active_subscription.update_highest_ordered_epoch_and_round(
+                            proof_block_info.epoch(),
+                            proof_block_info.round(),
+                        );
+                    }
+                }
             },
             ConsensusObserverDirectSend::CommitDecision(commit_decision) => {
                 // Log the received commit decision message
@@ -766,8 +839,19 @@ impl ConsensusObserver {
                 );
                 log_received_message(log_message);
 
-                // Process the commit decision message
-                self.process_commit_decision_message(commit_decision);
+                // Cross-check the commit decision against the commit decisions
+                // from other publishers, and process it if it's new.
+                let commit_info = commit_decision.proof_block_info().clone();
+                if self.cross_check_message(
+                    &peer_network_id,
+                    metrics::COMMIT_DECISION_LABEL,
+                    self.publisher_cross_checker
+                        .check_commit_decision(&commit_info),
+                    &commit_info,
+                    |epoch_state| commit_decision.verify_commit_proof(epoch_state),
+                ) {
+                    self.process_commit_decision_message(commit_decision);
+                }
             },
             ConsensusObserverDirectSend::BlockPayload(block_payload) => {
                 // Log the received block payload message
@@ -786,6 +870,81 @@ impl ConsensusObserver {
         self.update_processed_blocks_metrics();
     }
 
+    /// Handles the result of cross-checking a message (with the given block info)
+    /// against the messages from other publishers. Returns true iff the message
+    /// is new and should be processed. Duplicates are dropped, and disagreements
+    /// between publishers are reported. Note: conflicting messages are verified
+    /// (using the given function) before reporting, so that invalid messages
+    /// can't be used to fabricate disagreements.
+    fn cross_check_message(
+        &self,
+        peer_network_id: &PeerNetworkId,
+        message_label: &str,
+        cross_check_result: CrossCheckResult,
+        block_info: &BlockInfo,
+        verify_message: impl FnOnce(&EpochState) -> Result<(), Error>,
+    ) -> bool {
+        match cross_check_result {
+            CrossCheckResult::New => true,
+            CrossCheckResult::Duplicate => {
+                // Drop the duplicate message (it has already been processed)
+                metrics::increment_request_counter(
+                    &metrics::OBSERVER_DUPLICATE_MESSAGES,
+                    message_label,
+                    peer_network_id,
+                );
+                false
+            },
+            CrossCheckResult::Conflict(accepted_block_info) => {
+                // Verify the conflicting message (accepted messages are always verified)
+                let epoch_state = self.get_epoch_state();
+                let verification_result = if block_info.epoch() == epoch_state.epoch {
+                    verify_message(&epoch_state)
+                } else {
+                    Err(Error::InvalidMessageError(format!(
+                        "Message is not for the current epoch: {}",
+                        epoch_state.epoch
+                    )))
+                };
+
+                // If verification failed, drop the message (the peer sent an invalid message)
+                if let Err(error) = verification_result {
+                    sample!(
+                        SampleRate::Duration(Duration::from_secs(
+                            INVALID_CONFLICTING_MESSAGE_LOG_INTERVAL_SECS
+                        )),
+                        warn!(
+                            LogSchema::new(LogEntry::ConsensusObserver).message(&format!(
+                                "Failed to verify conflicting message! Ignoring. Peer: {}, message type: {}, received: {}, Error: {:?}",
+                                peer_network_id, message_label, block_info, error
+                            ))
+                        )
+                    );
+                    metrics::increment_request_counter(
+                        &metrics::OBSERVER_INVALID_CONFLICTING_MESSAGES,
+                        message_label,
+                        peer_network_id,
+                    );
+                    return false;
+                }
+
+                // Otherwise, report the disagreement and drop the message
+                error!(
+                    LogSchema::new(LogEntry::ConsensusObserver).message(&format!(
+                        "Publisher disagreement detected! Peer: {}, message type: {}, received: {}, accepted: {}",
+                        peer_network_id, message_label, block_info, accepted_block_info
+                    ))
+                );
+                metrics::increment_request_counter(
+                    &metrics::OBSERVER_PUBLISHER_DISAGREEMENTS,
+                    message_label,
+                    peer_network_id,
+                );
+                false
+            },
+        }
+    }
+
     /// Processes the ordered block
     async fn process_ordered_block_message(&mut self, ordered_block: OrderedBlock) {
         // Verify the ordered blocks before processing
@@ -858,6 +1017,10 @@ impl ConsensusObserver {
             self.ordered_block_store
                 .insert_ordered_block(ordered_block.clone());
 
+            // Record the ordered block (to identify duplicates from other publishers)
+            self.publisher_cross_checker
+                .record_ordered_block(ordered_block.proof_block_info());
+
             // If we're not in sync mode, finalize the ordered blocks
             if !self.in_state_sync_mode() {
                 self.finalize_ordered_block(ordered_block).await;
@@ -930,19 +1093,24 @@ impl ConsensusObserver {
         }
     }
 
-    /// Produces a list of sorted peers to service our subscription request. Peers
+    /// Produces a list of sorted peers to service our subscription requests. Peers
     /// are prioritized by validator distance and latency.
-    /// Note: if `previous_subscription_peer` is provided, it will be excluded
-    /// from the selection process. Likewise, all peers currently subscribed to us
-    /// will be excluded from the selection process.
+    /// Note: all peers in `excluded_peers` will be excluded from the selection
+    /// process. Likewise, all peers that we're already subscribed to, and all
+    /// peers currently subscribed to us will be excluded from the selection process.
     fn sort_peers_for_subscription(
         &mut self,
-        previous_subscription_peer: Option<PeerNetworkId>,
+        excluded_peers: Vec<PeerNetworkId>,
     ) -> Option<Vec<PeerNetworkId>> {
         if let Some(mut peers_and_metadata) = self.get_connected_peers_and_metadata() {
-            // Remove the previous subscription peer (if provided)
-            if let Some(previous_subscription_peer) = previous_subscription_peer {
-                let _ = peers_and_metadata.remove(&previous_subscription_peer);
+            // Remove the excluded peers
+            for excluded_peer in excluded_peers {
+                let _ = peers_and_metadata.remove(&excluded_peer);
+            }
+
+            // Remove any peers that we're already subscribed to
+            for active_subscription_peer in self.active_observer_subscriptions.keys() {
+                let _ = peers_and_metadata.remove(active_subscription_peer);
             }
 
             // Remove any peers that are currently subscribed to us
@@ -1025,12 +1193,8 @@ impl ConsensusObserver {
 
     /// Updates the subscription creation metrics for the given peer
     fn update_subscription_creation_metrics(&self, peer_network_id: PeerNetworkId) {
-        // Set the number of active subscriptions
-        metrics::set_gauge(
-            &metrics::OBSERVER_NUM_ACTIVE_SUBSCRIPTIONS,
-            &peer_network_id.network_id(),
-            1,
-        );
+        // Update the number of active subscriptions
+        self.update_active_subscriptions_gauge(peer_network_id);
 
         // Update the number of created subscriptions
         metrics::increment_request_counter(
@@ -1046,12 +1210,8 @@ impl ConsensusObserver {
         peer_network_id: PeerNetworkId,
         error: Error,
     ) {
-        // Reset the number of active subscriptions
-        metrics::set_gauge(
-            &metrics::OBSERVER_NUM_ACTIVE_SUBSCRIPTIONS,
-            &peer_network_id.network_id(),
-            0,
-        );
+        // Update the number of active subscriptions
+        self.update_active_subscriptions_gauge(peer_network_id);
 
         // Update the number of terminated subscriptions
         metrics::increment_request_counter(
@@ -1061,6 +1221,21 @@ impl ConsensusObserver {
         );
     }
 
+    /// Sets the number of active subscriptions on the network of the given peer
+    fn update_active_subscriptions_gauge(&self, peer_network_id: PeerNetworkId) {
+        let network_id = peer_network_id.network_id();
+        let num_active_subscriptions = self
+            .active_observer_subscriptions
+            .keys()
+            .filter(|peer| peer.network_id() == network_id)
+            .count();
+        metrics::set_gauge(
+            &metrics::OBSERVER_NUM_ACTIVE_SUBSCRIPTIONS,
+            &network_id,
+            num_active_subscriptions as i64,
+        );
+    }
+
     /// Waits for a new epoch to start
     async fn wait_for_epoch_start(&mut self) {
         // Extract the epoch state and on-chain configs
@@ -1162,6 +1337,53 @@ impl ConsensusObserver {
     }
 }
 
+/// Checks if the given subscription is still healthy. If not, an error is returned.
+fn check_subscription_health(
+    subscription: &mut ConsensusObserverSubscription,
+    connected_peers_and_metadata: &Option<HashMap<PeerNetworkId, PeerMetadata>>,
+    active_subscription_peers: &HashSet<PeerNetworkId>,
+    highest_ordered_epoch_and_round: Option<(u64, Round)>,
+) -> Result<(), Error> {
+    // Verify the peer is still connected
+    let peer_network_id = subscription.get_peer_network_id();
+    let peer_still_connected = connected_peers_and_metadata
+        .as_ref()
+        .map_or(false, |peers_and_metadata| {
+            peers_and_metadata.contains_key(&peer_network_id)
+        });
+    if !peer_still_connected {
+        return Err(Error::SubscriptionDisconnected(
+            "The peer is no longer connected!".to_string(),
+        ));
+    }
+
+    // Verify the subscription has not timed out
+    subscription.check_subscription_timeout()?;
+
+    // Verify that the DB is continuing to sync and commit new data
+    subscription.check_syncing_progress()?;
+
+    // Verify that the subscription is not lagging behind the other subscriptions
+    if let Some(highest_ordered_epoch_and_round) = highest_ordered_epoch_and_round {
+        subscription.check_subscription_round_lag(highest_ordered_epoch_and_round)?;
+    }
+
+    // Verify that the subscription peer is optimal. Peers that are
+    // already serving other subscriptions are not considered.
+    if let Some(peers_and_metadata) = connected_peers_and_metadata {
+        let peers_and_metadata = peers_and_metadata
+            .iter()
+            .filter(|(peer, _)| {
+                **peer == peer_network_id || !active_subscription_peers.contains(*peer)
+            })
+            .map(|(peer, metadata)| (*peer, metadata.clone()))
+            .collect();
+        subscription.check_subscription_peer_optimality(peers_and_metadata)?;
+    }
+
+    Ok(())
+}
+
 /// Checks that the epoch and round match the current root
 fn check_root_epoch_and_round(
     root: Arc<Mutex<LedgerInfoWithSignatures>>,
@@ -1320,3 +1542,363 @@ fn sync_to_commit_decision(
     ));
     abort_handle
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::pipeline::execution_client::DummyExecutionClient;
+    use aptos_config::{config::ConsensusObserverConfig, network_id::NetworkId};
+    use aptos_crypto::HashValue;
+    use aptos_network::{application::storage::PeersAndMetadata, transport::ConnectionMetadata};
+    use aptos_storage_interface::Result;
+    use aptos_types::{
+        aggregate_signature::AggregateSignature, ledger_info::LedgerInfo, transaction::Version,
+        validator_signer::ValidatorSigner, validator_verifier::ValidatorVerifier, PeerId,
+    };
+    use maplit::hashmap;
+    use mockall::mock;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    // This is a simple mock of the DbReader (it generates a MockDatabaseReader)
+    mock! {
+        pub DatabaseReader {}
+        impl DbReader for DatabaseReader {
+            fn get_latest_ledger_info(&self) -> Result<LedgerInfoWithSignatures>;
+
+            fn get_latest_ledger_info_version(&self) -> Result<Version>;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_multiple_subscriptions_dedup_and_failover() {
+        // Create a mock DB reader with a root ledger info
+        let current_epoch = 10;
+        let mut mock_db_reader = MockDatabaseReader::new();
+        mock_db_reader
+            .expect_get_latest_ledger_info()
+            .returning(move || Ok(create_ledger_info(current_epoch, 0)));
+        mock_db_reader
+            .expect_get_latest_ledger_info_version()
+            .returning(|| Ok(0));
+
+        // Create a consensus observer
+        let network_id = NetworkId::Public;
+        let peers_and_metadata = PeersAndMetadata::new(&[network_id]);
+        let time_service = TimeService::mock();
+        let (sync_notification_sender, _sync_notification_receiver) = unbounded_channel();
+        let mut consensus_observer = create_consensus_observer(
+            peers_and_metadata.clone(),
+            Arc::new(mock_db_reader),
+            sync_notification_sender,
+            time_service.clone(),
+        );
+
+        // Set the epoch state for the current epoch (with an empty verifier)
+        consensus_observer.epoch_state = Some(Arc::new(EpochState::new(
+            current_epoch,
+            ValidatorVerifier::new(vec![]),
+        )));
+
+        // Subscribe to two connected publishers
+        let peer_network_id_1 = PeerNetworkId::new(network_id, PeerId::random());
+        let peer_network_id_2 = PeerNetworkId::new(network_id, PeerId::random());
+        for peer_network_id in [peer_network_id_1, peer_network_id_2] {
+            let connection_metadata = ConnectionMetadata::mock(peer_network_id.peer_id());
+            peers_and_metadata
+                .insert_connection_metadata(peer_network_id, connection_metadata)
+                .unwrap();
+            let subscription = ConsensusObserverSubscription::new(
+                consensus_observer.node_config.consensus_observer,
+                consensus_observer.db_reader.clone(),
+                peer_network_id,
+                time_service.clone(),
+            );
+            consensus_observer
+                .active_observer_subscriptions
+                .insert(peer_network_id, subscription);
+        }
+
+        // Send a stale commit decision (for the root round) from the first publisher
+        let stale_commit_decision = CommitDecision::new(create_ledger_info(current_epoch, 0));
+        consensus_observer
+            .process_network_message(create_commit_decision_message(
+                peer_network_id_1,
+                stale_commit_decision.clone(),
+            ))
+            .await;
+
+        // Verify that the commit decision was not processed, and so it was not
+        // recorded (i.e., the same decision from another publisher is not a duplicate).
+        assert!(!consensus_observer.in_state_sync_mode());
+        assert_eq!(
+            consensus_observer
+                .publisher_cross_checker
+                .check_commit_decision(stale_commit_decision.proof_block_info()),
+            CrossCheckResult::New
+        );
+
+        // Send a commit decision for a future round from the first publisher
+        let num_duplicate_messages = get_num_duplicate_commit_decisions(network_id);
+        let commit_decision = CommitDecision::new(create_ledger_info(current_epoch, 5));
+        consensus_observer
+            .process_network_message(create_commit_decision_message(
+                peer_network_id_1,
+                commit_decision.clone(),
+            ))
+            .await;
+
+        // Verify that the observer started syncing to the commit decision, and that it was recorded
+        assert!(consensus_observer.in_state_sync_mode());
+        assert_eq!(
+            consensus_observer.root.lock().commit_info(),
+            commit_decision.proof_block_info()
+        );
+        assert_eq!(
+            consensus_observer
+                .publisher_cross_checker
+                .check_commit_decision(commit_decision.proof_block_info()),
+            CrossCheckResult::Duplicate
+        );
+        assert_eq!(
+            get_num_duplicate_commit_decisions(network_id),
+            num_duplicate_messages
+        );
+
+        // Send the same commit decision from the second publisher and verify it is dropped
+        consensus_observer
+            .process_network_message(create_commit_decision_message(
+                peer_network_id_2,
+                commit_decision.clone(),
+            ))
+            .await;
+        assert_eq!(
+            get_num_duplicate_commit_decisions(network_id),
+            num_duplicate_messages + 1
+        );
+
+        // Move the first publisher to the next epoch (the second publisher is left behind)
+        consensus_observer
+            .active_observer_subscriptions
+            .get_mut(&peer_network_id_1)
+            .unwrap()
+            .update_highest_ordered_epoch_and_round(current_epoch + 1, 1);
+        consensus_observer
+            .active_observer_subscriptions
+            .get_mut(&peer_network_id_2)
+            .unwrap()
+            .update_highest_ordered_epoch_and_round(current_epoch, 100);
+
+        // Verify that both subscriptions are still healthy (within the epoch lag grace period)
+        assert!(consensus_observer.check_active_subscriptions().is_empty());
+        assert_eq!(consensus_observer.active_observer_subscriptions.len(), 2);
+
+        // Elapse enough time to exceed the epoch lag grace period
+        let max_epoch_lag_ms = consensus_observer
+            .node_config
+            .consensus_observer
+            .max_subscription_epoch_lag_ms;
+        time_service
+            .into_mock()
+            .advance(Duration::from_millis(max_epoch_lag_ms + 1));
+
+        // Verify that the lagging subscription is terminated, and that we fail
+        // over to the remaining subscription.
+        assert_eq!(consensus_observer.check_active_subscriptions(), vec![
+            peer_network_id_2
+        ]);
+        assert_eq!(
+            consensus_observer
+                .active_observer_subscriptions
+                .keys()
+                .collect::<Vec<_>>(),
+            vec![&peer_network_id_1]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_conflicting_commit_decisions_are_verified() {
+        // Create a mock DB reader with a root ledger info
+        let current_epoch = 10;
+        let mut mock_db_reader = MockDatabaseReader::new();
+        mock_db_reader
+            .expect_get_latest_ledger_info()
+            .returning(move || Ok(create_ledger_info(current_epoch, 0)));
+        mock_db_reader
+            .expect_get_latest_ledger_info_version()
+            .returning(|| Ok(0));
+
+        // Create a consensus observer
+        let network_id = NetworkId::Vfn;
+        let peers_and_metadata = PeersAndMetadata::new(&[network_id]);
+        let time_service = TimeService::mock();
+        let (sync_notification_sender, _sync_notification_receiver) = unbounded_channel();
+        let mut consensus_observer = create_consensus_observer(
+            peers_and_metadata.clone(),
+            Arc::new(mock_db_reader),
+            sync_notification_sender,
+            time_service.clone(),
+        );
+
+        // Set the epoch state for the current epoch (with an empty verifier)
+        let empty_epoch_state = Arc::new(EpochState::new(
+            current_epoch,
+            ValidatorVerifier::new(vec![]),
+        ));
+        consensus_observer.epoch_state = Some(empty_epoch_state.clone());
+
+        // Subscribe to a connected publisher
+        let peer_network_id = PeerNetworkId::new(network_id, PeerId::random());
+        let connection_metadata = ConnectionMetadata::mock(peer_network_id.peer_id());
+        peers_and_metadata
+            .insert_connection_metadata(peer_network_id, connection_metadata)
+            .unwrap();
+        let subscription = ConsensusObserverSubscription::new(
+            consensus_observer.node_config.consensus_observer,
+            consensus_observer.db_reader.clone(),
+            peer_network_id,
+            time_service.clone(),
+        );
+        consensus_observer
+            .active_observer_subscriptions
+            .insert(peer_network_id, subscription);
+
+        // Send a commit decision for a future round and verify that it was recorded
+        let commit_decision = CommitDecision::new(create_ledger_info(current_epoch, 5));
+        consensus_observer
+            .process_network_message(create_commit_decision_message(
+                peer_network_id,
+                commit_decision.clone(),
+            ))
+            .await;
+        assert_eq!(
+            consensus_observer
+                .publisher_cross_checker
+                .check_commit_decision(commit_decision.proof_block_info()),
+            CrossCheckResult::Duplicate
+        );
+
+        // Require a signature from a validator to verify messages
+        let validator_signer = ValidatorSigner::random(None);
+        consensus_observer.epoch_state = Some(Arc::new(EpochState::new(
+            current_epoch,
+            ValidatorVerifier::new_single(validator_signer.author(), validator_signer.public_key()),
+        )));
+
+        // Send a conflicting (unsigned) commit decision and verify that it is
+        // dropped as invalid (and not reported as a disagreement).
+        let num_invalid_messages = get_num_invalid_conflicting_commit_decisions(network_id);
+        let num_disagreements = get_num_commit_decision_disagreements(network_id);
+        let conflicting_commit_decision = CommitDecision::new(create_ledger_info(current_epoch, 5));
+        consensus_observer
+            .process_network_message(create_commit_decision_message(
+                peer_network_id,
+                conflicting_commit_decision.clone(),
+            ))
+            .await;
+        assert_eq!(
+            get_num_invalid_conflicting_commit_decisions(network_id),
+            num_invalid_messages + 1
+        );
+        assert_eq!(
+            get_num_commit_decision_disagreements(network_id),
+            num_disagreements
+        );
+
+        // Verify that a valid conflicting commit decision is reported as a disagreement
+        consensus_observer.epoch_state = Some(empty_epoch_state);
+        consensus_observer
+            .process_network_message(create_commit_decision_message(
+                peer_network_id,
+                conflicting_commit_decision,
+            ))
+            .await;
+        assert_eq!(
+            get_num_invalid_conflicting_commit_decisions(network_id),
+            num_invalid_messages + 1
+        );
+        assert_eq!(
+            get_num_commit_decision_disagreements(network_id),
+            num_disagreements + 1
+        );
+    }
+
+    /// Creates a consensus observer for testing
+    fn create_consensus_observer(
+        peers_and_metadata: Arc<PeersAndMetadata>,
+        db_reader: Arc<dyn DbReader>,
+        sync_notification_sender: UnboundedSender<(u64, Round)>,
+        time_service: TimeService,
+    ) -> ConsensusObserver {
+        let network_client = NetworkClient::new(vec![], vec![], hashmap![], peers_and_metadata);
+        let consensus_observer_client = Arc::new(ConsensusObserverClient::new(network_client));
+        let node_config = NodeConfig {
+            consensus_observer: ConsensusObserverConfig {
+                observer_enabled: true,
+                ..ConsensusObserverConfig::default()
+            },
+            ..NodeConfig::default()
+        };
+
+        ConsensusObserver::new(
+            node_config,
+            consensus_observer_client,
+            db_reader,
+            Arc::new(DummyExecutionClient),
+            sync_notification_sender,
+            None,
+            None,
+            time_service,
+        )
+    }
+
+    /// Creates a commit decision network message from the given peer
+    fn create_commit_decision_message(
+        peer_network_id: PeerNetworkId,
+        commit_decision: CommitDecision,
+    ) -> ConsensusObserverNetworkMessage {
+        ConsensusObserverNetworkMessage::new(
+            peer_network_id,
+            ConsensusObserverDirectSend::CommitDecision(commit_decision),
+        )
+    }
+
+    /// Creates a ledger info (with an empty signature set) for the given epoch and round
+    fn create_ledger_info(epoch: u64, round: Round) -> LedgerInfoWithSignatures {
+        LedgerInfoWithSignatures::new(
+            LedgerInfo::new(
+                BlockInfo::new(
+                    epoch,
+                    round,
+                    HashValue::random(),
+                    HashValue::random(),
+                    round,
+                    0,
+                    None,
+                ),
+                HashValue::random(),
+            ),
+            AggregateSignature::empty(),
+        )
+    }
+
+    /// Returns the number of duplicate commit decisions dropped for the given network
+    fn get_num_duplicate_commit_decisions(network_id: NetworkId) -> u64 {
+        metrics::OBSERVER_DUPLICATE_MESSAGES
+            .with_label_values(&[metrics::COMMIT_DECISION_LABEL, network_id.as_str()])
+            .get()
+    }
+
+    /// Returns the number of commit decision disagreements for the given network
+    fn get_num_commit_decision_disagreements(network_id: NetworkId) -> u64 {
+        metrics::OBSERVER_PUBLISHER_DISAGREEMENTS
+            .with_label_values(&[metrics::COMMIT_DECISION_LABEL, network_id.as_str()])
+            .get()
+    }
+
+    /// Returns the number of invalid conflicting commit decisions for the given network
+    fn get_num_invalid_conflicting_commit_decisions(network_id: NetworkId) -> u64 {
+        metrics::OBSERVER_INVALID_CONFLICTING_MESSAGES
+            .with_label_values(&[metrics::COMMIT_DECISION_LABEL, network_id.as_str()])
+            .get()
+    }
+}
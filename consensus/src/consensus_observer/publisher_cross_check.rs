@@ -0,0 +1,258 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_config::config::ConsensusObserverConfig;
+use aptos_consensus_types::common::Round;
+use aptos_types::block_info::BlockInfo;
+use std::collections::BTreeMap;
+
+/// The result of cross-checking a message against the messages
+/// already accepted from the subscribed publishers.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CrossCheckResult {
+    /// The message has not been seen before
+    New,
+    /// The message matches a previously accepted message
+    Duplicate,
+    /// The message conflicts with a previously accepted message (which is returned)
+    Conflict(BlockInfo),
+}
+
+/// A simple struct that tracks the ordered blocks and commit decisions accepted
+/// by the consensus observer. This allows the observer to drop duplicate messages
+/// (e.g., when subscribed to several publishers) and detect disagreements
+/// between publishers. Note: only messages that have been verified should be recorded.
+pub struct PublisherCrossChecker {
+    // The configuration of the consensus observer
+    consensus_observer_config: ConsensusObserverConfig,
+
+    // The block info of the accepted ordered blocks (keyed by proof epoch and round)
+    ordered_blocks: BTreeMap<(u64, Round), BlockInfo>,
+
+    // The block info of the accepted commit decisions (keyed by commit epoch and round)
+    commit_decisions: BTreeMap<(u64, Round), BlockInfo>,
+}
+
+impl PublisherCrossChecker {
+    pub fn new(consensus_observer_config: ConsensusObserverConfig) -> Self {
+        Self {
+            consensus_observer_config,
+            ordered_blocks: BTreeMap::new(),
+            commit_decisions: BTreeMap::new(),
+        }
+    }
+
+    /// Cross-checks the given commit decision block info against the accepted commit
+    /// decisions. Note: the entire block info (including the execution results) must match.
+    pub fn check_commit_decision(&self, commit_info: &BlockInfo) -> CrossCheckResult {
+        cross_check(&self.commit_decisions, commit_info, |accepted, received| {
+            accepted == received
+        })
+    }
+
+    /// Cross-checks the given ordered proof block info against the accepted ordered
+    /// blocks. Note: only the block IDs must match, as ordered blocks are not yet executed.
+    pub fn check_ordered_block(&self, proof_block_info: &BlockInfo) -> CrossCheckResult {
+        cross_check(
+            &self.ordered_blocks,
+            proof_block_info,
+            |accepted, received| accepted.id() == received.id(),
+        )
+    }
+
+    /// Records the given (verified) commit decision block info
+    pub fn record_commit_decision(&mut self, commit_info: &BlockInfo) {
+        let max_num_entries = self.max_num_entries();
+        record(&mut self.commit_decisions, commit_info, max_num_entries);
+    }
+
+    /// Records the given (verified) ordered proof block info
+    pub fn record_ordered_block(&mut self, proof_block_info: &BlockInfo) {
+        let max_num_entries = self.max_num_entries();
+        record(&mut self.ordered_blocks, proof_block_info, max_num_entries);
+    }
+
+    /// Returns the maximum number of entries to track for each message type
+    fn max_num_entries(&self) -> usize {
+        self.consensus_observer_config.max_num_pending_blocks as usize
+    }
+}
+
+/// Cross-checks the block info against the accepted entries
+fn cross_check(
+    accepted_entries: &BTreeMap<(u64, Round), BlockInfo>,
+    block_info: &BlockInfo,
+    matches: impl Fn(&BlockInfo, &BlockInfo) -> bool,
+) -> CrossCheckResult {
+    match accepted_entries.get(&(block_info.epoch(), block_info.round())) {
+        Some(accepted_block_info) if matches(accepted_block_info, block_info) => {
+            CrossCheckResult::Duplicate
+        },
+        Some(accepted_block_info) => CrossCheckResult::Conflict(accepted_block_info.clone()),
+        None => CrossCheckResult::New,
+    }
+}
+
+/// Records the block info in the accepted entries, and removes
+/// the oldest entries if the maximum number of entries is exceeded.
+fn record(
+    accepted_entries: &mut BTreeMap<(u64, Round), BlockInfo>,
+    block_info: &BlockInfo,
+    max_num_entries: usize,
+) {
+    accepted_entries.insert((block_info.epoch(), block_info.round()), block_info.clone());
+    while accepted_entries.len() > max_num_entries {
+        accepted_entries.pop_first();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aptos_crypto::HashValue;
+
+    #[test]
+    fn test_cross_check_ordered_blocks() {
+        // Create a new cross checker
+        let mut cross_checker = PublisherCrossChecker::new(ConsensusObserverConfig::default());
+
+        // Verify that an unseen ordered block is new
+        let block_info = create_block_info(1, 10);
+        assert_eq!(
+            cross_checker.check_ordered_block(&block_info),
+            CrossCheckResult::New
+        );
+
+        // Record the ordered block and verify that it is now a duplicate
+        cross_checker.record_ordered_block(&block_info);
+        assert_eq!(
+            cross_checker.check_ordered_block(&block_info),
+            CrossCheckResult::Duplicate
+        );
+
+        // Verify that a different block for the same epoch and round is a conflict
+        let conflicting_block_info = create_block_info(1, 10);
+        assert_eq!(
+            cross_checker.check_ordered_block(&conflicting_block_info),
+            CrossCheckResult::Conflict(block_info.clone())
+        );
+
+        // Verify that the same block with different execution results is a duplicate
+        let executed_block_info = BlockInfo::new(
+            block_info.epoch(),
+            block_info.round(),
+            block_info.id(),
+            HashValue::random(),
+            block_info.version() + 1,
+            block_info.timestamp_usecs(),
+            None,
+        );
+        assert_eq!(
+            cross_checker.check_ordered_block(&executed_block_info),
+            CrossCheckResult::Duplicate
+        );
+
+        // Verify that the same block is new for commit decisions
+        assert_eq!(
+            cross_checker.check_commit_decision(&block_info),
+            CrossCheckResult::New
+        );
+    }
+
+    #[test]
+    fn test_cross_check_commit_decisions() {
+        // Create a new cross checker
+        let mut cross_checker = PublisherCrossChecker::new(ConsensusObserverConfig::default());
+
+        // Record several commit decisions
+        let block_infos: Vec<_> = (0..10).map(|round| create_block_info(2, round)).collect();
+        for block_info in &block_infos {
+            assert_eq!(
+                cross_checker.check_commit_decision(block_info),
+                CrossCheckResult::New
+            );
+            cross_checker.record_commit_decision(block_info);
+        }
+
+        // Verify that all commit decisions are duplicates
+        for block_info in &block_infos {
+            assert_eq!(
+                cross_checker.check_commit_decision(block_info),
+                CrossCheckResult::Duplicate
+            );
+        }
+
+        // Verify that conflicting commit decisions are detected
+        let conflicting_block_info = create_block_info(2, 5);
+        assert_eq!(
+            cross_checker.check_commit_decision(&conflicting_block_info),
+            CrossCheckResult::Conflict(block_infos[5].clone())
+        );
+
+        // Verify that commit decisions with different execution results are conflicts
+        let block_info = &block_infos[3];
+        let conflicting_block_info = BlockInfo::new(
+            block_info.epoch(),
+            block_info.round(),
+            block_info.id(),
+            HashValue::random(),
+            block_info.version(),
+            block_info.timestamp_usecs(),
+            None,
+        );
+        assert_eq!(
+            cross_checker.check_commit_decision(&conflicting_block_info),
+            CrossCheckResult::Conflict(block_info.clone())
+        );
+    }
+
+    #[test]
+    fn test_cross_check_max_entries() {
+        // Create a new cross checker with a small number of max entries
+        let max_num_pending_blocks = 5;
+        let consensus_observer_config = ConsensusObserverConfig {
+            max_num_pending_blocks,
+            ..ConsensusObserverConfig::default()
+        };
+        let mut cross_checker = PublisherCrossChecker::new(consensus_observer_config);
+
+        // Record more ordered blocks than the maximum
+        let num_blocks = max_num_pending_blocks * 2;
+        let block_infos: Vec<_> = (0..num_blocks)
+            .map(|round| create_block_info(0, round))
+            .collect();
+        for block_info in &block_infos {
+            cross_checker.record_ordered_block(block_info);
+        }
+
+        // Verify that only the most recent blocks are tracked
+        assert_eq!(
+            cross_checker.ordered_blocks.len(),
+            max_num_pending_blocks as usize
+        );
+        for (index, block_info) in block_infos.iter().enumerate() {
+            let expected_result = if index < max_num_pending_blocks as usize {
+                CrossCheckResult::New
+            } else {
+                CrossCheckResult::Duplicate
+            };
+            assert_eq!(
+                cross_checker.check_ordered_block(block_info),
+                expected_result
+            );
+        }
+    }
+
+    /// Creates a new block info with a random block ID
+    fn create_block_info(epoch: u64, round: Round) -> BlockInfo {
+        BlockInfo::new(
+            epoch,
+            round,
+            HashValue::random(),
+            HashValue::random(),
+            round,
+            0,
+            None,
+        )
+    }
+}
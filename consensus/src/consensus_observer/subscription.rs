@@ -6,6 +6,7 @@ use crate::consensus_observer::{
     logging::{LogEntry, LogSchema},
 };
 use aptos_config::{config::ConsensusObserverConfig, network_id::PeerNetworkId};
+use aptos_consensus_types::common::Round;
 use aptos_logger::warn;
 use aptos_network::application::metadata::PeerMetadata;
 use aptos_storage_interface::DbReader;
@@ -40,6 +41,13 @@ pub struct ConsensusObserverSubscription {
     // The highest synced version we've seen from storage, along with the time at which it was seen
     highest_synced_version_and_time: (u64, Instant),
 
+    // The highest (verified) ordered block epoch and round received from the peer
+    highest_ordered_epoch_and_round: Option<(u64, Round)>,
+
+    // The time at which the subscription was first seen in an older epoch than the
+    // most up-to-date subscription (this is reset once the subscription catches up).
+    epoch_lag_start_time: Option<Instant>,
+
     // The time service (used to check the last message receive time)
     time_service: TimeService,
 }
@@ -60,6 +68,8 @@ impl ConsensusObserverSubscription {
             last_message_receive_time: time_now,
             last_peer_optimality_check: time_now,
             highest_synced_version_and_time: (0, time_now),
+            highest_ordered_epoch_and_round: None,
+            epoch_lag_start_time: None,
             time_service,
         }
     }
@@ -119,6 +129,58 @@ impl ConsensusObserverSubscription {
         Ok(())
     }
 
+    /// Verifies that the subscription is not lagging too far behind the
+    /// highest ordered epoch and round received across all subscriptions.
+    /// Subscriptions in an older epoch are given a grace period to catch up.
+    pub fn check_subscription_round_lag(
+        &mut self,
+        highest_ordered_epoch_and_round: (u64, Round),
+    ) -> Result<(), Error> {
+        // If we haven't received any ordered blocks, we rely on the subscription timeout
+        let (epoch, round) = match self.highest_ordered_epoch_and_round {
+            Some(epoch_and_round) => epoch_and_round,
+            None => return Ok(()),
+        };
+
+        // If the subscription is in an older epoch, check how long it has been lagging
+        let (highest_epoch, highest_round) = highest_ordered_epoch_and_round;
+        if epoch < highest_epoch {
+            let time_now = self.time_service.now();
+            let epoch_lag_start_time = *self.epoch_lag_start_time.get_or_insert(time_now);
+            let duration_since_epoch_lag = time_now.duration_since(epoch_lag_start_time);
+            if duration_since_epoch_lag
+                > Duration::from_millis(
+                    self.consensus_observer_config.max_subscription_epoch_lag_ms,
+                )
+            {
+                return Err(Error::SubscriptionStalled(format!(
+                    "Subscription to peer: {} is stuck in an old epoch! Highest ordered epoch: {}, latest epoch: {}, elapsed: {:?}",
+                    self.peer_network_id, epoch, highest_epoch, duration_since_epoch_lag
+                )));
+            }
+            return Ok(());
+        }
+
+        // The subscription is no longer in an older epoch
+        self.epoch_lag_start_time = None;
+
+        // Rounds are only comparable within the same epoch
+        if epoch != highest_epoch {
+            return Ok(());
+        }
+
+        // Check if the subscription is lagging too far behind
+        let round_lag = highest_round.saturating_sub(round);
+        if round_lag > self.consensus_observer_config.max_subscription_round_lag {
+            return Err(Error::SubscriptionStalled(format!(
+                "Subscription to peer: {} is lagging behind! Highest ordered round: {}, lag: {}",
+                self.peer_network_id, round, round_lag
+            )));
+        }
+
+        Ok(())
+    }
+
     /// Verifies that the DB is continuing to sync and commit new data
     pub fn check_syncing_progress(&mut self) -> Result<(), Error> {
         // Get the current synced version from storage
@@ -158,11 +220,26 @@ impl ConsensusObserverSubscription {
         Ok(())
     }
 
+    /// Returns the highest (verified) ordered epoch and round received from the peer
+    pub fn get_highest_ordered_epoch_and_round(&self) -> Option<(u64, Round)> {
+        self.highest_ordered_epoch_and_round
+    }
+
     /// Returns the peer network id of the subscription
     pub fn get_peer_network_id(&self) -> PeerNetworkId {
         self.peer_network_id
     }
 
+    /// Updates the highest (verified) ordered epoch and round received from the peer
+    pub fn update_highest_ordered_epoch_and_round(&mut self, epoch: u64, round: Round) {
+        if self
+            .highest_ordered_epoch_and_round
+            .map_or(true, |highest| (epoch, round) > highest)
+        {
+            self.highest_ordered_epoch_and_round = Some((epoch, round));
+        }
+    }
+
     /// Verifies the given message is from the expected peer
     pub fn verify_message_sender(&mut self, peer_network_id: &PeerNetworkId) -> Result<(), Error> {
         // Verify the message is from the expected peer
@@ -363,6 +440,99 @@ mod test {
         assert_eq!(subscription.last_peer_optimality_check, current_time);
     }
 
+    #[test]
+    fn test_check_subscription_round_lag() {
+        // Create a new observer subscription
+        let consensus_observer_config = ConsensusObserverConfig::default();
+        let mut subscription = ConsensusObserverSubscription::new(
+            consensus_observer_config,
+            Arc::new(MockDatabaseReader::new()),
+            PeerNetworkId::random(),
+            TimeService::mock(),
+        );
+
+        // Verify that the subscription is not lagging (no ordered blocks have been received)
+        let max_round_lag = consensus_observer_config.max_subscription_round_lag;
+        assert!(subscription
+            .check_subscription_round_lag((0, max_round_lag * 10))
+            .is_ok());
+
+        // Update the highest ordered round and verify the subscription is not lagging
+        subscription.update_highest_ordered_epoch_and_round(0, 10);
+        assert!(subscription
+            .check_subscription_round_lag((0, 10 + max_round_lag))
+            .is_ok());
+
+        // Verify that older rounds do not update the highest ordered round
+        subscription.update_highest_ordered_epoch_and_round(0, 5);
+        assert_eq!(
+            subscription.get_highest_ordered_epoch_and_round(),
+            Some((0, 10))
+        );
+
+        // Verify that the subscription is lagging if the lag is too large
+        assert!(matches!(
+            subscription.check_subscription_round_lag((0, 10 + max_round_lag + 1)),
+            Err(Error::SubscriptionStalled(_))
+        ));
+
+        // Verify that rounds in different epochs are not compared
+        assert!(subscription
+            .check_subscription_round_lag((1, 10 + max_round_lag + 1))
+            .is_ok());
+
+        // Update the highest ordered round to a new epoch and verify the subscription is not lagging
+        subscription.update_highest_ordered_epoch_and_round(1, 0);
+        assert!(subscription.check_subscription_round_lag((1, 1)).is_ok());
+    }
+
+    #[test]
+    fn test_check_subscription_epoch_lag() {
+        // Create a new observer subscription
+        let consensus_observer_config = ConsensusObserverConfig::default();
+        let time_service = TimeService::mock();
+        let mut subscription = ConsensusObserverSubscription::new(
+            consensus_observer_config,
+            Arc::new(MockDatabaseReader::new()),
+            PeerNetworkId::random(),
+            time_service.clone(),
+        );
+
+        // Update the highest ordered round and verify the subscription
+        // is not stalled (the other subscriptions are in a newer epoch).
+        subscription.update_highest_ordered_epoch_and_round(0, 10);
+        assert!(subscription.check_subscription_round_lag((1, 0)).is_ok());
+
+        // Elapse some time (but less than the epoch lag grace period)
+        let mock_time_service = time_service.into_mock();
+        let max_epoch_lag_ms = consensus_observer_config.max_subscription_epoch_lag_ms;
+        mock_time_service.advance(Duration::from_millis(max_epoch_lag_ms / 2));
+
+        // Verify that the subscription is not stalled yet
+        assert!(subscription.check_subscription_round_lag((1, 5)).is_ok());
+
+        // Elapse enough time to exceed the grace period and verify the subscription is stalled
+        mock_time_service.advance(Duration::from_millis(max_epoch_lag_ms / 2 + 1));
+        assert!(matches!(
+            subscription.check_subscription_round_lag((1, 10)),
+            Err(Error::SubscriptionStalled(_))
+        ));
+
+        // Update the highest ordered round to the new epoch and verify the subscription is not stalled
+        subscription.update_highest_ordered_epoch_and_round(1, 10);
+        assert!(subscription.check_subscription_round_lag((1, 10)).is_ok());
+
+        // Verify that the grace period restarts if the subscription lags behind a new epoch again
+        assert!(subscription.check_subscription_round_lag((2, 0)).is_ok());
+        mock_time_service.advance(Duration::from_millis(max_epoch_lag_ms));
+        assert!(subscription.check_subscription_round_lag((2, 1)).is_ok());
+        mock_time_service.advance(Duration::from_millis(1));
+        assert!(matches!(
+            subscription.check_subscription_round_lag((2, 2)),
+            Err(Error::SubscriptionStalled(_))
+        ));
+    }
+
     #[test]
     fn test_check_subscription_timeout() {
         // Create a new observer subscription
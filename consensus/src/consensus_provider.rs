@@ -3,18 +3,26 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    block_storage::tracing::{BlockTraces, MAX_NUM_BLOCK_TRACES},
     consensus_observer::{
         network_client::ConsensusObserverClient, network_handler::ConsensusObserverNetworkMessage,
         network_message::ConsensusObserverMessage, observer::ConsensusObserver,
         publisher::ConsensusPublisher,
     },
     counters,
+    dag::DagVisualizer,
     epoch_manager::EpochManager,
+    liveness::block_building_simulator::{
+        BlockBuildingSnapshots, MAX_NUM_BLOCK_BUILDING_SNAPSHOTS,
+    },
     network::NetworkTask,
     network_interface::{ConsensusMsg, ConsensusNetworkClient},
     persistent_liveness_storage::StorageWriteProxy,
     pipeline::execution_client::{DummyExecutionClient, ExecutionProxyClient, TExecutionClient},
-    quorum_store::quorum_store_db::QuorumStoreDB,
+    quorum_store::{
+        batch_lifecycle::{BatchLifecycles, MAX_NUM_BATCH_LIFECYCLES},
+        quorum_store_db::QuorumStoreDB,
+    },
     rand::rand_gen::storage::db::RandDb,
     state_computer::ExecutionProxy,
     transaction_filter::TransactionFilter,
@@ -51,11 +59,50 @@ pub fn start_consensus(
     reconfig_events: ReconfigNotificationListener<DbBackedOnChainConfig>,
     vtxn_pool: VTxnPoolState,
     consensus_publisher: Option<Arc<ConsensusPublisher>>,
-) -> (Runtime, Arc<StorageWriteProxy>, Arc<QuorumStoreDB>) {
+) -> (
+    Runtime,
+    Arc<StorageWriteProxy>,
+    Arc<QuorumStoreDB>,
+    Option<Arc<BatchLifecycles>>,
+    Option<Arc<BlockTraces>>,
+    Arc<DagVisualizer>,
+    Option<Arc<BlockBuildingSnapshots>>,
+) {
     let runtime = aptos_runtimes::spawn_named_runtime("consensus".into(), None);
     let storage = Arc::new(StorageWriteProxy::new(node_config, aptos_db.reader.clone()));
     let quorum_store_db = Arc::new(QuorumStoreDB::new(node_config.storage.dir()));
 
+    // Create the batch lifecycle tracker (if enabled)
+    let batch_lifecycles = (node_config
+        .consensus
+        .quorum_store
+        .enable_batch_lifecycle_tracking
+        || node_config
+            .dag_consensus
+            .quorum_store
+            .enable_batch_lifecycle_tracking)
+        .then(|| Arc::new(BatchLifecycles::new(MAX_NUM_BATCH_LIFECYCLES)));
+
+    // Create the block tracer (if enabled)
+    let block_traces = node_config
+        .consensus
+        .enable_block_tracing
+        .then(|| Arc::new(BlockTraces::new(MAX_NUM_BLOCK_TRACES)));
+
+    // Create the DAG visualizer (the DAG of each epoch is registered at bootstrap)
+    let dag_visualizer = Arc::new(DagVisualizer::new());
+
+    // Create the block building snapshot recorder (if enabled)
+    let block_building_snapshots =
+        node_config
+            .consensus
+            .enable_block_building_snapshots
+            .then(|| {
+                Arc::new(BlockBuildingSnapshots::new(
+                    MAX_NUM_BLOCK_BUILDING_SNAPSHOTS,
+                ))
+            });
+
     let txn_notifier = Arc::new(MempoolNotifier::new(
         consensus_to_mempool_sender.clone(),
         node_config.consensus.mempool_executed_txn_timeout_ms,
@@ -92,6 +139,7 @@ pub fn start_consensus(
         rand_storage.clone(),
         node_config.consensus_observer,
         consensus_publisher.clone(),
+        block_traces.clone(),
     ));
 
     let epoch_mgr = EpochManager::new(
@@ -110,6 +158,10 @@ pub fn start_consensus(
         vtxn_pool,
         rand_storage,
         consensus_publisher,
+        batch_lifecycles.clone(),
+        block_traces.clone(),
+        Some(dag_visualizer.clone()),
+        block_building_snapshots.clone(),
     );
 
     let (network_task, network_receiver) = NetworkTask::new(network_service_events, self_receiver);
@@ -118,7 +170,15 @@ pub fn start_consensus(
     runtime.spawn(epoch_mgr.start(timeout_receiver, network_receiver));
 
     debug!("Consensus started.");
-    (runtime, storage, quorum_store_db)
+    (
+        runtime,
+        storage,
+        quorum_store_db,
+        batch_lifecycles,
+        block_traces,
+        dag_visualizer,
+        block_building_snapshots,
+    )
 }
 
 /// A helper function to start the consensus observer
@@ -175,6 +235,7 @@ pub fn start_consensus_observer(
             rand_storage.clone(),
             node_config.consensus_observer,
             consensus_publisher.clone(),
+            None,
         ));
         execution_proxy_client as Arc<dyn TExecutionClient>
     } else {
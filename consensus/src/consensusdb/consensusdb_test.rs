@@ -4,14 +4,22 @@
 
 use self::schema::dag::NodeSchema;
 use super::*;
-use crate::dag::{CertifiedNode, Extensions, Node, Vote};
+use crate::{
+    dag::{CertifiedNode, Extensions, Node, Vote},
+    equivocation_evidence::EquivocationEvidence,
+};
 use aptos_consensus_types::{
     block::block_test_utils::certificate_for_genesis,
     common::{Author, Payload},
+    vote::Vote as ConsensusVote,
+    vote_data::VoteData,
 };
 use aptos_crypto::bls12381::Signature;
 use aptos_temppath::TempPath;
-use aptos_types::aggregate_signature::AggregateSignature;
+use aptos_types::{
+    aggregate_signature::AggregateSignature, block_info::BlockInfo, ledger_info::LedgerInfo,
+    validator_verifier::random_validator_verifier,
+};
 use std::{collections::HashMap, hash::Hash};
 
 #[test]
@@ -116,3 +124,35 @@ fn test_dag() {
     let vote = Vote::new(node.metadata().clone(), Signature::dummy_signature());
     test_dag_type::<DagVoteSchema, <DagVoteSchema as Schema>::Key>(node.id(), vote, &db);
 }
+
+#[test]
+fn test_equivocation_evidence() {
+    let tmp_dir = TempPath::new();
+    let db = ConsensusDB::new(&tmp_dir);
+    let (signers, validator_verifier) = random_validator_verifier(4, None, false);
+
+    // Create two conflicting votes from the same author (for the same round)
+    let create_vote = |signer_index: usize| {
+        let block_info = BlockInfo::new(1, 1, HashValue::random(), HashValue::random(), 0, 0, None);
+        let ledger_info = LedgerInfo::new(block_info.clone(), HashValue::zero());
+        let vote_data = VoteData::new(block_info, BlockInfo::random(0));
+        let signer = &signers[signer_index];
+        ConsensusVote::new(vote_data, signer.author(), ledger_info, signer).unwrap()
+    };
+    let first_vote = create_vote(0);
+    let second_vote = create_vote(0);
+
+    // Verify the evidence proves equivocation
+    let evidence = EquivocationEvidence::new_vote_evidence(first_vote.clone(), second_vote);
+    evidence.verify(&validator_verifier).unwrap();
+
+    // Verify votes from different authors do not prove equivocation
+    let invalid_evidence = EquivocationEvidence::new_vote_evidence(first_vote, create_vote(1));
+    assert!(invalid_evidence.verify(&validator_verifier).is_err());
+
+    // Save the evidence and verify it is deduplicated
+    assert!(db.save_equivocation_evidence(&evidence).unwrap());
+    assert!(!db.save_equivocation_evidence(&evidence).unwrap());
+    let all_evidence = db.get_all_equivocation_evidence().unwrap();
+    assert_eq!(all_evidence, vec![(evidence.key(), evidence)]);
+}
@@ -6,7 +6,10 @@
 mod consensusdb_test;
 mod schema;
 
-use crate::error::DbError;
+use crate::{
+    equivocation_evidence::{EquivocationEvidence, EquivocationEvidenceKey},
+    error::DbError,
+};
 use anyhow::Result;
 use aptos_consensus_types::{block::Block, quorum_cert::QuorumCert};
 use aptos_crypto::HashValue;
@@ -16,12 +19,13 @@ use aptos_storage_interface::AptosDbError;
 pub use schema::{
     block::BlockSchema,
     dag::{CertifiedNodeSchema, DagVoteSchema, NodeSchema},
+    equivocation_evidence::EquivocationEvidenceSchema,
     quorum_certificate::QCSchema,
 };
 use schema::{
     single_entry::{SingleEntryKey, SingleEntrySchema},
-    BLOCK_CF_NAME, CERTIFIED_NODE_CF_NAME, DAG_VOTE_CF_NAME, NODE_CF_NAME, QC_CF_NAME,
-    SINGLE_ENTRY_CF_NAME,
+    BLOCK_CF_NAME, CERTIFIED_NODE_CF_NAME, DAG_VOTE_CF_NAME, EQUIVOCATION_EVIDENCE_CF_NAME,
+    NODE_CF_NAME, QC_CF_NAME, SINGLE_ENTRY_CF_NAME,
 };
 use std::{iter::Iterator, path::Path, time::Instant};
 
@@ -59,6 +63,7 @@ impl ConsensusDB {
             CERTIFIED_NODE_CF_NAME,
             DAG_VOTE_CF_NAME,
             "ordered_anchor_id", // deprecated CF
+            EQUIVOCATION_EVIDENCE_CF_NAME,
         ];
 
         let path = db_root_path.as_ref().join(CONSENSUS_DB_NAME);
@@ -152,6 +157,28 @@ impl ConsensusDB {
         self.commit(batch)
     }
 
+    /// Persists the given equivocation evidence. Evidence is deduplicated by
+    /// key, so only the first evidence for each equivocation is stored.
+    /// Returns true iff the evidence was new (i.e., it was not already stored).
+    pub fn save_equivocation_evidence(
+        &self,
+        evidence: &EquivocationEvidence,
+    ) -> Result<bool, DbError> {
+        let key = evidence.key();
+        if self.get::<EquivocationEvidenceSchema>(&key)?.is_some() {
+            return Ok(false);
+        }
+        self.put::<EquivocationEvidenceSchema>(&key, evidence)?;
+        Ok(true)
+    }
+
+    /// Returns all persisted equivocation evidence
+    pub fn get_all_equivocation_evidence(
+        &self,
+    ) -> Result<Vec<(EquivocationEvidenceKey, EquivocationEvidence)>, DbError> {
+        self.get_all::<EquivocationEvidenceSchema>()
+    }
+
     /// Write the whole schema batch including all data necessary to mutate the ledger
     /// state of some transaction by leveraging rocksdb atomicity support.
     fn commit(&self, batch: SchemaBatch) -> Result<(), DbError> {
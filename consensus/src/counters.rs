@@ -573,6 +573,16 @@ pub static QC_ROUNDS_COUNT: Lazy<IntCounter> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Count of the new equivocation evidence persisted since last restart (by equivocation kind).
+pub static EQUIVOCATION_EVIDENCE_SAVED_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_consensus_equivocation_evidence_saved_count",
+        "Count of the new equivocation evidence persisted since last restart (by equivocation kind).",
+        &["kind"]
+    )
+    .unwrap()
+});
+
 /// Count of the timeout rounds since last restart (close to 0 in happy path).
 pub static TIMEOUT_ROUNDS_COUNT: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!(
@@ -3,7 +3,10 @@
 
 use super::{
     dag_store::DagStore,
-    observability::counters::{NUM_NODES_PER_BLOCK, NUM_ROUNDS_PER_BLOCK},
+    observability::{
+        counters::{NUM_NODES_PER_BLOCK, NUM_ROUNDS_PER_BLOCK},
+        visualization::DagVisualizer,
+    },
 };
 use crate::{
     block_storage::tracing::{observe_block, BlockStage},
@@ -101,6 +104,7 @@ pub(super) struct OrderedNotifierAdapter {
     ledger_info_provider: Arc<RwLock<LedgerInfoProvider>>,
     block_ordered_ts: Arc<RwLock<BTreeMap<Round, Instant>>>,
     allow_batches_without_pos_in_proposal: bool,
+    dag_visualizer: Option<Arc<DagVisualizer>>,
 }
 
 impl OrderedNotifierAdapter {
@@ -111,6 +115,7 @@ impl OrderedNotifierAdapter {
         parent_block_info: BlockInfo,
         ledger_info_provider: Arc<RwLock<LedgerInfoProvider>>,
         allow_batches_without_pos_in_proposal: bool,
+        dag_visualizer: Option<Arc<DagVisualizer>>,
     ) -> Self {
         Self {
             executor_channel,
@@ -120,6 +125,7 @@ impl OrderedNotifierAdapter {
             ledger_info_provider,
             block_ordered_ts: Arc::new(RwLock::new(BTreeMap::new())),
             allow_batches_without_pos_in_proposal,
+            dag_visualizer,
         }
     }
 
@@ -201,6 +207,7 @@ impl OrderedNotifier for OrderedNotifierAdapter {
         let block_info = block.block_info();
         let ledger_info_provider = self.ledger_info_provider.clone();
         let dag = self.dag.clone();
+        let dag_visualizer = self.dag_visualizer.clone();
         *self.parent_block_info.write() = block_info.clone();
 
         self.block_ordered_ts
@@ -223,6 +230,12 @@ impl OrderedNotifier for OrderedNotifierAdapter {
                         .write()
                         .retain(|&round, _| round > commit_decision.commit_info().round());
                     dag.commit_callback(commit_decision.commit_info().round());
+                    if let Some(dag_visualizer) = &dag_visualizer {
+                        dag_visualizer.record_commit(
+                            commit_decision.commit_info().epoch(),
+                            commit_decision.commit_info().round(),
+                        );
+                    }
                     ledger_info_provider
                         .write()
                         .notify_commit_proof(commit_decision);
@@ -22,7 +22,10 @@ use crate::{
         adapter::{compute_initial_block_and_ledger_info, LedgerInfoProvider},
         anchor_election::{LeaderReputationAdapter, MetadataBackendAdapter},
         dag_state_sync::{SyncModeMessageHandler, SyncOutcome},
-        observability::logging::{LogEvent, LogSchema},
+        observability::{
+            logging::{LogEvent, LogSchema},
+            visualization::DagVisualizer,
+        },
         round_state::{AdaptiveResponsive, RoundState},
     },
     liveness::{
@@ -340,6 +343,7 @@ pub struct DagBootstrapper {
     jwk_consensus_config: OnChainJWKConsensusConfig,
     executor: BoundedExecutor,
     allow_batches_without_pos_in_proposal: bool,
+    dag_visualizer: Option<Arc<DagVisualizer>>,
 }
 
 impl DagBootstrapper {
@@ -365,6 +369,7 @@ impl DagBootstrapper {
         jwk_consensus_config: OnChainJWKConsensusConfig,
         executor: BoundedExecutor,
         allow_batches_without_pos_in_proposal: bool,
+        dag_visualizer: Option<Arc<DagVisualizer>>,
     ) -> Self {
         Self {
             self_peer,
@@ -387,6 +392,7 @@ impl DagBootstrapper {
             jwk_consensus_config,
             executor,
             allow_batches_without_pos_in_proposal,
+            dag_visualizer,
         }
     }
 
@@ -529,6 +535,14 @@ impl DagBootstrapper {
             initial_round,
             dag_window_size_config,
         ));
+        if let Some(dag_visualizer) = &self.dag_visualizer {
+            dag_visualizer.register(
+                self.epoch_state.clone(),
+                dag.clone(),
+                anchor_election.clone(),
+                commit_round,
+            );
+        }
 
         let ordered_notifier = Arc::new(OrderedNotifierAdapter::new(
             self.ordered_nodes_tx.clone(),
@@ -537,6 +551,7 @@ impl DagBootstrapper {
             parent_block_info,
             ledger_info_provider.clone(),
             self.allow_batches_without_pos_in_proposal,
+            self.dag_visualizer.clone(),
         ));
 
         let order_rule = Arc::new(Mutex::new(OrderRule::new(
@@ -547,6 +562,7 @@ impl DagBootstrapper {
             ordered_notifier.clone(),
             self.onchain_config.dag_ordering_causal_history_window as Round,
             commit_events,
+            self.dag_visualizer.clone(),
         )));
 
         BootstrapBaseState {
@@ -716,6 +732,9 @@ impl DagBootstrapper {
             select! {
                 biased;
                 Ok(ack_tx) = &mut shutdown_rx => {
+                    if let Some(dag_visualizer) = &self.dag_visualizer {
+                        dag_visualizer.clear();
+                    }
                     let _ = ack_tx.send(());
                     info!(LogSchema::new(LogEvent::Shutdown), epoch = self.epoch_state.epoch);
                     return;
@@ -769,6 +788,7 @@ pub(super) fn bootstrap_dag_for_test(
         OnChainJWKConsensusConfig::default_enabled(),
         BoundedExecutor::new(2, Handle::current()),
         true,
+        None,
     );
 
     let (_base_state, handler, fetch_service) = bootstraper.full_bootstrap();
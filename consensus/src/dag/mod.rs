@@ -27,6 +27,10 @@ pub use adapter::{ProofNotifier, StorageAdapter};
 pub use bootstrap::DagBootstrapper;
 pub use commit_signer::DagCommitSigner;
 pub use dag_network::{RpcHandler, RpcWithFallback, TDAGNetworkSender};
+pub use observability::visualization::{
+    DagAnchorExport, DagAnchorStatus, DagExport, DagNodeExport, DagNodeRef, DagNodeStatus,
+    DagVisualizer,
+};
 #[cfg(test)]
 pub use types::Extensions;
 pub use types::{CertifiedNode, DAGMessage, DAGNetworkMessage, DAGRpcResult, Node, NodeId, Vote};
@@ -0,0 +1,431 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::dag::{
+    anchor_election::AnchorElection,
+    dag_store::{DagStore, NodeStatus},
+    CertifiedNode,
+};
+use anyhow::{bail, ensure};
+use aptos_consensus_types::common::{Author, Round};
+use aptos_crypto::HashValue;
+use aptos_infallible::RwLock;
+use aptos_types::epoch_state::EpochState;
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    sync::Arc,
+};
+
+/// The default number of rounds to export (if no window is specified)
+pub const DEFAULT_NUM_EXPORTED_ROUNDS: u64 = 20;
+
+/// The ordering status of a node in the exported DAG
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DagNodeStatus {
+    Unordered,
+    Ordered,
+    Committed,
+}
+
+/// The status of an anchor (i.e., the node elected for a round) in the exported DAG
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DagAnchorStatus {
+    /// The anchor was ordered (and its causal history with it)
+    Ordered,
+    /// The anchor was skipped when a later anchor was ordered
+    Skipped,
+    /// The anchor round has not been ordered or skipped yet
+    Pending,
+}
+
+/// A reference to a node in the exported DAG
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct DagNodeRef {
+    pub round: Round,
+    pub author: Author,
+}
+
+/// A single node in the exported DAG
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct DagNodeExport {
+    pub round: Round,
+    pub author: Author,
+    pub digest: HashValue,
+    pub timestamp_usecs: u64,
+    /// The parents (strong links) of the node
+    pub parents: Vec<DagNodeRef>,
+    /// The validators that signed the node certificate
+    pub certificate_signers: Vec<Author>,
+    pub status: DagNodeStatus,
+    /// The round of the anchor that ordered the node (if known)
+    pub ordered_by_anchor_round: Option<Round>,
+    /// The aggregated voting power of the nodes linking to this node (only for unordered nodes)
+    pub aggregated_strong_voting_power: Option<u128>,
+    pub aggregated_weak_voting_power: Option<u128>,
+}
+
+/// A single anchor round in the exported DAG
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct DagAnchorExport {
+    pub round: Round,
+    /// The author elected (by the anchor election) for the round
+    pub elected_author: Author,
+    /// True iff the elected author's node exists in the DAG
+    pub node_exists: bool,
+    /// True iff the anchor has enough votes to be ordered (only for unordered anchors)
+    pub has_enough_votes: Option<bool>,
+    pub status: DagAnchorStatus,
+}
+
+/// A window of the DAG, exported for debugging and visualization
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct DagExport {
+    pub epoch: u64,
+    pub from_round: Round,
+    pub to_round: Round,
+    pub lowest_round: Round,
+    pub highest_round: Round,
+    pub highest_committed_round: Round,
+    pub lowest_unordered_anchor_round: Round,
+    pub nodes: Vec<DagNodeExport>,
+    pub anchors: Vec<DagAnchorExport>,
+}
+
+impl DagExport {
+    /// Renders the exported DAG in the Graphviz DOT format. Nodes are grouped
+    /// by round, colored by status, and anchors are drawn with a bold border.
+    pub fn to_dot(&self) -> String {
+        let anchors: HashMap<Round, &DagAnchorExport> = self
+            .anchors
+            .iter()
+            .map(|anchor| (anchor.round, anchor))
+            .collect();
+
+        let mut dot = String::new();
+        let _ = writeln!(dot, "digraph dag {{");
+        let _ = writeln!(
+            dot,
+            "  label=\"DAG epoch {} (rounds {} to {}, committed round {})\";",
+            self.epoch, self.from_round, self.to_round, self.highest_committed_round
+        );
+        let _ = writeln!(dot, "  rankdir=RL;");
+        let _ = writeln!(dot, "  node [shape=box, style=filled];");
+
+        // Group the nodes by round
+        let mut nodes_by_round: BTreeMap<Round, Vec<&DagNodeExport>> = BTreeMap::new();
+        for node in &self.nodes {
+            nodes_by_round.entry(node.round).or_default().push(node);
+        }
+        for (round, nodes) in &nodes_by_round {
+            let anchor = anchors.get(round);
+            let round_label = match anchor {
+                Some(anchor) => format!(
+                    "round {} (anchor {}: {:?})",
+                    round,
+                    anchor.elected_author.short_str_lossless(),
+                    anchor.status
+                ),
+                None => format!("round {}", round),
+            };
+            let _ = writeln!(dot, "  subgraph cluster_round_{} {{", round);
+            let _ = writeln!(dot, "    label=\"{}\";", round_label);
+            for node in nodes {
+                let is_anchor = anchor.map_or(false, |anchor| anchor.elected_author == node.author);
+                let _ = writeln!(
+                    dot,
+                    "    {} [label=\"{}\\n{:?}\", fillcolor={}, penwidth={}];",
+                    dot_node_id(node.round, &node.author),
+                    node.author.short_str_lossless(),
+                    node.status,
+                    dot_fill_color(node.status),
+                    if is_anchor { 3 } else { 1 }
+                );
+            }
+            let _ = writeln!(dot, "  }}");
+        }
+
+        // Add the edges between the nodes (parents outside the window are ignored)
+        for node in &self.nodes {
+            for parent in &node.parents {
+                if parent.round >= self.from_round {
+                    let _ = writeln!(
+                        dot,
+                        "  {} -> {};",
+                        dot_node_id(node.round, &node.author),
+                        dot_node_id(parent.round, &parent.author)
+                    );
+                }
+            }
+        }
+
+        let _ = writeln!(dot, "}}");
+        dot
+    }
+}
+
+/// Returns the DOT identifier of the node with the given round and author
+fn dot_node_id(round: Round, author: &Author) -> String {
+    format!("\"{}_{}\"", round, author)
+}
+
+/// Returns the DOT fill color for the given node status
+fn dot_fill_color(status: DagNodeStatus) -> &'static str {
+    match status {
+        DagNodeStatus::Unordered => "white",
+        DagNodeStatus::Ordered => "lightblue",
+        DagNodeStatus::Committed => "palegreen",
+    }
+}
+
+/// The DAG state tracked by the visualizer
+#[derive(Clone)]
+struct VisualizerState {
+    epoch_state: Arc<EpochState>,
+    dag: Arc<DagStore>,
+    anchor_election: Arc<dyn AnchorElection>,
+    /// The elected authors and statuses of the ordered and skipped anchor rounds
+    anchors: BTreeMap<Round, (Author, DagAnchorStatus)>,
+    /// The anchor rounds that ordered each node (keyed by node round and author)
+    node_anchor_rounds: HashMap<(Round, Author), Round>,
+    lowest_unordered_anchor_round: Round,
+    highest_committed_round: Round,
+}
+
+/// Tracks the DAG of the current epoch, and the ordering decisions made
+/// over it, so that a window of the DAG can be exported for inspection.
+/// Note: the visualizer never holds its own lock while reading the DAG.
+pub struct DagVisualizer {
+    state: RwLock<Option<VisualizerState>>,
+}
+
+impl DagVisualizer {
+    pub fn new() -> Self {
+        Self {
+            state: RwLock::new(None),
+        }
+    }
+
+    /// Registers the DAG (and anchor election) of a new epoch or bootstrap
+    pub fn register(
+        &self,
+        epoch_state: Arc<EpochState>,
+        dag: Arc<DagStore>,
+        anchor_election: Arc<dyn AnchorElection>,
+        highest_committed_round: Round,
+    ) {
+        *self.state.write() = Some(VisualizerState {
+            epoch_state,
+            dag,
+            anchor_election,
+            anchors: BTreeMap::new(),
+            node_anchor_rounds: HashMap::new(),
+            lowest_unordered_anchor_round: highest_committed_round + 1,
+            highest_committed_round,
+        });
+    }
+
+    /// Drops the registered DAG (e.g., at the end of an epoch)
+    pub fn clear(&self) {
+        *self.state.write() = None;
+    }
+
+    /// Records that the given anchor ordered the given nodes (and skipped the failed anchors)
+    pub fn record_ordered_anchor(
+        &self,
+        anchor: &CertifiedNode,
+        failed_authors_and_rounds: &[(Round, Author)],
+        ordered_nodes: &[Arc<CertifiedNode>],
+    ) {
+        let mut state = self.state.write();
+        let Some(state) = state.as_mut() else {
+            return;
+        };
+        if state.epoch_state.epoch != anchor.epoch() {
+            return; // The anchor is from a different DAG
+        }
+
+        for (round, author) in failed_authors_and_rounds {
+            state
+                .anchors
+                .insert(*round, (*author, DagAnchorStatus::Skipped));
+        }
+        state
+            .anchors
+            .insert(anchor.round(), (*anchor.author(), DagAnchorStatus::Ordered));
+        for node in ordered_nodes {
+            state
+                .node_anchor_rounds
+                .insert((node.round(), *node.author()), anchor.round());
+        }
+        state.lowest_unordered_anchor_round = anchor.round() + 1;
+    }
+
+    /// Records the commit of the given round, and prunes the state below the DAG window
+    pub fn record_commit(&self, epoch: u64, commit_round: Round) {
+        let dag = match self.state.read().as_ref() {
+            Some(state) if state.epoch_state.epoch == epoch => state.dag.clone(),
+            _ => return,
+        };
+        let lowest_round = dag.read().lowest_round();
+
+        let mut state = self.state.write();
+        let Some(state) = state.as_mut() else {
+            return;
+        };
+        if state.epoch_state.epoch != epoch {
+            return; // A new DAG was registered in the meantime
+        }
+        state.highest_committed_round = state.highest_committed_round.max(commit_round);
+        state.anchors = state.anchors.split_off(&lowest_round);
+        state
+            .node_anchor_rounds
+            .retain(|(round, _), _| *round >= lowest_round);
+    }
+
+    /// Exports the DAG between the given rounds (inclusive). By default,
+    /// the most recent `DEFAULT_NUM_EXPORTED_ROUNDS` rounds are exported.
+    pub fn export(
+        &self,
+        from_round: Option<Round>,
+        to_round: Option<Round>,
+    ) -> anyhow::Result<DagExport> {
+        // Clone the state so that the lock isn't held while reading the DAG
+        let state = match self.state.read().as_ref() {
+            Some(state) => state.clone(),
+            None => bail!("The DAG is not running!"),
+        };
+
+        let dag_reader = state.dag.read();
+        let lowest_round = dag_reader.lowest_round();
+        let highest_round = dag_reader.highest_round();
+        let to_round = to_round.unwrap_or(highest_round).min(highest_round);
+        let from_round = from_round
+            .unwrap_or_else(|| to_round.saturating_sub(DEFAULT_NUM_EXPORTED_ROUNDS - 1))
+            .max(lowest_round);
+        ensure!(
+            from_round <= to_round,
+            "Invalid round window! From round: {}, to round: {}, DAG rounds: [{}, {}]",
+            from_round,
+            to_round,
+            lowest_round,
+            highest_round
+        );
+
+        // Export the nodes in the window
+        let validators = state.epoch_state.verifier.get_ordered_account_addresses();
+        let mut nodes = vec![];
+        for round in from_round..=to_round {
+            for author in &validators {
+                let Some(node_status) = dag_reader.get_node_ref(round, author) else {
+                    continue;
+                };
+                let node = node_status.as_node();
+                let ordered_by_anchor_round =
+                    state.node_anchor_rounds.get(&(round, *author)).copied();
+                let (status, aggregated_strong_voting_power, aggregated_weak_voting_power) =
+                    match node_status {
+                        NodeStatus::Unordered {
+                            aggregated_weak_voting_power,
+                            aggregated_strong_voting_power,
+                            ..
+                        } => (
+                            DagNodeStatus::Unordered,
+                            Some(*aggregated_strong_voting_power),
+                            Some(*aggregated_weak_voting_power),
+                        ),
+                        NodeStatus::Ordered(_) => {
+                            // Nodes ordered before the DAG was registered were
+                            // recovered from commit events, so they are committed.
+                            let ordering_round = ordered_by_anchor_round.unwrap_or(round);
+                            let status = if ordering_round <= state.highest_committed_round {
+                                DagNodeStatus::Committed
+                            } else {
+                                DagNodeStatus::Ordered
+                            };
+                            (status, None, None)
+                        },
+                    };
+                nodes.push(DagNodeExport {
+                    round,
+                    author: *author,
+                    digest: node.digest(),
+                    timestamp_usecs: node.timestamp(),
+                    parents: node
+                        .parents_metadata()
+                        .map(|parent| DagNodeRef {
+                            round: parent.round(),
+                            author: *parent.author(),
+                        })
+                        .collect(),
+                    certificate_signers: node.certificate().signers(&validators),
+                    status,
+                    ordered_by_anchor_round,
+                    aggregated_strong_voting_power,
+                    aggregated_weak_voting_power,
+                });
+            }
+        }
+
+        // Export the ordered and skipped anchors in the window
+        let mut anchors: Vec<_> = state
+            .anchors
+            .range(from_round..=to_round)
+            .map(|(round, (elected_author, status))| (*round, *elected_author, *status))
+            .collect();
+
+        // Export the pending anchors in the window (i.e., the rounds the order rule will check next)
+        let mut pending_round = state.lowest_unordered_anchor_round;
+        while pending_round <= to_round {
+            if pending_round >= from_round {
+                let elected_author = state.anchor_election.get_anchor(pending_round);
+                anchors.push((pending_round, elected_author, DagAnchorStatus::Pending));
+            }
+            pending_round += 2;
+        }
+
+        let anchors = anchors
+            .into_iter()
+            .map(|(round, elected_author, status)| {
+                let node_status = dag_reader.get_node_ref(round, &elected_author);
+                let has_enough_votes = match node_status {
+                    Some(node_status @ NodeStatus::Unordered { .. }) => {
+                        Some(dag_reader.check_votes_for_node(
+                            node_status.as_node().metadata(),
+                            &state.epoch_state.verifier,
+                        ))
+                    },
+                    _ => None,
+                };
+                DagAnchorExport {
+                    round,
+                    elected_author,
+                    node_exists: node_status.is_some(),
+                    has_enough_votes,
+                    status,
+                }
+            })
+            .collect();
+
+        Ok(DagExport {
+            epoch: state.epoch_state.epoch,
+            from_round,
+            to_round,
+            lowest_round,
+            highest_round,
+            highest_committed_round: state.highest_committed_round,
+            lowest_unordered_anchor_round: state.lowest_unordered_anchor_round,
+            nodes,
+            anchors,
+        })
+    }
+}
+
+impl Default for DagVisualizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
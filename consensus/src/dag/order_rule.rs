@@ -9,6 +9,7 @@ use crate::dag::{
     observability::{
         logging::{LogEvent, LogSchema},
         tracing::{observe_node, NodeStage},
+        visualization::DagVisualizer,
     },
     storage::CommitEvent,
     types::NodeMetadata,
@@ -33,6 +34,7 @@ pub struct OrderRule {
     anchor_election: Arc<dyn AnchorElection>,
     notifier: Arc<dyn OrderedNotifier>,
     dag_window_size_config: Round,
+    dag_visualizer: Option<Arc<DagVisualizer>>,
 }
 
 impl OrderRule {
@@ -44,6 +46,7 @@ impl OrderRule {
         notifier: Arc<dyn OrderedNotifier>,
         dag_window_size_config: Round,
         commit_events: Option<Vec<CommitEvent>>,
+        dag_visualizer: Option<Arc<DagVisualizer>>,
     ) -> Self {
         if let Some(commit_events) = commit_events {
             // make sure it's sorted
@@ -72,6 +75,7 @@ impl OrderRule {
             anchor_election,
             notifier,
             dag_window_size_config,
+            dag_visualizer,
         };
         // re-check if anything can be ordered to recover pending anchors
         order_rule.process_all();
@@ -217,6 +221,14 @@ impl OrderRule {
             ordered_nodes.len()
         );
 
+        if let Some(dag_visualizer) = &self.dag_visualizer {
+            dag_visualizer.record_ordered_anchor(
+                &anchor,
+                &failed_authors_and_rounds,
+                &ordered_nodes,
+            );
+        }
+
         self.lowest_unordered_anchor_round = anchor.round() + 1;
         self.notifier
             .send_ordered_nodes(ordered_nodes, failed_authors_and_rounds);
@@ -175,6 +175,7 @@ fn setup(
         Arc::new(TestNotifier { tx }),
         TEST_DAG_WINDOW as Round,
         None,
+        None,
     )));
 
     let fetch_requester = Arc::new(MockFetchRequester {});
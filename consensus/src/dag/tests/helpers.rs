@@ -24,6 +24,8 @@ pub(super) struct MockPayloadManager {}
 impl TPayloadManager for MockPayloadManager {
     fn prefetch_payload_data(&self, _payload: &Payload, _timestamp: u64) {}
 
+    fn notify_block_inserted(&self, _block: &Block) {}
+
     fn notify_commit(&self, _block_timestamp: u64, _payloads: Vec<Payload>) {}
 
     fn check_payload_availability(&self, _block: &Block) -> bool {
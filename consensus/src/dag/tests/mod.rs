@@ -11,3 +11,4 @@ mod integration_tests;
 mod order_rule_tests;
 mod rb_handler_tests;
 mod types_test;
+mod visualization_tests;
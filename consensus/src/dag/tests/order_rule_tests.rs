@@ -108,6 +108,7 @@ fn create_order_rule(
             Arc::new(TestNotifier { tx }),
             TEST_DAG_WINDOW as Round,
             None,
+            None,
         ),
         rx,
     )
@@ -0,0 +1,178 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::dag::{
+    anchor_election::{AnchorElection, RoundRobinAnchorElection},
+    dag_store::{DagStore, InMemDag},
+    observability::visualization::{DagAnchorStatus, DagNodeStatus, DagVisualizer},
+    tests::{
+        dag_test::MockStorage,
+        helpers::{new_certified_node, MockPayloadManager, TEST_DAG_WINDOW},
+    },
+    types::NodeCertificate,
+};
+use aptos_consensus_types::common::{Author, Round};
+use aptos_types::{epoch_state::EpochState, validator_verifier::random_validator_verifier};
+use std::sync::Arc;
+
+const NUM_ROUNDS: Round = 3;
+const NUM_VALIDATORS: usize = 4;
+
+/// Creates a fully connected DAG (with the given number of rounds) and the anchor election
+fn create_dag() -> (
+    Arc<EpochState>,
+    Arc<DagStore>,
+    Arc<dyn AnchorElection>,
+    Vec<Author>,
+) {
+    let (_, validator_verifier) = random_validator_verifier(NUM_VALIDATORS, None, false);
+    let validators = validator_verifier.get_ordered_account_addresses();
+    let epoch_state = Arc::new(EpochState {
+        epoch: 1,
+        verifier: validator_verifier,
+    });
+
+    let mut dag = InMemDag::new_empty(epoch_state.clone(), 1, TEST_DAG_WINDOW);
+    let mut parents: Vec<NodeCertificate> = vec![];
+    for round in 1..=NUM_ROUNDS {
+        let round_parents = parents.clone();
+        parents.clear();
+        for author in &validators {
+            let node = new_certified_node(round, *author, round_parents.clone());
+            parents.push(node.certificate());
+            dag.add_node_for_test(node).unwrap();
+        }
+    }
+
+    let dag = Arc::new(DagStore::new_for_test(
+        dag,
+        Arc::new(MockStorage::new()),
+        Arc::new(MockPayloadManager {}),
+    ));
+    let anchor_election: Arc<dyn AnchorElection> =
+        Arc::new(RoundRobinAnchorElection::new(validators.clone()));
+    (epoch_state, dag, anchor_election, validators)
+}
+
+#[test]
+fn test_export_unordered_dag() {
+    // Verify that the export fails if no DAG is registered
+    let visualizer = DagVisualizer::new();
+    assert!(visualizer.export(None, None).is_err());
+
+    // Register the DAG and export it
+    let (epoch_state, dag, anchor_election, validators) = create_dag();
+    visualizer.register(epoch_state, dag, anchor_election.clone(), 0);
+    let export = visualizer.export(None, None).unwrap();
+
+    // Verify the exported nodes
+    assert_eq!(export.from_round, 1);
+    assert_eq!(export.to_round, NUM_ROUNDS);
+    assert_eq!(export.nodes.len(), NUM_VALIDATORS * NUM_ROUNDS as usize);
+    for node in &export.nodes {
+        assert_eq!(node.status, DagNodeStatus::Unordered);
+        assert_eq!(node.ordered_by_anchor_round, None);
+        if node.round > 1 {
+            assert_eq!(node.parents.len(), validators.len());
+        }
+    }
+
+    // Verify the pending anchors (only the anchor in round 1 has enough votes)
+    assert_eq!(export.anchors.len(), 2);
+    for (anchor, (round, has_enough_votes)) in export.anchors.iter().zip([(1, true), (3, false)]) {
+        assert_eq!(anchor.round, round);
+        assert_eq!(anchor.elected_author, anchor_election.get_anchor(round));
+        assert_eq!(anchor.status, DagAnchorStatus::Pending);
+        assert!(anchor.node_exists);
+        assert_eq!(anchor.has_enough_votes, Some(has_enough_votes));
+    }
+
+    // Clear the DAG (e.g., at the end of the epoch) and verify the export fails
+    visualizer.clear();
+    assert!(visualizer.export(None, None).is_err());
+}
+
+#[test]
+fn test_export_ordered_and_committed_dag() {
+    // Register the DAG
+    let visualizer = DagVisualizer::new();
+    let (epoch_state, dag, anchor_election, _) = create_dag();
+    visualizer.register(epoch_state, dag.clone(), anchor_election.clone(), 0);
+
+    // Order the anchor in the last round (skipping the anchor in round 1)
+    let anchor = dag
+        .read()
+        .get_node_by_round_author(NUM_ROUNDS, &anchor_election.get_anchor(NUM_ROUNDS))
+        .cloned()
+        .unwrap();
+    let ordered_nodes: Vec<_> = dag
+        .write()
+        .reachable_mut(&anchor, None)
+        .map(|node_status| {
+            node_status.mark_as_ordered();
+            node_status.as_node().clone()
+        })
+        .collect();
+    let failed_authors_and_rounds = vec![(1, anchor_election.get_anchor(1))];
+    visualizer.record_ordered_anchor(&anchor, &failed_authors_and_rounds, &ordered_nodes);
+
+    // Verify the ordered nodes and anchors
+    let export = visualizer.export(None, None).unwrap();
+    assert_eq!(export.lowest_unordered_anchor_round, NUM_ROUNDS + 1);
+    let num_ordered_nodes = export
+        .nodes
+        .iter()
+        .filter(|node| node.status == DagNodeStatus::Ordered)
+        .inspect(|node| assert_eq!(node.ordered_by_anchor_round, Some(NUM_ROUNDS)))
+        .count();
+    assert_eq!(num_ordered_nodes, ordered_nodes.len());
+    let anchor_statuses: Vec<_> = export
+        .anchors
+        .iter()
+        .map(|anchor| (anchor.round, anchor.status))
+        .collect();
+    assert_eq!(anchor_statuses, vec![
+        (1, DagAnchorStatus::Skipped),
+        (NUM_ROUNDS, DagAnchorStatus::Ordered)
+    ]);
+
+    // Verify that commits for other epochs are ignored
+    visualizer.record_commit(100, NUM_ROUNDS);
+    let export = visualizer.export(None, None).unwrap();
+    assert_eq!(export.highest_committed_round, 0);
+
+    // Commit the anchor and verify the ordered nodes are now committed
+    visualizer.record_commit(1, NUM_ROUNDS);
+    let export = visualizer.export(None, None).unwrap();
+    assert_eq!(export.highest_committed_round, NUM_ROUNDS);
+    for node in &export.nodes {
+        assert_ne!(node.status, DagNodeStatus::Ordered);
+    }
+    let json = serde_json::to_value(&export).unwrap();
+    assert_eq!(json["nodes"][0]["status"], "committed");
+}
+
+#[test]
+fn test_export_window_and_dot() {
+    // Register the DAG
+    let visualizer = DagVisualizer::new();
+    let (epoch_state, dag, anchor_election, validators) = create_dag();
+    visualizer.register(epoch_state, dag, anchor_election, 0);
+
+    // Export a window of the DAG and verify only the nodes in the window are exported
+    let export = visualizer.export(Some(2), Some(NUM_ROUNDS)).unwrap();
+    assert_eq!(export.nodes.len(), NUM_VALIDATORS * 2);
+    assert!(export.nodes.iter().all(|node| node.round >= 2));
+
+    // Verify the DOT output (edges to parents outside the window are dropped)
+    let dot = export.to_dot();
+    assert!(dot.starts_with("digraph dag {"));
+    assert!(dot.contains("subgraph cluster_round_2"));
+    assert!(dot.contains("subgraph cluster_round_3"));
+    assert!(!dot.contains("subgraph cluster_round_1"));
+    let num_edges = dot.matches(" -> ").count();
+    assert_eq!(num_edges, validators.len() * validators.len());
+
+    // Verify that invalid windows are rejected
+    assert!(visualizer.export(Some(NUM_ROUNDS), Some(1)).is_err());
+}
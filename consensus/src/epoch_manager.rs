@@ -5,14 +5,16 @@
 use crate::{
     block_storage::{
         pending_blocks::PendingBlocks,
-        tracing::{observe_block, BlockStage},
+        tracing::{observe_block, BlockStage, BlockTraces},
         BlockStore,
     },
     consensus_observer::publisher::ConsensusPublisher,
     counters,
-    dag::{DagBootstrapper, DagCommitSigner, StorageAdapter},
+    dag::{DagBootstrapper, DagCommitSigner, DagVisualizer, StorageAdapter},
     error::{error_kind, DbError},
     liveness::{
+        block_building_policy::create_block_building_policy,
+        block_building_simulator::BlockBuildingSnapshots,
         cached_proposer_election::CachedProposerElection,
         leader_reputation::{
             extract_epoch_to_proposers, AptosDBBackend, LeaderReputation,
@@ -41,6 +43,7 @@ use crate::{
     persistent_liveness_storage::{LedgerRecoveryData, PersistentLivenessStorage, RecoveryData},
     pipeline::execution_client::TExecutionClient,
     quorum_store::{
+        batch_lifecycle::BatchLifecycles,
         quorum_store_builder::{DirectMempoolInnerBuilder, InnerBuilder, QuorumStoreBuilder},
         quorum_store_coordinator::CoordinatorCommand,
         quorum_store_db::QuorumStoreStorage,
@@ -178,6 +181,10 @@ pub struct EpochManager<P: OnChainConfigProvider> {
     proof_cache: ProofCache,
     consensus_publisher: Option<Arc<ConsensusPublisher>>,
     pending_blocks: Arc<Mutex<PendingBlocks>>,
+    batch_lifecycles: Option<Arc<BatchLifecycles>>,
+    block_traces: Option<Arc<BlockTraces>>,
+    dag_visualizer: Option<Arc<DagVisualizer>>,
+    block_building_snapshots: Option<Arc<BlockBuildingSnapshots>>,
 }
 
 impl<P: OnChainConfigProvider> EpochManager<P> {
@@ -198,6 +205,10 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
         vtxn_pool: VTxnPoolState,
         rand_storage: Arc<dyn RandStorage<AugmentedData>>,
         consensus_publisher: Option<Arc<ConsensusPublisher>>,
+        batch_lifecycles: Option<Arc<BatchLifecycles>>,
+        block_traces: Option<Arc<BlockTraces>>,
+        dag_visualizer: Option<Arc<DagVisualizer>>,
+        block_building_snapshots: Option<Arc<BlockBuildingSnapshots>>,
     ) -> Self {
         let author = node_config.validator_network.as_ref().unwrap().peer_id();
         let config = node_config.consensus.clone();
@@ -247,6 +258,10 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
                 .build(),
             consensus_publisher,
             pending_blocks: Arc::new(Mutex::new(PendingBlocks::new())),
+            batch_lifecycles,
+            block_traces,
+            dag_visualizer,
+            block_building_snapshots,
         }
     }
 
@@ -712,6 +727,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
                 self.config.safety_rules.backend.clone(),
                 self.quorum_store_storage.clone(),
                 !consensus_config.is_dag_enabled(),
+                self.batch_lifecycles.clone(),
             ))
         } else {
             info!("Building DirectMempool");
@@ -841,6 +857,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
             payload_manager,
             onchain_consensus_config.order_vote_enabled(),
             self.pending_blocks.clone(),
+            self.block_traces.clone(),
         ));
 
         info!(epoch = epoch, "Create ProposalGenerator");
@@ -871,6 +888,8 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
             self.config
                 .quorum_store
                 .allow_batches_without_pos_in_proposal,
+            create_block_building_policy(&self.config.block_building_policy),
+            self.block_building_snapshots.clone(),
         );
         let (round_manager_tx, round_manager_rx) = aptos_channel::new(
             QueueStyle::KLAST,
@@ -1374,6 +1393,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
             self.config
                 .quorum_store
                 .allow_batches_without_pos_in_proposal,
+            self.dag_visualizer.clone(),
         );
 
         let (dag_rpc_tx, dag_rpc_rx) = aptos_channel::new(QueueStyle::FIFO, 10, None);
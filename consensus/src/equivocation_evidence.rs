@@ -0,0 +1,181 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Equivocation evidence is a pair of conflicting messages signed by the same
+//! validator for the same epoch and round (e.g., two votes for different ledger
+//! infos, or two different proposals). The evidence is self-contained, i.e., it
+//! can be verified after the fact using only the validator set of the epoch.
+
+use anyhow::ensure;
+use aptos_consensus_types::{
+    block::Block,
+    common::{Author, Round},
+    vote::Vote,
+};
+use aptos_crypto::hash::CryptoHash;
+use aptos_types::validator_verifier::ValidatorVerifier;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// The kind of message a validator equivocated on
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum EquivocationKind {
+    Vote,
+    Proposal,
+}
+
+impl EquivocationKind {
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            EquivocationKind::Vote => "vote",
+            EquivocationKind::Proposal => "proposal",
+        }
+    }
+}
+
+/// Uniquely identifies an equivocation. At most one piece of evidence is
+/// stored per key (any additional conflicting messages are redundant).
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub struct EquivocationEvidenceKey {
+    pub epoch: u64,
+    pub round: Round,
+    pub author: Author,
+    pub kind: EquivocationKind,
+}
+
+impl Display for EquivocationEvidenceKey {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "[{} equivocation, author: {}, epoch: {}, round: {}]",
+            self.kind.get_label(),
+            self.author,
+            self.epoch,
+            self.round
+        )
+    }
+}
+
+/// Two conflicting messages signed by the same validator for the same round
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum EquivocationEvidence {
+    /// The validator voted for two different ledger infos
+    Vote { first_vote: Vote, second_vote: Vote },
+    /// The validator proposed two different blocks
+    Proposal {
+        first_proposal: Block,
+        second_proposal: Block,
+    },
+}
+
+impl EquivocationEvidence {
+    pub fn new_vote_evidence(first_vote: Vote, second_vote: Vote) -> Self {
+        EquivocationEvidence::Vote {
+            first_vote,
+            second_vote,
+        }
+    }
+
+    pub fn new_proposal_evidence(first_proposal: Block, second_proposal: Block) -> Self {
+        EquivocationEvidence::Proposal {
+            first_proposal,
+            second_proposal,
+        }
+    }
+
+    /// Returns the key that uniquely identifies the equivocation
+    pub fn key(&self) -> EquivocationEvidenceKey {
+        match self {
+            EquivocationEvidence::Vote { first_vote, .. } => EquivocationEvidenceKey {
+                epoch: first_vote.epoch(),
+                round: first_vote.vote_data().proposed().round(),
+                author: first_vote.author(),
+                kind: EquivocationKind::Vote,
+            },
+            EquivocationEvidence::Proposal { first_proposal, .. } => EquivocationEvidenceKey {
+                epoch: first_proposal.epoch(),
+                round: first_proposal.round(),
+                author: first_proposal.author().unwrap_or(Author::ZERO),
+                kind: EquivocationKind::Proposal,
+            },
+        }
+    }
+
+    /// Verifies that the evidence proves equivocation, i.e., both messages
+    /// are correctly signed by the same author for the same epoch and round,
+    /// but the messages conflict.
+    pub fn verify(&self, validator: &ValidatorVerifier) -> anyhow::Result<()> {
+        match self {
+            EquivocationEvidence::Vote {
+                first_vote,
+                second_vote,
+            } => {
+                ensure!(
+                    first_vote.author() == second_vote.author(),
+                    "The votes have different authors"
+                );
+                ensure!(
+                    (
+                        first_vote.epoch(),
+                        first_vote.vote_data().proposed().round()
+                    ) == (
+                        second_vote.epoch(),
+                        second_vote.vote_data().proposed().round()
+                    ),
+                    "The votes are for different epochs or rounds"
+                );
+                ensure!(
+                    first_vote.ledger_info().hash() != second_vote.ledger_info().hash(),
+                    "The votes are for the same ledger info"
+                );
+                first_vote.verify(validator)?;
+                second_vote.verify(validator)
+            },
+            EquivocationEvidence::Proposal {
+                first_proposal,
+                second_proposal,
+            } => {
+                ensure!(
+                    first_proposal.author().is_some(),
+                    "The proposals have no author"
+                );
+                ensure!(
+                    first_proposal.author() == second_proposal.author(),
+                    "The proposals have different authors"
+                );
+                ensure!(
+                    (first_proposal.epoch(), first_proposal.round())
+                        == (second_proposal.epoch(), second_proposal.round()),
+                    "The proposals are for different epochs or rounds"
+                );
+                ensure!(
+                    first_proposal.id() != second_proposal.id(),
+                    "The proposals are for the same block"
+                );
+                first_proposal.validate_signature(validator)?;
+                second_proposal.validate_signature(validator)
+            },
+        }
+    }
+}
+
+/// A structured export of equivocation evidence (e.g., for governance
+/// proposals and monitoring). The evidence is exported in both human
+/// readable (JSON) form and as BCS bytes that can be independently verified.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct EquivocationEvidenceExport {
+    pub key: EquivocationEvidenceKey,
+    pub evidence: EquivocationEvidence,
+    pub evidence_bcs_hex: String,
+}
+
+impl EquivocationEvidenceExport {
+    pub fn new(evidence: EquivocationEvidence) -> anyhow::Result<Self> {
+        let evidence_bcs_hex = hex::encode(bcs::to_bytes(&evidence)?);
+        Ok(Self {
+            key: evidence.key(),
+            evidence,
+            evidence_bcs_hex,
+        })
+    }
+}
@@ -21,6 +21,7 @@ mod block_storage;
 mod consensusdb;
 mod dag;
 mod epoch_manager;
+pub mod equivocation_evidence;
 mod error;
 mod liveness;
 mod logging;
@@ -65,9 +66,22 @@ mod transaction_shuffler;
 mod txn_hash_and_authenticator_deduper;
 
 use aptos_metrics_core::IntGauge;
+pub use block_storage::tracing::{BlockStageEvent, BlockTrace, BlockTraces};
 pub use consensusdb::create_checkpoint;
 /// Required by the smoke tests
 pub use consensusdb::CONSENSUS_DB_NAME;
+pub use dag::{DagExport, DagVisualizer};
+pub use liveness::{
+    block_building_policy::{
+        create_block_building_policy, BackpressureSignals, BatchSelection, BlockBuildingPolicy,
+        BlockLimits, BlockTargets, DefaultBlockBuildingPolicy, PulledBatches,
+        StrictBackpressurePolicy,
+    },
+    block_building_simulator::{
+        load_snapshots, BatchSnapshot, BlockBuildingSimulator, BlockBuildingSnapshot,
+        BlockBuildingSnapshots, PolicyEvaluation, SimulatedBlock,
+    },
+};
 pub use quorum_store::quorum_store_db::QUORUM_STORE_DB_NAME;
 #[cfg(feature = "fuzzing")]
 pub use round_manager::round_manager_fuzzing;
@@ -0,0 +1,409 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_config::config::{
+    BlockBuildingPolicyType, ChainHealthBackoffValues, PipelineBackpressureValues,
+};
+use aptos_consensus_types::{
+    common::{Payload, ProofWithData, ProofWithDataWithTxnLimit},
+    payload::{BatchPointer, OptQuorumStorePayload, TDataInfo},
+    utils::PayloadTxnsSize,
+};
+use serde::{Deserialize, Serialize};
+use std::{sync::Arc, time::Duration};
+
+/// The static block size limits of the proposer (i.e., without any backpressure applied)
+#[derive(Clone, Debug)]
+pub struct BlockLimits {
+    // Max number of transactions (count, bytes) to be added to a proposed block.
+    pub max_block_txns: PayloadTxnsSize,
+    // Max number of unique transactions to be added to a proposed block.
+    pub max_block_txns_after_filtering: u64,
+    // Max number of inline transactions (count, bytes) to be added to a proposed block.
+    pub max_inline_txns: PayloadTxnsSize,
+    // If backpressure target block size is below it, update `max_txns_to_execute` instead.
+    pub min_max_txns_in_block_after_filtering_from_backpressure: u64,
+}
+
+/// The backpressure signals observed by the proposer when building a block
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BackpressureSignals {
+    /// The chain health backoff that applies (if any)
+    pub chain_health_backoff: Option<ChainHealthBackoffValues>,
+    /// The pipeline backpressure that applies (if any)
+    pub pipeline_backpressure: Option<PipelineBackpressureValues>,
+    /// The calibrated block size from execution backpressure (if any)
+    pub execution_backpressure_block_size: Option<u64>,
+}
+
+/// The targets for a single proposed block, as computed by a block building policy
+#[derive(Clone, Debug)]
+pub struct BlockTargets {
+    // Max number of transactions (count, bytes) to pull for the block
+    pub max_block_txns: PayloadTxnsSize,
+    // Max number of unique transactions to pull for the block
+    pub max_block_txns_after_filtering: u64,
+    // Max number of transactions from the block to execute (if limited)
+    pub max_txns_from_block_to_execute: Option<u64>,
+    // Max number of inline transactions (count, bytes) to pull for the block
+    pub max_inline_txns: PayloadTxnsSize,
+    // The percentage of the block to fill with optimistic batches (i.e., without proofs)
+    pub opt_batch_txns_pct: u8,
+    // The delay to wait before pulling the payload
+    pub proposal_delay: Duration,
+}
+
+/// The sizes of the batches pulled for a block, by kind (in the order they were pulled)
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PulledBatches {
+    /// The batches with proofs of store
+    pub proofs: Vec<PayloadTxnsSize>,
+    /// The optimistic batches (i.e., without proofs of store, proposed by reference)
+    pub opt_batches: Vec<PayloadTxnsSize>,
+    /// The inline batches (i.e., without proofs of store, proposed with their transactions)
+    pub inline_batches: Vec<PayloadTxnsSize>,
+}
+
+impl PulledBatches {
+    /// Returns the pulled batches of the given quorum store payload (or None for
+    /// direct mempool payloads, which have no batches).
+    pub fn from_payload(payload: &Payload) -> Option<Self> {
+        let pulled_batches = match payload {
+            Payload::DirectMempool(_) => return None,
+            Payload::InQuorumStore(proof_with_data) => Self {
+                proofs: batch_sizes(&proof_with_data.proofs),
+                ..Default::default()
+            },
+            Payload::InQuorumStoreWithLimit(proof_with_data) => Self {
+                proofs: batch_sizes(&proof_with_data.proof_with_data.proofs),
+                ..Default::default()
+            },
+            Payload::QuorumStoreInlineHybrid(inline_batches, proof_with_data, _) => Self {
+                proofs: batch_sizes(&proof_with_data.proofs),
+                opt_batches: vec![],
+                inline_batches: inline_batches
+                    .iter()
+                    .map(|(batch_info, _)| batch_size(batch_info))
+                    .collect(),
+            },
+            Payload::OptQuorumStore(opt_qs_payload) => Self {
+                proofs: batch_sizes(opt_qs_payload.proof_with_data()),
+                opt_batches: batch_sizes(opt_qs_payload.opt_batches()),
+                inline_batches: batch_sizes(&opt_qs_payload.inline_batches().batch_infos()),
+            },
+        };
+        Some(pulled_batches)
+    }
+}
+
+/// The batches selected for the proposed block (as indices into the pulled batches,
+/// in the order they are proposed), and the number of transactions to execute.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BatchSelection {
+    pub proofs: Vec<usize>,
+    pub opt_batches: Vec<usize>,
+    pub inline_batches: Vec<usize>,
+    pub max_txns_to_execute: Option<u64>,
+}
+
+impl BatchSelection {
+    /// Selects all the pulled batches (in the order they were pulled)
+    pub fn all(pulled_batches: &PulledBatches) -> Self {
+        Self {
+            proofs: (0..pulled_batches.proofs.len()).collect(),
+            opt_batches: (0..pulled_batches.opt_batches.len()).collect(),
+            inline_batches: (0..pulled_batches.inline_batches.len()).collect(),
+            max_txns_to_execute: None,
+        }
+    }
+
+    /// Returns the total size of the selected batches
+    pub fn size(&self, pulled_batches: &PulledBatches) -> PayloadTxnsSize {
+        let mut size = PayloadTxnsSize::zero();
+        for (indices, sizes) in [
+            (&self.proofs, &pulled_batches.proofs),
+            (&self.opt_batches, &pulled_batches.opt_batches),
+            (&self.inline_batches, &pulled_batches.inline_batches),
+        ] {
+            for index in indices {
+                size += sizes[*index];
+            }
+        }
+        size
+    }
+
+    /// Returns true iff all the pulled batches are selected (in the order they were pulled)
+    fn selects_all(&self, pulled_batches: &PulledBatches) -> bool {
+        let all = Self::all(pulled_batches);
+        self.proofs == all.proofs
+            && self.opt_batches == all.opt_batches
+            && self.inline_batches == all.inline_batches
+    }
+}
+
+/// A block building policy decides how the proposer builds a block: the size targets
+/// (and proposal delay) given the current backpressure signals, and the selection (and
+/// ordering) of the pulled batches that are finally proposed. The block building
+/// simulator uses the same hooks, so policies can be evaluated against recorded snapshots.
+pub trait BlockBuildingPolicy: Send + Sync {
+    /// Returns the name of the policy (e.g., for logging and simulation reports)
+    fn name(&self) -> &'static str;
+
+    /// Computes the targets for the next block, given the limits and backpressure signals
+    fn compute_block_targets(
+        &self,
+        limits: &BlockLimits,
+        signals: &BackpressureSignals,
+    ) -> BlockTargets;
+
+    /// Selects (and orders) the batches to propose from the batches pulled using the
+    /// given targets. The selected indices must be valid and unique.
+    fn select_batches(
+        &self,
+        pulled_batches: &PulledBatches,
+        targets: &BlockTargets,
+    ) -> BatchSelection;
+}
+
+/// Selects the payload to propose from the payload pulled using the given targets,
+/// by applying the batch selection of the policy. Direct mempool payloads are not modified.
+pub fn select_payload(
+    policy: &dyn BlockBuildingPolicy,
+    payload: Payload,
+    targets: &BlockTargets,
+) -> Payload {
+    let Some(pulled_batches) = PulledBatches::from_payload(&payload) else {
+        return payload;
+    };
+    let selection = policy.select_batches(&pulled_batches, targets);
+
+    // Avoid rebuilding the payload if all batches are selected
+    if selection.selects_all(&pulled_batches) {
+        return match (payload, selection.max_txns_to_execute) {
+            (payload @ Payload::InQuorumStoreWithLimit(_), _) | (payload, None) => payload,
+            (payload, max_txns_to_execute) => {
+                payload.transform_to_quorum_store_v2(max_txns_to_execute)
+            },
+        };
+    }
+
+    let BatchSelection {
+        proofs,
+        opt_batches,
+        inline_batches,
+        max_txns_to_execute,
+    } = selection;
+    let payload = match payload {
+        payload @ Payload::DirectMempool(_) => return payload,
+        Payload::InQuorumStore(proof_with_data) => Payload::InQuorumStore(ProofWithData::new(
+            select_items(proof_with_data.proofs, &proofs),
+        )),
+        Payload::InQuorumStoreWithLimit(proof_with_data) => {
+            return Payload::InQuorumStoreWithLimit(ProofWithDataWithTxnLimit::new(
+                ProofWithData::new(select_items(
+                    proof_with_data.proof_with_data.proofs,
+                    &proofs,
+                )),
+                max_txns_to_execute.or(proof_with_data.max_txns_to_execute),
+            ));
+        },
+        Payload::QuorumStoreInlineHybrid(inline_batch_txns, proof_with_data, limit) => {
+            Payload::QuorumStoreInlineHybrid(
+                select_items(inline_batch_txns, &inline_batches),
+                ProofWithData::new(select_items(proof_with_data.proofs, &proofs)),
+                limit,
+            )
+        },
+        Payload::OptQuorumStore(opt_qs_payload) => {
+            let (mut inline_batch_txns, opt_batch_infos, proofs_of_store, execution_limits) =
+                opt_qs_payload.into_inner().into_parts();
+            let inline_batch_txns = std::mem::take(&mut *inline_batch_txns);
+            Payload::OptQuorumStore(OptQuorumStorePayload::new(
+                select_items(inline_batch_txns, &inline_batches).into(),
+                BatchPointer::new(select_items(opt_batch_infos.batch_summary, &opt_batches)),
+                BatchPointer::new(select_items(proofs_of_store.batch_summary, &proofs)),
+                execution_limits,
+            ))
+        },
+    };
+    match max_txns_to_execute {
+        Some(_) => payload.transform_to_quorum_store_v2(max_txns_to_execute),
+        None => payload,
+    }
+}
+
+/// Returns the items at the given indices (in the order of the indices)
+fn select_items<T>(items: Vec<T>, indices: &[usize]) -> Vec<T> {
+    let mut items: Vec<_> = items.into_iter().map(Some).collect();
+    indices
+        .iter()
+        .filter_map(|index| items.get_mut(*index).and_then(Option::take))
+        .collect()
+}
+
+fn batch_size<T: TDataInfo>(batch: &T) -> PayloadTxnsSize {
+    PayloadTxnsSize::new(batch.num_txns(), batch.num_bytes())
+}
+
+fn batch_sizes<T: TDataInfo>(batches: &[T]) -> Vec<PayloadTxnsSize> {
+    batches.iter().map(batch_size).collect()
+}
+
+/// Creates the block building policy for the given policy type
+pub fn create_block_building_policy(
+    policy_type: &BlockBuildingPolicyType,
+) -> Arc<dyn BlockBuildingPolicy> {
+    match policy_type {
+        BlockBuildingPolicyType::Default => Arc::new(DefaultBlockBuildingPolicy),
+        BlockBuildingPolicyType::StrictBackpressure => Arc::new(StrictBackpressurePolicy),
+    }
+}
+
+/// Returns the most restrictive targets across the limits and all backpressure
+/// signals, as (max block txns, max block txns after filtering, proposal delay).
+fn most_restrictive_targets(
+    limits: &BlockLimits,
+    signals: &BackpressureSignals,
+) -> (PayloadTxnsSize, u64, Duration) {
+    let mut values_max_block_txns_after_filtering = vec![limits.max_block_txns_after_filtering];
+    let mut values_max_block = vec![limits.max_block_txns];
+    let mut values_proposal_delay = vec![Duration::ZERO];
+
+    if let Some(value) = &signals.chain_health_backoff {
+        values_max_block_txns_after_filtering
+            .push(value.max_sending_block_txns_after_filtering_override);
+        values_max_block.push(
+            limits
+                .max_block_txns
+                .compute_with_bytes(value.max_sending_block_bytes_override),
+        );
+        values_proposal_delay.push(Duration::from_millis(value.backoff_proposal_delay_ms));
+    }
+
+    if let Some(value) = &signals.pipeline_backpressure {
+        values_max_block_txns_after_filtering
+            .push(value.max_sending_block_txns_after_filtering_override);
+        values_max_block.push(
+            limits
+                .max_block_txns
+                .compute_with_bytes(value.max_sending_block_bytes_override),
+        );
+        values_proposal_delay.push(Duration::from_millis(value.backpressure_proposal_delay_ms));
+    }
+
+    if let Some(execution_backpressure_block_size) = signals.execution_backpressure_block_size {
+        values_max_block_txns_after_filtering.push(execution_backpressure_block_size);
+    }
+
+    let max_block_txns_after_filtering = values_max_block_txns_after_filtering
+        .into_iter()
+        .min()
+        .expect("always initialized to at least one value");
+    let max_block_size = values_max_block
+        .into_iter()
+        .reduce(PayloadTxnsSize::minimum)
+        .expect("always initialized to at least one value");
+    let proposal_delay = values_proposal_delay
+        .into_iter()
+        .max()
+        .expect("always initialized to at least one value");
+
+    (
+        max_block_size,
+        max_block_txns_after_filtering,
+        proposal_delay,
+    )
+}
+
+/// The default block building policy. The most restrictive backpressure targets are
+/// applied, but as quorum store batches cannot be split, the number of pulled transactions
+/// is never reduced below `min_max_txns_in_block_after_filtering_from_backpressure`.
+/// Instead, the number of transactions to execute from the block is limited.
+pub struct DefaultBlockBuildingPolicy;
+
+impl BlockBuildingPolicy for DefaultBlockBuildingPolicy {
+    fn name(&self) -> &'static str {
+        "default"
+    }
+
+    fn compute_block_targets(
+        &self,
+        limits: &BlockLimits,
+        signals: &BackpressureSignals,
+    ) -> BlockTargets {
+        let (max_block_txns, max_block_txns_after_filtering, proposal_delay) =
+            most_restrictive_targets(limits, signals);
+
+        let (max_block_txns_after_filtering, max_txns_from_block_to_execute) = if limits
+            .min_max_txns_in_block_after_filtering_from_backpressure
+            > max_block_txns_after_filtering
+        {
+            (
+                limits.min_max_txns_in_block_after_filtering_from_backpressure,
+                Some(max_block_txns_after_filtering),
+            )
+        } else {
+            (max_block_txns_after_filtering, None)
+        };
+
+        BlockTargets {
+            max_block_txns,
+            max_block_txns_after_filtering,
+            max_txns_from_block_to_execute,
+            max_inline_txns: limits.max_inline_txns,
+            opt_batch_txns_pct: 0,
+            proposal_delay,
+        }
+    }
+
+    fn select_batches(
+        &self,
+        pulled_batches: &PulledBatches,
+        targets: &BlockTargets,
+    ) -> BatchSelection {
+        let mut selection = BatchSelection::all(pulled_batches);
+        if let Some(max_txns_to_execute) = targets.max_txns_from_block_to_execute {
+            if selection.size(pulled_batches).count() > max_txns_to_execute {
+                selection.max_txns_to_execute = Some(max_txns_to_execute);
+            }
+        }
+        selection
+    }
+}
+
+/// A block building policy that applies the most restrictive backpressure targets
+/// directly to the pulled payload. This creates smaller blocks under backpressure
+/// (at the cost of leaving larger batches in the proof queue for longer).
+pub struct StrictBackpressurePolicy;
+
+impl BlockBuildingPolicy for StrictBackpressurePolicy {
+    fn name(&self) -> &'static str {
+        "strict_backpressure"
+    }
+
+    fn compute_block_targets(
+        &self,
+        limits: &BlockLimits,
+        signals: &BackpressureSignals,
+    ) -> BlockTargets {
+        let (max_block_txns, max_block_txns_after_filtering, proposal_delay) =
+            most_restrictive_targets(limits, signals);
+
+        BlockTargets {
+            max_block_txns,
+            max_block_txns_after_filtering,
+            max_txns_from_block_to_execute: None,
+            max_inline_txns: limits.max_inline_txns.minimum(max_block_txns),
+            opt_batch_txns_pct: 0,
+            proposal_delay,
+        }
+    }
+
+    fn select_batches(
+        &self,
+        pulled_batches: &PulledBatches,
+        _targets: &BlockTargets,
+    ) -> BatchSelection {
+        BatchSelection::all(pulled_batches)
+    }
+}
@@ -0,0 +1,362 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::liveness::block_building_policy::{
+    BackpressureSignals, BlockBuildingPolicy, BlockLimits, PulledBatches,
+};
+use anyhow::Context;
+use aptos_config::config::ConsensusConfig;
+use aptos_consensus_types::{common::Payload, proof_of_store::BatchInfo, utils::PayloadTxnsSize};
+use aptos_infallible::Mutex;
+use aptos_types::PeerId;
+use serde::{Deserialize, Serialize};
+use std::{collections::VecDeque, fs, path::Path};
+
+/// The max number of block building snapshots recorded by the proposer
+pub const MAX_NUM_BLOCK_BUILDING_SNAPSHOTS: usize = 1_000;
+
+/// A (recorded) batch available to the proposer
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BatchSnapshot {
+    pub author: PeerId,
+    pub num_txns: u64,
+    pub num_bytes: u64,
+}
+
+impl From<&BatchInfo> for BatchSnapshot {
+    fn from(batch_info: &BatchInfo) -> Self {
+        Self {
+            author: batch_info.author(),
+            num_txns: batch_info.num_txns(),
+            num_bytes: batch_info.num_bytes(),
+        }
+    }
+}
+
+/// A recorded snapshot of the state available to the proposer when building a block:
+/// the backpressure signals and the batches in the quorum store proof queue.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct BlockBuildingSnapshot {
+    /// The backpressure signals observed by the proposer
+    pub signals: BackpressureSignals,
+    /// The batches with proofs of store (in the order they are pulled)
+    pub proofs: Vec<BatchSnapshot>,
+    /// The batches without proofs of store that can be inlined (in the order they are pulled)
+    pub batches_without_proof: Vec<BatchSnapshot>,
+}
+
+impl BlockBuildingSnapshot {
+    /// Creates a snapshot from the signals observed by the proposer and the payload it
+    /// pulled. Note: only the pulled batches are visible to the proposer, so the batches
+    /// left in the proof queue (e.g., beyond the pull targets) are not recorded.
+    pub fn from_pulled_payload(signals: BackpressureSignals, payload: &Payload) -> Self {
+        let (proofs, batches_without_proof) = match payload {
+            Payload::DirectMempool(_) => (vec![], vec![]),
+            Payload::InQuorumStore(proof_with_data) => (
+                proof_with_data
+                    .proofs
+                    .iter()
+                    .map(|proof| proof.info().into())
+                    .collect(),
+                vec![],
+            ),
+            Payload::InQuorumStoreWithLimit(proof_with_data) => (
+                proof_with_data
+                    .proof_with_data
+                    .proofs
+                    .iter()
+                    .map(|proof| proof.info().into())
+                    .collect(),
+                vec![],
+            ),
+            Payload::QuorumStoreInlineHybrid(inline_batches, proof_with_data, _) => (
+                proof_with_data
+                    .proofs
+                    .iter()
+                    .map(|proof| proof.info().into())
+                    .collect(),
+                inline_batches
+                    .iter()
+                    .map(|(batch_info, _)| batch_info.into())
+                    .collect(),
+            ),
+            Payload::OptQuorumStore(opt_qs_payload) => (
+                opt_qs_payload
+                    .proof_with_data()
+                    .iter()
+                    .map(|proof| proof.info().into())
+                    .collect(),
+                opt_qs_payload
+                    .inline_batches()
+                    .batch_infos()
+                    .iter()
+                    .chain(opt_qs_payload.opt_batches().iter())
+                    .map(BatchSnapshot::from)
+                    .collect(),
+            ),
+        };
+        Self {
+            signals,
+            proofs,
+            batches_without_proof,
+        }
+    }
+}
+
+/// A bounded buffer of the block building snapshots recorded by the proposer (so that
+/// they can be exported and replayed by the simulator). Once the buffer is full, the
+/// oldest snapshots are evicted.
+pub struct BlockBuildingSnapshots {
+    max_num_snapshots: usize,
+    snapshots: Mutex<VecDeque<BlockBuildingSnapshot>>,
+}
+
+impl BlockBuildingSnapshots {
+    pub fn new(max_num_snapshots: usize) -> Self {
+        Self {
+            max_num_snapshots,
+            snapshots: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Records the given snapshot (evicting the oldest snapshot if the buffer is full)
+    pub fn record(&self, snapshot: BlockBuildingSnapshot) {
+        if self.max_num_snapshots == 0 {
+            return;
+        }
+
+        let mut snapshots = self.snapshots.lock();
+        while snapshots.len() >= self.max_num_snapshots {
+            snapshots.pop_front();
+        }
+        snapshots.push_back(snapshot);
+    }
+
+    /// Returns the recorded snapshots (from oldest to newest)
+    pub fn get_snapshots(&self) -> Vec<BlockBuildingSnapshot> {
+        self.snapshots.lock().iter().cloned().collect()
+    }
+}
+
+/// Loads a list of snapshots from the given JSON file
+pub fn load_snapshots(path: &Path) -> anyhow::Result<Vec<BlockBuildingSnapshot>> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read the snapshots file: {:?}", path))?;
+    serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse the snapshots file: {:?}", path))
+}
+
+/// The block that would have been proposed for a single snapshot
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SimulatedBlock {
+    pub num_proofs: u64,
+    pub num_inline_batches: u64,
+    pub num_txns: u64,
+    pub num_bytes: u64,
+    /// The number of transactions in the block that would be executed
+    pub num_txns_to_execute: u64,
+    pub proposal_delay_ms: u64,
+    /// The number of proofs left in the proof queue
+    pub num_remaining_proofs: u64,
+}
+
+/// The evaluation of a single policy against a list of snapshots
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct PolicyEvaluation {
+    pub policy: String,
+    pub num_blocks: u64,
+    pub total_txns_to_execute: u64,
+    pub total_proposal_delay_ms: u64,
+    /// The average number of transactions to execute per block
+    pub avg_txns_to_execute: f64,
+    /// The average fraction of the (non-backpressured) block limits that was filled
+    pub avg_fill_fraction: f64,
+    pub blocks: Vec<SimulatedBlock>,
+}
+
+/// A simple simulator that evaluates block building policies against recorded snapshots.
+/// The payload pull is approximated in the same way as the quorum store proof manager:
+/// proofs are pulled first, and batches are only inlined once all proofs have been pulled.
+/// The pulled batches are then selected by the policy (as done by the proposal generator).
+/// Note: the simulator assumes that all transactions in the batches are unique.
+pub struct BlockBuildingSimulator {
+    limits: BlockLimits,
+    allow_batches_without_pos_in_proposal: bool,
+}
+
+impl BlockBuildingSimulator {
+    pub fn new(limits: BlockLimits, allow_batches_without_pos_in_proposal: bool) -> Self {
+        Self {
+            limits,
+            allow_batches_without_pos_in_proposal,
+        }
+    }
+
+    /// Creates a simulator using the proposer limits in the given consensus config
+    pub fn new_from_config(consensus_config: &ConsensusConfig) -> Self {
+        let limits = BlockLimits {
+            max_block_txns: PayloadTxnsSize::new(
+                consensus_config.max_sending_block_txns,
+                consensus_config.max_sending_block_bytes,
+            ),
+            max_block_txns_after_filtering: consensus_config.max_sending_block_txns_after_filtering,
+            max_inline_txns: PayloadTxnsSize::new(
+                consensus_config.max_sending_inline_txns,
+                consensus_config.max_sending_inline_bytes,
+            ),
+            min_max_txns_in_block_after_filtering_from_backpressure: consensus_config
+                .min_max_txns_in_block_after_filtering_from_backpressure,
+        };
+        Self::new(
+            limits,
+            consensus_config
+                .quorum_store
+                .allow_batches_without_pos_in_proposal,
+        )
+    }
+
+    /// Evaluates the given policy against all snapshots
+    pub fn evaluate(
+        &self,
+        policy: &dyn BlockBuildingPolicy,
+        snapshots: &[BlockBuildingSnapshot],
+    ) -> PolicyEvaluation {
+        let blocks: Vec<_> = snapshots
+            .iter()
+            .map(|snapshot| self.simulate_block(policy, snapshot))
+            .collect();
+
+        let num_blocks = blocks.len() as u64;
+        let total_txns_to_execute = blocks.iter().map(|block| block.num_txns_to_execute).sum();
+        let total_proposal_delay_ms = blocks.iter().map(|block| block.proposal_delay_ms).sum();
+        let total_fill_fraction: f64 = blocks.iter().map(|block| self.fill_fraction(block)).sum();
+        let (avg_txns_to_execute, avg_fill_fraction) = if num_blocks == 0 {
+            (0.0, 0.0)
+        } else {
+            (
+                total_txns_to_execute as f64 / num_blocks as f64,
+                total_fill_fraction / num_blocks as f64,
+            )
+        };
+
+        PolicyEvaluation {
+            policy: policy.name().to_string(),
+            num_blocks,
+            total_txns_to_execute,
+            total_proposal_delay_ms,
+            avg_txns_to_execute,
+            avg_fill_fraction,
+            blocks,
+        }
+    }
+
+    /// Simulates the block that the given policy would propose for the snapshot
+    pub fn simulate_block(
+        &self,
+        policy: &dyn BlockBuildingPolicy,
+        snapshot: &BlockBuildingSnapshot,
+    ) -> SimulatedBlock {
+        let targets = policy.compute_block_targets(&self.limits, &snapshot.signals);
+        let soft_max_txns_after_filtering = targets
+            .max_txns_from_block_to_execute
+            .unwrap_or(targets.max_block_txns_after_filtering);
+
+        // Pull the proofs
+        let max_txns_with_proof = targets
+            .max_block_txns
+            .compute_pct(100 - targets.opt_batch_txns_pct);
+        let (num_proofs, proofs_size) = pull_batches(
+            &snapshot.proofs,
+            max_txns_with_proof,
+            targets.max_block_txns_after_filtering,
+            soft_max_txns_after_filtering,
+        );
+
+        // Inline batches without proofs (only if all proofs were pulled)
+        let mut num_inline_batches = 0;
+        if self.allow_batches_without_pos_in_proposal && num_proofs == snapshot.proofs.len() {
+            let max_inline_txns = targets
+                .max_block_txns
+                .saturating_sub(proofs_size)
+                .minimum(targets.max_inline_txns);
+            let (num_batches, _) = pull_batches(
+                &snapshot.batches_without_proof,
+                max_inline_txns,
+                targets
+                    .max_block_txns_after_filtering
+                    .saturating_sub(proofs_size.count()),
+                soft_max_txns_after_filtering.saturating_sub(proofs_size.count()),
+            );
+            num_inline_batches = num_batches;
+        }
+
+        // Select the batches to propose
+        let pulled_batches = PulledBatches {
+            proofs: batch_sizes(&snapshot.proofs[..num_proofs]),
+            opt_batches: vec![],
+            inline_batches: batch_sizes(&snapshot.batches_without_proof[..num_inline_batches]),
+        };
+        let selection = policy.select_batches(&pulled_batches, &targets);
+        let block_size = selection.size(&pulled_batches);
+        let num_txns_to_execute = selection
+            .max_txns_to_execute
+            .map_or(block_size.count(), |max_txns_to_execute| {
+                block_size.count().min(max_txns_to_execute)
+            });
+
+        SimulatedBlock {
+            num_proofs: selection.proofs.len() as u64,
+            num_inline_batches: selection.inline_batches.len() as u64,
+            num_txns: block_size.count(),
+            num_bytes: block_size.size_in_bytes(),
+            num_txns_to_execute,
+            proposal_delay_ms: targets.proposal_delay.as_millis() as u64,
+            num_remaining_proofs: (snapshot.proofs.len() - selection.proofs.len()) as u64,
+        }
+    }
+
+    /// Returns the fraction of the (non-backpressured) block limits filled by the block
+    fn fill_fraction(&self, block: &SimulatedBlock) -> f64 {
+        let count_fraction =
+            block.num_txns as f64 / self.limits.max_block_txns.count().max(1) as f64;
+        let bytes_fraction =
+            block.num_bytes as f64 / self.limits.max_block_txns.size_in_bytes().max(1) as f64;
+        count_fraction.max(bytes_fraction)
+    }
+}
+
+/// Pulls batches (in order) until the next batch doesn't fit, or the soft limit is
+/// reached. Returns the number of batches pulled and their total size.
+fn pull_batches(
+    batches: &[BatchSnapshot],
+    max_txns: PayloadTxnsSize,
+    max_txns_after_filtering: u64,
+    soft_max_txns_after_filtering: u64,
+) -> (usize, PayloadTxnsSize) {
+    let mut size = PayloadTxnsSize::zero();
+    let mut num_batches = 0;
+    for batch in batches {
+        if size.count() >= soft_max_txns_after_filtering {
+            break;
+        }
+        let batch_size = PayloadTxnsSize::new(batch.num_txns, batch.num_bytes);
+        let new_size = size + batch_size;
+        if new_size.count() > max_txns.count()
+            || new_size.size_in_bytes() > max_txns.size_in_bytes()
+            || new_size.count() > max_txns_after_filtering
+        {
+            break;
+        }
+        size = new_size;
+        num_batches += 1;
+    }
+    (num_batches, size)
+}
+
+fn batch_sizes(batches: &[BatchSnapshot]) -> Vec<PayloadTxnsSize> {
+    batches
+        .iter()
+        .map(|batch| PayloadTxnsSize::new(batch.num_txns, batch.num_bytes))
+        .collect()
+}
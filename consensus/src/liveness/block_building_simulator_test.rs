@@ -0,0 +1,345 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::liveness::{
+    block_building_policy::{
+        select_payload, BackpressureSignals, BatchSelection, BlockBuildingPolicy, BlockLimits,
+        BlockTargets, DefaultBlockBuildingPolicy, PulledBatches, StrictBackpressurePolicy,
+    },
+    block_building_simulator::{
+        load_snapshots, BatchSnapshot, BlockBuildingSimulator, BlockBuildingSnapshot,
+        BlockBuildingSnapshots,
+    },
+};
+use aptos_config::config::{ChainHealthBackoffValues, PipelineBackpressureValues};
+use aptos_consensus_types::{
+    common::{Payload, ProofWithData},
+    proof_of_store::{BatchId, BatchInfo, ProofOfStore},
+    utils::PayloadTxnsSize,
+};
+use aptos_crypto::HashValue;
+use aptos_temppath::TempPath;
+use aptos_types::{aggregate_signature::AggregateSignature, PeerId};
+use std::time::Duration;
+
+/// Creates the block limits used by all tests
+fn create_block_limits() -> BlockLimits {
+    BlockLimits {
+        max_block_txns: PayloadTxnsSize::new(1000, 1_000_000),
+        max_block_txns_after_filtering: 800,
+        max_inline_txns: PayloadTxnsSize::new(100, 100_000),
+        min_max_txns_in_block_after_filtering_from_backpressure: 200,
+    }
+}
+
+/// Creates backpressure signals for both chain health and pipeline backpressure
+fn create_backpressure_signals() -> BackpressureSignals {
+    BackpressureSignals {
+        chain_health_backoff: Some(ChainHealthBackoffValues {
+            backoff_if_below_participating_voting_power_percentage: 80,
+            max_sending_block_txns_after_filtering_override: 300,
+            max_sending_block_bytes_override: 800_000,
+            backoff_proposal_delay_ms: 100,
+        }),
+        pipeline_backpressure: Some(PipelineBackpressureValues {
+            back_pressure_pipeline_latency_limit_ms: 1000,
+            max_sending_block_txns_after_filtering_override: 100,
+            max_sending_block_bytes_override: 500_000,
+            backpressure_proposal_delay_ms: 50,
+        }),
+        execution_backpressure_block_size: None,
+    }
+}
+
+/// Creates a snapshot with the given signals and batches
+fn create_snapshot(
+    signals: BackpressureSignals,
+    num_proofs: usize,
+    num_batches_without_proof: usize,
+) -> BlockBuildingSnapshot {
+    let create_batch = |num_txns| BatchSnapshot {
+        author: PeerId::random(),
+        num_txns,
+        num_bytes: num_txns * 100,
+    };
+    BlockBuildingSnapshot {
+        signals,
+        proofs: (0..num_proofs).map(|_| create_batch(60)).collect(),
+        batches_without_proof: (0..num_batches_without_proof)
+            .map(|_| create_batch(20))
+            .collect(),
+    }
+}
+
+/// Creates a proof of store for a batch with the given number of transactions
+fn create_proof(batch_sequence: u64, num_txns: u64) -> ProofOfStore {
+    let batch_info = BatchInfo::new(
+        PeerId::random(),
+        BatchId::new_for_test(batch_sequence),
+        0,
+        0,
+        HashValue::random(),
+        num_txns,
+        num_txns * 100,
+        0,
+    );
+    ProofOfStore::new(batch_info, AggregateSignature::empty())
+}
+
+/// A test policy that proposes the pulled proofs in reverse order (without any inline batches)
+struct ReversedProofsPolicy;
+
+impl BlockBuildingPolicy for ReversedProofsPolicy {
+    fn name(&self) -> &'static str {
+        "reversed_proofs"
+    }
+
+    fn compute_block_targets(
+        &self,
+        limits: &BlockLimits,
+        signals: &BackpressureSignals,
+    ) -> BlockTargets {
+        StrictBackpressurePolicy.compute_block_targets(limits, signals)
+    }
+
+    fn select_batches(
+        &self,
+        pulled_batches: &PulledBatches,
+        _targets: &BlockTargets,
+    ) -> BatchSelection {
+        BatchSelection {
+            proofs: (0..pulled_batches.proofs.len()).rev().collect(),
+            ..Default::default()
+        }
+    }
+}
+
+#[test]
+fn test_policy_targets_without_backpressure() {
+    let limits = create_block_limits();
+    let signals = BackpressureSignals::default();
+
+    // Verify that both policies use the block limits when there is no backpressure
+    let policies: [&dyn BlockBuildingPolicy; 2] =
+        [&DefaultBlockBuildingPolicy, &StrictBackpressurePolicy];
+    for policy in policies {
+        let targets = policy.compute_block_targets(&limits, &signals);
+        assert_eq!(targets.max_block_txns, limits.max_block_txns);
+        assert_eq!(
+            targets.max_block_txns_after_filtering,
+            limits.max_block_txns_after_filtering
+        );
+        assert_eq!(targets.max_txns_from_block_to_execute, None);
+        assert_eq!(targets.max_inline_txns, limits.max_inline_txns);
+        assert_eq!(targets.proposal_delay, Duration::ZERO);
+    }
+}
+
+#[test]
+fn test_policy_targets_with_backpressure() {
+    let limits = create_block_limits();
+    let signals = create_backpressure_signals();
+
+    // Verify that the default policy limits the number of transactions to execute
+    let targets = DefaultBlockBuildingPolicy.compute_block_targets(&limits, &signals);
+    assert_eq!(targets.max_block_txns, PayloadTxnsSize::new(500, 500_000));
+    assert_eq!(targets.max_block_txns_after_filtering, 200);
+    assert_eq!(targets.max_txns_from_block_to_execute, Some(100));
+    assert_eq!(targets.max_inline_txns, limits.max_inline_txns);
+    assert_eq!(targets.proposal_delay, Duration::from_millis(100));
+
+    // Verify that the strict policy limits the pulled transactions directly
+    let targets = StrictBackpressurePolicy.compute_block_targets(&limits, &signals);
+    assert_eq!(targets.max_block_txns, PayloadTxnsSize::new(500, 500_000));
+    assert_eq!(targets.max_block_txns_after_filtering, 100);
+    assert_eq!(targets.max_txns_from_block_to_execute, None);
+    assert_eq!(targets.proposal_delay, Duration::from_millis(100));
+
+    // Verify that execution backpressure is applied by both policies
+    let signals = BackpressureSignals {
+        execution_backpressure_block_size: Some(50),
+        ..BackpressureSignals::default()
+    };
+    let targets = DefaultBlockBuildingPolicy.compute_block_targets(&limits, &signals);
+    assert_eq!(targets.max_txns_from_block_to_execute, Some(50));
+    let targets = StrictBackpressurePolicy.compute_block_targets(&limits, &signals);
+    assert_eq!(targets.max_block_txns_after_filtering, 50);
+}
+
+#[test]
+fn test_policy_select_payload() {
+    let limits = create_block_limits();
+    let signals = create_backpressure_signals();
+
+    // Create a quorum store payload with more transactions than the execution limit
+    let proofs: Vec<_> = (0..2)
+        .map(|batch_sequence| create_proof(batch_sequence, 60))
+        .collect();
+    let payload = Payload::InQuorumStore(ProofWithData::new(proofs.clone()));
+
+    // Verify that the default policy limits the transactions to execute
+    let targets = DefaultBlockBuildingPolicy.compute_block_targets(&limits, &signals);
+    let selected_payload = select_payload(&DefaultBlockBuildingPolicy, payload.clone(), &targets);
+    assert!(matches!(
+        selected_payload,
+        Payload::InQuorumStoreWithLimit(_)
+    ));
+
+    // Verify that the strict policy proposes the payload as is
+    let targets = StrictBackpressurePolicy.compute_block_targets(&limits, &signals);
+    let selected_payload = select_payload(&StrictBackpressurePolicy, payload, &targets);
+    assert!(matches!(selected_payload, Payload::InQuorumStore(_)));
+
+    // Verify that the batch selection (and ordering) of the policy is applied
+    let inline_batch = (proofs[0].info().clone(), vec![]);
+    let payload = Payload::QuorumStoreInlineHybrid(
+        vec![inline_batch],
+        ProofWithData::new(proofs.clone()),
+        None,
+    );
+    let selected_payload = select_payload(&ReversedProofsPolicy, payload, &targets);
+    match selected_payload {
+        Payload::QuorumStoreInlineHybrid(inline_batches, proof_with_data, None) => {
+            assert!(inline_batches.is_empty());
+            let reversed_proofs: Vec<_> = proofs.into_iter().rev().collect();
+            assert_eq!(proof_with_data.proofs, reversed_proofs);
+        },
+        payload => panic!("Unexpected payload: {}", payload),
+    }
+
+    // Verify that direct mempool payloads are proposed as is
+    let payload = Payload::DirectMempool(vec![]);
+    let selected_payload = select_payload(&ReversedProofsPolicy, payload.clone(), &targets);
+    assert_eq!(selected_payload, payload);
+}
+
+#[test]
+fn test_simulate_blocks() {
+    let simulator = BlockBuildingSimulator::new(create_block_limits(), true);
+
+    // Simulate a block without backpressure and verify that all batches are pulled
+    let snapshot = create_snapshot(BackpressureSignals::default(), 5, 10);
+    let block = simulator.simulate_block(&DefaultBlockBuildingPolicy, &snapshot);
+    assert_eq!(block.num_proofs, 5);
+    assert_eq!(block.num_remaining_proofs, 0);
+    assert_eq!(block.num_inline_batches, 5); // Limited by the max inline txns
+    assert_eq!(block.num_txns, 400);
+    assert_eq!(block.num_bytes, 40_000);
+    assert_eq!(block.num_txns_to_execute, 400);
+    assert_eq!(block.proposal_delay_ms, 0);
+
+    // Simulate a block with backpressure using the default policy
+    let snapshot = create_snapshot(create_backpressure_signals(), 5, 10);
+    let block = simulator.simulate_block(&DefaultBlockBuildingPolicy, &snapshot);
+    assert_eq!(block.num_proofs, 2);
+    assert_eq!(block.num_remaining_proofs, 3);
+    assert_eq!(block.num_inline_batches, 0);
+    assert_eq!(block.num_txns, 120);
+    assert_eq!(block.num_txns_to_execute, 100);
+    assert_eq!(block.proposal_delay_ms, 100);
+
+    // Simulate a block with backpressure using the strict policy
+    let block = simulator.simulate_block(&StrictBackpressurePolicy, &snapshot);
+    assert_eq!(block.num_proofs, 1);
+    assert_eq!(block.num_remaining_proofs, 4);
+    assert_eq!(block.num_txns, 60);
+    assert_eq!(block.num_txns_to_execute, 60);
+
+    // Verify that batches are not inlined if it is disabled
+    let simulator = BlockBuildingSimulator::new(create_block_limits(), false);
+    let snapshot = create_snapshot(BackpressureSignals::default(), 5, 10);
+    let block = simulator.simulate_block(&DefaultBlockBuildingPolicy, &snapshot);
+    assert_eq!(block.num_inline_batches, 0);
+    assert_eq!(block.num_txns, 300);
+
+    // Verify that the simulator applies the batch selection of the policy
+    let simulator = BlockBuildingSimulator::new(create_block_limits(), true);
+    let block = simulator.simulate_block(&ReversedProofsPolicy, &snapshot);
+    assert_eq!(block.num_proofs, 5);
+    assert_eq!(block.num_remaining_proofs, 0);
+    assert_eq!(block.num_inline_batches, 0);
+    assert_eq!(block.num_txns, 300);
+}
+
+#[test]
+fn test_record_snapshots() {
+    // Create a snapshot from a pulled payload
+    let proofs: Vec<_> = (0..2)
+        .map(|batch_sequence| create_proof(batch_sequence, 60))
+        .collect();
+    let inline_batch_info = create_proof(2, 20).info().clone();
+    let payload = Payload::QuorumStoreInlineHybrid(
+        vec![(inline_batch_info.clone(), vec![])],
+        ProofWithData::new(proofs.clone()),
+        None,
+    );
+    let signals = create_backpressure_signals();
+    let snapshot = BlockBuildingSnapshot::from_pulled_payload(signals.clone(), &payload);
+    assert_eq!(snapshot.signals, signals);
+    let expected_proofs: Vec<_> = proofs
+        .iter()
+        .map(|proof| BatchSnapshot::from(proof.info()))
+        .collect();
+    assert_eq!(snapshot.proofs, expected_proofs);
+    assert_eq!(snapshot.batches_without_proof, vec![BatchSnapshot::from(
+        &inline_batch_info
+    )]);
+
+    // Record the snapshots and verify the oldest snapshots are evicted
+    let snapshots = BlockBuildingSnapshots::new(2);
+    for num_proofs in 0..3 {
+        snapshots.record(create_snapshot(
+            BackpressureSignals::default(),
+            num_proofs,
+            0,
+        ));
+    }
+    let recorded_snapshots = snapshots.get_snapshots();
+    assert_eq!(recorded_snapshots.len(), 2);
+    assert_eq!(recorded_snapshots[0].proofs.len(), 1);
+    assert_eq!(recorded_snapshots[1].proofs.len(), 2);
+
+    // Verify that the recorded snapshots can be replayed by the simulator
+    let simulator = BlockBuildingSimulator::new(create_block_limits(), true);
+    let evaluation = simulator.evaluate(&DefaultBlockBuildingPolicy, &recorded_snapshots);
+    assert_eq!(evaluation.total_txns_to_execute, 180);
+}
+
+#[test]
+fn test_evaluate_recorded_snapshots() {
+    // Record the snapshots to a file
+    let snapshots = vec![
+        create_snapshot(BackpressureSignals::default(), 5, 0),
+        create_snapshot(create_backpressure_signals(), 5, 0),
+    ];
+    let snapshots_file = TempPath::new();
+    snapshots_file.create_as_file().unwrap();
+    std::fs::write(
+        snapshots_file.path(),
+        serde_json::to_string(&snapshots).unwrap(),
+    )
+    .unwrap();
+
+    // Load the snapshots and verify they match
+    let loaded_snapshots = load_snapshots(snapshots_file.path()).unwrap();
+    assert_eq!(loaded_snapshots, snapshots);
+
+    // Evaluate both policies against the snapshots
+    let simulator = BlockBuildingSimulator::new(create_block_limits(), true);
+    let default_evaluation = simulator.evaluate(&DefaultBlockBuildingPolicy, &loaded_snapshots);
+    assert_eq!(default_evaluation.policy, "default");
+    assert_eq!(default_evaluation.num_blocks, 2);
+    assert_eq!(default_evaluation.total_txns_to_execute, 400);
+    assert_eq!(default_evaluation.total_proposal_delay_ms, 100);
+    assert_eq!(default_evaluation.avg_txns_to_execute, 200.0);
+    assert_eq!(default_evaluation.blocks.len(), 2);
+
+    let strict_evaluation = simulator.evaluate(&StrictBackpressurePolicy, &loaded_snapshots);
+    assert_eq!(strict_evaluation.policy, "strict_backpressure");
+    assert_eq!(strict_evaluation.total_txns_to_execute, 360);
+    assert!(strict_evaluation.avg_fill_fraction < default_evaluation.avg_fill_fraction);
+
+    // Verify that invalid snapshot files are rejected
+    std::fs::write(snapshots_file.path(), "invalid").unwrap();
+    assert!(load_snapshots(snapshots_file.path()).is_err());
+}
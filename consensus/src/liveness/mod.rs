@@ -2,6 +2,8 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub(crate) mod block_building_policy;
+pub(crate) mod block_building_simulator;
 pub(crate) mod cached_proposer_election;
 pub(crate) mod leader_reputation;
 pub(crate) mod proposal_generator;
@@ -11,6 +13,8 @@ pub(crate) mod round_proposer_election;
 pub(crate) mod round_state;
 pub(crate) mod unequivocal_proposer_election;
 
+#[cfg(test)]
+mod block_building_simulator_test;
 #[cfg(test)]
 mod cached_proposer_election_test;
 #[cfg(test)]
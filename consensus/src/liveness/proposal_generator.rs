@@ -2,7 +2,13 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use super::proposer_election::ProposerElection;
+use super::{
+    block_building_policy::{
+        select_payload, BackpressureSignals, BlockBuildingPolicy, BlockLimits, BlockTargets,
+    },
+    block_building_simulator::{BlockBuildingSnapshot, BlockBuildingSnapshots},
+    proposer_election::ProposerElection,
+};
 use crate::{
     block_storage::BlockReader,
     counters::{
@@ -267,6 +273,11 @@ pub struct ProposalGenerator {
     vtxn_config: ValidatorTxnConfig,
 
     allow_batches_without_pos_in_proposal: bool,
+
+    // The policy used to compute the block targets and select the proposed payload
+    block_building_policy: Arc<dyn BlockBuildingPolicy>,
+    // The recorder of block building snapshots (if enabled), e.g., for the simulator
+    block_building_snapshots: Option<Arc<BlockBuildingSnapshots>>,
 }
 
 impl ProposalGenerator {
@@ -287,6 +298,8 @@ impl ProposalGenerator {
         quorum_store_enabled: bool,
         vtxn_config: ValidatorTxnConfig,
         allow_batches_without_pos_in_proposal: bool,
+        block_building_policy: Arc<dyn BlockBuildingPolicy>,
+        block_building_snapshots: Option<Arc<BlockBuildingSnapshots>>,
     ) -> Self {
         Self {
             author,
@@ -305,6 +318,8 @@ impl ProposalGenerator {
             quorum_store_enabled,
             vtxn_config,
             allow_batches_without_pos_in_proposal,
+            block_building_policy,
+            block_building_snapshots,
         }
     }
 
@@ -400,17 +415,14 @@ impl ProposalGenerator {
 
             let voting_power_ratio = proposer_election.get_voting_power_participation_ratio(round);
 
-            let (
-                max_block_txns,
-                max_block_txns_after_filtering,
-                max_txns_from_block_to_execute,
-                proposal_delay,
-            ) = self
-                .calculate_max_block_sizes(voting_power_ratio, timestamp, round)
+            let (signals, block_targets) = self
+                .calculate_block_targets(voting_power_ratio, timestamp, round)
                 .await;
+            let proposal_delay = block_targets.proposal_delay;
 
-            PROPOSER_MAX_BLOCK_TXNS_AFTER_FILTERING.observe(max_block_txns_after_filtering as f64);
-            if let Some(max_to_execute) = max_txns_from_block_to_execute {
+            PROPOSER_MAX_BLOCK_TXNS_AFTER_FILTERING
+                .observe(block_targets.max_block_txns_after_filtering as f64);
+            if let Some(max_to_execute) = block_targets.max_txns_from_block_to_execute {
                 PROPOSER_MAX_BLOCK_TXNS_TO_EXECUTE.observe(max_to_execute as f64);
             }
 
@@ -446,17 +458,18 @@ impl ProposalGenerator {
             let validator_txn_filter =
                 vtxn_pool::TransactionFilter::PendingTxnHashSet(pending_validator_txn_hashes);
 
-            let (validator_txns, mut payload) = self
+            let (validator_txns, payload) = self
                 .payload_client
                 .pull_payload(
                     PayloadPullParameters {
                         max_poll_time: self.quorum_store_poll_time.saturating_sub(proposal_delay),
-                        max_txns: max_block_txns,
-                        max_txns_after_filtering: max_block_txns_after_filtering,
-                        soft_max_txns_after_filtering: max_txns_from_block_to_execute
-                            .unwrap_or(max_block_txns_after_filtering),
-                        max_inline_txns: self.max_inline_txns,
-                        opt_batch_txns_pct: 0,
+                        max_txns: block_targets.max_block_txns,
+                        max_txns_after_filtering: block_targets.max_block_txns_after_filtering,
+                        soft_max_txns_after_filtering: block_targets
+                            .max_txns_from_block_to_execute
+                            .unwrap_or(block_targets.max_block_txns_after_filtering),
+                        max_inline_txns: block_targets.max_inline_txns,
+                        opt_batch_txns_pct: block_targets.opt_batch_txns_pct,
                         user_txn_filter: payload_filter,
                         pending_ordering,
                         pending_uncommitted_blocks: pending_blocks.len(),
@@ -469,12 +482,13 @@ impl ProposalGenerator {
                 .await
                 .context("Fail to retrieve payload")?;
 
-            if !payload.is_direct()
-                && max_txns_from_block_to_execute.is_some()
-                && max_txns_from_block_to_execute.map_or(false, |v| payload.len() as u64 > v)
-            {
-                payload = payload.transform_to_quorum_store_v2(max_txns_from_block_to_execute);
+            if let Some(block_building_snapshots) = &self.block_building_snapshots {
+                block_building_snapshots.record(BlockBuildingSnapshot::from_pulled_payload(
+                    signals, &payload,
+                ));
             }
+            let payload =
+                select_payload(self.block_building_policy.as_ref(), payload, &block_targets);
             (validator_txns, payload, timestamp.as_micros() as u64)
         };
 
@@ -510,118 +524,91 @@ impl ProposalGenerator {
         Ok(block)
     }
 
-    async fn calculate_max_block_sizes(
+    async fn calculate_block_targets(
         &self,
         voting_power_ratio: f64,
         timestamp: Duration,
         round: Round,
-    ) -> (PayloadTxnsSize, u64, Option<u64>, Duration) {
-        let mut values_max_block_txns_after_filtering = vec![self.max_block_txns_after_filtering];
-        let mut values_max_block = vec![self.max_block_txns];
-        let mut values_proposal_delay = vec![Duration::ZERO];
-
+    ) -> (BackpressureSignals, BlockTargets) {
         let chain_health_backoff = self
             .chain_health_backoff_config
-            .get_backoff(voting_power_ratio);
-        if let Some(value) = chain_health_backoff {
-            values_max_block_txns_after_filtering
-                .push(value.max_sending_block_txns_after_filtering_override);
-            values_max_block.push(
-                self.max_block_txns
-                    .compute_with_bytes(value.max_sending_block_bytes_override),
-            );
-            values_proposal_delay.push(Duration::from_millis(value.backoff_proposal_delay_ms));
-            CHAIN_HEALTH_BACKOFF_TRIGGERED.observe(1.0);
-        } else {
-            CHAIN_HEALTH_BACKOFF_TRIGGERED.observe(0.0);
-        }
+            .get_backoff(voting_power_ratio)
+            .cloned();
+        CHAIN_HEALTH_BACKOFF_TRIGGERED.observe(
+            if chain_health_backoff.is_some() {
+                1.0
+            } else {
+                0.0
+            },
+        );
 
         let pipeline_pending_latency = self.block_store.pipeline_pending_latency(timestamp);
         let pipeline_backpressure = self
             .pipeline_backpressure_config
-            .get_backoff(pipeline_pending_latency);
-        if let Some(value) = pipeline_backpressure {
-            values_max_block_txns_after_filtering
-                .push(value.max_sending_block_txns_after_filtering_override);
-            values_max_block.push(
-                self.max_block_txns
-                    .compute_with_bytes(value.max_sending_block_bytes_override),
-            );
-            values_proposal_delay.push(Duration::from_millis(value.backpressure_proposal_delay_ms));
-            PIPELINE_BACKPRESSURE_ON_PROPOSAL_TRIGGERED.observe(1.0);
-        } else {
-            PIPELINE_BACKPRESSURE_ON_PROPOSAL_TRIGGERED.observe(0.0);
-        };
+            .get_backoff(pipeline_pending_latency)
+            .cloned();
+        PIPELINE_BACKPRESSURE_ON_PROPOSAL_TRIGGERED.observe(
+            if pipeline_backpressure.is_some() {
+                1.0
+            } else {
+                0.0
+            },
+        );
 
-        let mut execution_backpressure_applied = false;
-        if let Some(config) = &self.pipeline_backpressure_config.execution {
-            let execution_backpressure = self
-                .pipeline_backpressure_config
-                .get_execution_block_size_backoff(
-                    &self
-                        .block_store
-                        .get_recent_block_execution_times(config.num_blocks_to_look_at),
-                    self.max_block_txns_after_filtering,
-                );
-            if let Some(execution_backpressure_block_size) = execution_backpressure {
-                values_max_block_txns_after_filtering.push(execution_backpressure_block_size);
-                execution_backpressure_applied = true;
-            }
-        }
+        let execution_backpressure_block_size = self
+            .pipeline_backpressure_config
+            .execution
+            .as_ref()
+            .and_then(|config| {
+                self.pipeline_backpressure_config
+                    .get_execution_block_size_backoff(
+                        &self
+                            .block_store
+                            .get_recent_block_execution_times(config.num_blocks_to_look_at),
+                        self.max_block_txns_after_filtering,
+                    )
+            });
         EXECUTION_BACKPRESSURE_ON_PROPOSAL_TRIGGERED.observe(
-            if execution_backpressure_applied {
+            if execution_backpressure_block_size.is_some() {
                 1.0
             } else {
                 0.0
             },
         );
 
-        let max_block_txns_after_filtering = values_max_block_txns_after_filtering
-            .into_iter()
-            .min()
-            .expect("always initialized to at least one value");
-
-        let max_block_size = values_max_block
-            .into_iter()
-            .reduce(PayloadTxnsSize::minimum)
-            .expect("always initialized to at least one value");
-        let proposal_delay = values_proposal_delay
-            .into_iter()
-            .max()
-            .expect("always initialized to at least one value");
-
-        let (max_block_txns_after_filtering, max_txns_from_block_to_execute) = if self
-            .min_max_txns_in_block_after_filtering_from_backpressure
-            > max_block_txns_after_filtering
-        {
-            (
-                self.min_max_txns_in_block_after_filtering_from_backpressure,
-                Some(max_block_txns_after_filtering),
-            )
-        } else {
-            (max_block_txns_after_filtering, None)
+        let signals = BackpressureSignals {
+            chain_health_backoff,
+            pipeline_backpressure,
+            execution_backpressure_block_size,
         };
+        let limits = BlockLimits {
+            max_block_txns: self.max_block_txns,
+            max_block_txns_after_filtering: self.max_block_txns_after_filtering,
+            max_inline_txns: self.max_inline_txns,
+            min_max_txns_in_block_after_filtering_from_backpressure: self
+                .min_max_txns_in_block_after_filtering_from_backpressure,
+        };
+        let block_targets = self
+            .block_building_policy
+            .compute_block_targets(&limits, &signals);
 
         warn!(
             pipeline_pending_latency = pipeline_pending_latency.as_millis(),
-            proposal_delay_ms = proposal_delay.as_millis(),
-            max_block_txns_after_filtering = max_block_txns_after_filtering,
-            max_txns_from_block_to_execute =
-                max_txns_from_block_to_execute.unwrap_or(max_block_txns_after_filtering),
-            max_block_size = max_block_size,
-            is_pipeline_backpressure = pipeline_backpressure.is_some(),
-            is_execution_backpressure = execution_backpressure_applied,
-            is_chain_health_backoff = chain_health_backoff.is_some(),
+            proposal_delay_ms = block_targets.proposal_delay.as_millis(),
+            max_block_txns_after_filtering = block_targets.max_block_txns_after_filtering,
+            max_txns_from_block_to_execute = block_targets
+                .max_txns_from_block_to_execute
+                .unwrap_or(block_targets.max_block_txns_after_filtering),
+            max_block_size = block_targets.max_block_txns,
+            is_pipeline_backpressure = signals.pipeline_backpressure.is_some(),
+            is_execution_backpressure = signals.execution_backpressure_block_size.is_some(),
+            is_chain_health_backoff = signals.chain_health_backoff.is_some(),
+            block_building_policy = self.block_building_policy.name(),
             round = round,
             "Proposal generation backpressure details",
         );
 
-        (
-            max_block_size,
-            max_block_txns_after_filtering,
-            max_txns_from_block_to_execute,
-            proposal_delay,
-        )
+        (signals, block_targets)
     }
 
     fn ensure_highest_quorum_cert(&self, round: Round) -> anyhow::Result<Arc<QuorumCert>> {
@@ -5,6 +5,7 @@
 use crate::{
     block_storage::BlockReader,
     liveness::{
+        block_building_policy::DefaultBlockBuildingPolicy,
         proposal_generator::{
             ChainHealthBackoffConfig, PipelineBackpressureConfig, ProposalGenerator,
         },
@@ -47,6 +48,8 @@ async fn test_proposal_generation_empty_tree() {
         false,
         ValidatorTxnConfig::default_disabled(),
         true,
+        Arc::new(DefaultBlockBuildingPolicy),
+        None,
     );
     let proposer_election = Arc::new(UnequivocalProposerElection::new(Arc::new(
         RotatingProposer::new(vec![signer.author()], 1),
@@ -92,6 +95,8 @@ async fn test_proposal_generation_parent() {
         false,
         ValidatorTxnConfig::default_disabled(),
         true,
+        Arc::new(DefaultBlockBuildingPolicy),
+        None,
     );
     let proposer_election = Arc::new(UnequivocalProposerElection::new(Arc::new(
         RotatingProposer::new(vec![inserter.signer().author()], 1),
@@ -167,6 +172,8 @@ async fn test_old_proposal_generation() {
         false,
         ValidatorTxnConfig::default_disabled(),
         true,
+        Arc::new(DefaultBlockBuildingPolicy),
+        None,
     );
     let proposer_election = Arc::new(UnequivocalProposerElection::new(Arc::new(
         RotatingProposer::new(vec![inserter.signer().author()], 1),
@@ -207,6 +214,8 @@ async fn test_correct_failed_authors() {
         false,
         ValidatorTxnConfig::default_disabled(),
         true,
+        Arc::new(DefaultBlockBuildingPolicy),
+        None,
     );
     let proposer_election = Arc::new(UnequivocalProposerElection::new(Arc::new(
         RotatingProposer::new(vec![author, peer1, peer2], 1),
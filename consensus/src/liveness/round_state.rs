@@ -9,8 +9,11 @@ use crate::{
 };
 use aptos_config::config::QcAggregatorType;
 use aptos_consensus_types::{
-    common::Round, delayed_qc_msg::DelayedQcMsg, sync_info::SyncInfo,
-    timeout_2chain::TwoChainTimeoutWithPartialSignatures, vote::Vote,
+    common::{Author, Round},
+    delayed_qc_msg::DelayedQcMsg,
+    sync_info::SyncInfo,
+    timeout_2chain::TwoChainTimeoutWithPartialSignatures,
+    vote::Vote,
 };
 use aptos_crypto::HashValue;
 use aptos_logger::{prelude::*, Schema};
@@ -304,6 +307,11 @@ impl RoundState {
         }
     }
 
+    /// Returns the pending vote (if any) for the given author in the current round
+    pub fn get_pending_vote_by_author(&self, author: &Author) -> Option<Vote> {
+        self.pending_votes.get_vote_by_author(author).cloned()
+    }
+
     pub fn record_vote(&mut self, vote: Vote) {
         if vote.vote_data().proposed().round() == self.current_round {
             self.vote_sent = Some(vote);
@@ -18,6 +18,8 @@ use std::{cmp::Ordering, sync::Arc};
 pub struct UnequivocalProposerElection {
     proposer_election: Arc<dyn ProposerElection + Send + Sync>,
     already_proposed: Mutex<(Round, HashValue)>,
+    // The last accepted proposal (used to provide evidence of equivocation)
+    last_proposal: Mutex<Option<Block>>,
 }
 
 impl ProposerElection for UnequivocalProposerElection {
@@ -36,6 +38,7 @@ impl UnequivocalProposerElection {
         Self {
             proposer_election,
             already_proposed: Mutex::new((0, HashValue::zero())),
+            last_proposal: Mutex::new(None),
         }
     }
 
@@ -64,6 +67,7 @@ impl UnequivocalProposerElection {
                 Ordering::Greater => {
                     already_proposed.0 = block.round();
                     already_proposed.1 = block.id();
+                    *self.last_proposal.lock() = Some(block.clone());
                     true
                 },
                 Ordering::Equal => {
@@ -85,4 +89,18 @@ impl UnequivocalProposerElection {
             }
         })
     }
+
+    // Return the previously accepted proposal iff it conflicts with the given
+    // block, i.e., it has the same author and round, but a different id.
+    pub fn get_conflicting_proposal(&self, block: &Block) -> Option<Block> {
+        self.last_proposal
+            .lock()
+            .as_ref()
+            .filter(|last_proposal| {
+                last_proposal.author() == block.author()
+                    && last_proposal.round() == block.round()
+                    && last_proposal.id() != block.id()
+            })
+            .cloned()
+    }
 }
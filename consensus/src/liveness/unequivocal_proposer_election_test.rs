@@ -94,6 +94,12 @@ fn test_is_valid_proposal() {
 
     // another proposal from the valid proposer should fail
     assert!(!pe.is_valid_proposal(&bad_duplicate_proposal));
+    // the first proposal is returned as evidence of equivocation
+    assert_eq!(
+        pe.get_conflicting_proposal(&bad_duplicate_proposal),
+        Some(good_proposal.clone())
+    );
+    assert_eq!(pe.get_conflicting_proposal(&good_proposal), None);
     // good proposal still passes
     assert!(pe.is_valid_proposal(&good_proposal));
 
@@ -8,7 +8,10 @@ use crate::{
         publisher::ConsensusPublisher,
     },
     counters,
-    quorum_store::{batch_store::BatchReader, quorum_store_coordinator::CoordinatorCommand},
+    quorum_store::{
+        batch_lifecycle::BatchLifecycles, batch_store::BatchReader,
+        quorum_store_coordinator::CoordinatorCommand,
+    },
 };
 use aptos_consensus_types::{
     block::Block,
@@ -45,6 +48,10 @@ pub trait TPayloadManager: Send + Sync {
     /// available when block is executed.
     fn prefetch_payload_data(&self, payload: &Payload, timestamp: u64);
 
+    /// Notify the payload manager that a block has been inserted into the block store. This
+    /// is used to track the blocks that the batches in the block's payload were included in.
+    fn notify_block_inserted(&self, block: &Block);
+
     /// Check if the transactions corresponding are available. This is specific to payload
     /// manager implementations. For optimistic quorum store, we only check if optimistic
     /// batches are available locally.
@@ -72,6 +79,8 @@ impl TPayloadManager for DirectMempoolPayloadManager {
 
     fn prefetch_payload_data(&self, _payload: &Payload, _timestamp: u64) {}
 
+    fn notify_block_inserted(&self, _block: &Block) {}
+
     fn check_payload_availability(&self, _block: &Block) -> bool {
         true
     }
@@ -103,6 +112,7 @@ pub struct QuorumStorePayloadManager {
     coordinator_tx: Sender<CoordinatorCommand>,
     maybe_consensus_publisher: Option<Arc<ConsensusPublisher>>,
     ordered_authors: Vec<PeerId>,
+    batch_lifecycles: Option<Arc<BatchLifecycles>>,
 }
 
 impl QuorumStorePayloadManager {
@@ -111,12 +121,56 @@ impl QuorumStorePayloadManager {
         coordinator_tx: Sender<CoordinatorCommand>,
         maybe_consensus_publisher: Option<Arc<ConsensusPublisher>>,
         ordered_authors: Vec<PeerId>,
+        batch_lifecycles: Option<Arc<BatchLifecycles>>,
     ) -> Self {
         Self {
             batch_reader,
             coordinator_tx,
             maybe_consensus_publisher,
             ordered_authors,
+            batch_lifecycles,
+        }
+    }
+
+    /// Returns the infos of all batches (inline, optimistic and proven) in the given payload
+    fn get_batch_infos(payload: &Payload) -> Vec<BatchInfo> {
+        match payload {
+            Payload::DirectMempool(_) => {
+                unreachable!("InQuorumStore should be used");
+            },
+            Payload::InQuorumStore(proof_with_status) => proof_with_status
+                .proofs
+                .iter()
+                .map(|proof| proof.info().clone())
+                .collect::<Vec<_>>(),
+            Payload::InQuorumStoreWithLimit(proof_with_status) => proof_with_status
+                .proof_with_data
+                .proofs
+                .iter()
+                .map(|proof| proof.info().clone())
+                .collect::<Vec<_>>(),
+            Payload::QuorumStoreInlineHybrid(inline_batches, proof_with_data, _) => inline_batches
+                .iter()
+                .map(|(batch_info, _)| batch_info.clone())
+                .chain(
+                    proof_with_data
+                        .proofs
+                        .iter()
+                        .map(|proof| proof.info().clone()),
+                )
+                .collect::<Vec<_>>(),
+            Payload::OptQuorumStore(opt_quorum_store_payload) => opt_quorum_store_payload
+                .inline_batches()
+                .batch_infos()
+                .into_iter()
+                .chain(opt_quorum_store_payload.opt_batches().iter().cloned())
+                .chain(
+                    opt_quorum_store_payload
+                        .proof_with_data()
+                        .iter()
+                        .map(|proof| proof.info().clone()),
+                )
+                .collect::<Vec<_>>(),
         }
     }
 
@@ -158,40 +212,13 @@ impl TPayloadManager for QuorumStorePayloadManager {
         self.batch_reader
             .update_certified_timestamp(block_timestamp);
 
-        let batches: Vec<_> = payloads
-            .into_iter()
-            .flat_map(|payload| match payload {
-                Payload::DirectMempool(_) => {
-                    unreachable!("InQuorumStore should be used");
-                },
-                Payload::InQuorumStore(proof_with_status) => proof_with_status
-                    .proofs
-                    .iter()
-                    .map(|proof| proof.info().clone())
-                    .collect::<Vec<_>>(),
-                Payload::InQuorumStoreWithLimit(proof_with_status) => proof_with_status
-                    .proof_with_data
-                    .proofs
-                    .iter()
-                    .map(|proof| proof.info().clone())
-                    .collect::<Vec<_>>(),
-                Payload::QuorumStoreInlineHybrid(inline_batches, proof_with_data, _) => {
-                    inline_batches
-                        .iter()
-                        .map(|(batch_info, _)| batch_info.clone())
-                        .chain(
-                            proof_with_data
-                                .proofs
-                                .iter()
-                                .map(|proof| proof.info().clone()),
-                        )
-                        .collect::<Vec<_>>()
-                },
-                Payload::OptQuorumStore(opt_quorum_store_payload) => {
-                    opt_quorum_store_payload.into_inner().get_all_batch_infos()
-                },
-            })
-            .collect();
+        let batches: Vec<_> = payloads.iter().flat_map(Self::get_batch_infos).collect();
+
+        if let Some(batch_lifecycles) = &self.batch_lifecycles {
+            for batch in &batches {
+                batch_lifecycles.record_committed(batch, block_timestamp);
+            }
+        }
 
         let mut tx = self.coordinator_tx.clone();
 
@@ -284,6 +311,19 @@ impl TPayloadManager for QuorumStorePayloadManager {
         };
     }
 
+    fn notify_block_inserted(&self, block: &Block) {
+        let (Some(batch_lifecycles), Some(payload)) = (&self.batch_lifecycles, block.payload())
+        else {
+            return;
+        };
+        if payload.is_direct() {
+            return;
+        }
+        for batch_info in Self::get_batch_infos(payload) {
+            batch_lifecycles.record_included(&batch_info, block.id(), block.timestamp_usecs());
+        }
+    }
+
     fn check_payload_availability(&self, block: &Block) -> bool {
         let Some(payload) = block.payload() else {
             return true;
@@ -667,6 +707,10 @@ impl TPayloadManager for ConsensusObserverPayloadManager {
         // noop
     }
 
+    fn notify_block_inserted(&self, _block: &Block) {
+        // noop
+    }
+
     fn check_payload_availability(&self, _block: &Block) -> bool {
         unreachable!("this method isn't used in ConsensusObserver")
     }
@@ -99,6 +99,11 @@ impl PendingVotes {
         }
     }
 
+    /// Returns the vote (if any) that was previously inserted for the given author
+    pub fn get_vote_by_author(&self, author: &Author) -> Option<&Vote> {
+        self.author_to_vote.get(author).map(|(vote, _)| vote)
+    }
+
     /// Insert a vote and if the vote is valid, return a QuorumCertificate preferentially over a
     /// TimeoutCertificate if either can can be formed
     pub fn insert_vote(
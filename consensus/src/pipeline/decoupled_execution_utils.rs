@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    block_storage::tracing::BlockTraces,
     consensus_observer::publisher::ConsensusPublisher,
     network::{IncomingCommitRequest, NetworkSender},
     pipeline::{
@@ -41,6 +42,7 @@ pub fn prepare_phases_and_buffer_manager(
     order_vote_enabled: bool,
     consensus_observer_config: ConsensusObserverConfig,
     consensus_publisher: Option<Arc<ConsensusPublisher>>,
+    block_traces: Option<Arc<BlockTraces>>,
 ) -> (
     PipelinePhase<ExecutionSchedulePhase>,
     PipelinePhase<ExecutionWaitPhase>,
@@ -56,7 +58,8 @@ pub fn prepare_phases_and_buffer_manager(
         create_channel::<CountedRequest<ExecutionRequest>>();
     let (execution_schedule_phase_response_tx, execution_schedule_phase_response_rx) =
         create_channel::<ExecutionWaitRequest>();
-    let execution_schedule_phase_processor = ExecutionSchedulePhase::new(execution_proxy);
+    let execution_schedule_phase_processor =
+        ExecutionSchedulePhase::new(execution_proxy, block_traces.clone());
     let execution_schedule_phase = PipelinePhase::new(
         execution_schedule_phase_request_rx,
         Some(execution_schedule_phase_response_tx),
@@ -68,7 +71,7 @@ pub fn prepare_phases_and_buffer_manager(
         create_channel::<CountedRequest<ExecutionWaitRequest>>();
     let (execution_wait_phase_response_tx, execution_wait_phase_response_rx) =
         create_channel::<ExecutionResponse>();
-    let execution_wait_phase_processor = ExecutionWaitPhase;
+    let execution_wait_phase_processor = ExecutionWaitPhase::new(block_traces.clone());
     let execution_wait_phase = PipelinePhase::new(
         execution_wait_phase_request_rx,
         Some(execution_wait_phase_response_tx),
@@ -82,7 +85,7 @@ pub fn prepare_phases_and_buffer_manager(
     let (signing_phase_response_tx, signing_phase_response_rx) =
         create_channel::<SigningResponse>();
 
-    let signing_phase_processor = SigningPhase::new(safety_rules);
+    let signing_phase_processor = SigningPhase::new(safety_rules, block_traces.clone());
     let signing_phase = PipelinePhase::new(
         signing_phase_request_rx,
         Some(signing_phase_response_tx),
@@ -94,7 +97,7 @@ pub fn prepare_phases_and_buffer_manager(
     let (persisting_phase_request_tx, persisting_phase_request_rx) =
         create_channel::<CountedRequest<PersistingRequest>>();
 
-    let persisting_phase_processor = PersistingPhase::new(persisting_proxy);
+    let persisting_phase_processor = PersistingPhase::new(persisting_proxy, block_traces);
     let persisting_phase = PipelinePhase::new(
         persisting_phase_request_rx,
         None,
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    block_storage::tracing::BlockTraces,
     consensus_observer::publisher::ConsensusPublisher,
     counters,
     error::StateSyncError,
@@ -153,6 +154,7 @@ pub struct ExecutionProxyClient {
     rand_storage: Arc<dyn RandStorage<AugmentedData>>,
     consensus_observer_config: ConsensusObserverConfig,
     consensus_publisher: Option<Arc<ConsensusPublisher>>,
+    block_traces: Option<Arc<BlockTraces>>,
 }
 
 impl ExecutionProxyClient {
@@ -166,6 +168,7 @@ impl ExecutionProxyClient {
         rand_storage: Arc<dyn RandStorage<AugmentedData>>,
         consensus_observer_config: ConsensusObserverConfig,
         consensus_publisher: Option<Arc<ConsensusPublisher>>,
+        block_traces: Option<Arc<BlockTraces>>,
     ) -> Self {
         Self {
             consensus_config,
@@ -178,6 +181,7 @@ impl ExecutionProxyClient {
             rand_storage,
             consensus_observer_config,
             consensus_publisher,
+            block_traces,
         }
     }
 
@@ -278,6 +282,7 @@ impl ExecutionProxyClient {
             onchain_consensus_config.order_vote_enabled(),
             consensus_observer_config,
             consensus_publisher,
+            self.block_traces.clone(),
         );
 
         tokio::spawn(execution_schedule_phase.start());
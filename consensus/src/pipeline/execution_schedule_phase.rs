@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    block_storage::tracing::{trace_block, BlockStage, BlockTraces},
     pipeline::{
         execution_wait_phase::ExecutionWaitRequest,
         pipeline_phase::{CountedRequest, StatelessPipeline},
@@ -44,11 +45,18 @@ impl Display for ExecutionRequest {
 
 pub struct ExecutionSchedulePhase {
     execution_proxy: Arc<dyn StateComputer>,
+    block_traces: Option<Arc<BlockTraces>>,
 }
 
 impl ExecutionSchedulePhase {
-    pub fn new(execution_proxy: Arc<dyn StateComputer>) -> Self {
-        Self { execution_proxy }
+    pub fn new(
+        execution_proxy: Arc<dyn StateComputer>,
+        block_traces: Option<Arc<BlockTraces>>,
+    ) -> Self {
+        Self {
+            execution_proxy,
+            block_traces,
+        }
     }
 }
 
@@ -88,6 +96,13 @@ impl StatelessPipeline for ExecutionSchedulePhase {
                     lifetime_guard.spawn(()),
                 )
                 .await;
+            trace_block(
+                self.block_traces.as_deref(),
+                b.id(),
+                b.epoch(),
+                b.round(),
+                BlockStage::EXECUTION_SCHEDULED,
+            );
             futs.push(fut)
         }
 
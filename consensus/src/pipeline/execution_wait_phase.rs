@@ -2,12 +2,18 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::pipeline::{buffer_item::ExecutionFut, pipeline_phase::StatelessPipeline};
+use crate::{
+    block_storage::tracing::{trace_block, BlockStage, BlockTraces},
+    pipeline::{buffer_item::ExecutionFut, pipeline_phase::StatelessPipeline},
+};
 use aptos_consensus_types::pipelined_block::PipelinedBlock;
 use aptos_crypto::HashValue;
 use aptos_executor_types::ExecutorResult;
 use async_trait::async_trait;
-use std::fmt::{Debug, Display, Formatter};
+use std::{
+    fmt::{Debug, Display, Formatter},
+    sync::Arc,
+};
 
 /// [ This class is used when consensus.decoupled = true ]
 /// ExecutionWaitPhase is a singleton that receives scheduled execution futures
@@ -35,7 +41,15 @@ pub struct ExecutionResponse {
     pub inner: ExecutorResult<Vec<PipelinedBlock>>,
 }
 
-pub struct ExecutionWaitPhase;
+pub struct ExecutionWaitPhase {
+    block_traces: Option<Arc<BlockTraces>>,
+}
+
+impl ExecutionWaitPhase {
+    pub fn new(block_traces: Option<Arc<BlockTraces>>) -> Self {
+        Self { block_traces }
+    }
+}
 
 #[async_trait]
 impl StatelessPipeline for ExecutionWaitPhase {
@@ -47,9 +61,19 @@ impl StatelessPipeline for ExecutionWaitPhase {
     async fn process(&self, req: ExecutionWaitRequest) -> ExecutionResponse {
         let ExecutionWaitRequest { block_id, fut } = req;
 
-        ExecutionResponse {
-            block_id,
-            inner: fut.await,
+        let inner = fut.await;
+        if let Ok(executed_blocks) = &inner {
+            for block in executed_blocks {
+                trace_block(
+                    self.block_traces.as_deref(),
+                    block.id(),
+                    block.epoch(),
+                    block.round(),
+                    BlockStage::EXECUTION_FINISHED,
+                );
+            }
         }
+
+        ExecutionResponse { block_id, inner }
     }
 }
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    block_storage::tracing::{trace_block, BlockStage, BlockTraces},
     pipeline::pipeline_phase::StatelessPipeline,
     state_replication::{StateComputer, StateComputerCommitCallBackType},
 };
@@ -46,11 +47,18 @@ pub type PersistingResponse = ExecutorResult<()>;
 
 pub struct PersistingPhase {
     persisting_handle: Arc<dyn StateComputer>,
+    block_traces: Option<Arc<BlockTraces>>,
 }
 
 impl PersistingPhase {
-    pub fn new(persisting_handle: Arc<dyn StateComputer>) -> Self {
-        Self { persisting_handle }
+    pub fn new(
+        persisting_handle: Arc<dyn StateComputer>,
+        block_traces: Option<Arc<BlockTraces>>,
+    ) -> Self {
+        Self {
+            persisting_handle,
+            block_traces,
+        }
     }
 }
 
@@ -68,8 +76,21 @@ impl StatelessPipeline for PersistingPhase {
             callback,
         } = req;
 
-        self.persisting_handle
+        let response = self
+            .persisting_handle
             .commit(&blocks, commit_ledger_info, callback)
-            .await
+            .await;
+        if response.is_ok() {
+            for block in &blocks {
+                trace_block(
+                    self.block_traces.as_deref(),
+                    block.id(),
+                    block.epoch(),
+                    block.round(),
+                    BlockStage::PERSISTED,
+                );
+            }
+        }
+        response
     }
 }
@@ -2,7 +2,10 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::pipeline::pipeline_phase::StatelessPipeline;
+use crate::{
+    block_storage::tracing::{trace_block, BlockStage, BlockTraces},
+    pipeline::pipeline_phase::StatelessPipeline,
+};
 use aptos_crypto::bls12381;
 use aptos_safety_rules::Error;
 use aptos_types::ledger_info::{LedgerInfo, LedgerInfoWithSignatures};
@@ -53,11 +56,18 @@ pub struct SigningResponse {
 
 pub struct SigningPhase {
     safety_rule_handle: Arc<dyn CommitSignerProvider>,
+    block_traces: Option<Arc<BlockTraces>>,
 }
 
 impl SigningPhase {
-    pub fn new(safety_rule_handle: Arc<dyn CommitSignerProvider>) -> Self {
-        Self { safety_rule_handle }
+    pub fn new(
+        safety_rule_handle: Arc<dyn CommitSignerProvider>,
+        block_traces: Option<Arc<BlockTraces>>,
+    ) -> Self {
+        Self {
+            safety_rule_handle,
+            block_traces,
+        }
     }
 }
 
@@ -74,10 +84,22 @@ impl StatelessPipeline for SigningPhase {
             commit_ledger_info,
         } = req;
 
+        let signature_result = self
+            .safety_rule_handle
+            .sign_commit_vote(ordered_ledger_info, commit_ledger_info.clone());
+        if signature_result.is_ok() {
+            let commit_info = commit_ledger_info.commit_info();
+            trace_block(
+                self.block_traces.as_deref(),
+                commit_info.id(),
+                commit_info.epoch(),
+                commit_info.round(),
+                BlockStage::SIGNED_COMMIT_VOTE,
+            );
+        }
+
         SigningResponse {
-            signature_result: self
-                .safety_rule_handle
-                .sign_commit_vote(ordered_ledger_info, commit_ledger_info.clone()),
+            signature_result,
             commit_ledger_info,
         }
     }
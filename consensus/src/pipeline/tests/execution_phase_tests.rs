@@ -37,8 +37,8 @@ pub struct ExecutionPhaseForTest {
 
 impl ExecutionPhaseForTest {
     pub fn new(execution_proxy: Arc<dyn StateComputer>) -> Self {
-        let schedule_phase = ExecutionSchedulePhase::new(execution_proxy);
-        let wait_phase = ExecutionWaitPhase;
+        let schedule_phase = ExecutionSchedulePhase::new(execution_proxy, None);
+        let wait_phase = ExecutionWaitPhase::new(None);
         Self {
             schedule_phase,
             wait_phase,
@@ -127,7 +127,7 @@ fn signing_phase_tests() {
 
     let (safety_rule_handle, signers) = prepare_safety_rules();
 
-    let signing_phase = SigningPhase::new(safety_rule_handle);
+    let signing_phase = SigningPhase::new(safety_rule_handle, None);
 
     // unit tests
     let mut unit_phase_tester = PhaseTester::<SigningPhase>::new();
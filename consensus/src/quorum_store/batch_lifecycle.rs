@@ -0,0 +1,271 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_consensus_types::proof_of_store::{BatchId, BatchInfo};
+use aptos_crypto::HashValue;
+use aptos_infallible::{duration_since_epoch, Mutex};
+use aptos_types::PeerId;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+/// The maximum number of batch lifecycles to keep in memory
+pub const MAX_NUM_BATCH_LIFECYCLES: usize = 10_000;
+
+/// The stages of a batch, in lifecycle order. The stage of a batch only moves forward.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchStage {
+    /// The batch was created (locally) or received (from a peer) and stored
+    Created,
+    /// A proof of store was formed (locally) or received (from a peer)
+    ProofOfStore,
+    /// The batch was included in a block (inserted into the block store)
+    Included,
+    /// A block containing the batch was committed
+    Committed,
+    /// The batch expired before it was committed
+    Expired,
+    /// The batch was removed from the batch store
+    GarbageCollected,
+}
+
+/// The lifecycle of a single batch. All times are local times (in microseconds).
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BatchLifecycle {
+    pub digest: HashValue,
+    pub author: PeerId,
+    pub batch_id: BatchId,
+    pub epoch: u64,
+    pub num_txns: u64,
+    pub num_bytes: u64,
+    pub expiration_usecs: u64,
+    pub stage: BatchStage,
+    pub created_time_usecs: Option<u64>,
+    /// The validators that have signed the batch so far (only tracked for local batches)
+    pub signers: Vec<PeerId>,
+    pub proof_of_store_time_usecs: Option<u64>,
+    /// The id of the last block the batch was included in
+    pub included_block_id: Option<HashValue>,
+    /// The timestamp of the last block the batch was included in
+    pub included_block_timestamp_usecs: Option<u64>,
+    /// The timestamp of the committed block that contained the batch
+    pub committed_block_timestamp_usecs: Option<u64>,
+    pub expired_time_usecs: Option<u64>,
+    pub garbage_collected_time_usecs: Option<u64>,
+}
+
+impl BatchLifecycle {
+    fn new(batch_info: &BatchInfo) -> Self {
+        Self {
+            digest: *batch_info.digest(),
+            author: batch_info.author(),
+            batch_id: batch_info.batch_id(),
+            epoch: batch_info.epoch(),
+            num_txns: batch_info.num_txns(),
+            num_bytes: batch_info.num_bytes(),
+            expiration_usecs: batch_info.expiration(),
+            stage: BatchStage::Created,
+            created_time_usecs: None,
+            signers: vec![],
+            proof_of_store_time_usecs: None,
+            included_block_id: None,
+            included_block_timestamp_usecs: None,
+            committed_block_timestamp_usecs: None,
+            expired_time_usecs: None,
+            garbage_collected_time_usecs: None,
+        }
+    }
+
+    /// Moves the batch to the given stage (if it is further along the lifecycle)
+    fn advance_stage(&mut self, stage: BatchStage) {
+        self.stage = self.stage.max(stage);
+    }
+}
+
+/// The backlog of a single author in the proof queue
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AuthorBacklog {
+    /// The number of uncommitted batches without a proof of store
+    pub num_batches_without_proof: u64,
+    /// The number of uncommitted proofs of store
+    pub num_proofs: u64,
+    /// The number of transactions in the uncommitted proofs of store
+    pub num_txns_in_proofs: u64,
+}
+
+/// A summary of the tracked batches and the proof queue backlog
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BatchLifecycleSummary {
+    pub num_tracked_batches: usize,
+    /// The number of tracked batches per author and stage
+    pub stages_per_author: BTreeMap<PeerId, BTreeMap<BatchStage, u64>>,
+    /// The latest proof queue backlog per author
+    pub backlog_per_author: BTreeMap<PeerId, AuthorBacklog>,
+}
+
+#[derive(Default)]
+struct BatchLifecyclesInner {
+    batch_order: VecDeque<HashValue>,
+    lifecycles: HashMap<HashValue, BatchLifecycle>,
+    backlog_per_author: BTreeMap<PeerId, AuthorBacklog>,
+}
+
+/// A bounded ring buffer of batch lifecycles. Once the buffer is full,
+/// the lifecycles of the oldest batches are evicted.
+pub struct BatchLifecycles {
+    max_num_lifecycles: usize,
+    inner: Mutex<BatchLifecyclesInner>,
+}
+
+impl BatchLifecycles {
+    pub fn new(max_num_lifecycles: usize) -> Self {
+        Self {
+            max_num_lifecycles,
+            inner: Mutex::new(BatchLifecyclesInner::default()),
+        }
+    }
+
+    /// Records that the batch was created or received (and stored)
+    pub fn record_created(&self, batch_info: &BatchInfo) {
+        self.update(batch_info, |lifecycle, time_usecs| {
+            lifecycle.created_time_usecs.get_or_insert(time_usecs);
+        });
+    }
+
+    /// Records a signature from the given signer for the batch
+    pub fn record_signature(&self, batch_info: &BatchInfo, signer: PeerId) {
+        self.update(batch_info, |lifecycle, _| {
+            if !lifecycle.signers.contains(&signer) {
+                lifecycle.signers.push(signer);
+            }
+        });
+    }
+
+    /// Records that a proof of store was formed or received for the batch
+    pub fn record_proof_of_store(&self, batch_info: &BatchInfo) {
+        self.update(batch_info, |lifecycle, time_usecs| {
+            lifecycle
+                .proof_of_store_time_usecs
+                .get_or_insert(time_usecs);
+            lifecycle.advance_stage(BatchStage::ProofOfStore);
+        });
+    }
+
+    /// Records that the batch was included in the block with the given id and timestamp
+    pub fn record_included(
+        &self,
+        batch_info: &BatchInfo,
+        block_id: HashValue,
+        block_timestamp_usecs: u64,
+    ) {
+        self.update(batch_info, |lifecycle, _| {
+            lifecycle.included_block_id = Some(block_id);
+            lifecycle.included_block_timestamp_usecs = Some(block_timestamp_usecs);
+            lifecycle.advance_stage(BatchStage::Included);
+        });
+    }
+
+    /// Records that a block (with the given timestamp) containing the batch was committed
+    pub fn record_committed(&self, batch_info: &BatchInfo, block_timestamp_usecs: u64) {
+        self.update(batch_info, |lifecycle, _| {
+            lifecycle
+                .committed_block_timestamp_usecs
+                .get_or_insert(block_timestamp_usecs);
+            lifecycle.advance_stage(BatchStage::Committed);
+        });
+    }
+
+    /// Records that the batch expired before it was committed
+    pub fn record_expired(&self, batch_info: &BatchInfo) {
+        self.update(batch_info, |lifecycle, time_usecs| {
+            lifecycle.expired_time_usecs.get_or_insert(time_usecs);
+            lifecycle.advance_stage(BatchStage::Expired);
+        });
+    }
+
+    /// Records that the batch was removed from the batch store. Note: only
+    /// batches that are already tracked are updated (the batch info is unknown).
+    pub fn record_garbage_collected(&self, digest: &HashValue) {
+        if self.max_num_lifecycles == 0 {
+            return;
+        }
+
+        let time_usecs = duration_since_epoch().as_micros() as u64;
+        if let Some(lifecycle) = self.inner.lock().lifecycles.get_mut(digest) {
+            lifecycle
+                .garbage_collected_time_usecs
+                .get_or_insert(time_usecs);
+            lifecycle.advance_stage(BatchStage::GarbageCollected);
+        }
+    }
+
+    /// Replaces the proof queue backlog per author
+    pub fn update_backlog(&self, backlog_per_author: BTreeMap<PeerId, AuthorBacklog>) {
+        self.inner.lock().backlog_per_author = backlog_per_author;
+    }
+
+    /// Updates the lifecycle of the given batch (creating it if it doesn't exist)
+    fn update(&self, batch_info: &BatchInfo, update: impl FnOnce(&mut BatchLifecycle, u64)) {
+        if self.max_num_lifecycles == 0 {
+            return;
+        }
+
+        let digest = *batch_info.digest();
+        let time_usecs = duration_since_epoch().as_micros() as u64;
+        let mut inner = self.inner.lock();
+        if !inner.lifecycles.contains_key(&digest) {
+            // Evict the oldest lifecycles if the buffer is full
+            while inner.batch_order.len() >= self.max_num_lifecycles {
+                if let Some(evicted_digest) = inner.batch_order.pop_front() {
+                    inner.lifecycles.remove(&evicted_digest);
+                }
+            }
+            inner.batch_order.push_back(digest);
+            inner
+                .lifecycles
+                .insert(digest, BatchLifecycle::new(batch_info));
+        }
+
+        let lifecycle = inner
+            .lifecycles
+            .get_mut(&digest)
+            .expect("The batch lifecycle must exist!");
+        update(lifecycle, time_usecs);
+    }
+
+    /// Returns the lifecycle of the given batch (if one exists)
+    pub fn get_lifecycle(&self, digest: &HashValue) -> Option<BatchLifecycle> {
+        self.inner.lock().lifecycles.get(digest).cloned()
+    }
+
+    /// Returns the lifecycles of all batches from the given author (ordered from oldest to newest)
+    pub fn get_lifecycles_for_author(&self, author: &PeerId) -> Vec<BatchLifecycle> {
+        let inner = self.inner.lock();
+        inner
+            .batch_order
+            .iter()
+            .filter_map(|digest| inner.lifecycles.get(digest))
+            .filter(|lifecycle| lifecycle.author == *author)
+            .cloned()
+            .collect()
+    }
+
+    /// Returns a summary of the tracked batches and the proof queue backlog
+    pub fn get_summary(&self) -> BatchLifecycleSummary {
+        let inner = self.inner.lock();
+        let mut stages_per_author: BTreeMap<PeerId, BTreeMap<BatchStage, u64>> = BTreeMap::new();
+        for lifecycle in inner.lifecycles.values() {
+            *stages_per_author
+                .entry(lifecycle.author)
+                .or_default()
+                .entry(lifecycle.stage)
+                .or_default() += 1;
+        }
+
+        BatchLifecycleSummary {
+            num_tracked_batches: inner.lifecycles.len(),
+            stages_per_author,
+            backlog_per_author: inner.backlog_per_author.clone(),
+        }
+    }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::{
+    batch_lifecycle::{AuthorBacklog, BatchLifecycles},
     batch_store::BatchStore,
     utils::{BatchKey, BatchSortKey, TimeExpirations},
 };
@@ -62,10 +63,15 @@ pub struct BatchProofQueue {
     remaining_proofs: u64,
     remaining_local_txns: u64,
     remaining_local_proofs: u64,
+    batch_lifecycles: Option<Arc<BatchLifecycles>>,
 }
 
 impl BatchProofQueue {
-    pub(crate) fn new(my_peer_id: PeerId, batch_store: Arc<BatchStore>) -> Self {
+    pub(crate) fn new(
+        my_peer_id: PeerId,
+        batch_store: Arc<BatchStore>,
+        batch_lifecycles: Option<Arc<BatchLifecycles>>,
+    ) -> Self {
         Self {
             my_peer_id,
             author_to_batches: HashMap::new(),
@@ -78,6 +84,7 @@ impl BatchProofQueue {
             remaining_proofs: 0,
             remaining_local_txns: 0,
             remaining_local_proofs: 0,
+            batch_lifecycles,
         }
     }
 
@@ -163,6 +170,10 @@ impl BatchProofQueue {
             return;
         }
 
+        if let Some(batch_lifecycles) = &self.batch_lifecycles {
+            batch_lifecycles.record_proof_of_store(proof.info());
+        }
+
         let author = proof.author();
         let bucket = proof.gas_bucket_start();
         let num_txns = proof.num_txns();
@@ -619,6 +630,11 @@ impl BatchProofQueue {
                         .items
                         .get(&key.batch_key)
                         .expect("Entry for unexpired batch must exist");
+                    if !item.is_committed() {
+                        if let Some(batch_lifecycles) = &self.batch_lifecycles {
+                            batch_lifecycles.record_expired(&batch);
+                        }
+                    }
                     if item.proof.is_some() {
                         // non-committed proof that is expired
                         num_expired_but_not_committed += 1;
@@ -683,6 +699,28 @@ impl BatchProofQueue {
         count
     }
 
+    // The uncommitted batches and proofs in the queue, per author
+    pub(crate) fn backlog_per_author(&self) -> BTreeMap<PeerId, AuthorBacklog> {
+        let mut backlog_per_author = BTreeMap::new();
+        for (author, batches) in &self.author_to_batches {
+            let mut backlog = AuthorBacklog::default();
+            for (sort_key, batch) in batches {
+                match self.items.get(&sort_key.batch_key) {
+                    Some(item) if item.proof.is_some() => {
+                        backlog.num_proofs += 1;
+                        backlog.num_txns_in_proofs += batch.num_txns();
+                    },
+                    Some(item) if !item.is_committed() => {
+                        backlog.num_batches_without_proof += 1;
+                    },
+                    _ => {},
+                }
+            }
+            backlog_per_author.insert(*author, backlog);
+        }
+        backlog_per_author
+    }
+
     pub(crate) fn remaining_txns_and_proofs(&self) -> (u64, u64) {
         let start = Instant::now();
         counters::NUM_TOTAL_TXNS_LEFT_ON_UPDATE.observe(self.remaining_txns_with_duplicates as f64);
@@ -719,6 +757,14 @@ impl BatchProofQueue {
             .observe(self.num_proofs_without_batch_summary() as f64);
         counters::PROOFS_WITH_BATCH_SUMMARY.observe(self.num_proofs_with_batch_summary() as f64);
 
+        // Publish the backlog per author (for batch lifecycle inspection)
+        if let Some(batch_lifecycles) = &self.batch_lifecycles {
+            sample!(
+                SampleRate::Duration(Duration::from_secs(1)),
+                batch_lifecycles.update_backlog(self.backlog_per_author())
+            );
+        }
+
         counters::PROOF_QUEUE_REMAINING_TXNS_DURATION.observe_duration(start.elapsed());
         (remaining_txns_without_duplicates, self.remaining_proofs)
     }
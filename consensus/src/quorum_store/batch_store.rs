@@ -4,6 +4,7 @@
 use crate::{
     network::QuorumStoreSender,
     quorum_store::{
+        batch_lifecycle::BatchLifecycles,
         batch_requester::BatchRequester,
         counters,
         quorum_store_db::QuorumStoreStorage,
@@ -117,6 +118,7 @@ pub struct BatchStore {
     batch_quota: usize,
     validator_signer: ValidatorSigner,
     persist_subscribers: DashMap<HashValue, Vec<oneshot::Sender<PersistedValue>>>,
+    batch_lifecycles: Option<Arc<BatchLifecycles>>,
 }
 
 impl BatchStore {
@@ -128,6 +130,7 @@ impl BatchStore {
         db_quota: usize,
         batch_quota: usize,
         validator_signer: ValidatorSigner,
+        batch_lifecycles: Option<Arc<BatchLifecycles>>,
     ) -> Self {
         let db_clone = db.clone();
         let batch_store = Self {
@@ -142,6 +145,7 @@ impl BatchStore {
             batch_quota,
             validator_signer,
             persist_subscribers: DashMap::new(),
+            batch_lifecycles,
         };
         let db_content = db_clone
             .get_all_batches()
@@ -303,6 +307,9 @@ impl BatchStore {
             // No longer holding the lock on db_cache entry.
             if let Some(value) = removed_value {
                 self.free_quota(value);
+                if let Some(batch_lifecycles) = &self.batch_lifecycles {
+                    batch_lifecycles.record_garbage_collected(&h);
+                }
                 ret.push(h);
             }
         }
@@ -313,6 +320,9 @@ impl BatchStore {
         match self.save(&persist_request) {
             Ok(needs_db) => {
                 let batch_info = persist_request.batch_info().clone();
+                if let Some(batch_lifecycles) = &self.batch_lifecycles {
+                    batch_lifecycles.record_created(&batch_info);
+                }
                 trace!("QS: sign digest {}", persist_request.digest());
                 if needs_db {
                     #[allow(clippy::unwrap_in_result)]
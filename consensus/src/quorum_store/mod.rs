@@ -5,6 +5,8 @@ pub mod counters;
 /// Equivalent to directly fetching blocks from mempool without a quorum store.
 pub mod direct_mempool_quorum_store;
 
+pub mod batch_lifecycle;
+
 pub(crate) mod batch_coordinator;
 pub(crate) mod batch_generator;
 pub(crate) mod batch_proof_queue;
@@ -6,7 +6,8 @@ use crate::{
     monitor,
     network::QuorumStoreSender,
     quorum_store::{
-        batch_generator::BatchGeneratorCommand, batch_store::BatchReader, counters, utils::Timeouts,
+        batch_generator::BatchGeneratorCommand, batch_lifecycle::BatchLifecycles,
+        batch_store::BatchReader, counters, utils::Timeouts,
     },
 };
 use aptos_consensus_types::proof_of_store::{
@@ -146,6 +147,7 @@ pub(crate) struct ProofCoordinator {
     batch_generator_cmd_tx: tokio::sync::mpsc::Sender<BatchGeneratorCommand>,
     proof_cache: ProofCache,
     broadcast_proofs: bool,
+    batch_lifecycles: Option<Arc<BatchLifecycles>>,
 }
 
 //PoQS builder object - gather signed digest to form PoQS
@@ -157,6 +159,7 @@ impl ProofCoordinator {
         batch_generator_cmd_tx: tokio::sync::mpsc::Sender<BatchGeneratorCommand>,
         proof_cache: ProofCache,
         broadcast_proofs: bool,
+        batch_lifecycles: Option<Arc<BatchLifecycles>>,
     ) -> Self {
         Self {
             peer_id,
@@ -168,6 +171,7 @@ impl ProofCoordinator {
             batch_generator_cmd_tx,
             proof_cache,
             broadcast_proofs,
+            batch_lifecycles,
         }
     }
 
@@ -223,8 +227,15 @@ impl ProofCoordinator {
             .get_mut(signed_batch_info.batch_info())
         {
             value.add_signature(&signed_batch_info, validator_verifier)?;
+            if let Some(batch_lifecycles) = &self.batch_lifecycles {
+                batch_lifecycles
+                    .record_signature(signed_batch_info.batch_info(), signed_batch_info.signer());
+            }
             if !value.completed && value.ready(validator_verifier) {
                 let proof = value.take(validator_verifier);
+                if let Some(batch_lifecycles) = &self.batch_lifecycles {
+                    batch_lifecycles.record_proof_of_store(proof.info());
+                }
                 // proof validated locally, so adding to cache
                 self.proof_cache
                     .insert(proof.info().clone(), proof.multi_signature().clone());
@@ -4,7 +4,10 @@
 use super::batch_store::BatchStore;
 use crate::{
     monitor,
-    quorum_store::{batch_generator::BackPressure, batch_proof_queue::BatchProofQueue, counters},
+    quorum_store::{
+        batch_generator::BackPressure, batch_lifecycle::BatchLifecycles,
+        batch_proof_queue::BatchProofQueue, counters,
+    },
 };
 use aptos_consensus_types::{
     common::{Payload, PayloadFilter, ProofWithData, TxnSummaryWithExpiration},
@@ -45,9 +48,10 @@ impl ProofManager {
         batch_store: Arc<BatchStore>,
         allow_batches_without_pos_in_proposal: bool,
         enable_opt_quorum_store: bool,
+        batch_lifecycles: Option<Arc<BatchLifecycles>>,
     ) -> Self {
         Self {
-            batch_proof_queue: BatchProofQueue::new(my_peer_id, batch_store),
+            batch_proof_queue: BatchProofQueue::new(my_peer_id, batch_store, batch_lifecycles),
             back_pressure_total_txn_limit,
             remaining_total_txn_num: 0,
             back_pressure_total_proof_limit,
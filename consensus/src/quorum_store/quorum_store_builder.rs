@@ -11,6 +11,7 @@ use crate::{
     quorum_store::{
         batch_coordinator::{BatchCoordinator, BatchCoordinatorCommand},
         batch_generator::{BackPressure, BatchGenerator, BatchGeneratorCommand},
+        batch_lifecycle::BatchLifecycles,
         batch_requester::BatchRequester,
         batch_store::{BatchReader, BatchReaderImpl, BatchStore},
         counters,
@@ -148,9 +149,11 @@ pub struct InnerBuilder {
     batch_store: Option<Arc<BatchStore>>,
     batch_reader: Option<Arc<dyn BatchReader>>,
     broadcast_proofs: bool,
+    batch_lifecycles: Option<Arc<BatchLifecycles>>,
 }
 
 impl InnerBuilder {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         epoch: u64,
         author: Author,
@@ -166,6 +169,7 @@ impl InnerBuilder {
         backend: SecureBackend,
         quorum_store_storage: Arc<dyn QuorumStoreStorage>,
         broadcast_proofs: bool,
+        batch_lifecycles: Option<Arc<BatchLifecycles>>,
     ) -> Self {
         let (coordinator_tx, coordinator_rx) = futures_channel::mpsc::channel(config.channel_size);
         let (batch_generator_cmd_tx, batch_generator_cmd_rx) =
@@ -221,6 +225,7 @@ impl InnerBuilder {
             batch_store: None,
             batch_reader: None,
             broadcast_proofs,
+            batch_lifecycles,
         }
     }
 
@@ -260,6 +265,7 @@ impl InnerBuilder {
             self.config.db_quota,
             self.config.batch_quota,
             signer,
+            self.batch_lifecycles.clone(),
         ));
         self.batch_store = Some(batch_store.clone());
         let batch_reader = Arc::new(BatchReaderImpl::new(batch_store.clone(), batch_requester));
@@ -345,6 +351,7 @@ impl InnerBuilder {
             self.batch_generator_cmd_tx.clone(),
             self.proof_cache,
             self.broadcast_proofs,
+            self.batch_lifecycles.clone(),
         );
         spawn_named!(
             "proof_coordinator",
@@ -366,6 +373,7 @@ impl InnerBuilder {
             self.batch_store.clone().unwrap(),
             self.config.allow_batches_without_pos_in_proposal,
             self.config.enable_opt_quorum_store,
+            self.batch_lifecycles.clone(),
         );
         spawn_named!(
             "proof_manager",
@@ -446,6 +454,7 @@ impl InnerBuilder {
                 self.coordinator_tx.clone(),
                 consensus_publisher,
                 self.verifier.get_ordered_account_addresses(),
+                self.batch_lifecycles.clone(),
             )),
             Some(self.quorum_store_msg_tx.clone()),
         )
@@ -0,0 +1,140 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::quorum_store::batch_lifecycle::{AuthorBacklog, BatchLifecycles, BatchStage};
+use aptos_consensus_types::proof_of_store::{BatchId, BatchInfo};
+use aptos_crypto::HashValue;
+use aptos_types::PeerId;
+use std::collections::BTreeMap;
+
+/// Returns a new batch info for the given author and batch ID
+fn create_batch_info(author: PeerId, batch_id: u64) -> BatchInfo {
+    BatchInfo::new(
+        author,
+        BatchId::new_for_test(batch_id),
+        1,
+        1000,
+        HashValue::random(),
+        10,
+        100,
+        0,
+    )
+}
+
+#[test]
+fn test_batch_lifecycle_stages() {
+    let batch_lifecycles = BatchLifecycles::new(10);
+    let author = PeerId::random();
+    let batch_info = create_batch_info(author, 0);
+
+    // Create the batch and add signatures (including a duplicate signature)
+    batch_lifecycles.record_created(&batch_info);
+    let signers = vec![author, PeerId::random()];
+    for signer in signers.iter().chain(signers.iter()) {
+        batch_lifecycles.record_signature(&batch_info, *signer);
+    }
+    let lifecycle = batch_lifecycles.get_lifecycle(batch_info.digest()).unwrap();
+    assert_eq!(lifecycle.stage, BatchStage::Created);
+    assert!(lifecycle.created_time_usecs.is_some());
+    assert_eq!(lifecycle.signers, signers);
+    assert_eq!(lifecycle.num_txns, batch_info.num_txns());
+
+    // Form the proof, include the batch in a block and commit it
+    let block_id = HashValue::random();
+    batch_lifecycles.record_proof_of_store(&batch_info);
+    batch_lifecycles.record_included(&batch_info, block_id, 500);
+    batch_lifecycles.record_committed(&batch_info, 600);
+    let lifecycle = batch_lifecycles.get_lifecycle(batch_info.digest()).unwrap();
+    assert_eq!(lifecycle.stage, BatchStage::Committed);
+    assert!(lifecycle.proof_of_store_time_usecs.is_some());
+    assert_eq!(lifecycle.included_block_id, Some(block_id));
+    assert_eq!(lifecycle.included_block_timestamp_usecs, Some(500));
+    assert_eq!(lifecycle.committed_block_timestamp_usecs, Some(600));
+
+    // Verify that the stage never moves backwards
+    batch_lifecycles.record_proof_of_store(&batch_info);
+    let lifecycle = batch_lifecycles.get_lifecycle(batch_info.digest()).unwrap();
+    assert_eq!(lifecycle.stage, BatchStage::Committed);
+
+    // Garbage collect the batch and verify the stage
+    batch_lifecycles.record_garbage_collected(batch_info.digest());
+    let lifecycle = batch_lifecycles.get_lifecycle(batch_info.digest()).unwrap();
+    assert_eq!(lifecycle.stage, BatchStage::GarbageCollected);
+    assert!(lifecycle.garbage_collected_time_usecs.is_some());
+
+    // Verify that unknown batches are not tracked when garbage collected
+    let unknown_digest = HashValue::random();
+    batch_lifecycles.record_garbage_collected(&unknown_digest);
+    assert!(batch_lifecycles.get_lifecycle(&unknown_digest).is_none());
+}
+
+#[test]
+fn test_batch_lifecycle_eviction() {
+    // Create a buffer that holds at most two lifecycles
+    let batch_lifecycles = BatchLifecycles::new(2);
+
+    // Record the creation of three different batches
+    let author = PeerId::random();
+    let batch_infos: Vec<_> = (0..3).map(|id| create_batch_info(author, id)).collect();
+    for batch_info in &batch_infos {
+        batch_lifecycles.record_created(batch_info);
+    }
+
+    // Verify the oldest lifecycle was evicted
+    assert!(batch_lifecycles
+        .get_lifecycle(batch_infos[0].digest())
+        .is_none());
+    let lifecycles = batch_lifecycles.get_lifecycles_for_author(&author);
+    let digests: Vec<_> = lifecycles
+        .iter()
+        .map(|lifecycle| lifecycle.digest)
+        .collect();
+    assert_eq!(digests, vec![
+        *batch_infos[1].digest(),
+        *batch_infos[2].digest()
+    ]);
+}
+
+#[test]
+fn test_batch_lifecycle_summary() {
+    let batch_lifecycles = BatchLifecycles::new(10);
+
+    // Record batches for two authors (one of which expires)
+    let author_0 = PeerId::random();
+    let author_1 = PeerId::random();
+    let batch_info_0 = create_batch_info(author_0, 0);
+    let batch_info_1 = create_batch_info(author_0, 1);
+    let batch_info_2 = create_batch_info(author_1, 2);
+    batch_lifecycles.record_created(&batch_info_0);
+    batch_lifecycles.record_proof_of_store(&batch_info_1);
+    batch_lifecycles.record_expired(&batch_info_2);
+
+    // Update the backlog
+    let mut backlog_per_author = BTreeMap::new();
+    backlog_per_author.insert(author_0, AuthorBacklog {
+        num_batches_without_proof: 1,
+        num_proofs: 1,
+        num_txns_in_proofs: 10,
+    });
+    batch_lifecycles.update_backlog(backlog_per_author.clone());
+
+    // Verify the summary
+    let summary = batch_lifecycles.get_summary();
+    assert_eq!(summary.num_tracked_batches, 3);
+    assert_eq!(summary.backlog_per_author, backlog_per_author);
+    assert_eq!(
+        summary.stages_per_author[&author_0],
+        BTreeMap::from([(BatchStage::Created, 1), (BatchStage::ProofOfStore, 1)])
+    );
+    assert_eq!(
+        summary.stages_per_author[&author_1],
+        BTreeMap::from([(BatchStage::Expired, 1)])
+    );
+
+    // Verify the summary can be serialized to JSON
+    let summary_json = serde_json::to_value(&summary).unwrap();
+    assert_eq!(
+        summary_json["stages_per_author"][author_1.to_hex().as_str()]["expired"],
+        1
+    );
+}
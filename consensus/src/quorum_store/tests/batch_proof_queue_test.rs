@@ -62,7 +62,7 @@ fn proof_of_store_with_size(
 fn test_proof_queue_sorting() {
     let my_peer_id = PeerId::random();
     let batch_store = batch_store_for_test(5 * 1024 * 1024);
-    let mut proof_queue = BatchProofQueue::new(my_peer_id, batch_store);
+    let mut proof_queue = BatchProofQueue::new(my_peer_id, batch_store, None);
 
     let author_0 = PeerId::random();
     let author_1 = PeerId::random();
@@ -149,7 +149,7 @@ fn test_proof_queue_sorting() {
 fn test_proof_calculate_remaining_txns_and_proofs() {
     let my_peer_id = PeerId::random();
     let batch_store = batch_store_for_test(5 * 1024 * 1024);
-    let mut proof_queue = BatchProofQueue::new(my_peer_id, batch_store);
+    let mut proof_queue = BatchProofQueue::new(my_peer_id, batch_store, None);
     let now_in_secs = aptos_infallible::duration_since_epoch().as_secs() as u64;
     let now_in_usecs = aptos_infallible::duration_since_epoch().as_micros() as u64;
     let author_0 = PeerId::random();
@@ -409,7 +409,7 @@ fn test_proof_calculate_remaining_txns_and_proofs() {
 fn test_proof_pull_proofs_with_duplicates() {
     let my_peer_id = PeerId::random();
     let batch_store = batch_store_for_test(5 * 1024 * 1024);
-    let mut proof_queue = BatchProofQueue::new(my_peer_id, batch_store);
+    let mut proof_queue = BatchProofQueue::new(my_peer_id, batch_store, None);
     let now_in_secs = aptos_infallible::duration_since_epoch().as_secs() as u64;
     let now_in_usecs = now_in_secs * 1_000_000;
     let txns = vec![
@@ -657,7 +657,7 @@ fn test_proof_pull_proofs_with_duplicates() {
 fn test_proof_queue_soft_limit() {
     let my_peer_id = PeerId::random();
     let batch_store = batch_store_for_test(5 * 1024 * 1024);
-    let mut proof_queue = BatchProofQueue::new(my_peer_id, batch_store);
+    let mut proof_queue = BatchProofQueue::new(my_peer_id, batch_store, None);
 
     let author = PeerId::random();
 
@@ -694,3 +694,37 @@ fn test_proof_queue_soft_limit() {
     assert_eq!(pulled.len(), 2);
     assert_eq!(num_unique_txns, 20);
 }
+
+#[test]
+fn test_proof_queue_backlog_per_author() {
+    let my_peer_id = PeerId::random();
+    let batch_store = batch_store_for_test(5 * 1024 * 1024);
+    let mut proof_queue = BatchProofQueue::new(my_peer_id, batch_store, None);
+    let expiration = aptos_infallible::duration_since_epoch().as_micros() as u64 + 1_000_000;
+
+    // Insert two proofs for the first author (and commit one of them)
+    let author_0 = PeerId::random();
+    let proof_0 = proof_of_store_with_size(author_0, BatchId::new_for_test(0), 100, expiration, 5);
+    let proof_1 = proof_of_store_with_size(author_0, BatchId::new_for_test(1), 100, expiration, 7);
+    proof_queue.insert_proof(proof_0.clone());
+    proof_queue.insert_proof(proof_1);
+    proof_queue.mark_committed(vec![proof_0.info().clone()]);
+
+    // Insert a batch without a proof for the second author
+    let author_1 = PeerId::random();
+    let batch_info = proof_of_store(author_1, BatchId::new_for_test(2), 100, expiration)
+        .info()
+        .clone();
+    proof_queue.insert_batches(vec![(batch_info, vec![])]);
+
+    // Verify the backlog per author
+    let backlog_per_author = proof_queue.backlog_per_author();
+    assert_eq!(backlog_per_author.len(), 2);
+    let backlog_0 = &backlog_per_author[&author_0];
+    assert_eq!(backlog_0.num_proofs, 1);
+    assert_eq!(backlog_0.num_txns_in_proofs, 7);
+    assert_eq!(backlog_0.num_batches_without_proof, 0);
+    let backlog_1 = &backlog_per_author[&author_1];
+    assert_eq!(backlog_1.num_proofs, 0);
+    assert_eq!(backlog_1.num_batches_without_proof, 1);
+}
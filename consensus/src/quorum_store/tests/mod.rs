@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod batch_generator_test;
+mod batch_lifecycle_test;
 mod batch_proof_queue_test;
 mod batch_requester_test;
 mod batch_store_test;
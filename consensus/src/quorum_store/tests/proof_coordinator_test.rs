@@ -58,6 +58,7 @@ async fn test_proof_coordinator_basic() {
         tx,
         proof_cache.clone(),
         true,
+        None,
     );
     let (proof_coordinator_tx, proof_coordinator_rx) = channel(100);
     let (tx, mut rx) = channel(100);
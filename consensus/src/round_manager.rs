@@ -13,6 +13,7 @@ use crate::{
         PROPOSAL_VOTE_BROADCASTED, PROPOSED_VTXN_BYTES, PROPOSED_VTXN_COUNT,
         QC_AGGREGATED_FROM_VOTES, SYNC_INFO_RECEIVED_WITH_NEWER_CERT,
     },
+    equivocation_evidence::EquivocationEvidence,
     error::{error_kind, VerifyError},
     liveness::{
         proposal_generator::ProposalGenerator,
@@ -547,6 +548,12 @@ impl RoundManager {
             proposal_msg.proposal().timestamp_usecs(),
             BlockStage::ROUND_MANAGER_RECEIVED,
         );
+        self.block_store.trace_block(
+            proposal_msg.proposal().id(),
+            proposal_msg.proposal().epoch(),
+            proposal_msg.proposal().round(),
+            BlockStage::PROPOSAL_RECEIVED,
+        );
         info!(
             self.new_log(LogEvent::ReceiveProposal)
                 .remote_peer(proposal_msg.proposer()),
@@ -856,8 +863,20 @@ impl RoundManager {
             self.local_config.max_receiving_block_bytes,
         );
 
+        let is_valid_proposal = self.proposer_election.is_valid_proposal(&proposal);
+        if !is_valid_proposal {
+            // If the proposer equivocated, persist the conflicting proposals as evidence
+            if let Some(conflicting_proposal) =
+                self.proposer_election.get_conflicting_proposal(&proposal)
+            {
+                self.save_equivocation_evidence(EquivocationEvidence::new_proposal_evidence(
+                    conflicting_proposal,
+                    proposal.clone(),
+                ));
+            }
+        }
         ensure!(
-            self.proposer_election.is_valid_proposal(&proposal),
+            is_valid_proposal,
             "[RoundManager] Proposer {} for block {} is not a valid proposer for this round or created duplicate proposal",
             author,
             proposal,
@@ -1066,6 +1085,12 @@ impl RoundManager {
         ))?;
         if !block_arc.block().is_nil_block() {
             observe_block(block_arc.block().timestamp_usecs(), BlockStage::VOTED);
+            self.block_store.trace_block(
+                block_arc.id(),
+                block_arc.epoch(),
+                block_arc.round(),
+                BlockStage::VOTED,
+            );
         }
 
         self.storage
@@ -1248,6 +1273,12 @@ impl RoundManager {
                         qc.certified_block().timestamp_usecs(),
                         BlockStage::QC_AGGREGATED,
                     );
+                    self.block_store.trace_block(
+                        qc.certified_block().id(),
+                        qc.certified_block().epoch(),
+                        qc.certified_block().round(),
+                        BlockStage::QC_AGGREGATED,
+                    );
                 }
                 QC_AGGREGATED_FROM_VOTES.inc();
                 self.new_qc_aggregated(qc.clone(), vote.author())
@@ -1292,10 +1323,42 @@ impl RoundManager {
             VoteReceptionResult::VoteAddedQCDelayed(_)
             | VoteReceptionResult::EchoTimeout(_)
             | VoteReceptionResult::DuplicateVote => Ok(()),
+            VoteReceptionResult::EquivocateVote => {
+                // Persist the conflicting votes as evidence
+                if let Some(previous_vote) =
+                    self.round_state.get_pending_vote_by_author(&vote.author())
+                {
+                    self.save_equivocation_evidence(EquivocationEvidence::new_vote_evidence(
+                        previous_vote,
+                        vote.clone(),
+                    ));
+                }
+                Err(anyhow::anyhow!("{:?}", VoteReceptionResult::EquivocateVote))
+            },
             e => Err(anyhow::anyhow!("{:?}", e)),
         }
     }
 
+    /// Persists the given equivocation evidence (if it hasn't already been persisted)
+    fn save_equivocation_evidence(&self, evidence: EquivocationEvidence) {
+        let evidence_key = evidence.key();
+        match self.storage.save_equivocation_evidence(&evidence) {
+            Ok(true) => {
+                counters::EQUIVOCATION_EVIDENCE_SAVED_COUNT
+                    .with_label_values(&[evidence_key.kind.get_label()])
+                    .inc();
+                warn!("Persisted equivocation evidence {}", evidence_key);
+            },
+            Ok(false) => {}, // The evidence has already been persisted
+            Err(error) => {
+                error!(
+                    "Failed to persist equivocation evidence {}: {:?}",
+                    evidence_key, error
+                );
+            },
+        }
+    }
+
     async fn process_order_vote_reception_result(
         &mut self,
         result: OrderVoteReceptionResult,
@@ -7,6 +7,7 @@
 use crate::{
     block_storage::{pending_blocks::PendingBlocks, BlockStore},
     liveness::{
+        block_building_policy::DefaultBlockBuildingPolicy,
         proposal_generator::{
             ChainHealthBackoffConfig, PipelineBackpressureConfig, ProposalGenerator,
         },
@@ -95,6 +96,7 @@ fn build_empty_store(
         Arc::from(DirectMempoolPayloadManager::new()),
         false,
         Arc::new(Mutex::new(PendingBlocks::new())),
+        None,
     ))
 }
 
@@ -194,6 +196,8 @@ fn create_node_for_fuzzing() -> RoundManager {
         false,
         ValidatorTxnConfig::default_disabled(),
         true,
+        Arc::new(DefaultBlockBuildingPolicy),
+        None,
     );
 
     //
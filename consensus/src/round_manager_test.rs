@@ -4,7 +4,9 @@
 
 use crate::{
     block_storage::{pending_blocks::PendingBlocks, BlockReader, BlockStore},
+    equivocation_evidence::EquivocationEvidence,
     liveness::{
+        block_building_policy::DefaultBlockBuildingPolicy,
         proposal_generator::{
             ChainHealthBackoffConfig, PipelineBackpressureConfig, ProposalGenerator,
         },
@@ -295,6 +297,7 @@ impl NodeSetup {
             Arc::from(DirectMempoolPayloadManager::new()),
             false,
             Arc::new(Mutex::new(PendingBlocks::new())),
+            None,
         ));
 
         let proposer_election = Self::create_proposer_election(proposers.clone());
@@ -314,6 +317,8 @@ impl NodeSetup {
             false,
             onchain_consensus_config.effective_validator_txn_config(),
             true,
+            Arc::new(DefaultBlockBuildingPolicy),
+            None,
         );
 
         let round_state = Self::create_round_state(time_service);
@@ -753,6 +758,74 @@ fn vote_on_successful_proposal() {
     });
 }
 
+#[test]
+/// If the proposer equivocates, the conflicting proposals should be persisted as evidence
+fn equivocation_evidence_on_duplicate_proposal() {
+    let runtime = consensus_runtime();
+    let mut playground = NetworkPlayground::new(runtime.handle().clone());
+    let mut nodes = NodeSetup::create_nodes(
+        &mut playground,
+        runtime.handle().clone(),
+        1,
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+    let node = &mut nodes[0];
+
+    let genesis_qc = certificate_for_genesis();
+    timed_block_on(&runtime, async {
+        // Start round 1 and clear the message queue
+        node.next_proposal().await;
+
+        // Process a valid proposal
+        let proposal = Block::new_proposal(
+            Payload::empty(false, true),
+            1,
+            1,
+            genesis_qc.clone(),
+            &node.signer,
+            Vec::new(),
+        )
+        .unwrap();
+        node.round_manager
+            .process_proposal(proposal.clone())
+            .await
+            .unwrap();
+
+        // Process a conflicting proposal (twice) and verify it is rejected
+        let duplicate_proposal = Block::new_proposal(
+            Payload::empty(false, true),
+            1,
+            2,
+            genesis_qc.clone(),
+            &node.signer,
+            Vec::new(),
+        )
+        .unwrap();
+        for _ in 0..2 {
+            assert!(node
+                .round_manager
+                .process_proposal(duplicate_proposal.clone())
+                .await
+                .is_err());
+        }
+
+        // Verify the evidence was persisted (only once)
+        let equivocation_evidence = node.storage.shared_storage.equivocation_evidence.lock();
+        assert_eq!(equivocation_evidence.len(), 1);
+        let evidence = equivocation_evidence.values().next().unwrap();
+        assert_eq!(
+            evidence,
+            &EquivocationEvidence::new_proposal_evidence(proposal, duplicate_proposal)
+        );
+        assert_eq!(evidence.key().author, node.signer.author());
+        assert_eq!(evidence.key().round, 1);
+    });
+}
+
 #[test]
 /// In back pressure mode, verify that the proposals are processed after we get out of back pressure.
 fn delay_proposal_processing_in_sync_only() {
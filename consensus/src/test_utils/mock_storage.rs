@@ -4,6 +4,7 @@
 
 use crate::{
     epoch_manager::LivenessStorageData,
+    equivocation_evidence::{EquivocationEvidence, EquivocationEvidenceKey},
     persistent_liveness_storage::{
         LedgerRecoveryData, PersistentLivenessStorage, RecoveryData, RootMetadata,
     },
@@ -33,6 +34,9 @@ pub struct MockSharedStorage {
     // Liveness state
     pub highest_2chain_timeout_certificate: Mutex<Option<TwoChainTimeoutCertificate>>,
     pub validator_set: ValidatorSet,
+
+    // Equivocation evidence
+    pub equivocation_evidence: Mutex<HashMap<EquivocationEvidenceKey, EquivocationEvidence>>,
 }
 
 impl MockSharedStorage {
@@ -44,6 +48,7 @@ impl MockSharedStorage {
             last_vote: Mutex::new(None),
             highest_2chain_timeout_certificate: Mutex::new(None),
             validator_set,
+            equivocation_evidence: Mutex::new(HashMap::new()),
         }
     }
 }
@@ -221,6 +226,15 @@ impl PersistentLivenessStorage for MockStorage {
         Ok(())
     }
 
+    fn save_equivocation_evidence(&self, evidence: &EquivocationEvidence) -> Result<bool> {
+        let mut equivocation_evidence = self.shared_storage.equivocation_evidence.lock();
+        if equivocation_evidence.contains_key(&evidence.key()) {
+            return Ok(false);
+        }
+        equivocation_evidence.insert(evidence.key(), evidence.clone());
+        Ok(true)
+    }
+
     fn retrieve_epoch_change_proof(&self, version: u64) -> Result<EpochChangeProof> {
         let lis = self
             .shared_storage
@@ -301,6 +315,10 @@ impl PersistentLivenessStorage for EmptyStorage {
         Ok(())
     }
 
+    fn save_equivocation_evidence(&self, _: &EquivocationEvidence) -> Result<bool> {
+        Ok(true)
+    }
+
     fn retrieve_epoch_change_proof(&self, _version: u64) -> Result<EpochChangeProof> {
         Ok(EpochChangeProof::new(vec![], false))
     }
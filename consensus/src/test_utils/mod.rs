@@ -96,6 +96,7 @@ pub fn build_empty_tree() -> Arc<BlockStore> {
         Arc::from(DirectMempoolPayloadManager::new()),
         false,
         Arc::new(Mutex::new(PendingBlocks::new())),
+        None,
     ))
 }
 
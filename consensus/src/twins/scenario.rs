@@ -0,0 +1,486 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A declarative format for twins scenarios, and a runner that executes them.
+//!
+//! A scenario describes the validators (and their twins), the leader schedule,
+//! the per-round network partitions and the expected safety and liveness
+//! properties. Scenarios are written in YAML, e.g.:
+//!
+//! ```yaml
+//! name: twins_proposer
+//! num_validators: 4
+//! num_twins: 2
+//! leaders:
+//!   rounds:
+//!     - first_round: 1
+//!       last_round: 9
+//!       leader: n0
+//! partitions:
+//!   - first_round: 1
+//!     last_round: 9
+//!     partitions: [[n0, n1, n2], [n3, t0, t1]]
+//! expectations:
+//!   expect_safety: false
+//! ```
+//!
+//! Nodes are referenced by `n<i>` (the i-th validator) and `t<i>` (the twin
+//! of the i-th validator). Twins share the identity (and leadership) of their
+//! validator, so leaders must always be validators.
+
+use crate::{
+    network_tests::{NetworkPlayground, TwinId},
+    test_utils::{consensus_runtime, timed_block_on},
+    twins::twins_node::SMRNode,
+};
+use anyhow::{bail, ensure, format_err, Result};
+use aptos_consensus_types::common::Round;
+use aptos_crypto::HashValue;
+use aptos_types::{
+    ledger_info::LedgerInfoWithSignatures,
+    on_chain_config::ProposerElectionType::{FixedProposer, RotatingProposer, RoundProposer},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{Display, Formatter},
+    path::Path,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+// The interval at which the runner checks for new commits
+const COMMIT_POLL_INTERVAL_MS: u64 = 50;
+
+/// A reference to a validator (`n<i>`) or the twin of a validator (`t<i>`)
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum NodeRef {
+    Validator(usize),
+    Twin(usize),
+}
+
+impl NodeRef {
+    /// Returns the index of the node (as started by the `SMRNode` harness,
+    /// i.e., all validators followed by all twins).
+    fn node_index(&self, num_validators: usize) -> usize {
+        match self {
+            NodeRef::Validator(index) => *index,
+            NodeRef::Twin(index) => num_validators + index,
+        }
+    }
+}
+
+impl FromStr for NodeRef {
+    type Err = anyhow::Error;
+
+    fn from_str(node: &str) -> Result<Self> {
+        let parse_index = |index: &str| {
+            index
+                .parse::<usize>()
+                .map_err(|error| format_err!("Invalid node reference {}: {}", node, error))
+        };
+        if let Some(index) = node.strip_prefix('n') {
+            Ok(NodeRef::Validator(parse_index(index)?))
+        } else if let Some(index) = node.strip_prefix('t') {
+            Ok(NodeRef::Twin(parse_index(index)?))
+        } else {
+            bail!(
+                "Invalid node reference {}! Expected n<index> or t<index>.",
+                node
+            )
+        }
+    }
+}
+
+impl TryFrom<String> for NodeRef {
+    type Error = anyhow::Error;
+
+    fn try_from(node: String) -> Result<Self> {
+        NodeRef::from_str(&node)
+    }
+}
+
+impl From<NodeRef> for String {
+    fn from(node: NodeRef) -> Self {
+        node.to_string()
+    }
+}
+
+impl Display for NodeRef {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            NodeRef::Validator(index) => write!(f, "n{}", index),
+            NodeRef::Twin(index) => write!(f, "t{}", index),
+        }
+    }
+}
+
+/// The leader of all rounds in the given (inclusive) range
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct LeaderAssignment {
+    pub first_round: Round,
+    pub last_round: Round,
+    pub leader: NodeRef,
+}
+
+/// The leader schedule of the scenario
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderSchedule {
+    /// Rotate through the validators (changing leaders every `contiguous_rounds`)
+    Rotating(u32),
+    /// A single, fixed leader (changing every `contiguous_rounds`)
+    Fixed(u32),
+    /// Explicit leaders for each round. Rounds without an explicit
+    /// leader default to the first validator.
+    Rounds(Vec<LeaderAssignment>),
+}
+
+impl Default for LeaderSchedule {
+    fn default() -> Self {
+        LeaderSchedule::Rotating(2)
+    }
+}
+
+/// The network partitions for all rounds in the given (inclusive) range.
+/// Messages between nodes in different partitions are dropped.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RoundPartitions {
+    pub first_round: Round,
+    pub last_round: Round,
+    pub partitions: Vec<Vec<NodeRef>>,
+}
+
+/// The minimum number of commits a node is expected to observe
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct CommitExpectation {
+    pub node: NodeRef,
+    pub min_commits: usize,
+}
+
+/// The expected safety and liveness properties of the scenario
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Expectations {
+    /// Whether the nodes are expected to commit the same blocks (i.e., no
+    /// conflicting commits). Scenarios that exceed the fault threshold can
+    /// set this to false to verify that the violation is observable.
+    pub expect_safety: bool,
+    /// The nodes that are expected to make progress
+    pub expect_commits: Vec<CommitExpectation>,
+    /// The nodes that are expected to never commit (e.g., minority partitions)
+    pub expect_no_commits: Vec<NodeRef>,
+}
+
+impl Default for Expectations {
+    fn default() -> Self {
+        Self {
+            expect_safety: true,
+            expect_commits: vec![],
+            expect_no_commits: vec![],
+        }
+    }
+}
+
+/// A declarative twins scenario
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TwinsScenario {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub num_validators: usize,
+    #[serde(default)]
+    pub num_twins: usize,
+    #[serde(default)]
+    pub leaders: LeaderSchedule,
+    #[serde(default)]
+    pub partitions: Vec<RoundPartitions>,
+    #[serde(default)]
+    pub expectations: Expectations,
+    /// The maximum time to run the scenario (the scenario stops early
+    /// once all commit expectations are met, unless nodes are expected
+    /// to never commit).
+    #[serde(default = "default_run_duration_ms")]
+    pub run_duration_ms: u64,
+}
+
+fn default_run_duration_ms() -> u64 {
+    10_000
+}
+
+impl TwinsScenario {
+    /// Parses a scenario from the given YAML string
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        let scenario: TwinsScenario = serde_yaml::from_str(yaml)?;
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    /// Reads a scenario from the given YAML file
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let yaml = std::fs::read_to_string(path)?;
+        Self::from_yaml(&yaml)
+            .map_err(|error| format_err!("Failed to load twins scenario {:?}: {}", path, error))
+    }
+
+    /// Verifies that the scenario is well-formed
+    pub fn validate(&self) -> Result<()> {
+        ensure!(self.num_validators > 0, "The scenario has no validators!");
+        ensure!(
+            self.num_twins <= self.num_validators,
+            "The number of twins ({}) exceeds the number of validators ({})!",
+            self.num_twins,
+            self.num_validators
+        );
+
+        if let LeaderSchedule::Rounds(assignments) = &self.leaders {
+            for assignment in assignments {
+                self.validate_round_range(assignment.first_round, assignment.last_round)?;
+                match assignment.leader {
+                    NodeRef::Validator(_) => self.validate_node(&assignment.leader)?,
+                    NodeRef::Twin(_) => bail!(
+                        "Leaders must be validators (twins share their leadership), found: {}",
+                        assignment.leader
+                    ),
+                }
+            }
+        }
+
+        for round_partitions in &self.partitions {
+            self.validate_round_range(round_partitions.first_round, round_partitions.last_round)?;
+            let mut seen_nodes = vec![];
+            for node in round_partitions.partitions.iter().flatten() {
+                self.validate_node(node)?;
+                ensure!(
+                    !seen_nodes.contains(node),
+                    "Node {} appears in multiple partitions for rounds [{}, {}]!",
+                    node,
+                    round_partitions.first_round,
+                    round_partitions.last_round
+                );
+                seen_nodes.push(*node);
+            }
+        }
+
+        for commit_expectation in &self.expectations.expect_commits {
+            self.validate_node(&commit_expectation.node)?;
+        }
+        for node in &self.expectations.expect_no_commits {
+            self.validate_node(node)?;
+        }
+
+        Ok(())
+    }
+
+    fn validate_node(&self, node: &NodeRef) -> Result<()> {
+        let (index, num_nodes) = match node {
+            NodeRef::Validator(index) => (*index, self.num_validators),
+            NodeRef::Twin(index) => (*index, self.num_twins),
+        };
+        ensure!(
+            index < num_nodes,
+            "Node {} does not exist in the scenario!",
+            node
+        );
+        Ok(())
+    }
+
+    fn validate_round_range(&self, first_round: Round, last_round: Round) -> Result<()> {
+        ensure!(
+            first_round > 0 && first_round <= last_round,
+            "Invalid round range: [{}, {}]",
+            first_round,
+            last_round
+        );
+        Ok(())
+    }
+
+    fn all_nodes(&self) -> Vec<NodeRef> {
+        (0..self.num_validators)
+            .map(NodeRef::Validator)
+            .chain((0..self.num_twins).map(NodeRef::Twin))
+            .collect()
+    }
+}
+
+/// The result of running a scenario
+#[derive(Clone, Debug, Default)]
+pub struct ScenarioReport {
+    pub name: String,
+    /// The number of commits observed by each node
+    pub num_commits: BTreeMap<NodeRef, usize>,
+    /// The violated expectations (if any)
+    pub violations: Vec<String>,
+}
+
+impl ScenarioReport {
+    pub fn is_success(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+impl Display for ScenarioReport {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "Scenario {}: commits {:?}", self.name, self.num_commits)?;
+        if self.violations.is_empty() {
+            write!(f, ", no violations")
+        } else {
+            write!(f, ", violations: {:?}", self.violations)
+        }
+    }
+}
+
+/// Runs the given scenario and reports any violated expectations
+pub fn run_scenario(scenario: &TwinsScenario) -> ScenarioReport {
+    let runtime = consensus_runtime();
+    let mut playground = NetworkPlayground::new(runtime.handle().clone());
+
+    // Start the validators and twins with the leader schedule
+    let (proposer_type, round_proposers) = match &scenario.leaders {
+        LeaderSchedule::Rotating(contiguous_rounds) => (RotatingProposer(*contiguous_rounds), None),
+        LeaderSchedule::Fixed(contiguous_rounds) => (FixedProposer(*contiguous_rounds), None),
+        LeaderSchedule::Rounds(assignments) => {
+            let mut round_proposers: HashMap<Round, usize> = HashMap::new();
+            for assignment in assignments {
+                for round in assignment.first_round..=assignment.last_round {
+                    round_proposers
+                        .insert(round, assignment.leader.node_index(scenario.num_validators));
+                }
+            }
+            (RoundProposer(HashMap::new()), Some(round_proposers))
+        },
+    };
+    let mut nodes = SMRNode::start_num_nodes_with_twins(
+        scenario.num_validators,
+        scenario.num_twins,
+        &mut playground,
+        proposer_type,
+        round_proposers,
+    );
+
+    // Create the network partitions
+    let twin_id = |node: &NodeRef| -> TwinId { nodes[node.node_index(scenario.num_validators)].id };
+    let mut round_partitions: HashMap<Round, Vec<Vec<TwinId>>> = HashMap::new();
+    for partitions in &scenario.partitions {
+        let twin_partitions: Vec<Vec<TwinId>> = partitions
+            .partitions
+            .iter()
+            .map(|partition| partition.iter().map(twin_id).collect())
+            .collect();
+        for round in partitions.first_round..=partitions.last_round {
+            round_partitions.insert(round, twin_partitions.clone());
+        }
+    }
+    assert!(playground.split_network_round(&round_partitions));
+    runtime.spawn(playground.start());
+
+    // Collect the commits of all nodes
+    let all_nodes = scenario.all_nodes();
+    let mut commits: BTreeMap<NodeRef, Vec<LedgerInfoWithSignatures>> = BTreeMap::new();
+    timed_block_on(&runtime, async {
+        let start_time = Instant::now();
+        let run_duration = Duration::from_millis(scenario.run_duration_ms);
+        while start_time.elapsed() < run_duration {
+            for node in &all_nodes {
+                let smr_node = &mut nodes[node.node_index(scenario.num_validators)];
+                while let Ok(Some(commit)) = smr_node.commit_cb_receiver.try_next() {
+                    commits.entry(*node).or_default().push(commit);
+                }
+            }
+            if scenario.expectations.expect_no_commits.is_empty()
+                && !scenario.expectations.expect_commits.is_empty()
+                && get_commit_violations(scenario, &commits).is_empty()
+            {
+                break; // All commit expectations have been met
+            }
+            tokio::time::sleep(Duration::from_millis(COMMIT_POLL_INTERVAL_MS)).await;
+        }
+    });
+
+    // Verify the expectations
+    let mut violations = get_commit_violations(scenario, &commits);
+    violations.extend(get_safety_violations(scenario, &commits));
+    ScenarioReport {
+        name: scenario.name.clone(),
+        num_commits: all_nodes
+            .iter()
+            .map(|node| (*node, commits.get(node).map_or(0, |commits| commits.len())))
+            .collect(),
+        violations,
+    }
+}
+
+/// Returns the violated liveness expectations
+fn get_commit_violations(
+    scenario: &TwinsScenario,
+    commits: &BTreeMap<NodeRef, Vec<LedgerInfoWithSignatures>>,
+) -> Vec<String> {
+    let num_commits = |node: &NodeRef| commits.get(node).map_or(0, |commits| commits.len());
+
+    let mut violations = vec![];
+    for commit_expectation in &scenario.expectations.expect_commits {
+        let num_node_commits = num_commits(&commit_expectation.node);
+        if num_node_commits < commit_expectation.min_commits {
+            violations.push(format!(
+                "Node {} observed {} commits, but expected at least {}",
+                commit_expectation.node, num_node_commits, commit_expectation.min_commits
+            ));
+        }
+    }
+    for node in &scenario.expectations.expect_no_commits {
+        let num_node_commits = num_commits(node);
+        if num_node_commits > 0 {
+            violations.push(format!(
+                "Node {} observed {} commits, but expected none",
+                node, num_node_commits
+            ));
+        }
+    }
+    violations
+}
+
+/// Returns the violated safety expectations, i.e., conflicting commits
+/// across nodes (or the lack of them, if a violation was expected).
+fn get_safety_violations(
+    scenario: &TwinsScenario,
+    commits: &BTreeMap<NodeRef, Vec<LedgerInfoWithSignatures>>,
+) -> Vec<String> {
+    let mut committed_blocks: HashMap<(u64, Round), (NodeRef, HashValue)> = HashMap::new();
+    let mut conflicts = vec![];
+    for (node, node_commits) in commits {
+        for commit in node_commits {
+            let commit_info = commit.ledger_info().commit_info();
+            let key = (commit_info.epoch(), commit_info.round());
+            match committed_blocks.get(&key) {
+                Some((other_node, block_id)) if *block_id != commit_info.id() => {
+                    conflicts.push(format!(
+                        "Conflicting commits at epoch {}, round {}: node {} committed {}, but node {} committed {}",
+                        key.0,
+                        key.1,
+                        other_node,
+                        block_id,
+                        node,
+                        commit_info.id()
+                    ));
+                },
+                Some(_) => {},
+                None => {
+                    committed_blocks.insert(key, (*node, commit_info.id()));
+                },
+            }
+        }
+    }
+
+    if scenario.expectations.expect_safety {
+        conflicts
+    } else if conflicts.is_empty() {
+        vec!["Expected conflicting commits, but all nodes committed the same blocks".into()]
+    } else {
+        vec![]
+    }
+}
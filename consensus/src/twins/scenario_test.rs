@@ -0,0 +1,132 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::twins::scenario::{run_scenario, LeaderSchedule, NodeRef, TwinsScenario};
+use std::path::PathBuf;
+
+#[test]
+/// This test runs all scenarios in the twins scenario library
+/// (i.e., `src/twins/scenarios`) and reports any violations.
+///
+/// Run the test:
+/// cargo xtest -p consensus twins_scenario_library_test -- --nocapture
+fn twins_scenario_library_test() {
+    let scenario_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/twins/scenarios");
+    let mut scenario_paths: Vec<_> = std::fs::read_dir(scenario_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            path.extension()
+                .map_or(false, |extension| extension == "yaml")
+        })
+        .collect();
+    scenario_paths.sort();
+    assert!(!scenario_paths.is_empty());
+
+    let mut failed_reports = vec![];
+    for scenario_path in scenario_paths {
+        let scenario = TwinsScenario::from_file(&scenario_path).unwrap();
+        let report = run_scenario(&scenario);
+        println!("{}", report);
+        if !report.is_success() {
+            failed_reports.push(report);
+        }
+    }
+
+    assert!(
+        failed_reports.is_empty(),
+        "Twins scenarios failed: {:?}",
+        failed_reports
+    );
+}
+
+#[test]
+fn twins_scenario_parsing_test() {
+    let scenario = TwinsScenario::from_yaml(
+        r#"
+name: test
+num_validators: 4
+num_twins: 1
+leaders:
+  rounds:
+    - first_round: 1
+      last_round: 5
+      leader: n0
+partitions:
+  - first_round: 1
+    last_round: 5
+    partitions: [[n0, n1], [n2, n3, t0]]
+expectations:
+  expect_safety: false
+"#,
+    )
+    .unwrap();
+    assert_eq!(scenario.num_twins, 1);
+    assert!(!scenario.expectations.expect_safety);
+    assert_eq!(scenario.partitions[0].partitions[1], vec![
+        NodeRef::Validator(2),
+        NodeRef::Validator(3),
+        NodeRef::Twin(0)
+    ]);
+    match scenario.leaders {
+        LeaderSchedule::Rounds(assignments) => {
+            assert_eq!(assignments[0].leader, NodeRef::Validator(0))
+        },
+        _ => panic!("Unexpected leader schedule!"),
+    }
+}
+
+#[test]
+fn twins_scenario_validation_test() {
+    // Twins cannot be leaders
+    assert!(TwinsScenario::from_yaml(
+        r#"
+name: test
+num_validators: 4
+num_twins: 1
+leaders:
+  rounds:
+    - first_round: 1
+      last_round: 5
+      leader: t0
+"#,
+    )
+    .is_err());
+
+    // Nodes must exist
+    assert!(TwinsScenario::from_yaml(
+        r#"
+name: test
+num_validators: 4
+partitions:
+  - first_round: 1
+    last_round: 5
+    partitions: [[n0, n1], [n2, t0]]
+"#,
+    )
+    .is_err());
+
+    // Nodes cannot appear in multiple partitions
+    assert!(TwinsScenario::from_yaml(
+        r#"
+name: test
+num_validators: 4
+partitions:
+  - first_round: 1
+    last_round: 5
+    partitions: [[n0, n1], [n1, n2]]
+"#,
+    )
+    .is_err());
+
+    // Invalid node references are rejected
+    assert!(TwinsScenario::from_yaml(
+        r#"
+name: test
+num_validators: 4
+expectations:
+  expect_no_commits: [x0]
+"#,
+    )
+    .is_err());
+}
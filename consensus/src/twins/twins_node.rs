@@ -164,6 +164,10 @@ impl SMRNode {
             vtxn_pool,
             Arc::new(InMemRandDb::new()),
             None,
+            None,
+            None,
+            None,
+            None,
         );
         let (network_task, network_receiver) =
             NetworkTask::new(network_service_events, self_receiver);
@@ -0,0 +1,589 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Replays leader reputation over historical chain data (i.e., committed
+//! `NewBlockEvent`s) under alternative configs. This allows changes to the
+//! on-chain `LeaderReputationType` (and `ProposerAndVoterConfig`) to be
+//! evaluated before they are proposed via governance.
+//!
+//! The simulation is closed-loop: the elected proposers are fed back into the
+//! reputation window, just as they would be on chain. Because we can't know how
+//! a validator would have behaved in rounds it didn't lead, we infer its health
+//! at each round from the historical data: a validator is healthy if its closest
+//! historical proposal (preferring the most recent one) succeeded. If it never
+//! proposed in the epoch, it is healthy if it voted for the covering block.
+
+use crate::liveness::{
+    leader_reputation::{
+        LeaderReputation, MetadataBackend, NewBlockEventAggregation, ProposerAndVoterHeuristic,
+        ReputationHeuristic,
+    },
+    proposer_election::ProposerElection,
+};
+use anyhow::{bail, ensure, format_err, Result};
+use aptos_config::config::ConsensusConfig;
+use aptos_consensus_types::common::{Author, Round};
+use aptos_crypto::HashValue;
+use aptos_infallible::Mutex;
+use aptos_storage_interface::DbReader;
+use aptos_types::{
+    account_address::AccountAddress,
+    account_config::NewBlockEvent,
+    on_chain_config::{LeaderReputationType, OnChainConsensusConfig, ProposerElectionType},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+#[cfg(test)]
+#[path = "leader_reputation_simulator_test.rs"]
+mod leader_reputation_simulator_test;
+
+/// The validator set and committed blocks of a single epoch
+#[derive(Clone, Debug)]
+pub struct EpochHistory {
+    pub epoch: u64,
+    /// The validators of the epoch (ordered by validator index), and their voting powers
+    pub validators: Vec<(Author, u64)>,
+    /// The committed block events of the epoch (ordered by round)
+    pub events: Vec<NewBlockEvent>,
+}
+
+impl EpochHistory {
+    fn proposers(&self) -> Vec<Author> {
+        self.validators.iter().map(|(author, _)| *author).collect()
+    }
+
+    /// Returns the historical outcomes of all rounds in the epoch
+    fn round_outcomes(&self) -> Result<Vec<RoundOutcome>> {
+        let proposers = self.proposers();
+        let mut outcomes = vec![];
+        for event in &self.events {
+            let failed_proposers = NewBlockEventAggregation::indices_to_validators(
+                &proposers,
+                &event.failed_proposer_indices,
+            )
+            .map_err(|error| format_err!("Invalid block event: {}", error))?;
+            let first_failed_round = event.round().saturating_sub(failed_proposers.len() as u64);
+            for (round, failed_proposer) in (first_failed_round..).zip(failed_proposers) {
+                outcomes.push(RoundOutcome::new(
+                    self.epoch,
+                    round,
+                    *failed_proposer,
+                    false,
+                ));
+            }
+            outcomes.push(RoundOutcome::new(
+                self.epoch,
+                event.round(),
+                event.proposer(),
+                true,
+            ));
+        }
+        Ok(outcomes)
+    }
+}
+
+/// The parameters of a single simulation
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SimulationConfig {
+    /// The number of most recent rounds excluded from the reputation window
+    pub exclude_round: u64,
+    /// The leader reputation config to simulate
+    pub leader_reputation_type: LeaderReputationType,
+    /// The maximum number of failed proposers recorded in each block event
+    pub max_failed_authors_to_store: usize,
+    /// The window size (in blocks) used to compute chain health
+    pub window_for_chain_health: usize,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        let onchain_config = OnChainConsensusConfig::default();
+        let leader_reputation_type = match onchain_config.proposer_election_type() {
+            ProposerElectionType::LeaderReputation(leader_reputation_type) => {
+                leader_reputation_type.clone()
+            },
+            _ => unreachable!("The default proposer election type must be leader reputation!"),
+        };
+        Self {
+            exclude_round: onchain_config.leader_reputation_exclude_round(),
+            leader_reputation_type,
+            max_failed_authors_to_store: onchain_config.max_failed_authors_to_store(),
+            window_for_chain_health: ConsensusConfig::default().window_for_chain_health,
+        }
+    }
+}
+
+/// The outcome of a single round (i.e., whether the proposer produced a block)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundOutcome {
+    pub epoch: u64,
+    pub round: Round,
+    pub proposer: Author,
+    pub success: bool,
+}
+
+impl RoundOutcome {
+    fn new(epoch: u64, round: Round, proposer: Author, success: bool) -> Self {
+        Self {
+            epoch,
+            round,
+            proposer,
+            success,
+        }
+    }
+}
+
+/// The number of successful and failed proposals of a validator
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ValidatorProposalStats {
+    pub successful_proposals: u64,
+    pub failed_proposals: u64,
+}
+
+/// Statistics about how quickly unhealthy validators stop being elected.
+/// An unhealthy period starts after a validator's last successful (historical)
+/// proposal, and the rounds to exclusion measure the time from the start of
+/// the period until the validator's last failed round as proposer.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct ExclusionStats {
+    pub num_unhealthy_periods: u64,
+    pub average_rounds_to_exclusion: f64,
+    pub max_rounds_to_exclusion: u64,
+    pub average_failed_rounds_per_period: f64,
+}
+
+/// Aggregated statistics for a sequence of round outcomes
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct ProposerElectionStats {
+    pub num_rounds: u64,
+    pub num_failed_rounds: u64,
+    pub proposer_distribution: BTreeMap<Author, ValidatorProposalStats>,
+    pub exclusion_stats: ExclusionStats,
+}
+
+/// The report of a simulation, compared against the historical data
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SimulationReport {
+    pub start_epoch: u64,
+    pub end_epoch: u64,
+    pub historical: ProposerElectionStats,
+    pub simulated: ProposerElectionStats,
+}
+
+/// Infers the health of validators from the historical data
+struct HealthModel {
+    // The historical proposal outcomes (round, success) of each validator (by epoch)
+    proposal_outcomes: HashMap<(u64, Author), Vec<(Round, bool)>>,
+    // The historical block events of each epoch
+    events: HashMap<u64, Vec<NewBlockEvent>>,
+    // The validators of each epoch
+    proposers: HashMap<u64, Vec<Author>>,
+}
+
+impl HealthModel {
+    fn new(histories: &[EpochHistory]) -> Result<Self> {
+        let mut proposal_outcomes: HashMap<_, Vec<_>> = HashMap::new();
+        for history in histories {
+            for outcome in history.round_outcomes()? {
+                proposal_outcomes
+                    .entry((outcome.epoch, outcome.proposer))
+                    .or_default()
+                    .push((outcome.round, outcome.success));
+            }
+        }
+        Ok(Self {
+            proposal_outcomes,
+            events: histories
+                .iter()
+                .map(|history| (history.epoch, history.events.clone()))
+                .collect(),
+            proposers: histories
+                .iter()
+                .map(|history| (history.epoch, history.proposers()))
+                .collect(),
+        })
+    }
+
+    /// Returns the historical block event that covers the given round,
+    /// i.e., the first block committed at or after the round.
+    fn get_covering_event(&self, epoch: u64, round: Round) -> Option<&NewBlockEvent> {
+        let events = self.events.get(&epoch)?;
+        let index = events.partition_point(|event| event.round() < round);
+        events.get(index).or_else(|| events.last())
+    }
+
+    /// Returns true iff the validator is considered healthy at the given round
+    fn is_healthy(&self, epoch: u64, round: Round, author: &Author) -> bool {
+        if let Some(outcomes) = self.proposal_outcomes.get(&(epoch, *author)) {
+            let index = outcomes.partition_point(|(outcome_round, _)| *outcome_round <= round);
+            let closest_outcome = if index > 0 {
+                outcomes.get(index - 1)
+            } else {
+                outcomes.first()
+            };
+            if let Some((_, success)) = closest_outcome {
+                return *success;
+            }
+        }
+
+        // The validator never proposed, so fall back to whether it voted
+        let (Some(event), Some(proposers)) = (
+            self.get_covering_event(epoch, round),
+            self.proposers.get(&epoch),
+        ) else {
+            return false;
+        };
+        NewBlockEventAggregation::bitvec_to_voters(
+            proposers,
+            &event.previous_block_votes_bitvec().clone().into(),
+        )
+        .map(|voters| voters.contains(&author))
+        .unwrap_or(false)
+    }
+
+    /// Returns the first round of the unhealthy period containing the given
+    /// (failed) round, i.e., the round after the last successful proposal.
+    fn get_unhealthy_period_start(
+        &self,
+        epoch: u64,
+        round: Round,
+        author: &Author,
+        epoch_start_round: Round,
+    ) -> Round {
+        self.proposal_outcomes
+            .get(&(epoch, *author))
+            .and_then(|outcomes| {
+                outcomes
+                    .iter()
+                    .rev()
+                    .find(|(outcome_round, success)| *success && *outcome_round < round)
+                    .map(|(outcome_round, _)| outcome_round + 1)
+            })
+            .unwrap_or(epoch_start_round)
+    }
+}
+
+/// A metadata backend that serves the simulated block events
+struct SimulatedMetadataBackend {
+    window_size: usize,
+    // The simulated block events (ordered from oldest to newest)
+    events: Arc<Mutex<Vec<NewBlockEvent>>>,
+}
+
+impl MetadataBackend for SimulatedMetadataBackend {
+    fn get_block_metadata(
+        &self,
+        target_epoch: u64,
+        target_round: Round,
+    ) -> (Vec<NewBlockEvent>, HashValue) {
+        let events: Vec<_> = self
+            .events
+            .lock()
+            .iter()
+            .rev()
+            .filter(|event| (event.epoch(), event.round()) <= (target_epoch, target_round))
+            .take(self.window_size)
+            .cloned()
+            .collect();
+
+        // There is no accumulator root hash for simulated events, so we derive
+        // a (deterministic) seed from the most recent event in the window.
+        let root_hash = events.first().map_or(HashValue::zero(), |event| {
+            HashValue::sha3_256_of(&bcs::to_bytes(event).unwrap_or_default())
+        });
+        (events, root_hash)
+    }
+}
+
+/// Simulates leader reputation over the given epoch histories
+pub struct LeaderReputationSimulator {
+    config: SimulationConfig,
+    histories: Vec<EpochHistory>,
+}
+
+impl LeaderReputationSimulator {
+    pub fn new(config: SimulationConfig, mut histories: Vec<EpochHistory>) -> Result<Self> {
+        ensure!(!histories.is_empty(), "No epoch histories to simulate!");
+        histories.sort_by_key(|history| history.epoch);
+        for history in &histories {
+            ensure!(
+                !history.validators.is_empty(),
+                "Epoch {} has no validators!",
+                history.epoch
+            );
+            if let Some(event) = history
+                .events
+                .iter()
+                .find(|event| event.epoch() != history.epoch)
+            {
+                bail!(
+                    "Epoch {} contains a block event for epoch {}!",
+                    history.epoch,
+                    event.epoch()
+                );
+            }
+        }
+        Ok(Self { config, histories })
+    }
+
+    /// Runs the simulation and returns the report
+    pub fn simulate(&self) -> Result<SimulationReport> {
+        let health_model = HealthModel::new(&self.histories)?;
+
+        let mut historical_outcomes = vec![];
+        for history in &self.histories {
+            historical_outcomes.extend(history.round_outcomes()?);
+        }
+        let simulated_outcomes = self.simulate_round_outcomes(&health_model)?;
+
+        Ok(SimulationReport {
+            start_epoch: self.histories.first().map_or(0, |history| history.epoch),
+            end_epoch: self.histories.last().map_or(0, |history| history.epoch),
+            historical: self.compute_stats(&health_model, &historical_outcomes),
+            simulated: self.compute_stats(&health_model, &simulated_outcomes),
+        })
+    }
+
+    fn simulate_round_outcomes(&self, health_model: &HealthModel) -> Result<Vec<RoundOutcome>> {
+        let (LeaderReputationType::ProposerAndVoter(proposer_and_voter_config)
+        | LeaderReputationType::ProposerAndVoterV2(proposer_and_voter_config)) =
+            &self.config.leader_reputation_type;
+        let simulated_events = Arc::new(Mutex::new(vec![]));
+
+        let mut outcomes = vec![];
+        for history in &self.histories {
+            let (Some(first_event), Some(last_event)) =
+                (history.events.first(), history.events.last())
+            else {
+                continue; // There are no rounds to simulate
+            };
+
+            // Create the leader reputation instance (as done by the epoch manager)
+            let proposers = history.proposers();
+            let proposer_window_size = proposers.len()
+                * proposer_and_voter_config.proposer_window_num_validators_multiplier;
+            let voter_window_size =
+                proposers.len() * proposer_and_voter_config.voter_window_num_validators_multiplier;
+            let heuristic: Box<dyn ReputationHeuristic> = Box::new(ProposerAndVoterHeuristic::new(
+                Author::ZERO,
+                proposer_and_voter_config.active_weight,
+                proposer_and_voter_config.inactive_weight,
+                proposer_and_voter_config.failed_weight,
+                proposer_and_voter_config.failure_threshold_percent,
+                voter_window_size,
+                proposer_window_size,
+                self.config
+                    .leader_reputation_type
+                    .use_reputation_window_from_stale_end(),
+            ));
+            let backend = Arc::new(SimulatedMetadataBackend {
+                window_size: std::cmp::max(proposer_window_size, voter_window_size),
+                events: simulated_events.clone(),
+            });
+            let voting_powers = if proposer_and_voter_config.weight_by_voting_power {
+                history.validators.iter().map(|(_, power)| *power).collect()
+            } else {
+                vec![1; proposers.len()]
+            };
+            let epoch_to_proposers = self
+                .histories
+                .iter()
+                .filter(|other| {
+                    other.epoch <= history.epoch
+                        && other.epoch
+                            + proposer_and_voter_config.use_history_from_previous_epoch_max_count
+                                as u64
+                            >= history.epoch
+                })
+                .map(|other| (other.epoch, other.proposers()))
+                .collect();
+            let proposer_election = LeaderReputation::new(
+                history.epoch,
+                epoch_to_proposers,
+                voting_powers,
+                backend,
+                heuristic,
+                self.config.exclude_round,
+                self.config.leader_reputation_type.use_root_hash_for_seed(),
+                self.config.window_for_chain_health,
+            );
+
+            // Simulate each round of the epoch
+            let first_round = first_event
+                .round()
+                .saturating_sub(first_event.failed_proposer_indices().len() as u64);
+            let mut failed_proposer_indices = vec![];
+            for round in first_round..=last_event.round() {
+                let proposer = proposer_election.get_valid_proposer(round);
+                let success = health_model.is_healthy(history.epoch, round, &proposer);
+                if success {
+                    let covering_event = health_model
+                        .get_covering_event(history.epoch, round)
+                        .ok_or_else(|| format_err!("No block event for round {}!", round))?;
+                    simulated_events.lock().push(NewBlockEvent::new(
+                        AccountAddress::ZERO,
+                        history.epoch,
+                        round,
+                        covering_event.height(),
+                        covering_event.previous_block_votes_bitvec().clone(),
+                        proposer,
+                        std::mem::take(&mut failed_proposer_indices),
+                        covering_event.proposed_time(),
+                    ));
+                } else if failed_proposer_indices.len() < self.config.max_failed_authors_to_store {
+                    let index = proposers
+                        .iter()
+                        .position(|author| *author == proposer)
+                        .expect("The elected proposer must be a validator!");
+                    failed_proposer_indices.push(index as u64);
+                }
+                outcomes.push(RoundOutcome::new(history.epoch, round, proposer, success));
+            }
+        }
+
+        Ok(outcomes)
+    }
+
+    fn compute_stats(
+        &self,
+        health_model: &HealthModel,
+        outcomes: &[RoundOutcome],
+    ) -> ProposerElectionStats {
+        let mut stats = ProposerElectionStats::default();
+        for history in &self.histories {
+            for (author, _) in &history.validators {
+                stats.proposer_distribution.entry(*author).or_default();
+            }
+        }
+
+        // Track the failed rounds in each unhealthy period (epoch, author, period start)
+        let mut epoch_start_rounds: HashMap<u64, Round> = HashMap::new();
+        let mut unhealthy_periods: BTreeMap<(u64, Author, Round), (u64, Round)> = BTreeMap::new();
+        for outcome in outcomes {
+            let epoch_start_round = *epoch_start_rounds
+                .entry(outcome.epoch)
+                .or_insert(outcome.round);
+
+            stats.num_rounds += 1;
+            let proposal_stats = stats
+                .proposer_distribution
+                .entry(outcome.proposer)
+                .or_default();
+            if outcome.success {
+                proposal_stats.successful_proposals += 1;
+            } else {
+                proposal_stats.failed_proposals += 1;
+                stats.num_failed_rounds += 1;
+
+                let period_start = health_model.get_unhealthy_period_start(
+                    outcome.epoch,
+                    outcome.round,
+                    &outcome.proposer,
+                    epoch_start_round,
+                );
+                let (num_failed_rounds, last_failed_round) = unhealthy_periods
+                    .entry((outcome.epoch, outcome.proposer, period_start))
+                    .or_default();
+                *num_failed_rounds += 1;
+                *last_failed_round = std::cmp::max(*last_failed_round, outcome.round);
+            }
+        }
+
+        // Calculate the exclusion statistics
+        let num_unhealthy_periods = unhealthy_periods.len() as u64;
+        if num_unhealthy_periods > 0 {
+            let rounds_to_exclusion: Vec<u64> = unhealthy_periods
+                .iter()
+                .map(|((_, _, period_start), (_, last_failed_round))| {
+                    last_failed_round.saturating_sub(*period_start) + 1
+                })
+                .collect();
+            let total_failed_rounds: u64 = unhealthy_periods
+                .values()
+                .map(|(num_failed_rounds, _)| *num_failed_rounds)
+                .sum();
+            stats.exclusion_stats = ExclusionStats {
+                num_unhealthy_periods,
+                average_rounds_to_exclusion: rounds_to_exclusion.iter().sum::<u64>() as f64
+                    / num_unhealthy_periods as f64,
+                max_rounds_to_exclusion: rounds_to_exclusion.into_iter().max().unwrap_or(0),
+                average_failed_rounds_per_period: total_failed_rounds as f64
+                    / num_unhealthy_periods as f64,
+            };
+        }
+
+        stats
+    }
+}
+
+/// Reads the epoch histories (for the given range of epochs, inclusive) from the DB
+pub fn fetch_epoch_histories_from_db(
+    db: &dyn DbReader,
+    start_epoch: u64,
+    end_epoch: u64,
+) -> Result<Vec<EpochHistory>> {
+    ensure!(
+        start_epoch > 0 && start_epoch <= end_epoch,
+        "Invalid epoch range: [{}, {}]",
+        start_epoch,
+        end_epoch
+    );
+
+    let latest_ledger_info = db.get_latest_ledger_info()?;
+    let latest_epoch = latest_ledger_info.ledger_info().next_block_epoch();
+    let latest_version = latest_ledger_info.ledger_info().version();
+
+    let mut histories = vec![];
+    for epoch in start_epoch..=std::cmp::min(end_epoch, latest_epoch) {
+        // The ledger info that ends the previous epoch contains the validator set
+        let epoch_change_proof = db.get_epoch_ending_ledger_infos(epoch - 1, epoch)?;
+        let ledger_info = epoch_change_proof
+            .ledger_info_with_sigs
+            .first()
+            .ok_or_else(|| format_err!("No epoch ending ledger info for epoch {}!", epoch - 1))?;
+        let epoch_state = ledger_info
+            .ledger_info()
+            .next_epoch_state()
+            .ok_or_else(|| format_err!("No next epoch state for epoch {}!", epoch - 1))?;
+        let validators = epoch_state
+            .verifier
+            .get_ordered_account_addresses_iter()
+            .map(|author| {
+                let voting_power = epoch_state.verifier.get_voting_power(&author).unwrap_or(0);
+                (author, voting_power)
+            })
+            .collect();
+
+        // Read all block events in the epoch
+        let mut events = vec![];
+        let first_version = ledger_info.ledger_info().version() + 1;
+        if first_version <= latest_version {
+            let (_, _, first_event) = db.get_block_info_by_version(first_version)?;
+            let mut height = first_event.height();
+            loop {
+                let event = match db.get_block_info_by_height(height) {
+                    Ok((_, _, event)) => event,
+                    Err(_) => break, // We've reached the end of the chain
+                };
+                if event.epoch() != epoch {
+                    break;
+                }
+                events.push(event);
+                height += 1;
+            }
+        }
+
+        histories.push(EpochHistory {
+            epoch,
+            validators,
+            events,
+        });
+    }
+
+    Ok(histories)
+}
@@ -0,0 +1,113 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::util::leader_reputation_simulator::{
+    EpochHistory, LeaderReputationSimulator, SimulationConfig,
+};
+use aptos_bitvec::BitVec;
+use aptos_consensus_types::common::Author;
+use aptos_types::{account_address::AccountAddress, account_config::NewBlockEvent};
+
+// Useful test constants
+const NUM_ROUNDS: u64 = 400;
+const NUM_VALIDATORS: usize = 4;
+
+#[test]
+fn test_simulation_excludes_failing_validator() {
+    // Create an epoch history where the last validator always fails to propose
+    // (the historical leaders were elected in a round-robin fashion).
+    let validators: Vec<_> = (0..NUM_VALIDATORS)
+        .map(|index| (Author::new([index as u8; Author::LENGTH]), 1))
+        .collect();
+    let failing_index = NUM_VALIDATORS - 1;
+    let mut events = vec![];
+    let mut failed_proposer_indices = vec![];
+    for round in 1..=NUM_ROUNDS {
+        let leader_index = (round as usize) % NUM_VALIDATORS;
+        if leader_index == failing_index {
+            failed_proposer_indices.push(leader_index as u64);
+            continue;
+        }
+
+        // Only the healthy validators vote
+        let mut votes_bitvec = BitVec::with_num_bits(NUM_VALIDATORS as u16);
+        for index in 0..failing_index {
+            votes_bitvec.set(index as u16);
+        }
+        events.push(NewBlockEvent::new(
+            AccountAddress::ZERO,
+            1,
+            round,
+            events.len() as u64,
+            votes_bitvec.into(),
+            validators[leader_index].0,
+            std::mem::take(&mut failed_proposer_indices),
+            round * 1000,
+        ));
+    }
+    let history = EpochHistory {
+        epoch: 1,
+        validators: validators.clone(),
+        events,
+    };
+
+    // Run the simulation with the default config
+    let simulator =
+        LeaderReputationSimulator::new(SimulationConfig::default(), vec![history]).unwrap();
+    let report = simulator.simulate().unwrap();
+
+    // Verify the historical statistics
+    let failing_validator = validators[failing_index].0;
+    let num_historical_failures = NUM_ROUNDS / NUM_VALIDATORS as u64;
+    assert_eq!(report.historical.num_rounds, NUM_ROUNDS);
+    assert_eq!(report.historical.num_failed_rounds, num_historical_failures);
+    assert_eq!(
+        report.historical.proposer_distribution[&failing_validator].failed_proposals,
+        num_historical_failures
+    );
+    assert_eq!(report.historical.exclusion_stats.num_unhealthy_periods, 1);
+
+    // Verify the failing validator is quickly excluded in the simulation
+    assert_eq!(report.simulated.num_rounds, NUM_ROUNDS);
+    assert!(report.simulated.num_failed_rounds < num_historical_failures / 2);
+    assert_eq!(
+        report.simulated.proposer_distribution[&failing_validator].successful_proposals,
+        0
+    );
+    assert_eq!(report.simulated.exclusion_stats.num_unhealthy_periods, 1);
+    assert!(
+        report
+            .simulated
+            .exclusion_stats
+            .average_failed_rounds_per_period
+            < report
+                .historical
+                .exclusion_stats
+                .average_failed_rounds_per_period
+    );
+}
+
+#[test]
+fn test_simulation_invalid_histories() {
+    // Verify that empty histories are rejected
+    assert!(LeaderReputationSimulator::new(SimulationConfig::default(), vec![]).is_err());
+
+    // Verify that events from other epochs are rejected
+    let validators = vec![(Author::random(), 1)];
+    let event = NewBlockEvent::new(
+        AccountAddress::random(),
+        2,
+        1,
+        0,
+        vec![0],
+        validators[0].0,
+        vec![],
+        0,
+    );
+    let history = EpochHistory {
+        epoch: 1,
+        validators,
+        events: vec![event],
+    };
+    assert!(LeaderReputationSimulator::new(SimulationConfig::default(), vec![history]).is_err());
+}
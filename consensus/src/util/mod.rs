@@ -8,6 +8,7 @@ use aptos_types::{
 };
 
 pub mod db_tool;
+pub mod leader_reputation_simulator;
 #[cfg(any(test, feature = "fuzzing"))]
 pub mod mock_time_service;
 pub mod time_service;
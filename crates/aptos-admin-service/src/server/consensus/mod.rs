@@ -3,13 +3,16 @@
 
 use anyhow::{bail, Error};
 use aptos_consensus::{
+    equivocation_evidence::{EquivocationEvidence, EquivocationEvidenceExport},
     persistent_liveness_storage::PersistentLivenessStorage,
-    quorum_store::quorum_store_db::QuorumStoreStorage, util::db_tool::extract_txns_from_block,
+    quorum_store::{batch_lifecycle::BatchLifecycles, quorum_store_db::QuorumStoreStorage},
+    util::db_tool::extract_txns_from_block,
+    BlockBuildingSnapshots, BlockTraces, DagVisualizer,
 };
 use aptos_crypto::HashValue;
 use aptos_logger::info;
 use aptos_system_utils::utils::{reply_with, reply_with_status, spawn_blocking};
-use aptos_types::transaction::Transaction;
+use aptos_types::{transaction::Transaction, PeerId};
 use http::header::{HeaderValue, CONTENT_LENGTH};
 use hyper::{Body, Request, Response, StatusCode};
 use std::{collections::HashMap, sync::Arc};
@@ -37,6 +40,200 @@ pub async fn handle_dump_consensus_db_request(
     }
 }
 
+pub async fn handle_dump_equivocation_evidence_request(
+    req: Request<Body>,
+    consensus_db: Arc<dyn PersistentLivenessStorage>,
+) -> hyper::Result<Response<Body>> {
+    let query = req.uri().query().unwrap_or("");
+    let query_pairs: HashMap<_, _> = url::form_urlencoded::parse(query.as_bytes()).collect();
+
+    let bcs: bool = match query_pairs.get("bcs") {
+        Some(val) => match val.parse() {
+            Ok(val) => val,
+            Err(err) => return Ok(reply_with_status(StatusCode::BAD_REQUEST, err.to_string())),
+        },
+        None => false,
+    };
+
+    info!("Dumping equivocation evidence.");
+
+    match spawn_blocking(move || {
+        if bcs {
+            dump_equivocation_evidence_bcs(consensus_db.as_ref()).map(Into::<Body>::into)
+        } else {
+            dump_equivocation_evidence(consensus_db.as_ref()).map(Into::into)
+        }
+    })
+    .await
+    {
+        Ok(result) => {
+            info!("Finished dumping equivocation evidence.");
+            Ok(reply_with(vec![], result))
+        },
+        Err(e) => {
+            info!("Failed to dump equivocation evidence: {e:?}");
+            Ok(reply_with_status(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                e.to_string(),
+            ))
+        },
+    }
+}
+
+pub async fn handle_dump_batch_lifecycles_request(
+    req: Request<Body>,
+    batch_lifecycles: Arc<BatchLifecycles>,
+) -> hyper::Result<Response<Body>> {
+    let query = req.uri().query().unwrap_or("");
+    let query_pairs: HashMap<_, _> = url::form_urlencoded::parse(query.as_bytes()).collect();
+
+    let digest: Option<HashValue> = match query_pairs.get("digest") {
+        Some(val) => match val.parse() {
+            Ok(val) => Some(val),
+            Err(err) => return Ok(reply_with_status(StatusCode::BAD_REQUEST, err.to_string())),
+        },
+        None => None,
+    };
+    let author: Option<PeerId> = match query_pairs.get("author") {
+        Some(val) => match val.parse() {
+            Ok(val) => Some(val),
+            Err(err) => return Ok(reply_with_status(StatusCode::BAD_REQUEST, err.to_string())),
+        },
+        None => None,
+    };
+
+    info!("Dumping batch lifecycles.");
+
+    match dump_batch_lifecycles(&batch_lifecycles, digest, author) {
+        Ok(result) => {
+            info!("Finished dumping batch lifecycles.");
+            let headers: Vec<(_, HeaderValue)> =
+                vec![(CONTENT_LENGTH, HeaderValue::from(result.len()))];
+            Ok(reply_with(headers, result))
+        },
+        Err(e) => {
+            info!("Failed to dump batch lifecycles: {e:?}");
+            Ok(reply_with_status(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                e.to_string(),
+            ))
+        },
+    }
+}
+
+pub async fn handle_dump_block_traces_request(
+    req: Request<Body>,
+    block_traces: Arc<BlockTraces>,
+) -> hyper::Result<Response<Body>> {
+    let query = req.uri().query().unwrap_or("");
+    let query_pairs: HashMap<_, _> = url::form_urlencoded::parse(query.as_bytes()).collect();
+
+    let chrome_format = match query_pairs.get("format").map(|val| val.as_ref()) {
+        Some("json") | None => false,
+        Some("chrome") => true,
+        Some(format) => {
+            return Ok(reply_with_status(
+                StatusCode::BAD_REQUEST,
+                format!("Unsupported format: {format}. Expected json or chrome."),
+            ))
+        },
+    };
+
+    info!("Dumping block traces.");
+
+    match dump_block_traces(&block_traces, chrome_format) {
+        Ok(result) => {
+            info!("Finished dumping block traces.");
+            let headers: Vec<(_, HeaderValue)> =
+                vec![(CONTENT_LENGTH, HeaderValue::from(result.len()))];
+            Ok(reply_with(headers, result))
+        },
+        Err(e) => {
+            info!("Failed to dump block traces: {e:?}");
+            Ok(reply_with_status(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                e.to_string(),
+            ))
+        },
+    }
+}
+
+pub async fn handle_dump_block_building_snapshots_request(
+    block_building_snapshots: Arc<BlockBuildingSnapshots>,
+) -> hyper::Result<Response<Body>> {
+    info!("Dumping block building snapshots.");
+
+    // The snapshots are dumped in the format expected by the block building simulator
+    match serde_json::to_string_pretty(&block_building_snapshots.get_snapshots()) {
+        Ok(result) => {
+            info!("Finished dumping block building snapshots.");
+            let headers: Vec<(_, HeaderValue)> =
+                vec![(CONTENT_LENGTH, HeaderValue::from(result.len()))];
+            Ok(reply_with(headers, result))
+        },
+        Err(e) => {
+            info!("Failed to dump block building snapshots: {e:?}");
+            Ok(reply_with_status(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                e.to_string(),
+            ))
+        },
+    }
+}
+
+pub async fn handle_dump_dag_request(
+    req: Request<Body>,
+    dag_visualizer: Arc<DagVisualizer>,
+) -> hyper::Result<Response<Body>> {
+    let query = req.uri().query().unwrap_or("");
+    let query_pairs: HashMap<_, _> = url::form_urlencoded::parse(query.as_bytes()).collect();
+
+    let dot_format = match query_pairs.get("format").map(|val| val.as_ref()) {
+        Some("json") | None => false,
+        Some("dot") => true,
+        Some(format) => {
+            return Ok(reply_with_status(
+                StatusCode::BAD_REQUEST,
+                format!("Unsupported format: {format}. Expected json or dot."),
+            ))
+        },
+    };
+
+    let mut rounds = [None, None];
+    for (round, key) in rounds.iter_mut().zip(["from_round", "to_round"]) {
+        if let Some(val) = query_pairs.get(key) {
+            match val.parse::<u64>() {
+                Ok(val) => *round = Some(val),
+                Err(err) => {
+                    return Ok(reply_with_status(
+                        StatusCode::BAD_REQUEST,
+                        format!("Invalid {key}: {err}"),
+                    ))
+                },
+            }
+        }
+    }
+    let [from_round, to_round] = rounds;
+
+    info!("Dumping dag.");
+
+    match dump_dag(&dag_visualizer, from_round, to_round, dot_format) {
+        Ok(result) => {
+            info!("Finished dumping dag.");
+            let headers: Vec<(_, HeaderValue)> =
+                vec![(CONTENT_LENGTH, HeaderValue::from(result.len()))];
+            Ok(reply_with(headers, result))
+        },
+        Err(e) => {
+            info!("Failed to dump dag: {e:?}");
+            Ok(reply_with_status(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                e.to_string(),
+            ))
+        },
+    }
+}
+
 pub async fn handle_dump_quorum_store_db_request(
     req: Request<Body>,
     quorum_store_db: Arc<dyn QuorumStoreStorage>,
@@ -155,6 +352,70 @@ fn dump_consensus_db(consensus_db: &dyn PersistentLivenessStorage) -> anyhow::Re
     Ok(body)
 }
 
+fn dump_equivocation_evidence(
+    consensus_db: &dyn PersistentLivenessStorage,
+) -> anyhow::Result<String> {
+    let all_evidence = consensus_db
+        .consensus_db()
+        .get_all_equivocation_evidence()?
+        .into_iter()
+        .map(|(_, evidence)| EquivocationEvidenceExport::new(evidence))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    serde_json::to_string_pretty(&all_evidence).map_err(Error::msg)
+}
+
+fn dump_equivocation_evidence_bcs(
+    consensus_db: &dyn PersistentLivenessStorage,
+) -> anyhow::Result<Vec<u8>> {
+    let all_evidence: Vec<EquivocationEvidence> = consensus_db
+        .consensus_db()
+        .get_all_equivocation_evidence()?
+        .into_iter()
+        .map(|(_, evidence)| evidence)
+        .collect();
+    bcs::to_bytes(&all_evidence).map_err(Error::msg)
+}
+
+fn dump_batch_lifecycles(
+    batch_lifecycles: &BatchLifecycles,
+    digest: Option<HashValue>,
+    author: Option<PeerId>,
+) -> anyhow::Result<String> {
+    if let Some(digest) = digest {
+        match batch_lifecycles.get_lifecycle(&digest) {
+            Some(lifecycle) => serde_json::to_string_pretty(&lifecycle).map_err(Error::msg),
+            None => bail!("No lifecycle found for batch: {digest}"),
+        }
+    } else if let Some(author) = author {
+        serde_json::to_string_pretty(&batch_lifecycles.get_lifecycles_for_author(&author))
+            .map_err(Error::msg)
+    } else {
+        serde_json::to_string_pretty(&batch_lifecycles.get_summary()).map_err(Error::msg)
+    }
+}
+
+fn dump_block_traces(block_traces: &BlockTraces, chrome_format: bool) -> anyhow::Result<String> {
+    if chrome_format {
+        serde_json::to_string(&block_traces.get_traces_chrome_format()).map_err(Error::msg)
+    } else {
+        serde_json::to_string_pretty(&block_traces.get_traces()).map_err(Error::msg)
+    }
+}
+
+fn dump_dag(
+    dag_visualizer: &DagVisualizer,
+    from_round: Option<u64>,
+    to_round: Option<u64>,
+    dot_format: bool,
+) -> anyhow::Result<String> {
+    let dag_export = dag_visualizer.export(from_round, to_round)?;
+    if dot_format {
+        Ok(dag_export.to_dot())
+    } else {
+        serde_json::to_string_pretty(&dag_export).map_err(Error::msg)
+    }
+}
+
 fn dump_quorum_store_db(
     quorum_store_db: &dyn QuorumStoreStorage,
     digest: Option<HashValue>,
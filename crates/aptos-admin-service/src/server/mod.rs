@@ -3,7 +3,9 @@
 
 use aptos_config::config::{AuthenticationConfig, NodeConfig};
 use aptos_consensus::{
-    persistent_liveness_storage::StorageWriteProxy, quorum_store::quorum_store_db::QuorumStoreDB,
+    persistent_liveness_storage::StorageWriteProxy,
+    quorum_store::{batch_lifecycle::BatchLifecycles, quorum_store_db::QuorumStoreDB},
+    BlockBuildingSnapshots, BlockTraces, DagVisualizer,
 };
 use aptos_infallible::RwLock;
 use aptos_logger::info;
@@ -34,6 +36,10 @@ pub struct Context {
     aptos_db: RwLock<Option<Arc<DbReaderWriter>>>,
     consensus_db: RwLock<Option<Arc<StorageWriteProxy>>>,
     quorum_store_db: RwLock<Option<Arc<QuorumStoreDB>>>,
+    batch_lifecycles: RwLock<Option<Arc<BatchLifecycles>>>,
+    block_traces: RwLock<Option<Arc<BlockTraces>>>,
+    dag_visualizer: RwLock<Option<Arc<DagVisualizer>>>,
+    block_building_snapshots: RwLock<Option<Arc<BlockBuildingSnapshots>>>,
 }
 
 impl Context {
@@ -49,6 +55,22 @@ impl Context {
         *self.consensus_db.write() = Some(consensus_db);
         *self.quorum_store_db.write() = Some(quorum_store_db);
     }
+
+    fn set_batch_lifecycles(&self, batch_lifecycles: Arc<BatchLifecycles>) {
+        *self.batch_lifecycles.write() = Some(batch_lifecycles);
+    }
+
+    fn set_block_traces(&self, block_traces: Arc<BlockTraces>) {
+        *self.block_traces.write() = Some(block_traces);
+    }
+
+    fn set_dag_visualizer(&self, dag_visualizer: Arc<DagVisualizer>) {
+        *self.dag_visualizer.write() = Some(dag_visualizer);
+    }
+
+    fn set_block_building_snapshots(&self, block_building_snapshots: Arc<BlockBuildingSnapshots>) {
+        *self.block_building_snapshots.write() = Some(block_building_snapshots);
+    }
 }
 
 pub struct AdminService {
@@ -107,6 +129,26 @@ impl AdminService {
             .set_consensus_dbs(consensus_db, quorum_store_db)
     }
 
+    pub fn set_batch_lifecycles(&self, batch_lifecycles: Arc<BatchLifecycles>) {
+        self.context.set_batch_lifecycles(batch_lifecycles)
+    }
+
+    pub fn set_block_traces(&self, block_traces: Arc<BlockTraces>) {
+        self.context.set_block_traces(block_traces)
+    }
+
+    pub fn set_dag_visualizer(&self, dag_visualizer: Arc<DagVisualizer>) {
+        self.context.set_dag_visualizer(dag_visualizer)
+    }
+
+    pub fn set_block_building_snapshots(
+        &self,
+        block_building_snapshots: Arc<BlockBuildingSnapshots>,
+    ) {
+        self.context
+            .set_block_building_snapshots(block_building_snapshots)
+    }
+
     fn start(&self, address: SocketAddr, enabled: bool) {
         let context = self.context.clone();
         self.runtime.spawn(async move {
@@ -182,6 +224,64 @@ impl AdminService {
                     ))
                 }
             },
+            (hyper::Method::GET, "/debug/consensus/equivocation_evidence") => {
+                let consensus_db = context.consensus_db.read().clone();
+                if let Some(consensus_db) = consensus_db {
+                    consensus::handle_dump_equivocation_evidence_request(req, consensus_db).await
+                } else {
+                    Ok(reply_with_status(
+                        StatusCode::NOT_FOUND,
+                        "Consensus db is not available.",
+                    ))
+                }
+            },
+            (hyper::Method::GET, "/debug/consensus/batches") => {
+                let batch_lifecycles = context.batch_lifecycles.read().clone();
+                if let Some(batch_lifecycles) = batch_lifecycles {
+                    consensus::handle_dump_batch_lifecycles_request(req, batch_lifecycles).await
+                } else {
+                    Ok(reply_with_status(
+                        StatusCode::NOT_FOUND,
+                        "Batch lifecycle tracking is not enabled.",
+                    ))
+                }
+            },
+            (hyper::Method::GET, "/debug/consensus/block_traces") => {
+                let block_traces = context.block_traces.read().clone();
+                if let Some(block_traces) = block_traces {
+                    consensus::handle_dump_block_traces_request(req, block_traces).await
+                } else {
+                    Ok(reply_with_status(
+                        StatusCode::NOT_FOUND,
+                        "Block tracing is not enabled.",
+                    ))
+                }
+            },
+            (hyper::Method::GET, "/debug/consensus/block_building_snapshots") => {
+                let block_building_snapshots = context.block_building_snapshots.read().clone();
+                if let Some(block_building_snapshots) = block_building_snapshots {
+                    consensus::handle_dump_block_building_snapshots_request(
+                        block_building_snapshots,
+                    )
+                    .await
+                } else {
+                    Ok(reply_with_status(
+                        StatusCode::NOT_FOUND,
+                        "Block building snapshots are not enabled.",
+                    ))
+                }
+            },
+            (hyper::Method::GET, "/debug/consensus/dag") => {
+                let dag_visualizer = context.dag_visualizer.read().clone();
+                if let Some(dag_visualizer) = dag_visualizer {
+                    consensus::handle_dump_dag_request(req, dag_visualizer).await
+                } else {
+                    Ok(reply_with_status(
+                        StatusCode::NOT_FOUND,
+                        "Consensus is not running.",
+                    ))
+                }
+            },
             (hyper::Method::GET, "/debug/consensus/quorumstoredb") => {
                 let quorum_store_db = context.quorum_store_db.read().clone();
                 if let Some(quorum_store_db) = quorum_store_db {
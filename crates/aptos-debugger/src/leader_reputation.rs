@@ -0,0 +1,204 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, format_err, Result};
+use aptos_config::config::{
+    RocksdbConfigs, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
+    DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_STORAGE_PRUNER_CONFIG,
+};
+use aptos_consensus::util::leader_reputation_simulator::{
+    fetch_epoch_histories_from_db, EpochHistory, LeaderReputationSimulator, SimulationConfig,
+    SimulationReport,
+};
+use aptos_db::AptosDB;
+use aptos_rest_client::{Client, VersionedNewBlockEvent};
+use aptos_types::{
+    account_config::CORE_CODE_ADDRESS, on_chain_config::ValidatorSet,
+    validator_verifier::ValidatorVerifier,
+};
+use clap::Parser;
+use serde::Serialize;
+use std::path::PathBuf;
+use url::Url;
+
+// The maximum number of block events to fetch in a single REST request
+const MAX_EVENTS_PER_REQUEST: u16 = 100;
+
+/// Replays leader reputation over historical block events under alternative
+/// configs, and reports the proposer distribution, failed rounds and how
+/// quickly unhealthy validators are excluded (compared to the historical data).
+#[derive(Parser)]
+#[clap(group(clap::ArgGroup::new("target")
+        .required(true)
+        .multiple(false)
+        .args(&["rest_endpoint", "db_path"]),
+))]
+pub struct Command {
+    /// Read the historical block events from a full node's REST API
+    #[clap(long, group = "target")]
+    rest_endpoint: Option<String>,
+
+    /// Read the historical block events from a local db
+    #[clap(long, group = "target", value_parser)]
+    db_path: Option<PathBuf>,
+
+    /// The first epoch to simulate
+    #[clap(long)]
+    start_epoch: u64,
+
+    /// The last epoch to simulate (inclusive)
+    #[clap(long)]
+    end_epoch: u64,
+
+    /// The simulation configs (YAML files) to evaluate. If none are provided,
+    /// the default on-chain leader reputation config is simulated.
+    #[clap(long, value_parser)]
+    config_file: Vec<PathBuf>,
+}
+
+#[derive(Serialize)]
+struct ConfigReport {
+    config: SimulationConfig,
+    report: SimulationReport,
+}
+
+impl Command {
+    pub async fn run(self) -> Result<()> {
+        ensure!(
+            self.start_epoch > 0 && self.start_epoch <= self.end_epoch,
+            "Invalid epoch range: [{}, {}]",
+            self.start_epoch,
+            self.end_epoch
+        );
+
+        // Load the configs to simulate
+        let configs = if self.config_file.is_empty() {
+            vec![SimulationConfig::default()]
+        } else {
+            self.config_file
+                .iter()
+                .map(|path| {
+                    let contents = std::fs::read_to_string(path)?;
+                    serde_yaml::from_str(&contents).map_err(|error| {
+                        format_err!("Failed to parse config file {:?}: {}", path, error)
+                    })
+                })
+                .collect::<Result<Vec<SimulationConfig>>>()?
+        };
+
+        // Fetch the historical data
+        let histories = if let Some(rest_endpoint) = self.rest_endpoint {
+            let client = Client::new(Url::parse(&rest_endpoint)?);
+            fetch_epoch_histories_from_rest(&client, self.start_epoch, self.end_epoch).await?
+        } else if let Some(db_path) = self.db_path {
+            let db = AptosDB::open(
+                StorageDirPaths::from_path(db_path),
+                true,
+                NO_OP_STORAGE_PRUNER_CONFIG,
+                RocksdbConfigs::default(),
+                false, /* indexer */
+                BUFFERED_STATE_TARGET_ITEMS,
+                DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+                None,
+            )?;
+            fetch_epoch_histories_from_db(&db, self.start_epoch, self.end_epoch)?
+        } else {
+            unreachable!("Must provide one target.");
+        };
+
+        // Simulate each config and print the reports
+        let mut reports = vec![];
+        for config in configs {
+            let simulator = LeaderReputationSimulator::new(config.clone(), histories.clone())?;
+            reports.push(ConfigReport {
+                config,
+                report: simulator.simulate()?,
+            });
+        }
+        println!("{}", serde_json::to_string_pretty(&reports)?);
+
+        Ok(())
+    }
+}
+
+/// Reads the epoch histories (for the given range of epochs, inclusive) from
+/// the REST API. Block events are indexed by height, so we binary search for
+/// the first block of the start epoch.
+async fn fetch_epoch_histories_from_rest(
+    client: &Client,
+    start_epoch: u64,
+    end_epoch: u64,
+) -> Result<Vec<EpochHistory>> {
+    let ledger_info = client.get_ledger_information().await?.into_inner();
+
+    // Find the first block event in the start epoch
+    let (mut low, mut high) = (
+        ledger_info.oldest_block_height,
+        ledger_info.block_height + 1,
+    );
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if get_block_event(client, middle).await?.event.epoch() < start_epoch {
+            low = middle + 1;
+        } else {
+            high = middle;
+        }
+    }
+
+    // Fetch all block events until the end epoch
+    let mut histories: Vec<EpochHistory> = vec![];
+    let mut cursor = low;
+    'outer: while cursor <= ledger_info.block_height {
+        let events = client
+            .get_new_block_events_bcs(Some(cursor), Some(MAX_EVENTS_PER_REQUEST))
+            .await?
+            .into_inner();
+        if events.is_empty() {
+            break;
+        }
+        cursor += events.len() as u64;
+
+        for versioned_event in events {
+            let epoch = versioned_event.event.epoch();
+            if epoch > end_epoch {
+                break 'outer;
+            }
+
+            // Fetch the validator set at the start of each epoch
+            if histories.last().map(|history| history.epoch) != Some(epoch) {
+                let validator_set: ValidatorSet = client
+                    .get_account_resource_at_version_bcs(
+                        CORE_CODE_ADDRESS,
+                        "0x1::stake::ValidatorSet",
+                        versioned_event.version,
+                    )
+                    .await?
+                    .into_inner();
+                let verifier = ValidatorVerifier::from(&validator_set);
+                let validators = verifier
+                    .get_ordered_account_addresses_iter()
+                    .map(|author| (author, verifier.get_voting_power(&author).unwrap_or(0)))
+                    .collect();
+                histories.push(EpochHistory {
+                    epoch,
+                    validators,
+                    events: vec![],
+                });
+            }
+            if let Some(history) = histories.last_mut() {
+                history.events.push(versioned_event.event);
+            }
+        }
+    }
+
+    Ok(histories)
+}
+
+async fn get_block_event(client: &Client, height: u64) -> Result<VersionedNewBlockEvent> {
+    client
+        .get_new_block_events_bcs(Some(height), Some(1))
+        .await?
+        .into_inner()
+        .pop()
+        .ok_or_else(|| format_err!("No block event found at height {}!", height))
+}
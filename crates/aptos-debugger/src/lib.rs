@@ -4,6 +4,9 @@
 use anyhow::Result;
 use clap::Parser;
 
+pub mod leader_reputation;
+pub mod network_capture;
+
 #[derive(Parser)]
 pub enum Cmd {
     #[clap(subcommand)]
@@ -15,6 +18,11 @@ pub enum Cmd {
 
     #[clap(subcommand)]
     Move(aptos_move_debugger::common::Command),
+
+    #[clap(subcommand)]
+    NetworkCapture(network_capture::Command),
+
+    SimulateLeaderReputation(leader_reputation::Command),
 }
 
 impl Cmd {
@@ -24,6 +32,8 @@ impl Cmd {
             Cmd::Decode(cmd) => cmd.run().await,
             Cmd::DumpPendingTxns(cmd) => cmd.run().await,
             Cmd::Move(cmd) => cmd.run().await,
+            Cmd::NetworkCapture(cmd) => cmd.run().await,
+            Cmd::SimulateLeaderReputation(cmd) => cmd.run().await,
         }
     }
 }
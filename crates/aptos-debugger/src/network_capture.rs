@@ -0,0 +1,381 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, Result};
+use aptos_channels::{aptos_channel, message_queues::QueueStyle};
+use aptos_consensus::{
+    consensus_observer::network_message::ConsensusObserverMessage, network_interface::ConsensusMsg,
+};
+use aptos_dkg_runtime::types::DKGMessage;
+use aptos_jwk_consensus::types::JWKConsensusMsg;
+use aptos_mempool::MempoolSyncMsg;
+use aptos_network::{
+    capture::{
+        replay::{
+            list_capture_files, read_capture_files, replay_inbound_messages, write_capture_file,
+            ProtocolResolver,
+        },
+        CaptureDirection, CapturedMessage,
+    },
+    protocols::{
+        health_checker::HealthCheckerMsg,
+        wire::messaging::v1::{NetworkMessage, RequestId},
+    },
+    ProtocolId,
+};
+use aptos_peer_monitoring_service_types::PeerMonitoringServiceMessage;
+use aptos_storage_service_types::StorageServiceMessage;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use aptos_types::PeerId;
+use clap::{Parser, Subcommand, ValueEnum};
+use futures::StreamExt;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{collections::BTreeMap, path::PathBuf};
+
+/// Inspects the network messages captured by a node (see `message_capture_config`)
+#[derive(Subcommand)]
+pub enum Command {
+    /// Decodes the captured messages by protocol and prints them as JSON lines
+    Decode(Decode),
+    /// Extracts the matching messages into a new capture file (e.g., for replay in a test)
+    Extract(Extract),
+    /// Replays the captured inbound messages through an upstream handler channel and
+    /// prints them (decoded) as they are received
+    Replay(Replay),
+    /// Prints the number of messages and bytes per protocol and direction
+    Summary(Summary),
+}
+
+impl Command {
+    pub async fn run(self) -> Result<()> {
+        match self {
+            Command::Decode(cmd) => cmd.run(),
+            Command::Extract(cmd) => cmd.run(),
+            Command::Replay(cmd) => cmd.run().await,
+            Command::Summary(cmd) => cmd.run(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Direction {
+    Inbound,
+    Outbound,
+}
+
+#[derive(Parser)]
+pub struct CaptureFilter {
+    /// The directory containing the capture files
+    #[clap(long, value_parser)]
+    capture_dir: PathBuf,
+
+    /// Only include messages for the given protocols (e.g., `ConsensusRpcCompressed`)
+    #[clap(long, value_parser = parse_protocol_id)]
+    protocol: Vec<ProtocolId>,
+
+    /// Only include messages exchanged with the given peer
+    #[clap(long)]
+    peer_id: Option<PeerId>,
+
+    /// Only include messages sent in the given direction
+    #[clap(long, value_enum)]
+    direction: Option<Direction>,
+}
+
+impl CaptureFilter {
+    /// Reads the captured messages (in order) and returns the ones matching the
+    /// filter, together with their (resolved) protocol id.
+    fn read_messages(&self) -> Result<Vec<(Option<ProtocolId>, CapturedMessage)>> {
+        let capture_files = list_capture_files(&self.capture_dir)?;
+        let mut protocol_resolver = ProtocolResolver::new();
+
+        let mut messages = vec![];
+        for captured_message in read_capture_files(&capture_files)? {
+            let protocol_id = protocol_resolver.resolve(&captured_message);
+            if self.matches(protocol_id, &captured_message) {
+                messages.push((protocol_id, captured_message));
+            }
+        }
+        Ok(messages)
+    }
+
+    fn matches(&self, protocol_id: Option<ProtocolId>, captured_message: &CapturedMessage) -> bool {
+        let protocol_matches = self.protocol.is_empty()
+            || protocol_id.map_or(false, |protocol_id| self.protocol.contains(&protocol_id));
+        let peer_matches = self
+            .peer_id
+            .map_or(true, |peer_id| peer_id == captured_message.peer_id);
+        let direction_matches = match self.direction {
+            None => true,
+            Some(Direction::Inbound) => captured_message.direction == CaptureDirection::Inbound,
+            Some(Direction::Outbound) => captured_message.direction == CaptureDirection::Outbound,
+        };
+        protocol_matches && peer_matches && direction_matches
+    }
+}
+
+#[derive(Parser)]
+pub struct Decode {
+    #[clap(flatten)]
+    filter: CaptureFilter,
+
+    /// Print the raw (hex encoded) bytes of messages that cannot be decoded
+    #[clap(long)]
+    print_raw_bytes: bool,
+}
+
+impl Decode {
+    fn run(self) -> Result<()> {
+        for (protocol_id, captured_message) in self.filter.read_messages()? {
+            let mut output = match decode_network_message(
+                protocol_id,
+                &captured_message.message,
+                self.print_raw_bytes,
+            ) {
+                Some(output) => output,
+                None => continue,
+            };
+            output["timestamp_usecs"] = captured_message.timestamp_usecs.into();
+            output["network_id"] = serde_json::to_value(captured_message.network_id)?;
+            output["peer_id"] = serde_json::to_value(captured_message.peer_id)?;
+            output["direction"] = serde_json::to_value(captured_message.direction)?;
+            println!("{}", output);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Parser)]
+pub struct Replay {
+    #[clap(flatten)]
+    filter: CaptureFilter,
+
+    /// Reproduce the original delays between the replayed messages
+    #[clap(long)]
+    preserve_timing: bool,
+
+    /// Print the raw (hex encoded) bytes of messages that cannot be decoded
+    #[clap(long)]
+    print_raw_bytes: bool,
+}
+
+impl Replay {
+    async fn run(self) -> Result<()> {
+        let captured_messages: Vec<_> = self
+            .filter
+            .read_messages()?
+            .into_iter()
+            .map(|(_, captured_message)| captured_message)
+            .collect();
+        let protocol_ids = if self.filter.protocol.is_empty() {
+            ProtocolId::all().to_vec()
+        } else {
+            self.filter.protocol.clone()
+        };
+
+        // Replay the messages into the same channel the peer actors would push to
+        let queue_size = captured_messages.len().max(1);
+        let (upstream_handler, mut upstream_receiver) =
+            aptos_channel::new(QueueStyle::FIFO, queue_size, None);
+        let time_service = TimeService::real();
+        let replay_start_time = time_service.now();
+        let replay = async {
+            let result = replay_inbound_messages(
+                captured_messages,
+                &protocol_ids,
+                &upstream_handler,
+                time_service.clone(),
+                self.preserve_timing,
+            )
+            .await;
+            drop(upstream_handler);
+            result
+        };
+
+        // Print the messages as they are received by the "application"
+        let receive = async {
+            let mut num_messages = 0;
+            while let Some(received_message) = upstream_receiver.next().await {
+                let protocol_id = received_message.protocol_id();
+                if let Some(mut output) = decode_network_message(
+                    protocol_id,
+                    &received_message.message,
+                    self.print_raw_bytes,
+                ) {
+                    output["replay_offset_ms"] = (time_service
+                        .now()
+                        .duration_since(replay_start_time)
+                        .as_millis() as u64)
+                        .into();
+                    output["network_id"] = serde_json::json!(received_message.sender.network_id());
+                    output["peer_id"] = serde_json::json!(received_message.sender.peer_id());
+                    println!("{}", output);
+                }
+                num_messages += 1;
+            }
+            num_messages
+        };
+
+        let (rpc_responses, num_messages) = futures::join!(replay, receive);
+        eprintln!(
+            "Replayed {} messages ({} rpc requests)",
+            num_messages,
+            rpc_responses?.len()
+        );
+        Ok(())
+    }
+}
+
+#[derive(Parser)]
+pub struct Extract {
+    #[clap(flatten)]
+    filter: CaptureFilter,
+
+    /// The capture file to write the matching messages to
+    #[clap(long, value_parser)]
+    output_file: PathBuf,
+}
+
+impl Extract {
+    fn run(self) -> Result<()> {
+        let captured_messages: Vec<_> = self
+            .filter
+            .read_messages()?
+            .into_iter()
+            .map(|(_, captured_message)| captured_message)
+            .collect();
+        write_capture_file(&self.output_file, &captured_messages)?;
+        println!(
+            "Extracted {} messages to {:?}",
+            captured_messages.len(),
+            self.output_file
+        );
+        Ok(())
+    }
+}
+
+#[derive(Parser)]
+pub struct Summary {
+    #[clap(flatten)]
+    filter: CaptureFilter,
+}
+
+impl Summary {
+    fn run(self) -> Result<()> {
+        // Count the messages and bytes per protocol and direction
+        let mut summary: BTreeMap<(&'static str, &'static str), (u64, u64)> = BTreeMap::new();
+        for (protocol_id, captured_message) in self.filter.read_messages()? {
+            let protocol = protocol_id.map_or("unknown", |protocol_id| protocol_id.as_str());
+            let (num_messages, num_bytes) = summary
+                .entry((protocol, captured_message.direction.as_str()))
+                .or_default();
+            *num_messages += 1;
+            *num_bytes += captured_message.message.data_len() as u64;
+        }
+
+        println!(
+            "{:<36} {:<10} {:>12} {:>16}",
+            "protocol", "direction", "messages", "bytes"
+        );
+        for ((protocol, direction), (num_messages, num_bytes)) in summary {
+            println!(
+                "{:<36} {:<10} {:>12} {:>16}",
+                protocol, direction, num_messages, num_bytes
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Returns the type, request id, protocol and decoded contents of the given message
+/// as JSON (or `None` if the message carries no application data).
+fn decode_network_message(
+    protocol_id: Option<ProtocolId>,
+    message: &NetworkMessage,
+    print_raw_bytes: bool,
+) -> Option<serde_json::Value> {
+    let (message_type, request_id, bytes): (_, Option<RequestId>, _) = match message {
+        NetworkMessage::DirectSendMsg(message) => ("DirectSend", None, &message.raw_msg),
+        NetworkMessage::RpcRequest(request) => {
+            ("RpcRequest", Some(request.request_id), &request.raw_request)
+        },
+        NetworkMessage::RpcResponse(response) => (
+            "RpcResponse",
+            Some(response.request_id),
+            &response.raw_response,
+        ),
+        NetworkMessage::Error(_) => return None,
+    };
+
+    let decoded_message = match protocol_id {
+        Some(protocol_id) => decode_message(protocol_id, bytes),
+        None => Err(anyhow!("Unknown protocol (no matching rpc request)")),
+    };
+    let mut output = serde_json::json!({
+        "message_type": message_type,
+        "protocol_id": protocol_id.map(|protocol_id| protocol_id.as_str()),
+        "request_id": request_id,
+        "num_bytes": bytes.len(),
+    });
+    match decoded_message {
+        Ok(decoded_message) => output["message"] = decoded_message,
+        Err(error) => {
+            output["decode_error"] = error.to_string().into();
+            if print_raw_bytes {
+                output["raw_bytes"] = hex::encode(bytes).into();
+            }
+        },
+    }
+    Some(output)
+}
+
+fn parse_protocol_id(protocol: &str) -> Result<ProtocolId> {
+    ProtocolId::all()
+        .iter()
+        .find(|protocol_id| protocol_id.as_str() == protocol)
+        .copied()
+        .ok_or_else(|| anyhow!("Unknown protocol: {}", protocol))
+}
+
+/// Decodes the given message bytes using the message type of the protocol
+fn decode_message(protocol_id: ProtocolId, bytes: &[u8]) -> Result<serde_json::Value> {
+    use ProtocolId::*;
+    match protocol_id {
+        ConsensusRpcBcs
+        | ConsensusDirectSendBcs
+        | ConsensusDirectSendJson
+        | ConsensusRpcJson
+        | ConsensusRpcCompressed
+        | ConsensusDirectSendCompressed => decode_as::<ConsensusMsg>(protocol_id, bytes),
+        ConsensusObserver | ConsensusObserverRpc => {
+            decode_as::<ConsensusObserverMessage>(protocol_id, bytes)
+        },
+        MempoolDirectSend | MempoolRpc => decode_as::<MempoolSyncMsg>(protocol_id, bytes),
+        HealthCheckerRpc => decode_as::<HealthCheckerMsg>(protocol_id, bytes),
+        StorageServiceRpc => decode_as::<StorageServiceMessage>(protocol_id, bytes),
+        PeerMonitoringServiceRpc => decode_as::<PeerMonitoringServiceMessage>(protocol_id, bytes),
+        DKGDirectSendCompressed
+        | DKGDirectSendBcs
+        | DKGDirectSendJson
+        | DKGRpcCompressed
+        | DKGRpcBcs
+        | DKGRpcJson => decode_as::<DKGMessage>(protocol_id, bytes),
+        JWKConsensusDirectSendCompressed
+        | JWKConsensusDirectSendBcs
+        | JWKConsensusDirectSendJson
+        | JWKConsensusRpcCompressed
+        | JWKConsensusRpcBcs
+        | JWKConsensusRpcJson => decode_as::<JWKConsensusMsg>(protocol_id, bytes),
+        StateSyncDirectSend | DiscoveryDirectSend | NetbenchDirectSend | NetbenchRpc => Err(
+            anyhow!("No known message type for protocol: {}", protocol_id),
+        ),
+    }
+}
+
+fn decode_as<T: DeserializeOwned + Serialize>(
+    protocol_id: ProtocolId,
+    bytes: &[u8],
+) -> Result<serde_json::Value> {
+    let message: T = protocol_id.from_bytes(bytes)?;
+    Ok(serde_json::to_value(&message)?)
+}
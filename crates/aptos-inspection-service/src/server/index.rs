@@ -3,7 +3,8 @@
 
 use crate::{
     server::utils::CONTENT_TYPE_TEXT, CONFIGURATION_PATH, FORGE_METRICS_PATH, JSON_METRICS_PATH,
-    METRICS_PATH, PEER_INFORMATION_PATH, SYSTEM_INFORMATION_PATH,
+    LIGHT_CLIENT_PATH, METRICS_PATH, PEER_INFORMATION_PATH, STATE_SYNC_PROGRESS_PATH,
+    SYSTEM_INFORMATION_PATH,
 };
 use hyper::{Body, StatusCode};
 
@@ -27,8 +28,10 @@ fn get_index_response() -> String {
     index_response.push(format!("\t- {}", CONFIGURATION_PATH));
     index_response.push(format!("\t- {}", FORGE_METRICS_PATH));
     index_response.push(format!("\t- {}", JSON_METRICS_PATH));
+    index_response.push(format!("\t- {}", LIGHT_CLIENT_PATH));
     index_response.push(format!("\t- {}", METRICS_PATH));
     index_response.push(format!("\t- {}", PEER_INFORMATION_PATH));
+    index_response.push(format!("\t- {}", STATE_SYNC_PROGRESS_PATH));
     index_response.push(format!("\t- {}", SYSTEM_INFORMATION_PATH));
 
     index_response.join("\n") // Separate each entry with a newline
@@ -0,0 +1,148 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::server::utils::{CONTENT_TYPE_JSON, CONTENT_TYPE_TEXT};
+use aptos_data_client::client::AptosDataClient;
+use aptos_state_sync_driver::light_client::LightClient;
+use aptos_types::{account_address::AccountAddress, account_config::AccountResource};
+use hyper::{Body, StatusCode};
+use serde::Serialize;
+use std::str::FromStr;
+
+// The message to display when the node is not running a light client
+pub const LIGHT_CLIENT_DISABLED_MESSAGE: &str =
+    "This endpoint is only available for nodes running in light client mode! Set the node config at state_sync.state_sync_driver.bootstrapping_mode: LightClient";
+
+// The query parameter used to request a (verified) account
+const ACCOUNT_QUERY_PARAMETER: &str = "account";
+
+/// The verified state exposed by the light client endpoint
+#[derive(Serialize)]
+struct LightClientState {
+    epoch: u64,                       // The epoch of the latest verified ledger info
+    version: u64,                     // The version of the latest verified ledger info
+    timestamp_usecs: u64,             // The timestamp of the latest verified ledger info
+    account: Option<VerifiedAccount>, // The requested account (if any)
+}
+
+/// An account resource verified against the latest ledger info
+#[derive(Serialize)]
+struct VerifiedAccount {
+    address: String,                    // The address of the account
+    exists: bool,                       // Whether the account exists at the verified version
+    sequence_number: Option<u64>,       // The sequence number of the account
+    authentication_key: Option<String>, // The (hex encoded) authentication key of the account
+}
+
+/// Handles a new light client request. If the request specifies an account
+/// (e.g., `/light_client?account=0x1`), the account resource is fetched from
+/// peers and verified against the latest verified ledger info.
+pub async fn handle_light_client_request(
+    light_client: Option<LightClient<AptosDataClient>>,
+    query: Option<&str>,
+) -> (StatusCode, Body, String) {
+    // Only return the light client state if the node is running a light client
+    let light_client = match light_client {
+        Some(light_client) => light_client,
+        None => {
+            return (
+                StatusCode::FORBIDDEN,
+                Body::from(LIGHT_CLIENT_DISABLED_MESSAGE),
+                CONTENT_TYPE_TEXT.into(),
+            )
+        },
+    };
+
+    // Get the latest verified ledger info
+    let ledger_info = match light_client.get_latest_ledger_info() {
+        Some(ledger_info) => ledger_info,
+        None => {
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Body::from("The light client has not verified a ledger info yet!"),
+                CONTENT_TYPE_TEXT.into(),
+            )
+        },
+    };
+
+    // Parse the requested account (if any)
+    let account_address = match get_account_address(query) {
+        Ok(account_address) => account_address,
+        Err(error) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Body::from(error),
+                CONTENT_TYPE_TEXT.into(),
+            )
+        },
+    };
+
+    // Fetch and verify the requested account
+    let account = match account_address {
+        Some(account_address) => {
+            match light_client
+                .get_resource::<AccountResource>(&account_address)
+                .await
+            {
+                Ok(account_resource) => Some(VerifiedAccount {
+                    address: account_address.to_hex_literal(),
+                    exists: account_resource.is_some(),
+                    sequence_number: account_resource
+                        .as_ref()
+                        .map(|account_resource| account_resource.sequence_number()),
+                    authentication_key: account_resource
+                        .as_ref()
+                        .map(|account_resource| hex::encode(account_resource.authentication_key())),
+                }),
+                Err(error) => {
+                    return (
+                        StatusCode::SERVICE_UNAVAILABLE,
+                        Body::from(format!("Failed to fetch the account! Error: {}", error)),
+                        CONTENT_TYPE_TEXT.into(),
+                    )
+                },
+            }
+        },
+        None => None,
+    };
+
+    // Return the light client state as a JSON string
+    let light_client_state = LightClientState {
+        epoch: ledger_info.ledger_info().epoch(),
+        version: ledger_info.ledger_info().version(),
+        timestamp_usecs: ledger_info.ledger_info().timestamp_usecs(),
+        account,
+    };
+    match serde_json::to_string(&light_client_state) {
+        Ok(light_client_state) => (
+            StatusCode::OK,
+            Body::from(light_client_state),
+            CONTENT_TYPE_JSON.into(),
+        ),
+        Err(error) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Body::from(format!(
+                "Failed to get the light client state! Error: {}",
+                error
+            )),
+            CONTENT_TYPE_TEXT.into(),
+        ),
+    }
+}
+
+/// Returns the account address specified in the request query (if any)
+fn get_account_address(query: Option<&str>) -> Result<Option<AccountAddress>, String> {
+    let account = query.and_then(|query| {
+        query.split('&').find_map(|parameter| {
+            parameter
+                .strip_prefix(ACCOUNT_QUERY_PARAMETER)
+                .and_then(|value| value.strip_prefix('='))
+        })
+    });
+    account
+        .map(|account| {
+            AccountAddress::from_str(account)
+                .map_err(|error| format!("Invalid account address: {}! Error: {}", account, error))
+        })
+        .transpose()
+}
@@ -6,6 +6,7 @@ use aptos_config::config::NodeConfig;
 use aptos_data_client::client::AptosDataClient;
 use aptos_logger::debug;
 use aptos_network::application::storage::PeersAndMetadata;
+use aptos_state_sync_driver::{light_client::LightClient, progress::StateSyncProgressTracker};
 use hyper::{
     service::{make_service_fn, service_fn},
     Body, Method, Request, Response, Server, StatusCode,
@@ -20,8 +21,10 @@ use std::{
 mod configuration;
 mod index;
 mod json_encoder;
+mod light_client;
 mod metrics;
 mod peer_information;
+mod state_sync_progress;
 mod system_information;
 pub mod utils;
 
@@ -33,8 +36,10 @@ pub const CONFIGURATION_PATH: &str = "/configuration";
 pub const FORGE_METRICS_PATH: &str = "/forge_metrics";
 pub const INDEX_PATH: &str = "/";
 pub const JSON_METRICS_PATH: &str = "/json_metrics";
+pub const LIGHT_CLIENT_PATH: &str = "/light_client";
 pub const METRICS_PATH: &str = "/metrics";
 pub const PEER_INFORMATION_PATH: &str = "/peer_information";
+pub const STATE_SYNC_PROGRESS_PATH: &str = "/state_sync_progress";
 pub const SYSTEM_INFORMATION_PATH: &str = "/system_information";
 
 // Useful string constants
@@ -48,6 +53,8 @@ pub fn start_inspection_service(
     node_config: NodeConfig,
     aptos_data_client: AptosDataClient,
     peers_and_metadata: Arc<PeersAndMetadata>,
+    progress_tracker: StateSyncProgressTracker,
+    light_client: Option<LightClient<AptosDataClient>>,
 ) {
     // Fetch the service port and address
     let service_port = node_config.inspection_service.port;
@@ -75,6 +82,8 @@ pub fn start_inspection_service(
             let node_config = node_config.clone();
             let aptos_data_client = aptos_data_client.clone();
             let peers_and_metadata = peers_and_metadata.clone();
+            let progress_tracker = progress_tracker.clone();
+            let light_client = light_client.clone();
             async move {
                 Ok::<_, Infallible>(service_fn(move |request| {
                     serve_requests(
@@ -82,6 +91,8 @@ pub fn start_inspection_service(
                         node_config.clone(),
                         aptos_data_client.clone(),
                         peers_and_metadata.clone(),
+                        progress_tracker.clone(),
+                        light_client.clone(),
                     )
                 }))
             }
@@ -103,6 +114,8 @@ async fn serve_requests(
     node_config: NodeConfig,
     aptos_data_client: AptosDataClient,
     peers_and_metadata: Arc<PeersAndMetadata>,
+    progress_tracker: StateSyncProgressTracker,
+    light_client: Option<LightClient<AptosDataClient>>,
 ) -> Result<Response<Body>, hyper::Error> {
     // Process the request and get the response components
     let (status_code, body, content_type) = match req.uri().path() {
@@ -126,6 +139,11 @@ async fn serve_requests(
             // Exposes JSON encoded metrics
             metrics::handle_json_metrics_request()
         },
+        LIGHT_CLIENT_PATH => {
+            // /light_client
+            // Exposes the state verified by the light client
+            light_client::handle_light_client_request(light_client, req.uri().query()).await
+        },
         METRICS_PATH => {
             // /metrics
             // Exposes text encoded metrics
@@ -140,6 +158,16 @@ async fn serve_requests(
                 peers_and_metadata,
             )
         },
+        STATE_SYNC_PROGRESS_PATH => {
+            // /state_sync_progress
+            // Exposes the state sync progress
+            state_sync_progress::handle_state_sync_progress_request(
+                &node_config,
+                aptos_data_client,
+                peers_and_metadata,
+                progress_tracker,
+            )
+        },
         SYSTEM_INFORMATION_PATH => {
             // /system_information
             // Exposes the system and build information
@@ -0,0 +1,107 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::server::utils::{CONTENT_TYPE_JSON, CONTENT_TYPE_TEXT};
+use aptos_config::config::NodeConfig;
+use aptos_data_client::client::AptosDataClient;
+use aptos_network::application::storage::PeersAndMetadata;
+use aptos_state_sync_driver::progress::{StateSyncProgressReport, StateSyncProgressTracker};
+use hyper::{Body, StatusCode};
+use serde::Serialize;
+use std::sync::Arc;
+
+// The message to display when the state sync progress endpoint is disabled
+pub const STATE_SYNC_PROGRESS_DISABLED_MESSAGE: &str =
+    "This endpoint is disabled! Enable it in the node config at inspection_service.expose_state_sync_progress: true";
+
+/// The state sync progress exposed by the inspection service
+#[derive(Serialize)]
+struct StateSyncProgress {
+    progress: StateSyncProgressReport, // The progress reported by the state sync driver
+    serving_peers: Vec<ServingPeer>,   // The peers currently serving state sync data
+}
+
+/// A peer that is currently serving state sync data
+#[derive(Serialize)]
+struct ServingPeer {
+    peer: String,                           // The peer and network ID
+    score: f64,                             // The score of the peer (in the data client)
+    average_ping_latency_secs: Option<f64>, // The average ping latency to the peer
+    highest_synced_version: Option<u64>,    // The highest synced version advertised by the peer
+}
+
+/// Handles a new state sync progress request
+pub fn handle_state_sync_progress_request(
+    node_config: &NodeConfig,
+    aptos_data_client: AptosDataClient,
+    peers_and_metadata: Arc<PeersAndMetadata>,
+    progress_tracker: StateSyncProgressTracker,
+) -> (StatusCode, Body, String) {
+    // Only return the state sync progress if the endpoint is enabled
+    if node_config.inspection_service.expose_state_sync_progress {
+        let state_sync_progress =
+            get_state_sync_progress_json(aptos_data_client, peers_and_metadata, progress_tracker);
+        (
+            StatusCode::OK,
+            Body::from(state_sync_progress),
+            CONTENT_TYPE_JSON.into(),
+        )
+    } else {
+        (
+            StatusCode::FORBIDDEN,
+            Body::from(STATE_SYNC_PROGRESS_DISABLED_MESSAGE),
+            CONTENT_TYPE_TEXT.into(),
+        )
+    }
+}
+
+/// Returns a simple JSON formatted string with the state sync progress
+fn get_state_sync_progress_json(
+    aptos_data_client: AptosDataClient,
+    peers_and_metadata: Arc<PeersAndMetadata>,
+    progress_tracker: StateSyncProgressTracker,
+) -> String {
+    // Get the progress report from the state sync driver
+    let progress = progress_tracker.get_progress_report();
+
+    // Get the peers currently serving data (i.e., those with a storage summary)
+    let mut serving_peers = vec![];
+    let peer_to_state = aptos_data_client.get_peer_states().get_peer_to_states();
+    for peer_state_entry in peer_to_state.iter() {
+        let peer = *peer_state_entry.key();
+        if let Some(storage_summary) = peer_state_entry.get_storage_summary() {
+            let average_ping_latency_secs = peers_and_metadata
+                .get_metadata_for_peer(peer)
+                .ok()
+                .and_then(|peer_metadata| {
+                    peer_metadata
+                        .get_peer_monitoring_metadata()
+                        .average_ping_latency_secs
+                });
+            let highest_synced_version = storage_summary
+                .data_summary
+                .synced_ledger_info
+                .map(|ledger_info| ledger_info.ledger_info().version());
+
+            serving_peers.push(ServingPeer {
+                peer: peer.to_string(),
+                score: peer_state_entry.get_score(),
+                average_ping_latency_secs,
+                highest_synced_version,
+            });
+        }
+    }
+
+    // Sort the serving peers by score (highest first)
+    serving_peers.sort_by(|peer_1, peer_2| peer_2.score.total_cmp(&peer_1.score));
+
+    // Return the state sync progress as a JSON string
+    let state_sync_progress = StateSyncProgress {
+        progress,
+        serving_peers,
+    };
+    match serde_json::to_string(&state_sync_progress) {
+        Ok(state_sync_progress) => state_sync_progress,
+        Err(error) => format!("Failed to get the state sync progress! Error: {}", error),
+    }
+}
@@ -3,19 +3,22 @@
 
 use crate::{
     server::{
-        configuration::CONFIGURATION_DISABLED_MESSAGE,
+        configuration::CONFIGURATION_DISABLED_MESSAGE, light_client::LIGHT_CLIENT_DISABLED_MESSAGE,
         peer_information::PEER_INFO_DISABLED_MESSAGE, serve_requests,
+        state_sync_progress::STATE_SYNC_PROGRESS_DISABLED_MESSAGE,
         system_information::SYS_INFO_DISABLED_MESSAGE, utils::get_all_metrics,
     },
-    CONFIGURATION_PATH, FORGE_METRICS_PATH, INDEX_PATH, JSON_METRICS_PATH, METRICS_PATH,
-    PEER_INFORMATION_PATH, SYSTEM_INFORMATION_PATH,
+    CONFIGURATION_PATH, FORGE_METRICS_PATH, INDEX_PATH, JSON_METRICS_PATH, LIGHT_CLIENT_PATH,
+    METRICS_PATH, PEER_INFORMATION_PATH, STATE_SYNC_PROGRESS_PATH, SYSTEM_INFORMATION_PATH,
 };
 use aptos_config::config::{AptosDataClientConfig, BaseConfig, NodeConfig};
 use aptos_data_client::client::AptosDataClient;
 use aptos_network::application::{interface::NetworkClient, storage::PeersAndMetadata};
+use aptos_state_sync_driver::{light_client::LightClient, progress::StateSyncProgressTracker};
 use aptos_storage_interface::DbReader;
 use aptos_storage_service_client::StorageServiceClient;
 use aptos_time_service::TimeService;
+use aptos_types::waypoint::Waypoint;
 use assert_approx_eq::assert_approx_eq;
 use futures::executor::block_on;
 use hyper::{body, Body, Method, Request, Response, StatusCode};
@@ -90,6 +93,7 @@ async fn test_inspect_index() {
     assert!(response_body_string.contains(JSON_METRICS_PATH));
     assert!(response_body_string.contains(METRICS_PATH));
     assert!(response_body_string.contains(PEER_INFORMATION_PATH));
+    assert!(response_body_string.contains(STATE_SYNC_PROGRESS_PATH));
     assert!(response_body_string.contains(SYSTEM_INFORMATION_PATH));
 }
 
@@ -109,6 +113,25 @@ async fn test_inspect_json_metrics() {
     assert!(response_body_string.contains(INT_COUNTER_NAME));
 }
 
+#[tokio::test]
+async fn test_inspect_light_client() {
+    // Create a PFN config
+    let config = NodeConfig::get_default_pfn_config();
+
+    // Ping the light client endpoint on a node without a light client
+    let mut response = send_get_request_to_path(&config, LIGHT_CLIENT_PATH).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+
+    // Verify that the response contains an error
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert_eq!(response_body, LIGHT_CLIENT_DISABLED_MESSAGE);
+
+    // Ping the endpoint on a node whose light client hasn't verified a ledger info
+    let endpoint = format!("{}?account=0x1", LIGHT_CLIENT_PATH);
+    let response = serve_get_request(&config, &endpoint, true).await;
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+}
+
 #[tokio::test]
 async fn test_inspect_metrics() {
     // Create a validator config
@@ -180,6 +203,33 @@ async fn test_inspect_peer_information() {
     assert!(response_body_string.contains("State sync metadata"));
 }
 
+#[tokio::test]
+async fn test_inspect_state_sync_progress() {
+    // Create a validator node config
+    let mut config = NodeConfig::get_default_validator_config();
+
+    // Disable the state sync progress endpoint and ping it
+    config.inspection_service.expose_state_sync_progress = false;
+    let mut response = send_get_request_to_path(&config, STATE_SYNC_PROGRESS_PATH).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+
+    // Verify that the response contains an error
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert_eq!(response_body, STATE_SYNC_PROGRESS_DISABLED_MESSAGE);
+
+    // Enable the state sync progress endpoint and ping it
+    config.inspection_service.expose_state_sync_progress = true;
+    let mut response = send_get_request_to_path(&config, STATE_SYNC_PROGRESS_PATH).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+    let response_body_string = read_to_string(response_body.as_ref()).unwrap();
+
+    // Verify that the response contains the expected information
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response_body_string.contains("phase"));
+    assert!(response_body_string.contains("estimated_secs_remaining"));
+    assert!(response_body_string.contains("serving_peers"));
+}
+
 rusty_fork_test! {
 #[test]
 fn test_gather_metrics() {
@@ -248,6 +298,16 @@ fn test_publish_metrics() {
 
 // Exercise the serve_requests() handler with a GET request to the given path
 async fn send_get_request_to_path(config: &NodeConfig, endpoint: &str) -> Response<Body> {
+    serve_get_request(config, endpoint, false).await
+}
+
+// Exercise the serve_requests() handler with a GET request to the given path,
+// optionally for a node that runs a light client.
+async fn serve_get_request(
+    config: &NodeConfig,
+    endpoint: &str,
+    run_light_client: bool,
+) -> Response<Body> {
     // Build the URI
     let uri = format!("http://127.0.0.1:9201{}", endpoint);
 
@@ -266,6 +326,15 @@ async fn send_get_request_to_path(config: &NodeConfig, endpoint: &str) -> Respon
         None,
     );
 
+    // Create the light client (if required)
+    let light_client = run_light_client.then(|| {
+        LightClient::new(
+            aptos_data_client.clone(),
+            AptosDataClientConfig::default(),
+            Waypoint::default(),
+        )
+    });
+
     // Serve the request
     serve_requests(
         Request::builder()
@@ -276,6 +345,8 @@ async fn send_get_request_to_path(config: &NodeConfig, endpoint: &str) -> Respon
         config.clone(),
         aptos_data_client,
         peers_and_metadata,
+        StateSyncProgressTracker::new(TimeService::mock()),
+        light_client,
     )
     .await
     .unwrap()
@@ -3,5 +3,6 @@
 
 pub mod init;
 pub mod local_simulation;
+pub mod simulation_session;
 pub mod types;
 pub mod utils;
@@ -0,0 +1,644 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Simulation sessions fork the state of a network at a given version, and apply simulated
+//! transactions on top of each other. This allows multi-step deployments and governance
+//! actions to be rehearsed locally, before anything is submitted to the network.
+//!
+//! A session is stored in a directory, consisting of:
+//! - `config.json`: the network and version the session was forked from, together with the
+//!   history of the operations applied so far.
+//! - `delta.bcs`: all state changes made by the session, stacked on top of the remote state.
+//!
+//! State that has not been changed by the session is fetched on demand from the network
+//! using the debugger.
+
+use crate::common::{
+    types::{CliError, CliTypedResult},
+    utils::{create_dir_if_not_exist, read_from_file, write_to_file},
+};
+use aptos_move_debugger::aptos_debugger::AptosDebugger;
+use aptos_resource_viewer::AptosValueAnnotator;
+use aptos_rest_client::Client;
+use aptos_types::{
+    account_address::AccountAddress,
+    account_config::{
+        fungible_store::{primary_store, FungibleStoreResource},
+        AccountResource, ChainIdResource, CoinStoreResource, ObjectGroupResource,
+    },
+    chain_id::ChainId,
+    state_store::{
+        state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
+        Result as StateStoreResult, TStateView,
+    },
+    transaction::{SignedTransaction, TransactionOutput, TransactionStatus},
+    vm_status::VMStatus,
+    write_set::TransactionWrite,
+};
+use aptos_vm::{data_cache::AsMoveResolver, AptosVM};
+use aptos_vm_logging::log_schema::AdapterLogSchema;
+use move_core_types::{
+    language_storage::StructTag,
+    move_resource::{MoveResource, MoveStructType},
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+use url::Url;
+
+const CONFIG_FILE_NAME: &str = "config.json";
+const DELTA_FILE_NAME: &str = "delta.bcs";
+
+/// A resource group, as stored on chain
+type ResourceGroup = BTreeMap<StructTag, Vec<u8>>;
+
+/// The state of the network the session was forked from
+type RemoteStateView = Box<dyn TStateView<Key = StateKey> + Send + Sync>;
+
+/// An operation applied to a simulation session
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SessionOperation {
+    pub description: String,
+    pub success: bool,
+    pub gas_used: Option<u64>,
+    pub vm_status: Option<String>,
+}
+
+/// The persisted configuration of a simulation session
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SessionConfig {
+    /// The REST endpoint of the network the session was forked from
+    pub node_url: Url,
+    /// The version the session was forked at
+    pub base_version: u64,
+    /// The operations applied to the session, in order
+    pub history: Vec<SessionOperation>,
+}
+
+/// A simulation session, see the module documentation.
+pub struct Session {
+    path: PathBuf,
+    config: SessionConfig,
+    delta: BTreeMap<StateKey, Option<StateValue>>,
+    remote: RemoteStateView,
+}
+
+/// A state view stacking the changes of a session on top of the remote state
+pub struct SessionStateView<'a> {
+    session: &'a Session,
+}
+
+impl<'a> TStateView for SessionStateView<'a> {
+    type Key = StateKey;
+
+    fn get_state_value(&self, state_key: &Self::Key) -> StateStoreResult<Option<StateValue>> {
+        match self.session.delta.get(state_key) {
+            Some(state_value) => Ok(state_value.clone()),
+            None => self.session.remote.get_state_value(state_key),
+        }
+    }
+
+    fn get_usage(&self) -> StateStoreResult<StateStorageUsage> {
+        Ok(StateStorageUsage::Untracked)
+    }
+}
+
+fn remote_state_view(node_url: &Url, version: u64) -> CliTypedResult<RemoteStateView> {
+    let debugger = AptosDebugger::rest_client(Client::new(node_url.clone()))?;
+    Ok(Box::new(debugger.state_view_at_version(version)))
+}
+
+fn state_error(err: impl std::fmt::Display) -> CliError {
+    CliError::UnexpectedError(format!("Failed to read state: {}", err))
+}
+
+impl Session {
+    /// Creates a new session in `path`, forking the network at the given version, or at the
+    /// latest version if none is given.
+    pub async fn init(path: &Path, node_url: Url, version: Option<u64>) -> CliTypedResult<Self> {
+        if path.join(CONFIG_FILE_NAME).exists() {
+            return Err(CliError::CommandArgumentError(format!(
+                "A simulation session already exists at {}",
+                path.display()
+            )));
+        }
+
+        let base_version = match version {
+            Some(version) => version,
+            None => {
+                Client::new(node_url.clone())
+                    .get_ledger_information()
+                    .await?
+                    .into_inner()
+                    .version
+            },
+        };
+
+        let remote = remote_state_view(&node_url, base_version)?;
+        Self::create(
+            path,
+            SessionConfig {
+                node_url,
+                base_version,
+                history: vec![],
+            },
+            remote,
+        )
+    }
+
+    fn create(path: &Path, config: SessionConfig, remote: RemoteStateView) -> CliTypedResult<Self> {
+        let session = Self {
+            path: path.to_path_buf(),
+            config,
+            delta: BTreeMap::new(),
+            remote,
+        };
+        create_dir_if_not_exist(path)?;
+        session.save()?;
+        Ok(session)
+    }
+
+    /// Loads an existing session from `path`
+    pub fn load(path: &Path) -> CliTypedResult<Self> {
+        Self::load_with_remote(path, |config| {
+            remote_state_view(&config.node_url, config.base_version)
+        })
+    }
+
+    fn load_with_remote(
+        path: &Path,
+        remote: impl FnOnce(&SessionConfig) -> CliTypedResult<RemoteStateView>,
+    ) -> CliTypedResult<Self> {
+        let config_path = path.join(CONFIG_FILE_NAME);
+        if !config_path.exists() {
+            return Err(CliError::CommandArgumentError(format!(
+                "No simulation session found at {}, create one with `aptos move sim init`",
+                path.display()
+            )));
+        }
+        let config: SessionConfig = serde_json::from_slice(&read_from_file(&config_path)?)
+            .map_err(|err| CliError::UnableToParse(CONFIG_FILE_NAME, err.to_string()))?;
+        let delta = bcs::from_bytes(&read_from_file(&path.join(DELTA_FILE_NAME))?)
+            .map_err(|err| CliError::BCS(DELTA_FILE_NAME, err))?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            remote: remote(&config)?,
+            config,
+            delta,
+        })
+    }
+
+    fn save(&self) -> CliTypedResult<()> {
+        let config = serde_json::to_vec_pretty(&self.config)
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+        write_to_file(&self.path.join(CONFIG_FILE_NAME), CONFIG_FILE_NAME, &config)?;
+        let delta =
+            bcs::to_bytes(&self.delta).map_err(|err| CliError::BCS(DELTA_FILE_NAME, err))?;
+        write_to_file(&self.path.join(DELTA_FILE_NAME), DELTA_FILE_NAME, &delta)
+    }
+
+    pub fn config(&self) -> &SessionConfig {
+        &self.config
+    }
+
+    pub fn state_view(&self) -> SessionStateView<'_> {
+        SessionStateView { session: self }
+    }
+
+    fn get_state_value(&self, state_key: &StateKey) -> CliTypedResult<Option<StateValue>> {
+        self.state_view()
+            .get_state_value(state_key)
+            .map_err(state_error)
+    }
+
+    fn get_resource<T: MoveResource + DeserializeOwned>(
+        &self,
+        address: &AccountAddress,
+    ) -> CliTypedResult<Option<T>> {
+        let state_key = StateKey::resource_typed::<T>(address).map_err(state_error)?;
+        self.get_state_value(&state_key)?
+            .map(|value| {
+                bcs::from_bytes(value.bytes()).map_err(|err| CliError::BCS("resource", err))
+            })
+            .transpose()
+    }
+
+    fn get_resource_group(
+        &self,
+        address: &AccountAddress,
+        group_tag: &StructTag,
+    ) -> CliTypedResult<Option<ResourceGroup>> {
+        let state_key = StateKey::resource_group(address, group_tag);
+        self.get_state_value(&state_key)?
+            .map(|value| {
+                bcs::from_bytes(value.bytes()).map_err(|err| CliError::BCS("resource group", err))
+            })
+            .transpose()
+    }
+
+    /// Sets the bytes of an existing state value, keeping its metadata (i.e., storage deposits)
+    fn update_state_value(
+        &mut self,
+        state_key: StateKey,
+        state_value: StateValue,
+        bytes: Vec<u8>,
+    ) -> CliTypedResult<()> {
+        let state_value = state_value
+            .map_bytes(|_| Ok(bytes.into()))
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+        self.delta.insert(state_key, Some(state_value));
+        Ok(())
+    }
+
+    pub fn chain_id(&self) -> CliTypedResult<ChainId> {
+        self.get_resource::<ChainIdResource>(&AccountAddress::ONE)?
+            .map(|resource| resource.chain_id())
+            .ok_or_else(|| CliError::UnexpectedError("Chain ID not found".to_string()))
+    }
+
+    /// Returns the sequence number of the account, or 0 if the account does not exist
+    pub fn sequence_number(&self, account: &AccountAddress) -> CliTypedResult<u64> {
+        Ok(self
+            .get_resource::<AccountResource>(account)?
+            .map(|resource| resource.sequence_number())
+            .unwrap_or(0))
+    }
+
+    fn primary_fungible_store(
+        &self,
+        account: &AccountAddress,
+    ) -> CliTypedResult<Option<(ResourceGroup, FungibleStoreResource)>> {
+        let group = match self
+            .get_resource_group(&primary_store(account), &ObjectGroupResource::struct_tag())?
+        {
+            Some(group) => group,
+            None => return Ok(None),
+        };
+        let store = match group.get(&FungibleStoreResource::struct_tag()) {
+            Some(bytes) => {
+                bcs::from_bytes(bytes).map_err(|err| CliError::BCS("FungibleStore", err))?
+            },
+            None => return Ok(None),
+        };
+        Ok(Some((group, store)))
+    }
+
+    /// Returns the APT balance of the account, in both coin and fungible asset form
+    pub fn apt_balance(&self, account: &AccountAddress) -> CliTypedResult<u64> {
+        let coin = self
+            .get_resource::<CoinStoreResource>(account)?
+            .map(|store| store.coin())
+            .unwrap_or(0);
+        let fungible_asset = self
+            .primary_fungible_store(account)?
+            .map(|(_, store)| store.balance())
+            .unwrap_or(0);
+        Ok(coin + fungible_asset)
+    }
+
+    /// Injects `amount` octas into the account, by directly increasing its APT balance.
+    ///
+    /// The account must already have a coin store, or a primary fungible store for APT.
+    /// Note that the total supply of APT is not updated.
+    pub fn fund_account(&mut self, account: AccountAddress, amount: u64) -> CliTypedResult<u64> {
+        let coin_store_key =
+            StateKey::resource_typed::<CoinStoreResource>(&account).map_err(state_error)?;
+        if let Some(state_value) = self.get_state_value(&coin_store_key)? {
+            let store: CoinStoreResource = bcs::from_bytes(state_value.bytes())
+                .map_err(|err| CliError::BCS("CoinStore", err))?;
+            let store = CoinStoreResource::new(
+                store.coin().checked_add(amount).ok_or_else(|| {
+                    CliError::CommandArgumentError("Balance would overflow".to_string())
+                })?,
+                store.frozen(),
+                store.deposit_events().clone(),
+                store.withdraw_events().clone(),
+            );
+            let bytes = bcs::to_bytes(&store).map_err(|err| CliError::BCS("CoinStore", err))?;
+            self.update_state_value(coin_store_key, state_value, bytes)?;
+        } else if let Some((mut group, store)) = self.primary_fungible_store(&account)? {
+            let store = FungibleStoreResource::new(
+                store.metadata(),
+                store.balance().checked_add(amount).ok_or_else(|| {
+                    CliError::CommandArgumentError("Balance would overflow".to_string())
+                })?,
+                store.frozen(),
+            );
+            group.insert(
+                FungibleStoreResource::struct_tag(),
+                bcs::to_bytes(&store).map_err(|err| CliError::BCS("FungibleStore", err))?,
+            );
+
+            let group_key = StateKey::resource_group(
+                &primary_store(&account),
+                &ObjectGroupResource::struct_tag(),
+            );
+            let state_value = self
+                .get_state_value(&group_key)?
+                .expect("resource group must exist");
+            let bytes = bcs::to_bytes(&group).map_err(|err| CliError::BCS("ObjectGroup", err))?;
+            self.update_state_value(group_key, state_value, bytes)?;
+        } else {
+            return Err(CliError::CommandArgumentError(format!(
+                "Account {} has no APT balance to fund, create it first",
+                account
+            )));
+        }
+
+        self.config.history.push(SessionOperation {
+            description: format!("fund {} with {} octas", account, amount),
+            success: true,
+            gas_used: None,
+            vm_status: None,
+        });
+        self.save()?;
+        self.apt_balance(&account)
+    }
+
+    /// Returns the resource as JSON, looking into resource groups if needed
+    pub fn view_resource(
+        &self,
+        account: &AccountAddress,
+        resource: &StructTag,
+    ) -> CliTypedResult<Option<serde_json::Value>> {
+        let state_view = self.state_view();
+        let annotator = AptosValueAnnotator::new(&state_view);
+
+        let bytes = match annotator.view_resource_group_member(resource) {
+            Some(group_tag) => self
+                .get_resource_group(account, &group_tag)?
+                .and_then(|mut group| group.remove(resource)),
+            None => {
+                let state_key = StateKey::resource(account, resource).map_err(state_error)?;
+                self.get_state_value(&state_key)?
+                    .map(|value| value.bytes().to_vec())
+            },
+        };
+        let bytes = match bytes {
+            Some(bytes) => bytes,
+            None => return Ok(None),
+        };
+
+        let value = annotator.view_resource(resource, &bytes).map_err(|err| {
+            CliError::UnexpectedError(format!("Failed to decode resource: {:#}", err))
+        })?;
+        serde_json::to_value(value)
+            .map(Some)
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))
+    }
+
+    /// Executes the transaction on top of the session state. If the transaction is kept
+    /// (whether successful or not), its effects are applied to the session.
+    pub fn execute_transaction(
+        &mut self,
+        description: String,
+        transaction: SignedTransaction,
+    ) -> CliTypedResult<(VMStatus, TransactionOutput)> {
+        let (vm_status, txn_output) = {
+            let state_view = self.state_view();
+            let vm = AptosVM::new(&state_view);
+            let log_context = AdapterLogSchema::new(state_view.id(), 0);
+            let resolver = state_view.as_move_resolver();
+
+            let (vm_status, vm_output) =
+                vm.execute_user_transaction(&resolver, &transaction, &log_context);
+            let txn_output = vm_output
+                .try_materialize_into_transaction_output(&resolver)
+                .map_err(|err| {
+                    CliError::UnexpectedError(format!(
+                        "Failed to materialize into transaction output: {}",
+                        err
+                    ))
+                })?;
+            (vm_status, txn_output)
+        };
+
+        let success = match txn_output.status() {
+            TransactionStatus::Keep(exec_status) => {
+                for (state_key, write_op) in txn_output.write_set() {
+                    self.delta
+                        .insert(state_key.clone(), write_op.as_state_value());
+                }
+                exec_status.is_success()
+            },
+            TransactionStatus::Discard(_) | TransactionStatus::Retry => false,
+        };
+
+        self.config.history.push(SessionOperation {
+            description,
+            success,
+            gas_used: Some(txn_output.gas_used()),
+            vm_status: Some(vm_status.to_string()),
+        });
+        self.save()?;
+
+        Ok((vm_status, txn_output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_cached_packages::aptos_stdlib;
+    use aptos_sdk::transaction_builder::TransactionFactory;
+    use aptos_types::{
+        account_config::aptos_test_root_address,
+        event::{EventHandle, EventKey},
+        state_store::in_memory_state_view::InMemoryStateView,
+    };
+    use aptos_vm_genesis::{
+        generate_genesis_change_set_for_testing, GenesisOptions, GENESIS_KEYPAIR,
+    };
+
+    fn genesis_state_view() -> RemoteStateView {
+        let change_set = generate_genesis_change_set_for_testing(GenesisOptions::Head);
+        let state_data = change_set
+            .write_set()
+            .iter()
+            .filter_map(|(state_key, write_op)| {
+                write_op
+                    .as_state_value()
+                    .map(|state_value| (state_key.clone(), state_value))
+            })
+            .collect();
+        Box::new(InMemoryStateView::new(state_data))
+    }
+
+    fn create_session(path: &Path) -> Session {
+        let config = SessionConfig {
+            node_url: Url::parse("http://localhost:8080").unwrap(),
+            base_version: 0,
+            history: vec![],
+        };
+        Session::create(path, config, genesis_state_view()).unwrap()
+    }
+
+    #[test]
+    fn test_state_view_layering() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut session = create_session(dir.path());
+        let chain_id_key =
+            StateKey::resource_typed::<ChainIdResource>(&AccountAddress::ONE).unwrap();
+        let remote_value = session.get_state_value(&chain_id_key).unwrap();
+        assert!(remote_value.is_some());
+        assert_eq!(session.chain_id().unwrap(), ChainId::test());
+
+        // Changes of the session shadow the remote state
+        let changed_value = StateValue::new_legacy(vec![1, 2, 3].into());
+        session
+            .delta
+            .insert(chain_id_key.clone(), Some(changed_value.clone()));
+        assert_eq!(
+            session.get_state_value(&chain_id_key).unwrap(),
+            Some(changed_value)
+        );
+
+        // Deletions in the session hide the remote value
+        session.delta.insert(chain_id_key.clone(), None);
+        assert_eq!(session.get_state_value(&chain_id_key).unwrap(), None);
+
+        // Other state is still read from the remote state
+        let account_key =
+            StateKey::resource_typed::<AccountResource>(&AccountAddress::ONE).unwrap();
+        assert_eq!(
+            session.get_state_value(&account_key).unwrap(),
+            session.remote.get_state_value(&account_key).unwrap()
+        );
+        assert!(session.get_state_value(&account_key).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut session = create_session(dir.path());
+        let state_key = StateKey::resource_typed::<ChainIdResource>(&AccountAddress::ONE).unwrap();
+        let deleted_key =
+            StateKey::resource_typed::<AccountResource>(&AccountAddress::ONE).unwrap();
+        session.delta.insert(
+            state_key.clone(),
+            Some(StateValue::new_legacy(vec![4].into())),
+        );
+        session.delta.insert(deleted_key, None);
+        session.config.history.push(SessionOperation {
+            description: "operation".to_string(),
+            success: true,
+            gas_used: Some(5),
+            vm_status: None,
+        });
+        session.save().unwrap();
+
+        let loaded = Session::load_with_remote(dir.path(), |config| {
+            assert_eq!(config.base_version, 0);
+            Ok(genesis_state_view())
+        })
+        .unwrap();
+        assert_eq!(loaded.delta, session.delta);
+        assert_eq!(loaded.config.node_url, session.config.node_url);
+        assert_eq!(loaded.config.history.len(), 1);
+        assert_eq!(loaded.config.history[0].description, "operation");
+        assert_eq!(loaded.config.history[0].gas_used, Some(5));
+    }
+
+    #[test]
+    fn test_load_missing_session() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(matches!(
+            Session::load_with_remote(dir.path(), |_| Ok(genesis_state_view())),
+            Err(CliError::CommandArgumentError(_))
+        ));
+    }
+
+    #[test]
+    fn test_fund_account() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut session = create_session(dir.path());
+
+        // An account holding APT as a coin
+        let coin_account = AccountAddress::from_hex_literal("0xc01").unwrap();
+        let coin_store = CoinStoreResource::new(
+            10,
+            false,
+            EventHandle::new(EventKey::new(2, coin_account), 0),
+            EventHandle::new(EventKey::new(3, coin_account), 0),
+        );
+        session.delta.insert(
+            StateKey::resource_typed::<CoinStoreResource>(&coin_account).unwrap(),
+            Some(StateValue::new_legacy(
+                bcs::to_bytes(&coin_store).unwrap().into(),
+            )),
+        );
+        assert_eq!(session.fund_account(coin_account, 5).unwrap(), 15);
+        assert_eq!(session.apt_balance(&coin_account).unwrap(), 15);
+
+        // An account holding APT as a fungible asset, in its primary store
+        let fa_account = AccountAddress::from_hex_literal("0xfa").unwrap();
+        let fungible_store = FungibleStoreResource::new(AccountAddress::TEN, 20, false);
+        let group: ResourceGroup = [(
+            FungibleStoreResource::struct_tag(),
+            bcs::to_bytes(&fungible_store).unwrap(),
+        )]
+        .into_iter()
+        .collect();
+        session.delta.insert(
+            StateKey::resource_group(
+                &primary_store(&fa_account),
+                &ObjectGroupResource::struct_tag(),
+            ),
+            Some(StateValue::new_legacy(
+                bcs::to_bytes(&group).unwrap().into(),
+            )),
+        );
+        assert_eq!(session.fund_account(fa_account, 7).unwrap(), 27);
+        assert_eq!(session.apt_balance(&fa_account).unwrap(), 27);
+
+        // Accounts without any APT store cannot be funded
+        let missing_account = AccountAddress::from_hex_literal("0x404").unwrap();
+        assert!(session.fund_account(missing_account, 1).is_err());
+
+        assert_eq!(session.config.history.len(), 2);
+    }
+
+    #[test]
+    fn test_execute_transaction() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut session = create_session(dir.path());
+        let sender = aptos_test_root_address();
+        let receiver = AccountAddress::from_hex_literal("0xbeef").unwrap();
+        let sequence_number = session.sequence_number(&sender).unwrap();
+        assert_eq!(session.apt_balance(&receiver).unwrap(), 0);
+
+        let transaction = TransactionFactory::new(session.chain_id().unwrap())
+            .payload(aptos_stdlib::aptos_account_transfer(receiver, 1000))
+            .sender(sender)
+            .sequence_number(sequence_number)
+            .build()
+            .sign(&GENESIS_KEYPAIR.0, GENESIS_KEYPAIR.1.clone())
+            .unwrap()
+            .into_inner();
+        let (_, txn_output) = session
+            .execute_transaction("transfer".to_string(), transaction)
+            .unwrap();
+
+        // The write set is applied to the session
+        assert!(txn_output.status().status().unwrap().is_success());
+        for (state_key, write_op) in txn_output.write_set() {
+            assert_eq!(
+                session.delta.get(state_key),
+                Some(&write_op.as_state_value())
+            );
+        }
+        assert_eq!(session.apt_balance(&receiver).unwrap(), 1000);
+        assert_eq!(
+            session.sequence_number(&sender).unwrap(),
+            sequence_number + 1
+        );
+        assert!(session.config.history[0].success);
+
+        // ... and kept when loading the session again
+        let loaded = Session::load_with_remote(dir.path(), |_| Ok(genesis_state_view())).unwrap();
+        assert_eq!(loaded.apt_balance(&receiver).unwrap(), 1000);
+    }
+}
@@ -6,6 +6,7 @@ use crate::{
     common::{
         init::Network,
         local_simulation,
+        simulation_session::Session,
         utils::{
             check_if_file_exists, create_dir_if_not_exist, dir_default_to_current,
             get_account_with_state, get_auth_key, get_sequence_number, parse_json_file,
@@ -62,7 +63,7 @@ use std::{
     convert::TryFrom,
     fmt::{Debug, Display, Formatter},
     fs::OpenOptions,
-    path::PathBuf,
+    path::{Path, PathBuf},
     str::FromStr,
     time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
@@ -104,6 +105,10 @@ pub enum CliError {
     MoveCompilationError(String),
     #[error("Move unit tests failed")]
     MoveTestError,
+    #[error("Gas regression in Move unit tests: {0}")]
+    GasRegressionError(String),
+    #[error("Package upgrade is not compatible: {0}")]
+    IncompatibleUpgradeError(String),
     #[error("Move Prover failed: {0}")]
     MoveProverError(String),
     #[error("Unable to parse '{0}': error: {1}")]
@@ -130,6 +135,8 @@ impl CliError {
             CliError::IO(_, _) => "IO",
             CliError::MoveCompilationError(_) => "MoveCompilationError",
             CliError::MoveTestError => "MoveTestError",
+            CliError::GasRegressionError(_) => "GasRegressionError",
+            CliError::IncompatibleUpgradeError(_) => "IncompatibleUpgradeError",
             CliError::MoveProverError(_) => "MoveProverError",
             CliError::UnableToParse(_, _) => "UnableToParse",
             CliError::UnableToReadFile(_, _) => "UnableToReadFile",
@@ -1540,6 +1547,14 @@ pub struct TransactionOptions {
     /// flamegraphs that reflect the gas usage.
     #[clap(long)]
     pub(crate) profile_gas: bool,
+
+    /// If this option is set, simulate the transaction in the given simulation session, on top
+    /// of all previously simulated transactions, and keep its effects in the session.
+    ///
+    /// Sessions are created with `aptos move sim init`. Commands which cannot simulate their
+    /// transactions in a session fail instead of submitting them.
+    #[clap(long, value_parser, conflicts_with_all = ["local", "benchmark", "profile_gas"])]
+    pub(crate) session: Option<PathBuf>,
 }
 
 impl TransactionOptions {
@@ -1625,6 +1640,14 @@ impl TransactionOptions {
         &self,
         payload: TransactionPayload,
     ) -> CliTypedResult<Transaction> {
+        // A transaction meant for a simulation session must never reach the network
+        if let Some(session_path) = &self.session {
+            return Err(CliError::CommandArgumentError(format!(
+                "This command does not support simulation sessions, remove `--session {}` to submit the transaction",
+                session_path.display()
+            )));
+        }
+
         let client = self.rest_client()?;
         let (sender_public_key, sender_address) = self.get_public_key_and_address()?;
 
@@ -1892,6 +1915,71 @@ impl TransactionOptions {
         .await
     }
 
+    /// Simulates the transaction in a simulation session, applying its effects to the session.
+    pub fn simulate_in_session(
+        &self,
+        session_path: &Path,
+        payload: TransactionPayload,
+    ) -> CliTypedResult<TransactionSummary> {
+        // TODO(Gas): get the following from the session state
+        const DEFAULT_GAS_UNIT_PRICE: u64 = 100;
+        const DEFAULT_MAX_GAS: u64 = 2_000_000;
+
+        let mut session = Session::load(session_path)?;
+
+        let (sender_key, sender_address) = self.get_key_and_address()?;
+        let gas_unit_price = self
+            .gas_options
+            .gas_unit_price
+            .unwrap_or(DEFAULT_GAS_UNIT_PRICE);
+        let sequence_number = session.sequence_number(&sender_address)?;
+        let balance = session.apt_balance(&sender_address)?;
+        let max_gas = self.gas_options.max_gas.unwrap_or_else(|| {
+            if gas_unit_price == 0 {
+                DEFAULT_MAX_GAS
+            } else {
+                std::cmp::min(balance / gas_unit_price, DEFAULT_MAX_GAS)
+            }
+        });
+
+        let description = match &payload {
+            TransactionPayload::EntryFunction(entry_function) => {
+                format!("{}::{}", entry_function.module(), entry_function.function())
+            },
+            TransactionPayload::Script(_) => "script".to_string(),
+            _ => "transaction".to_string(),
+        };
+
+        let transaction_factory = TransactionFactory::new(session.chain_id()?)
+            .with_gas_unit_price(gas_unit_price)
+            .with_max_gas_amount(max_gas)
+            .with_transaction_expiration_time(self.gas_options.expiration_secs);
+        let sender_account = &mut LocalAccount::new(sender_address, sender_key, sequence_number);
+        let transaction =
+            sender_account.sign_with_transaction_builder(transaction_factory.payload(payload));
+        let hash = transaction.committed_hash();
+
+        let (vm_status, txn_output) = session.execute_transaction(description, transaction)?;
+
+        let success = match txn_output.status() {
+            TransactionStatus::Keep(exec_status) => Some(exec_status.is_success()),
+            TransactionStatus::Discard(_) | TransactionStatus::Retry => None,
+        };
+
+        Ok(TransactionSummary {
+            transaction_hash: hash.into(),
+            gas_used: Some(txn_output.gas_used()),
+            gas_unit_price: Some(gas_unit_price),
+            pending: None,
+            sender: Some(sender_address),
+            sequence_number: None, // The transaction is only applied to the session.
+            success,
+            timestamp_us: None,
+            version: None, // The transaction is only applied to the session.
+            vm_status: Some(vm_status.to_string()),
+        })
+    }
+
     pub async fn estimate_gas_price(&self) -> CliTypedResult<u64> {
         let client = self.rest_client()?;
         client
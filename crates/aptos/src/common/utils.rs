@@ -513,6 +513,8 @@ pub async fn profile_or_submit(
         txn_options_ref.benchmark_locally(payload).await
     } else if txn_options_ref.local {
         txn_options_ref.simulate_locally(payload).await
+    } else if let Some(session_path) = &txn_options_ref.session {
+        txn_options_ref.simulate_in_session(session_path, payload)
     } else {
         // Otherwise submit the transaction.
         txn_options_ref
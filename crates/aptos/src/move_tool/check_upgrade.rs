@@ -0,0 +1,409 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    common::types::{
+        load_account_arg, CliCommand, CliError, CliTypedResult, MovePackageDir, ProfileOptions,
+        RestOptions,
+    },
+    move_tool::{stored_package::CachedPackageRegistry, IncludedArtifacts},
+};
+use aptos_framework::{natives::code::UpgradePolicy, BuildOptions, BuiltPackage};
+use async_trait::async_trait;
+use clap::Parser;
+use move_binary_format::{
+    access::ModuleAccess,
+    compatibility::{Compatibility, IncompatibleItem},
+    file_format::{FunctionDefinitionIndex, StructDefinitionIndex},
+    CompiledModule,
+};
+use move_compiler::compiled_unit::{CompiledUnit, NamedCompiledModule};
+use move_core_types::account_address::AccountAddress;
+use move_ir_types::location::Loc;
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+/// Check whether a package can be upgraded in a compatible way
+///
+/// The package is compared against the version published on-chain under `--account`, or
+/// against another local package given by `--against`. Every change which is not allowed
+/// by the upgrade policy of the published package is reported, e.g. removed or changed
+/// public functions, changed struct layouts or removed abilities.
+#[derive(Parser)]
+pub struct CheckUpgrade {
+    /// Address of the account the package is published under
+    #[clap(long, value_parser = load_account_arg, required_unless_present = "against")]
+    pub(crate) account: Option<AccountAddress>,
+
+    /// Path to a local version of the package to compare against, instead of the on-chain one
+    #[clap(long, value_parser, conflicts_with = "account")]
+    pub(crate) against: Option<PathBuf>,
+
+    /// Also check that friend functions and friend declarations are unchanged
+    ///
+    /// By default, friend functions are treated like private functions, as on-chain.
+    #[clap(long)]
+    pub(crate) check_friend_linking: bool,
+
+    /// Artifacts to be generated when building this package.
+    #[clap(long, default_value_t = IncludedArtifacts::Sparse)]
+    pub(crate) included_artifacts: IncludedArtifacts,
+
+    #[clap(flatten)]
+    pub(crate) move_options: MovePackageDir,
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+/// A change of a package which is not allowed by its upgrade policy
+#[derive(Debug, Serialize)]
+pub struct UpgradeIncompatibility {
+    /// The module the change was found in, if the change is not about the package itself
+    pub module: Option<String>,
+    /// The location in the new version of the package, as `<file>:<line>`
+    pub location: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for UpgradeIncompatibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(location) = &self.location {
+            write!(f, "{}: ", location)?;
+        }
+        if let Some(module) = &self.module {
+            write!(f, "module `{}`: ", module)?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+/// The previous version of a package
+struct PreviousPackage {
+    upgrade_policy: UpgradePolicy,
+    modules: BTreeMap<String, CompiledModule>,
+}
+
+#[async_trait]
+impl CliCommand<&'static str> for CheckUpgrade {
+    fn command_name(&self) -> &'static str {
+        "CheckUpgrade"
+    }
+
+    async fn execute(self) -> CliTypedResult<&'static str> {
+        let pack = self.build_package(self.move_options.get_package_path()?)?;
+        let upgrade_policy = pack.extract_metadata()?.upgrade_policy;
+        let previous = match &self.against {
+            Some(path) => {
+                let previous = self.build_package(path.clone())?;
+                PreviousPackage {
+                    upgrade_policy: previous.extract_metadata()?.upgrade_policy,
+                    modules: previous
+                        .modules()
+                        .map(|module| (module.self_id().name().to_string(), module.clone()))
+                        .collect(),
+                }
+            },
+            None => self.fetch_package(pack.name()).await?,
+        };
+
+        let compatibility = Compatibility::new(true, self.check_friend_linking);
+        let incompatibilities =
+            check_package_upgrade(&previous, upgrade_policy, &pack, compatibility);
+        if incompatibilities.is_empty() {
+            return Ok("Package upgrade is compatible");
+        }
+        for incompatibility in &incompatibilities {
+            eprintln!("{}", incompatibility);
+        }
+        Err(CliError::IncompatibleUpgradeError(format!(
+            "found {} incompatible changes in package `{}`",
+            incompatibilities.len(),
+            pack.name()
+        )))
+    }
+}
+
+impl CheckUpgrade {
+    fn build_package(&self, package_path: PathBuf) -> CliTypedResult<BuiltPackage> {
+        let build_options = BuildOptions {
+            install_dir: self.move_options.output_dir.clone(),
+            bytecode_version: self.move_options.bytecode_version,
+            ..self.included_artifacts.build_options(
+                self.move_options.dev,
+                self.move_options.skip_fetch_latest_git_deps,
+                self.move_options.named_addresses(),
+                self.move_options.override_std.clone(),
+                self.move_options.bytecode_version,
+                self.move_options.compiler_version,
+                self.move_options.language_version,
+                self.move_options.skip_attribute_checks,
+                self.move_options.check_test_code,
+            )
+        };
+        BuiltPackage::build(package_path, build_options)
+            .map_err(|e| CliError::MoveCompilationError(format!("{:#}", e)))
+    }
+
+    async fn fetch_package(&self, name: &str) -> CliTypedResult<PreviousPackage> {
+        let account = self.account.ok_or_else(|| {
+            CliError::CommandArgumentError("Either --account or --against is required".to_owned())
+        })?;
+        let url = self.rest_options.url(&self.profile_options)?;
+        let registry = CachedPackageRegistry::create(url, account, true).await?;
+        let package = registry
+            .get_package(name)
+            .await
+            .map_err(|s| CliError::CommandArgumentError(s.to_string()))?;
+
+        let mut modules = BTreeMap::new();
+        for module_name in package.module_names() {
+            let bytecode = registry.get_bytecode(module_name).await?.ok_or_else(|| {
+                CliError::UnexpectedError(format!(
+                    "Bytecode of module `{}` not found on-chain",
+                    module_name
+                ))
+            })?;
+            let module = CompiledModule::deserialize(bytecode)
+                .map_err(|err| CliError::UnableToParse("on-chain module", err.to_string()))?;
+            modules.insert(module_name.to_string(), module);
+        }
+        Ok(PreviousPackage {
+            upgrade_policy: package.upgrade_policy(),
+            modules,
+        })
+    }
+}
+
+/// Checks the rules of `code::check_upgradability` and the compatibility checks done by the
+/// VM when publishing `pack` as an upgrade of `previous`.
+fn check_package_upgrade(
+    previous: &PreviousPackage,
+    upgrade_policy: UpgradePolicy,
+    pack: &BuiltPackage,
+    compatibility: Compatibility,
+) -> Vec<UpgradeIncompatibility> {
+    let mut incompatibilities = vec![];
+    let package_error = |message: String| UpgradeIncompatibility {
+        module: None,
+        location: None,
+        message,
+    };
+
+    if let Some(message) = check_upgrade_policy(previous.upgrade_policy, upgrade_policy) {
+        incompatibilities.push(package_error(message));
+    }
+    // Nothing else is checked for packages which can be upgraded arbitrarily, and
+    // immutable packages cannot be upgraded at all.
+    if previous.upgrade_policy != UpgradePolicy::compat() {
+        return incompatibilities;
+    }
+
+    let new_modules = pack
+        .package
+        .root_modules()
+        .filter_map(|unit| match &unit.unit {
+            CompiledUnit::Module(module) => Some((
+                module.name.to_string(),
+                (module, unit.source_path.as_path()),
+            )),
+            CompiledUnit::Script(_) => None,
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    for (name, old_module) in &previous.modules {
+        let (new_module, source_path) = match new_modules.get(name) {
+            Some(new_module) => new_module,
+            None => {
+                incompatibilities.push(package_error(format!("removed module `{}`", name)));
+                continue;
+            },
+        };
+        for incompatibility in compatibility.incompatibilities(old_module, &new_module.module) {
+            let loc = item_location(new_module, &incompatibility.item);
+            incompatibilities.push(UpgradeIncompatibility {
+                module: Some(name.clone()),
+                location: source_location(source_path, loc),
+                message: incompatibility.message,
+            });
+        }
+    }
+    incompatibilities
+}
+
+/// Returns why a package with `old` upgrade policy cannot be upgraded to a package with
+/// `new` upgrade policy, if it cannot.
+fn check_upgrade_policy(old: UpgradePolicy, new: UpgradePolicy) -> Option<String> {
+    if old == UpgradePolicy::immutable() {
+        Some("the published package is immutable and cannot be upgraded".to_owned())
+    } else if new.policy < old.policy {
+        Some(format!(
+            "the upgrade policy cannot be weakened from `{}` to `{}`",
+            old, new
+        ))
+    } else {
+        None
+    }
+}
+
+/// Returns the location of the definition of the item in the module, falling back to the
+/// location of the module if the item does not exist anymore.
+fn item_location(module: &NamedCompiledModule, item: &IncompatibleItem) -> Loc {
+    let compiled = &module.module;
+    let loc = match item {
+        IncompatibleItem::Struct(name) => compiled
+            .struct_defs()
+            .iter()
+            .position(|def| {
+                compiled.identifier_at(compiled.struct_handle_at(def.struct_handle).name)
+                    == name.as_ident_str()
+            })
+            .and_then(|idx| {
+                module
+                    .source_map
+                    .get_struct_source_map(StructDefinitionIndex(idx as u16))
+                    .ok()
+            })
+            .map(|source_map| source_map.definition_location),
+        IncompatibleItem::Function(name) => compiled
+            .function_defs()
+            .iter()
+            .position(|def| {
+                compiled.identifier_at(compiled.function_handle_at(def.function).name)
+                    == name.as_ident_str()
+            })
+            .and_then(|idx| {
+                module
+                    .source_map
+                    .get_function_source_map(FunctionDefinitionIndex(idx as u16))
+                    .ok()
+            })
+            .map(|source_map| source_map.definition_location),
+        IncompatibleItem::Module | IncompatibleItem::FriendDeclarations => None,
+    };
+    loc.unwrap_or(module.source_map.definition_location)
+}
+
+/// Formats the location as `<file>:<line>`, if the source file can be read.
+fn source_location(source_path: &Path, loc: Loc) -> Option<String> {
+    let source = std::fs::read_to_string(source_path).ok()?;
+    Some(format!(
+        "{}:{}",
+        source_path.display(),
+        line_number(&source, loc.start())
+    ))
+}
+
+/// Returns the 1-based line number of the byte offset in the source.
+fn line_number(source: &str, offset: u32) -> usize {
+    let offset = (offset as usize).min(source.len());
+    source.as_bytes()[..offset]
+        .iter()
+        .filter(|byte| **byte == b'\n')
+        .count()
+        + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn build_test_package(dir: &TempDir, modules: &[(&str, &str)]) -> BuiltPackage {
+        std::fs::write(
+            dir.path().join("Move.toml"),
+            "[package]\nname = \"Test\"\nversion = \"0.0.0\"\nupgrade_policy = \"compatible\"\n\n\
+            [addresses]\ntest = \"0xcafe\"\n",
+        )
+        .unwrap();
+        let sources = dir.path().join("sources");
+        std::fs::create_dir_all(&sources).unwrap();
+        for (name, source) in modules {
+            std::fs::write(sources.join(format!("{}.move", name)), source).unwrap();
+        }
+        BuiltPackage::build(dir.path().to_path_buf(), BuildOptions::default()).unwrap()
+    }
+
+    #[test]
+    fn test_check_package_upgrade() {
+        let old_dir = TempDir::new().unwrap();
+        let old = build_test_package(&old_dir, &[
+            (
+                "m",
+                "module test::m {\n    struct S has key { a: u64 }\n    public fun f(): u64 { 1 }\n    public fun g() {}\n}\n",
+            ),
+            ("removed", "module test::removed {\n    public fun h() {}\n}\n"),
+        ]);
+        let new_dir = TempDir::new().unwrap();
+        let new = build_test_package(&new_dir, &[(
+            "m",
+            "module test::m {\n    public fun g() {}\n\n    struct S has key { a: u64, b: bool }\n}\n",
+        )]);
+
+        let previous = PreviousPackage {
+            upgrade_policy: old.extract_metadata().unwrap().upgrade_policy,
+            modules: old
+                .modules()
+                .map(|module| (module.self_id().name().to_string(), module.clone()))
+                .collect(),
+        };
+        let incompatibilities = check_package_upgrade(
+            &previous,
+            UpgradePolicy::compat(),
+            &new,
+            Compatibility::new(true, false),
+        );
+
+        let reported = incompatibilities
+            .iter()
+            .map(|incompatibility| {
+                (
+                    incompatibility.module.as_deref(),
+                    incompatibility.message.as_str(),
+                    incompatibility
+                        .location
+                        .as_deref()
+                        .and_then(|location| location.rsplit(':').next()),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(reported, vec![
+            (Some("m"), "changed layout of struct `S`", Some("4")),
+            // Removed items are reported at the module definition.
+            (Some("m"), "removed function `f`", Some("1")),
+            (None, "removed module `removed`", None),
+        ]);
+        let location = incompatibilities[0].location.as_ref().unwrap();
+        assert!(location.ends_with("m.move:4"), "{}", location);
+    }
+
+    #[test]
+    fn test_check_upgrade_policy() {
+        let (arbitrary, compat, immutable) = (
+            UpgradePolicy::arbitrary(),
+            UpgradePolicy::compat(),
+            UpgradePolicy::immutable(),
+        );
+        assert_eq!(check_upgrade_policy(compat, compat), None);
+        assert_eq!(check_upgrade_policy(compat, immutable), None);
+        assert_eq!(check_upgrade_policy(arbitrary, compat), None);
+        assert_eq!(
+            check_upgrade_policy(compat, arbitrary),
+            Some("the upgrade policy cannot be weakened from `compatible` to `arbitrary`".into())
+        );
+        assert!(check_upgrade_policy(immutable, immutable).is_some());
+    }
+
+    #[test]
+    fn test_line_number() {
+        let source = "module 0x1::m {\n    struct S {}\n}\n";
+        assert_eq!(line_number(source, 0), 1);
+        assert_eq!(line_number(source, 20), 2);
+        assert_eq!(line_number(source, 1000), 4);
+    }
+}
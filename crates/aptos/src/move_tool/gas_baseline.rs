@@ -0,0 +1,485 @@
+// Copyright (c) Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    common::{
+        types::{CliError, CliTypedResult},
+        utils::{read_from_file, write_to_file},
+    },
+    move_tool::aptos_debug_natives,
+};
+use aptos_gas_meter::{AptosGasMeter, StandardGasAlgebra, StandardGasMeter};
+use aptos_gas_profiling::{GasChange, GasDiff, GasProfiler};
+use aptos_gas_schedule::{AptosGasParameters, InitialGasSchedule, LATEST_GAS_FEATURE_VERSION};
+use aptos_types::{state_store::state_key::StateKey, write_set::WriteOpSize};
+use aptos_vm_types::storage::StorageGasParameters;
+use clap::Parser;
+use move_core_types::{
+    effects::{ChangeSet, Op},
+    identifier::IdentStr,
+    language_storage::ModuleId,
+};
+use move_unit_test::test_runner::TestGasMeterFactory;
+use move_vm_runtime::native_functions::NativeFunctionTable;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// The gas used by each unit test of a package, keyed by the fully qualified test name
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasBaseline {
+    pub gas_used: BTreeMap<String, u64>,
+}
+
+impl GasBaseline {
+    pub fn load(path: &Path) -> CliTypedResult<Self> {
+        let bytes = read_from_file(path)?;
+        serde_json::from_slice(&bytes)
+            .map_err(|err| CliError::UnableToParse("gas baseline", err.to_string()))
+    }
+
+    pub fn save(&self, path: &Path) -> CliTypedResult<()> {
+        let bytes = serde_json::to_vec_pretty(self)
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+        write_to_file(path, &path.display().to_string(), &bytes)
+    }
+}
+
+/// Meters unit tests with the latest Aptos gas schedule, using a profiling gas meter.
+///
+/// The gas used by a test is the execution and IO gas it would be charged on-chain, including the
+/// IO gas for the writes of tests which execute successfully. Storage fees (which are paid in
+/// octas, and depend on the state of the chain) are not included. The gas is measured in internal
+/// gas units, as most unit tests use less than one (external) gas unit.
+pub struct AptosGasMeterFactory {
+    gas_params: AptosGasParameters,
+    storage_gas_params: StorageGasParameters,
+}
+
+impl AptosGasMeterFactory {
+    pub fn new() -> Self {
+        Self {
+            gas_params: AptosGasParameters::initial(),
+            storage_gas_params: StorageGasParameters::latest(),
+        }
+    }
+
+    /// Returns the natives to run the tests with, which charge gas according to the gas schedule
+    pub fn natives(&self) -> NativeFunctionTable {
+        aptos_debug_natives::aptos_debug_natives(
+            self.gas_params.natives.clone(),
+            self.gas_params.vm.misc.clone(),
+        )
+    }
+}
+
+impl Default for AptosGasMeterFactory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestGasMeterFactory for AptosGasMeterFactory {
+    type GasMeter<'a> = GasProfiler<StandardGasMeter<StandardGasAlgebra>>;
+
+    fn new_gas_meter(
+        &self,
+        module_id: &ModuleId,
+        function_name: &IdentStr,
+        execution_bound: u64,
+    ) -> Self::GasMeter<'_> {
+        // Tests are bounded by the given amount of gas, rather than by the execution and IO
+        // limits of a transaction
+        let mut vm_gas_params = self.gas_params.vm.clone();
+        vm_gas_params.txn.max_execution_gas = u64::MAX.into();
+        vm_gas_params.txn.max_io_gas = u64::MAX.into();
+
+        let algebra = StandardGasAlgebra::new(
+            LATEST_GAS_FEATURE_VERSION,
+            vm_gas_params,
+            self.storage_gas_params.clone(),
+            false,
+            execution_bound,
+        );
+        GasProfiler::new_function(
+            StandardGasMeter::new(algebra),
+            module_id.clone(),
+            function_name.to_owned(),
+            vec![],
+        )
+    }
+
+    fn gas_used(
+        &self,
+        mut gas_meter: Self::GasMeter<'_>,
+        _execution_bound: u64,
+        change_set: Option<&ChangeSet>,
+    ) -> u64 {
+        if let Some(change_set) = change_set {
+            for (key, op) in write_ops(change_set) {
+                // If the test ran out of gas, all of its balance is used up already
+                if gas_meter.charge_io_gas_for_write(&key, &op).is_err() {
+                    break;
+                }
+            }
+        }
+
+        u64::from(gas_meter.finish().exec_io.total)
+    }
+}
+
+/// Returns the state keys and sizes of the writes in the change set of a test
+fn write_ops(change_set: &ChangeSet) -> Vec<(StateKey, WriteOpSize)> {
+    let modules = change_set
+        .modules()
+        .map(|(address, name, op)| (StateKey::module(&address, name), write_op_size(op)));
+    let resources = change_set
+        .resources()
+        .filter_map(|(address, struct_tag, op)| {
+            StateKey::resource(&address, struct_tag)
+                .ok()
+                .map(|key| (key, write_op_size(op)))
+        });
+    modules.chain(resources).collect()
+}
+
+fn write_op_size<T: AsRef<[u8]>>(op: Op<T>) -> WriteOpSize {
+    match op {
+        Op::New(bytes) => WriteOpSize::Creation {
+            write_len: bytes.as_ref().len() as u64,
+        },
+        Op::Modify(bytes) => WriteOpSize::Modification {
+            write_len: bytes.as_ref().len() as u64,
+        },
+        Op::Delete => WriteOpSize::Deletion,
+    }
+}
+
+/// Options for checking the gas used by unit tests against a baseline
+///
+/// If a baseline is given, the tests are metered with the latest Aptos gas schedule (see
+/// `AptosGasMeterFactory`), and the gas used by a test is the execution and IO gas charged
+/// on-chain, in internal gas units.
+#[derive(Default, Parser)]
+pub struct GasBaselineOptions {
+    /// Path to a JSON file with the gas used by each unit test
+    ///
+    /// If set, the tests are metered with the Aptos gas schedule, and the gas used by each
+    /// passing test (in internal gas units) is compared against the baseline. The command
+    /// fails if any test uses more gas than allowed by `--gas-threshold`.
+    #[clap(long, value_parser)]
+    pub(crate) gas_baseline: Option<PathBuf>,
+
+    /// Write the gas used by each passing test to the baseline file, instead of comparing
+    ///
+    /// If a filter is given, only the entries of the tests that ran are updated.
+    #[clap(long, requires = "gas_baseline")]
+    pub(crate) update_gas_baseline: bool,
+
+    /// The change in gas usage, in percent, up to which a test is considered unchanged
+    #[clap(long, default_value_t = 0.0, requires = "gas_baseline")]
+    pub(crate) gas_threshold: f64,
+
+    /// Only warn, instead of failing, when a test uses more gas than in the baseline
+    #[clap(long, requires = "gas_baseline")]
+    pub(crate) warn_on_gas_regression: bool,
+
+    /// Directory to write a gas diff report to, as `index.html` and `report.md`
+    #[clap(long, value_parser, requires = "gas_baseline")]
+    pub(crate) gas_report_dir: Option<PathBuf>,
+}
+
+impl GasBaselineOptions {
+    /// Returns true iff the tests should be metered and compared against a baseline
+    pub fn is_enabled(&self) -> bool {
+        self.gas_baseline.is_some()
+    }
+
+    /// Updates the baseline, or compares against it, depending on the options.
+    ///
+    /// `filtered` indicates that only a subset of the tests ran, in which case tests that
+    /// are in the baseline but did not run are left untouched.
+    pub fn check_or_update(
+        &self,
+        gas_used: BTreeMap<String, u64>,
+        filtered: bool,
+    ) -> CliTypedResult<()> {
+        let baseline_path = match &self.gas_baseline {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+
+        if self.update_gas_baseline {
+            let mut baseline = if filtered && baseline_path.exists() {
+                GasBaseline::load(baseline_path)?
+            } else {
+                GasBaseline::default()
+            };
+            let num_tests = gas_used.len();
+            baseline.gas_used.extend(gas_used);
+            baseline.save(baseline_path)?;
+            eprintln!(
+                "Updated the gas usage of {} tests in {}",
+                num_tests,
+                baseline_path.display()
+            );
+            return Ok(());
+        }
+
+        if self.gas_threshold < 0.0 {
+            return Err(CliError::CommandArgumentError(
+                "--gas-threshold must not be negative".to_string(),
+            ));
+        }
+
+        let mut baseline = GasBaseline::load(baseline_path)?;
+        if filtered {
+            baseline
+                .gas_used
+                .retain(|name, _| gas_used.contains_key(name));
+        }
+        let diff = GasDiff::new(&baseline.gas_used, &gas_used, self.gas_threshold);
+        self.report(&diff)?;
+
+        if diff.has_regressions() && !self.warn_on_gas_regression {
+            Err(CliError::GasRegressionError(format!(
+                "{} tests use more gas than in {}, rerun with --update-gas-baseline \
+                if this is expected",
+                diff.entries_with(GasChange::Regressed).count(),
+                baseline_path.display()
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn report(&self, diff: &GasDiff) -> CliTypedResult<()> {
+        const HEADER: &str = "Unit Test Gas Usage";
+
+        if diff.has_changes() {
+            eprintln!("{}", diff.generate_markdown_report(HEADER));
+        }
+
+        if let Some(report_dir) = &self.gas_report_dir {
+            diff.generate_html_report(report_dir, HEADER.to_string())
+                .map_err(|err| {
+                    CliError::UnexpectedError(format!("Failed to generate gas report: {:#}", err))
+                })?;
+            let markdown_path = report_dir.join("report.md");
+            write_to_file(
+                &markdown_path,
+                &markdown_path.display().to_string(),
+                diff.generate_markdown_report(HEADER).as_bytes(),
+            )?;
+            eprintln!("Gas report saved to {}", report_dir.display());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::types::{CliCommand, MovePackageDir},
+        move_tool::TestPackage,
+    };
+
+    fn gas_used(entries: &[(&str, u64)]) -> BTreeMap<String, u64> {
+        entries
+            .iter()
+            .map(|(name, gas)| (name.to_string(), *gas))
+            .collect()
+    }
+
+    fn options(baseline: &Path, update: bool) -> GasBaselineOptions {
+        GasBaselineOptions {
+            gas_baseline: Some(baseline.to_path_buf()),
+            update_gas_baseline: update,
+            gas_threshold: 5.0,
+            warn_on_gas_regression: false,
+            gas_report_dir: None,
+        }
+    }
+
+    #[test]
+    fn test_update_and_check() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("gas-baseline.json");
+
+        let current = gas_used(&[("0x1::m::test_a", 100), ("0x1::m::test_b", 200)]);
+        options(&path, true)
+            .check_or_update(current.clone(), false)
+            .unwrap();
+        assert_eq!(GasBaseline::load(&path).unwrap().gas_used, current);
+
+        // Changes within the threshold, as well as improvements, pass.
+        options(&path, false)
+            .check_or_update(
+                gas_used(&[("0x1::m::test_a", 104), ("0x1::m::test_b", 100)]),
+                false,
+            )
+            .unwrap();
+
+        // Regressions beyond the threshold fail, unless only warning.
+        let regressed = gas_used(&[("0x1::m::test_a", 106), ("0x1::m::test_b", 200)]);
+        assert!(matches!(
+            options(&path, false).check_or_update(regressed.clone(), false),
+            Err(CliError::GasRegressionError(_))
+        ));
+        let mut warn_only = options(&path, false);
+        warn_only.warn_on_gas_regression = true;
+        warn_only.check_or_update(regressed, false).unwrap();
+    }
+
+    #[test]
+    fn test_filtered_update_keeps_other_tests() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("gas-baseline.json");
+
+        options(&path, true)
+            .check_or_update(
+                gas_used(&[("0x1::m::test_a", 100), ("0x1::m::test_b", 200)]),
+                false,
+            )
+            .unwrap();
+        options(&path, true)
+            .check_or_update(gas_used(&[("0x1::m::test_b", 300)]), true)
+            .unwrap();
+        assert_eq!(
+            GasBaseline::load(&path).unwrap().gas_used,
+            gas_used(&[("0x1::m::test_a", 100), ("0x1::m::test_b", 300)])
+        );
+
+        // Only the tests which ran are compared.
+        options(&path, false)
+            .check_or_update(gas_used(&[("0x1::m::test_b", 300)]), true)
+            .unwrap();
+        assert!(matches!(
+            options(&path, false).check_or_update(gas_used(&[("0x1::m::test_a", 200)]), true),
+            Err(CliError::GasRegressionError(_))
+        ));
+    }
+
+    const PACKAGE_SOURCE: &str = r#"module 0xcafe::m {
+    struct R has key { v: u64 }
+
+    fun sum(n: u64): u64 {
+        let i = 0;
+        let s = 0;
+        while (i < n) {
+            s = s + i;
+            i = i + 1;
+        };
+        s
+    }
+
+    #[test]
+    fun test_sum() {
+        assert!(sum(NUM_ITERATIONS) > 0, 0);
+    }
+
+    #[test]
+    fun test_pack() {
+        let R { v: _ } = R { v: sum(10) };
+    }
+
+    #[test(account = @0xcafe)]
+    fun test_store(account: signer) {
+        move_to(&account, R { v: sum(10) });
+    }
+}
+"#;
+
+    fn write_package(dir: &Path, num_iterations: u64) {
+        std::fs::create_dir_all(dir.join("sources")).unwrap();
+        std::fs::write(
+            dir.join("Move.toml"),
+            "[package]\nname = \"GasBaseline\"\nversion = \"0.0.0\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("sources").join("m.move"),
+            PACKAGE_SOURCE.replace("NUM_ITERATIONS", &num_iterations.to_string()),
+        )
+        .unwrap();
+    }
+
+    async fn run_package_tests(dir: &Path, options: GasBaselineOptions) -> CliTypedResult<()> {
+        TestPackage {
+            filter: None,
+            ignore_compile_warnings: true,
+            move_options: MovePackageDir::new(dir.to_path_buf()),
+            instruction_execution_bound: 100_000,
+            compute_coverage: false,
+            dump_state: false,
+            gas_baseline_options: options,
+        }
+        .execute()
+        .await
+        .map(|_| ())
+    }
+
+    #[tokio::test]
+    async fn test_package_gas_baseline() {
+        let dir = tempfile::tempdir().unwrap();
+        let package_dir = dir.path().join("package");
+        let path = dir.path().join("gas-baseline.json");
+
+        // Record the baseline of a package
+        write_package(&package_dir, 10);
+        run_package_tests(&package_dir, options(&path, true))
+            .await
+            .unwrap();
+        let baseline = GasBaseline::load(&path).unwrap().gas_used;
+        assert_eq!(baseline.keys().collect::<Vec<_>>(), vec![
+            "0xcafe::m::test_pack",
+            "0xcafe::m::test_store",
+            "0xcafe::m::test_sum"
+        ]);
+
+        // The tests are metered with the gas schedule: storing a resource costs more than
+        // only packing it, as it is charged for the instruction and the write
+        let gas_used = |name: &str| baseline[&format!("0xcafe::m::{}", name)];
+        assert!(gas_used("test_pack") > 0);
+        assert!(gas_used("test_store") > gas_used("test_pack"));
+
+        // Metering is deterministic, so the unchanged package passes the check
+        run_package_tests(&package_dir, options(&path, false))
+            .await
+            .unwrap();
+
+        // More loop iterations are a regression
+        write_package(&package_dir, 1_000);
+        assert!(matches!(
+            run_package_tests(&package_dir, options(&path, false)).await,
+            Err(CliError::GasRegressionError(_))
+        ));
+    }
+
+    #[test]
+    fn test_report() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("gas-baseline.json");
+        let report_dir = dir.path().join("report");
+
+        options(&path, true)
+            .check_or_update(gas_used(&[("0x1::m::test_a", 100)]), false)
+            .unwrap();
+        let mut opts = options(&path, false);
+        opts.warn_on_gas_regression = true;
+        opts.gas_report_dir = Some(report_dir.clone());
+        opts.check_or_update(
+            gas_used(&[("0x1::m::test_a", 200), ("0x1::m::test_c", 10)]),
+            false,
+        )
+        .unwrap();
+
+        let markdown = std::fs::read_to_string(report_dir.join("report.md")).unwrap();
+        assert!(markdown.contains("| `0x1::m::test_a` | 100 | 200 | +100.00% | regressed |"));
+        assert!(markdown.contains("| `0x1::m::test_c` | - | 10 | - | added |"));
+        assert!(report_dir.join("index.html").exists());
+    }
+}
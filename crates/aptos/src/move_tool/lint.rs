@@ -0,0 +1,140 @@
+// Copyright (c) Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::{
+    types::{CliCommand, CliError, CliTypedResult, MovePackageDir},
+    utils::parse_map,
+};
+use aptos_framework::extended_checks;
+use async_trait::async_trait;
+use clap::Parser;
+use codespan_reporting::{
+    diagnostic::Severity,
+    term::termcolor::{ColorChoice, StandardStream},
+};
+use move_compiler_v2::env_pipeline::model_ast_lints::{self, LintConfig, LintLevel};
+use move_model::metadata::CompilerVersion;
+use move_package::source_package::layout::SourcePackageLayout;
+use std::{collections::BTreeMap, path::Path, str::FromStr};
+
+/// The section of `Move.toml` configuring the lint levels, e.g.:
+///
+/// ```toml
+/// [lints]
+/// needless_borrow_global_mut = "deny"
+/// while_true = "allow"
+/// ```
+const LINTS_SECTION: &str = "lints";
+
+/// Run the Move linter on a package
+///
+/// Lints can be configured to `allow`, `warn` or `deny` in the `[lints]` section of
+/// `Move.toml`, or on the command line. Individual modules, structs and functions
+/// can allow lints with the `#[lint::allow(<lint_name>, ..)]` attribute.
+#[derive(Parser)]
+pub struct LintPackage {
+    #[clap(flatten)]
+    move_options: MovePackageDir,
+
+    /// Lint levels overriding the ones in `Move.toml`
+    ///
+    /// Example: `needless_borrow_global_mut=deny,while_true=allow`
+    #[clap(long, value_parser = parse_map::<String, String>, default_value = "")]
+    lints: BTreeMap<String, String>,
+
+    /// List all available lints (with their default levels) instead of linting the package
+    #[clap(long)]
+    list: bool,
+}
+
+#[async_trait]
+impl CliCommand<&'static str> for LintPackage {
+    fn command_name(&self) -> &'static str {
+        "LintPackage"
+    }
+
+    async fn execute(self) -> CliTypedResult<&'static str> {
+        if self.list {
+            // The catalogue is written to stderr, as stdout is reserved for the JSON result
+            for lint in model_ast_lints::all_lints() {
+                eprintln!(
+                    "{:<45} {:<6} {}",
+                    lint.name, lint.default_level, lint.description
+                );
+            }
+            return Ok("Success");
+        }
+
+        let package_path = self.move_options.get_package_path()?;
+        let mut levels = read_manifest_lint_levels(&package_path)?;
+        for (name, level) in &self.lints {
+            levels.insert(name.clone(), parse_lint_level(name, level)?);
+        }
+        let config = LintConfig::new(levels)
+            .map_err(|err| CliError::CommandArgumentError(err.to_string()))?;
+
+        let env = aptos_framework::build_model(
+            self.move_options.dev,
+            package_path.as_path(),
+            self.move_options.named_addresses(),
+            None,
+            self.move_options.bytecode_version,
+            Some(CompilerVersion::V2_0),
+            self.move_options.language_version,
+            self.move_options.skip_attribute_checks,
+            extended_checks::get_all_attribute_names().clone(),
+            vec![],
+        )
+        .map_err(|err| CliError::MoveCompilationError(format!("{:#}", err)))?;
+
+        model_ast_lints::run_lints(&env, &config);
+        env.report_diag(
+            &mut StandardStream::stderr(ColorChoice::Auto),
+            Severity::Warning,
+        );
+        if env.has_errors() {
+            Err(CliError::MoveCompilationError(
+                "lints at level `deny` were reported".to_string(),
+            ))
+        } else {
+            Ok("Success")
+        }
+    }
+}
+
+/// Reads the lint levels from the `[lints]` section of the package manifest (if any)
+fn read_manifest_lint_levels(package_path: &Path) -> CliTypedResult<BTreeMap<String, LintLevel>> {
+    let manifest_path = package_path.join(SourcePackageLayout::Manifest.path());
+    let contents = std::fs::read_to_string(&manifest_path)
+        .map_err(|err| CliError::IO(manifest_path.display().to_string(), err))?;
+    let manifest: toml::Value = toml::from_str(&contents)
+        .map_err(|err| CliError::UnableToParse("Move.toml", err.to_string()))?;
+
+    let mut levels = BTreeMap::new();
+    match manifest.get(LINTS_SECTION) {
+        None => {},
+        Some(toml::Value::Table(lints)) => {
+            for (name, level) in lints {
+                let level = level.as_str().ok_or_else(|| {
+                    CliError::UnableToParse(
+                        "Move.toml",
+                        format!("level of lint `{}` must be a string", name),
+                    )
+                })?;
+                levels.insert(name.clone(), parse_lint_level(name, level)?);
+            }
+        },
+        Some(_) => {
+            return Err(CliError::UnableToParse(
+                "Move.toml",
+                format!("`[{}]` must be a table", LINTS_SECTION),
+            ))
+        },
+    }
+    Ok(levels)
+}
+
+fn parse_lint_level(name: &str, level: &str) -> CliTypedResult<LintLevel> {
+    LintLevel::from_str(level)
+        .map_err(|err| CliError::CommandArgumentError(format!("lint `{}`: {}", name, err)))
+}
@@ -22,6 +22,8 @@ use crate::{
         bytecode::{Decompile, Disassemble},
         coverage::SummaryCoverage,
         fmt::Fmt,
+        gas_baseline::{AptosGasMeterFactory, GasBaselineOptions},
+        lint::LintPackage,
         manifest::{Dependency, ManifestNamedAddress, MovePackageManifest, PackageInfo},
     },
     CliCommand, CliResult,
@@ -55,7 +57,7 @@ use move_package::{
     source_package::{layout::SourcePackageLayout, std_lib::StdVersion},
     BuildConfig, CompilerConfig,
 };
-use move_unit_test::UnitTestingConfig;
+use move_unit_test::{test_runner::CostTableGasMeterFactory, UnitTestingConfig};
 pub use package_hooks::*;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -71,11 +73,16 @@ use url::Url;
 
 mod aptos_debug_natives;
 mod bytecode;
+mod check_upgrade;
 pub mod coverage;
 mod fmt;
+pub mod gas_baseline;
+mod lint;
 mod manifest;
+mod mutation;
 pub mod package_hooks;
 mod show;
+mod sim;
 pub mod stored_package;
 
 const HELLO_BLOCKCHAIN_EXAMPLE: &str = include_str!(
@@ -90,6 +97,7 @@ const HELLO_BLOCKCHAIN_EXAMPLE: &str = include_str!(
 #[derive(Subcommand)]
 pub enum MoveTool {
     BuildPublishPayload(BuildPublishPayload),
+    CheckUpgrade(check_upgrade::CheckUpgrade),
     Clean(CleanPackage),
     #[clap(alias = "build")]
     Compile(CompilePackage),
@@ -108,7 +116,9 @@ pub enum MoveTool {
     Document(DocumentPackage),
     Download(DownloadPackage),
     Init(InitPackage),
+    Lint(LintPackage),
     List(ListPackage),
+    Mutate(mutation::MutationTest),
     Prove(ProvePackage),
     #[clap(alias = "deploy")]
     Publish(PublishPackage),
@@ -116,6 +126,8 @@ pub enum MoveTool {
     RunScript(RunScript),
     #[clap(subcommand, hide = true)]
     Show(show::ShowTool),
+    #[clap(subcommand)]
+    Sim(sim::SimTool),
     Test(TestPackage),
     VerifyPackage(VerifyPackage),
     View(ViewFunction),
@@ -127,6 +139,7 @@ impl MoveTool {
     pub async fn execute(self) -> CliResult {
         match self {
             MoveTool::BuildPublishPayload(tool) => tool.execute_serialized().await,
+            MoveTool::CheckUpgrade(tool) => tool.execute_serialized().await,
             MoveTool::Clean(tool) => tool.execute_serialized().await,
             MoveTool::Compile(tool) => tool.execute_serialized().await,
             MoveTool::CompileScript(tool) => tool.execute_serialized().await,
@@ -145,12 +158,15 @@ impl MoveTool {
             MoveTool::Document(tool) => tool.execute_serialized().await,
             MoveTool::Download(tool) => tool.execute_serialized().await,
             MoveTool::Init(tool) => tool.execute_serialized_success().await,
+            MoveTool::Lint(tool) => tool.execute_serialized().await,
             MoveTool::List(tool) => tool.execute_serialized().await,
+            MoveTool::Mutate(tool) => tool.execute_serialized().await,
             MoveTool::Prove(tool) => tool.execute_serialized().await,
             MoveTool::Publish(tool) => tool.execute_serialized().await,
             MoveTool::Run(tool) => tool.execute_serialized().await,
             MoveTool::RunScript(tool) => tool.execute_serialized().await,
             MoveTool::Show(tool) => tool.execute_serialized().await,
+            MoveTool::Sim(tool) => tool.execute().await,
             MoveTool::Test(tool) => tool.execute_serialized().await,
             MoveTool::VerifyPackage(tool) => tool.execute_serialized().await,
             MoveTool::View(tool) => tool.execute_serialized().await,
@@ -522,6 +538,9 @@ pub struct TestPackage {
     /// Dump storage state on failure.
     #[clap(long = "dump")]
     pub dump_state: bool,
+
+    #[clap(flatten)]
+    pub(crate) gas_baseline_options: GasBaselineOptions,
 }
 
 #[async_trait]
@@ -550,28 +569,50 @@ impl CliCommand<&'static str> for TestPackage {
         };
 
         let path = self.move_options.get_package_path()?;
-        let result = move_cli::base::test::run_move_unit_tests(
-            path.as_path(),
-            config.clone(),
-            UnitTestingConfig {
-                filter: self.filter.clone(),
-                report_stacktrace_on_abort: true,
-                report_storage_on_error: self.dump_state,
-                ignore_compile_warnings: self.ignore_compile_warnings,
-                ..UnitTestingConfig::default_with_bound(None)
-            },
-            // TODO(Gas): we may want to switch to non-zero costs in the future
-            aptos_debug_natives::aptos_debug_natives(
-                NativeGasParameters::zeros(),
-                MiscGasParameters::zeros(),
-            ),
-            aptos_test_feature_flags_genesis(),
-            None,
-            self.compute_coverage,
-            &mut std::io::stdout(),
-        )
+        let unit_test_config = UnitTestingConfig {
+            filter: self.filter.clone(),
+            report_stacktrace_on_abort: true,
+            report_storage_on_error: self.dump_state,
+            ignore_compile_warnings: self.ignore_compile_warnings,
+            ..UnitTestingConfig::default_with_bound(None)
+        };
+        // Tests are only metered with the Aptos gas schedule when checking against a gas baseline
+        let (result, gas_used) = if self.gas_baseline_options.is_enabled() {
+            let gas_meter_factory = AptosGasMeterFactory::new();
+            move_cli::base::test::run_move_unit_tests_with_gas_meter(
+                path.as_path(),
+                config.clone(),
+                unit_test_config,
+                gas_meter_factory.natives(),
+                aptos_test_feature_flags_genesis(),
+                gas_meter_factory,
+                self.compute_coverage,
+                &mut std::io::stdout(),
+            )
+        } else {
+            move_cli::base::test::run_move_unit_tests_with_gas_meter(
+                path.as_path(),
+                config.clone(),
+                unit_test_config,
+                // TODO(Gas): we may want to switch to non-zero costs in the future
+                aptos_debug_natives::aptos_debug_natives(
+                    NativeGasParameters::zeros(),
+                    MiscGasParameters::zeros(),
+                ),
+                aptos_test_feature_flags_genesis(),
+                CostTableGasMeterFactory::new(None),
+                self.compute_coverage,
+                &mut std::io::stdout(),
+            )
+        }
         .map_err(|err| CliError::UnexpectedError(format!("Failed to run tests: {:#}", err)))?;
 
+        // Compare the gas used by the tests against the baseline, if one is given
+        if result == UnitTestResult::Success {
+            self.gas_baseline_options
+                .check_or_update(gas_used, self.filter.is_some())?;
+        }
+
         // Print coverage summary if --coverage is set
         if self.compute_coverage {
             // TODO: config seems to be dead here.
@@ -1787,6 +1828,22 @@ pub struct Replay {
     pub(crate) skip_comparison: bool,
 }
 
+impl ReplayNetworkSelection {
+    /// Returns the REST endpoint of the network
+    pub(crate) fn rest_url(&self) -> CliTypedResult<Url> {
+        use ReplayNetworkSelection::*;
+
+        let rest_endpoint = match self {
+            Mainnet => "https://fullnode.mainnet.aptoslabs.com",
+            Testnet => "https://fullnode.testnet.aptoslabs.com",
+            Devnet => "https://fullnode.devnet.aptoslabs.com",
+            RestEndpoint(url) => url,
+        };
+        Url::parse(rest_endpoint)
+            .map_err(|_err| CliError::UnableToParse("url", rest_endpoint.to_string()))
+    }
+}
+
 impl FromStr for ReplayNetworkSelection {
     type Err = CliError;
 
@@ -1807,25 +1864,13 @@ impl CliCommand<TransactionSummary> for Replay {
     }
 
     async fn execute(self) -> CliTypedResult<TransactionSummary> {
-        use ReplayNetworkSelection::*;
-
         if self.profile_gas && self.benchmark {
             return Err(CliError::UnexpectedError(
                 "Cannot perform benchmarking and gas profiling at the same time.".to_string(),
             ));
         }
 
-        let rest_endpoint = match &self.network {
-            Mainnet => "https://fullnode.mainnet.aptoslabs.com",
-            Testnet => "https://fullnode.testnet.aptoslabs.com",
-            Devnet => "https://fullnode.devnet.aptoslabs.com",
-            RestEndpoint(url) => url,
-        };
-
-        let debugger = AptosDebugger::rest_client(Client::new(
-            Url::parse(rest_endpoint)
-                .map_err(|_err| CliError::UnableToParse("url", rest_endpoint.to_string()))?,
-        ))?;
+        let debugger = AptosDebugger::rest_client(Client::new(self.network.rest_url()?))?;
 
         // Fetch the transaction to replay.
         let (txn, txn_info) = debugger
@@ -0,0 +1,663 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    common::types::{CliCommand, CliError, CliTypedResult, MovePackageDir},
+    move_tool::aptos_debug_natives,
+};
+use aptos_framework::extended_checks;
+use aptos_gas_schedule::{MiscGasParameters, NativeGasParameters};
+use aptos_types::on_chain_config::aptos_test_feature_flags_genesis;
+use async_trait::async_trait;
+use clap::Parser;
+use move_cli::base::test::UnitTestResult;
+use move_model::{
+    ast::{ExpData, Operation, Value},
+    metadata::CompilerVersion,
+    model::{GlobalEnv, NodeId},
+};
+use move_package::{BuildConfig, CompilerConfig};
+use move_unit_test::UnitTestingConfig;
+use serde::Serialize;
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+/// The name of the directory packages are built into, which is not copied with the package
+const BUILD_DIR: &str = "build";
+
+/// Run mutation testing on a package
+///
+/// Small bugs (mutants) are introduced one at a time in the source code of the package:
+/// flipped comparisons, removed `assert!`s, number literals off by one and swapped arithmetic
+/// operators. The unit tests are run against every mutant, and the mutants for which all tests
+/// still pass are reported: they point at behavior which is not checked by the tests.
+///
+/// Only code which is not test code is mutated. The package is copied next to its directory,
+/// so that relative paths to local dependencies still resolve, and the copy is removed
+/// afterwards.
+#[derive(Parser)]
+pub struct MutationTest {
+    /// A filter string to determine which unit tests to run
+    #[clap(long, short)]
+    pub filter: Option<String>,
+
+    /// Only mutate the source files whose path contains this string
+    #[clap(long)]
+    pub mutate_filter: Option<String>,
+
+    /// List the mutants without running the tests against them
+    #[clap(long)]
+    pub list: bool,
+
+    /// The maximum number of instructions that can be executed by a test
+    ///
+    /// Mutants may cause infinite loops, which are stopped by this bound.
+    #[clap(long = "instructions", default_value_t = 100000)]
+    pub instruction_execution_bound: u64,
+
+    #[clap(flatten)]
+    pub(crate) move_options: MovePackageDir,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MutationKind {
+    /// `<` and `<=`, `>` and `>=`, `==` and `!=` replaced by each other
+    FlippedComparison,
+    /// `+` and `-`, `*` and `/` replaced by each other
+    SwappedArithmetic,
+    /// A number literal incremented or decremented by one
+    OffByOneLiteral,
+    /// An `assert!` removed
+    ///
+    /// Only `assert!`s written in the mutated function are found: an `assert!` originating from
+    /// the expansion of a macro (e.g. the body of a `macro fun`) is located at the macro call,
+    /// whose source text is not the `assert!`, so it is not removed.
+    RemovedAssert,
+}
+
+/// A mutation of a source file of the package
+#[derive(Clone, Debug, Serialize)]
+pub struct Mutant {
+    /// The source file, relative to the package directory
+    pub file: PathBuf,
+    /// The 1-based line of the mutated code
+    pub line: usize,
+    /// The 1-based column of the mutated code
+    pub column: usize,
+    pub kind: MutationKind,
+    pub original: String,
+    pub replacement: String,
+    /// The byte range of the mutated code in the source file
+    #[serde(skip)]
+    start: usize,
+    #[serde(skip)]
+    end: usize,
+}
+
+impl Mutant {
+    /// Applies the mutation to the source of its file.
+    fn apply(&self, source: &str) -> CliTypedResult<String> {
+        if source.get(self.start..self.end) != Some(self.original.as_str()) {
+            return Err(CliError::UnexpectedError(format!(
+                "{}: source changed while running mutation testing",
+                self
+            )));
+        }
+        Ok(format!(
+            "{}{}{}",
+            &source[..self.start],
+            self.replacement,
+            &source[self.end..]
+        ))
+    }
+}
+
+impl fmt::Display for Mutant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: replaced `{}` with `{}`",
+            self.file.display(),
+            self.line,
+            self.column,
+            self.original,
+            self.replacement
+        )
+    }
+}
+
+/// What happened to a mutant when running the tests against it
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum MutantOutcome {
+    /// At least one test failed
+    Killed,
+    /// All tests passed
+    Survived,
+    /// The mutated package did not compile
+    NotCompiled,
+}
+
+#[async_trait]
+impl CliCommand<Vec<Mutant>> for MutationTest {
+    fn command_name(&self) -> &'static str {
+        "MutationTest"
+    }
+
+    async fn execute(self) -> CliTypedResult<Vec<Mutant>> {
+        let package_path = self.move_options.get_package_path()?;
+        let env = aptos_framework::build_model(
+            self.move_options.dev,
+            package_path.as_path(),
+            self.move_options.named_addresses(),
+            self.mutate_filter.clone(),
+            self.move_options.bytecode_version,
+            Some(CompilerVersion::V2_0),
+            self.move_options.language_version,
+            self.move_options.skip_attribute_checks,
+            extended_checks::get_all_attribute_names().clone(),
+            vec![],
+        )
+        .map_err(|err| CliError::MoveCompilationError(format!("{:#}", err)))?;
+        let mutants = find_mutants(&env, &package_path)?;
+        if self.list {
+            return Ok(mutants);
+        }
+
+        let parent = package_path
+            .canonicalize()
+            .map_err(|err| CliError::IO(package_path.display().to_string(), err))?
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(std::env::temp_dir);
+        let copy_dir = tempfile::Builder::new()
+            .prefix(".mutants-")
+            .tempdir_in(&parent)
+            .map_err(|err| CliError::IO(parent.display().to_string(), err))?;
+        copy_package(&package_path, copy_dir.path())?;
+
+        let mut build_config = self.build_config();
+        if self.run_tests(copy_dir.path(), &build_config)? != UnitTestResult::Success {
+            eprintln!("The tests must pass before running them against mutants");
+            return Err(CliError::MoveTestError);
+        }
+        // Dependencies were fetched when running the tests against the unmutated package
+        build_config.skip_fetch_latest_git_deps = true;
+
+        let mut surviving = vec![];
+        let mut killed = 0;
+        for (index, mutant) in mutants.iter().enumerate() {
+            let outcome = self.test_mutant(copy_dir.path(), &build_config, mutant)?;
+            eprintln!(
+                "[{}/{}] {}: {}",
+                index + 1,
+                mutants.len(),
+                mutant,
+                match outcome {
+                    MutantOutcome::Killed => "killed",
+                    MutantOutcome::Survived => "SURVIVED",
+                    MutantOutcome::NotCompiled => "not compiled",
+                }
+            );
+            match outcome {
+                MutantOutcome::Killed => killed += 1,
+                MutantOutcome::Survived => surviving.push(mutant.clone()),
+                MutantOutcome::NotCompiled => {},
+            }
+        }
+        eprintln!(
+            "{} mutants: {} killed, {} survived, {} did not compile",
+            mutants.len(),
+            killed,
+            surviving.len(),
+            mutants.len() - killed - surviving.len()
+        );
+        Ok(surviving)
+    }
+}
+
+impl MutationTest {
+    fn build_config(&self) -> BuildConfig {
+        BuildConfig {
+            dev_mode: self.move_options.dev,
+            additional_named_addresses: self.move_options.named_addresses(),
+            test_mode: true,
+            skip_fetch_latest_git_deps: self.move_options.skip_fetch_latest_git_deps,
+            compiler_config: CompilerConfig {
+                known_attributes: extended_checks::get_all_attribute_names().clone(),
+                skip_attribute_checks: self.move_options.skip_attribute_checks,
+                compiler_version: Some(CompilerVersion::V2_0),
+                language_version: self.move_options.language_version,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Runs the unit tests against the mutant, in the copy of the package, and restores the
+    /// mutated source file afterwards.
+    fn test_mutant(
+        &self,
+        package_path: &Path,
+        build_config: &BuildConfig,
+        mutant: &Mutant,
+    ) -> CliTypedResult<MutantOutcome> {
+        let path = package_path.join(&mutant.file);
+        let source = fs::read_to_string(&path)
+            .map_err(|err| CliError::IO(path.display().to_string(), err))?;
+        fs::write(&path, mutant.apply(&source)?)
+            .map_err(|err| CliError::IO(path.display().to_string(), err))?;
+
+        // The test runner exits the process on compilation errors, so check them first
+        let outcome = if build_config
+            .clone()
+            .compile_package_no_exit(package_path, &mut Vec::new())
+            .is_err()
+        {
+            Ok(MutantOutcome::NotCompiled)
+        } else {
+            self.run_tests(package_path, build_config)
+                .map(|result| match result {
+                    UnitTestResult::Success => MutantOutcome::Survived,
+                    UnitTestResult::Failure => MutantOutcome::Killed,
+                })
+        };
+
+        fs::write(&path, source).map_err(|err| CliError::IO(path.display().to_string(), err))?;
+        outcome
+    }
+
+    fn run_tests(
+        &self,
+        package_path: &Path,
+        build_config: &BuildConfig,
+    ) -> CliTypedResult<UnitTestResult> {
+        move_cli::base::test::run_move_unit_tests(
+            package_path,
+            build_config.clone(),
+            UnitTestingConfig {
+                filter: self.filter.clone(),
+                ignore_compile_warnings: true,
+                ..UnitTestingConfig::default_with_bound(Some(self.instruction_execution_bound))
+            },
+            aptos_debug_natives::aptos_debug_natives(
+                NativeGasParameters::zeros(),
+                MiscGasParameters::zeros(),
+            ),
+            aptos_test_feature_flags_genesis(),
+            None,
+            false,
+            &mut std::io::sink(),
+        )
+        .map_err(|err| CliError::UnexpectedError(format!("Failed to run tests: {:#}", err)))
+    }
+}
+
+/// Finds the mutants of the code of the primary target modules which is not test code, sorted
+/// by location.
+fn find_mutants(env: &GlobalEnv, package_path: &Path) -> CliTypedResult<Vec<Mutant>> {
+    let mut sites = vec![];
+    for module in env
+        .get_modules()
+        .filter(|module| module.is_primary_target())
+    {
+        for function in module.get_functions() {
+            let Some(def) = function.get_def() else {
+                continue;
+            };
+            // Code in spec blocks is not executed by the tests
+            let mut spec_depth = 0;
+            def.visit_pre_post(&mut |post, e| {
+                match (post, e) {
+                    (false, ExpData::SpecBlock(..)) => spec_depth += 1,
+                    (true, ExpData::SpecBlock(..)) => spec_depth -= 1,
+                    (false, _) if spec_depth == 0 => sites.extend(mutation_sites(env, e)),
+                    _ => {},
+                }
+                true
+            });
+        }
+    }
+    // Inline functions are expanded into their callers, so sites can be found more than once
+    sites.sort_by_key(|site| {
+        (
+            site.file_idx,
+            site.start,
+            site.end,
+            site.replacement.clone(),
+        )
+    });
+    sites.dedup_by(|a, b| {
+        (a.file_idx, a.start, a.end, &a.replacement) == (b.file_idx, b.start, b.end, &b.replacement)
+    });
+
+    let package_path = package_path
+        .canonicalize()
+        .map_err(|err| CliError::IO(package_path.display().to_string(), err))?;
+    let mut mutants = vec![];
+    for site in sites {
+        let file_id = env.file_idx_to_id(site.file_idx);
+        let file_path = PathBuf::from(env.get_file(file_id));
+        let Some(file) = file_path
+            .canonicalize()
+            .ok()
+            .and_then(|path| Some(path.strip_prefix(&package_path).ok()?.to_path_buf()))
+        else {
+            continue;
+        };
+        let source = env.get_file_source(file_id);
+        let (line, column) = line_and_column(source, site.start);
+        mutants.push(Mutant {
+            file,
+            line,
+            column,
+            kind: site.kind,
+            original: source[site.start..site.end].to_string(),
+            replacement: site.replacement,
+            start: site.start,
+            end: site.end,
+        });
+    }
+    Ok(mutants)
+}
+
+/// A location in the source where a mutation can be applied
+struct MutationSite {
+    /// The index of the source file in the model
+    file_idx: u16,
+    start: usize,
+    end: usize,
+    kind: MutationKind,
+    replacement: String,
+}
+
+/// Returns the mutations of the expression itself (not of its subexpressions).
+///
+/// Mutations are found in the model, but applied to the source text, so only expressions whose
+/// source text is the expected one are mutated; e.g. no mutations are found in expressions
+/// resulting from the expansion of other expressions, or from constant folding.
+fn mutation_sites(env: &GlobalEnv, e: &ExpData) -> Vec<MutationSite> {
+    let site = |node_id: NodeId, kind: MutationKind, replacement: String| {
+        let loc = env.get_node_loc(node_id);
+        MutationSite {
+            file_idx: env.file_id_to_idx(loc.file_id()),
+            start: loc.span().start().to_usize(),
+            end: loc.span().end().to_usize(),
+            kind,
+            replacement,
+        }
+    };
+    match e {
+        ExpData::Call(_, op, args) if args.len() == 2 => {
+            let Some((kind, operator, replacement)) = operator_mutation(op) else {
+                return vec![];
+            };
+            let lhs = env.get_node_loc(args[0].node_id());
+            let rhs = env.get_node_loc(args[1].node_id());
+            if lhs.file_id() != rhs.file_id() || lhs.span().end() > rhs.span().start() {
+                return vec![];
+            }
+            let start = lhs.span().end().to_usize();
+            let source = env.get_file_source(lhs.file_id());
+            let between = &source[start..rhs.span().start().to_usize()];
+            if between.trim_matches(|c: char| c.is_whitespace() || c == '(' || c == ')') != operator
+            {
+                return vec![];
+            }
+            let start = start + between.find(operator).expect("operator in source");
+            vec![MutationSite {
+                file_idx: env.file_id_to_idx(lhs.file_id()),
+                start,
+                end: start + operator.len(),
+                kind,
+                replacement: replacement.to_string(),
+            }]
+        },
+        ExpData::Value(id, Value::Number(value)) => {
+            let text = env.get_source(&env.get_node_loc(*id)).unwrap_or_default();
+            match parse_number_literal(text) {
+                Some((literal, _, _)) if literal.to_string() == value.to_string() => {
+                    off_by_one_literals(text)
+                        .into_iter()
+                        .map(|replacement| site(*id, MutationKind::OffByOneLiteral, replacement))
+                        .collect()
+                },
+                _ => vec![],
+            }
+        },
+        // `assert!(cond, code)` is expanded to `if (cond) () else abort code`. Asserts expanded
+        // from macros are located at the macro call, so they are not matched.
+        ExpData::IfElse(id, ..) => {
+            let text = env.get_source(&env.get_node_loc(*id)).unwrap_or_default();
+            if text.starts_with("assert!") {
+                vec![site(*id, MutationKind::RemovedAssert, "()".to_string())]
+            } else {
+                vec![]
+            }
+        },
+        _ => vec![],
+    }
+}
+
+/// Returns the kind of mutation of a binary operation, the operator and its replacement.
+fn operator_mutation(op: &Operation) -> Option<(MutationKind, &'static str, &'static str)> {
+    use MutationKind::{FlippedComparison, SwappedArithmetic};
+    Some(match op {
+        Operation::Lt => (FlippedComparison, "<", "<="),
+        Operation::Le => (FlippedComparison, "<=", "<"),
+        Operation::Gt => (FlippedComparison, ">", ">="),
+        Operation::Ge => (FlippedComparison, ">=", ">"),
+        Operation::Eq => (FlippedComparison, "==", "!="),
+        Operation::Neq => (FlippedComparison, "!=", "=="),
+        Operation::Add => (SwappedArithmetic, "+", "-"),
+        Operation::Sub => (SwappedArithmetic, "-", "+"),
+        Operation::Mul => (SwappedArithmetic, "*", "/"),
+        Operation::Div => (SwappedArithmetic, "/", "*"),
+        _ => return None,
+    })
+}
+
+/// Parses a number literal, e.g. `10`, `0xff` or `1_000u64`, into its value, whether it is
+/// hexadecimal, and its type suffix. Values which do not fit in a `u128` are not parsed.
+fn parse_number_literal(text: &str) -> Option<(u128, bool, &str)> {
+    let (digits, suffix) = text.split_at(text.find('u').unwrap_or(text.len()));
+    if !matches!(suffix, "" | "u8" | "u16" | "u32" | "u64" | "u128" | "u256") {
+        return None;
+    }
+    let digits = digits.replace('_', "");
+    let (value, hex) = match digits.strip_prefix("0x") {
+        Some(hex_digits) => (u128::from_str_radix(hex_digits, 16), true),
+        None => (digits.parse::<u128>(), false),
+    };
+    Some((value.ok()?, hex, suffix))
+}
+
+/// Returns the number literal incremented and decremented by one, in the same format.
+fn off_by_one_literals(text: &str) -> Vec<String> {
+    let Some((value, hex, suffix)) = parse_number_literal(text) else {
+        return vec![];
+    };
+    [value.checked_add(1), value.checked_sub(1)]
+        .into_iter()
+        .flatten()
+        .map(|value| {
+            if hex {
+                format!("0x{:x}{}", value, suffix)
+            } else {
+                format!("{}{}", value, suffix)
+            }
+        })
+        .collect()
+}
+
+/// Returns the 1-based line and column of the byte offset in the source.
+fn line_and_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset.min(source.len())];
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+/// Copies the package directory, without its build directory and hidden files.
+fn copy_package(from: &Path, to: &Path) -> CliTypedResult<()> {
+    let to_cli_err = |path: &Path, err| CliError::IO(path.display().to_string(), err);
+    for entry in fs::read_dir(from).map_err(|err| to_cli_err(from, err))? {
+        let entry = entry.map_err(|err| to_cli_err(from, err))?;
+        let name = entry.file_name();
+        if name == BUILD_DIR || name.to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let (from, to) = (entry.path(), to.join(&name));
+        if entry
+            .file_type()
+            .map_err(|err| to_cli_err(&from, err))?
+            .is_dir()
+        {
+            fs::create_dir(&to).map_err(|err| to_cli_err(&to, err))?;
+            copy_package(&from, &to)?;
+        } else {
+            fs::copy(&from, &to).map_err(|err| to_cli_err(&from, err))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_number_literal() {
+        assert_eq!(parse_number_literal("10"), Some((10, false, "")));
+        assert_eq!(parse_number_literal("0xff"), Some((255, true, "")));
+        assert_eq!(parse_number_literal("1_000u64"), Some((1000, false, "u64")));
+        assert_eq!(parse_number_literal("0x10u8"), Some((16, true, "u8")));
+        assert_eq!(parse_number_literal("MAX"), None);
+        assert_eq!(parse_number_literal("1 + 2"), None);
+        assert_eq!(parse_number_literal("1u7"), None);
+    }
+
+    #[test]
+    fn test_off_by_one_literals() {
+        assert_eq!(off_by_one_literals("10"), vec!["11", "9"]);
+        assert_eq!(off_by_one_literals("0u8"), vec!["1u8"]);
+        assert_eq!(off_by_one_literals("0xffu64"), vec!["0x100u64", "0xfeu64"]);
+    }
+
+    #[test]
+    fn test_mutant_apply() {
+        let source = "if (x < 10) y else z";
+        let mutant = Mutant {
+            file: PathBuf::from("sources/m.move"),
+            line: 1,
+            column: 7,
+            kind: MutationKind::FlippedComparison,
+            original: "<".to_string(),
+            replacement: "<=".to_string(),
+            start: 6,
+            end: 7,
+        };
+        assert_eq!(mutant.apply(source).unwrap(), "if (x <= 10) y else z");
+        assert!(mutant.apply("if (x > 10) y else z").is_err());
+    }
+
+    const PACKAGE_SOURCE: &str = r#"module 0xcafe::m {
+    public fun is_adult(age: u64): bool {
+        age >= 18
+    }
+
+    public fun double(x: u64): u64 {
+        assert!(x < 1000, 1);
+        x * 2
+    }
+
+    #[test]
+    fun test_is_adult() {
+        assert!(is_adult(18), 0);
+        assert!(!is_adult(17), 0);
+    }
+}
+"#;
+
+    fn mutation_test(dir: &Path, list: bool) -> MutationTest {
+        MutationTest {
+            filter: None,
+            mutate_filter: None,
+            list,
+            instruction_execution_bound: 100_000,
+            move_options: MovePackageDir::new(dir.to_path_buf()),
+        }
+    }
+
+    fn locations(mutants: &[Mutant]) -> Vec<(usize, &str, &str)> {
+        mutants
+            .iter()
+            .map(|mutant| {
+                (
+                    mutant.line,
+                    mutant.original.as_str(),
+                    mutant.replacement.as_str(),
+                )
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_package_mutants() {
+        let dir = tempfile::tempdir().unwrap();
+        let package_dir = dir.path().join("package");
+        fs::create_dir_all(package_dir.join("sources")).unwrap();
+        fs::write(
+            package_dir.join("Move.toml"),
+            "[package]\nname = \"Mutation\"\nversion = \"0.0.0\"\n",
+        )
+        .unwrap();
+        fs::write(package_dir.join("sources").join("m.move"), PACKAGE_SOURCE).unwrap();
+
+        // The tested function has mutants, but only the untested function has surviving ones
+        let mutants = mutation_test(&package_dir, true).execute().await.unwrap();
+        let mutants = locations(&mutants);
+        for mutant in [(3, ">=", ">"), (3, "18", "19"), (3, "18", "17")] {
+            assert!(mutants.contains(&mutant), "{:?} not found", mutant);
+        }
+        let surviving = mutation_test(&package_dir, false).execute().await.unwrap();
+        let surviving_kinds: Vec<_> = surviving.iter().map(|mutant| mutant.kind).collect();
+        let surviving = locations(&surviving);
+        assert!(
+            surviving
+                .iter()
+                .all(|(line, _, _)| *line == 7 || *line == 8),
+            "{:?}",
+            surviving
+        );
+        for mutant in [
+            (7, "<", "<="),
+            (7, "1000", "1001"),
+            (7, "1000", "999"),
+            (8, "*", "/"),
+            (8, "2", "3"),
+            (8, "2", "1"),
+        ] {
+            assert!(surviving.contains(&mutant), "{:?} did not survive", mutant);
+        }
+        assert!(surviving_kinds.contains(&MutationKind::RemovedAssert));
+
+        // The package is restored and its copy removed
+        assert_eq!(
+            fs::read_to_string(package_dir.join("sources").join("m.move")).unwrap(),
+            PACKAGE_SOURCE
+        );
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_line_and_column() {
+        let source = "module 0x1::m {\n    fun f() {}\n}\n";
+        assert_eq!(line_and_column(source, 0), (1, 1));
+        assert_eq!(line_and_column(source, 20), (2, 5));
+    }
+}
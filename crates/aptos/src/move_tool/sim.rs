@@ -0,0 +1,180 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    common::{
+        simulation_session::{Session, SessionConfig, SessionOperation},
+        types::{load_account_arg, CliCommand, CliResult, CliTypedResult},
+    },
+    move_tool::ReplayNetworkSelection,
+};
+use async_trait::async_trait;
+use clap::{Parser, Subcommand};
+use move_core_types::{account_address::AccountAddress, language_storage::StructTag};
+use std::path::PathBuf;
+
+/// Simulate sequences of transactions against a local fork of a network
+///
+/// A session forks the state of a network at a given version. Transactions simulated
+/// with `--session <PATH>` (e.g., `aptos move publish --session <PATH>`) are applied on
+/// top of each other in the session, without being submitted to the network.
+#[derive(Subcommand)]
+pub enum SimTool {
+    Init(InitSession),
+    Fund(FundAccount),
+    ViewResource(ViewResource),
+    Balance(ViewBalance),
+    History(ShowHistory),
+}
+
+impl SimTool {
+    pub async fn execute(self) -> CliResult {
+        match self {
+            Self::Init(tool) => tool.execute_serialized().await,
+            Self::Fund(tool) => tool.execute_serialized().await,
+            Self::ViewResource(tool) => tool.execute_serialized().await,
+            Self::Balance(tool) => tool.execute_serialized().await,
+            Self::History(tool) => tool.execute_serialized().await,
+        }
+    }
+}
+
+#[derive(Debug, Parser)]
+pub struct SessionPath {
+    /// Directory of the simulation session
+    #[clap(long, value_parser)]
+    pub(crate) session: PathBuf,
+}
+
+/// Create a simulation session by forking a network
+#[derive(Debug, Parser)]
+pub struct InitSession {
+    #[clap(flatten)]
+    session_path: SessionPath,
+
+    /// The network to fork
+    ///
+    /// Possible values:
+    ///     mainnet, testnet, devnet, <REST_ENDPOINT_URL>
+    #[clap(long)]
+    network: ReplayNetworkSelection,
+
+    /// The version to fork the network at, defaults to the latest version
+    #[clap(long)]
+    version: Option<u64>,
+}
+
+#[async_trait]
+impl CliCommand<SessionConfig> for InitSession {
+    fn command_name(&self) -> &'static str {
+        "InitSimulationSession"
+    }
+
+    async fn execute(self) -> CliTypedResult<SessionConfig> {
+        let session = Session::init(
+            &self.session_path.session,
+            self.network.rest_url()?,
+            self.version,
+        )
+        .await?;
+        Ok(session.config().clone())
+    }
+}
+
+/// Inject APT into an account of a simulation session
+///
+/// The APT balance of the account is increased directly, without a transaction.
+#[derive(Debug, Parser)]
+pub struct FundAccount {
+    #[clap(flatten)]
+    session_path: SessionPath,
+
+    /// Address of the account to fund
+    #[clap(long, value_parser = load_account_arg)]
+    account: AccountAddress,
+
+    /// Number of Octas to add to the balance of the account
+    #[clap(long)]
+    amount: u64,
+}
+
+#[async_trait]
+impl CliCommand<u64> for FundAccount {
+    fn command_name(&self) -> &'static str {
+        "FundSimulationAccount"
+    }
+
+    async fn execute(self) -> CliTypedResult<u64> {
+        let mut session = Session::load(&self.session_path.session)?;
+        session.fund_account(self.account, self.amount)
+    }
+}
+
+/// Show a resource of an account in a simulation session
+#[derive(Debug, Parser)]
+pub struct ViewResource {
+    #[clap(flatten)]
+    session_path: SessionPath,
+
+    /// Address of the account holding the resource
+    #[clap(long, value_parser = load_account_arg)]
+    account: AccountAddress,
+
+    /// Type of the resource, e.g. `0x1::account::Account`
+    #[clap(long)]
+    resource: StructTag,
+}
+
+#[async_trait]
+impl CliCommand<Option<serde_json::Value>> for ViewResource {
+    fn command_name(&self) -> &'static str {
+        "ViewSimulationResource"
+    }
+
+    async fn execute(self) -> CliTypedResult<Option<serde_json::Value>> {
+        let session = Session::load(&self.session_path.session)?;
+        session.view_resource(&self.account, &self.resource)
+    }
+}
+
+/// Show the APT balance of an account in a simulation session, in Octas
+#[derive(Debug, Parser)]
+pub struct ViewBalance {
+    #[clap(flatten)]
+    session_path: SessionPath,
+
+    /// Address of the account
+    #[clap(long, value_parser = load_account_arg)]
+    account: AccountAddress,
+}
+
+#[async_trait]
+impl CliCommand<u64> for ViewBalance {
+    fn command_name(&self) -> &'static str {
+        "ViewSimulationBalance"
+    }
+
+    async fn execute(self) -> CliTypedResult<u64> {
+        let session = Session::load(&self.session_path.session)?;
+        session.apt_balance(&self.account)
+    }
+}
+
+/// Show the operations applied to a simulation session so far
+#[derive(Debug, Parser)]
+pub struct ShowHistory {
+    #[clap(flatten)]
+    session_path: SessionPath,
+}
+
+#[async_trait]
+impl CliCommand<Vec<SessionOperation>> for ShowHistory {
+    fn command_name(&self) -> &'static str {
+        "ShowSimulationHistory"
+    }
+
+    async fn execute(self) -> CliTypedResult<Vec<SessionOperation>> {
+        let session = Session::load(&self.session_path.session)?;
+        Ok(session.config().history.clone())
+    }
+}
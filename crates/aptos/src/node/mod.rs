@@ -9,9 +9,10 @@ use crate::{
     common::{
         types::{
             CliCommand, CliError, CliResult, CliTypedResult, OptionalPoolAddressArgs,
-            PoolAddressArgs, ProfileOptions, RestOptions, TransactionOptions, TransactionSummary,
+            PoolAddressArgs, ProfileOptions, PromptOptions, RestOptions, TransactionOptions,
+            TransactionSummary,
         },
-        utils::read_from_file,
+        utils::{check_if_file_exists, read_from_file, write_to_file},
     },
     genesis::git::from_yaml,
     node::analyze::{
@@ -25,6 +26,8 @@ use aptos_backup_cli::{
     utils::GlobalRestoreOpt,
 };
 use aptos_cached_packages::aptos_stdlib;
+use aptos_config::config::NodeConfig;
+use aptos_consensus_types::safety_data::SafetyData;
 use aptos_crypto::{bls12381, bls12381::PublicKey, x25519, ValidCryptoMaterialStringExt};
 use aptos_genesis::config::{HostAndPort, OperatorConfiguration};
 use aptos_logger::Level;
@@ -32,6 +35,9 @@ use aptos_network_checker::args::{
     validate_address, CheckEndpointArgs, HandshakeArgs, NodeAddressArgs,
 };
 use aptos_rest_client::{aptos_api_types::VersionedEvent, Client, State};
+use aptos_safety_rules::{
+    safety_data_export::SignedSafetyDataExport, safety_rules_manager, PersistentSafetyStorage,
+};
 use aptos_types::{
     account_address::AccountAddress,
     account_config::{BlockResource, CORE_CODE_ADDRESS},
@@ -52,7 +58,7 @@ use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
     convert::{TryFrom, TryInto},
-    path::PathBuf,
+    path::{Path, PathBuf},
     time::Duration,
 };
 
@@ -67,12 +73,15 @@ pub enum NodeTool {
     AnalyzeValidatorPerformance(AnalyzeValidatorPerformance),
     BootstrapDb(BootstrapDb),
     CheckNetworkConnectivity(CheckNetworkConnectivity),
+    ExportSafetyData(ExportSafetyData),
     GetPerformance(GetPerformance),
     GetStakePool(GetStakePool),
+    ImportSafetyData(ImportSafetyData),
     InitializeValidator(InitializeValidator),
     JoinValidatorSet(JoinValidatorSet),
     LeaveValidatorSet(LeaveValidatorSet),
     ShowEpochInfo(ShowEpochInfo),
+    ShowStateSyncProgress(ShowStateSyncProgress),
     ShowValidatorConfig(ShowValidatorConfig),
     ShowValidatorSet(ShowValidatorSet),
     ShowValidatorStake(ShowValidatorStake),
@@ -92,12 +101,15 @@ impl NodeTool {
                     .await
             },
             CheckNetworkConnectivity(tool) => tool.execute_serialized().await,
+            ExportSafetyData(tool) => tool.execute_serialized().await,
             GetPerformance(tool) => tool.execute_serialized().await,
             GetStakePool(tool) => tool.execute_serialized().await,
+            ImportSafetyData(tool) => tool.execute_serialized().await,
             InitializeValidator(tool) => tool.execute_serialized().await,
             JoinValidatorSet(tool) => tool.execute_serialized().await,
             LeaveValidatorSet(tool) => tool.execute_serialized().await,
             ShowEpochInfo(tool) => tool.execute_serialized().await,
+            ShowStateSyncProgress(tool) => tool.execute_serialized().await,
             ShowValidatorSet(tool) => tool.execute_serialized().await,
             ShowValidatorStake(tool) => tool.execute_serialized().await,
             ShowValidatorConfig(tool) => tool.execute_serialized().await,
@@ -1404,6 +1416,146 @@ impl CliCommand<String> for CheckNetworkConnectivity {
     }
 }
 
+/// Loads the safety rules storage of the validator with the given node config
+fn load_safety_storage(node_config_file: &Path) -> CliTypedResult<PersistentSafetyStorage> {
+    let node_config = NodeConfig::load_from_path(node_config_file).map_err(|error| {
+        CliError::UnexpectedError(format!(
+            "Failed to load the node config file {}: {}",
+            node_config_file.display(),
+            error
+        ))
+    })?;
+    Ok(safety_rules_manager::storage(
+        &node_config.consensus.safety_rules,
+    ))
+}
+
+/// Export the consensus safety data of a validator
+///
+/// Exports the safety data (i.e., the epoch, last voted round, preferred round
+/// and last vote) from the validator's secure storage into a versioned file signed
+/// by the validator's consensus key. The validator must be stopped before exporting,
+/// and must not be restarted on the old host after the export.
+#[derive(Parser)]
+pub struct ExportSafetyData {
+    /// Path to the validator node config file
+    #[clap(long, value_parser)]
+    pub(crate) node_config_file: PathBuf,
+
+    /// Output file for the signed safety data export
+    #[clap(long, value_parser)]
+    pub(crate) output_file: PathBuf,
+
+    #[clap(flatten)]
+    pub(crate) prompt_options: PromptOptions,
+}
+
+#[async_trait]
+impl CliCommand<SafetyData> for ExportSafetyData {
+    fn command_name(&self) -> &'static str {
+        "ExportSafetyData"
+    }
+
+    async fn execute(self) -> CliTypedResult<SafetyData> {
+        check_if_file_exists(&self.output_file, self.prompt_options)?;
+
+        let mut safety_storage = load_safety_storage(&self.node_config_file)?;
+        let signed_export = safety_storage
+            .export_safety_data()
+            .map_err(|error| CliError::UnexpectedError(error.to_string()))?;
+        let bytes = serde_json::to_vec_pretty(&signed_export)
+            .map_err(|error| CliError::UnexpectedError(error.to_string()))?;
+        write_to_file(&self.output_file, "Safety data export", &bytes)?;
+
+        Ok(signed_export.export.safety_data)
+    }
+}
+
+/// Import the consensus safety data of a validator
+///
+/// Imports a safety data file created by `aptos node export-safety-data` into the
+/// validator's secure storage (e.g., on a new host). The export must be signed by
+/// the validator's consensus key, and the import is refused if it would lower any
+/// of the safety watermarks already in storage.
+#[derive(Parser)]
+pub struct ImportSafetyData {
+    /// Path to the validator node config file
+    #[clap(long, value_parser)]
+    pub(crate) node_config_file: PathBuf,
+
+    /// The signed safety data export to import
+    #[clap(long, value_parser)]
+    pub(crate) input_file: PathBuf,
+}
+
+#[async_trait]
+impl CliCommand<SafetyData> for ImportSafetyData {
+    fn command_name(&self) -> &'static str {
+        "ImportSafetyData"
+    }
+
+    async fn execute(self) -> CliTypedResult<SafetyData> {
+        let signed_export: SignedSafetyDataExport =
+            serde_json::from_slice(&read_from_file(&self.input_file)?).map_err(|error| {
+                CliError::UnableToParse("safety data export", error.to_string())
+            })?;
+
+        let mut safety_storage = load_safety_storage(&self.node_config_file)?;
+        safety_storage
+            .import_safety_data(&signed_export)
+            .map_err(|error| CliError::UnexpectedError(error.to_string()))?;
+        safety_storage
+            .safety_data()
+            .map_err(|error| CliError::UnexpectedError(error.to_string()))
+    }
+}
+
+/// Show the state sync progress of a node
+///
+/// Queries the inspection service of the node and displays the current
+/// sync phase, the synced and target versions, the throughput, the
+/// estimated time remaining and the peers currently serving data.
+#[derive(Parser)]
+pub struct ShowStateSyncProgress {
+    /// URL of the node's inspection service
+    #[clap(long, default_value = "http://localhost:9101")]
+    pub inspection_service_url: reqwest::Url,
+}
+
+#[async_trait]
+impl CliCommand<serde_json::Value> for ShowStateSyncProgress {
+    fn command_name(&self) -> &'static str {
+        "ShowStateSyncProgress"
+    }
+
+    async fn execute(self) -> CliTypedResult<serde_json::Value> {
+        let url = self
+            .inspection_service_url
+            .join("state_sync_progress")
+            .map_err(|error| {
+                CliError::UnableToParse("inspection_service_url", error.to_string())
+            })?;
+
+        // Fetch the state sync progress from the inspection service
+        let response = reqwest::get(url)
+            .await
+            .map_err(|error| CliError::ApiError(error.to_string()))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(CliError::ApiError(format!(
+                "Failed to fetch the state sync progress! Status: {}, response: {}",
+                status, body
+            )));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|error| CliError::UnableToParse("state_sync_progress", error.to_string()))
+    }
+}
+
 /// Show epoch information
 ///
 /// Displays the current epoch, the epoch length, and the estimated time of the next epoch
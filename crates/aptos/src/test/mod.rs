@@ -28,9 +28,9 @@ use crate::{
         SubmitVote, SubmitVoteArgs, VerifyProposal, VerifyProposalResponse,
     },
     move_tool::{
-        ArgWithType, CompilePackage, DownloadPackage, FrameworkPackageArgs, IncludedArtifacts,
-        IncludedArtifactsArgs, InitPackage, MemberId, PublishPackage, RunFunction, RunScript,
-        TestPackage,
+        gas_baseline::GasBaselineOptions, ArgWithType, CompilePackage, DownloadPackage,
+        FrameworkPackageArgs, IncludedArtifacts, IncludedArtifactsArgs, InitPackage, MemberId,
+        PublishPackage, RunFunction, RunScript, TestPackage,
     },
     node::{
         AnalyzeMode, AnalyzeValidatorPerformance, GetStakePool, InitializeValidator,
@@ -870,6 +870,7 @@ impl CliTestFramework {
             ignore_compile_warnings: false,
             compute_coverage: false,
             dump_state: false,
+            gas_baseline_options: GasBaselineOptions::default(),
         }
         .execute()
         .await
@@ -12,9 +12,10 @@
 //! long as the latter is in its trusted peers set.
 use aptos_config::{
     config::{
-        DiscoveryMethod, NetworkConfig, Peer, PeerRole, PeerSet, RoleType, CONNECTION_BACKOFF_BASE,
-        CONNECTIVITY_CHECK_INTERVAL_MS, MAX_CONNECTION_DELAY_MS, MAX_FRAME_SIZE,
-        MAX_FULLNODE_OUTBOUND_CONNECTIONS, MAX_INBOUND_CONNECTIONS, NETWORK_CHANNEL_SIZE,
+        DiscoveryMethod, MessageCaptureConfig, NetworkConfig, Peer, PeerRole, PeerSet, RoleType,
+        CONNECTION_BACKOFF_BASE, CONNECTIVITY_CHECK_INTERVAL_MS, MAX_CONNECTION_DELAY_MS,
+        MAX_FRAME_SIZE, MAX_FULLNODE_OUTBOUND_CONNECTIONS, MAX_INBOUND_CONNECTIONS,
+        NETWORK_CHANNEL_SIZE,
     },
     network_id::NetworkContext,
 };
@@ -197,6 +198,10 @@ impl NetworkBuilder {
             ),
         );
 
+        if let Some(capture_config) = &config.message_capture_config {
+            network_builder.add_message_capture(capture_config);
+        }
+
         network_builder.add_connection_monitoring(
             config.ping_interval_ms,
             config.ping_timeout_ms,
@@ -395,6 +400,17 @@ impl NetworkBuilder {
         }
     }
 
+    /// Capture all application messages exchanged with peers to local disk.
+    fn add_message_capture(&mut self, capture_config: &MessageCaptureConfig) -> &mut Self {
+        self.peer_manager_builder
+            .add_message_capture(capture_config);
+        debug!(
+            NetworkSchema::new(&self.network_context),
+            "{} Created message capture", self.network_context
+        );
+        self
+    }
+
     /// Add a HealthChecker to the network.
     fn add_connection_monitoring(
         &mut self,
@@ -0,0 +1,268 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Opt-in capture of the application messages exchanged with remote peers.
+//!
+//! When enabled (see [`MessageCaptureConfig`]), every [`Peer`](crate::peer::Peer)
+//! actor hands the inbound and outbound [`NetworkMessage`]s it processes to a
+//! shared [`MessageCapture`] instance. The messages are timestamped, tagged with
+//! the remote peer and direction, and written (by a dedicated thread) to a set
+//! of rotating files on local disk. This allows the (already decrypted) traffic
+//! of a node to be inspected and replayed offline (see [`replay`]).
+//!
+//! Each capture file is a sequence of records, where every record is the
+//! little-endian `u32` length of a BCS serialized [`CapturedMessage`],
+//! followed by the serialized message itself.
+
+use crate::{counters, logging::NetworkSchema, protocols::wire::messaging::v1::NetworkMessage};
+use aptos_config::{
+    config::MessageCaptureConfig,
+    network_id::{NetworkContext, NetworkId},
+};
+use aptos_logger::prelude::*;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use aptos_types::PeerId;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, SyncSender, TrySendError},
+    thread,
+};
+
+pub mod replay;
+#[cfg(test)]
+mod test;
+
+/// The file extension used by all capture files
+pub const CAPTURE_FILE_EXTENSION: &str = "netcap";
+
+/// The direction of a captured message, relative to the local node
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum CaptureDirection {
+    Inbound,
+    Outbound,
+}
+
+impl CaptureDirection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CaptureDirection::Inbound => counters::INBOUND_LABEL,
+            CaptureDirection::Outbound => counters::OUTBOUND_LABEL,
+        }
+    }
+}
+
+/// A single application message captured on the wire
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CapturedMessage {
+    /// The unix time (in microseconds) at which the message was captured
+    pub timestamp_usecs: u64,
+    /// The network the message was exchanged on
+    pub network_id: NetworkId,
+    /// The remote peer the message was received from (or sent to)
+    pub peer_id: PeerId,
+    /// Whether the message was received or sent by the local node
+    pub direction: CaptureDirection,
+    /// The captured message (including the protocol id and BCS bytes)
+    pub message: NetworkMessage,
+}
+
+/// Shared handle used by the peer actors to capture messages. The messages
+/// are handed off to a dedicated writer thread, so capturing never blocks the
+/// network. If the writer falls behind, new messages are dropped.
+pub struct MessageCapture {
+    network_context: NetworkContext,
+    time_service: TimeService,
+    message_sender: SyncSender<CapturedMessage>,
+}
+
+impl MessageCapture {
+    /// Creates a new message capture and spawns the writer thread
+    pub fn new(
+        network_context: NetworkContext,
+        time_service: TimeService,
+        capture_config: &MessageCaptureConfig,
+    ) -> io::Result<Self> {
+        fs::create_dir_all(&capture_config.capture_dir)?;
+
+        let (message_sender, message_receiver) =
+            mpsc::sync_channel(capture_config.max_pending_messages);
+        let file_writer = CaptureFileWriter::new(network_context, capture_config.clone());
+        thread::Builder::new()
+            .name(format!("net-capture-{}", network_context.network_id()))
+            .spawn(move || file_writer.run(message_receiver))?;
+
+        info!(
+            NetworkSchema::new(&network_context),
+            "{} Capturing network messages to: {:?}", network_context, capture_config.capture_dir
+        );
+
+        Ok(Self {
+            network_context,
+            time_service,
+            message_sender,
+        })
+    }
+
+    /// Captures the given network message. Error messages are not
+    /// application messages, so they are ignored.
+    pub fn capture(&self, peer_id: PeerId, direction: CaptureDirection, message: &NetworkMessage) {
+        if matches!(message, NetworkMessage::Error(_)) {
+            return;
+        }
+
+        let captured_message = CapturedMessage {
+            timestamp_usecs: self.time_service.now_unix_time().as_micros() as u64,
+            network_id: self.network_context.network_id(),
+            peer_id,
+            direction,
+            message: message.clone(),
+        };
+        let label = match self.message_sender.try_send(captured_message) {
+            Ok(()) => counters::SENT_LABEL,
+            Err(TrySendError::Full(_)) | Err(TrySendError::Disconnected(_)) => {
+                counters::DECLINED_LABEL
+            },
+        };
+        counters::captured_messages(&self.network_context, direction.as_str(), label).inc();
+    }
+}
+
+/// Writes captured messages to the rotating capture files
+struct CaptureFileWriter {
+    network_context: NetworkContext,
+    capture_config: MessageCaptureConfig,
+    capture_files: VecDeque<PathBuf>,
+    current_file: Option<(BufWriter<File>, u64)>,
+    next_file_index: u64,
+}
+
+impl CaptureFileWriter {
+    fn new(network_context: NetworkContext, capture_config: MessageCaptureConfig) -> Self {
+        Self {
+            network_context,
+            capture_config,
+            capture_files: VecDeque::new(),
+            current_file: None,
+            next_file_index: 0,
+        }
+    }
+
+    /// Writes messages until all message senders have been dropped
+    fn run(mut self, message_receiver: Receiver<CapturedMessage>) {
+        while let Ok(message) = message_receiver.recv() {
+            // Write the message and any others that are already pending
+            let mut result = self.write_message(&message);
+            while result.is_ok() {
+                match message_receiver.try_recv() {
+                    Ok(message) => result = self.write_message(&message),
+                    Err(_) => break,
+                }
+            }
+
+            // Flush the batch to disk
+            if let Ok(()) = result {
+                if let Some((writer, _)) = self.current_file.as_mut() {
+                    result = writer.flush();
+                }
+            }
+
+            if let Err(error) = result {
+                warn!(
+                    NetworkSchema::new(&self.network_context),
+                    error = %error,
+                    "{} Failed to write captured network messages: {}",
+                    self.network_context,
+                    error
+                );
+                // Start a new file on the next write
+                self.current_file = None;
+            }
+        }
+    }
+
+    fn write_message(&mut self, message: &CapturedMessage) -> io::Result<()> {
+        let bytes = serialize_captured_message(message)?;
+        let record_length = bytes.len() as u64;
+
+        // Rotate the capture file if it would grow too large
+        let max_file_size_bytes = self.capture_config.max_file_size_bytes;
+        if self.current_file.as_ref().map_or(false, |(_, file_size)| {
+            file_size + record_length > max_file_size_bytes
+        }) {
+            self.rotate_file()?;
+        }
+        if self.current_file.is_none() {
+            self.open_new_file(message.timestamp_usecs)?;
+        }
+
+        let (writer, file_size) = self.current_file.as_mut().expect("File was just opened!");
+        writer.write_all(&bytes)?;
+        *file_size += record_length;
+        Ok(())
+    }
+
+    fn rotate_file(&mut self) -> io::Result<()> {
+        if let Some((mut writer, _)) = self.current_file.take() {
+            writer.flush()?;
+        }
+        Ok(())
+    }
+
+    fn open_new_file(&mut self, timestamp_usecs: u64) -> io::Result<()> {
+        // Garbage collect the oldest files written by this writer
+        while self.capture_files.len() >= self.capture_config.max_num_files.max(1) {
+            if let Some(old_file) = self.capture_files.pop_front() {
+                if let Err(error) = fs::remove_file(&old_file) {
+                    warn!(
+                        "{} Failed to remove old capture file {:?}: {}",
+                        self.network_context, old_file, error
+                    );
+                }
+            }
+        }
+
+        let path = capture_file_path(
+            &self.capture_config.capture_dir,
+            self.network_context.network_id(),
+            timestamp_usecs,
+            self.next_file_index,
+        );
+        let file = File::create(&path)?;
+        self.next_file_index += 1;
+        self.capture_files.push_back(path);
+        self.current_file = Some((BufWriter::new(file), 0));
+        Ok(())
+    }
+}
+
+/// Serializes the given message into a capture file record (i.e., the
+/// length prefixed BCS bytes of the message)
+pub(crate) fn serialize_captured_message(message: &CapturedMessage) -> io::Result<Vec<u8>> {
+    let message_bytes =
+        bcs::to_bytes(message).map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+    let mut record = Vec::with_capacity(4 + message_bytes.len());
+    record.extend_from_slice(&(message_bytes.len() as u32).to_le_bytes());
+    record.extend_from_slice(&message_bytes);
+    Ok(record)
+}
+
+/// Returns the path of the capture file for the given network, started at the given time.
+/// The timestamp is zero padded so that the files of a network sort chronologically.
+fn capture_file_path(
+    capture_dir: &Path,
+    network_id: NetworkId,
+    timestamp_usecs: u64,
+    file_index: u64,
+) -> PathBuf {
+    capture_dir.join(format!(
+        "{}_{:020}_{:06}.{}",
+        network_id.as_str(),
+        timestamp_usecs,
+        file_index,
+        CAPTURE_FILE_EXTENSION
+    ))
+}
@@ -0,0 +1,231 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Utilities for reading capture files and replaying the captured inbound
+//! messages into a single application (e.g., a component under test).
+
+use crate::{
+    capture::{
+        serialize_captured_message, CaptureDirection, CapturedMessage, CAPTURE_FILE_EXTENSION,
+    },
+    protocols::{
+        network::ReceivedMessage,
+        rpc::error::RpcError,
+        wire::messaging::v1::{NetworkMessage, RequestId},
+    },
+    ProtocolId,
+};
+use anyhow::{anyhow, Context};
+use aptos_channels::aptos_channel;
+use aptos_config::network_id::PeerNetworkId;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use aptos_types::PeerId;
+use bytes::Bytes;
+use futures::channel::oneshot;
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{BufReader, BufWriter, ErrorKind, Read, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+/// Returns all capture files in the given directory, sorted chronologically
+pub fn list_capture_files(capture_dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut capture_files = vec![];
+    for entry in fs::read_dir(capture_dir)
+        .with_context(|| format!("Failed to read capture directory {:?}", capture_dir))?
+    {
+        let path = entry?.path();
+        if path.extension().and_then(|extension| extension.to_str()) == Some(CAPTURE_FILE_EXTENSION)
+        {
+            capture_files.push(path);
+        }
+    }
+    capture_files.sort();
+    Ok(capture_files)
+}
+
+/// Iterates over the captured messages in a single capture file
+pub struct CaptureFileReader {
+    reader: BufReader<File>,
+}
+
+impl CaptureFileReader {
+    pub fn open(path: &Path) -> anyhow::Result<Self> {
+        let file =
+            File::open(path).with_context(|| format!("Failed to open capture file {:?}", path))?;
+        Ok(Self {
+            reader: BufReader::new(file),
+        })
+    }
+
+    fn read_message(&mut self) -> anyhow::Result<Option<CapturedMessage>> {
+        let mut length_bytes = [0u8; 4];
+        match self.reader.read_exact(&mut length_bytes) {
+            Ok(()) => {},
+            Err(error) if error.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(error) => return Err(error.into()),
+        }
+
+        // A truncated record (e.g., if the node was killed mid-write) ends the file
+        let mut bytes = vec![0u8; u32::from_le_bytes(length_bytes) as usize];
+        match self.reader.read_exact(&mut bytes) {
+            Ok(()) => Ok(Some(bcs::from_bytes(&bytes)?)),
+            Err(error) if error.kind() == ErrorKind::UnexpectedEof => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+}
+
+impl Iterator for CaptureFileReader {
+    type Item = anyhow::Result<CapturedMessage>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_message().transpose()
+    }
+}
+
+/// Reads all captured messages from the given capture files (in order)
+pub fn read_capture_files(capture_files: &[PathBuf]) -> anyhow::Result<Vec<CapturedMessage>> {
+    let mut captured_messages = vec![];
+    for capture_file in capture_files {
+        for captured_message in CaptureFileReader::open(capture_file)? {
+            captured_messages.push(captured_message?);
+        }
+    }
+    Ok(captured_messages)
+}
+
+/// Writes the given messages to a new capture file. This is useful for
+/// extracting the relevant subset of a capture (e.g., to replay in a test).
+pub fn write_capture_file(
+    path: &Path,
+    captured_messages: &[CapturedMessage],
+) -> anyhow::Result<()> {
+    let file =
+        File::create(path).with_context(|| format!("Failed to create capture file {:?}", path))?;
+    let mut writer = BufWriter::new(file);
+    for captured_message in captured_messages {
+        writer.write_all(&serialize_captured_message(captured_message)?)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Returns the protocol id of the given captured message. Rpc responses do not
+/// carry a protocol id on the wire, so the protocol of the matching rpc request
+/// (sent in the opposite direction, to/from the same peer) is used instead.
+pub struct ProtocolResolver {
+    pending_requests: HashMap<(PeerId, CaptureDirection, RequestId), ProtocolId>,
+}
+
+impl ProtocolResolver {
+    pub fn new() -> Self {
+        Self {
+            pending_requests: HashMap::new(),
+        }
+    }
+
+    /// Resolves the protocol id of the given message. Messages must be
+    /// processed in the order they were captured.
+    pub fn resolve(&mut self, captured_message: &CapturedMessage) -> Option<ProtocolId> {
+        match &captured_message.message {
+            NetworkMessage::DirectSendMsg(message) => Some(message.protocol_id),
+            NetworkMessage::RpcRequest(request) => {
+                self.pending_requests.insert(
+                    (
+                        captured_message.peer_id,
+                        captured_message.direction,
+                        request.request_id,
+                    ),
+                    request.protocol_id,
+                );
+                Some(request.protocol_id)
+            },
+            NetworkMessage::RpcResponse(response) => {
+                let request_direction = match captured_message.direction {
+                    CaptureDirection::Inbound => CaptureDirection::Outbound,
+                    CaptureDirection::Outbound => CaptureDirection::Inbound,
+                };
+                self.pending_requests.remove(&(
+                    captured_message.peer_id,
+                    request_direction,
+                    response.request_id,
+                ))
+            },
+            NetworkMessage::Error(_) => None,
+        }
+    }
+}
+
+impl Default for ProtocolResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The rpc responses produced by the application during a replay
+pub type ReplayedRpcResponse = (
+    PeerNetworkId,
+    RequestId,
+    oneshot::Receiver<Result<Bytes, RpcError>>,
+);
+
+/// Replays the captured inbound direct send messages and rpc requests for the
+/// given protocols into the application's upstream handler (i.e., the same
+/// channel that the peer actors would use). If `preserve_timing` is set, the
+/// original inter-message delays are reproduced using the time service.
+///
+/// Returns the response channels for all replayed rpc requests, so that the
+/// caller can inspect how the application responded.
+pub async fn replay_inbound_messages(
+    captured_messages: impl IntoIterator<Item = CapturedMessage>,
+    protocol_ids: &[ProtocolId],
+    upstream_handler: &aptos_channel::Sender<(PeerId, ProtocolId), ReceivedMessage>,
+    time_service: TimeService,
+    preserve_timing: bool,
+) -> anyhow::Result<Vec<ReplayedRpcResponse>> {
+    let mut rpc_responses = vec![];
+    let mut last_timestamp_usecs = None;
+
+    for captured_message in captured_messages {
+        if captured_message.direction != CaptureDirection::Inbound {
+            continue;
+        }
+        let protocol_id = match &captured_message.message {
+            NetworkMessage::DirectSendMsg(message) => message.protocol_id,
+            NetworkMessage::RpcRequest(request) => request.protocol_id,
+            _ => continue, // Only requests and direct sends can be replayed
+        };
+        if !protocol_ids.contains(&protocol_id) {
+            continue;
+        }
+
+        // Wait for the original delay between messages (if required)
+        if preserve_timing {
+            if let Some(last_timestamp_usecs) = last_timestamp_usecs {
+                let delay_usecs = captured_message
+                    .timestamp_usecs
+                    .saturating_sub(last_timestamp_usecs);
+                time_service.sleep(Duration::from_micros(delay_usecs)).await;
+            }
+            last_timestamp_usecs = Some(captured_message.timestamp_usecs);
+        }
+
+        // Push the message to the application
+        let sender = PeerNetworkId::new(captured_message.network_id, captured_message.peer_id);
+        let mut received_message = ReceivedMessage::new(captured_message.message, sender);
+        if let NetworkMessage::RpcRequest(request) = &received_message.message {
+            let (response_sender, response_receiver) = oneshot::channel();
+            received_message.rpc_replier = Some(Arc::new(response_sender));
+            rpc_responses.push((sender, request.request_id, response_receiver));
+        }
+        upstream_handler
+            .push((captured_message.peer_id, protocol_id), received_message)
+            .map_err(|error| anyhow!("Failed to replay captured message: {:?}", error))?;
+    }
+
+    Ok(rpc_responses)
+}
@@ -0,0 +1,282 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    capture::{
+        replay::{
+            list_capture_files, read_capture_files, replay_inbound_messages, ProtocolResolver,
+        },
+        CaptureDirection, CaptureFileWriter, CapturedMessage,
+    },
+    protocols::wire::messaging::v1::{DirectSendMsg, NetworkMessage, RpcRequest, RpcResponse},
+    ProtocolId,
+};
+use aptos_channels::{aptos_channel, message_queues::QueueStyle};
+use aptos_config::{
+    config::MessageCaptureConfig,
+    network_id::{NetworkContext, NetworkId, PeerNetworkId},
+};
+use aptos_temppath::TempPath;
+use aptos_time_service::TimeService;
+use aptos_types::PeerId;
+use bytes::Bytes;
+use futures::StreamExt;
+use std::sync::Arc;
+
+#[test]
+fn test_capture_round_trip() {
+    // Create a capture writer with a large file size limit
+    let capture_dir = TempPath::new();
+    capture_dir.create_as_dir().unwrap();
+    let mut file_writer = create_file_writer(&capture_dir, 1024 * 1024, 10);
+
+    // Write several messages
+    let peer_id = PeerId::random();
+    let captured_messages: Vec<_> = (0..10)
+        .map(|index| create_direct_send(peer_id, index))
+        .collect();
+    for captured_message in &captured_messages {
+        file_writer.write_message(captured_message).unwrap();
+    }
+    file_writer.rotate_file().unwrap();
+
+    // Verify that all messages are read back in order
+    let capture_files = list_capture_files(capture_dir.path()).unwrap();
+    assert_eq!(capture_files.len(), 1);
+    assert_eq!(
+        read_capture_files(&capture_files).unwrap(),
+        captured_messages
+    );
+}
+
+#[test]
+fn test_capture_file_rotation() {
+    // Create a capture writer that rotates after every message and keeps 3 files
+    let capture_dir = TempPath::new();
+    capture_dir.create_as_dir().unwrap();
+    let mut file_writer = create_file_writer(&capture_dir, 1, 3);
+
+    // Write several messages
+    let peer_id = PeerId::random();
+    let captured_messages: Vec<_> = (0..10)
+        .map(|index| create_direct_send(peer_id, index))
+        .collect();
+    for captured_message in &captured_messages {
+        file_writer.write_message(captured_message).unwrap();
+    }
+    file_writer.rotate_file().unwrap();
+
+    // Verify that only the latest files (and messages) were kept
+    let capture_files = list_capture_files(capture_dir.path()).unwrap();
+    assert_eq!(capture_files.len(), 3);
+    assert_eq!(
+        read_capture_files(&capture_files).unwrap(),
+        captured_messages[7..].to_vec()
+    );
+}
+
+#[test]
+fn test_protocol_resolver() {
+    let peer_id = PeerId::random();
+    let mut protocol_resolver = ProtocolResolver::new();
+
+    // An outbound request is resolved by its protocol id
+    let request = create_captured_message(
+        peer_id,
+        CaptureDirection::Outbound,
+        NetworkMessage::RpcRequest(RpcRequest {
+            protocol_id: ProtocolId::StorageServiceRpc,
+            request_id: 5,
+            priority: 0,
+            raw_request: vec![],
+        }),
+    );
+    assert_eq!(
+        protocol_resolver.resolve(&request),
+        Some(ProtocolId::StorageServiceRpc)
+    );
+
+    // A response in the same direction is not matched
+    let response = NetworkMessage::RpcResponse(RpcResponse {
+        request_id: 5,
+        priority: 0,
+        raw_response: vec![],
+    });
+    let outbound_response =
+        create_captured_message(peer_id, CaptureDirection::Outbound, response.clone());
+    assert_eq!(protocol_resolver.resolve(&outbound_response), None);
+
+    // The inbound response is matched to the request (only once)
+    let inbound_response = create_captured_message(peer_id, CaptureDirection::Inbound, response);
+    assert_eq!(
+        protocol_resolver.resolve(&inbound_response),
+        Some(ProtocolId::StorageServiceRpc)
+    );
+    assert_eq!(protocol_resolver.resolve(&inbound_response), None);
+}
+
+#[tokio::test]
+async fn test_capture_decode_replay_round_trip() {
+    // Create the captured messages: an inbound rpc request and its (outbound)
+    // response, and two inbound direct send messages for different protocols.
+    let peer_id = PeerId::random();
+    let rpc_protocol = ProtocolId::HealthCheckerRpc;
+    let direct_send_protocol = ProtocolId::ConsensusDirectSendBcs;
+    let captured_messages = vec![
+        create_captured_message(
+            peer_id,
+            CaptureDirection::Inbound,
+            NetworkMessage::RpcRequest(RpcRequest {
+                protocol_id: rpc_protocol,
+                request_id: 10,
+                priority: 0,
+                raw_request: rpc_protocol.to_bytes(&"ping".to_string()).unwrap(),
+            }),
+        ),
+        create_captured_message(
+            peer_id,
+            CaptureDirection::Outbound,
+            NetworkMessage::RpcResponse(RpcResponse {
+                request_id: 10,
+                priority: 0,
+                raw_response: rpc_protocol.to_bytes(&"pong".to_string()).unwrap(),
+            }),
+        ),
+        create_captured_message(
+            peer_id,
+            CaptureDirection::Inbound,
+            NetworkMessage::DirectSendMsg(DirectSendMsg {
+                protocol_id: direct_send_protocol,
+                priority: 0,
+                raw_msg: direct_send_protocol.to_bytes(&7u64).unwrap(),
+            }),
+        ),
+        create_captured_message(
+            peer_id,
+            CaptureDirection::Inbound,
+            NetworkMessage::DirectSendMsg(DirectSendMsg {
+                protocol_id: ProtocolId::MempoolDirectSend,
+                priority: 0,
+                raw_msg: vec![],
+            }),
+        ),
+    ];
+
+    // Capture the messages and read them back
+    let capture_dir = TempPath::new();
+    capture_dir.create_as_dir().unwrap();
+    let mut file_writer = create_file_writer(&capture_dir, 1024 * 1024, 10);
+    for captured_message in &captured_messages {
+        file_writer.write_message(captured_message).unwrap();
+    }
+    file_writer.rotate_file().unwrap();
+    let capture_files = list_capture_files(capture_dir.path()).unwrap();
+    let read_messages = read_capture_files(&capture_files).unwrap();
+    assert_eq!(read_messages, captured_messages);
+
+    // Decode the rpc response using the protocol of the matching request
+    let mut protocol_resolver = ProtocolResolver::new();
+    let protocol_ids: Vec<_> = read_messages
+        .iter()
+        .map(|captured_message| protocol_resolver.resolve(captured_message))
+        .collect();
+    assert_eq!(protocol_ids[1], Some(rpc_protocol));
+    match &read_messages[1].message {
+        NetworkMessage::RpcResponse(response) => {
+            let response: String = rpc_protocol.from_bytes(&response.raw_response).unwrap();
+            assert_eq!(response, "pong");
+        },
+        message => panic!("Unexpected captured message: {:?}", message),
+    }
+
+    // Replay the inbound messages for the rpc and direct send protocols
+    let (upstream_handler, mut upstream_receiver) = aptos_channel::new(QueueStyle::FIFO, 10, None);
+    let mut rpc_responses = replay_inbound_messages(
+        read_messages,
+        &[rpc_protocol, direct_send_protocol],
+        &upstream_handler,
+        TimeService::mock(),
+        false,
+    )
+    .await
+    .unwrap();
+    drop(upstream_handler);
+
+    // Verify the application receives the rpc request and can respond to it
+    let sender = PeerNetworkId::new(NetworkId::Validator, peer_id);
+    let received_request = upstream_receiver.next().await.unwrap();
+    assert_eq!(received_request.sender, sender);
+    match &received_request.message {
+        NetworkMessage::RpcRequest(request) => {
+            let request: String = rpc_protocol.from_bytes(&request.raw_request).unwrap();
+            assert_eq!(request, "ping");
+        },
+        message => panic!("Unexpected replayed message: {:?}", message),
+    }
+    let rpc_replier = Arc::try_unwrap(received_request.rpc_replier.unwrap()).unwrap();
+    rpc_replier.send(Ok(Bytes::from_static(b"pong"))).unwrap();
+    assert_eq!(rpc_responses.len(), 1);
+    let (response_sender, request_id, response_receiver) = rpc_responses.remove(0);
+    assert_eq!((response_sender, request_id), (sender, 10));
+    assert_eq!(
+        response_receiver.await.unwrap().unwrap(),
+        Bytes::from_static(b"pong")
+    );
+
+    // Verify the direct send message is received, and the other messages are not replayed
+    let received_message = upstream_receiver.next().await.unwrap();
+    match &received_message.message {
+        NetworkMessage::DirectSendMsg(message) => {
+            assert_eq!(message.protocol_id, direct_send_protocol);
+            let value: u64 = direct_send_protocol.from_bytes(&message.raw_msg).unwrap();
+            assert_eq!(value, 7);
+        },
+        message => panic!("Unexpected replayed message: {:?}", message),
+    }
+    assert!(upstream_receiver.next().await.is_none());
+}
+
+/// Creates a capture file writer for the given directory
+fn create_file_writer(
+    capture_dir: &TempPath,
+    max_file_size_bytes: u64,
+    max_num_files: usize,
+) -> CaptureFileWriter {
+    let capture_config = MessageCaptureConfig {
+        capture_dir: capture_dir.path().to_path_buf(),
+        max_file_size_bytes,
+        max_num_files,
+        ..Default::default()
+    };
+    CaptureFileWriter::new(NetworkContext::mock(), capture_config)
+}
+
+/// Creates an inbound captured direct send message
+fn create_direct_send(peer_id: PeerId, index: u64) -> CapturedMessage {
+    let mut captured_message = create_captured_message(
+        peer_id,
+        CaptureDirection::Inbound,
+        NetworkMessage::DirectSendMsg(DirectSendMsg {
+            protocol_id: ProtocolId::ConsensusDirectSendBcs,
+            priority: 0,
+            raw_msg: index.to_le_bytes().to_vec(),
+        }),
+    );
+    captured_message.timestamp_usecs = index;
+    captured_message
+}
+
+/// Creates a captured message for the given peer, direction and message
+fn create_captured_message(
+    peer_id: PeerId,
+    direction: CaptureDirection,
+    message: NetworkMessage,
+) -> CapturedMessage {
+    CapturedMessage {
+        timestamp_usecs: 0,
+        network_id: NetworkId::Validator,
+        peer_id,
+        direction,
+        message,
+    }
+}
@@ -655,3 +655,25 @@ pub fn inbound_queue_delay_observe(protocol_id: ProtocolId, seconds: f64) {
         .with_label_values(&[protocol_id.as_str()])
         .observe(seconds)
 }
+
+pub static NETWORK_CAPTURED_MESSAGES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_captured_messages",
+        "Number of application messages handed to the message capture",
+        &["role_type", "network_id", "direction", "state"]
+    )
+    .unwrap()
+});
+
+pub fn captured_messages(
+    network_context: &NetworkContext,
+    direction_label: &'static str,
+    state_label: &'static str,
+) -> IntCounter {
+    NETWORK_CAPTURED_MESSAGES.with_label_values(&[
+        network_context.role().as_str(),
+        network_context.network_id().as_str(),
+        direction_label,
+        state_label,
+    ])
+}
@@ -12,6 +12,7 @@
 // #![doc = include_str!("../README.md")]
 
 pub mod application;
+pub mod capture;
 pub mod connectivity_manager;
 pub mod constants;
 pub mod counters;
@@ -16,6 +16,7 @@
 //! [`PeerManager`]: crate::peer_manager::PeerManager
 
 use crate::{
+    capture::{CaptureDirection, MessageCapture},
     counters::{
         self, network_application_inbound_traffic, network_application_outbound_traffic,
         DECLINED_LABEL, FAILED_LABEL, RECEIVED_LABEL, SENT_LABEL, UNKNOWN_LABEL,
@@ -130,6 +131,8 @@ pub struct Peer<TSocket> {
     max_message_size: usize,
     /// Inbound stream buffer
     inbound_stream: InboundStreamBuffer,
+    /// Capture for inbound and outbound application messages (if enabled)
+    message_capture: Option<Arc<MessageCapture>>,
 }
 
 impl<TSocket> Peer<TSocket>
@@ -152,6 +155,7 @@ where
         max_concurrent_outbound_rpcs: u32,
         max_frame_size: usize,
         max_message_size: usize,
+        message_capture: Option<Arc<MessageCapture>>,
     ) -> Self {
         let Connection {
             metadata: connection_metadata,
@@ -185,6 +189,7 @@ where
             max_frame_size,
             max_message_size,
             inbound_stream: InboundStreamBuffer::new(max_fragments),
+            message_capture,
         }
     }
 
@@ -222,6 +227,7 @@ where
             writer,
             self.max_frame_size,
             self.max_message_size,
+            self.message_capture.clone(),
         );
 
         // Start main Peer event loop.
@@ -325,6 +331,7 @@ where
         mut writer: MultiplexMessageSink<impl AsyncWrite + Unpin + Send + 'static>,
         max_frame_size: usize,
         max_message_size: usize,
+        message_capture: Option<Arc<MessageCapture>>,
     ) -> (
         aptos_channel::Sender<(), NetworkMessage>,
         oneshot::Sender<()>,
@@ -413,6 +420,10 @@ where
             let mut outbound_stream =
                 OutboundStream::new(max_frame_size, max_message_size, stream_msg_tx);
             while let Some(message) = write_reqs_rx.next().await {
+                if let Some(message_capture) = &message_capture {
+                    message_capture.capture(remote_peer_id, CaptureDirection::Outbound, &message);
+                }
+
                 // either channel full would block the other one
                 let result = if outbound_stream.should_stream(&message) {
                     outbound_stream.stream_message(message).await
@@ -441,6 +452,10 @@ where
         &mut self,
         message: NetworkMessage,
     ) -> Result<(), PeerManagerError> {
+        if let Some(message_capture) = &self.message_capture {
+            message_capture.capture(self.remote_peer_id(), CaptureDirection::Inbound, &message);
+        }
+
         match &message {
             NetworkMessage::DirectSendMsg(direct) => {
                 let data_len = direct.raw_msg.len();
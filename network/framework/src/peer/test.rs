@@ -97,6 +97,7 @@ fn build_test_peer(
         MAX_CONCURRENT_OUTBOUND_RPCS,
         MAX_FRAME_SIZE,
         MAX_MESSAGE_SIZE,
+        None,
     );
     let peer_handle = PeerHandle(peer_reqs_tx);
 
@@ -4,6 +4,7 @@
 
 use crate::{
     application::storage::PeersAndMetadata,
+    capture::MessageCapture,
     counters,
     noise::{stream::NoiseStream, HandshakeAuthMode},
     peer_manager::{
@@ -18,7 +19,10 @@ use crate::{
     ProtocolId,
 };
 use aptos_channels::{self, aptos_channel, message_queues::QueueStyle};
-use aptos_config::{config::HANDSHAKE_VERSION, network_id::NetworkContext};
+use aptos_config::{
+    config::{MessageCaptureConfig, HANDSHAKE_VERSION},
+    network_id::NetworkContext,
+};
 use aptos_crypto::x25519;
 use aptos_logger::prelude::*;
 #[cfg(any(test, feature = "testing", feature = "fuzzing"))]
@@ -78,6 +82,7 @@ struct PeerManagerContext {
     max_message_size: usize,
     inbound_connection_limit: usize,
     tcp_buffer_cfg: TCPBufferCfg,
+    message_capture: Option<Arc<MessageCapture>>,
 }
 
 impl PeerManagerContext {
@@ -116,6 +121,7 @@ impl PeerManagerContext {
             max_message_size,
             inbound_connection_limit,
             tcp_buffer_cfg,
+            message_capture: None,
         }
     }
 
@@ -237,6 +243,28 @@ impl PeerManagerBuilder {
             .expect("Cannot get PeerManagerContext once PeerManager has been built")
     }
 
+    /// Enables the capture of all application messages exchanged with peers.
+    /// Capturing is best effort: if the capture cannot be started, the
+    /// network runs without it.
+    pub fn add_message_capture(&mut self, capture_config: &MessageCaptureConfig) -> &mut Self {
+        match MessageCapture::new(
+            self.network_context,
+            self.time_service.clone(),
+            capture_config,
+        ) {
+            Ok(message_capture) => {
+                self.peer_manager_context().message_capture = Some(Arc::new(message_capture));
+            },
+            Err(error) => {
+                error!(
+                    "{} Failed to start the network message capture: {}",
+                    self.network_context, error
+                );
+            },
+        }
+        self
+    }
+
     /// Create the configured transport and start PeerManager.
     /// Return the actual NetworkAddress over which this peer is listening.
     pub fn build(&mut self, executor: &Handle) -> &mut Self {
@@ -340,6 +368,7 @@ impl PeerManagerBuilder {
             pm_context.max_frame_size,
             pm_context.max_message_size,
             pm_context.inbound_connection_limit,
+            pm_context.message_capture,
         );
 
         // PeerManager constructor appends a public key to the listen_address.
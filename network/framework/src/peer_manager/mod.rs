@@ -12,6 +12,7 @@
 //!  notification about new/lost Peers to the rest of the network stack.
 //!  * An actor responsible for dialing and listening for new connections.
 use crate::{
+    capture::MessageCapture,
     constants,
     counters::{self},
     logging::*,
@@ -117,6 +118,8 @@ where
     max_message_size: usize,
     /// Inbound connection limit separate of outbound connections
     inbound_connection_limit: usize,
+    /// Capture for the application messages exchanged with peers (if enabled)
+    message_capture: Option<Arc<MessageCapture>>,
 }
 
 impl<TTransport, TSocket> PeerManager<TTransport, TSocket>
@@ -144,6 +147,7 @@ where
         max_frame_size: usize,
         max_message_size: usize,
         inbound_connection_limit: usize,
+        message_capture: Option<Arc<MessageCapture>>,
     ) -> Self {
         let (transport_notifs_tx, transport_notifs_rx) = aptos_channels::new(
             channel_size,
@@ -185,6 +189,7 @@ where
             max_frame_size,
             max_message_size,
             inbound_connection_limit,
+            message_capture,
         }
     }
 
@@ -661,6 +666,7 @@ where
             constants::MAX_CONCURRENT_OUTBOUND_RPCS,
             self.max_frame_size,
             self.max_message_size,
+            self.message_capture.clone(),
         );
         self.executor.spawn(peer.start());
 
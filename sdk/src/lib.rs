@@ -12,6 +12,7 @@
 //! * `move_types` - Includes types used when interacting with the Move VM
 //! * `rest_client` - The Aptos API Client, used for sending requests to the Aptos Blockchain.
 //! * `transaction_builder` - Includes helpers for constructing transactions
+//! * `transaction_submitter` - Submits transactions from an account shared by concurrent tasks
 //! * `types` - Includes types for Aptos on-chain data structures
 //!
 //! ## Example
@@ -38,4 +39,6 @@ pub mod rest_client {
 
 pub mod transaction_builder;
 
+pub mod transaction_submitter;
+
 pub mod types;
@@ -0,0 +1,684 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Submission of transactions from a single account, shared by concurrent tasks.
+//!
+//! The [`TransactionSubmitter`] allocates sequence numbers from its [`LocalAccount`], submits
+//! transactions, waits for them to be committed, and recovers from the usual failures: the
+//! local sequence number falling behind or running ahead of the chain, a full mempool, and
+//! expired transactions.
+
+use crate::{
+    crypto::HashValue,
+    rest_client::{
+        aptos_api_types::{AptosErrorCode, TransactionData, TransactionOnChainData},
+        error::RestError,
+        Client, Response,
+    },
+    transaction_builder::TransactionFactory,
+    types::{
+        account_address::AccountAddress,
+        account_config::AccountResource,
+        transaction::{SignedTransaction, TransactionPayload},
+        vm_status::StatusCode,
+        LocalAccount,
+    },
+};
+use anyhow::{anyhow, bail, Result};
+use aptos_cached_packages::aptos_stdlib;
+use aptos_infallible::Mutex;
+use async_trait::async_trait;
+use std::{fmt, sync::Arc, time::Duration};
+
+/// What to do with a transaction which expired before being committed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ExpirationPolicy {
+    /// Sign the payload again, with the same sequence number and a new expiration time.
+    Resubmit,
+    /// Give up on the payload, and cancel its sequence number.
+    Cancel,
+}
+
+#[derive(Clone, Debug)]
+pub struct TransactionSubmitterConfig {
+    /// Maximum number of times a payload is submitted again, after its transaction expired or
+    /// could not be accepted by the mempool.
+    pub max_retries: usize,
+    /// Delay before submitting again a transaction which could not be accepted by the mempool.
+    pub retry_delay: Duration,
+    pub expiration_policy: ExpirationPolicy,
+}
+
+impl Default for TransactionSubmitterConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            retry_delay: Duration::from_secs(1),
+            expiration_policy: ExpirationPolicy::Resubmit,
+        }
+    }
+}
+
+/// The result of submitting a transaction once and waiting for it.
+enum Attempt {
+    Committed(TransactionOnChainData),
+    /// The sequence number of the transaction was already used.
+    SequenceNumberTooOld,
+    /// The sequence number of the transaction is too far ahead of the one of the account.
+    SequenceNumberTooNew,
+    MempoolIsFull,
+    Expired,
+    /// The transaction was rejected, and will never be committed.
+    Rejected(RestError),
+}
+
+/// The requests of the submitter to the node, which are mocked in tests.
+#[async_trait]
+trait SubmissionClient: fmt::Debug + Send + Sync {
+    async fn submit(&self, txn: &SignedTransaction) -> Result<(), RestError>;
+
+    async fn wait_for(&self, txn: &SignedTransaction) -> Result<TransactionOnChainData, RestError>;
+
+    async fn get_account(
+        &self,
+        address: AccountAddress,
+    ) -> Result<Response<AccountResource>, RestError>;
+
+    async fn get_transaction(&self, hash: HashValue) -> Result<TransactionData, RestError>;
+}
+
+#[async_trait]
+impl SubmissionClient for Client {
+    async fn submit(&self, txn: &SignedTransaction) -> Result<(), RestError> {
+        self.submit_bcs(txn).await.map(|_| ())
+    }
+
+    async fn wait_for(&self, txn: &SignedTransaction) -> Result<TransactionOnChainData, RestError> {
+        Ok(self
+            .wait_for_signed_transaction_bcs(txn)
+            .await?
+            .into_inner())
+    }
+
+    async fn get_account(
+        &self,
+        address: AccountAddress,
+    ) -> Result<Response<AccountResource>, RestError> {
+        self.get_account_bcs(address).await
+    }
+
+    async fn get_transaction(&self, hash: HashValue) -> Result<TransactionData, RestError> {
+        Ok(self.get_transaction_by_hash_bcs(hash).await?.into_inner())
+    }
+}
+
+/// Submits transactions from a single account. It can be shared by concurrent tasks, e.g.
+/// behind an `Arc`, which then get distinct sequence numbers.
+///
+/// As a transaction with a given sequence number can only be committed once all transactions
+/// with lower sequence numbers are, a sequence number which ends up unused by the payload it was
+/// allocated for is cancelled: it is used by a transaction transferring nothing to the account
+/// itself.
+#[derive(Debug)]
+pub struct TransactionSubmitter {
+    client: Arc<dyn SubmissionClient>,
+    account: Arc<LocalAccount>,
+    transaction_factory: TransactionFactory,
+    config: TransactionSubmitterConfig,
+    /// The number of sequence numbers held by submissions which did not complete yet. It is
+    /// locked while allocating sequence numbers and resyncing them with the chain, so that a
+    /// resync never hands out a sequence number which is still held.
+    in_flight: Mutex<usize>,
+}
+
+/// Releases the sequence number held by a submission once it completes, or is dropped.
+struct InFlightGuard<'a>(&'a Mutex<usize>);
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        *self.0.lock() -= 1;
+    }
+}
+
+impl TransactionSubmitter {
+    pub fn new(
+        client: Client,
+        account: Arc<LocalAccount>,
+        transaction_factory: TransactionFactory,
+        config: TransactionSubmitterConfig,
+    ) -> Self {
+        Self::with_client(Arc::new(client), account, transaction_factory, config)
+    }
+
+    fn with_client(
+        client: Arc<dyn SubmissionClient>,
+        account: Arc<LocalAccount>,
+        transaction_factory: TransactionFactory,
+        config: TransactionSubmitterConfig,
+    ) -> Self {
+        Self {
+            client,
+            account,
+            transaction_factory,
+            config,
+            in_flight: Mutex::new(0),
+        }
+    }
+
+    pub fn account(&self) -> &LocalAccount {
+        &self.account
+    }
+
+    /// Sets the sequence number of the account to the one on chain. This should be done
+    /// before submitting transactions, unless the sequence number is known to be up to date.
+    pub async fn sync_sequence_number(&self) -> Result<u64> {
+        let sequence_number = self.fetch_sequence_number().await?;
+        let _in_flight = self.in_flight.lock();
+        self.account.set_sequence_number(sequence_number);
+        Ok(sequence_number)
+    }
+
+    /// Submits a transaction with the given payload, and waits for it to be committed.
+    ///
+    /// Fails if the transaction was committed but failed execution, if it was rejected, or if
+    /// it could not be committed after `max_retries` retries.
+    pub async fn submit_and_wait(
+        &self,
+        payload: TransactionPayload,
+    ) -> Result<TransactionOnChainData> {
+        let (mut sequence_number, _guard) = self.allocate_sequence_number();
+        let mut retries = 0;
+        loop {
+            let txn = self.sign(payload.clone(), sequence_number);
+            let attempt = self.attempt(&txn).await?;
+            let can_retry = retries < self.config.max_retries;
+            let can_resubmit_expired =
+                can_retry && self.config.expiration_policy == ExpirationPolicy::Resubmit;
+            retries += 1;
+            match attempt {
+                Attempt::Committed(committed_txn) => return Ok(committed_txn),
+                Attempt::SequenceNumberTooOld if can_retry => {
+                    sequence_number = self.resync_forward_and_reallocate().await?;
+                },
+                Attempt::SequenceNumberTooOld => {
+                    bail!(
+                        "Sequence number {} of account {} is too old",
+                        sequence_number,
+                        self.account.address()
+                    )
+                },
+                // Transactions with lower sequence numbers may still be in flight, so the
+                // sequence number is only resynced once waiting for them did not help.
+                Attempt::SequenceNumberTooNew if can_retry => {
+                    tokio::time::sleep(self.config.retry_delay).await;
+                },
+                Attempt::SequenceNumberTooNew => {
+                    let resynced_sequence_number = self.resync_after_failure().await?;
+                    bail!(
+                        "Sequence number {} of account {} is too new, resynced it to {}",
+                        sequence_number,
+                        self.account.address(),
+                        resynced_sequence_number
+                    )
+                },
+                Attempt::MempoolIsFull if can_retry => {
+                    tokio::time::sleep(self.config.retry_delay).await;
+                },
+                Attempt::MempoolIsFull => {
+                    self.cancel(sequence_number).await?;
+                    bail!(
+                        "Mempool is full, cancelled sequence number {}",
+                        sequence_number
+                    )
+                },
+                // The payload is signed again, with a new expiration time.
+                Attempt::Expired if can_resubmit_expired => {},
+                Attempt::Expired => {
+                    self.cancel(sequence_number).await?;
+                    bail!(
+                        "Transaction {} expired, cancelled sequence number {}",
+                        txn.committed_hash(),
+                        sequence_number
+                    )
+                },
+                Attempt::Rejected(error) => {
+                    self.cancel(sequence_number).await?;
+                    return Err(error.into());
+                },
+            }
+        }
+    }
+
+    fn allocate_sequence_number(&self) -> (u64, InFlightGuard<'_>) {
+        let mut in_flight = self.in_flight.lock();
+        *in_flight += 1;
+        (
+            self.account.increment_sequence_number(),
+            InFlightGuard(&self.in_flight),
+        )
+    }
+
+    fn sign(&self, payload: TransactionPayload, sequence_number: u64) -> SignedTransaction {
+        let raw_txn = self
+            .transaction_factory
+            .payload(payload)
+            .sender(self.account.address())
+            .sequence_number(sequence_number)
+            .build();
+        self.account.sign_transaction(raw_txn)
+    }
+
+    async fn attempt(&self, txn: &SignedTransaction) -> Result<Attempt> {
+        if let Err(error) = self.client.submit(txn).await {
+            // Otherwise, the transaction may have been submitted nonetheless, e.g. after a
+            // network error, so wait for it.
+            if let Some(attempt) = submission_failure(error) {
+                return Ok(attempt);
+            }
+        }
+        match self.client.wait_for(txn).await {
+            Ok(committed_txn) => Ok(Attempt::Committed(committed_txn)),
+            Err(error) => self.check_status(txn, error).await,
+        }
+    }
+
+    /// Determines what happened to a transaction after waiting for it failed.
+    async fn check_status(&self, txn: &SignedTransaction, error: RestError) -> Result<Attempt> {
+        let (account, state) = self.client.get_account(txn.sender()).await?.into_parts();
+        if txn.sequence_number() < account.sequence_number() {
+            return match self.client.get_transaction(txn.committed_hash()).await {
+                Ok(TransactionData::OnChain(txn)) if txn.info.status().is_success() => {
+                    Ok(Attempt::Committed(txn))
+                },
+                Ok(TransactionData::OnChain(txn)) => Err(anyhow!(
+                    "Transaction committed on chain, but failed execution: {:?}",
+                    txn.info.status()
+                )),
+                Ok(TransactionData::Pending(_)) => Err(error.into()),
+                // The sequence number was used by another transaction.
+                Err(lookup_error) if is_not_found(&lookup_error) => {
+                    Ok(Attempt::SequenceNumberTooOld)
+                },
+                // The transaction may have been committed, so it must not be submitted again.
+                Err(lookup_error) => Err(lookup_error.into()),
+            };
+        }
+        if txn.expiration_timestamp_secs() <= state.timestamp_usecs / 1_000_000 {
+            return Ok(Attempt::Expired);
+        }
+        // The ledger of the node lags behind, so the transaction may still be committed.
+        Err(error.into())
+    }
+
+    /// Cancels a sequence number which will not be used by the payload it was allocated for,
+    /// so that transactions with later sequence numbers can be committed. If this fails, the
+    /// sequence number of the account is resynced with the chain.
+    async fn cancel(&self, sequence_number: u64) -> Result<()> {
+        let txn = self.sign(
+            aptos_stdlib::aptos_account_transfer(self.account.address(), 0),
+            sequence_number,
+        );
+        match self.attempt(&txn).await {
+            Ok(Attempt::Committed(_) | Attempt::SequenceNumberTooOld) => Ok(()),
+            _ => {
+                let resynced_sequence_number = self.resync_after_failure().await?;
+                bail!(
+                    "Failed to cancel sequence number {}, resynced it to {}",
+                    sequence_number,
+                    resynced_sequence_number
+                )
+            },
+        }
+    }
+
+    /// Moves the sequence number of the account forward to the one on chain, if it is behind,
+    /// and allocates a new sequence number to a submission whose sequence number was used.
+    async fn resync_forward_and_reallocate(&self) -> Result<u64> {
+        let sequence_number = self.fetch_sequence_number().await?;
+        let _in_flight = self.in_flight.lock();
+        if self.account.sequence_number() < sequence_number {
+            self.account.set_sequence_number(sequence_number);
+        }
+        Ok(self.account.increment_sequence_number())
+    }
+
+    /// Resyncs the sequence number of the account with the chain, after a submission could
+    /// neither use nor cancel its sequence number. It is only moved back to the one on chain if
+    /// no other submission holds a sequence number, as these would otherwise be allocated again.
+    async fn resync_after_failure(&self) -> Result<u64> {
+        let sequence_number = self.fetch_sequence_number().await?;
+        let in_flight = self.in_flight.lock();
+        if *in_flight <= 1 || self.account.sequence_number() < sequence_number {
+            self.account.set_sequence_number(sequence_number);
+        }
+        Ok(self.account.sequence_number())
+    }
+
+    async fn fetch_sequence_number(&self) -> Result<u64> {
+        Ok(self
+            .client
+            .get_account(self.account.address())
+            .await?
+            .into_inner()
+            .sequence_number())
+    }
+}
+
+/// Returns whether the node answered that the requested transaction does not exist, as opposed
+/// to failing to answer.
+fn is_not_found(error: &RestError) -> bool {
+    match error {
+        RestError::Api(response) => matches!(
+            response.error.error_code,
+            AptosErrorCode::TransactionNotFound
+        ),
+        RestError::Http(status_code, _) => status_code.as_u16() == 404,
+        _ => false,
+    }
+}
+
+/// Classifies the error returned when submitting a transaction, or returns `None` if it is not
+/// known whether the transaction was submitted.
+fn submission_failure(error: RestError) -> Option<Attempt> {
+    let (error_code, vm_error_code) = match &error {
+        RestError::Api(response) => (response.error.error_code, response.error.vm_error_code),
+        _ => return None,
+    };
+    let is_vm_status = |status_code: StatusCode| vm_error_code == Some(status_code as u64);
+    Some(match error_code {
+        AptosErrorCode::SequenceNumberTooOld => Attempt::SequenceNumberTooOld,
+        AptosErrorCode::MempoolIsFull => Attempt::MempoolIsFull,
+        AptosErrorCode::VmError if is_vm_status(StatusCode::SEQUENCE_NUMBER_TOO_OLD) => {
+            Attempt::SequenceNumberTooOld
+        },
+        AptosErrorCode::VmError if is_vm_status(StatusCode::SEQUENCE_NUMBER_TOO_NEW) => {
+            Attempt::SequenceNumberTooNew
+        },
+        AptosErrorCode::VmError if is_vm_status(StatusCode::TRANSACTION_EXPIRED) => {
+            Attempt::Expired
+        },
+        _ => Attempt::Rejected(error),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        rest_client::{aptos_api_types::AptosError, error::AptosErrorResponse, State},
+        types::{
+            chain_id::ChainId,
+            event::{EventHandle, EventKey},
+            transaction::{ExecutionStatus, Transaction, TransactionInfo},
+            write_set::WriteSet,
+        },
+    };
+    use std::{
+        collections::VecDeque,
+        sync::atomic::{AtomicU64, Ordering},
+    };
+    use tokio::sync::Notify;
+
+    /// A node on which transactions are committed when waited for, unless scripted otherwise.
+    #[derive(Debug)]
+    struct MockClient {
+        /// The sequence number of the account on chain
+        sequence_number: AtomicU64,
+        /// The timestamp of the ledger of the node
+        timestamp_usecs: AtomicU64,
+        submitted: Mutex<Vec<SignedTransaction>>,
+        /// The results of the next submissions, which succeed once these are consumed
+        submit_results: Mutex<VecDeque<Result<(), RestError>>>,
+        /// The results of the next waits, which commit the transaction once these are consumed
+        wait_results: Mutex<VecDeque<Result<(), RestError>>>,
+        /// The error returned when looking up a transaction by hash
+        lookup_error: fn() -> RestError,
+        /// Waiting for the transaction with this sequence number blocks until `release`
+        blocked_sequence_number: Option<u64>,
+        release: Notify,
+    }
+
+    impl MockClient {
+        fn new() -> Self {
+            Self {
+                sequence_number: AtomicU64::new(0),
+                timestamp_usecs: AtomicU64::new(0),
+                submitted: Mutex::new(vec![]),
+                submit_results: Mutex::new(VecDeque::new()),
+                wait_results: Mutex::new(VecDeque::new()),
+                lookup_error: not_found_error,
+                blocked_sequence_number: None,
+                release: Notify::new(),
+            }
+        }
+
+        fn submitted_sequence_numbers(&self) -> Vec<u64> {
+            self.submitted
+                .lock()
+                .iter()
+                .map(|txn| txn.sequence_number())
+                .collect()
+        }
+
+        fn state(&self) -> State {
+            State {
+                chain_id: ChainId::test().id(),
+                epoch: 1,
+                version: 0,
+                timestamp_usecs: self.timestamp_usecs.load(Ordering::SeqCst),
+                oldest_ledger_version: 0,
+                oldest_block_height: 0,
+                block_height: 0,
+                cursor: None,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl SubmissionClient for MockClient {
+        async fn submit(&self, txn: &SignedTransaction) -> Result<(), RestError> {
+            self.submitted.lock().push(txn.clone());
+            self.submit_results.lock().pop_front().unwrap_or(Ok(()))
+        }
+
+        async fn wait_for(
+            &self,
+            txn: &SignedTransaction,
+        ) -> Result<TransactionOnChainData, RestError> {
+            if self.blocked_sequence_number == Some(txn.sequence_number()) {
+                self.release.notified().await;
+            }
+            self.wait_results.lock().pop_front().unwrap_or(Ok(()))?;
+            self.sequence_number
+                .fetch_max(txn.sequence_number() + 1, Ordering::SeqCst);
+            Ok(TransactionOnChainData {
+                version: 0,
+                transaction: Transaction::UserTransaction(txn.clone()),
+                info: TransactionInfo::new(
+                    HashValue::zero(),
+                    HashValue::zero(),
+                    HashValue::zero(),
+                    None,
+                    0,
+                    ExecutionStatus::Success,
+                ),
+                events: vec![],
+                accumulator_root_hash: HashValue::zero(),
+                changes: WriteSet::default(),
+            })
+        }
+
+        async fn get_account(
+            &self,
+            address: AccountAddress,
+        ) -> Result<Response<AccountResource>, RestError> {
+            let account = AccountResource::new(
+                self.sequence_number.load(Ordering::SeqCst),
+                vec![],
+                EventHandle::new(EventKey::new(0, address), 0),
+                EventHandle::new(EventKey::new(1, address), 0),
+            );
+            Ok(Response::new(account, self.state()))
+        }
+
+        async fn get_transaction(&self, _hash: HashValue) -> Result<TransactionData, RestError> {
+            Err((self.lookup_error)())
+        }
+    }
+
+    fn api_error(error: AptosError, status_code: u16) -> RestError {
+        RestError::Api(AptosErrorResponse {
+            error,
+            state: None,
+            status_code: reqwest::StatusCode::from_u16(status_code).unwrap(),
+        })
+    }
+
+    fn not_found_error() -> RestError {
+        api_error(
+            AptosError::new_with_error_code(
+                "Transaction not found",
+                AptosErrorCode::TransactionNotFound,
+            ),
+            404,
+        )
+    }
+
+    fn unavailable_error() -> RestError {
+        api_error(
+            AptosError::new_with_error_code("Service unavailable", AptosErrorCode::InternalError),
+            503,
+        )
+    }
+
+    fn rejected_error() -> RestError {
+        api_error(
+            AptosError::new_with_vm_status(
+                "Invalid transaction",
+                AptosErrorCode::VmError,
+                StatusCode::INSUFFICIENT_BALANCE_FOR_TRANSACTION_FEE,
+            ),
+            400,
+        )
+    }
+
+    fn wait_error() -> RestError {
+        RestError::Unknown(anyhow!("Transaction expired"))
+    }
+
+    fn submitter(client: Arc<MockClient>) -> Arc<TransactionSubmitter> {
+        let account = LocalAccount::generate(&mut rand::rngs::OsRng);
+        Arc::new(TransactionSubmitter::with_client(
+            client,
+            Arc::new(account),
+            TransactionFactory::new(ChainId::test()),
+            TransactionSubmitterConfig {
+                retry_delay: Duration::from_millis(1),
+                ..TransactionSubmitterConfig::default()
+            },
+        ))
+    }
+
+    fn payload() -> TransactionPayload {
+        aptos_stdlib::aptos_account_transfer(AccountAddress::ONE, 1)
+    }
+
+    #[tokio::test]
+    async fn test_committed() {
+        let client = Arc::new(MockClient::new());
+        let submitter = submitter(client.clone());
+
+        let committed_txn = submitter.submit_and_wait(payload()).await.unwrap();
+        let txn = committed_txn.transaction.try_as_signed_user_txn().unwrap();
+        assert_eq!(txn.sequence_number(), 0);
+        assert_eq!(client.submitted_sequence_numbers(), vec![0]);
+        assert_eq!(submitter.account().sequence_number(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_sequence_number_too_old() {
+        let client = Arc::new(MockClient::new());
+        // Sequence numbers 0 to 2 were used by other transactions, which are not found
+        client.sequence_number.store(3, Ordering::SeqCst);
+        client.wait_results.lock().push_back(Err(wait_error()));
+        let submitter = submitter(client.clone());
+
+        submitter.submit_and_wait(payload()).await.unwrap();
+        assert_eq!(client.submitted_sequence_numbers(), vec![0, 3]);
+        assert_eq!(submitter.account().sequence_number(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_transient_lookup_error_is_not_resubmitted() {
+        let mut client = MockClient::new();
+        client.lookup_error = unavailable_error;
+        // The transaction may have been committed, but it cannot be looked up
+        client.sequence_number.store(1, Ordering::SeqCst);
+        client.wait_results.lock().push_back(Err(wait_error()));
+        let client = Arc::new(client);
+        let submitter = submitter(client.clone());
+
+        assert!(submitter.submit_and_wait(payload()).await.is_err());
+        assert_eq!(client.submitted_sequence_numbers(), vec![0]);
+        assert_eq!(submitter.account().sequence_number(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_expired_is_resubmitted() {
+        let client = Arc::new(MockClient::new());
+        // The ledger is past the expiration time of the first transaction
+        let expired_usecs = (aptos_infallible::duration_since_epoch()
+            + Duration::from_secs(24 * 60 * 60))
+        .as_micros() as u64;
+        client
+            .timestamp_usecs
+            .store(expired_usecs, Ordering::SeqCst);
+        client.wait_results.lock().push_back(Err(wait_error()));
+        let submitter = submitter(client.clone());
+
+        submitter.submit_and_wait(payload()).await.unwrap();
+        assert_eq!(client.submitted_sequence_numbers(), vec![0, 0]);
+        assert_eq!(submitter.account().sequence_number(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_submissions_after_rejection() {
+        let mut client = MockClient::new();
+        client.blocked_sequence_number = Some(0);
+        let client = Arc::new(client);
+        let submitter = submitter(client.clone());
+
+        // The first submission holds sequence number 0 until it is released
+        let first = tokio::spawn({
+            let submitter = submitter.clone();
+            async move { submitter.submit_and_wait(payload()).await }
+        });
+        while client.submitted.lock().is_empty() {
+            tokio::task::yield_now().await;
+        }
+
+        // The second submission is rejected, and so is the cancellation of its sequence number
+        client
+            .submit_results
+            .lock()
+            .extend([Err(rejected_error()), Err(rejected_error())]);
+        assert!(submitter.submit_and_wait(payload()).await.is_err());
+        assert_eq!(client.submitted_sequence_numbers(), vec![0, 1, 1]);
+        // Sequence number 0 is still held, so the sequence number is not moved back
+        assert_eq!(submitter.account().sequence_number(), 2);
+
+        client.release.notify_one();
+        first.await.unwrap().unwrap();
+        assert_eq!(client.sequence_number.load(Ordering::SeqCst), 1);
+
+        // Once no sequence number is held anymore, a failed cancellation moves it back
+        client
+            .submit_results
+            .lock()
+            .extend([Err(rejected_error()), Err(rejected_error())]);
+        assert!(submitter.submit_and_wait(payload()).await.is_err());
+        assert_eq!(submitter.account().sequence_number(), 1);
+        submitter.submit_and_wait(payload()).await.unwrap();
+        assert_eq!(client.submitted_sequence_numbers(), vec![0, 1, 1, 2, 2, 1]);
+    }
+}
@@ -5,16 +5,22 @@
 use crate::{
     crypto::{
         ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
-        signing_message,
+        hash::CryptoHash,
+        multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
+        secp256k1_ecdsa, secp256r1_ecdsa, signing_message,
         traits::Uniform,
+        HashValue,
     },
     transaction_builder::TransactionBuilder,
     types::{
         account_address::AccountAddress,
-        transaction::{authenticator::AuthenticationKey, RawTransaction, SignedTransaction},
+        transaction::{
+            authenticator::{AccountAuthenticator, AuthenticationKey, TransactionAuthenticator},
+            RawTransaction, RawTransactionWithData, SignedTransaction,
+        },
     },
 };
-use anyhow::Result;
+use anyhow::{anyhow, ensure, Result};
 use aptos_crypto::{ed25519::Ed25519Signature, PrivateKey, SigningKey};
 use aptos_ledger::AptosLedgerError;
 pub use aptos_types::*;
@@ -24,10 +30,19 @@ use aptos_types::{
         Claims, Configuration, EphemeralCertificate, IdCommitment, KeylessPublicKey,
         KeylessSignature, OpenIdSig, Pepper, TransactionAndProof, ZeroKnowledgeSig,
     },
-    transaction::authenticator::{AnyPublicKey, EphemeralPublicKey, EphemeralSignature},
+    transaction::{
+        authenticator::{
+            AnyPublicKey, AnySignature, EphemeralPublicKey, EphemeralSignature, MultiKey,
+            MultiKeyAuthenticator, SingleKeyAuthenticator,
+        },
+        webauthn::{AssertionSignature, PartialAuthenticatorAssertionResponse},
+    },
 };
 use bip39::{Language, Mnemonic, Seed};
 use ed25519_dalek_bip32::{DerivationPath, ExtendedSecretKey};
+use p256::ecdsa::signature::Signer;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
 use std::{
     str::FromStr,
     sync::atomic::{AtomicU64, Ordering},
@@ -37,27 +52,57 @@ use std::{
 #[derive(Debug)]
 enum LocalAccountAuthenticator {
     PrivateKey(AccountKey),
+    SingleKey(AnyPrivateKey),
+    MultiEd25519(MultiEd25519Account),
+    MultiKey(MultiKeyAccount),
     Keyless(KeylessAccount),
-    // TODO: Add support for keyless authentication
 }
 
 impl LocalAccountAuthenticator {
     pub fn sign_transaction(&self, txn: RawTransaction) -> SignedTransaction {
-        match self {
-            LocalAccountAuthenticator::PrivateKey(key) => txn
-                .sign(key.private_key(), key.public_key().clone())
-                .expect("Signing a txn can't fail")
-                .into_inner(),
+        let authenticator = match self.sign(&txn).expect("Signing a txn can't fail") {
+            AccountAuthenticator::Ed25519 {
+                public_key,
+                signature,
+            } => TransactionAuthenticator::ed25519(public_key, signature),
+            AccountAuthenticator::MultiEd25519 {
+                public_key,
+                signature,
+            } => TransactionAuthenticator::multi_ed25519(public_key, signature),
+            authenticator => TransactionAuthenticator::single_sender(authenticator),
+        };
+        SignedTransaction::new_signed_transaction(txn, authenticator)
+    }
+
+    /// Signs `message`, which is either a `RawTransaction` or, for multi-agent and fee payer
+    /// transactions, a `RawTransactionWithData`.
+    pub fn sign<T: CryptoHash + Serialize>(&self, message: &T) -> Result<AccountAuthenticator> {
+        Ok(match self {
+            LocalAccountAuthenticator::PrivateKey(key) => AccountAuthenticator::ed25519(
+                key.public_key().clone(),
+                key.private_key().sign(message)?,
+            ),
+            LocalAccountAuthenticator::SingleKey(key) => AccountAuthenticator::single_key(
+                SingleKeyAuthenticator::new(key.public_key(), key.sign(message)?),
+            ),
+            LocalAccountAuthenticator::MultiEd25519(account) => {
+                AccountAuthenticator::multi_ed25519(
+                    account.public_key().clone(),
+                    account.sign(message)?,
+                )
+            },
+            LocalAccountAuthenticator::MultiKey(account) => {
+                AccountAuthenticator::multi_key(account.sign(message)?)
+            },
             LocalAccountAuthenticator::Keyless(keyless_account) => {
                 let proof = keyless_account.zk_sig.proof;
                 let txn_and_zkp = TransactionAndProof {
-                    message: txn.clone(),
+                    message,
                     proof: Some(proof),
                 };
 
                 let esk = &keyless_account.ephemeral_key_pair.private_key;
-                let ephemeral_signature =
-                    EphemeralSignature::ed25519(esk.sign(&txn_and_zkp).unwrap());
+                let ephemeral_signature = EphemeralSignature::ed25519(esk.sign(&txn_and_zkp)?);
 
                 let sig = KeylessSignature {
                     cert: EphemeralCertificate::ZeroKnowledgeSig(keyless_account.zk_sig.clone()),
@@ -67,11 +112,15 @@ impl LocalAccountAuthenticator {
                     ephemeral_signature,
                 };
 
-                SignedTransaction::new_keyless(txn, keyless_account.public_key.clone(), sig)
+                AccountAuthenticator::single_key(SingleKeyAuthenticator::new(
+                    AnyPublicKey::keyless(keyless_account.public_key.clone()),
+                    AnySignature::keyless(sig),
+                ))
             },
-        }
+        })
     }
 }
+
 impl<T: Into<AccountKey>> From<T> for LocalAccountAuthenticator {
     fn from(key: T) -> Self {
         Self::PrivateKey(key.into())
@@ -123,6 +172,44 @@ impl LocalAccount {
         }
     }
 
+    /// Create a local representation of an account which signs with a single key of any
+    /// scheme, e.g. a Secp256k1 key.
+    pub fn new_single_key<T: Into<AnyPrivateKey>>(
+        address: AccountAddress,
+        key: T,
+        sequence_number: u64,
+    ) -> Self {
+        Self {
+            address,
+            auth: LocalAccountAuthenticator::SingleKey(key.into()),
+            sequence_number: AtomicU64::new(sequence_number),
+        }
+    }
+
+    pub fn new_multi_ed25519(
+        address: AccountAddress,
+        multi_ed25519_account: MultiEd25519Account,
+        sequence_number: u64,
+    ) -> Self {
+        Self {
+            address,
+            auth: LocalAccountAuthenticator::MultiEd25519(multi_ed25519_account),
+            sequence_number: AtomicU64::new(sequence_number),
+        }
+    }
+
+    pub fn new_multi_key(
+        address: AccountAddress,
+        multi_key_account: MultiKeyAccount,
+        sequence_number: u64,
+    ) -> Self {
+        Self {
+            address,
+            auth: LocalAccountAuthenticator::MultiKey(multi_key_account),
+            sequence_number: AtomicU64::new(sequence_number),
+        }
+    }
+
     /// Recover an account from derive path (e.g. m/44'/637'/0'/0'/0') and mnemonic phrase,
     pub fn from_derive_path(
         derive_path: &str,
@@ -182,26 +269,33 @@ impl LocalAccount {
         secondary_signers: Vec<&Self>,
         builder: TransactionBuilder,
     ) -> SignedTransaction {
-        let secondary_signer_addresses = secondary_signers
+        let secondary_signer_addresses: Vec<_> = secondary_signers
             .iter()
             .map(|signer| signer.address())
             .collect();
-        let secondary_signer_privkeys = secondary_signers
-            .iter()
-            .map(|signer| signer.private_key())
-            .collect();
         let raw_txn = builder
             .sender(self.address())
             .sequence_number(self.increment_sequence_number())
             .build();
-        raw_txn
-            .sign_multi_agent(
-                self.private_key(),
-                secondary_signer_addresses,
-                secondary_signer_privkeys,
-            )
-            .expect("Signing multi agent txn failed")
-            .into_inner()
+        let message = RawTransactionWithData::new_multi_agent(
+            raw_txn.clone(),
+            secondary_signer_addresses.clone(),
+        );
+        let sender_authenticator = self
+            .auth
+            .sign(&message)
+            .expect("Signing multi agent txn failed");
+        let secondary_authenticators = secondary_signers
+            .iter()
+            .map(|signer| signer.auth.sign(&message))
+            .collect::<Result<_>>()
+            .expect("Signing multi agent txn failed");
+        SignedTransaction::new_multi_agent(
+            raw_txn,
+            sender_authenticator,
+            secondary_signer_addresses,
+            secondary_authenticators,
+        )
     }
 
     pub fn sign_fee_payer_with_transaction_builder(
@@ -210,51 +304,114 @@ impl LocalAccount {
         fee_payer_signer: &Self,
         builder: TransactionBuilder,
     ) -> SignedTransaction {
-        let secondary_signer_addresses = secondary_signers
+        let secondary_signer_addresses: Vec<_> = secondary_signers
             .iter()
             .map(|signer| signer.address())
             .collect();
-        let secondary_signer_privkeys = secondary_signers
-            .iter()
-            .map(|signer| signer.private_key())
-            .collect();
         let raw_txn = builder
             .sender(self.address())
             .sequence_number(self.increment_sequence_number())
             .build();
-        raw_txn
-            .sign_fee_payer(
-                self.private_key(),
-                secondary_signer_addresses,
-                secondary_signer_privkeys,
-                fee_payer_signer.address(),
-                fee_payer_signer.private_key(),
-            )
-            .expect("Signing multi agent txn failed")
-            .into_inner()
+        let message = RawTransactionWithData::new_fee_payer(
+            raw_txn.clone(),
+            secondary_signer_addresses.clone(),
+            fee_payer_signer.address(),
+        );
+        let sender_authenticator = self
+            .auth
+            .sign(&message)
+            .expect("Signing fee payer txn failed");
+        let secondary_authenticators = secondary_signers
+            .iter()
+            .map(|signer| signer.auth.sign(&message))
+            .collect::<Result<_>>()
+            .expect("Signing fee payer txn failed");
+        let fee_payer_authenticator = fee_payer_signer
+            .auth
+            .sign(&message)
+            .expect("Signing fee payer txn failed");
+        SignedTransaction::new_fee_payer(
+            raw_txn,
+            sender_authenticator,
+            secondary_signer_addresses,
+            secondary_authenticators,
+            fee_payer_signer.address(),
+            fee_payer_authenticator,
+        )
     }
 
     pub fn address(&self) -> AccountAddress {
         self.address
     }
 
+    /// Returns the Ed25519 private key of the account. Panics if the account isn't
+    /// authenticated by a single Ed25519 key (see `try_private_key`).
     pub fn private_key(&self) -> &Ed25519PrivateKey {
+        self.try_private_key()
+            .expect("Only accounts with a single Ed25519 key have an Ed25519 private key!")
+    }
+
+    /// Returns the Ed25519 private key of the account, or `None` if the account
+    /// isn't authenticated by a single Ed25519 key (e.g., a `MultiKey` account).
+    pub fn try_private_key(&self) -> Option<&Ed25519PrivateKey> {
         match &self.auth {
-            LocalAccountAuthenticator::PrivateKey(key) => key.private_key(),
-            LocalAccountAuthenticator::Keyless(_) => todo!(),
+            LocalAccountAuthenticator::PrivateKey(key) => Some(key.private_key()),
+            LocalAccountAuthenticator::SingleKey(_)
+            | LocalAccountAuthenticator::MultiEd25519(_)
+            | LocalAccountAuthenticator::MultiKey(_)
+            | LocalAccountAuthenticator::Keyless(_) => None,
         }
     }
 
+    /// Returns the Ed25519 public key of the account. Panics if the account isn't
+    /// authenticated by a single Ed25519 key (see `try_public_key`).
     pub fn public_key(&self) -> &Ed25519PublicKey {
+        self.try_public_key()
+            .expect("Only accounts with a single Ed25519 key have an Ed25519 public key!")
+    }
+
+    /// Returns the Ed25519 public key of the account, or `None` if the account
+    /// isn't authenticated by a single Ed25519 key (e.g., a `MultiKey` account).
+    pub fn try_public_key(&self) -> Option<&Ed25519PublicKey> {
+        match &self.auth {
+            LocalAccountAuthenticator::PrivateKey(key) => Some(key.public_key()),
+            LocalAccountAuthenticator::SingleKey(_)
+            | LocalAccountAuthenticator::MultiEd25519(_)
+            | LocalAccountAuthenticator::MultiKey(_)
+            | LocalAccountAuthenticator::Keyless(_) => None,
+        }
+    }
+
+    /// Returns the `SingleKey` private key of the account (if it has one)
+    pub fn single_key(&self) -> Option<&AnyPrivateKey> {
+        match &self.auth {
+            LocalAccountAuthenticator::SingleKey(key) => Some(key),
+            _ => None,
+        }
+    }
+
+    /// Returns the MultiEd25519 account (if this is one)
+    pub fn multi_ed25519_account(&self) -> Option<&MultiEd25519Account> {
+        match &self.auth {
+            LocalAccountAuthenticator::MultiEd25519(account) => Some(account),
+            _ => None,
+        }
+    }
+
+    /// Returns the `MultiKey` account (if this is one)
+    pub fn multi_key_account(&self) -> Option<&MultiKeyAccount> {
         match &self.auth {
-            LocalAccountAuthenticator::PrivateKey(key) => key.public_key(),
-            LocalAccountAuthenticator::Keyless(_) => todo!(),
+            LocalAccountAuthenticator::MultiKey(account) => Some(account),
+            _ => None,
         }
     }
 
     pub fn authentication_key(&self) -> AuthenticationKey {
         match &self.auth {
             LocalAccountAuthenticator::PrivateKey(key) => key.authentication_key(),
+            LocalAccountAuthenticator::SingleKey(key) => key.authentication_key(),
+            LocalAccountAuthenticator::MultiEd25519(account) => account.authentication_key(),
+            LocalAccountAuthenticator::MultiKey(account) => account.authentication_key(),
             LocalAccountAuthenticator::Keyless(keyless_account) => {
                 keyless_account.authentication_key()
             },
@@ -278,10 +435,26 @@ impl LocalAccount {
             .store(sequence_number, Ordering::SeqCst);
     }
 
+    /// Replaces the Ed25519 key of the account, and returns the old key. Panics if the
+    /// account isn't authenticated by a single Ed25519 key (see `try_rotate_key`).
     pub fn rotate_key<T: Into<AccountKey>>(&mut self, new_key: T) -> AccountKey {
+        self.try_rotate_key(new_key)
+            .expect("Failed to rotate the account key!")
+    }
+
+    /// Replaces the Ed25519 key of the account, and returns the old key. Only accounts
+    /// authenticated by a single Ed25519 key can rotate their key this way.
+    pub fn try_rotate_key<T: Into<AccountKey>>(&mut self, new_key: T) -> Result<AccountKey> {
         match &mut self.auth {
-            LocalAccountAuthenticator::PrivateKey(key) => std::mem::replace(key, new_key.into()),
-            LocalAccountAuthenticator::Keyless(_) => todo!(),
+            LocalAccountAuthenticator::PrivateKey(key) => {
+                Ok(std::mem::replace(key, new_key.into()))
+            },
+            LocalAccountAuthenticator::SingleKey(_)
+            | LocalAccountAuthenticator::MultiEd25519(_)
+            | LocalAccountAuthenticator::MultiKey(_)
+            | LocalAccountAuthenticator::Keyless(_) => Err(anyhow!(
+                "Only accounts with a single Ed25519 key can rotate their key!"
+            )),
         }
     }
 
@@ -468,6 +641,233 @@ impl From<Ed25519PrivateKey> for AccountKey {
     }
 }
 
+/// Relying party of the WebAuthn assertions created by Secp256r1 keys. The chain only checks
+/// the challenge of an assertion, so any relying party will do.
+const WEBAUTHN_RP_ID: &str = "localhost";
+const WEBAUTHN_ORIGIN: &str = "http://localhost";
+
+/// A private key which signs as a single key, i.e. with a `SingleKeyAuthenticator`.
+#[derive(Debug)]
+pub enum AnyPrivateKey {
+    Ed25519(Ed25519PrivateKey),
+    Secp256k1Ecdsa(secp256k1_ecdsa::PrivateKey),
+    /// Signs as a WebAuthn authenticator, as the chain only accepts Secp256r1 signatures in
+    /// WebAuthn assertions.
+    Secp256r1Ecdsa(secp256r1_ecdsa::PrivateKey),
+}
+
+impl AnyPrivateKey {
+    pub fn public_key(&self) -> AnyPublicKey {
+        match self {
+            AnyPrivateKey::Ed25519(private_key) => AnyPublicKey::ed25519(private_key.public_key()),
+            AnyPrivateKey::Secp256k1Ecdsa(private_key) => {
+                AnyPublicKey::secp256k1_ecdsa(private_key.public_key())
+            },
+            AnyPrivateKey::Secp256r1Ecdsa(private_key) => {
+                AnyPublicKey::secp256r1_ecdsa(private_key.public_key())
+            },
+        }
+    }
+
+    pub fn authentication_key(&self) -> AuthenticationKey {
+        AuthenticationKey::any_key(self.public_key())
+    }
+
+    pub fn sign<T: CryptoHash + Serialize>(&self, message: &T) -> Result<AnySignature> {
+        Ok(match self {
+            AnyPrivateKey::Ed25519(private_key) => {
+                AnySignature::ed25519(private_key.sign(message)?)
+            },
+            AnyPrivateKey::Secp256k1Ecdsa(private_key) => {
+                AnySignature::secp256k1_ecdsa(private_key.sign(message)?)
+            },
+            AnyPrivateKey::Secp256r1Ecdsa(private_key) => {
+                AnySignature::webauthn(sign_webauthn_assertion(private_key, message)?)
+            },
+        })
+    }
+}
+
+impl From<Ed25519PrivateKey> for AnyPrivateKey {
+    fn from(private_key: Ed25519PrivateKey) -> Self {
+        AnyPrivateKey::Ed25519(private_key)
+    }
+}
+
+impl From<secp256k1_ecdsa::PrivateKey> for AnyPrivateKey {
+    fn from(private_key: secp256k1_ecdsa::PrivateKey) -> Self {
+        AnyPrivateKey::Secp256k1Ecdsa(private_key)
+    }
+}
+
+impl From<secp256r1_ecdsa::PrivateKey> for AnyPrivateKey {
+    fn from(private_key: secp256r1_ecdsa::PrivateKey) -> Self {
+        AnyPrivateKey::Secp256r1Ecdsa(private_key)
+    }
+}
+
+/// Creates the WebAuthn assertion a passkey would return for `message`, whose challenge is the
+/// SHA3-256 digest of the signing message.
+fn sign_webauthn_assertion<T: CryptoHash + Serialize>(
+    private_key: &secp256r1_ecdsa::PrivateKey,
+    message: &T,
+) -> Result<PartialAuthenticatorAssertionResponse> {
+    let challenge = HashValue::sha3_256_of(&signing_message(message)?);
+    let client_data_json = format!(
+        r#"{{"type":"webauthn.get","challenge":"{}","origin":"{}","crossOrigin":false}}"#,
+        base64::encode_config(challenge.to_vec(), base64::URL_SAFE_NO_PAD),
+        WEBAUTHN_ORIGIN,
+    )
+    .into_bytes();
+    // The RP ID hash, followed by the flags (user present and verified) and a zero counter.
+    let mut authenticator_data = Sha256::digest(WEBAUTHN_RP_ID.as_bytes()).to_vec();
+    authenticator_data.push(0b101);
+    authenticator_data.extend([0u8; 4]);
+
+    let verification_data = [
+        authenticator_data.as_slice(),
+        Sha256::digest(&client_data_json).as_slice(),
+    ]
+    .concat();
+    let signing_key = p256::ecdsa::SigningKey::from_slice(&private_key.to_bytes())
+        .map_err(|e| anyhow!("Invalid Secp256r1 private key: {}", e))?;
+    let signature: p256::ecdsa::Signature = signing_key.sign(&verification_data);
+    let signature =
+        secp256r1_ecdsa::Signature::make_canonical_from_bytes_unchecked(&signature.to_bytes())?;
+
+    Ok(PartialAuthenticatorAssertionResponse::new(
+        AssertionSignature::Secp256r1Ecdsa { signature },
+        authenticator_data,
+        client_data_json,
+    ))
+}
+
+/// Checks that each private key of a k-of-n account matches a distinct public key, and that
+/// enough private keys are given to sign.
+fn check_private_keys<P: PartialEq>(
+    public_keys: &[P],
+    private_keys: impl IntoIterator<Item = (u8, P)>,
+    signatures_required: u8,
+) -> Result<()> {
+    let mut is_signing = vec![false; public_keys.len()];
+    for (index, public_key) in private_keys {
+        let index = index as usize;
+        ensure!(
+            index < public_keys.len(),
+            "Private key index {} is out of public key range {}",
+            index,
+            public_keys.len()
+        );
+        ensure!(!is_signing[index], "Duplicate private key index {}", index);
+        ensure!(
+            public_keys[index] == public_key,
+            "Private key does not match the public key at index {}",
+            index
+        );
+        is_signing[index] = true;
+    }
+    let num_private_keys = is_signing.iter().filter(|is_signing| **is_signing).count();
+    ensure!(
+        num_private_keys >= signatures_required as usize,
+        "{} signatures are required, but only {} private keys are given",
+        signatures_required,
+        num_private_keys
+    );
+    Ok(())
+}
+
+/// A k-of-n MultiEd25519 account, of which at least k private keys are held locally.
+#[derive(Debug)]
+pub struct MultiEd25519Account {
+    public_key: MultiEd25519PublicKey,
+    /// The private keys, along with the indices of their public keys in `public_key`.
+    private_keys: Vec<(u8, Ed25519PrivateKey)>,
+}
+
+impl MultiEd25519Account {
+    pub fn new(
+        public_key: MultiEd25519PublicKey,
+        private_keys: Vec<(u8, Ed25519PrivateKey)>,
+    ) -> Result<Self> {
+        check_private_keys(
+            public_key.public_keys(),
+            private_keys
+                .iter()
+                .map(|(index, private_key)| (*index, private_key.public_key())),
+            *public_key.threshold(),
+        )?;
+        Ok(Self {
+            public_key,
+            private_keys,
+        })
+    }
+
+    pub fn public_key(&self) -> &MultiEd25519PublicKey {
+        &self.public_key
+    }
+
+    pub fn authentication_key(&self) -> AuthenticationKey {
+        AuthenticationKey::multi_ed25519(&self.public_key)
+    }
+
+    /// Signs `message` with the first k private keys.
+    pub fn sign<T: CryptoHash + Serialize>(&self, message: &T) -> Result<MultiEd25519Signature> {
+        let signatures = self
+            .private_keys
+            .iter()
+            .take(*self.public_key.threshold() as usize)
+            .map(|(index, private_key)| Ok((private_key.sign(message)?, *index)))
+            .collect::<Result<_>>()?;
+        Ok(MultiEd25519Signature::new(signatures)?)
+    }
+}
+
+/// A k-of-n `MultiKey` account, of which at least k private keys are held locally.
+#[derive(Debug)]
+pub struct MultiKeyAccount {
+    public_key: MultiKey,
+    /// The private keys, along with the indices of their public keys in `public_key`
+    /// (sorted by index).
+    private_keys: Vec<(u8, AnyPrivateKey)>,
+}
+
+impl MultiKeyAccount {
+    pub fn new(public_key: MultiKey, mut private_keys: Vec<(u8, AnyPrivateKey)>) -> Result<Self> {
+        // Signatures are verified in ascending index order (i.e., the bitmap order)
+        private_keys.sort_by_key(|(index, _)| *index);
+        check_private_keys(
+            public_key.public_keys(),
+            private_keys
+                .iter()
+                .map(|(index, private_key)| (*index, private_key.public_key())),
+            public_key.signatures_required(),
+        )?;
+        Ok(Self {
+            public_key,
+            private_keys,
+        })
+    }
+
+    pub fn public_key(&self) -> &MultiKey {
+        &self.public_key
+    }
+
+    pub fn authentication_key(&self) -> AuthenticationKey {
+        AuthenticationKey::multi_key(self.public_key.clone())
+    }
+
+    /// Signs `message` with the k private keys of the lowest indices.
+    pub fn sign<T: CryptoHash + Serialize>(&self, message: &T) -> Result<MultiKeyAuthenticator> {
+        let signatures = self
+            .private_keys
+            .iter()
+            .take(self.public_key.signatures_required() as usize)
+            .map(|(index, private_key)| Ok((*index, private_key.sign(message)?)))
+            .collect::<Result<_>>()?;
+        MultiKeyAuthenticator::new(self.public_key.clone(), signatures)
+    }
+}
+
 #[derive(Debug)]
 pub struct EphemeralKeyPair {
     private_key: Ed25519PrivateKey,
@@ -565,6 +965,7 @@ impl KeylessAccount {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{transaction_builder::TransactionFactory, types::chain_id::ChainId};
 
     #[test]
     fn test_recover_account_from_derive_path() {
@@ -606,4 +1007,168 @@ mod tests {
         // Test invalid private key hex literal.
         assert!(LocalAccount::from_private_key("invalid_private_key", 0).is_err());
     }
+
+    fn transaction_builder() -> TransactionBuilder {
+        TransactionFactory::new(ChainId::test()).transfer(AccountAddress::ONE, 1)
+    }
+
+    fn single_key_account(key: AnyPrivateKey) -> LocalAccount {
+        LocalAccount::new_single_key(key.authentication_key().account_address(), key, 0)
+    }
+
+    fn any_private_keys() -> Vec<AnyPrivateKey> {
+        let mut rng = rand::rngs::OsRng;
+        vec![
+            Ed25519PrivateKey::generate(&mut rng).into(),
+            secp256k1_ecdsa::PrivateKey::generate(&mut rng).into(),
+            secp256r1_ecdsa::PrivateKey::generate(&mut rng).into(),
+        ]
+    }
+
+    fn multi_key_account() -> LocalAccount {
+        let private_keys = any_private_keys();
+        let public_key = MultiKey::new(
+            private_keys.iter().map(AnyPrivateKey::public_key).collect(),
+            2,
+        )
+        .unwrap();
+        // Only the keys at indices 1 and 2 are held locally.
+        let account = MultiKeyAccount::new(
+            public_key,
+            private_keys
+                .into_iter()
+                .enumerate()
+                .skip(1)
+                .map(|(i, key)| (i as u8, key))
+                .collect(),
+        )
+        .unwrap();
+        LocalAccount::new_multi_key(account.authentication_key().account_address(), account, 0)
+    }
+
+    fn multi_ed25519_account() -> LocalAccount {
+        let private_keys: Vec<_> = (0..3)
+            .map(|_| Ed25519PrivateKey::generate(&mut rand::rngs::OsRng))
+            .collect();
+        let public_key = MultiEd25519PublicKey::new(
+            private_keys
+                .iter()
+                .map(Ed25519PrivateKey::public_key)
+                .collect(),
+            2,
+        )
+        .unwrap();
+        let account = MultiEd25519Account::new(
+            public_key,
+            private_keys
+                .into_iter()
+                .enumerate()
+                .map(|(i, key)| (i as u8, key))
+                .collect(),
+        )
+        .unwrap();
+        LocalAccount::new_multi_ed25519(account.authentication_key().account_address(), account, 0)
+    }
+
+    #[test]
+    fn test_sign_transaction_with_any_key() {
+        let mut accounts: Vec<_> = any_private_keys()
+            .into_iter()
+            .map(single_key_account)
+            .collect();
+        accounts.push(LocalAccount::generate(&mut rand::rngs::OsRng));
+        accounts.push(multi_ed25519_account());
+        accounts.push(multi_key_account());
+
+        for account in &accounts {
+            let txn = account.sign_with_transaction_builder(transaction_builder());
+            assert!(txn.verify_signature().is_ok());
+        }
+
+        let (sender, secondary_signers) = accounts.split_first().unwrap();
+        let txn = sender.sign_multi_agent_with_transaction_builder(
+            secondary_signers.iter().collect(),
+            transaction_builder(),
+        );
+        assert!(txn.verify_signature().is_ok());
+
+        let (fee_payer, secondary_signers) = secondary_signers.split_last().unwrap();
+        let txn = sender.sign_fee_payer_with_transaction_builder(
+            secondary_signers.iter().collect(),
+            fee_payer,
+            transaction_builder(),
+        );
+        assert!(txn.verify_signature().is_ok());
+    }
+
+    #[test]
+    fn test_create_multi_key_account() {
+        let private_keys = any_private_keys();
+        let public_key = MultiKey::new(
+            private_keys.iter().map(AnyPrivateKey::public_key).collect(),
+            2,
+        )
+        .unwrap();
+        let [ed25519, secp256k1, secp256r1]: [AnyPrivateKey; 3] = private_keys.try_into().unwrap();
+
+        // Not enough private keys to sign.
+        assert!(MultiKeyAccount::new(public_key.clone(), vec![(0, ed25519)]).is_err());
+
+        // A private key which does not match the public key at its index.
+        assert!(MultiKeyAccount::new(public_key, vec![(0, secp256k1), (2, secp256r1)]).is_err());
+    }
+
+    #[test]
+    fn test_sign_multi_key_descending_indices() {
+        let private_keys = any_private_keys();
+        let public_key = MultiKey::new(
+            private_keys.iter().map(AnyPrivateKey::public_key).collect(),
+            2,
+        )
+        .unwrap();
+
+        // The private keys are given in descending index order.
+        let account = MultiKeyAccount::new(
+            public_key,
+            private_keys
+                .into_iter()
+                .enumerate()
+                .map(|(i, key)| (i as u8, key))
+                .rev()
+                .collect(),
+        )
+        .unwrap();
+        let account =
+            LocalAccount::new_multi_key(account.authentication_key().account_address(), account, 0);
+
+        let txn = account.sign_with_transaction_builder(transaction_builder());
+        assert!(txn.verify_signature().is_ok());
+    }
+
+    #[test]
+    fn test_key_accessors() {
+        let mut account = LocalAccount::generate(&mut rand::rngs::OsRng);
+        assert_eq!(account.try_private_key(), Some(account.private_key()));
+        assert_eq!(account.try_public_key(), Some(account.public_key()));
+        let public_key = account.public_key().clone();
+        let old_key = account.rotate_key(AccountKey::generate(&mut rand::rngs::OsRng));
+        assert_eq!(old_key.public_key(), &public_key);
+        assert!(account
+            .try_rotate_key(AccountKey::generate(&mut rand::rngs::OsRng))
+            .is_ok());
+
+        let mut account = multi_key_account();
+        assert!(account.try_private_key().is_none());
+        assert!(account.try_public_key().is_none());
+        assert!(account.multi_key_account().is_some());
+        assert!(account
+            .try_rotate_key(AccountKey::generate(&mut rand::rngs::OsRng))
+            .is_err());
+
+        let [ed25519, ..]: [AnyPrivateKey; 3] = any_private_keys().try_into().unwrap();
+        let account = single_key_account(ed25519);
+        assert!(account.try_private_key().is_none());
+        assert!(account.single_key().is_some());
+        assert!(multi_ed25519_account().multi_ed25519_account().is_some());
+    }
 }
@@ -0,0 +1,414 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{from_base64, to_base64, CryptoKVStorage, Error, GetResponse, KVStorage};
+use aes_gcm::{
+    aead::{Aead, Payload},
+    Aes256Gcm, Key, KeyInit, Nonce,
+};
+use aptos_temppath::TempPath;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use rand::{rngs::OsRng, RngCore};
+use ring::pbkdf2;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
+use std::{
+    collections::HashMap,
+    fmt,
+    fs::{self, File, OpenOptions},
+    io::{Read, Write},
+    num::NonZeroU32,
+    path::{Path, PathBuf},
+};
+
+/// The current version of the encrypted storage file format
+const ENCRYPTED_STORAGE_VERSION: u64 = 1;
+
+// The associated data used to bind each ciphertext to its purpose
+const DATA_AAD: &[u8] = b"APTOS::EncryptedOnDiskStorage::Data";
+const DATA_KEY_AAD: &[u8] = b"APTOS::EncryptedOnDiskStorage::DataKey";
+
+// The PBKDF2 parameters used when deriving a wrapping key from a passphrase
+const PBKDF2_ITERATIONS: u32 = 600_000;
+const SALT_LENGTH: usize = 32;
+
+// The AES-256-GCM key and nonce lengths
+const KEY_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 12;
+
+/// The secret used to wrap (i.e., encrypt) the data key of an EncryptedOnDiskStorage.
+/// This is either a passphrase (from which the wrapping key is derived using
+/// PBKDF2-HMAC-SHA256), or a raw 32 byte key.
+#[derive(Clone, Eq, PartialEq)]
+pub enum WrappingKey {
+    Passphrase(String),
+    Key(Vec<u8>),
+}
+
+impl fmt::Debug for WrappingKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Never print the secret
+        match self {
+            WrappingKey::Passphrase(_) => write!(f, "WrappingKey::Passphrase(<elided>)"),
+            WrappingKey::Key(_) => write!(f, "WrappingKey::Key(<elided>)"),
+        }
+    }
+}
+
+/// The key derivation used to derive the wrapping key from the wrapping secret
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+enum KeyDerivation {
+    Pbkdf2HmacSha256 {
+        #[serde(serialize_with = "to_base64", deserialize_with = "from_base64")]
+        salt: Vec<u8>,
+        iterations: u32,
+    },
+    Raw,
+}
+
+/// An AES-256-GCM ciphertext and the nonce used to produce it
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+struct EncryptedBlob {
+    #[serde(serialize_with = "to_base64", deserialize_with = "from_base64")]
+    nonce: Vec<u8>,
+    #[serde(serialize_with = "to_base64", deserialize_with = "from_base64")]
+    ciphertext: Vec<u8>,
+}
+
+/// The contents of the storage file. The data is encrypted with a random data key,
+/// which is itself encrypted (wrapped) by the wrapping key. Rotating the wrapping key
+/// also replaces the data key, so the data is re-encrypted on rotation.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(deny_unknown_fields)]
+struct EncryptedFile {
+    version: u64,
+    key_derivation: KeyDerivation,
+    wrapped_data_key: EncryptedBlob,
+    data: EncryptedBlob,
+}
+
+/// EncryptedOnDiskStorage is a key value store that is persisted to the local filesystem,
+/// similar to OnDiskStorage. Unlike OnDiskStorage, all data is encrypted at rest using
+/// authenticated encryption (AES-256-GCM), under a data key that is wrapped by a key
+/// derived from a passphrase or a key file. Writes are atomic (i.e., the file is written
+/// to a temporary path and then renamed). Like OnDiskStorage, this is intended for
+/// single threads (or must be wrapped by a Arc<RwLock<>>).
+pub struct EncryptedOnDiskStorage {
+    file_path: PathBuf,
+    temp_path: TempPath,
+    time_service: TimeService,
+    data_key: Vec<u8>,
+    key_derivation: KeyDerivation,
+    wrapped_data_key: EncryptedBlob,
+}
+
+impl EncryptedOnDiskStorage {
+    /// Opens the encrypted storage at the given path (creating it if it does not
+    /// exist). Returns an error if the wrapping key cannot decrypt the existing storage.
+    pub fn new(file_path: PathBuf, wrapping_key: WrappingKey) -> Result<Self, Error> {
+        Self::new_with_time_service(file_path, wrapping_key, TimeService::real())
+    }
+
+    fn new_with_time_service(
+        file_path: PathBuf,
+        wrapping_key: WrappingKey,
+        time_service: TimeService,
+    ) -> Result<Self, Error> {
+        // The parent will be none when only a filename is supplied. Therefore use the current
+        // working directory provided by PathBuf::new().
+        let file_dir = file_path
+            .parent()
+            .map_or(PathBuf::new(), |p| p.to_path_buf());
+        let temp_path = TempPath::new_with_temp_dir(file_dir);
+
+        // Open the existing storage file
+        if let Some(encrypted_file) = read_encrypted_file(&file_path)? {
+            if encrypted_file.version != ENCRYPTED_STORAGE_VERSION {
+                return Err(Error::SerializationError(format!(
+                    "Unsupported encrypted storage version: {}, expected: {}",
+                    encrypted_file.version, ENCRYPTED_STORAGE_VERSION
+                )));
+            }
+            let derived_key = derive_key(&wrapping_key, &encrypted_file.key_derivation)?;
+            let data_key = decrypt(&derived_key, &encrypted_file.wrapped_data_key, DATA_KEY_AAD)?;
+            return Ok(Self {
+                file_path,
+                temp_path,
+                time_service,
+                data_key,
+                key_derivation: encrypted_file.key_derivation,
+                wrapped_data_key: encrypted_file.wrapped_data_key,
+            });
+        }
+
+        // Otherwise, create a new storage file with a fresh data key
+        let data_key = random_bytes(KEY_LENGTH);
+        let (key_derivation, wrapped_data_key) = wrap_data_key(&wrapping_key, &data_key)?;
+        let storage = Self {
+            file_path,
+            temp_path,
+            time_service,
+            data_key,
+            key_derivation,
+            wrapped_data_key,
+        };
+        storage.write(&HashMap::new())?;
+        Ok(storage)
+    }
+
+    /// Opens the encrypted storage at the given path, completing a pending rotation
+    /// if required: if the storage cannot be opened with the wrapping key, it is opened
+    /// with the previous wrapping key and then rotated to the wrapping key.
+    pub fn new_with_previous_wrapping_key(
+        file_path: PathBuf,
+        wrapping_key: WrappingKey,
+        previous_wrapping_key: WrappingKey,
+    ) -> Result<Self, Error> {
+        match Self::new(file_path.clone(), wrapping_key.clone()) {
+            Ok(storage) => Ok(storage),
+            Err(error) => {
+                let mut storage = Self::new(file_path, previous_wrapping_key).map_err(|_| error)?;
+                storage.rotate_wrapping_key(wrapping_key)?;
+                Ok(storage)
+            },
+        }
+    }
+
+    /// Rotates the wrapping key. The data is re-encrypted under a fresh data key,
+    /// which is wrapped by the new wrapping key. Once this returns, the storage can
+    /// only be opened with the new wrapping key. If the rotation fails, the storage
+    /// (both on disk and in memory) still uses the previous wrapping key.
+    pub fn rotate_wrapping_key(&mut self, new_wrapping_key: WrappingKey) -> Result<(), Error> {
+        let data = self.read()?;
+        let data_key = random_bytes(KEY_LENGTH);
+        let (key_derivation, wrapped_data_key) = wrap_data_key(&new_wrapping_key, &data_key)?;
+        let encrypted_file = encrypt_file(&data_key, &key_derivation, &wrapped_data_key, &data)?;
+        self.write_encrypted_file(&encrypted_file)?;
+
+        // Only switch to the new keys once the rotated file is durable
+        self.data_key = data_key;
+        self.key_derivation = key_derivation;
+        self.wrapped_data_key = wrapped_data_key;
+        Ok(())
+    }
+
+    fn read(&self) -> Result<HashMap<String, Value>, Error> {
+        let encrypted_file = read_encrypted_file(&self.file_path)?.ok_or_else(|| {
+            Error::InternalError(format!(
+                "Encrypted storage file is missing: {:?}",
+                self.file_path
+            ))
+        })?;
+        let contents = decrypt(&self.data_key, &encrypted_file.data, DATA_AAD)?;
+        Ok(serde_json::from_slice(&contents)?)
+    }
+
+    fn write(&self, data: &HashMap<String, Value>) -> Result<(), Error> {
+        let encrypted_file = encrypt_file(
+            &self.data_key,
+            &self.key_derivation,
+            &self.wrapped_data_key,
+            data,
+        )?;
+        self.write_encrypted_file(&encrypted_file)
+    }
+
+    fn write_encrypted_file(&self, encrypted_file: &EncryptedFile) -> Result<(), Error> {
+        // Only the owner may read or write the storage file
+        let mut options = OpenOptions::new();
+        #[cfg(unix)]
+        options.mode(0o600);
+        let mut file = options
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(self.temp_path.path())?;
+        file.write_all(&serde_json::to_vec(encrypted_file)?)?;
+        file.sync_all()?;
+        fs::rename(&self.temp_path, &self.file_path)?;
+        sync_parent_dir(&self.file_path)
+    }
+
+    /// Returns the temporary path used for atomic writes (e.g., to inject write failures)
+    #[cfg(test)]
+    pub(crate) fn temp_path(&self) -> &Path {
+        self.temp_path.path()
+    }
+}
+
+/// Syncs the directory containing the given file, so that a rename into it is durable.
+#[cfg(unix)]
+fn sync_parent_dir(file_path: &Path) -> Result<(), Error> {
+    let dir = match file_path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    File::open(dir)?.sync_all()?;
+    Ok(())
+}
+
+/// Directories cannot be opened (and synced) as files on non-unix platforms.
+#[cfg(not(unix))]
+fn sync_parent_dir(_file_path: &Path) -> Result<(), Error> {
+    Ok(())
+}
+
+impl KVStorage for EncryptedOnDiskStorage {
+    fn available(&self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn get<V: DeserializeOwned>(&self, key: &str) -> Result<GetResponse<V>, Error> {
+        let mut data = self.read()?;
+        data.remove(key)
+            .ok_or_else(|| Error::KeyNotSet(key.to_string()))
+            .and_then(|value| serde_json::from_value(value).map_err(|e| e.into()))
+    }
+
+    fn set<V: Serialize>(&mut self, key: &str, value: V) -> Result<(), Error> {
+        let now = self.time_service.now_secs();
+        let mut data = self.read()?;
+        data.insert(
+            key.to_string(),
+            serde_json::to_value(&GetResponse::new(value, now))?,
+        );
+        self.write(&data)
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    fn reset_and_clear(&mut self) -> Result<(), Error> {
+        self.write(&HashMap::new())
+    }
+}
+
+impl CryptoKVStorage for EncryptedOnDiskStorage {}
+
+/// Reads the encrypted file at the given path. Returns None if the file
+/// does not exist (or is empty).
+fn read_encrypted_file(file_path: &Path) -> Result<Option<EncryptedFile>, Error> {
+    if !file_path.exists() {
+        return Ok(None);
+    }
+    let mut file = File::open(file_path)?;
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)?;
+    if contents.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::from_str(&contents)?))
+}
+
+/// Encrypts the given data under the data key, and bundles it with the wrapped data key
+fn encrypt_file(
+    data_key: &[u8],
+    key_derivation: &KeyDerivation,
+    wrapped_data_key: &EncryptedBlob,
+    data: &HashMap<String, Value>,
+) -> Result<EncryptedFile, Error> {
+    let contents = serde_json::to_vec(data)?;
+    Ok(EncryptedFile {
+        version: ENCRYPTED_STORAGE_VERSION,
+        key_derivation: key_derivation.clone(),
+        wrapped_data_key: wrapped_data_key.clone(),
+        data: encrypt(data_key, &contents, DATA_AAD)?,
+    })
+}
+
+/// Wraps the data key using a freshly derived wrapping key
+fn wrap_data_key(
+    wrapping_key: &WrappingKey,
+    data_key: &[u8],
+) -> Result<(KeyDerivation, EncryptedBlob), Error> {
+    let key_derivation = match wrapping_key {
+        WrappingKey::Passphrase(_) => KeyDerivation::Pbkdf2HmacSha256 {
+            salt: random_bytes(SALT_LENGTH),
+            iterations: PBKDF2_ITERATIONS,
+        },
+        WrappingKey::Key(_) => KeyDerivation::Raw,
+    };
+    let derived_key = derive_key(wrapping_key, &key_derivation)?;
+    let wrapped_data_key = encrypt(&derived_key, data_key, DATA_KEY_AAD)?;
+    Ok((key_derivation, wrapped_data_key))
+}
+
+/// Derives the AES-256-GCM wrapping key from the wrapping secret
+fn derive_key(
+    wrapping_key: &WrappingKey,
+    key_derivation: &KeyDerivation,
+) -> Result<Vec<u8>, Error> {
+    match (wrapping_key, key_derivation) {
+        (
+            WrappingKey::Passphrase(passphrase),
+            KeyDerivation::Pbkdf2HmacSha256 { salt, iterations },
+        ) => {
+            let iterations = NonZeroU32::new(*iterations).ok_or_else(|| {
+                Error::SerializationError("PBKDF2 iterations must be non-zero".into())
+            })?;
+            let mut derived_key = vec![0u8; KEY_LENGTH];
+            pbkdf2::derive(
+                pbkdf2::PBKDF2_HMAC_SHA256,
+                iterations,
+                salt,
+                passphrase.as_bytes(),
+                &mut derived_key,
+            );
+            Ok(derived_key)
+        },
+        (WrappingKey::Key(key), KeyDerivation::Raw) => {
+            if key.len() != KEY_LENGTH {
+                return Err(Error::InternalError(format!(
+                    "Invalid wrapping key length: {}, expected: {}",
+                    key.len(),
+                    KEY_LENGTH
+                )));
+            }
+            Ok(key.clone())
+        },
+        (wrapping_key, key_derivation) => Err(Error::InternalError(format!(
+            "The wrapping key ({:?}) does not match the storage key derivation ({:?})",
+            wrapping_key, key_derivation
+        ))),
+    }
+}
+
+fn encrypt(key: &[u8], plaintext: &[u8], aad: &[u8]) -> Result<EncryptedBlob, Error> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let nonce = random_bytes(NONCE_LENGTH);
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), Payload {
+            msg: plaintext,
+            aad,
+        })
+        .map_err(|error| Error::InternalError(format!("Encryption failed: {}", error)))?;
+    Ok(EncryptedBlob { nonce, ciphertext })
+}
+
+fn decrypt(key: &[u8], blob: &EncryptedBlob, aad: &[u8]) -> Result<Vec<u8>, Error> {
+    if blob.nonce.len() != NONCE_LENGTH {
+        return Err(Error::SerializationError(format!(
+            "Invalid nonce length: {}, expected: {}",
+            blob.nonce.len(),
+            NONCE_LENGTH
+        )));
+    }
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    cipher
+        .decrypt(Nonce::from_slice(&blob.nonce), Payload {
+            msg: &blob.ciphertext,
+            aad,
+        })
+        .map_err(|_| {
+            Error::DecryptionError(
+                "Unable to decrypt the storage (is the wrapping key correct?)".into(),
+            )
+        })
+}
+
+fn random_bytes(length: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; length];
+    OsRng.fill_bytes(&mut bytes);
+    bytes
+}
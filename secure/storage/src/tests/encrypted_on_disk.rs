@@ -0,0 +1,180 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    tests::suite, EncryptedOnDiskStorage, Error, KVStorage, OnDiskStorage, Storage, WrappingKey,
+};
+use aptos_temppath::TempPath;
+
+// Useful test constants
+const SECRET_KEY: &str = "Secret_Key";
+const SECRET_VALUE: &str = "Secret_Value";
+
+fn create_wrapping_key(byte: u8) -> WrappingKey {
+    WrappingKey::Key(vec![byte; 32])
+}
+
+#[test]
+fn encrypted_on_disk() {
+    let path_buf = TempPath::new().path().to_path_buf();
+    let storage = EncryptedOnDiskStorage::new(path_buf, create_wrapping_key(0)).unwrap();
+    suite::execute_all_storage_tests(&mut Storage::from(storage));
+}
+
+#[test]
+fn encrypted_on_disk_data_is_not_plaintext() {
+    let path_buf = TempPath::new().path().to_path_buf();
+    let mut storage =
+        EncryptedOnDiskStorage::new(path_buf.clone(), create_wrapping_key(0)).unwrap();
+    storage.set(SECRET_KEY, SECRET_VALUE).unwrap();
+
+    let contents = std::fs::read_to_string(&path_buf).unwrap();
+    assert!(!contents.contains(SECRET_KEY));
+    assert!(!contents.contains(SECRET_VALUE));
+
+    // Verify the plaintext on disk storage cannot read the data
+    let on_disk_storage = OnDiskStorage::new(path_buf);
+    assert!(on_disk_storage.get::<String>(SECRET_KEY).is_err());
+}
+
+#[test]
+fn encrypted_on_disk_reopen() {
+    let path_buf = TempPath::new().path().to_path_buf();
+    let passphrase = WrappingKey::Passphrase("correct horse battery staple".into());
+    let mut storage = EncryptedOnDiskStorage::new(path_buf.clone(), passphrase.clone()).unwrap();
+    storage.set(SECRET_KEY, SECRET_VALUE).unwrap();
+
+    // Verify the storage can be reopened with the same passphrase
+    let storage = EncryptedOnDiskStorage::new(path_buf.clone(), passphrase).unwrap();
+    assert_eq!(
+        storage.get::<String>(SECRET_KEY).unwrap().value,
+        SECRET_VALUE
+    );
+
+    // Verify the storage cannot be opened with a different passphrase or key
+    let wrong_passphrase = WrappingKey::Passphrase("incorrect horse battery staple".into());
+    assert!(matches!(
+        EncryptedOnDiskStorage::new(path_buf.clone(), wrong_passphrase),
+        Err(Error::DecryptionError(_))
+    ));
+    assert!(EncryptedOnDiskStorage::new(path_buf, create_wrapping_key(0)).is_err());
+}
+
+#[test]
+fn encrypted_on_disk_tampering() {
+    let path_buf = TempPath::new().path().to_path_buf();
+    let mut storage =
+        EncryptedOnDiskStorage::new(path_buf.clone(), create_wrapping_key(0)).unwrap();
+    storage.set(SECRET_KEY, SECRET_VALUE).unwrap();
+
+    // Flip a character in the encrypted data and verify decryption fails
+    let mut file: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path_buf).unwrap()).unwrap();
+    let ciphertext = file["data"]["ciphertext"].as_str().unwrap().to_string();
+    let mut ciphertext = base64::decode(ciphertext).unwrap();
+    ciphertext[0] ^= 1;
+    file["data"]["ciphertext"] = serde_json::Value::String(base64::encode(ciphertext));
+    std::fs::write(&path_buf, serde_json::to_vec(&file).unwrap()).unwrap();
+
+    assert!(matches!(
+        storage.get::<String>(SECRET_KEY),
+        Err(Error::DecryptionError(_))
+    ));
+}
+
+#[test]
+fn encrypted_on_disk_rotate_wrapping_key() {
+    let path_buf = TempPath::new().path().to_path_buf();
+    let mut storage =
+        EncryptedOnDiskStorage::new(path_buf.clone(), create_wrapping_key(0)).unwrap();
+    storage.set(SECRET_KEY, SECRET_VALUE).unwrap();
+
+    // Rotate the wrapping key and verify the data is still readable
+    storage.rotate_wrapping_key(create_wrapping_key(1)).unwrap();
+    assert_eq!(
+        storage.get::<String>(SECRET_KEY).unwrap().value,
+        SECRET_VALUE
+    );
+
+    // Verify only the new wrapping key can open the storage
+    assert!(EncryptedOnDiskStorage::new(path_buf.clone(), create_wrapping_key(0)).is_err());
+    let storage = EncryptedOnDiskStorage::new(path_buf, create_wrapping_key(1)).unwrap();
+    assert_eq!(
+        storage.get::<String>(SECRET_KEY).unwrap().value,
+        SECRET_VALUE
+    );
+}
+
+#[test]
+fn encrypted_on_disk_rotate_wrapping_key_write_failure() {
+    let path_buf = TempPath::new().path().to_path_buf();
+    let mut storage =
+        EncryptedOnDiskStorage::new(path_buf.clone(), create_wrapping_key(0)).unwrap();
+    storage.set(SECRET_KEY, SECRET_VALUE).unwrap();
+
+    // Block the temporary file with a directory so that the rotated file cannot be written
+    std::fs::create_dir(storage.temp_path()).unwrap();
+    assert!(storage.rotate_wrapping_key(create_wrapping_key(1)).is_err());
+
+    // Verify the storage still uses the previous wrapping key (in memory and on disk)
+    assert_eq!(
+        storage.get::<String>(SECRET_KEY).unwrap().value,
+        SECRET_VALUE
+    );
+    assert!(EncryptedOnDiskStorage::new(path_buf.clone(), create_wrapping_key(1)).is_err());
+    EncryptedOnDiskStorage::new(path_buf.clone(), create_wrapping_key(0)).unwrap();
+
+    // Unblock the temporary file and verify the rotation succeeds
+    std::fs::remove_dir(storage.temp_path()).unwrap();
+    storage.rotate_wrapping_key(create_wrapping_key(1)).unwrap();
+    assert_eq!(
+        storage.get::<String>(SECRET_KEY).unwrap().value,
+        SECRET_VALUE
+    );
+    assert!(EncryptedOnDiskStorage::new(path_buf.clone(), create_wrapping_key(0)).is_err());
+    EncryptedOnDiskStorage::new(path_buf, create_wrapping_key(1)).unwrap();
+}
+
+#[test]
+fn encrypted_on_disk_previous_wrapping_key() {
+    let path_buf = TempPath::new().path().to_path_buf();
+    let mut storage =
+        EncryptedOnDiskStorage::new(path_buf.clone(), create_wrapping_key(0)).unwrap();
+    storage.set(SECRET_KEY, SECRET_VALUE).unwrap();
+
+    // Open the storage with a new wrapping key (and the previous one), and verify it was rotated
+    let storage = EncryptedOnDiskStorage::new_with_previous_wrapping_key(
+        path_buf.clone(),
+        create_wrapping_key(1),
+        create_wrapping_key(0),
+    )
+    .unwrap();
+    assert_eq!(
+        storage.get::<String>(SECRET_KEY).unwrap().value,
+        SECRET_VALUE
+    );
+    assert!(EncryptedOnDiskStorage::new(path_buf.clone(), create_wrapping_key(0)).is_err());
+
+    // Verify reopening with the same keys is a no-op (the rotation already completed)
+    EncryptedOnDiskStorage::new_with_previous_wrapping_key(
+        path_buf.clone(),
+        create_wrapping_key(1),
+        create_wrapping_key(0),
+    )
+    .unwrap();
+    EncryptedOnDiskStorage::new(path_buf.clone(), create_wrapping_key(1)).unwrap();
+
+    // Verify the storage cannot be opened if neither key matches
+    assert!(EncryptedOnDiskStorage::new_with_previous_wrapping_key(
+        path_buf,
+        create_wrapping_key(2),
+        create_wrapping_key(3),
+    )
+    .is_err());
+}
+
+#[test]
+fn encrypted_on_disk_invalid_key_length() {
+    let path_buf = TempPath::new().path().to_path_buf();
+    assert!(EncryptedOnDiskStorage::new(path_buf, WrappingKey::Key(vec![0; 16])).is_err());
+}
@@ -20,7 +20,7 @@ use crate::{
     utils,
 };
 use aptos_config::{
-    config::{AptosDataClientConfig, BaseConfig},
+    config::{AptosDataClientConfig, BaseConfig, PeerSelectionStrategy},
     network_id::PeerNetworkId,
 };
 use aptos_id_generator::{IdGenerator, U64IdGenerator};
@@ -36,20 +36,23 @@ use aptos_storage_service_types::{
     requests::{
         DataRequest, EpochEndingLedgerInfoRequest, NewTransactionOutputsWithProofRequest,
         NewTransactionsOrOutputsWithProofRequest, NewTransactionsWithProofRequest,
-        StateValuesWithProofRequest, StorageServiceRequest,
+        StateValueWithProofRequest, StateValuesWithProofRequest, StorageServiceRequest,
         SubscribeTransactionOutputsWithProofRequest,
         SubscribeTransactionsOrOutputsWithProofRequest, SubscribeTransactionsWithProofRequest,
         SubscriptionStreamMetadata, TransactionOutputsWithProofRequest,
         TransactionsOrOutputsWithProofRequest, TransactionsWithProofRequest,
     },
-    responses::{StorageServerSummary, StorageServiceResponse, TransactionOrOutputListWithProof},
+    responses::{
+        ServerProtocolVersion, StateValueWithProof, StorageServerSummary, StorageServiceResponse,
+        TransactionOrOutputListWithProof,
+    },
     Epoch, StorageServiceMessage,
 };
-use aptos_time_service::TimeService;
+use aptos_time_service::{TimeService, TimeServiceTrait};
 use aptos_types::{
     epoch_change::EpochChangeProof,
     ledger_info::LedgerInfoWithSignatures,
-    state_store::state_value::StateValueChunkWithProof,
+    state_store::{state_key::StateKey, state_value::StateValueChunkWithProof},
     transaction::{TransactionListWithProof, TransactionOutputListWithProof, Version},
 };
 use arc_swap::ArcSwap;
@@ -68,6 +71,7 @@ use tokio::runtime::Handle;
 
 // Useful constants
 const PEER_METRICS_FREQ_SECS: u64 = 5; // The frequency to update peer metrics and logs
+const PROTOCOL_VERSION_ERROR_LOG_FREQ_SECS: u64 = 10; // The frequency to log protocol version fetch errors
 
 /// An [`AptosDataClientInterface`] that fulfills requests from remote peers' Storage Service
 /// over AptosNet.
@@ -372,8 +376,9 @@ impl AptosDataClient {
     }
 
     /// Chooses several peers to service the specific data request.
-    /// Peers are selected first by priority, and then by latency
-    /// (within priority groups).
+    /// Peers are selected first by priority, and then by latency or
+    /// measured throughput (within priority groups), depending on the
+    /// configured peer selection strategy.
     fn choose_peers_for_specific_data_request(
         &self,
         request: &StorageServiceRequest,
@@ -383,9 +388,23 @@ impl AptosDataClient {
         // Select peers by priority (starting with the highest priority first)
         let mut selected_peers = HashSet::new();
         for serviceable_peers in serviceable_peers_by_priorities {
-            // Select peers by distance and latency
+            // Select peers using the peer selection strategy
             let num_peers_remaining = num_peers_for_request.saturating_sub(selected_peers.len());
-            let peers = self.choose_random_peers_by_latency(serviceable_peers, num_peers_remaining);
+            let peers = match self
+                .data_client_config
+                .peer_selection_config
+                .peer_selection_strategy
+            {
+                PeerSelectionStrategy::Latency => {
+                    self.choose_random_peers_by_latency(serviceable_peers, num_peers_remaining)
+                },
+                PeerSelectionStrategy::AdaptiveThroughput => self
+                    .choose_random_peers_by_throughput(
+                        request,
+                        serviceable_peers,
+                        num_peers_remaining,
+                    ),
+            };
 
             // Add the peers to the entire set
             selected_peers.extend(peers);
@@ -522,6 +541,27 @@ impl AptosDataClient {
         utils::extend_with_random_peers(selected_peers, serviceable_peers, num_peers_to_choose)
     }
 
+    /// Chooses peers randomly weighted by measured throughput and failure
+    /// history (for the request type) from the given set of serviceable peers
+    fn choose_random_peers_by_throughput(
+        &self,
+        request: &StorageServiceRequest,
+        serviceable_peers: HashSet<PeerNetworkId>,
+        num_peers_to_choose: usize,
+    ) -> HashSet<PeerNetworkId> {
+        // Choose peers weighted by throughput
+        let selected_peers = utils::choose_random_peers_by_throughput(
+            self.data_client_config.clone(),
+            num_peers_to_choose as u64,
+            serviceable_peers.clone(),
+            self.peer_states.clone(),
+            request,
+        );
+
+        // Extend the selected peers with random peers (if necessary)
+        utils::extend_with_random_peers(selected_peers, serviceable_peers, num_peers_to_choose)
+    }
+
     /// Identifies the peers with the specified priority that can service the given request
     fn identify_serviceable(
         &self,
@@ -773,6 +813,7 @@ impl AptosDataClient {
         self.update_sent_request_metrics(peer, &request);
 
         // Send the request and process the result
+        let request_start_time = self.time_service.now();
         let result = self
             .storage_service_client
             .send_request(
@@ -802,6 +843,18 @@ impl AptosDataClient {
                 // feels simpler for the consumer.
                 self.peer_states.update_score_success(peer);
 
+                // Update the measured performance of the peer
+                let request_duration = self
+                    .time_service
+                    .now()
+                    .saturating_duration_since(request_start_time);
+                self.peer_states.update_request_performance_success(
+                    peer,
+                    &request,
+                    utils::get_num_response_bytes(&response),
+                    request_duration,
+                );
+
                 // Package up all of the context needed to fully report an error
                 // with this RPC.
                 let response_callback = AptosNetResponseCallback {
@@ -859,10 +912,65 @@ impl AptosDataClient {
         &self,
         _id: ResponseId,
         peer: PeerNetworkId,
-        _request: &StorageServiceRequest,
+        request: &StorageServiceRequest,
         error_type: ErrorType,
     ) {
         self.peer_states.update_score_error(peer, error_type);
+        self.peer_states
+            .update_request_performance_failure(peer, request);
+    }
+
+    /// Fetches the storage server protocol versions of all connected peers
+    /// that have advertised a storage summary, but whose version is unknown.
+    /// This is required before sending requests that older servers cannot
+    /// service (e.g., single state value requests).
+    async fn fetch_unknown_protocol_versions(&self, request_timeout_ms: u64) {
+        // Identify the peers with an unknown protocol version
+        let connected_peers = match self.get_all_connected_peers() {
+            Ok(connected_peers) => connected_peers,
+            Err(_) => return, // There are no peers to fetch versions from
+        };
+        let peers = self
+            .peer_states
+            .get_peers_with_unknown_protocol_version(connected_peers);
+
+        // Fetch the protocol versions concurrently
+        let mut pending_requests = peers
+            .into_iter()
+            .map(|peer| async move {
+                let request = StorageServiceRequest::new(
+                    DataRequest::GetServerProtocolVersion,
+                    self.data_client_config.use_compression,
+                );
+                let result: crate::error::Result<Response<ServerProtocolVersion>> = self
+                    .send_request_to_peer_and_decode(peer, request, request_timeout_ms)
+                    .await;
+                (peer, result)
+            })
+            .collect::<FuturesUnordered<_>>();
+
+        // Update the protocol versions of the peers that responded
+        while let Some((peer, result)) = pending_requests.next().await {
+            match result {
+                Ok(response) => {
+                    let protocol_version = response.into_payload().protocol_version;
+                    self.peer_states
+                        .update_protocol_version(peer, protocol_version);
+                },
+                Err(error) => {
+                    sample!(
+                        SampleRate::Duration(Duration::from_secs(PROTOCOL_VERSION_ERROR_LOG_FREQ_SECS)),
+                        warn!(
+                            (LogSchema::new(LogEntry::StorageServiceResponse)
+                                .event(LogEvent::ResponseError)
+                                .message("Failed to fetch the protocol version of a peer!")
+                                .error(&error)
+                                .peer(&peer))
+                        );
+                    );
+                },
+            }
+        }
     }
 
     /// Creates a storage service request using the given data request
@@ -997,6 +1105,27 @@ impl AptosDataClientInterface for AptosDataClient {
             .await
     }
 
+    async fn get_state_value_with_proof(
+        &self,
+        state_key: StateKey,
+        version: Version,
+        proof_version: Version,
+        request_timeout_ms: u64,
+    ) -> crate::error::Result<Response<StateValueWithProof>> {
+        // Older servers cannot service single state value requests, so
+        // we only send the request to peers with a known protocol version.
+        self.fetch_unknown_protocol_versions(request_timeout_ms)
+            .await;
+
+        let data_request = DataRequest::GetStateValueWithProof(StateValueWithProofRequest {
+            state_key,
+            version,
+            proof_version,
+        });
+        self.create_and_send_storage_request(request_timeout_ms, data_request)
+            .await
+    }
+
     async fn get_state_values_with_proof(
         &self,
         version: u64,
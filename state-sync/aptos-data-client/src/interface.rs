@@ -2,10 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{error, error::Error, global_summary::GlobalDataSummary};
-use aptos_storage_service_types::{responses::TransactionOrOutputListWithProof, Epoch};
+use aptos_storage_service_types::{
+    responses::{StateValueWithProof, TransactionOrOutputListWithProof},
+    Epoch,
+};
 use aptos_types::{
     ledger_info::LedgerInfoWithSignatures,
-    state_store::state_value::StateValueChunkWithProof,
+    state_store::{state_key::StateKey, state_value::StateValueChunkWithProof},
     transaction::{TransactionListWithProof, TransactionOutputListWithProof, Version},
 };
 use async_trait::async_trait;
@@ -74,6 +77,18 @@ pub trait AptosDataClientInterface {
         request_timeout_ms: u64,
     ) -> error::Result<Response<u64>>;
 
+    /// Fetches a single state value (which may not exist) for the given
+    /// state key at the specified version. The proof is relative to the
+    /// specified `proof_version`. If the data cannot be fetched, an error
+    /// is returned.
+    async fn get_state_value_with_proof(
+        &self,
+        state_key: StateKey,
+        version: Version,
+        proof_version: Version,
+        request_timeout_ms: u64,
+    ) -> error::Result<Response<StateValueWithProof>>;
+
     /// Fetches a single state value chunk with proof, containing the values
     /// from start to end index (inclusive) at the specified version. The proof
     /// version is the same as the specified version. In some cases, fewer
@@ -13,7 +13,8 @@ use aptos_config::{
 };
 use aptos_logger::prelude::*;
 use aptos_storage_service_types::{
-    requests::StorageServiceRequest, responses::StorageServerSummary,
+    requests::{DataRequest, StorageServiceRequest},
+    responses::{StorageServerSummary, STATE_VALUE_REQUEST_PROTOCOL_VERSION},
 };
 use aptos_time_service::TimeService;
 use dashmap::DashMap;
@@ -42,6 +43,10 @@ const MALICIOUS_MULTIPLIER: f64 = 0.8;
 /// Ignore a peer when their score dips below this threshold.
 const IGNORE_PEER_THRESHOLD: f64 = 25.0;
 
+/// The minimum request duration used when calculating throughput (to
+/// avoid unrealistic measurements for very fast responses).
+const MIN_REQUEST_DURATION_SECS_FOR_THROUGHPUT: f64 = 0.001; // 1 ms
+
 pub enum ErrorType {
     /// A response or error that's not actively malicious but also doesn't help
     /// us make progress, e.g., timeouts, remote errors, invalid data, etc...
@@ -51,6 +56,47 @@ pub enum ErrorType {
     Malicious,
 }
 
+/// The measured performance of a peer for a single request type
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RequestPerformance {
+    num_samples: u64,        // The number of successful measurements
+    average_throughput: f64, // The moving average of the throughput (bytes per second)
+    failure_rate: f64,       // The moving average of the failure rate (between 0 and 1)
+}
+
+impl RequestPerformance {
+    /// Returns the number of successful measurements
+    pub fn get_num_samples(&self) -> u64 {
+        self.num_samples
+    }
+
+    /// Returns the moving average of the throughput (bytes per second)
+    pub fn get_average_throughput(&self) -> f64 {
+        self.average_throughput
+    }
+
+    /// Returns the moving average of the failure rate (between 0 and 1)
+    pub fn get_failure_rate(&self) -> f64 {
+        self.failure_rate
+    }
+
+    /// Updates the performance according to a successful request
+    fn update_success(&mut self, throughput: f64, moving_average_weight: f64) {
+        self.average_throughput = if self.num_samples == 0 {
+            throughput // This is the first measurement
+        } else {
+            calculate_moving_average(self.average_throughput, throughput, moving_average_weight)
+        };
+        self.failure_rate = calculate_moving_average(self.failure_rate, 0.0, moving_average_weight);
+        self.num_samples += 1;
+    }
+
+    /// Updates the performance according to a failed request
+    fn update_failure(&mut self, moving_average_weight: f64) {
+        self.failure_rate = calculate_moving_average(self.failure_rate, 1.0, moving_average_weight);
+    }
+}
+
 impl From<ResponseError> for ErrorType {
     fn from(error: ResponseError) -> Self {
         match error {
@@ -70,6 +116,9 @@ pub struct PeerState {
     /// The number of responses received from this peer (by data request label)
     received_responses_by_type: Arc<DashMap<String, u64>>,
 
+    /// The measured performance of this peer (by data request label)
+    request_performance_by_type: Arc<DashMap<String, RequestPerformance>>,
+
     /// The number of requests sent to this peer (by data request label)
     sent_requests_by_type: Arc<DashMap<String, u64>>,
 
@@ -77,6 +126,10 @@ pub struct PeerState {
     /// haven't polled them yet.
     storage_summary: Option<StorageServerSummary>,
 
+    /// The storage server protocol version run by this peer, or `None` if
+    /// we haven't fetched it yet (it is only fetched on demand).
+    protocol_version: Option<u64>,
+
     /// For now, a simplified port of the original state-sync v1 scoring system.
     score: f64,
 }
@@ -86,8 +139,10 @@ impl PeerState {
         Self {
             data_client_config,
             received_responses_by_type: Arc::new(DashMap::new()),
+            request_performance_by_type: Arc::new(DashMap::new()),
             sent_requests_by_type: Arc::new(DashMap::new()),
             storage_summary: None,
+            protocol_version: None,
             score: STARTING_SCORE,
         }
     }
@@ -110,6 +165,13 @@ impl PeerState {
             .or_insert(1);
     }
 
+    /// Returns the measured performance of the peer for the given label
+    pub fn get_request_performance(&self, request_label: &str) -> Option<RequestPerformance> {
+        self.request_performance_by_type
+            .get(request_label)
+            .map(|request_performance| request_performance.clone())
+    }
+
     /// Returns the peer's score
     pub fn get_score(&self) -> f64 {
         self.score
@@ -120,6 +182,25 @@ impl PeerState {
         self.storage_summary.clone()
     }
 
+    /// Returns the storage server protocol version of the peer (if known)
+    pub fn get_protocol_version(&self) -> Option<u64> {
+        self.protocol_version
+    }
+
+    /// Returns true iff the peer's protocol version supports the given request.
+    /// Requests that were added to the protocol later (e.g., single state value
+    /// requests) are only sent to peers known to run a recent enough version.
+    fn supports_request(&self, request: &StorageServiceRequest) -> bool {
+        match request.data_request {
+            DataRequest::GetStateValueWithProof(_) => {
+                self.protocol_version.map_or(false, |version| {
+                    version >= STATE_VALUE_REQUEST_PROTOCOL_VERSION
+                })
+            },
+            _ => true,
+        }
+    }
+
     /// Returns a sorted copy of the sent requests by type map
     pub fn get_sent_requests_by_type(&self) -> BTreeMap<String, u64> {
         let mut sorted_requests_by_type = BTreeMap::new();
@@ -173,10 +254,43 @@ impl PeerState {
         self.score = f64::max(self.score * multiplier, MIN_SCORE);
     }
 
+    /// Updates the measured performance of the peer according
+    /// to a successful request (with the given throughput).
+    fn update_request_performance_success(&mut self, request_label: String, throughput: f64) {
+        let moving_average_weight = self.get_moving_average_weight();
+        self.request_performance_by_type
+            .entry(request_label)
+            .or_default()
+            .update_success(throughput, moving_average_weight);
+    }
+
+    /// Updates the measured performance of the peer according to a failed request
+    fn update_request_performance_failure(&mut self, request_label: String) {
+        let moving_average_weight = self.get_moving_average_weight();
+        self.request_performance_by_type
+            .entry(request_label)
+            .or_default()
+            .update_failure(moving_average_weight);
+    }
+
+    /// Returns the weight given to new measurements in the moving averages
+    fn get_moving_average_weight(&self) -> f64 {
+        let moving_average_weight_percentage = self
+            .data_client_config
+            .peer_selection_config
+            .moving_average_weight_percentage;
+        min(moving_average_weight_percentage, 100) as f64 / 100.0
+    }
+
     /// Updates the storage summary for the peer
     fn update_storage_summary(&mut self, storage_summary: StorageServerSummary) {
         self.storage_summary = Some(storage_summary);
     }
+
+    /// Updates the storage server protocol version for the peer
+    fn update_protocol_version(&mut self, protocol_version: u64) {
+        self.protocol_version = Some(protocol_version);
+    }
 }
 
 /// Contains all of the unbanned peers' most recent [`StorageServerSummary`] data
@@ -216,7 +330,12 @@ impl PeerStates {
         if let Some(peer_state) = self.peer_to_state.get(peer) {
             return match peer_state.get_storage_summary_if_not_ignored() {
                 Some(storage_summary) => {
-                    storage_summary.can_service(&self.data_client_config, time_service, request)
+                    peer_state.supports_request(request)
+                        && storage_summary.can_service(
+                            &self.data_client_config,
+                            time_service,
+                            request,
+                        )
                 },
                 None => false, // The peer is temporarily ignored
             };
@@ -321,6 +440,52 @@ impl PeerStates {
         }
     }
 
+    /// Updates the measured performance of the given peer according to a
+    /// successful request (that returned the given number of bytes).
+    pub fn update_request_performance_success(
+        &self,
+        peer: PeerNetworkId,
+        request: &StorageServiceRequest,
+        num_response_bytes: u64,
+        request_duration: Duration,
+    ) {
+        // Calculate the throughput of the request
+        let request_duration_secs = f64::max(
+            request_duration.as_secs_f64(),
+            MIN_REQUEST_DURATION_SECS_FOR_THROUGHPUT,
+        );
+        let throughput = num_response_bytes as f64 / request_duration_secs;
+
+        // Update the peer's performance
+        let request_label = request.data_request.get_label().into();
+        if let Some(mut entry) = self.peer_to_state.get_mut(&peer) {
+            entry.update_request_performance_success(request_label, throughput);
+        }
+    }
+
+    /// Updates the measured performance of the given peer according to a failed request
+    pub fn update_request_performance_failure(
+        &self,
+        peer: PeerNetworkId,
+        request: &StorageServiceRequest,
+    ) {
+        let request_label = request.data_request.get_label().into();
+        if let Some(mut entry) = self.peer_to_state.get_mut(&peer) {
+            entry.update_request_performance_failure(request_label);
+        }
+    }
+
+    /// Returns the measured performance of the given peer for the given request
+    pub fn get_request_performance(
+        &self,
+        peer: &PeerNetworkId,
+        request: &StorageServiceRequest,
+    ) -> Option<RequestPerformance> {
+        self.peer_to_state.get(peer).and_then(|peer_state| {
+            peer_state.get_request_performance(request.data_request.get_label())
+        })
+    }
+
     /// Updates the storage summary for the given peer
     pub fn update_summary(&self, peer: PeerNetworkId, storage_summary: StorageServerSummary) {
         self.peer_to_state
@@ -329,6 +494,29 @@ impl PeerStates {
             .update_storage_summary(storage_summary);
     }
 
+    /// Updates the storage server protocol version for the given peer
+    pub fn update_protocol_version(&self, peer: PeerNetworkId, protocol_version: u64) {
+        if let Some(mut peer_state) = self.peer_to_state.get_mut(&peer) {
+            peer_state.update_protocol_version(protocol_version);
+        }
+    }
+
+    /// Returns the peers (of the given peers) that have advertised a storage
+    /// summary, but whose protocol version has not been fetched yet.
+    pub fn get_peers_with_unknown_protocol_version(
+        &self,
+        peers: HashSet<PeerNetworkId>,
+    ) -> Vec<PeerNetworkId> {
+        peers
+            .into_iter()
+            .filter(|peer| {
+                self.peer_to_state.get(peer).map_or(false, |peer_state| {
+                    peer_state.storage_summary.is_some() && peer_state.protocol_version.is_none()
+                })
+            })
+            .collect()
+    }
+
     /// Garbage collects the peer states to remove data for disconnected peers
     pub fn garbage_collect_peer_states(&self, connected_peers: HashSet<PeerNetworkId>) {
         self.peer_to_state
@@ -455,6 +643,12 @@ fn median_or_max<T: Ord + Copy>(mut values: Vec<T>, max_value: T) -> T {
     min(median.unwrap_or(max_value), max_value)
 }
 
+/// Calculates the exponential moving average using the given
+/// previous average, new value and new value weight.
+fn calculate_moving_average(previous_average: f64, new_value: f64, new_value_weight: f64) -> f64 {
+    (new_value_weight * new_value) + ((1.0 - new_value_weight) * previous_average)
+}
+
 /// Returns the bucket ID for the given peer. This is useful
 /// for grouping peers together to avoid metric explosion.
 pub fn get_bucket_id_for_peer(peer: PeerNetworkId) -> u8 {
@@ -0,0 +1,241 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    client::AptosDataClient,
+    priority::PeerPriority,
+    tests::{mock::MockNetwork, utils, utils::NUM_SELECTION_ITERATIONS},
+};
+use aptos_config::{
+    config::{
+        AptosDataClientConfig, AptosDataMultiFetchConfig, AptosPeerSelectionConfig,
+        PeerSelectionStrategy,
+    },
+    network_id::PeerNetworkId,
+};
+use aptos_storage_service_types::requests::{DataRequest, StorageServiceRequest};
+use claims::{assert_none, assert_some};
+use std::{collections::HashSet, time::Duration};
+
+// Useful test constants
+const NUM_PEERS_TO_ADD: u64 = 10;
+
+#[tokio::test]
+async fn request_performance_measurements() {
+    // Create a data client config with a moving average weight of 50%
+    let data_client_config = AptosDataClientConfig {
+        peer_selection_config: AptosPeerSelectionConfig {
+            moving_average_weight_percentage: 50,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    // Create the mock network and client
+    let (mut mock_network, _, client, _) = MockNetwork::new(None, Some(data_client_config), None);
+
+    // Add a peer (with a storage summary)
+    let peers = utils::add_several_peers(&mut mock_network, 1, PeerPriority::HighPriority);
+    utils::update_storage_summaries_for_peers(&client, &peers, 100, 0);
+    let peer = *peers.iter().next().unwrap();
+
+    // Verify the peer has no measurements for the request
+    let storage_request = create_storage_request();
+    let peer_states = client.get_peer_states();
+    assert_none!(peer_states.get_request_performance(&peer, &storage_request));
+
+    // Record a successful request and verify the measurements
+    peer_states.update_request_performance_success(
+        peer,
+        &storage_request,
+        1000,
+        Duration::from_secs(1),
+    );
+    let request_performance = peer_states
+        .get_request_performance(&peer, &storage_request)
+        .unwrap();
+    assert_eq!(request_performance.get_num_samples(), 1);
+    assert_eq!(request_performance.get_average_throughput(), 1000.0);
+    assert_eq!(request_performance.get_failure_rate(), 0.0);
+
+    // Record another successful request and verify the moving average
+    peer_states.update_request_performance_success(
+        peer,
+        &storage_request,
+        3000,
+        Duration::from_secs(1),
+    );
+    let request_performance = peer_states
+        .get_request_performance(&peer, &storage_request)
+        .unwrap();
+    assert_eq!(request_performance.get_num_samples(), 2);
+    assert_eq!(request_performance.get_average_throughput(), 2000.0);
+
+    // Record two failures and verify the failure rate
+    peer_states.update_request_performance_failure(peer, &storage_request);
+    peer_states.update_request_performance_failure(peer, &storage_request);
+    let request_performance = peer_states
+        .get_request_performance(&peer, &storage_request)
+        .unwrap();
+    assert_eq!(request_performance.get_num_samples(), 2);
+    assert_eq!(request_performance.get_failure_rate(), 0.75);
+
+    // Verify the measurements are tracked separately for each request type
+    let other_storage_request =
+        StorageServiceRequest::new(DataRequest::GetServerProtocolVersion, true);
+    assert_none!(peer_states.get_request_performance(&peer, &other_storage_request));
+    peer_states.update_request_performance_failure(peer, &other_storage_request);
+    assert_some!(peer_states.get_request_performance(&peer, &other_storage_request));
+}
+
+#[tokio::test]
+async fn request_throughput_exploration() {
+    // Create a data client config that always explores
+    let data_client_config = create_adaptive_data_client_config(100);
+
+    // Create the mock network and client
+    let (mut mock_network, _, client, _) = MockNetwork::new(None, Some(data_client_config), None);
+
+    // Add several peers (with storage summaries)
+    let peers = utils::add_several_peers(
+        &mut mock_network,
+        NUM_PEERS_TO_ADD,
+        PeerPriority::HighPriority,
+    );
+    utils::update_storage_summaries_for_peers(&client, &peers, 100, 0);
+
+    // Make a single peer significantly faster than all other peers
+    let storage_request = create_storage_request();
+    let fast_peer = *peers.iter().next().unwrap();
+    update_peer_throughputs(&client, &peers, fast_peer, &storage_request);
+
+    // Select peers to service the request multiple times
+    let peers_and_selection_counts =
+        utils::select_peers_multiple_times(&client, 1, &storage_request);
+
+    // Verify that all peers are selected (because of exploration)
+    for peer in peers {
+        let selection_count = peers_and_selection_counts.get(&peer).unwrap();
+        assert!(*selection_count > 0);
+    }
+}
+
+#[tokio::test]
+async fn request_throughput_failures() {
+    // Create a data client config that never explores
+    let data_client_config = create_adaptive_data_client_config(0);
+
+    // Create the mock network and client
+    let (mut mock_network, _, client, _) = MockNetwork::new(None, Some(data_client_config), None);
+
+    // Add two peers (with storage summaries)
+    let peers = utils::add_several_peers(&mut mock_network, 2, PeerPriority::HighPriority);
+    utils::update_storage_summaries_for_peers(&client, &peers, 100, 0);
+
+    // Give both peers the same throughput
+    let storage_request = create_storage_request();
+    let peer_states = client.get_peer_states();
+    for peer in peers.iter() {
+        peer_states.update_request_performance_success(
+            *peer,
+            &storage_request,
+            1_000_000,
+            Duration::from_secs(1),
+        );
+    }
+
+    // Make one of the peers fail repeatedly
+    let mut peers = peers.into_iter();
+    let failing_peer = peers.next().unwrap();
+    let healthy_peer = peers.next().unwrap();
+    for _ in 0..20 {
+        peer_states.update_request_performance_failure(failing_peer, &storage_request);
+    }
+
+    // Select peers to service the request multiple times
+    let peers_and_selection_counts =
+        utils::select_peers_multiple_times(&client, 1, &storage_request);
+
+    // Verify the healthy peer is selected significantly more often
+    let healthy_peer_count = *peers_and_selection_counts.get(&healthy_peer).unwrap();
+    let failing_peer_count = *peers_and_selection_counts.get(&failing_peer).unwrap_or(&0);
+    assert!(healthy_peer_count > failing_peer_count * 10);
+}
+
+#[tokio::test]
+async fn request_throughput_selection() {
+    // Create a data client config that never explores
+    let data_client_config = create_adaptive_data_client_config(0);
+
+    // Create the mock network and client
+    let (mut mock_network, _, client, _) = MockNetwork::new(None, Some(data_client_config), None);
+
+    // Add several peers (with storage summaries)
+    let peers = utils::add_several_peers(
+        &mut mock_network,
+        NUM_PEERS_TO_ADD,
+        PeerPriority::HighPriority,
+    );
+    utils::update_storage_summaries_for_peers(&client, &peers, 100, 0);
+
+    // Make a single peer significantly faster than all other peers
+    let storage_request = create_storage_request();
+    let fast_peer = *peers.iter().next().unwrap();
+    update_peer_throughputs(&client, &peers, fast_peer, &storage_request);
+
+    // Select peers to service the request multiple times
+    let peers_and_selection_counts =
+        utils::select_peers_multiple_times(&client, 1, &storage_request);
+
+    // Verify the fast peer is selected for the vast majority of requests
+    let fast_peer_count = *peers_and_selection_counts.get(&fast_peer).unwrap();
+    assert!(fast_peer_count as u64 > (NUM_SELECTION_ITERATIONS * 9) / 10);
+}
+
+/// Creates a data client config that uses the adaptive throughput
+/// strategy with the given exploration percentage (and a single peer
+/// for each request).
+fn create_adaptive_data_client_config(exploration_percentage: u64) -> AptosDataClientConfig {
+    AptosDataClientConfig {
+        data_multi_fetch_config: AptosDataMultiFetchConfig {
+            enable_multi_fetch: false,
+            ..Default::default()
+        },
+        peer_selection_config: AptosPeerSelectionConfig {
+            exploration_percentage,
+            min_samples_for_throughput: 1,
+            peer_selection_strategy: PeerSelectionStrategy::AdaptiveThroughput,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+/// Creates a storage request that can be serviced by all peers
+fn create_storage_request() -> StorageServiceRequest {
+    StorageServiceRequest::new(DataRequest::GetStorageServerSummary, true)
+}
+
+/// Updates the measured throughputs of the given peers so that
+/// the fast peer is significantly faster than all other peers.
+fn update_peer_throughputs(
+    client: &AptosDataClient,
+    peers: &HashSet<PeerNetworkId>,
+    fast_peer: PeerNetworkId,
+    storage_request: &StorageServiceRequest,
+) {
+    let peer_states = client.get_peer_states();
+    for peer in peers {
+        let num_response_bytes = if *peer == fast_peer {
+            10_000_000 // 10 MB per second
+        } else {
+            1_000 // 1 KB per second
+        };
+        peer_states.update_request_performance_success(
+            *peer,
+            storage_request,
+            num_response_bytes,
+            Duration::from_secs(1),
+        );
+    }
+}
@@ -31,12 +31,13 @@ use aptos_storage_interface::DbReader;
 use aptos_storage_service_client::StorageServiceClient;
 use aptos_storage_service_server::network::{NetworkRequest, ResponseSender};
 use aptos_storage_service_types::{
-    responses::TransactionOrOutputListWithProof, Epoch, StorageServiceMessage,
+    responses::{StateValueWithProof, TransactionOrOutputListWithProof},
+    Epoch, StorageServiceMessage,
 };
 use aptos_time_service::{MockTimeService, TimeService};
 use aptos_types::{
     ledger_info::LedgerInfoWithSignatures,
-    state_store::state_value::StateValueChunkWithProof,
+    state_store::{state_key::StateKey, state_value::StateValueChunkWithProof},
     transaction::{TransactionListWithProof, TransactionOutputListWithProof, Version},
     PeerId,
 };
@@ -307,6 +308,14 @@ mock! {
             request_timeout_ms: u64,
         ) -> Result<Response<u64>>;
 
+        async fn get_state_value_with_proof(
+            &self,
+            state_key: StateKey,
+            version: Version,
+            proof_version: Version,
+            request_timeout_ms: u64,
+        ) -> Result<Response<StateValueWithProof>>;
+
         async fn get_state_values_with_proof(
             &self,
             version: u64,
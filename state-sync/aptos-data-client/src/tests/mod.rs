@@ -1,6 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+mod adaptive_selection;
 mod advertise;
 mod compression;
 pub mod mock;
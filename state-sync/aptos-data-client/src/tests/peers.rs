@@ -14,17 +14,21 @@ use aptos_config::{
     config::{AptosDataClientConfig, AptosDataMultiFetchConfig},
     network_id::{NetworkId, PeerNetworkId},
 };
+use aptos_infallible::Mutex;
 use aptos_storage_service_server::network::NetworkRequest;
 use aptos_storage_service_types::{
     requests::DataRequest,
-    responses::{CompleteDataRange, DataResponse, StorageServerSummary, StorageServiceResponse},
+    responses::{
+        CompleteDataRange, DataResponse, ServerProtocolVersion, StorageServerSummary,
+        StorageServiceResponse, STATE_VALUE_REQUEST_PROTOCOL_VERSION,
+    },
     StorageServiceError,
 };
-use aptos_types::transaction::TransactionListWithProof;
+use aptos_types::{state_store::state_key::StateKey, transaction::TransactionListWithProof};
 use claims::{assert_err, assert_matches, assert_ok};
 use maplit::hashset;
 use rand::{rngs::OsRng, Rng};
-use std::{collections::HashSet, time::Duration};
+use std::{collections::HashSet, sync::Arc, time::Duration};
 
 #[tokio::test]
 async fn all_bad_peers_with_invalid_responses() {
@@ -680,6 +684,89 @@ async fn single_good_peer_across_priorities() {
 
 /// Emulates network latencies by sleeping for some amount of time.
 /// If no duration is specified, the sleep duration is randomly chosen.
+#[tokio::test]
+async fn state_value_requests_require_protocol_version() {
+    // Create the mock network and client
+    let (mut mock_network, _, client, _) = MockNetwork::new(None, None, None);
+
+    // Add an old peer (that predates state value requests) and a new peer
+    let peer_priority = PeerPriority::HighPriority;
+    let (old_peer, network_id) = utils::add_peer_to_network(peer_priority, &mut mock_network);
+    let (new_peer, _) = utils::add_peer_to_network(peer_priority, &mut mock_network);
+
+    // Advertise states and transactions for both peers
+    let max_version = 200;
+    let mut storage_summary = utils::create_storage_summary(max_version);
+    storage_summary.data_summary.states = Some(CompleteDataRange::new(0, max_version).unwrap());
+    for peer in [old_peer, new_peer] {
+        client.update_peer_storage_summary(peer, storage_summary.clone());
+    }
+    client.update_global_summary_cache().unwrap();
+
+    // Spawn a handler that records the requests and responds to them
+    let received_requests = Arc::new(Mutex::new(vec![]));
+    let handler_requests = received_requests.clone();
+    tokio::spawn(async move {
+        while let Some(network_request) = mock_network.next_request(network_id).await {
+            let peer = network_request.peer_network_id;
+            let data_request = network_request.storage_service_request.data_request.clone();
+            handler_requests
+                .lock()
+                .push((peer, data_request.get_label()));
+            match data_request {
+                DataRequest::GetServerProtocolVersion => {
+                    let protocol_version = if peer == old_peer {
+                        STATE_VALUE_REQUEST_PROTOCOL_VERSION - 1
+                    } else {
+                        STATE_VALUE_REQUEST_PROTOCOL_VERSION
+                    };
+                    let data_response =
+                        DataResponse::ServerProtocolVersion(ServerProtocolVersion {
+                            protocol_version,
+                        });
+                    let use_compression = network_request.storage_service_request.use_compression;
+                    network_request
+                        .response_sender
+                        .send(Ok(StorageServiceResponse::new(
+                            data_response,
+                            use_compression,
+                        )
+                        .unwrap()));
+                },
+                _ => send_error_response(network_request),
+            }
+        }
+    });
+
+    // Send several state value requests (the new peer responds with errors)
+    let state_key = StateKey::raw(b"state_key");
+    for _ in 0..3 {
+        let result = client
+            .get_state_value_with_proof(state_key.clone(), max_version, max_version, 1000)
+            .await;
+        assert_err!(result);
+    }
+
+    // Verify the protocol versions were fetched once, and that the
+    // state value requests were only sent to the new peer.
+    let received_requests = received_requests.lock().clone();
+    for peer in [old_peer, new_peer] {
+        let num_version_requests = received_requests
+            .iter()
+            .filter(|(request_peer, label)| {
+                *request_peer == peer && *label == "get_server_protocol_version"
+            })
+            .count();
+        assert_eq!(num_version_requests, 1);
+    }
+    let state_value_peers: HashSet<_> = received_requests
+        .iter()
+        .filter(|(_, label)| *label == "get_state_value_with_proof")
+        .map(|(peer, _)| *peer)
+        .collect();
+    assert_eq!(state_value_peers, hashset![new_peer]);
+}
+
 async fn emulate_network_latencies(sleep_duration_ms: Option<u64>) {
     let sleep_duration_ms = sleep_duration_ms.unwrap_or_else(|| {
         OsRng.gen::<u64>() % 500 // Up to 0.5 seconds
@@ -4,13 +4,20 @@
 use crate::{
     error::Error,
     logging::{LogEntry, LogEvent, LogSchema},
+    peer_states::PeerStates,
 };
 use aptos_config::{config::AptosDataClientConfig, network_id::PeerNetworkId};
 use aptos_logger::{sample, sample::SampleRate, warn};
 use aptos_network::application::{metadata::PeerMetadata, storage::PeersAndMetadata};
+use aptos_storage_service_types::{
+    requests::StorageServiceRequest, responses::StorageServiceResponse,
+};
 use maplit::hashset;
 use ordered_float::OrderedFloat;
-use rand::seq::{IteratorRandom, SliceRandom};
+use rand::{
+    seq::{IteratorRandom, SliceRandom},
+    Rng,
+};
 use std::{
     collections::{BTreeMap, HashSet},
     sync::Arc,
@@ -120,6 +127,79 @@ pub fn choose_peers_by_latency(
     choose_random_peers_by_weight(num_peers_to_choose, potential_peers_and_latency_weights)
 }
 
+/// Chooses peers weighted by their measured throughput and failure
+/// history (for the given request type). To discover peers that have
+/// not yet been measured (or that may have improved), a configurable
+/// percentage of requests also explore a random peer.
+pub fn choose_random_peers_by_throughput(
+    data_client_config: Arc<AptosDataClientConfig>,
+    num_peers_to_choose: u64,
+    potential_peers: HashSet<PeerNetworkId>,
+    peer_states: Arc<PeerStates>,
+    request: &StorageServiceRequest,
+) -> HashSet<PeerNetworkId> {
+    // If no peers can be chosen, return an empty set
+    if num_peers_to_choose == 0 || potential_peers.is_empty() {
+        return hashset![];
+    }
+
+    // Determine if we should explore a random peer for this request
+    let peer_selection_config = &data_client_config.peer_selection_config;
+    let mut selected_peers = hashset![];
+    if rand::thread_rng().gen_range(0..100) < peer_selection_config.exploration_percentage {
+        selected_peers.extend(choose_random_peers(1, potential_peers.clone()));
+    }
+
+    // Gather the measured throughput and failure rates for the remaining peers
+    let mut measured_peers_and_throughputs = vec![];
+    let mut unmeasured_peers_and_failure_rates = vec![];
+    for peer in potential_peers.difference(&selected_peers) {
+        let request_performance = peer_states
+            .get_request_performance(peer, request)
+            .unwrap_or_default();
+        let failure_rate = request_performance.get_failure_rate();
+        if request_performance.get_num_samples() >= peer_selection_config.min_samples_for_throughput
+        {
+            let throughput = request_performance.get_average_throughput();
+            measured_peers_and_throughputs.push((*peer, throughput, failure_rate));
+        } else {
+            unmeasured_peers_and_failure_rates.push((*peer, failure_rate));
+        }
+    }
+
+    // Unmeasured peers are given the average throughput of the measured
+    // peers (so that they have a fair chance of being selected).
+    let average_throughput = if measured_peers_and_throughputs.is_empty() {
+        1.0 // No peers have been measured, so all peers are treated equally
+    } else {
+        let total_throughput: f64 = measured_peers_and_throughputs
+            .iter()
+            .map(|(_, throughput, _)| throughput)
+            .sum();
+        total_throughput / measured_peers_and_throughputs.len() as f64
+    };
+    let unmeasured_peers_and_throughputs = unmeasured_peers_and_failure_rates
+        .into_iter()
+        .map(|(peer, failure_rate)| (peer, average_throughput, failure_rate));
+
+    // Convert the throughputs and failure rates to weights
+    let potential_peers_and_weights = measured_peers_and_throughputs
+        .into_iter()
+        .chain(unmeasured_peers_and_throughputs)
+        .map(|(peer, throughput, failure_rate)| {
+            (peer, convert_throughput_to_weight(throughput, failure_rate))
+        })
+        .collect::<Vec<_>>();
+
+    // Select the remaining peers by weight
+    let num_peers_remaining = num_peers_to_choose.saturating_sub(selected_peers.len() as u64);
+    selected_peers.extend(choose_random_peers_by_weight(
+        num_peers_remaining,
+        potential_peers_and_weights,
+    ));
+    selected_peers
+}
+
 /// Selects a single peer randomly from the list of specified peers
 pub fn choose_random_peer(peers: HashSet<PeerNetworkId>) -> Option<PeerNetworkId> {
     peers.into_iter().choose(&mut rand::thread_rng())
@@ -182,6 +262,20 @@ fn convert_latency_to_weight(latency: f64) -> f64 {
     1000.0 / latency
 }
 
+/// Converts the given throughput and failure rate measurements to a
+/// weight. The higher the throughput (and the lower the failure rate),
+/// the higher the weight.
+fn convert_throughput_to_weight(throughput: f64, failure_rate: f64) -> f64 {
+    // If the throughput is <= 0, something has gone wrong, so return 0.
+    if throughput <= 0.0 {
+        return 0.0;
+    }
+
+    // Otherwise, scale the throughput by the success rate
+    let success_rate = (1.0 - failure_rate).clamp(0.0, 1.0);
+    throughput * success_rate
+}
+
 /// If the number of selected peers is less than the number of required peers,
 /// select remaining peers from the serviceable peers (at random).
 pub fn extend_with_random_peers(
@@ -281,6 +375,19 @@ pub fn get_metadata_for_peer(
     }
 }
 
+/// Returns the number of bytes in the given storage service response
+/// (i.e., the number of bytes sent over the network by the peer).
+pub fn get_num_response_bytes(response: &StorageServiceResponse) -> u64 {
+    match response {
+        StorageServiceResponse::CompressedResponse(_, compressed_data) => {
+            compressed_data.len() as u64
+        },
+        StorageServiceResponse::RawResponse(data_response) => {
+            bcs::serialized_size(data_response).unwrap_or_default() as u64
+        },
+    }
+}
+
 /// Logs the given schema as a warning with a sampled frequency
 fn log_warning_with_sample(log: LogSchema) {
     sample!(
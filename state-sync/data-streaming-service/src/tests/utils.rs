@@ -23,7 +23,7 @@ use aptos_storage_service_types::{
         SubscriptionStreamMetadata, TransactionOutputsWithProofRequest,
         TransactionsOrOutputsWithProofRequest, TransactionsWithProofRequest,
     },
-    responses::{CompleteDataRange, TransactionOrOutputListWithProof},
+    responses::{CompleteDataRange, StateValueWithProof, TransactionOrOutputListWithProof},
     Epoch,
 };
 use aptos_types::{
@@ -282,6 +282,19 @@ impl AptosDataClientInterface for MockAptosDataClient {
         }
     }
 
+    async fn get_state_value_with_proof(
+        &self,
+        _state_key: StateKey,
+        _version: Version,
+        _proof_version: Version,
+        _request_timeout_ms: u64,
+    ) -> Result<Response<StateValueWithProof>, aptos_data_client::error::Error> {
+        // Single state values are never requested by the data streaming service
+        Err(aptos_data_client::error::Error::UnexpectedErrorEncountered(
+            "Single state value requests are not supported by the mock client!".into(),
+        ))
+    }
+
     async fn get_state_values_with_proof(
         &self,
         version: Version,
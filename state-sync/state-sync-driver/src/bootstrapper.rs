@@ -9,6 +9,7 @@ use crate::{
     metadata_storage::MetadataStorageInterface,
     metrics,
     metrics::ExecutingComponent,
+    progress::SyncPhase,
     storage_synchronizer::{NotificationMetadata, StorageSynchronizerInterface},
     utils,
     utils::{OutputFallbackHandler, SpeculativeStreamState, PENDING_DATA_LOG_FREQ_SECS},
@@ -370,6 +371,33 @@ impl<
         self.bootstrapped
     }
 
+    /// Returns the bootstrapping phase that is currently executing
+    pub fn get_bootstrapping_phase(&self) -> SyncPhase {
+        if self.should_fetch_epoch_ending_ledger_infos() {
+            SyncPhase::VerifyingEpochEndingLedgerInfos
+        } else if self.get_bootstrapping_mode().is_fast_sync()
+            && self.state_value_syncer.ledger_info_to_sync.is_some()
+        {
+            SyncPhase::DownloadingStates
+        } else {
+            SyncPhase::SyncingTransactions
+        }
+    }
+
+    /// Returns the version of the state snapshot being synced and the
+    /// number of state values already processed (if states are being synced).
+    pub fn get_state_values_progress(&self) -> Option<(Version, u64)> {
+        self.state_value_syncer
+            .ledger_info_to_sync
+            .as_ref()
+            .map(|ledger_info_to_sync| {
+                (
+                    ledger_info_to_sync.ledger_info().version(),
+                    self.state_value_syncer.next_state_index_to_process,
+                )
+            })
+    }
+
     /// Marks bootstrapping as complete and notifies any listeners
     pub async fn bootstrapping_complete(&mut self) -> Result<(), Error> {
         info!(LogSchema::new(LogEntry::Bootstrapper)
@@ -746,7 +774,8 @@ impl<
                 Error::UnexpectedError("No higher epoch ending version known!".into())
             })?;
         let data_stream = match self.get_bootstrapping_mode() {
-            BootstrappingMode::ApplyTransactionOutputsFromGenesis => {
+            BootstrappingMode::ApplyTransactionOutputsFromGenesis
+            | BootstrappingMode::RestoreFromBackup => {
                 self.streaming_client
                     .get_all_transaction_outputs(
                         next_version,
@@ -1174,7 +1203,8 @@ impl<
 
         // Execute/apply and commit the transactions/outputs
         let num_transactions_or_outputs = match bootstrapping_mode {
-            BootstrappingMode::ApplyTransactionOutputsFromGenesis => {
+            BootstrappingMode::ApplyTransactionOutputsFromGenesis
+            | BootstrappingMode::RestoreFromBackup => {
                 if let Some(transaction_outputs_with_proof) = transaction_outputs_with_proof {
                     utils::apply_transaction_outputs(
                         self.storage_synchronizer.clone(),
@@ -1373,7 +1403,8 @@ impl<
     ) -> Result<Option<LedgerInfoWithSignatures>, Error> {
         // Calculate the payload end version
         let num_versions = match self.get_bootstrapping_mode() {
-            BootstrappingMode::ApplyTransactionOutputsFromGenesis => {
+            BootstrappingMode::ApplyTransactionOutputsFromGenesis
+            | BootstrappingMode::RestoreFromBackup => {
                 if let Some(transaction_outputs_with_proof) = transaction_outputs_with_proof {
                     transaction_outputs_with_proof
                         .transactions_and_outputs
@@ -16,6 +16,7 @@ use crate::{
         ConsensusNotificationHandler, ErrorNotification, ErrorNotificationListener,
         MempoolNotificationHandler, StorageServiceNotificationHandler,
     },
+    progress::{StateSyncProgressTracker, SyncPhase, NUMBER_OF_STATES_REQUEST_TIMEOUT_MS},
     storage_synchronizer::StorageSynchronizerInterface,
     utils,
     utils::{OutputFallbackHandler, PENDING_DATA_LOG_FREQ_SECS},
@@ -24,7 +25,7 @@ use aptos_config::config::{ConsensusObserverConfig, RoleType, StateSyncDriverCon
 use aptos_consensus_notifications::{
     ConsensusCommitNotification, ConsensusNotification, ConsensusSyncNotification,
 };
-use aptos_data_client::interface::AptosDataClientInterface;
+use aptos_data_client::{global_summary::GlobalDataSummary, interface::AptosDataClientInterface};
 use aptos_data_streaming_service::streaming_client::{
     DataStreamingClient, NotificationAndFeedback, NotificationFeedback,
 };
@@ -118,6 +119,9 @@ pub struct StateSyncDriver<
     // The handler for notifications to mempool
     mempool_notification_handler: MempoolNotificationHandler<MempoolNotifier>,
 
+    // The tracker for reporting the state sync progress
+    progress_tracker: StateSyncProgressTracker,
+
     // The timestamp at which the driver started executing
     start_time: Option<Instant>,
 
@@ -161,6 +165,7 @@ impl<
         event_subscription_service: Arc<Mutex<EventSubscriptionService>>,
         mempool_notification_handler: MempoolNotificationHandler<MempoolNotifier>,
         metadata_storage: MetadataStorage,
+        progress_tracker: StateSyncProgressTracker,
         storage_service_notification_handler: StorageServiceNotificationHandler<
             StorageServiceNotifier,
         >,
@@ -199,6 +204,7 @@ impl<
             error_notification_listener,
             event_subscription_service,
             mempool_notification_handler,
+            progress_tracker,
             start_time: None,
             storage,
             storage_service_notification_handler,
@@ -597,6 +603,7 @@ impl<
     async fn drive_progress(&mut self) {
         // Fetch the global data summary and verify we have active peers
         let global_data_summary = self.aptos_data_client.get_global_data_summary();
+        self.update_progress_tracker(&global_data_summary);
         if global_data_summary.is_empty() {
             trace!(LogSchema::new(LogEntry::Driver).message(
                 "The global data summary is empty! It's likely that we have no active peers."
@@ -661,4 +668,73 @@ impl<
             }
         };
     }
+
+    /// Updates the progress tracker with the latest sync progress
+    fn update_progress_tracker(&self, global_data_summary: &GlobalDataSummary) {
+        // Update the current sync phase
+        let sync_phase = if self.check_if_consensus_executing() {
+            SyncPhase::ConsensusExecuting
+        } else if self.bootstrapper.is_bootstrapped() {
+            SyncPhase::ContinuousSyncing
+        } else {
+            self.bootstrapper.get_bootstrapping_phase()
+        };
+        self.progress_tracker.update_phase(sync_phase);
+
+        // Update the synced version (the cached version is used between refreshes)
+        if self.progress_tracker.should_refresh_synced_version() {
+            let synced_version = utils::fetch_pre_committed_version(self.storage.clone())
+                .map_err(|error| {
+                    warn!(LogSchema::new(LogEntry::Driver).message(&format!(
+                        "Failed to fetch the synced version for the progress report: {:?}",
+                        error
+                    )));
+                })
+                .ok();
+            self.progress_tracker.update_synced_version(synced_version);
+        }
+
+        // Update the target version
+        let target_version = global_data_summary
+            .advertised_data
+            .highest_synced_ledger_info()
+            .map(|ledger_info| ledger_info.ledger_info().version());
+        self.progress_tracker.update_target_version(target_version);
+
+        // Update the state value progress (if we're syncing states)
+        if sync_phase == SyncPhase::DownloadingStates {
+            if let Some((state_snapshot_version, num_states_synced)) =
+                self.bootstrapper.get_state_values_progress()
+            {
+                self.progress_tracker
+                    .update_states_synced(state_snapshot_version, num_states_synced);
+            }
+        }
+
+        // Fetch the total number of states in the snapshot (if required)
+        if let Some(state_snapshot_version) = self.progress_tracker.should_fetch_total_num_states()
+        {
+            let aptos_data_client = self.aptos_data_client.clone();
+            let progress_tracker = self.progress_tracker.clone();
+            tokio::spawn(async move {
+                let total_num_states = match aptos_data_client
+                    .get_number_of_states(
+                        state_snapshot_version,
+                        NUMBER_OF_STATES_REQUEST_TIMEOUT_MS,
+                    )
+                    .await
+                {
+                    Ok(response) => Some(response.payload),
+                    Err(error) => {
+                        warn!(LogSchema::new(LogEntry::Driver).message(&format!(
+                            "Failed to fetch the number of states at version: {:?}, error: {:?}",
+                            state_snapshot_version, error
+                        )));
+                        None
+                    },
+                };
+                progress_tracker.update_total_num_states(state_snapshot_version, total_num_states);
+            });
+        }
+    }
 }
@@ -5,11 +5,13 @@
 use crate::{
     driver::{DriverConfiguration, StateSyncDriver},
     driver_client::{ClientNotificationListener, DriverClient, DriverNotification},
+    light_client::LightClient,
     metadata_storage::MetadataStorageInterface,
     notification_handlers::{
         CommitNotification, CommitNotificationListener, ConsensusNotificationHandler,
         ErrorNotificationListener, MempoolNotificationHandler, StorageServiceNotificationHandler,
     },
+    progress::StateSyncProgressTracker,
     storage_synchronizer::StorageSynchronizer,
 };
 use aptos_config::config::NodeConfig;
@@ -34,6 +36,8 @@ use tokio::runtime::Runtime;
 /// Creates a new state sync driver and client
 pub struct DriverFactory {
     client_notification_sender: mpsc::UnboundedSender<DriverNotification>,
+    light_client: Option<LightClient<AptosDataClient>>,
+    progress_tracker: StateSyncProgressTracker,
     _driver_runtime: Option<Runtime>,
 }
 
@@ -118,19 +122,12 @@ impl DriverFactory {
             Err(error) => panic!("Failed to fetch the initial synced version: {:?}", error),
         }
 
-        // Create the notification handlers
+        // Create the client and commit notification handlers
         let (client_notification_sender, client_notification_receiver) = mpsc::unbounded();
         let client_notification_listener =
             ClientNotificationListener::new(client_notification_receiver);
         let (commit_notification_sender, commit_notification_listener) =
             CommitNotificationListener::new();
-        let consensus_notification_handler = ConsensusNotificationHandler::new(consensus_listener);
-        let (error_notification_sender, error_notification_listener) =
-            ErrorNotificationListener::new();
-        let mempool_notification_handler =
-            MempoolNotificationHandler::new(mempool_notification_sender);
-        let storage_service_notification_handler =
-            StorageServiceNotificationHandler::new(storage_service_notification_sender);
 
         // Create a new runtime (if required)
         let driver_runtime = if create_runtime {
@@ -140,6 +137,46 @@ impl DriverFactory {
             None
         };
 
+        // If the node is running in light client mode, spawn the
+        // light client (instead of the state sync driver).
+        let state_sync_driver_config = node_config.state_sync.state_sync_driver;
+        if state_sync_driver_config
+            .bootstrapping_mode
+            .is_light_client()
+        {
+            let light_client = LightClient::new(
+                aptos_data_client,
+                node_config.state_sync.aptos_data_client,
+                waypoint,
+            );
+            let light_client_future = light_client.clone().start_light_client(
+                client_notification_listener,
+                state_sync_driver_config.progress_check_interval_ms,
+            );
+            if let Some(driver_runtime) = &driver_runtime {
+                driver_runtime.spawn(light_client_future);
+            } else {
+                tokio::spawn(light_client_future);
+            }
+
+            let driver_factory = Self {
+                client_notification_sender,
+                light_client: Some(light_client),
+                progress_tracker: StateSyncProgressTracker::new(time_service),
+                _driver_runtime: driver_runtime,
+            };
+            return (driver_factory, commit_notification_sender);
+        }
+
+        // Create the remaining notification handlers
+        let consensus_notification_handler = ConsensusNotificationHandler::new(consensus_listener);
+        let (error_notification_sender, error_notification_listener) =
+            ErrorNotificationListener::new();
+        let mempool_notification_handler =
+            MempoolNotificationHandler::new(mempool_notification_sender);
+        let storage_service_notification_handler =
+            StorageServiceNotificationHandler::new(storage_service_notification_sender);
+
         // Create the storage synchronizer
         let event_subscription_service = Arc::new(Mutex::new(event_subscription_service));
         let (storage_synchronizer, _) = StorageSynchronizer::new(
@@ -163,6 +200,9 @@ impl DriverFactory {
             waypoint,
         );
 
+        // Create the progress tracker
+        let progress_tracker = StateSyncProgressTracker::new(time_service.clone());
+
         // Create the state sync driver
         let state_sync_driver = StateSyncDriver::new(
             client_notification_listener,
@@ -173,6 +213,7 @@ impl DriverFactory {
             event_subscription_service,
             mempool_notification_handler,
             metadata_storage,
+            progress_tracker.clone(),
             storage_service_notification_handler,
             storage_synchronizer,
             aptos_data_client,
@@ -191,6 +232,8 @@ impl DriverFactory {
         // Create the driver factory
         let driver_factory = Self {
             client_notification_sender,
+            light_client: None,
+            progress_tracker,
             _driver_runtime: driver_runtime,
         };
 
@@ -201,6 +244,16 @@ impl DriverFactory {
     pub fn create_driver_client(&self) -> DriverClient {
         DriverClient::new(self.client_notification_sender.clone())
     }
+
+    /// Returns the light client (if the node is running in light client mode)
+    pub fn get_light_client(&self) -> Option<LightClient<AptosDataClient>> {
+        self.light_client.clone()
+    }
+
+    /// Returns the tracker that reports the state sync progress
+    pub fn get_progress_tracker(&self) -> StateSyncProgressTracker {
+        self.progress_tracker.clone()
+    }
 }
 
 /// A struct for holding the various runtimes required by state sync v2.
@@ -228,6 +281,16 @@ impl StateSyncRuntimes {
         }
     }
 
+    /// Returns the light client (if the node is running in light client mode)
+    pub fn get_light_client(&self) -> Option<LightClient<AptosDataClient>> {
+        self.state_sync.get_light_client()
+    }
+
+    /// Returns the tracker that reports the state sync progress
+    pub fn get_progress_tracker(&self) -> StateSyncProgressTracker {
+        self.state_sync.get_progress_tracker()
+    }
+
     pub fn block_until_initialized(&self) {
         let state_sync_client = self.state_sync.create_driver_client();
         block_on(state_sync_client.notify_once_bootstrapped())
@@ -10,12 +10,16 @@ mod driver;
 mod driver_client;
 pub mod driver_factory;
 mod error;
+pub mod light_client;
 mod logging;
 pub mod metadata_storage;
 pub mod metrics;
 mod notification_handlers;
+pub mod progress;
 mod storage_synchronizer;
 mod utils;
 
+pub use error::Error;
+
 #[cfg(test)]
 mod tests;
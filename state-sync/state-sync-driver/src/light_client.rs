@@ -0,0 +1,333 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    driver_client::{ClientNotificationListener, DriverNotification},
+    error::Error,
+    logging::{LogEntry, LogSchema},
+};
+use aptos_config::config::AptosDataClientConfig;
+use aptos_data_client::interface::{AptosDataClientInterface, ResponseError};
+use aptos_infallible::RwLock;
+use aptos_logger::prelude::*;
+use aptos_types::{
+    account_address::AccountAddress,
+    epoch_change::EpochChangeProof,
+    ledger_info::LedgerInfoWithSignatures,
+    state_store::{state_key::StateKey, state_value::StateValue},
+    trusted_state::TrustedState,
+    waypoint::Waypoint,
+};
+use futures::{channel::oneshot, StreamExt};
+use move_core_types::move_resource::MoveResource;
+use std::sync::Arc;
+use tokio::time::{interval, Duration};
+use tokio_stream::wrappers::IntervalStream;
+
+// Useful constants for the light client
+const LIGHT_CLIENT_ERROR_LOG_FREQ_SECS: u64 = 3;
+
+/// The verified view of the ledger held by the light client
+#[derive(Clone, Debug)]
+struct LightClientState {
+    trusted_state: TrustedState, // The latest trusted state (epoch and validator set)
+    latest_ledger_info: Option<LedgerInfoWithSignatures>, // The latest verified ledger info
+}
+
+/// A verifying light client that only syncs and verifies epoch ending ledger
+/// infos and the latest ledger info. State values (e.g., accounts and
+/// resources) are fetched from peers on demand, and are verified against the
+/// latest trusted ledger info using the proofs returned by the storage service.
+///
+/// The light client is run by the `DriverFactory` (instead of the state sync
+/// driver) when the node is configured with `BootstrappingMode::LightClient`.
+///
+/// Note: the light client does not persist any data. Syncing always starts
+/// from the given waypoint (and ratchets forward in memory). Moreover, state
+/// queries are only served via `get_state_value` and `get_resource` (e.g., by
+/// the inspection service). The node's REST API is not backed by the light
+/// client, so it must be disabled in light client mode.
+#[derive(Clone)]
+pub struct LightClient<DataClient> {
+    // The client used to fetch data from peers
+    aptos_data_client: DataClient,
+
+    // The timeout (ms) to use for each data client request
+    request_timeout_ms: u64,
+
+    // The latest verified state of the light client
+    state: Arc<RwLock<LightClientState>>,
+}
+
+impl<DataClient: AptosDataClientInterface + Send + Clone + 'static> LightClient<DataClient> {
+    pub fn new(
+        aptos_data_client: DataClient,
+        data_client_config: AptosDataClientConfig,
+        waypoint: Waypoint,
+    ) -> Self {
+        let state = LightClientState {
+            trusted_state: TrustedState::from_epoch_waypoint(waypoint),
+            latest_ledger_info: None,
+        };
+
+        Self {
+            aptos_data_client,
+            request_timeout_ms: data_client_config.response_timeout_ms,
+            state: Arc::new(RwLock::new(state)),
+        }
+    }
+
+    /// Starts the light client loop. The light client periodically syncs to
+    /// the latest ledger info advertised by peers, and notifies any bootstrap
+    /// listeners once the first ledger info has been verified.
+    pub async fn start_light_client(
+        self,
+        mut client_notification_listener: ClientNotificationListener,
+        sync_interval_ms: u64,
+    ) {
+        let mut sync_interval =
+            IntervalStream::new(interval(Duration::from_millis(sync_interval_ms))).fuse();
+        let mut bootstrap_notifier_channels: Vec<oneshot::Sender<Result<(), Error>>> = vec![];
+
+        // Start the light client
+        info!(LogSchema::new(LogEntry::LightClient).message("Started the light client!"));
+        loop {
+            ::futures::select! {
+                notification = client_notification_listener.select_next_some() => {
+                    let DriverNotification::NotifyOnceBootstrapped(notifier_channel) = notification;
+                    bootstrap_notifier_channels.push(notifier_channel);
+                },
+                _ = sync_interval.select_next_some() => {
+                    if let Err(error) = self.sync_to_latest_ledger_info().await {
+                        sample!(
+                            SampleRate::Duration(Duration::from_secs(LIGHT_CLIENT_ERROR_LOG_FREQ_SECS)),
+                            warn!(LogSchema::new(LogEntry::LightClient)
+                                .error(&error)
+                                .message("Failed to sync to the latest ledger info!"));
+                        );
+                    }
+                }
+            }
+
+            // Notify the bootstrap listeners once a ledger info has been verified
+            if self.get_latest_ledger_info().is_some() {
+                for notifier_channel in bootstrap_notifier_channels.drain(..) {
+                    if notifier_channel.send(Ok(())).is_err() {
+                        warn!(LogSchema::new(LogEntry::LightClient).message(
+                            "Failed to notify a bootstrap listener! The channel is closed."
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the latest verified ledger info (if one has been synced)
+    pub fn get_latest_ledger_info(&self) -> Option<LedgerInfoWithSignatures> {
+        self.state.read().latest_ledger_info.clone()
+    }
+
+    /// Returns the latest trusted state of the light client
+    pub fn get_trusted_state(&self) -> TrustedState {
+        self.state.read().trusted_state.clone()
+    }
+
+    /// Syncs the light client to the highest ledger info advertised by
+    /// peers. This verifies all epoch changes between the current trusted
+    /// state and the target, and returns the new latest ledger info.
+    pub async fn sync_to_latest_ledger_info(&self) -> Result<LedgerInfoWithSignatures, Error> {
+        // Identify the highest synced ledger info advertised by peers
+        let target_ledger_info = self
+            .aptos_data_client
+            .get_global_data_summary()
+            .advertised_data
+            .highest_synced_ledger_info()
+            .ok_or_else(|| {
+                Error::AdvertisedDataError(
+                    "No highest synced ledger info found in the network!".into(),
+                )
+            })?;
+
+        // If the target is not ahead of our trusted state, there's nothing to do
+        let state = self.state.read().clone();
+        if let Some(latest_ledger_info) = state.latest_ledger_info {
+            if target_ledger_info.ledger_info().version()
+                <= latest_ledger_info.ledger_info().version()
+            {
+                return Ok(latest_ledger_info);
+            }
+        }
+
+        // Verify all epoch changes up to the epoch of the target
+        let target_epoch = target_ledger_info.ledger_info().epoch();
+        let mut trusted_state = self.sync_epoch_ending_ledger_infos(target_epoch).await?;
+
+        // Verify the target ledger info (which is inside the latest trusted epoch)
+        let epoch_change_proof = EpochChangeProof::new(vec![], false);
+        let trusted_state_change = trusted_state
+            .verify_and_ratchet_inner(&target_ledger_info, &epoch_change_proof)
+            .map_err(|error| {
+                Error::VerificationError(format!(
+                    "Failed to verify the target ledger info: {:?}, error: {:?}",
+                    target_ledger_info, error
+                ))
+            })?;
+        if let Some(new_state) = trusted_state_change.new_state() {
+            trusted_state = new_state;
+        }
+
+        // Update the light client state (unless a concurrent sync got further)
+        let mut state = self.state.write();
+        if state.trusted_state.version() > trusted_state.version() {
+            return Ok(state
+                .latest_ledger_info
+                .clone()
+                .unwrap_or(target_ledger_info));
+        }
+        *state = LightClientState {
+            trusted_state,
+            latest_ledger_info: Some(target_ledger_info.clone()),
+        };
+        drop(state);
+        info!(LogSchema::new(LogEntry::LightClient).message(&format!(
+            "Synced to the latest ledger info! Epoch: {:?}, version: {:?}",
+            target_ledger_info.ledger_info().epoch(),
+            target_ledger_info.ledger_info().version()
+        )));
+
+        Ok(target_ledger_info)
+    }
+
+    /// Fetches and verifies the epoch ending ledger infos required to move
+    /// the trusted state into the target epoch. Returns the new trusted state.
+    async fn sync_epoch_ending_ledger_infos(
+        &self,
+        target_epoch: u64,
+    ) -> Result<TrustedState, Error> {
+        let mut trusted_state = self.get_trusted_state();
+
+        // If we only hold a waypoint, we don't know the epoch at the waypoint.
+        // Instead, we fetch from genesis and skip the stale ledger infos.
+        let mut next_epoch = match &trusted_state {
+            TrustedState::EpochWaypoint(_) => 0,
+            TrustedState::EpochState { epoch_state, .. } => epoch_state.epoch,
+        };
+
+        while next_epoch < target_epoch {
+            // Fetch the next chunk of epoch ending ledger infos
+            let response = self
+                .aptos_data_client
+                .get_epoch_ending_ledger_infos(
+                    next_epoch,
+                    target_epoch - 1,
+                    self.request_timeout_ms,
+                )
+                .await
+                .map_err(|error| Error::UnexpectedError(error.to_string()))?;
+            let (context, epoch_ending_ledger_infos) = response.into_parts();
+
+            // Verify that the response makes progress
+            let last_ledger_info = match epoch_ending_ledger_infos.last() {
+                Some(ledger_info) if ledger_info.ledger_info().epoch() >= next_epoch => {
+                    ledger_info.clone()
+                },
+                _ => {
+                    context
+                        .response_callback
+                        .notify_bad_response(ResponseError::InvalidData);
+                    return Err(Error::VerificationError(format!(
+                        "Received an invalid epoch ending ledger info response for epoch: {:?}",
+                        next_epoch
+                    )));
+                },
+            };
+            next_epoch = last_ledger_info.ledger_info().epoch() + 1;
+
+            // Skip the chunk if it ends before our waypoint
+            if last_ledger_info.ledger_info().version() < trusted_state.version() {
+                continue;
+            }
+
+            // Verify the epoch changes and ratchet the trusted state
+            let epoch_change_proof = EpochChangeProof::new(epoch_ending_ledger_infos, false);
+            let trusted_state_change = match trusted_state
+                .verify_and_ratchet_inner(&last_ledger_info, &epoch_change_proof)
+            {
+                Ok(trusted_state_change) => trusted_state_change,
+                Err(error) => {
+                    context
+                        .response_callback
+                        .notify_bad_response(ResponseError::ProofVerificationError);
+                    return Err(Error::VerificationError(format!(
+                        "Failed to verify the epoch ending ledger infos: {:?}",
+                        error
+                    )));
+                },
+            };
+            if let Some(new_state) = trusted_state_change.new_state() {
+                trusted_state = new_state;
+            }
+        }
+
+        Ok(trusted_state)
+    }
+
+    /// Fetches the state value for the given key at the latest verified
+    /// ledger info. The value (or its non-existence) is verified against
+    /// the ledger info before being returned.
+    pub async fn get_state_value(&self, state_key: &StateKey) -> Result<Option<StateValue>, Error> {
+        // Get the latest verified ledger info
+        let ledger_info = self.get_latest_ledger_info().ok_or_else(|| {
+            Error::UnexpectedError("The light client has not synced a ledger info yet!".into())
+        })?;
+        let version = ledger_info.ledger_info().version();
+
+        // Fetch the state value from peers
+        let response = self
+            .aptos_data_client
+            .get_state_value_with_proof(
+                state_key.clone(),
+                version,
+                version,
+                self.request_timeout_ms,
+            )
+            .await
+            .map_err(|error| Error::UnexpectedError(error.to_string()))?;
+        let (context, state_value_with_proof) = response.into_parts();
+
+        // Verify the state value against the ledger info
+        if let Err(error) =
+            state_value_with_proof.verify(ledger_info.ledger_info(), version, state_key)
+        {
+            context
+                .response_callback
+                .notify_bad_response(ResponseError::ProofVerificationError);
+            return Err(Error::VerificationError(format!(
+                "Failed to verify the state value for key: {:?}, error: {:?}",
+                state_key, error
+            )));
+        }
+
+        Ok(state_value_with_proof.state_value)
+    }
+
+    /// Fetches and deserializes the resource of the given type stored at
+    /// the specified address (at the latest verified ledger info).
+    pub async fn get_resource<T: MoveResource>(
+        &self,
+        address: &AccountAddress,
+    ) -> Result<Option<T>, Error> {
+        let state_key = StateKey::resource_typed::<T>(address)
+            .map_err(|error| Error::UnexpectedError(error.to_string()))?;
+        let state_value = self.get_state_value(&state_key).await?;
+        state_value
+            .map(|state_value| bcs::from_bytes(state_value.bytes()))
+            .transpose()
+            .map_err(|error| {
+                Error::InvalidPayload(format!(
+                    "Failed to deserialize the resource at address: {:?}, error: {:?}",
+                    address, error
+                ))
+            })
+    }
+}
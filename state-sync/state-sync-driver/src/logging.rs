@@ -32,6 +32,7 @@ pub enum LogEntry {
     ClientNotification,
     ConsensusNotification,
     Driver,
+    LightClient,
     NotificationHandler,
     StorageSynchronizer,
     SynchronizerNotification,
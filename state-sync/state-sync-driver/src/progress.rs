@@ -0,0 +1,312 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_infallible::RwLock;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use aptos_types::transaction::Version;
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+// The timeout (ms) for requests that fetch the total number of states
+pub const NUMBER_OF_STATES_REQUEST_TIMEOUT_MS: u64 = 10_000;
+
+// The interval (ms) at which the synced version is refreshed from storage
+const SYNCED_VERSION_REFRESH_INTERVAL_MS: u64 = 1_000;
+
+// The initial and maximum backoff (ms) after a failed progress request
+const MIN_FAILURE_BACKOFF_MS: u64 = 1_000;
+const MAX_FAILURE_BACKOFF_MS: u64 = 60_000;
+
+/// The phase that state sync is currently executing
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncPhase {
+    #[default]
+    Initializing, // The driver has not yet started to make progress
+    VerifyingEpochEndingLedgerInfos, // The bootstrapper is fetching and verifying epoch changes
+    DownloadingStates,               // The bootstrapper is downloading a state snapshot
+    SyncingTransactions,             // The bootstrapper is syncing transactions or outputs
+    ContinuousSyncing,               // The node has bootstrapped and is continuously syncing
+    ConsensusExecuting,              // Consensus (or the observer) is executing, not state sync
+}
+
+/// A structured report of the current state sync progress
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct StateSyncProgressReport {
+    pub phase: SyncPhase,                        // The current sync phase
+    pub phase_duration_secs: u64,                // The time spent in the current phase
+    pub synced_version: Option<Version>,         // The highest synced version
+    pub target_version: Option<Version>,         // The highest version advertised by peers
+    pub state_snapshot_version: Option<Version>, // The version of the state snapshot being synced
+    pub num_states_synced: Option<u64>,          // The number of states synced for the snapshot
+    pub total_num_states: Option<u64>,           // The total number of states in the snapshot
+    pub versions_per_sec: Option<f64>,           // The average version throughput in this phase
+    pub states_per_sec: Option<f64>,             // The average state throughput in this phase
+    pub estimated_secs_remaining: Option<u64>,   // The estimated time to complete this phase
+}
+
+/// The schedule of a progress request. After a failure, the request is
+/// retried with an exponential backoff (to avoid hammering storage or peers).
+#[derive(Clone, Debug)]
+struct RequestSchedule {
+    next_request_time: Instant,
+    num_failures: u32,
+}
+
+impl RequestSchedule {
+    fn new(now: Instant) -> Self {
+        Self {
+            next_request_time: now,
+            num_failures: 0,
+        }
+    }
+
+    /// Returns true iff the request can be sent at the given time
+    fn is_ready(&self, now: Instant) -> bool {
+        now >= self.next_request_time
+    }
+
+    /// Delays the next request by the given duration
+    fn delay(&mut self, now: Instant, duration: Duration) {
+        self.next_request_time = now + duration;
+    }
+
+    /// Resets the backoff after a successful request
+    fn record_success(&mut self) {
+        self.num_failures = 0;
+    }
+
+    /// Backs off the next request after a failed request
+    fn record_failure(&mut self, now: Instant) {
+        let backoff_ms =
+            (MIN_FAILURE_BACKOFF_MS << self.num_failures.min(16)).min(MAX_FAILURE_BACKOFF_MS);
+        self.num_failures = self.num_failures.saturating_add(1);
+        self.delay(now, Duration::from_millis(backoff_ms));
+    }
+}
+
+/// The internal progress state held by the tracker
+#[derive(Clone, Debug)]
+struct SyncProgress {
+    phase: SyncPhase,
+    phase_start_time: Instant,
+    phase_start_synced_version: Option<Version>,
+    phase_start_num_states_synced: Option<u64>,
+    synced_version: Option<Version>,
+    target_version: Option<Version>,
+    state_snapshot_version: Option<Version>,
+    num_states_synced: Option<u64>,
+    total_num_states: Option<u64>,
+    total_num_states_requested: bool,
+    total_num_states_schedule: RequestSchedule,
+    synced_version_schedule: RequestSchedule,
+}
+
+/// A simple tracker for the progress of state sync. The tracker is updated
+/// by the driver and can be read concurrently (e.g., by the inspection
+/// service) to report progress to operators.
+#[derive(Clone)]
+pub struct StateSyncProgressTracker {
+    progress: Arc<RwLock<SyncProgress>>,
+    time_service: TimeService,
+}
+
+impl StateSyncProgressTracker {
+    pub fn new(time_service: TimeService) -> Self {
+        let now = time_service.now();
+        let progress = SyncProgress {
+            phase: SyncPhase::default(),
+            phase_start_time: now,
+            phase_start_synced_version: None,
+            phase_start_num_states_synced: None,
+            synced_version: None,
+            target_version: None,
+            state_snapshot_version: None,
+            num_states_synced: None,
+            total_num_states: None,
+            total_num_states_requested: false,
+            total_num_states_schedule: RequestSchedule::new(now),
+            synced_version_schedule: RequestSchedule::new(now),
+        };
+
+        Self {
+            progress: Arc::new(RwLock::new(progress)),
+            time_service,
+        }
+    }
+
+    /// Updates the current phase. If the phase has changed,
+    /// the throughput calculations are reset.
+    pub fn update_phase(&self, phase: SyncPhase) {
+        let mut progress = self.progress.write();
+        if progress.phase != phase {
+            progress.phase = phase;
+            progress.phase_start_time = self.time_service.now();
+            progress.phase_start_synced_version = progress.synced_version;
+            progress.phase_start_num_states_synced = progress.num_states_synced;
+        }
+    }
+
+    /// Returns true iff the synced version should be refreshed from storage.
+    /// The synced version is cached between refreshes, and refreshes are
+    /// backed off if storage fails to return the version.
+    pub fn should_refresh_synced_version(&self) -> bool {
+        let now = self.time_service.now();
+        let mut progress = self.progress.write();
+        if !progress.synced_version_schedule.is_ready(now) {
+            return false;
+        }
+
+        progress.synced_version_schedule.delay(
+            now,
+            Duration::from_millis(SYNCED_VERSION_REFRESH_INTERVAL_MS),
+        );
+        true
+    }
+
+    /// Updates the synced version. If the version is missing (e.g., the
+    /// storage read failed), the cached version is kept and the next
+    /// refresh is backed off.
+    pub fn update_synced_version(&self, synced_version: Option<Version>) {
+        let mut progress = self.progress.write();
+        match synced_version {
+            Some(synced_version) => {
+                progress.synced_version_schedule.record_success();
+                if progress.phase_start_synced_version.is_none() {
+                    progress.phase_start_synced_version = Some(synced_version);
+                }
+                progress.synced_version = Some(synced_version);
+            },
+            None => {
+                let now = self.time_service.now();
+                progress.synced_version_schedule.record_failure(now);
+            },
+        }
+    }
+
+    /// Updates the target version
+    pub fn update_target_version(&self, target_version: Option<Version>) {
+        self.progress.write().target_version = target_version;
+    }
+
+    /// Updates the number of states synced for the given state snapshot
+    /// version. If the snapshot version has changed, the total is reset.
+    pub fn update_states_synced(&self, state_snapshot_version: Version, num_states_synced: u64) {
+        let mut progress = self.progress.write();
+        if progress.state_snapshot_version != Some(state_snapshot_version) {
+            progress.state_snapshot_version = Some(state_snapshot_version);
+            progress.phase_start_num_states_synced = Some(num_states_synced);
+            progress.total_num_states = None;
+            progress.total_num_states_requested = false;
+            progress.total_num_states_schedule = RequestSchedule::new(self.time_service.now());
+        } else if progress.phase_start_num_states_synced.is_none() {
+            progress.phase_start_num_states_synced = Some(num_states_synced);
+        }
+        progress.num_states_synced = Some(num_states_synced);
+    }
+
+    /// Updates the total number of states for the given state snapshot
+    /// version. If the total is missing (e.g., the request failed), the
+    /// total will be requested again (after a backoff).
+    pub fn update_total_num_states(
+        &self,
+        state_snapshot_version: Version,
+        total_num_states: Option<u64>,
+    ) {
+        let mut progress = self.progress.write();
+        if progress.state_snapshot_version == Some(state_snapshot_version) {
+            progress.total_num_states = total_num_states;
+            progress.total_num_states_requested = false;
+            match total_num_states {
+                Some(_) => progress.total_num_states_schedule.record_success(),
+                None => {
+                    let now = self.time_service.now();
+                    progress.total_num_states_schedule.record_failure(now);
+                },
+            }
+        }
+    }
+
+    /// Returns the state snapshot version for which the total number of
+    /// states should be fetched (if any). Each version is only returned
+    /// once, unless the previous request failed and the backoff has elapsed.
+    pub fn should_fetch_total_num_states(&self) -> Option<Version> {
+        let now = self.time_service.now();
+        let mut progress = self.progress.write();
+        if progress.phase != SyncPhase::DownloadingStates
+            || progress.total_num_states.is_some()
+            || progress.total_num_states_requested
+            || !progress.total_num_states_schedule.is_ready(now)
+        {
+            return None;
+        }
+
+        progress.total_num_states_requested = true;
+        progress.state_snapshot_version
+    }
+
+    /// Returns a report of the current state sync progress
+    pub fn get_progress_report(&self) -> StateSyncProgressReport {
+        let progress = self.progress.read().clone();
+
+        // Calculate the phase duration
+        let phase_duration = self
+            .time_service
+            .now()
+            .saturating_duration_since(progress.phase_start_time);
+        let phase_duration_secs = phase_duration.as_secs_f64();
+
+        // Calculate the throughput in this phase
+        let calculate_rate = |start: Option<u64>, current: Option<u64>| match (start, current) {
+            (Some(start), Some(current)) if phase_duration_secs > 0.0 => {
+                Some(current.saturating_sub(start) as f64 / phase_duration_secs)
+            },
+            _ => None,
+        };
+        let versions_per_sec =
+            calculate_rate(progress.phase_start_synced_version, progress.synced_version);
+        let states_per_sec = calculate_rate(
+            progress.phase_start_num_states_synced,
+            progress.num_states_synced,
+        );
+
+        // Estimate the remaining time for the phase
+        let estimate_secs = |remaining: Option<u64>, rate: Option<f64>| match (remaining, rate) {
+            (Some(remaining), Some(rate)) if rate > 0.0 => Some((remaining as f64 / rate) as u64),
+            _ => None,
+        };
+        let estimated_secs_remaining = match progress.phase {
+            SyncPhase::DownloadingStates => {
+                let remaining_states = progress
+                    .total_num_states
+                    .zip(progress.num_states_synced)
+                    .map(|(total, synced)| total.saturating_sub(synced));
+                estimate_secs(remaining_states, states_per_sec)
+            },
+            SyncPhase::SyncingTransactions | SyncPhase::ContinuousSyncing => {
+                let remaining_versions = progress
+                    .target_version
+                    .zip(progress.synced_version)
+                    .map(|(target, synced)| target.saturating_sub(synced));
+                estimate_secs(remaining_versions, versions_per_sec)
+            },
+            _ => None,
+        };
+
+        StateSyncProgressReport {
+            phase: progress.phase,
+            phase_duration_secs: phase_duration.as_secs(),
+            synced_version: progress.synced_version,
+            target_version: progress.target_version,
+            state_snapshot_version: progress.state_snapshot_version,
+            num_states_synced: progress.num_states_synced,
+            total_num_states: progress.total_num_states,
+            versions_per_sec,
+            states_per_sec,
+            estimated_secs_remaining,
+        }
+    }
+}
@@ -0,0 +1,596 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    driver_client::{ClientNotificationListener, DriverClient},
+    error::Error,
+    light_client::LightClient,
+};
+use aptos_config::config::AptosDataClientConfig;
+use aptos_crypto::{bls12381, hash::CryptoHash, HashValue, Uniform};
+use aptos_data_client::{
+    error,
+    global_summary::GlobalDataSummary,
+    interface::{
+        AptosDataClientInterface, Response, ResponseCallback, ResponseContext, ResponseError,
+        SubscriptionRequestMetadata,
+    },
+};
+use aptos_infallible::Mutex;
+use aptos_storage_service_types::{
+    responses::{StateValueWithProof, TransactionOrOutputListWithProof},
+    Epoch,
+};
+use aptos_types::{
+    account_address::AccountAddress,
+    aggregate_signature::{AggregateSignature, PartialSignatures},
+    block_info::BlockInfo,
+    epoch_state::EpochState,
+    ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    proof::{
+        accumulator::InMemoryTransactionAccumulator, SparseMerkleLeafNode, SparseMerkleProof,
+        TransactionAccumulatorProof, TransactionInfoWithProof,
+    },
+    state_store::{
+        state_key::StateKey,
+        state_value::{StateValue, StateValueChunkWithProof},
+    },
+    transaction::{
+        ExecutionStatus, TransactionInfo, TransactionListWithProof, TransactionOutputListWithProof,
+        Version,
+    },
+    trusted_state::TrustedState,
+    validator_signer::ValidatorSigner,
+    validator_verifier::ValidatorVerifier,
+    waypoint::Waypoint,
+};
+use async_trait::async_trait;
+use futures::channel::mpsc;
+use rand::rngs::OsRng;
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+use tokio::time::timeout;
+
+#[tokio::test]
+async fn test_sync_epoch_changes() {
+    // Create an epoch ending ledger info chain (genesis -> epoch 1 -> epoch 2)
+    let (signer_1, epoch_state_1) = create_signer_and_epoch_state(1);
+    let (signer_2, epoch_state_2) = create_signer_and_epoch_state(2);
+    let genesis_ledger_info = create_genesis_ledger_info(epoch_state_1.clone());
+    let epoch_ending_ledger_info = create_signed_ledger_info(
+        &signer_1,
+        &epoch_state_1.verifier,
+        1,
+        10,
+        HashValue::random(),
+        Some(epoch_state_2.clone()),
+    );
+
+    // Create the target ledger info (inside epoch 2)
+    let target_ledger_info = create_signed_ledger_info(
+        &signer_2,
+        &epoch_state_2.verifier,
+        2,
+        15,
+        HashValue::random(),
+        None,
+    );
+
+    // Create the light client
+    let data_client = TestDataClient::new(
+        vec![genesis_ledger_info.clone(), epoch_ending_ledger_info],
+        target_ledger_info.clone(),
+        None,
+    );
+    let light_client = create_light_client(data_client.clone(), &genesis_ledger_info);
+
+    // Sync to the latest ledger info and verify the epoch changes were verified
+    let synced_ledger_info = light_client.sync_to_latest_ledger_info().await.unwrap();
+    assert_eq!(synced_ledger_info, target_ledger_info);
+    assert_eq!(
+        light_client.get_latest_ledger_info(),
+        Some(target_ledger_info)
+    );
+    match light_client.get_trusted_state() {
+        TrustedState::EpochState { epoch_state, .. } => assert_eq!(epoch_state, epoch_state_2),
+        trusted_state => panic!("Unexpected trusted state: {:?}", trusted_state),
+    }
+    assert!(data_client.get_bad_responses().is_empty());
+}
+
+#[tokio::test]
+async fn test_sync_invalid_epoch_change() {
+    // Create an epoch ending ledger info chain where the epoch 1
+    // ledger info is signed by a validator outside the validator set.
+    let (_, epoch_state_1) = create_signer_and_epoch_state(1);
+    let (invalid_signer, invalid_epoch_state) = create_signer_and_epoch_state(1);
+    let (signer_2, epoch_state_2) = create_signer_and_epoch_state(2);
+    let genesis_ledger_info = create_genesis_ledger_info(epoch_state_1);
+    let epoch_ending_ledger_info = create_signed_ledger_info(
+        &invalid_signer,
+        &invalid_epoch_state.verifier,
+        1,
+        10,
+        HashValue::random(),
+        Some(epoch_state_2.clone()),
+    );
+
+    // Create the target ledger info (inside epoch 2)
+    let target_ledger_info = create_signed_ledger_info(
+        &signer_2,
+        &epoch_state_2.verifier,
+        2,
+        15,
+        HashValue::random(),
+        None,
+    );
+
+    // Create the light client
+    let data_client = TestDataClient::new(
+        vec![genesis_ledger_info.clone(), epoch_ending_ledger_info],
+        target_ledger_info,
+        None,
+    );
+    let light_client = create_light_client(data_client.clone(), &genesis_ledger_info);
+
+    // Verify that syncing fails and that the bad response is reported
+    let error = light_client.sync_to_latest_ledger_info().await.unwrap_err();
+    assert!(matches!(error, Error::VerificationError(_)));
+    assert!(light_client.get_latest_ledger_info().is_none());
+    assert_eq!(data_client.get_bad_responses(), vec![
+        ResponseError::ProofVerificationError
+    ]);
+}
+
+#[tokio::test]
+async fn test_sync_invalid_target() {
+    // Create an epoch ending ledger info chain (genesis -> epoch 1)
+    let (_, epoch_state_1) = create_signer_and_epoch_state(1);
+    let genesis_ledger_info = create_genesis_ledger_info(epoch_state_1);
+
+    // Create a target ledger info (inside epoch 1) signed by an invalid validator
+    let (invalid_signer, invalid_epoch_state) = create_signer_and_epoch_state(1);
+    let target_ledger_info = create_signed_ledger_info(
+        &invalid_signer,
+        &invalid_epoch_state.verifier,
+        1,
+        5,
+        HashValue::random(),
+        None,
+    );
+
+    // Create the light client
+    let data_client =
+        TestDataClient::new(vec![genesis_ledger_info.clone()], target_ledger_info, None);
+    let light_client = create_light_client(data_client, &genesis_ledger_info);
+
+    // Verify that syncing fails and that no ledger info is trusted
+    let error = light_client.sync_to_latest_ledger_info().await.unwrap_err();
+    assert!(matches!(error, Error::VerificationError(_)));
+    assert!(light_client.get_latest_ledger_info().is_none());
+}
+
+#[tokio::test]
+async fn test_get_state_value() {
+    // Create a state value and the corresponding state proof
+    let state_key = StateKey::raw(b"light_client_test_key");
+    let state_value = StateValue::from(vec![1, 2, 3]);
+    let (state_proof, transaction_info_with_proof, accumulator_root_hash) =
+        create_state_proofs(&state_key, &state_value);
+
+    // Create the genesis and target ledger infos (the target holds the state value)
+    let (signer_1, epoch_state_1) = create_signer_and_epoch_state(1);
+    let genesis_ledger_info = create_genesis_ledger_info(epoch_state_1.clone());
+    let target_ledger_info = create_signed_ledger_info(
+        &signer_1,
+        &epoch_state_1.verifier,
+        1,
+        1,
+        accumulator_root_hash,
+        None,
+    );
+
+    // Create the light client and sync to the target
+    let state_value_with_proof = StateValueWithProof {
+        state_value: Some(state_value.clone()),
+        state_proof,
+        transaction_info_with_proof,
+    };
+    let data_client = TestDataClient::new(
+        vec![genesis_ledger_info.clone()],
+        target_ledger_info,
+        Some(state_value_with_proof),
+    );
+    let light_client = create_light_client(data_client.clone(), &genesis_ledger_info);
+    light_client.sync_to_latest_ledger_info().await.unwrap();
+
+    // Verify that the state value is fetched and verified
+    let fetched_state_value = light_client.get_state_value(&state_key).await.unwrap();
+    assert_eq!(fetched_state_value, Some(state_value));
+    assert!(data_client.get_bad_responses().is_empty());
+}
+
+#[tokio::test]
+async fn test_get_state_value_invalid_proof() {
+    // Create a state value and the corresponding state proof
+    let state_key = StateKey::raw(b"light_client_test_key");
+    let state_value = StateValue::from(vec![1, 2, 3]);
+    let (state_proof, transaction_info_with_proof, accumulator_root_hash) =
+        create_state_proofs(&state_key, &state_value);
+
+    // Create the genesis and target ledger infos (the target holds the state value)
+    let (signer_1, epoch_state_1) = create_signer_and_epoch_state(1);
+    let genesis_ledger_info = create_genesis_ledger_info(epoch_state_1.clone());
+    let target_ledger_info = create_signed_ledger_info(
+        &signer_1,
+        &epoch_state_1.verifier,
+        1,
+        1,
+        accumulator_root_hash,
+        None,
+    );
+
+    // Create the light client (the peer responds with a modified state value)
+    let state_value_with_proof = StateValueWithProof {
+        state_value: Some(StateValue::from(vec![4, 5, 6])),
+        state_proof,
+        transaction_info_with_proof,
+    };
+    let data_client = TestDataClient::new(
+        vec![genesis_ledger_info.clone()],
+        target_ledger_info,
+        Some(state_value_with_proof),
+    );
+    let light_client = create_light_client(data_client.clone(), &genesis_ledger_info);
+    light_client.sync_to_latest_ledger_info().await.unwrap();
+
+    // Verify that the state value is rejected and that the bad response is reported
+    let error = light_client.get_state_value(&state_key).await.unwrap_err();
+    assert!(matches!(error, Error::VerificationError(_)));
+    assert_eq!(data_client.get_bad_responses(), vec![
+        ResponseError::ProofVerificationError
+    ]);
+}
+
+#[tokio::test]
+async fn test_notify_once_bootstrapped() {
+    // Create the genesis and target ledger infos
+    let (signer_1, epoch_state_1) = create_signer_and_epoch_state(1);
+    let genesis_ledger_info = create_genesis_ledger_info(epoch_state_1.clone());
+    let target_ledger_info = create_signed_ledger_info(
+        &signer_1,
+        &epoch_state_1.verifier,
+        1,
+        5,
+        HashValue::random(),
+        None,
+    );
+
+    // Create and start the light client
+    let data_client = TestDataClient::new(
+        vec![genesis_ledger_info.clone()],
+        target_ledger_info.clone(),
+        None,
+    );
+    let light_client = create_light_client(data_client, &genesis_ledger_info);
+    let (client_notification_sender, client_notification_receiver) = mpsc::unbounded();
+    tokio::spawn(light_client.clone().start_light_client(
+        ClientNotificationListener::new(client_notification_receiver),
+        10,
+    ));
+
+    // Verify that the bootstrap listener is notified once the target is synced
+    let driver_client = DriverClient::new(client_notification_sender);
+    timeout(
+        Duration::from_secs(10),
+        driver_client.notify_once_bootstrapped(),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    assert_eq!(
+        light_client.get_latest_ledger_info(),
+        Some(target_ledger_info)
+    );
+}
+
+/// Creates the genesis ledger info (which is verified by the waypoint)
+fn create_genesis_ledger_info(next_epoch_state: EpochState) -> LedgerInfoWithSignatures {
+    let block_info = BlockInfo::new(
+        0,
+        0,
+        HashValue::zero(),
+        HashValue::random(),
+        0,
+        0,
+        Some(next_epoch_state),
+    );
+    let ledger_info = LedgerInfo::new(block_info, HashValue::zero());
+    LedgerInfoWithSignatures::new(ledger_info, AggregateSignature::empty())
+}
+
+/// Creates a light client that trusts the given genesis ledger info
+fn create_light_client(
+    data_client: TestDataClient,
+    genesis_ledger_info: &LedgerInfoWithSignatures,
+) -> LightClient<TestDataClient> {
+    let waypoint = Waypoint::new_epoch_boundary(genesis_ledger_info.ledger_info()).unwrap();
+    LightClient::new(data_client, AptosDataClientConfig::default(), waypoint)
+}
+
+/// Creates a ledger info signed by the given signer
+fn create_signed_ledger_info(
+    signer: &ValidatorSigner,
+    verifier: &ValidatorVerifier,
+    epoch: Epoch,
+    version: Version,
+    accumulator_root_hash: HashValue,
+    next_epoch_state: Option<EpochState>,
+) -> LedgerInfoWithSignatures {
+    let block_info = BlockInfo::new(
+        epoch,
+        0,
+        HashValue::zero(),
+        accumulator_root_hash,
+        version,
+        0,
+        next_epoch_state,
+    );
+    let ledger_info = LedgerInfo::new(block_info, HashValue::zero());
+
+    // Sign the ledger info
+    let signature = signer.sign(&ledger_info).unwrap();
+    let partial_signatures = PartialSignatures::new(BTreeMap::from([(signer.author(), signature)]));
+    let aggregate_signature = verifier.aggregate_signatures(&partial_signatures).unwrap();
+
+    LedgerInfoWithSignatures::new(ledger_info, aggregate_signature)
+}
+
+/// Creates a single validator signer and the epoch state it belongs to
+fn create_signer_and_epoch_state(epoch: Epoch) -> (ValidatorSigner, EpochState) {
+    let private_key = bls12381::PrivateKey::generate(&mut OsRng);
+    let signer = ValidatorSigner::new(AccountAddress::random(), private_key);
+    let verifier = ValidatorVerifier::new_single(signer.author(), signer.public_key());
+    (signer, EpochState::new(epoch, verifier))
+}
+
+/// Creates the proofs for the given state value at version 1. Returns the state
+/// proof, the transaction info proof and the transaction accumulator root hash.
+fn create_state_proofs(
+    state_key: &StateKey,
+    state_value: &StateValue,
+) -> (SparseMerkleProof, TransactionInfoWithProof, HashValue) {
+    // Create a state proof for a tree holding only the state value
+    let leaf = SparseMerkleLeafNode::new(state_key.hash(), state_value.hash());
+    let state_proof = SparseMerkleProof::new(Some(leaf), vec![]);
+
+    // Create the transaction infos for versions 0 and 1
+    let transaction_info_0 = create_transaction_info(None);
+    let transaction_info_1 = create_transaction_info(Some(leaf.hash()));
+
+    // Create the transaction accumulator and the proof for version 1
+    let accumulator = InMemoryTransactionAccumulator::from_leaves(&[
+        transaction_info_0.hash(),
+        transaction_info_1.hash(),
+    ]);
+    let accumulator_proof = TransactionAccumulatorProof::new(vec![transaction_info_0.hash()]);
+    let transaction_info_with_proof =
+        TransactionInfoWithProof::new(accumulator_proof, transaction_info_1);
+
+    (
+        state_proof,
+        transaction_info_with_proof,
+        accumulator.root_hash(),
+    )
+}
+
+/// Creates a transaction info with the given state checkpoint hash
+fn create_transaction_info(state_checkpoint_hash: Option<HashValue>) -> TransactionInfo {
+    TransactionInfo::new(
+        HashValue::random(),
+        HashValue::random(),
+        HashValue::random(),
+        state_checkpoint_hash,
+        0,
+        ExecutionStatus::Success,
+    )
+}
+
+/// A simple data client that responds with a fixed set of
+/// ledger infos and state values (and records bad responses).
+#[derive(Clone, Debug)]
+struct TestDataClient {
+    bad_responses: Arc<Mutex<Vec<ResponseError>>>,
+    epoch_ending_ledger_infos: Vec<LedgerInfoWithSignatures>,
+    highest_synced_ledger_info: LedgerInfoWithSignatures,
+    state_value_with_proof: Option<StateValueWithProof>,
+}
+
+impl TestDataClient {
+    fn new(
+        epoch_ending_ledger_infos: Vec<LedgerInfoWithSignatures>,
+        highest_synced_ledger_info: LedgerInfoWithSignatures,
+        state_value_with_proof: Option<StateValueWithProof>,
+    ) -> Self {
+        Self {
+            bad_responses: Arc::new(Mutex::new(vec![])),
+            epoch_ending_ledger_infos,
+            highest_synced_ledger_info,
+            state_value_with_proof,
+        }
+    }
+
+    /// Returns the bad responses reported by the light client
+    fn get_bad_responses(&self) -> Vec<ResponseError> {
+        self.bad_responses.lock().clone()
+    }
+
+    /// Creates a response for the given payload
+    fn create_response<T>(&self, payload: T) -> Response<T> {
+        let response_callback = TestResponseCallback {
+            bad_responses: self.bad_responses.clone(),
+        };
+        Response::new(
+            ResponseContext::new(0, Box::new(response_callback)),
+            payload,
+        )
+    }
+}
+
+#[async_trait]
+impl AptosDataClientInterface for TestDataClient {
+    fn get_global_data_summary(&self) -> GlobalDataSummary {
+        let mut global_data_summary = GlobalDataSummary::empty();
+        global_data_summary.advertised_data.synced_ledger_infos =
+            vec![self.highest_synced_ledger_info.clone()];
+        global_data_summary
+    }
+
+    async fn get_epoch_ending_ledger_infos(
+        &self,
+        start_epoch: Epoch,
+        expected_end_epoch: Epoch,
+        _request_timeout_ms: u64,
+    ) -> error::Result<Response<Vec<LedgerInfoWithSignatures>>> {
+        let epoch_ending_ledger_infos = self
+            .epoch_ending_ledger_infos
+            .iter()
+            .filter(|ledger_info| {
+                let epoch = ledger_info.ledger_info().epoch();
+                epoch >= start_epoch && epoch <= expected_end_epoch
+            })
+            .cloned()
+            .collect();
+        Ok(self.create_response(epoch_ending_ledger_infos))
+    }
+
+    async fn get_new_transaction_outputs_with_proof(
+        &self,
+        _known_version: Version,
+        _known_epoch: Epoch,
+        _request_timeout_ms: u64,
+    ) -> error::Result<Response<(TransactionOutputListWithProof, LedgerInfoWithSignatures)>> {
+        unimplemented!("Not used by the light client!")
+    }
+
+    async fn get_new_transactions_with_proof(
+        &self,
+        _known_version: Version,
+        _known_epoch: Epoch,
+        _include_events: bool,
+        _request_timeout_ms: u64,
+    ) -> error::Result<Response<(TransactionListWithProof, LedgerInfoWithSignatures)>> {
+        unimplemented!("Not used by the light client!")
+    }
+
+    async fn get_new_transactions_or_outputs_with_proof(
+        &self,
+        _known_version: Version,
+        _known_epoch: Epoch,
+        _include_events: bool,
+        _request_timeout_ms: u64,
+    ) -> error::Result<Response<(TransactionOrOutputListWithProof, LedgerInfoWithSignatures)>> {
+        unimplemented!("Not used by the light client!")
+    }
+
+    async fn get_number_of_states(
+        &self,
+        _version: Version,
+        _request_timeout_ms: u64,
+    ) -> error::Result<Response<u64>> {
+        unimplemented!("Not used by the light client!")
+    }
+
+    async fn get_state_value_with_proof(
+        &self,
+        _state_key: StateKey,
+        _version: Version,
+        _proof_version: Version,
+        _request_timeout_ms: u64,
+    ) -> error::Result<Response<StateValueWithProof>> {
+        let state_value_with_proof = self
+            .state_value_with_proof
+            .clone()
+            .expect("No state value with proof was given to the test data client!");
+        Ok(self.create_response(state_value_with_proof))
+    }
+
+    async fn get_state_values_with_proof(
+        &self,
+        _version: u64,
+        _start_index: u64,
+        _end_index: u64,
+        _request_timeout_ms: u64,
+    ) -> error::Result<Response<StateValueChunkWithProof>> {
+        unimplemented!("Not used by the light client!")
+    }
+
+    async fn get_transaction_outputs_with_proof(
+        &self,
+        _proof_version: Version,
+        _start_version: Version,
+        _end_version: Version,
+        _request_timeout_ms: u64,
+    ) -> error::Result<Response<TransactionOutputListWithProof>> {
+        unimplemented!("Not used by the light client!")
+    }
+
+    async fn get_transactions_with_proof(
+        &self,
+        _proof_version: Version,
+        _start_version: Version,
+        _end_version: Version,
+        _include_events: bool,
+        _request_timeout_ms: u64,
+    ) -> error::Result<Response<TransactionListWithProof>> {
+        unimplemented!("Not used by the light client!")
+    }
+
+    async fn get_transactions_or_outputs_with_proof(
+        &self,
+        _proof_version: Version,
+        _start_version: Version,
+        _end_version: Version,
+        _include_events: bool,
+        _request_timeout_ms: u64,
+    ) -> error::Result<Response<TransactionOrOutputListWithProof>> {
+        unimplemented!("Not used by the light client!")
+    }
+
+    async fn subscribe_to_transaction_outputs_with_proof(
+        &self,
+        _subscription_request_metadata: SubscriptionRequestMetadata,
+        _request_timeout_ms: u64,
+    ) -> error::Result<Response<(TransactionOutputListWithProof, LedgerInfoWithSignatures)>> {
+        unimplemented!("Not used by the light client!")
+    }
+
+    async fn subscribe_to_transactions_with_proof(
+        &self,
+        _subscription_request_metadata: SubscriptionRequestMetadata,
+        _include_events: bool,
+        _request_timeout_ms: u64,
+    ) -> error::Result<Response<(TransactionListWithProof, LedgerInfoWithSignatures)>> {
+        unimplemented!("Not used by the light client!")
+    }
+
+    async fn subscribe_to_transactions_or_outputs_with_proof(
+        &self,
+        _subscription_request_metadata: SubscriptionRequestMetadata,
+        _include_events: bool,
+        _request_timeout_ms: u64,
+    ) -> error::Result<Response<(TransactionOrOutputListWithProof, LedgerInfoWithSignatures)>> {
+        unimplemented!("Not used by the light client!")
+    }
+}
+
+/// A response callback that records the reported bad responses
+#[derive(Debug)]
+struct TestResponseCallback {
+    bad_responses: Arc<Mutex<Vec<ResponseError>>>,
+}
+
+impl ResponseCallback for TestResponseCallback {
+    fn notify_bad_response(&self, error: ResponseError) {
+        self.bad_responses.lock().push(error);
+    }
+}
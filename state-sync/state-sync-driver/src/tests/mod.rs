@@ -6,7 +6,9 @@ mod bootstrapper;
 mod continuous_syncer;
 mod driver;
 mod driver_factory;
+mod light_client;
 mod metadata_storage;
 mod mocks;
+mod progress;
 mod storage_synchronizer;
 mod utils;
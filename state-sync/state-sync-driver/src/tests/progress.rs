@@ -0,0 +1,133 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::progress::{StateSyncProgressTracker, SyncPhase};
+use aptos_time_service::TimeService;
+use claims::{assert_none, assert_some_eq};
+use std::time::Duration;
+
+#[test]
+fn test_progress_report_states() {
+    // Create a progress tracker
+    let time_service = TimeService::mock();
+    let progress_tracker = StateSyncProgressTracker::new(time_service.clone());
+
+    // Start downloading states for a snapshot
+    let state_snapshot_version = 1000;
+    progress_tracker.update_phase(SyncPhase::DownloadingStates);
+    progress_tracker.update_synced_version(Some(0));
+    progress_tracker.update_target_version(Some(2000));
+    progress_tracker.update_states_synced(state_snapshot_version, 0);
+
+    // Verify the total number of states is only requested once
+    assert_some_eq!(
+        progress_tracker.should_fetch_total_num_states(),
+        state_snapshot_version
+    );
+    assert_none!(progress_tracker.should_fetch_total_num_states());
+
+    // Fail to fetch the total and verify it is only requested again after a backoff
+    let mock_time_service = time_service.into_mock();
+    progress_tracker.update_total_num_states(state_snapshot_version, None);
+    assert_none!(progress_tracker.should_fetch_total_num_states());
+    mock_time_service.advance(Duration::from_secs(1));
+    assert_some_eq!(
+        progress_tracker.should_fetch_total_num_states(),
+        state_snapshot_version
+    );
+
+    // Fail again and verify the backoff is doubled
+    progress_tracker.update_total_num_states(state_snapshot_version, None);
+    mock_time_service.advance(Duration::from_secs(1));
+    assert_none!(progress_tracker.should_fetch_total_num_states());
+    mock_time_service.advance(Duration::from_secs(1));
+    assert_some_eq!(
+        progress_tracker.should_fetch_total_num_states(),
+        state_snapshot_version
+    );
+    progress_tracker.update_total_num_states(state_snapshot_version, Some(10_000));
+    assert_none!(progress_tracker.should_fetch_total_num_states());
+
+    // Elapse some time and sync some states
+    mock_time_service.advance(Duration::from_secs(7));
+    progress_tracker.update_states_synced(state_snapshot_version, 1000);
+
+    // Verify the progress report
+    let progress_report = progress_tracker.get_progress_report();
+    assert_eq!(progress_report.phase, SyncPhase::DownloadingStates);
+    assert_eq!(progress_report.phase_duration_secs, 10);
+    assert_some_eq!(progress_report.num_states_synced, 1000);
+    assert_some_eq!(progress_report.total_num_states, 10_000);
+    assert_some_eq!(progress_report.states_per_sec, 100.0);
+    assert_some_eq!(progress_report.estimated_secs_remaining, 90);
+}
+
+#[test]
+fn test_progress_report_transactions() {
+    // Create a progress tracker
+    let time_service = TimeService::mock();
+    let progress_tracker = StateSyncProgressTracker::new(time_service.clone());
+
+    // Start syncing transactions
+    progress_tracker.update_synced_version(Some(100));
+    progress_tracker.update_target_version(Some(10_100));
+    progress_tracker.update_phase(SyncPhase::SyncingTransactions);
+
+    // Verify there's no estimate before any progress is made
+    let progress_report = progress_tracker.get_progress_report();
+    assert_none!(progress_report.versions_per_sec);
+    assert_none!(progress_report.estimated_secs_remaining);
+
+    // Elapse some time and sync some transactions
+    time_service.into_mock().advance(Duration::from_secs(20));
+    progress_tracker.update_synced_version(Some(2100));
+
+    // Verify the progress report
+    let progress_report = progress_tracker.get_progress_report();
+    assert_eq!(progress_report.phase, SyncPhase::SyncingTransactions);
+    assert_some_eq!(progress_report.synced_version, 2100);
+    assert_some_eq!(progress_report.target_version, 10_100);
+    assert_some_eq!(progress_report.versions_per_sec, 100.0);
+    assert_some_eq!(progress_report.estimated_secs_remaining, 80);
+
+    // Move to continuous syncing and verify the throughput is reset
+    progress_tracker.update_phase(SyncPhase::ContinuousSyncing);
+    let progress_report = progress_tracker.get_progress_report();
+    assert_eq!(progress_report.phase, SyncPhase::ContinuousSyncing);
+    assert_eq!(progress_report.phase_duration_secs, 0);
+    assert_none!(progress_report.versions_per_sec);
+}
+
+#[test]
+fn test_synced_version_refresh() {
+    // Create a progress tracker
+    let time_service = TimeService::mock();
+    let progress_tracker = StateSyncProgressTracker::new(time_service.clone());
+    let mock_time_service = time_service.into_mock();
+
+    // Verify the synced version is only refreshed once per interval
+    assert!(progress_tracker.should_refresh_synced_version());
+    progress_tracker.update_synced_version(Some(100));
+    assert!(!progress_tracker.should_refresh_synced_version());
+    mock_time_service.advance(Duration::from_secs(1));
+    assert!(progress_tracker.should_refresh_synced_version());
+
+    // Fail to refresh the synced version and verify the cached version is kept
+    progress_tracker.update_synced_version(None);
+    assert_some_eq!(progress_tracker.get_progress_report().synced_version, 100);
+
+    // Verify the refreshes are backed off after repeated failures
+    mock_time_service.advance(Duration::from_secs(1));
+    assert!(progress_tracker.should_refresh_synced_version());
+    progress_tracker.update_synced_version(None);
+    mock_time_service.advance(Duration::from_secs(1));
+    assert!(!progress_tracker.should_refresh_synced_version());
+    mock_time_service.advance(Duration::from_secs(1));
+    assert!(progress_tracker.should_refresh_synced_version());
+
+    // Verify a successful refresh resets the backoff
+    progress_tracker.update_synced_version(Some(200));
+    assert_some_eq!(progress_tracker.get_progress_report().synced_version, 200);
+    mock_time_service.advance(Duration::from_secs(1));
+    assert!(progress_tracker.should_refresh_synced_version());
+}
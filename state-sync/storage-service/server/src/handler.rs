@@ -21,12 +21,13 @@ use aptos_logger::{debug, sample, sample::SampleRate, trace, warn};
 use aptos_network::protocols::wire::handshake::v1::ProtocolId;
 use aptos_storage_service_types::{
     requests::{
-        DataRequest, EpochEndingLedgerInfoRequest, StateValuesWithProofRequest,
-        StorageServiceRequest, TransactionOutputsWithProofRequest,
+        DataRequest, EpochEndingLedgerInfoRequest, StateValueWithProofRequest,
+        StateValuesWithProofRequest, StorageServiceRequest, TransactionOutputsWithProofRequest,
         TransactionsOrOutputsWithProofRequest, TransactionsWithProofRequest,
     },
     responses::{
         DataResponse, ServerProtocolVersion, StorageServerSummary, StorageServiceResponse,
+        STORAGE_SERVER_PROTOCOL_VERSION,
     },
     StorageServiceError,
 };
@@ -39,7 +40,6 @@ use std::{sync::Arc, time::Duration};
 
 /// Storage server constants
 const ERROR_LOG_FREQUENCY_SECS: u64 = 5; // The frequency to log errors
-const STORAGE_SERVER_VERSION: u64 = 1;
 const SUMMARY_LOG_FREQUENCY_SECS: u64 = 5; // The frequency to log the storage server summary (secs)
 
 /// The `Handler` is the "pure" inbound request handler. It contains all the
@@ -409,6 +409,9 @@ impl<T: StorageReaderInterface> Handler<T> {
             DataRequest::GetTransactionsOrOutputsWithProof(request) => {
                 self.get_transactions_or_outputs_with_proof(request)
             },
+            DataRequest::GetStateValueWithProof(request) => {
+                self.get_state_value_with_proof(request)
+            },
             _ => Err(Error::UnexpectedErrorEncountered(format!(
                 "Received an unexpected request: {:?}",
                 request
@@ -458,6 +461,19 @@ impl<T: StorageReaderInterface> Handler<T> {
         ))
     }
 
+    fn get_state_value_with_proof(
+        &self,
+        request: &StateValueWithProofRequest,
+    ) -> aptos_storage_service_types::Result<DataResponse, Error> {
+        let state_value_with_proof = self.storage.get_state_value_with_proof(
+            &request.state_key,
+            request.version,
+            request.proof_version,
+        )?;
+
+        Ok(DataResponse::StateValueWithProof(state_value_with_proof))
+    }
+
     fn get_epoch_ending_ledger_infos(
         &self,
         request: &EpochEndingLedgerInfoRequest,
@@ -480,7 +496,7 @@ impl<T: StorageReaderInterface> Handler<T> {
 
     fn get_server_protocol_version(&self) -> DataResponse {
         let server_protocol_version = ServerProtocolVersion {
-            protocol_version: STORAGE_SERVER_VERSION,
+            protocol_version: STORAGE_SERVER_PROTOCOL_VERSION,
         };
         DataResponse::ServerProtocolVersion(server_protocol_version)
     }
@@ -6,13 +6,20 @@ use aptos_config::config::StorageServiceConfig;
 use aptos_logger::debug;
 use aptos_storage_interface::{AptosDbError, DbReader, Result as StorageResult};
 use aptos_storage_service_types::responses::{
-    CompleteDataRange, DataResponse, DataSummary, TransactionOrOutputListWithProof,
+    CompleteDataRange, DataResponse, DataSummary, StateValueWithProof,
+    TransactionOrOutputListWithProof,
 };
 use aptos_types::{
     epoch_change::EpochChangeProof,
     ledger_info::LedgerInfoWithSignatures,
-    state_store::state_value::StateValueChunkWithProof,
-    transaction::{TransactionListWithProof, TransactionOutputListWithProof, Version},
+    proof::SparseMerkleProof,
+    state_store::{
+        state_key::StateKey,
+        state_value::{StateValue, StateValueChunkWithProof},
+    },
+    transaction::{
+        TransactionListWithProof, TransactionOutputListWithProof, TransactionWithProof, Version,
+    },
 };
 use serde::Serialize;
 use std::{cmp::min, sync::Arc};
@@ -89,6 +96,16 @@ pub trait StorageReaderInterface: Clone + Send + 'static {
         start_index: u64,
         end_index: u64,
     ) -> aptos_storage_service_types::Result<StateValueChunkWithProof, Error>;
+
+    /// Returns the state value (if it exists) for the given key at the
+    /// specified `version`, along with a proof of the value relative to
+    /// the `proof_version`.
+    fn get_state_value_with_proof(
+        &self,
+        state_key: &StateKey,
+        version: u64,
+        proof_version: u64,
+    ) -> aptos_storage_service_types::Result<StateValueWithProof, Error>;
 }
 
 /// The underlying implementation of the StorageReaderInterface, used by the
@@ -498,6 +515,31 @@ impl StorageReaderInterface for StorageReader {
             version, start_index, end_index
         )))
     }
+
+    fn get_state_value_with_proof(
+        &self,
+        state_key: &StateKey,
+        version: u64,
+        proof_version: u64,
+    ) -> aptos_storage_service_types::Result<StateValueWithProof, Error> {
+        // Fetch the state value and the proof against the state root
+        let (state_value, state_proof) = self
+            .storage
+            .get_state_value_with_proof_by_version(state_key, version)
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+
+        // Fetch the transaction info (holding the state root) and the proof against the ledger
+        let transaction_with_proof = self
+            .storage
+            .get_transaction_by_version(version, proof_version, false)
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+
+        Ok(StateValueWithProof {
+            state_value,
+            state_proof,
+            transaction_info_with_proof: transaction_with_proof.proof,
+        })
+    }
 }
 
 // A simple macro that wraps each storage read call with a timer
@@ -578,6 +620,19 @@ impl DbReader for TimedStorageReader {
             start_idx: usize,
             chunk_size: usize,
         ) -> StorageResult<StateValueChunkWithProof>;
+
+        fn get_state_value_with_proof_by_version(
+            &self,
+            state_key: &StateKey,
+            version: Version,
+        ) -> StorageResult<(Option<StateValue>, SparseMerkleProof)>;
+
+        fn get_transaction_by_version(
+            &self,
+            version: Version,
+            ledger_version: Version,
+            fetch_events: bool,
+        ) -> StorageResult<TransactionWithProof>;
     );
 }
 
@@ -11,6 +11,7 @@ mod number_of_states;
 mod optimistic_fetch;
 mod protocol_version;
 mod request_moderator;
+mod state_value;
 mod state_values;
 mod storage_summary;
 mod subscribe_transaction_outputs;
@@ -0,0 +1,129 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::tests::{mock, mock::MockClient, utils};
+use aptos_crypto::hash::HashValue;
+use aptos_storage_service_types::{
+    requests::{DataRequest, StateValueWithProofRequest},
+    responses::{DataResponse, StateValueWithProof, StorageServiceResponse},
+    StorageServiceError,
+};
+use aptos_types::{
+    proof::{SparseMerkleProof, TransactionAccumulatorProof, TransactionInfoWithProof},
+    state_store::{state_key::StateKey, state_value::StateValue},
+    transaction::{ExecutionStatus, Transaction, TransactionInfo, TransactionWithProof},
+};
+use claims::assert_matches;
+use mockall::predicate::eq;
+
+#[tokio::test]
+async fn test_get_state_value_with_proof() {
+    // Test both existing and non-existing state values
+    for state_value in [Some(StateValue::from(vec![1, 2, 3])), None] {
+        // Create test data
+        let version = 101;
+        let proof_version = 150;
+        let state_key = StateKey::raw(b"test_key");
+        let state_proof = SparseMerkleProof::new(None, vec![HashValue::random()]);
+        let transaction_info_with_proof = create_transaction_info_with_proof();
+
+        // Create the mock db reader
+        let mut db_reader = mock::create_mock_db_reader();
+        let state_value_clone = state_value.clone();
+        let state_proof_clone = state_proof.clone();
+        db_reader
+            .expect_get_state_value_with_proof_by_version()
+            .times(1)
+            .with(eq(state_key.clone()), eq(version))
+            .returning(move |_, _| Ok((state_value_clone.clone(), state_proof_clone.clone())));
+        let transaction_with_proof = TransactionWithProof::new(
+            version,
+            Transaction::StateCheckpoint(HashValue::random()),
+            None,
+            transaction_info_with_proof.clone(),
+        );
+        db_reader
+            .expect_get_transaction_by_version()
+            .times(1)
+            .with(eq(version), eq(proof_version), eq(false))
+            .returning(move |_, _, _| Ok(transaction_with_proof.clone()));
+
+        // Create the storage client and server
+        let (mut mock_client, mut service, _, _, _) = MockClient::new(Some(db_reader), None);
+        utils::update_storage_server_summary(&mut service, proof_version, 10);
+        tokio::spawn(service.start());
+
+        // Process a request to fetch the state value with a proof
+        let response = get_state_value_with_proof(
+            &mut mock_client,
+            state_key.clone(),
+            version,
+            proof_version,
+            false,
+        )
+        .await
+        .unwrap();
+
+        // Verify the response is correct
+        assert_matches!(response, StorageServiceResponse::RawResponse(_));
+        assert_eq!(
+            response.get_data_response().unwrap(),
+            DataResponse::StateValueWithProof(StateValueWithProof {
+                state_value,
+                state_proof,
+                transaction_info_with_proof,
+            })
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_get_state_value_with_proof_not_serviceable() {
+    // Create test data
+    let version = 101;
+    let proof_version = 150;
+    let state_key = StateKey::raw(b"test_key");
+
+    // Create the storage client and server (that cannot service the request)
+    let (mut mock_client, mut service, _, _, _) = MockClient::new(None, None);
+    utils::update_storage_server_summary(&mut service, proof_version - 1, 10);
+    tokio::spawn(service.start());
+
+    // Process a request to fetch the state value with a proof
+    let response =
+        get_state_value_with_proof(&mut mock_client, state_key, version, proof_version, true)
+            .await
+            .unwrap_err();
+
+    // Verify the request is not serviceable
+    assert_matches!(response, StorageServiceError::InvalidRequest(_));
+}
+
+/// Creates a transaction info with proof for testing
+fn create_transaction_info_with_proof() -> TransactionInfoWithProof {
+    let transaction_info = TransactionInfo::new(
+        HashValue::random(),
+        HashValue::random(),
+        HashValue::random(),
+        Some(HashValue::random()),
+        0,
+        ExecutionStatus::Success,
+    );
+    TransactionInfoWithProof::new(TransactionAccumulatorProof::new(vec![]), transaction_info)
+}
+
+/// Sends a state value with proof request and processes the response
+async fn get_state_value_with_proof(
+    mock_client: &mut MockClient,
+    state_key: StateKey,
+    version: u64,
+    proof_version: u64,
+    use_compression: bool,
+) -> Result<StorageServiceResponse, StorageServiceError> {
+    let data_request = DataRequest::GetStateValueWithProof(StateValueWithProofRequest {
+        state_key,
+        version,
+        proof_version,
+    });
+    utils::send_storage_request(mock_client, use_compression, data_request).await
+}
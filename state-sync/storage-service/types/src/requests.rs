@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::COMPRESSION_SUFFIX_LABEL;
-use aptos_types::transaction::Version;
+use aptos_types::{state_store::state_key::StateKey, transaction::Version};
 use serde::{Deserialize, Serialize};
 
 /// A storage service request.
@@ -47,6 +47,7 @@ pub enum DataRequest {
     SubscribeTransactionOutputsWithProof(SubscribeTransactionOutputsWithProofRequest), // Subscribes to transaction outputs with a proof
     SubscribeTransactionsOrOutputsWithProof(SubscribeTransactionsOrOutputsWithProofRequest), // Subscribes to transactions or outputs with a proof
     SubscribeTransactionsWithProof(SubscribeTransactionsWithProofRequest), // Subscribes to transactions with a proof
+    GetStateValueWithProof(StateValueWithProofRequest), // Fetches a single state value with a proof
 }
 
 impl DataRequest {
@@ -73,6 +74,7 @@ impl DataRequest {
                 "subscribe_transactions_or_outputs_with_proof"
             },
             Self::SubscribeTransactionsWithProof(_) => "subscribe_transactions_with_proof",
+            Self::GetStateValueWithProof(_) => "get_state_value_with_proof",
         }
     }
 
@@ -130,6 +132,15 @@ pub struct StateValuesWithProofRequest {
     pub end_index: u64,   // The index to stop fetching state values (inclusive)
 }
 
+/// A storage service request for fetching a single state value (by key)
+/// at a specified version, with a proof relative to the proof version.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct StateValueWithProofRequest {
+    pub state_key: StateKey, // The key of the state value to fetch
+    pub version: u64,        // The version to fetch the state value at
+    pub proof_version: u64,  // The version the proof should be relative to
+}
+
 /// A storage service request for fetching a transaction output list with a
 /// corresponding proof.
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
@@ -5,10 +5,11 @@ use crate::{
     requests::DataRequest::{
         GetEpochEndingLedgerInfos, GetNewTransactionOutputsWithProof,
         GetNewTransactionsOrOutputsWithProof, GetNewTransactionsWithProof,
-        GetNumberOfStatesAtVersion, GetServerProtocolVersion, GetStateValuesWithProof,
-        GetStorageServerSummary, GetTransactionOutputsWithProof, GetTransactionsOrOutputsWithProof,
-        GetTransactionsWithProof, SubscribeTransactionOutputsWithProof,
-        SubscribeTransactionsOrOutputsWithProof, SubscribeTransactionsWithProof,
+        GetNumberOfStatesAtVersion, GetServerProtocolVersion, GetStateValueWithProof,
+        GetStateValuesWithProof, GetStorageServerSummary, GetTransactionOutputsWithProof,
+        GetTransactionsOrOutputsWithProof, GetTransactionsWithProof,
+        SubscribeTransactionOutputsWithProof, SubscribeTransactionsOrOutputsWithProof,
+        SubscribeTransactionsWithProof,
     },
     responses::Error::DegenerateRangeError,
     Epoch, StorageServiceRequest, COMPRESSION_SUFFIX_LABEL,
@@ -17,11 +18,16 @@ use aptos_compression::{client::CompressionClient, CompressedData};
 use aptos_config::config::{
     AptosDataClientConfig, StorageServiceConfig, MAX_APPLICATION_MESSAGE_SIZE,
 };
+use aptos_crypto::hash::CryptoHash;
 use aptos_time_service::{TimeService, TimeServiceTrait};
 use aptos_types::{
     epoch_change::EpochChangeProof,
-    ledger_info::LedgerInfoWithSignatures,
-    state_store::state_value::StateValueChunkWithProof,
+    ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    proof::{SparseMerkleProof, TransactionInfoWithProof},
+    state_store::{
+        state_key::StateKey,
+        state_value::{StateValue, StateValueChunkWithProof},
+    },
     transaction::{TransactionListWithProof, TransactionOutputListWithProof, Version},
 };
 use num_traits::{PrimInt, Zero};
@@ -37,6 +43,10 @@ use thiserror::Error;
 // Useful file constants
 pub const NUM_MICROSECONDS_IN_SECOND: u64 = 1_000_000;
 
+// The storage service protocol versions
+pub const STORAGE_SERVER_PROTOCOL_VERSION: u64 = 2; // The protocol version run by this server
+pub const STATE_VALUE_REQUEST_PROTOCOL_VERSION: u64 = 2; // The first version to support single state value requests
+
 #[derive(Clone, Debug, Deserialize, Error, PartialEq, Eq, Serialize)]
 pub enum Error {
     #[error("Data range cannot be degenerate!")]
@@ -139,6 +149,7 @@ pub enum DataResponse {
     TransactionsWithProof(TransactionListWithProof),
     NewTransactionsOrOutputsWithProof((TransactionOrOutputListWithProof, LedgerInfoWithSignatures)),
     TransactionsOrOutputsWithProof(TransactionOrOutputListWithProof),
+    StateValueWithProof(StateValueWithProof),
 }
 
 impl DataResponse {
@@ -156,6 +167,7 @@ impl DataResponse {
             Self::TransactionsWithProof(_) => "transactions_with_proof",
             Self::NewTransactionsOrOutputsWithProof(_) => "new_transactions_or_outputs_with_proof",
             Self::TransactionsOrOutputsWithProof(_) => "transactions_or_outputs_with_proof",
+            Self::StateValueWithProof(_) => "state_value_with_proof",
         }
     }
 }
@@ -347,6 +359,57 @@ impl TryFrom<StorageServiceResponse> for TransactionOrOutputListWithProof {
     }
 }
 
+impl TryFrom<StorageServiceResponse> for StateValueWithProof {
+    type Error = crate::responses::Error;
+
+    fn try_from(response: StorageServiceResponse) -> crate::Result<Self, Self::Error> {
+        let data_response = response.get_data_response()?;
+        match data_response {
+            DataResponse::StateValueWithProof(inner) => Ok(inner),
+            _ => Err(Error::UnexpectedResponseError(format!(
+                "expected state_value_with_proof, found {}",
+                data_response.get_label()
+            ))),
+        }
+    }
+}
+
+/// A single state value (which may not exist) at a specific version, together
+/// with a proof of the value against the state root at that version, and a
+/// proof of the transaction info (holding the state root) against the ledger.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StateValueWithProof {
+    pub state_value: Option<StateValue>, // The state value (if it exists)
+    pub state_proof: SparseMerkleProof,  // The proof of the state value against the state root
+    pub transaction_info_with_proof: TransactionInfoWithProof, // The proof of the transaction info against the ledger info
+}
+
+impl StateValueWithProof {
+    /// Verifies the state value for the given key at the specified
+    /// version, using the given (already verified) ledger info.
+    pub fn verify(
+        &self,
+        ledger_info: &LedgerInfo,
+        version: Version,
+        state_key: &StateKey,
+    ) -> Result<(), Error> {
+        // Verify the transaction info against the ledger info
+        self.transaction_info_with_proof
+            .verify(ledger_info, version)
+            .map_err(|error| Error::UnexpectedResponseError(error.to_string()))?;
+
+        // Verify the state value against the state root in the transaction info
+        let state_root_hash = self
+            .transaction_info_with_proof
+            .transaction_info()
+            .ensure_state_checkpoint_hash()
+            .map_err(|error| Error::UnexpectedResponseError(error.to_string()))?;
+        self.state_proof
+            .verify(state_root_hash, state_key.hash(), self.state_value.as_ref())
+            .map_err(|error| Error::UnexpectedResponseError(error.to_string()))
+    }
+}
+
 /// The protocol version run by this server. Clients request this first to
 /// identify what API calls and data requests the server supports.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -488,6 +551,28 @@ impl DataSummary {
 
                 can_serve_states && can_create_proof
             },
+            GetStateValueWithProof(request) => {
+                // The transaction info at the version is required to prove the state root
+                let can_serve_state = self
+                    .states
+                    .map(|range| range.contains(request.version))
+                    .unwrap_or(false);
+                let can_serve_txn_info = self
+                    .transactions
+                    .map(|range| range.contains(request.version))
+                    .unwrap_or(false);
+
+                let can_create_proof = self
+                    .synced_ledger_info
+                    .as_ref()
+                    .map(|li| li.ledger_info().version() >= request.proof_version)
+                    .unwrap_or(false);
+
+                can_serve_state
+                    && can_serve_txn_info
+                    && can_create_proof
+                    && request.version <= request.proof_version
+            },
             GetTransactionOutputsWithProof(request) => {
                 let desired_range =
                     match CompleteDataRange::new(request.start_version, request.end_version) {
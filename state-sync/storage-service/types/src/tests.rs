@@ -5,7 +5,8 @@ use crate::{
     requests::{
         DataRequest, EpochEndingLedgerInfoRequest, NewTransactionOutputsWithProofRequest,
         NewTransactionsOrOutputsWithProofRequest, NewTransactionsWithProofRequest,
-        StateValuesWithProofRequest, SubscribeTransactionOutputsWithProofRequest,
+        StateValueWithProofRequest, StateValuesWithProofRequest,
+        SubscribeTransactionOutputsWithProofRequest,
         SubscribeTransactionsOrOutputsWithProofRequest, SubscribeTransactionsWithProofRequest,
         SubscriptionStreamMetadata, TransactionOutputsWithProofRequest,
         TransactionsOrOutputsWithProofRequest, TransactionsWithProofRequest,
@@ -20,6 +21,7 @@ use aptos_types::{
     aggregate_signature::AggregateSignature,
     block_info::BlockInfo,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    state_store::state_key::StateKey,
     transaction::Version,
 };
 use claims::{assert_err, assert_ok};
@@ -466,6 +468,35 @@ fn test_data_summary_can_service_state_chunk_request() {
     }
 }
 
+#[test]
+fn test_data_summary_can_service_state_value_request() {
+    // Create a data client config and data summary
+    let data_client_config = AptosDataClientConfig::default();
+    let data_summary = DataSummary {
+        synced_ledger_info: Some(create_ledger_info_at_version(250)),
+        states: Some(create_data_range(100, 300)),
+        transactions: Some(create_data_range(150, 300)),
+        ..Default::default()
+    };
+
+    // Verify the different requests that can be serviced
+    for compression in [true, false] {
+        // Test the valid request versions
+        let valid_request_versions = vec![(150, 150), (150, 250), (200, 250), (250, 250)];
+        for (version, proof_version) in valid_request_versions {
+            let request = create_state_value_request(version, proof_version, compression);
+            verify_serviceability(&data_client_config, &data_summary, None, request, true);
+        }
+
+        // Test invalid request versions
+        let invalid_request_versions = vec![(100, 250), (149, 250), (200, 199), (250, 251)];
+        for (version, proof_version) in invalid_request_versions {
+            let request = create_state_value_request(version, proof_version, compression);
+            verify_serviceability(&data_client_config, &data_summary, None, request, false);
+        }
+    }
+}
+
 #[test]
 fn test_protocol_metadata_service() {
     // Create the protocol metadata
@@ -668,6 +699,20 @@ fn create_transactions_or_outputs_request(
     StorageServiceRequest::new(data_request, use_compression)
 }
 
+/// Creates a request for a single state value
+fn create_state_value_request(
+    version: Version,
+    proof_version: Version,
+    use_compression: bool,
+) -> StorageServiceRequest {
+    let data_request = DataRequest::GetStateValueWithProof(StateValueWithProofRequest {
+        state_key: StateKey::raw(b"test_key"),
+        version,
+        proof_version,
+    });
+    StorageServiceRequest::new(data_request, use_compression)
+}
+
 /// Creates a request for state values
 fn create_state_values_request(
     version: Version,
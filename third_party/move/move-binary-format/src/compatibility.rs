@@ -15,8 +15,8 @@ use crate::{
     },
     CompiledModule,
 };
-use move_core_types::vm_status::StatusCode;
-use std::collections::BTreeSet;
+use move_core_types::{identifier::Identifier, vm_status::StatusCode};
+use std::{collections::BTreeSet, fmt};
 
 /// The result of a linking and layout compatibility check. Here is what the different combinations. NOTE that if `check_struct_layout` is false, type safety over a series of upgrades cannot be guaranteed.
 /// mean:
@@ -78,17 +78,47 @@ impl Compatibility {
         old_module: &CompiledModule,
         new_module: &CompiledModule,
     ) -> PartialVMResult<()> {
+        let errors = self.incompatibilities(old_module, new_module);
+        if !errors.is_empty() {
+            Err(
+                PartialVMError::new(StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE).with_message(
+                    format!(
+                        "Module update failure: new module not compatible with \
+                        existing module in `{}`: {}",
+                        old_module.self_id(),
+                        errors
+                            .iter()
+                            .map(|error| error.message.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                ),
+            )
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns all incompatibilities of `new_module` relative to old module `old_module`.
+    pub fn incompatibilities(
+        &self,
+        old_module: &CompiledModule,
+        new_module: &CompiledModule,
+    ) -> Vec<Incompatibility> {
         let mut errors = vec![];
 
         // module's name and address are unchanged
         if old_module.address() != new_module.address() {
-            errors.push(format!(
-                "module address changed to `{}`",
-                new_module.address()
+            errors.push(Incompatibility::new(
+                IncompatibleItem::Module,
+                format!("module address changed to `{}`", new_module.address()),
             ));
         }
         if old_module.name() != new_module.name() {
-            errors.push(format!("module name changed to `{}`", new_module.name()));
+            errors.push(Incompatibility::new(
+                IncompatibleItem::Module,
+                format!("module name changed to `{}`", new_module.name()),
+            ));
         }
 
         let old_view = ModuleView::new(old_module);
@@ -102,30 +132,40 @@ impl Compatibility {
                     // Struct not present in new . Existing modules that depend on this struct will fail to link with the new version of the module.
                     // Also, struct layout cannot be guaranteed transitively, because after
                     // removing the struct, it could be re-added later with a different layout.
-                    errors.push(format!("removed struct `{}`", old_struct.name()));
-                    break;
+                    errors.push(Incompatibility::new(
+                        IncompatibleItem::Struct(old_struct.name().to_owned()),
+                        format!("removed struct `{}`", old_struct.name()),
+                    ));
+                    continue;
                 },
             };
 
+            let struct_item = IncompatibleItem::Struct(old_struct.name().to_owned());
             if !struct_abilities_compatible(old_struct.abilities(), new_struct.abilities()) {
-                errors.push(format!(
-                    "removed abilities `{}` from struct `{}`",
-                    old_struct.abilities().setminus(new_struct.abilities()),
-                    old_struct.name()
+                errors.push(Incompatibility::new(
+                    struct_item.clone(),
+                    format!(
+                        "removed abilities `{}` from struct `{}`",
+                        old_struct.abilities().setminus(new_struct.abilities()),
+                        old_struct.name()
+                    ),
                 ));
             }
             if !struct_type_parameters_compatible(
                 old_struct.type_parameters(),
                 new_struct.type_parameters(),
             ) {
-                errors.push(format!(
-                    "changed type parameters of struct `{}`",
-                    old_struct.name()
+                errors.push(Incompatibility::new(
+                    struct_item.clone(),
+                    format!("changed type parameters of struct `{}`", old_struct.name()),
                 ));
             }
             // Layout of old and new struct need to be compatible
             if self.check_struct_layout && !struct_layout_compatible(&old_struct, new_struct) {
-                errors.push(format!("changed layout of struct `{}`", old_struct.name()));
+                errors.push(Incompatibility::new(
+                    struct_item,
+                    format!("changed layout of struct `{}`", old_struct.name()),
+                ));
             }
         }
 
@@ -155,7 +195,10 @@ impl Compatibility {
                     if !matches!(old_func.visibility(), Visibility::Friend)
                         || self.check_friend_linking
                     {
-                        errors.push(format!("removed function `{}`", old_func.name()));
+                        errors.push(Incompatibility::new(
+                            IncompatibleItem::Function(old_func.name().to_owned()),
+                            format!("removed function `{}`", old_func.name()),
+                        ));
                     }
                     continue;
                 },
@@ -211,7 +254,10 @@ impl Compatibility {
                 None
             };
             if let Some(msg) = error_msg {
-                errors.push(format!("{} of function `{}`", msg, old_func.name()));
+                errors.push(Incompatibility::new(
+                    IncompatibleItem::Function(old_func.name().to_owned()),
+                    format!("{} of function `{}`", msg, old_func.name()),
+                ));
             }
         }
 
@@ -226,31 +272,50 @@ impl Compatibility {
             let new_friend_module_ids: BTreeSet<_> =
                 new_module.immediate_friends().iter().cloned().collect();
             if !old_friend_module_ids.is_subset(&new_friend_module_ids) {
-                errors.push(format!(
-                    "removed friend declaration {}",
-                    old_friend_module_ids
-                        .difference(&new_friend_module_ids)
-                        .map(|id| format!("`{}`", id))
-                        .collect::<Vec<_>>()
-                        .join(" and ")
+                errors.push(Incompatibility::new(
+                    IncompatibleItem::FriendDeclarations,
+                    format!(
+                        "removed friend declaration {}",
+                        old_friend_module_ids
+                            .difference(&new_friend_module_ids)
+                            .map(|id| format!("`{}`", id))
+                            .collect::<Vec<_>>()
+                            .join(" and ")
+                    ),
                 ))
             }
         }
 
-        if !errors.is_empty() {
-            Err(
-                PartialVMError::new(StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE).with_message(
-                    format!(
-                        "Module update failure: new module not compatible with \
-                        existing module in `{}`: {}",
-                        old_view.id(),
-                        errors.join(", ")
-                    ),
-                ),
-            )
-        } else {
-            Ok(())
-        }
+        errors
+    }
+}
+
+/// The item of a module affected by an incompatible change.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum IncompatibleItem {
+    /// The module itself, e.g. its name or address
+    Module,
+    Struct(Identifier),
+    Function(Identifier),
+    FriendDeclarations,
+}
+
+/// An incompatible change of a module, as found by `Compatibility::incompatibilities`.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Incompatibility {
+    pub item: IncompatibleItem,
+    pub message: String,
+}
+
+impl Incompatibility {
+    fn new(item: IncompatibleItem, message: String) -> Self {
+        Self { item, message }
+    }
+}
+
+impl fmt::Display for Incompatibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
     }
 }
 
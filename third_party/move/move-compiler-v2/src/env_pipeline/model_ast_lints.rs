@@ -2,26 +2,266 @@
 // SPDX-License-Identifier: Apache-2.0
 
 //! This module (and its submodules) contain various model-AST-based lint checks.
+//!
+//! Each lint has a level (`allow`, `warn` or `deny`), which can be configured via a
+//! `LintConfig`. Lints can also be allowed for a module, struct or function using the
+//! `#[lint::allow(<lint_name>, ..)]` attribute.
 
 mod blocks_in_conditions;
+mod needless_borrow_global_mut;
+mod object_signer_exposure;
+mod public_mut_ref_to_global;
+mod signer_capability_leak;
+mod undeletable_resource;
 mod unnecessary_boolean_identity_comparison;
 mod unnecessary_numerical_extreme_comparison;
+mod unused_acquires;
 mod while_true;
 
+use anyhow::bail;
+use codespan_reporting::diagnostic::Severity;
+use move_compiler::shared::known_attributes::LintAttribute;
 use move_model::{
-    ast::ExpData,
-    model::{FunctionEnv, GlobalEnv},
+    ast::{Attribute, ExpData},
+    model::{FunctionEnv, GlobalEnv, Loc, ModuleEnv},
+    ty::Type,
 };
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    str::FromStr,
+};
+
+/// The attribute used to allow lints for a module, struct or function.
+pub const LINT_ALLOW_ATTRIBUTE: &str = LintAttribute::ALLOW;
+
+/// The level at which a lint is reported.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum LintLevel {
+    /// The lint is not checked.
+    Allow,
+    /// The lint is reported as a warning.
+    Warn,
+    /// The lint is reported as an error.
+    Deny,
+}
+
+impl FromStr for LintLevel {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allow" => Ok(LintLevel::Allow),
+            "warn" => Ok(LintLevel::Warn),
+            "deny" => Ok(LintLevel::Deny),
+            _ => bail!(
+                "invalid lint level `{}` (expected `allow`, `warn` or `deny`)",
+                s
+            ),
+        }
+    }
+}
+
+impl fmt::Display for LintLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LintLevel::Allow => write!(f, "allow"),
+            LintLevel::Warn => write!(f, "warn"),
+            LintLevel::Deny => write!(f, "deny"),
+        }
+    }
+}
+
+/// Describes a lint in the lint catalogue.
+#[derive(Clone, Debug)]
+pub struct LintInfo {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub default_level: LintLevel,
+}
+
+/// Returns the catalogue of all available lints (sorted by name).
+pub fn all_lints() -> Vec<LintInfo> {
+    let lint = |name, description| LintInfo {
+        name,
+        description,
+        default_level: LintLevel::Warn,
+    };
+    vec![
+        lint(
+            "blocks_in_conditions",
+            "Blocks in conditions make code harder to read.",
+        ),
+        lint(
+            "needless_borrow_global_mut",
+            "`borrow_global_mut` is used, but the resource is never mutated.",
+        ),
+        lint(
+            "object_signer_exposure",
+            "A public function exposes an object signer (or a ref that can generate one).",
+        ),
+        lint(
+            "public_mut_ref_to_global",
+            "A public function returns a mutable reference into global storage.",
+        ),
+        lint(
+            "signer_capability_leak",
+            "A public function exposes an account signer capability (or a signer created from one).",
+        ),
+        lint(
+            "undeletable_resource",
+            "A resource is moved to global storage, but can never be removed from it.",
+        ),
+        lint(
+            "unnecessary_boolean_identity_comparison",
+            "A boolean expression is compared with `true` or `false`.",
+        ),
+        lint(
+            "unnecessary_numerical_extreme_comparison",
+            "A number is compared with the minimum or maximum value of its type.",
+        ),
+        lint(
+            "unused_acquires",
+            "A function declares `acquires` for a resource it never accesses (acquires-check off).",
+        ),
+        lint(
+            "while_true",
+            "`while (true)` is used instead of the more explicit `loop`.",
+        ),
+    ]
+}
+
+/// The configured levels of the lints. Lints without a configured level use their default level.
+#[derive(Clone, Debug, Default)]
+pub struct LintConfig {
+    levels: BTreeMap<String, LintLevel>,
+}
 
-/// Perform various lint checks on the model AST.
+impl LintConfig {
+    /// Creates a new lint config. Returns an error if any of the lints is unknown.
+    pub fn new(levels: BTreeMap<String, LintLevel>) -> anyhow::Result<Self> {
+        let lints = all_lints();
+        for name in levels.keys() {
+            if !lints.iter().any(|lint| lint.name == name) {
+                bail!("unknown lint `{}`", name);
+            }
+        }
+        Ok(Self { levels })
+    }
+
+    /// Returns the configured level of the given lint.
+    pub fn level(&self, lint_name: &str) -> LintLevel {
+        self.levels.get(lint_name).cloned().unwrap_or_else(|| {
+            all_lints()
+                .into_iter()
+                .find(|lint| lint.name == lint_name)
+                .map_or(LintLevel::Warn, |lint| lint.default_level)
+        })
+    }
+}
+
+/// The context in which lints are checked and reported: the configured lint levels,
+/// and the lints allowed (via attributes) in the current module, struct or function.
+pub struct LintContext<'a> {
+    env: &'a GlobalEnv,
+    config: &'a LintConfig,
+    allowed_lints: BTreeSet<String>,
+}
+
+impl<'a> LintContext<'a> {
+    fn new(env: &'a GlobalEnv, config: &'a LintConfig) -> Self {
+        Self {
+            env,
+            config,
+            allowed_lints: BTreeSet::new(),
+        }
+    }
+
+    /// Returns a new context for an item with the given attributes (nested in the current item).
+    fn with_attributes(&self, attributes: &[Attribute]) -> Self {
+        let mut allowed_lints = self.allowed_lints.clone();
+        allowed_lints.extend(get_allowed_lints(self.env, attributes));
+        Self {
+            env: self.env,
+            config: self.config,
+            allowed_lints,
+        }
+    }
+
+    pub fn env(&self) -> &'a GlobalEnv {
+        self.env
+    }
+
+    /// Returns the level of the given lint in this context.
+    pub fn level(&self, lint_name: &str) -> LintLevel {
+        if self.allowed_lints.contains(lint_name) {
+            LintLevel::Allow
+        } else {
+            self.config.level(lint_name)
+        }
+    }
+
+    /// Reports the lint `lint_name` at `loc`, according to its level in this context.
+    pub fn report(&self, loc: &Loc, lint_name: &str, msg: &str) {
+        let severity = match self.level(lint_name) {
+            LintLevel::Allow => return,
+            LintLevel::Warn => Severity::Warning,
+            LintLevel::Deny => Severity::Error,
+        };
+        self.env
+            .lint_diag_with_severity(severity, loc, lint_name, msg);
+    }
+}
+
+/// Returns the lints allowed by the `#[lint::allow(..)]` attributes. Unknown lints are reported.
+fn get_allowed_lints(env: &GlobalEnv, attributes: &[Attribute]) -> BTreeSet<String> {
+    let lints = all_lints();
+    let mut allowed_lints = BTreeSet::new();
+    for attribute in attributes {
+        let Attribute::Apply(_, name, args) = attribute else {
+            continue;
+        };
+        if env.symbol_pool().string(*name).as_str() != LINT_ALLOW_ATTRIBUTE {
+            continue;
+        }
+        for arg in args {
+            let lint_name = env.symbol_pool().string(arg.name()).to_string();
+            if !lints.iter().any(|lint| lint.name == lint_name) {
+                let loc = env.get_node_loc(arg.node_id());
+                env.diag(
+                    Severity::Warning,
+                    &loc,
+                    &format!("unknown lint `{}`", lint_name),
+                );
+            }
+            allowed_lints.insert(lint_name);
+        }
+    }
+    allowed_lints
+}
+
+/// Perform various lint checks on the model AST (using the default lint levels).
 pub fn checker(env: &mut GlobalEnv) {
+    run_lints(env, &LintConfig::default());
+}
+
+/// Perform various lint checks on the model AST, using the given lint config.
+pub fn run_lints(env: &GlobalEnv, config: &LintConfig) {
+    let context = LintContext::new(env, config);
     for module in env.get_modules() {
         if module.is_primary_target() {
+            let module_context = context.with_attributes(module.get_attributes());
+            for module_linter in get_module_linter_pipeline() {
+                if module_context.level(module_linter.get_name()) != LintLevel::Allow {
+                    module_linter.check_module(&module_context, &module);
+                }
+            }
             for function in module.get_functions() {
                 if function.is_native() {
                     continue;
                 }
-                check_function(&function);
+                let function_context = module_context.with_attributes(function.get_attributes());
+                check_function(&function_context, &function);
             }
         }
     }
@@ -35,27 +275,55 @@ trait ExpressionLinter {
     fn get_name(&self) -> &'static str;
 
     /// Examine `expr` before any of its children have been visited.
-    /// Potentially emit lint warnings using `env.lint_diag()`.
-    fn visit_expr_pre(&mut self, _env: &GlobalEnv, _expr: &ExpData) {}
+    /// Potentially emit lint warnings using `context.report()`.
+    fn visit_expr_pre(&mut self, _context: &LintContext, _expr: &ExpData) {}
 
     /// Examine `expr` after all its children have been visited.
-    /// Potentially emit lint warnings using `env.lint_diag()`.
-    fn visit_expr_post(&mut self, _env: &GlobalEnv, _expr: &ExpData) {}
+    /// Potentially emit lint warnings using `context.report()`.
+    fn visit_expr_post(&mut self, _context: &LintContext, _expr: &ExpData) {}
+}
+
+/// Implement this trait for lint checks that examine a function as a whole
+/// (e.g., its signature together with its body).
+trait FunctionLinter {
+    /// The name of the lint.
+    fn get_name(&self) -> &'static str;
+
+    /// Examine `function`, potentially emitting lint warnings using `context.report()`.
+    fn check_function(&self, context: &LintContext, function: &FunctionEnv);
+}
+
+/// Implement this trait for lint checks that examine a module as a whole
+/// (e.g., how its structs are used across its functions).
+trait ModuleLinter {
+    /// The name of the lint.
+    fn get_name(&self) -> &'static str;
+
+    /// Examine `module`, potentially emitting lint warnings using `context.report()`.
+    fn check_module(&self, context: &LintContext, module: &ModuleEnv);
 }
 
 /// Perform the lint checks on the code in `function`.
-fn check_function(function: &FunctionEnv) {
-    let mut expression_linters = get_expression_linter_pipeline();
+fn check_function(context: &LintContext, function: &FunctionEnv) {
+    for function_linter in get_function_linter_pipeline() {
+        if context.level(function_linter.get_name()) != LintLevel::Allow {
+            function_linter.check_function(context, function);
+        }
+    }
+
+    let mut expression_linters: Vec<_> = get_expression_linter_pipeline()
+        .into_iter()
+        .filter(|linter| context.level(linter.get_name()) != LintLevel::Allow)
+        .collect();
     if let Some(def) = function.get_def() {
         let mut visitor = |post: bool, e: &ExpData| {
-            let env = function.module_env.env;
             if !post {
                 for exp_lint in expression_linters.iter_mut() {
-                    exp_lint.visit_expr_pre(env, e);
+                    exp_lint.visit_expr_pre(context, e);
                 }
             } else {
                 for exp_lint in expression_linters.iter_mut() {
-                    exp_lint.visit_expr_post(env, e);
+                    exp_lint.visit_expr_post(context, e);
                 }
             }
             true
@@ -73,3 +341,58 @@ fn get_expression_linter_pipeline() -> Vec<Box<dyn ExpressionLinter>> {
         Box::<while_true::WhileTrue>::default(),
     ]
 }
+
+/// Returns a pipeline of "function linters" to run.
+fn get_function_linter_pipeline() -> Vec<Box<dyn FunctionLinter>> {
+    vec![
+        Box::<needless_borrow_global_mut::NeedlessBorrowGlobalMut>::default(),
+        Box::<object_signer_exposure::ObjectSignerExposure>::default(),
+        Box::<public_mut_ref_to_global::PublicMutRefToGlobal>::default(),
+        Box::<signer_capability_leak::SignerCapabilityLeak>::default(),
+        Box::<unused_acquires::UnusedAcquires>::default(),
+    ]
+}
+
+/// Returns a pipeline of "module linters" to run.
+fn get_module_linter_pipeline() -> Vec<Box<dyn ModuleLinter>> {
+    vec![Box::<undeletable_resource::UndeletableResource>::default()]
+}
+
+/// Returns true if the function can be called from outside its module and friends
+/// (i.e., it is `public`, but not `public(friend)` or private).
+fn is_publicly_callable(function: &FunctionEnv) -> bool {
+    function.visibility() == move_binary_format::file_format::Visibility::Public
+}
+
+/// Returns true if the result type of the function contains the struct with the given full
+/// name (with module address), e.g., `0x1::account::SignerCapability`.
+fn returns_struct(function: &FunctionEnv, struct_name: &str) -> bool {
+    let env = function.module_env.env;
+    function.get_result_type().contains(&|ty: &Type| {
+        if let Type::Struct(mid, sid, _) = ty {
+            env.get_struct(mid.qualified(*sid))
+                .get_full_name_with_address()
+                == struct_name
+        } else {
+            false
+        }
+    })
+}
+
+/// Returns true if the function calls a function whose full
+/// name (with module address) is one of `function_names`.
+fn calls_any_of(function: &FunctionEnv, function_names: &[&str]) -> bool {
+    use move_model::ast::Operation;
+    let env = function.module_env.env;
+    let Some(def) = function.get_def() else {
+        return false;
+    };
+    def.any(&mut |e| {
+        if let ExpData::Call(_, Operation::MoveFunction(mid, fid), _) = e {
+            let callee = env.get_function(mid.qualified(*fid));
+            function_names.contains(&callee.get_full_name_with_address().as_str())
+        } else {
+            false
+        }
+    })
+}
@@ -9,11 +9,8 @@
 //!
 //! We also only report on the outermost condition with blocks.
 
-use crate::env_pipeline::model_ast_lints::ExpressionLinter;
-use move_model::{
-    ast::ExpData,
-    model::{GlobalEnv, NodeId},
-};
+use crate::env_pipeline::model_ast_lints::{ExpressionLinter, LintContext};
+use move_model::{ast::ExpData, model::NodeId};
 
 /// Expression linter keeping track of traversal state.
 #[derive(Default)]
@@ -42,7 +39,7 @@ impl ExpressionLinter for BlocksInConditions {
         "blocks_in_conditions"
     }
 
-    fn visit_expr_pre(&mut self, _env: &GlobalEnv, expr: &ExpData) {
+    fn visit_expr_pre(&mut self, _context: &LintContext, expr: &ExpData) {
         use CondExprState::*;
         use ExpData::{Block, IfElse, Match, Sequence, SpecBlock};
         match self.state {
@@ -77,8 +74,9 @@ impl ExpressionLinter for BlocksInConditions {
         }
     }
 
-    fn visit_expr_post(&mut self, env: &GlobalEnv, expr: &ExpData) {
+    fn visit_expr_post(&mut self, context: &LintContext, expr: &ExpData) {
         use CondExprState::*;
+        let env = context.env();
         match self.state {
             Some(Traversing {
                 id,
@@ -88,7 +86,7 @@ impl ExpressionLinter for BlocksInConditions {
                 // We are done with traversing the condition of interest.
                 self.state = None;
                 if has_any_block && !has_spec_block {
-                    env.lint_diag(
+                    context.report(
                         &env.get_node_loc(id),
                         self.get_name(),
                         "Having blocks in conditions make code harder to read. Consider rewriting this code.",
@@ -0,0 +1,71 @@
+// Copyright (c) Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module implements a function linter that checks for uses of `borrow_global_mut`
+//! in functions which never mutate through a reference, nor pass a mutable reference
+//! on to a callee or the caller. In such functions, `borrow_global` suffices.
+//!
+//! The check is deliberately conservative: any mutation, mutable borrow, or mutable
+//! reference flowing into a call or the result suppresses the warning.
+
+use crate::env_pipeline::model_ast_lints::{FunctionLinter, LintContext};
+use move_model::{
+    ast::{ExpData, Operation},
+    model::FunctionEnv,
+    ty::{ReferenceKind, Type},
+};
+
+#[derive(Default)]
+pub struct NeedlessBorrowGlobalMut;
+
+impl FunctionLinter for NeedlessBorrowGlobalMut {
+    fn get_name(&self) -> &'static str {
+        "needless_borrow_global_mut"
+    }
+
+    fn check_function(&self, context: &LintContext, function: &FunctionEnv) {
+        let env = context.env();
+        let Some(def) = function.get_def() else {
+            return;
+        };
+        if function
+            .get_result_type()
+            .contains(&|ty: &Type| ty.is_mutable_reference())
+        {
+            return;
+        }
+        let mut borrows = vec![];
+        let mut may_mutate = false;
+        def.visit_pre_order(&mut |e| {
+            match e {
+                ExpData::Call(id, Operation::BorrowGlobal(ReferenceKind::Mutable), _) => {
+                    borrows.push(*id)
+                },
+                ExpData::Mutate(..)
+                | ExpData::Call(_, Operation::Borrow(ReferenceKind::Mutable), _) => {
+                    may_mutate = true
+                },
+                ExpData::Call(_, Operation::MoveFunction(..), args)
+                | ExpData::Invoke(_, _, args) => {
+                    may_mutate |= args
+                        .iter()
+                        .any(|arg| env.get_node_type(arg.node_id()).is_mutable_reference())
+                },
+                _ => {},
+            }
+            // Stop visiting once we know the body may mutate.
+            !may_mutate
+        });
+        if may_mutate {
+            return;
+        }
+        for id in borrows {
+            context.report(
+                &env.get_node_loc(id),
+                self.get_name(),
+                "`borrow_global_mut` is used, but the resource is never mutated. \
+                Consider using `borrow_global` instead.",
+            );
+        }
+    }
+}
@@ -0,0 +1,40 @@
+// Copyright (c) Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module implements a function linter that checks for public functions
+//! exposing an object signer (generated via `object::generate_signer` or
+//! `object::generate_signer_for_extending`), or an `ExtendRef` which can be
+//! used to generate one. Either gives any caller full control over the object.
+
+use crate::env_pipeline::model_ast_lints::{
+    calls_any_of, is_publicly_callable, returns_struct, FunctionLinter, LintContext,
+};
+use move_model::{model::FunctionEnv, ty::Type};
+
+#[derive(Default)]
+pub struct ObjectSignerExposure;
+
+impl FunctionLinter for ObjectSignerExposure {
+    fn get_name(&self) -> &'static str {
+        "object_signer_exposure"
+    }
+
+    fn check_function(&self, context: &LintContext, function: &FunctionEnv) {
+        if !is_publicly_callable(function) {
+            return;
+        }
+        let msg = if returns_struct(function, "0x1::object::ExtendRef") {
+            "public function exposes an object `ExtendRef`, which can generate the object signer"
+        } else if function.get_result_type().contains(&Type::is_signer)
+            && calls_any_of(function, &[
+                "0x1::object::generate_signer",
+                "0x1::object::generate_signer_for_extending",
+            ])
+        {
+            "public function exposes an object signer"
+        } else {
+            return;
+        };
+        context.report(&function.get_id_loc(), self.get_name(), msg);
+    }
+}
@@ -0,0 +1,50 @@
+// Copyright (c) Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module implements a function linter that checks for public functions
+//! returning a mutable reference into global storage, obtained via `borrow_global_mut`.
+//! Such functions allow any caller to arbitrarily modify the resource, bypassing
+//! the invariants enforced by the module.
+
+use crate::env_pipeline::model_ast_lints::{is_publicly_callable, FunctionLinter, LintContext};
+use move_model::{
+    ast::{ExpData, Operation},
+    model::FunctionEnv,
+    ty::{ReferenceKind, Type},
+};
+
+#[derive(Default)]
+pub struct PublicMutRefToGlobal;
+
+impl FunctionLinter for PublicMutRefToGlobal {
+    fn get_name(&self) -> &'static str {
+        "public_mut_ref_to_global"
+    }
+
+    fn check_function(&self, context: &LintContext, function: &FunctionEnv) {
+        if !is_publicly_callable(function)
+            || !function
+                .get_result_type()
+                .contains(&|ty: &Type| ty.is_mutable_reference())
+        {
+            return;
+        }
+        let Some(def) = function.get_def() else {
+            return;
+        };
+        let borrows_global_mut = def.any(&mut |e| {
+            matches!(
+                e,
+                ExpData::Call(_, Operation::BorrowGlobal(ReferenceKind::Mutable), _)
+            )
+        });
+        if borrows_global_mut {
+            context.report(
+                &function.get_id_loc(),
+                self.get_name(),
+                "public function returns a mutable reference into global storage, \
+                which allows any caller to modify the resource",
+            );
+        }
+    }
+}
@@ -0,0 +1,36 @@
+// Copyright (c) Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module implements a function linter that checks for public functions
+//! exposing an account `SignerCapability`, or a signer created from one.
+//! Either gives any caller full control over the (resource) account.
+
+use crate::env_pipeline::model_ast_lints::{
+    calls_any_of, is_publicly_callable, returns_struct, FunctionLinter, LintContext,
+};
+use move_model::{model::FunctionEnv, ty::Type};
+
+#[derive(Default)]
+pub struct SignerCapabilityLeak;
+
+impl FunctionLinter for SignerCapabilityLeak {
+    fn get_name(&self) -> &'static str {
+        "signer_capability_leak"
+    }
+
+    fn check_function(&self, context: &LintContext, function: &FunctionEnv) {
+        if !is_publicly_callable(function) {
+            return;
+        }
+        let msg = if returns_struct(function, "0x1::account::SignerCapability") {
+            "public function exposes an account `SignerCapability`"
+        } else if function.get_result_type().contains(&Type::is_signer)
+            && calls_any_of(function, &["0x1::account::create_signer_with_capability"])
+        {
+            "public function exposes a signer created from an account `SignerCapability`"
+        } else {
+            return;
+        };
+        context.report(&function.get_id_loc(), self.get_name(), msg);
+    }
+}
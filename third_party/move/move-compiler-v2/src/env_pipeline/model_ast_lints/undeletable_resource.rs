@@ -0,0 +1,65 @@
+// Copyright (c) Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module implements a module linter that checks for resources which are
+//! moved to global storage (via `move_to`) somewhere in their module, but are never
+//! moved out of it (via `move_from`). Such resources can never be deleted, which
+//! may be intended, but often is an oversight.
+
+use crate::env_pipeline::model_ast_lints::{LintContext, ModuleLinter};
+use move_model::{
+    ast::{ExpData, Operation},
+    model::ModuleEnv,
+    ty::Type,
+};
+use std::collections::BTreeSet;
+
+#[derive(Default)]
+pub struct UndeletableResource;
+
+impl ModuleLinter for UndeletableResource {
+    fn get_name(&self) -> &'static str {
+        "undeletable_resource"
+    }
+
+    fn check_module(&self, context: &LintContext, module: &ModuleEnv) {
+        let env = context.env();
+        let mid = module.get_id();
+        let mut moved_to = BTreeSet::new();
+        let mut moved_from = BTreeSet::new();
+        for function in module.get_functions() {
+            let Some(def) = function.get_def() else {
+                continue;
+            };
+            def.visit_pre_order(&mut |e| {
+                if let ExpData::Call(id, op @ (Operation::MoveTo | Operation::MoveFrom), _) = e {
+                    if let Some(Type::Struct(ty_mid, sid, _)) =
+                        env.get_node_instantiation(*id).first()
+                    {
+                        if *ty_mid == mid {
+                            if matches!(op, Operation::MoveTo) {
+                                moved_to.insert(*sid);
+                            } else {
+                                moved_from.insert(*sid);
+                            }
+                        }
+                    }
+                }
+                true
+            });
+        }
+        for sid in moved_to.difference(&moved_from) {
+            let struct_env = module.get_struct(*sid);
+            let struct_context = context.with_attributes(struct_env.get_attributes());
+            struct_context.report(
+                &struct_env.get_loc(),
+                self.get_name(),
+                &format!(
+                    "resource `{}` is moved to global storage, but never moved from it, \
+                    so it can never be deleted",
+                    struct_env.get_name().display(env.symbol_pool())
+                ),
+            );
+        }
+    }
+}
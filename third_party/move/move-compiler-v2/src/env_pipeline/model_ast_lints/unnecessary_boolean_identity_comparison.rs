@@ -9,11 +9,8 @@
 //!   `x == true` ==> `x`
 //!   `false != foo(x)` ==> `!foo(x)`
 
-use crate::env_pipeline::model_ast_lints::ExpressionLinter;
-use move_model::{
-    ast::{ExpData, Operation, Value},
-    model::GlobalEnv,
-};
+use crate::env_pipeline::model_ast_lints::{ExpressionLinter, LintContext};
+use move_model::ast::{ExpData, Operation, Value};
 
 #[derive(Default)]
 pub struct UnnecessaryBooleanIdentityComparison;
@@ -23,10 +20,11 @@ impl ExpressionLinter for UnnecessaryBooleanIdentityComparison {
         "unnecessary_boolean_identity_comparison"
     }
 
-    fn visit_expr_pre(&mut self, env: &GlobalEnv, expr: &ExpData) {
+    fn visit_expr_pre(&mut self, context: &LintContext, expr: &ExpData) {
         use ExpData::{Call, Value as ExpValue};
         use Operation::*;
         use Value::Bool;
+        let env = context.env();
         if let Call(_, cmp @ (Eq | Neq), args) = expr {
             // Narrowed down to == or != comparisons.
             debug_assert!(
@@ -45,7 +43,7 @@ impl ExpressionLinter for UnnecessaryBooleanIdentityComparison {
                         },
                         if *b { "true" } else { "false" }
                     );
-                    env.lint_diag(&env.get_node_loc(e.node_id()), self.get_name(), &msg);
+                    context.report(&env.get_node_loc(e.node_id()), self.get_name(), &msg);
                 },
                 _ => {},
             }
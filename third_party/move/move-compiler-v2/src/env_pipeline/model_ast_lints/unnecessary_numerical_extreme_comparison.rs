@@ -12,10 +12,9 @@
 //!   `x > 0` ==> can be clarified to `x != 0`
 //! and similarly for comparing `x` with u64::MAX.
 
-use crate::env_pipeline::model_ast_lints::ExpressionLinter;
+use crate::env_pipeline::model_ast_lints::{ExpressionLinter, LintContext};
 use move_model::{
     ast::{ExpData, Operation, Value},
-    model::GlobalEnv,
     ty::Type,
 };
 use num::BigInt;
@@ -29,9 +28,10 @@ impl ExpressionLinter for UnnecessaryNumericalExtremeComparison {
         "unnecessary_numerical_extreme_comparison"
     }
 
-    fn visit_expr_pre(&mut self, env: &GlobalEnv, expr: &ExpData) {
+    fn visit_expr_pre(&mut self, context: &LintContext, expr: &ExpData) {
         use ExpData::Call;
         use Operation::*;
+        let env = context.env();
         // Let's narrow down to the comparison operators we are interested in.
         if let Call(id, cmp @ (Le | Ge | Lt | Gt), args) = expr {
             debug_assert!(
@@ -43,7 +43,7 @@ impl ExpressionLinter for UnnecessaryNumericalExtremeComparison {
             // get the type of the left-hand side.
             let ty = env.get_node_type(lhs.node_id());
             if let Some(result) = Self::check_comparisons_with_extremes(lhs, cmp, rhs, &ty) {
-                env.lint_diag(&env.get_node_loc(*id), self.get_name(), &result.to_string());
+                context.report(&env.get_node_loc(*id), self.get_name(), &result.to_string());
             }
         }
     }
@@ -0,0 +1,79 @@
+// Copyright (c) Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module implements a function linter that checks for `acquires R` annotations
+//! where the function neither accesses `R` directly (via `move_from`, `borrow_global`
+//! or `borrow_global_mut`), nor calls any function of the same module which may.
+//!
+//! The lint is skipped if the strict acquires check is enabled, as that check
+//! already reports unnecessary acquires annotations as errors.
+
+use crate::{
+    env_pipeline::model_ast_lints::{FunctionLinter, LintContext},
+    Experiment, Options,
+};
+use move_model::{
+    ast::{ExpData, Operation, ResourceSpecifier},
+    model::FunctionEnv,
+    ty::Type,
+};
+
+#[derive(Default)]
+pub struct UnusedAcquires;
+
+impl FunctionLinter for UnusedAcquires {
+    fn get_name(&self) -> &'static str {
+        "unused_acquires"
+    }
+
+    fn check_function(&self, context: &LintContext, function: &FunctionEnv) {
+        let env = context.env();
+        if env
+            .get_extension::<Options>()
+            .is_some_and(|options| options.experiment_on(Experiment::ACQUIRES_CHECK))
+        {
+            return;
+        }
+        let (Some(access_specifiers), Some(def)) =
+            (function.get_access_specifiers(), function.get_def())
+        else {
+            return;
+        };
+        let mid = function.module_env.get_id();
+        let calls_same_module = def.any(&mut |e| {
+            matches!(e, ExpData::Call(_, Operation::MoveFunction(callee_mid, _), _) if *callee_mid == mid)
+        });
+        if calls_same_module {
+            // The resources may be acquired by the callee.
+            return;
+        }
+        for access_specifier in access_specifiers {
+            let ResourceSpecifier::Resource(inst_qid) = &access_specifier.resource.1 else {
+                continue;
+            };
+            if inst_qid.module_id != mid {
+                continue;
+            }
+            let accessed = def.any(&mut |e| match e {
+                ExpData::Call(id, Operation::MoveFrom | Operation::BorrowGlobal(_), _) => {
+                    matches!(
+                        env.get_node_instantiation(*id).first(),
+                        Some(Type::Struct(ty_mid, sid, _)) if *ty_mid == mid && *sid == inst_qid.id
+                    )
+                },
+                _ => false,
+            });
+            if !accessed {
+                let struct_env = env.get_struct(inst_qid.to_qualified_id());
+                context.report(
+                    &access_specifier.resource.0,
+                    self.get_name(),
+                    &format!(
+                        "function acquires `{}`, but never accesses it",
+                        struct_env.get_name().display(env.symbol_pool())
+                    ),
+                );
+            }
+        }
+    }
+}
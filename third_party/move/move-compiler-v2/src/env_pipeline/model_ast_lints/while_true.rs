@@ -4,7 +4,7 @@
 //! This module implements an expression linter that checks code of the form:
 //! `while (true) { ... }` and suggests to use `loop { ... }` instead.
 
-use crate::env_pipeline::model_ast_lints::ExpressionLinter;
+use crate::env_pipeline::model_ast_lints::{ExpressionLinter, LintContext};
 use move_compiler::parser::syntax::FOR_LOOP_UPDATE_ITER_FLAG;
 use move_model::{
     ast::{Exp, ExpData, Value},
@@ -19,8 +19,9 @@ impl ExpressionLinter for WhileTrue {
         "while_true"
     }
 
-    fn visit_expr_pre(&mut self, env: &GlobalEnv, expr: &ExpData) {
+    fn visit_expr_pre(&mut self, context: &LintContext, expr: &ExpData) {
         use ExpData::{IfElse, Loop};
+        let env = context.env();
         // Check if `expr` is of the form: Loop(IfElse(true, then, _)).
         let Loop(id, body) = expr else { return };
         let IfElse(_, cond, then, _) = body.as_ref() else {
@@ -37,7 +38,7 @@ impl ExpressionLinter for WhileTrue {
             return;
         }
         // If we are here, it is `while (true) {...}`.
-        env.lint_diag(
+        context.report(
             &env.get_node_loc(*id),
             self.get_name(),
             "Use the more explicit `loop` instead.",
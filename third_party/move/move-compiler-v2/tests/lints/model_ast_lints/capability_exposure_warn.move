@@ -0,0 +1,45 @@
+module 0x1::account {
+    struct SignerCapability has drop, store { account: address }
+
+    public fun create_signer_with_capability(_capability: &SignerCapability): signer {
+        abort 0
+    }
+}
+
+module 0x1::object {
+    struct ExtendRef has drop, store { self: address }
+
+    public fun generate_signer_for_extending(_ref: &ExtendRef): signer {
+        abort 0
+    }
+}
+
+module 0xc0ffee::m {
+    use 0x1::account::{Self, SignerCapability};
+    use 0x1::object::{Self, ExtendRef};
+
+    struct Store has key { cap: SignerCapability, extend_ref: ExtendRef }
+
+    public fun test_warn_1(addr: address): SignerCapability acquires Store {
+        let Store { cap, extend_ref: _ } = move_from<Store>(addr);
+        cap
+    }
+
+    public fun test_warn_2(addr: address): signer acquires Store {
+        account::create_signer_with_capability(&borrow_global<Store>(addr).cap)
+    }
+
+    public fun test_warn_3(addr: address): ExtendRef acquires Store {
+        let Store { cap: _, extend_ref } = move_from<Store>(addr);
+        extend_ref
+    }
+
+    public fun test_warn_4(addr: address): signer acquires Store {
+        object::generate_signer_for_extending(&borrow_global<Store>(addr).extend_ref)
+    }
+
+    public fun test_no_warn(addr: address): address acquires Store {
+        let s = account::create_signer_with_capability(&borrow_global<Store>(addr).cap);
+        std::signer::address_of(&s)
+    }
+}
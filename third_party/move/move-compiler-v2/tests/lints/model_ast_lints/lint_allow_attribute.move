@@ -0,0 +1,28 @@
+#[lint::allow(while_true)]
+module 0xc0ffee::m {
+    public fun test_no_warn(x: u64) {
+        while (true) {
+            if (x > 10) break;
+        }
+    }
+}
+
+module 0xc0ffee::n {
+    struct R has key { x: u64 }
+
+    #[lint::allow(needless_borrow_global_mut)]
+    public fun test_no_warn(addr: address): u64 acquires R {
+        borrow_global_mut<R>(addr).x
+    }
+
+    public fun test_warn(addr: address): u64 acquires R {
+        borrow_global_mut<R>(addr).x
+    }
+
+    #[lint::allow(unknown_lint)]
+    public fun test_unknown_lint(x: u64) {
+        while (true) {
+            if (x > 10) break;
+        }
+    }
+}
@@ -0,0 +1,31 @@
+module 0xc0ffee::m {
+    struct R has key { x: u64 }
+
+    public fun test_warn(addr: address): u64 acquires R {
+        let r = borrow_global_mut<R>(addr);
+        r.x
+    }
+
+    public fun test_no_warn_1(addr: address) acquires R {
+        let r = borrow_global_mut<R>(addr);
+        r.x = r.x + 1;
+    }
+
+    public fun test_no_warn_2(addr: address) acquires R {
+        let r = borrow_global_mut<R>(addr);
+        let x = &mut r.x;
+        *x = 2;
+    }
+
+    fun reset(r: &mut R) {
+        r.x = 0;
+    }
+
+    public fun test_no_warn_3(addr: address) acquires R {
+        reset(borrow_global_mut<R>(addr));
+    }
+
+    public fun test_no_warn_4(addr: address): u64 acquires R {
+        borrow_global<R>(addr).x
+    }
+}
@@ -0,0 +1,19 @@
+module 0xc0ffee::m {
+    struct R has key { x: u64 }
+
+    public fun test_warn(addr: address): &mut u64 acquires R {
+        &mut borrow_global_mut<R>(addr).x
+    }
+
+    fun get_mut(addr: address): &mut u64 acquires R {
+        &mut borrow_global_mut<R>(addr).x
+    }
+
+    public fun test_no_warn_1(addr: address) acquires R {
+        *get_mut(addr) = 1;
+    }
+
+    public fun test_no_warn_2(r: &mut R): &mut u64 {
+        &mut r.x
+    }
+}
@@ -0,0 +1,24 @@
+module 0xc0ffee::m {
+    struct Counter has key { value: u64 }
+
+    struct Config has key { value: u64 }
+
+    #[lint::allow(undeletable_resource)]
+    struct Registry has key { value: u64 }
+
+    public fun init_counter(account: &signer) {
+        move_to(account, Counter { value: 0 });
+    }
+
+    public fun init_config(account: &signer) {
+        move_to(account, Config { value: 0 });
+    }
+
+    public fun remove_config(addr: address) acquires Config {
+        let Config { value: _ } = move_from<Config>(addr);
+    }
+
+    public fun init_registry(account: &signer) {
+        move_to(account, Registry { value: 0 });
+    }
+}
@@ -0,0 +1,25 @@
+module 0xc0ffee::m {
+    struct R has key { x: u64 }
+
+    struct S has key { x: u64 }
+
+    public fun test_warn(): u64 acquires R {
+        1
+    }
+
+    public fun test_warn_partial(addr: address): u64 acquires S, R {
+        borrow_global<S>(addr).x
+    }
+
+    public fun test_no_warn_1(addr: address): u64 acquires R {
+        borrow_global<R>(addr).x
+    }
+
+    public fun test_no_warn_2(addr: address): R acquires R {
+        move_from<R>(addr)
+    }
+
+    public fun test_no_warn_3(addr: address): u64 acquires R {
+        test_no_warn_1(addr)
+    }
+}
@@ -639,6 +639,22 @@ const TEST_CONFIGS: Lazy<BTreeMap<&str, TestConfig>> = Lazy::new(|| {
             dump_bytecode: DumpLevel::None,
             dump_bytecode_filter: None,
         },
+        // Some lints (e.g., `unused_acquires`) are only reported if the acquires check is off
+        TestConfig {
+            name: "lint-checks-no-acquires-check",
+            runner: |p| run_test(p, get_config_by_name("lint-checks-no-acquires-check")),
+            include: vec!["/lints/no_acquires_check/"],
+            exclude: vec![],
+            exp_suffix: None,
+            options: opts
+                .clone()
+                .set_experiment(Experiment::LINT_CHECKS, true)
+                .set_experiment(Experiment::ACQUIRES_CHECK, false),
+            stop_after: StopAfter::AstPipeline,
+            dump_ast: DumpLevel::None,
+            dump_bytecode: DumpLevel::None,
+            dump_bytecode_filter: None,
+        },
     ];
     configs.into_iter().map(|c| (c.name, c)).collect()
 });
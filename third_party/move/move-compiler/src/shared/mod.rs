@@ -648,6 +648,7 @@ pub mod known_attributes {
         Verification(VerificationAttribute),
         Native(NativeAttribute),
         Deprecation(DeprecationAttribute),
+        Lint(LintAttribute),
     }
 
     #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -679,6 +680,12 @@ pub mod known_attributes {
         Deprecated,
     }
 
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum LintAttribute {
+        // Allows the listed lints for the annotated module, struct or function
+        Allow,
+    }
+
     impl fmt::Display for AttributePosition {
         fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             match self {
@@ -713,6 +720,7 @@ pub mod known_attributes {
                 DeprecationAttribute::DEPRECATED_NAME => {
                     Self::Deprecation(DeprecationAttribute::Deprecated)
                 },
+                LintAttribute::ALLOW => Self::Lint(LintAttribute::Allow),
                 _ => return None,
             })
         }
@@ -733,6 +741,7 @@ pub mod known_attributes {
             VerificationAttribute::add_attribute_names(table);
             NativeAttribute::add_attribute_names(table);
             DeprecationAttribute::add_attribute_names(table);
+            LintAttribute::add_attribute_names(table);
         }
 
         fn name(&self) -> &str {
@@ -741,6 +750,7 @@ pub mod known_attributes {
                 Self::Verification(a) => a.name(),
                 Self::Native(a) => a.name(),
                 Self::Deprecation(a) => a.name(),
+                Self::Lint(a) => a.name(),
             }
         }
 
@@ -750,6 +760,7 @@ pub mod known_attributes {
                 Self::Verification(a) => a.expected_positions(),
                 Self::Native(a) => a.expected_positions(),
                 Self::Deprecation(a) => a.expected_positions(),
+                Self::Lint(a) => a.expected_positions(),
             }
         }
     }
@@ -920,4 +931,37 @@ pub mod known_attributes {
             }
         }
     }
+
+    impl LintAttribute {
+        pub const ALLOW: &'static str = "lint::allow";
+        const ALL_ATTRIBUTE_NAMES: [&'static str; 1] = [Self::ALLOW];
+    }
+
+    impl AttributeKind for LintAttribute {
+        fn add_attribute_names(table: &mut BTreeSet<String>) {
+            for str in Self::ALL_ATTRIBUTE_NAMES {
+                table.insert(str.to_string());
+            }
+        }
+
+        fn name(&self) -> &str {
+            match self {
+                Self::Allow => Self::ALLOW,
+            }
+        }
+
+        fn expected_positions(&self) -> &'static BTreeSet<AttributePosition> {
+            static ALLOW_POSITIONS: Lazy<BTreeSet<AttributePosition>> = Lazy::new(|| {
+                IntoIterator::into_iter([
+                    AttributePosition::Module,
+                    AttributePosition::Struct,
+                    AttributePosition::Function,
+                ])
+                .collect()
+            });
+            match self {
+                Self::Allow => &ALLOW_POSITIONS,
+            }
+        }
+    }
 }
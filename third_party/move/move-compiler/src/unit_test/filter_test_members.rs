@@ -244,7 +244,8 @@ fn test_attributes(attrs: &P::Attributes) -> Vec<(Loc, known_attributes::Testing
                 KnownAttribute::Testing(test_attr) => Some((attr.loc, test_attr)),
                 KnownAttribute::Verification(_)
                 | KnownAttribute::Native(_)
-                | KnownAttribute::Deprecation(_) => None,
+                | KnownAttribute::Deprecation(_)
+                | KnownAttribute::Lint(_) => None,
             },
         )
         .collect()
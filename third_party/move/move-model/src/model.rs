@@ -932,8 +932,19 @@ impl GlobalEnv {
 
     /// Add a lint warning to this environment, given the `lint_name` and `msg`.
     pub fn lint_diag(&self, loc: &Loc, lint_name: &str, msg: &str) {
+        self.lint_diag_with_severity(Severity::Warning, loc, lint_name, msg)
+    }
+
+    /// Add a lint diagnostic of given severity to this environment, given the `lint_name` and `msg`.
+    pub fn lint_diag_with_severity(
+        &self,
+        severity: Severity,
+        loc: &Loc,
+        lint_name: &str,
+        msg: &str,
+    ) {
         let msg = format!("[lint: `{}`] {}", lint_name, msg);
-        self.diag(Severity::Warning, loc, &msg)
+        self.diag(severity, loc, &msg)
     }
 
     /// Adds a diagnostic of given severity to this environment, with notes.
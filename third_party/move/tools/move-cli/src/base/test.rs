@@ -20,7 +20,10 @@ use move_package::{
     compilation::{build_plan::BuildPlan, compiled_package::build_and_report_v2_driver},
     BuildConfig,
 };
-use move_unit_test::UnitTestingConfig;
+use move_unit_test::{
+    test_runner::{CostTableGasMeterFactory, TestGasMeterFactory},
+    UnitTestingConfig,
+};
 use move_vm_runtime::tracing::{LOGGING_FILE_WRITER, TRACING_ENABLED};
 use move_vm_test_utils::gas_schedule::CostTable;
 // if unix
@@ -30,7 +33,7 @@ use std::os::unix::prelude::ExitStatusExt;
 #[cfg(target_family = "windows")]
 use std::os::windows::process::ExitStatusExt;
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fs,
     io::Write,
     ops::Deref,
@@ -159,14 +162,40 @@ pub enum UnitTestResult {
 
 pub fn run_move_unit_tests<W: Write + Send>(
     pkg_path: &Path,
-    mut build_config: move_package::BuildConfig,
-    mut unit_test_config: UnitTestingConfig,
+    build_config: move_package::BuildConfig,
+    unit_test_config: UnitTestingConfig,
     natives: Vec<NativeFunctionRecord>,
     genesis: ChangeSet,
     cost_table: Option<CostTable>,
     compute_coverage: bool,
     writer: &mut W,
 ) -> Result<UnitTestResult> {
+    run_move_unit_tests_with_gas_meter(
+        pkg_path,
+        build_config,
+        unit_test_config,
+        natives,
+        genesis,
+        CostTableGasMeterFactory::new(cost_table),
+        compute_coverage,
+        writer,
+    )
+    .map(|(result, _)| result)
+}
+
+/// Same as `run_move_unit_tests`, but meters the tests with the gas meters created by
+/// `gas_meter_factory`, and additionally returns the gas used by each passing test, keyed by its
+/// fully qualified name (`<addr>::<module_name>::<fn_name>`).
+pub fn run_move_unit_tests_with_gas_meter<W: Write + Send, F: TestGasMeterFactory>(
+    pkg_path: &Path,
+    mut build_config: move_package::BuildConfig,
+    mut unit_test_config: UnitTestingConfig,
+    natives: Vec<NativeFunctionRecord>,
+    genesis: ChangeSet,
+    gas_meter_factory: F,
+    compute_coverage: bool,
+    writer: &mut W,
+) -> Result<(UnitTestResult, BTreeMap<String, u64>)> {
     let mut test_plan = None;
     let mut test_plan_v2 = None;
 
@@ -301,13 +330,18 @@ pub fn run_move_unit_tests<W: Write + Send>(
 
     // Run the tests. If any of the tests fail, then we don't produce a coverage report, so cleanup
     // the trace files.
-    if !unit_test_config
-        .run_and_report_unit_tests(test_plan, Some(natives), Some(genesis), cost_table, writer)
-        .unwrap()
-        .1
-    {
+    let (_, ok, gas_used) = unit_test_config
+        .run_and_report_unit_tests_with_gas_meter(
+            test_plan,
+            Some(natives),
+            Some(genesis),
+            gas_meter_factory,
+            writer,
+        )
+        .unwrap();
+    if !ok {
         cleanup_trace();
-        return Ok(UnitTestResult::Failure);
+        return Ok((UnitTestResult::Failure, gas_used));
     }
 
     // Compute the coverage map. This will be used by other commands after this.
@@ -319,7 +353,7 @@ pub fn run_move_unit_tests<W: Write + Send>(
         let coverage_map = CoverageMap::from_trace_file(trace_path);
         output_map_to_file(coverage_map_path, &coverage_map).unwrap();
     }
-    Ok(UnitTestResult::Success)
+    Ok((UnitTestResult::Success, gas_used))
 }
 
 impl From<UnitTestResult> for ExitStatus {
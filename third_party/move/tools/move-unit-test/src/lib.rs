@@ -7,7 +7,7 @@ pub mod extensions;
 pub mod test_reporter;
 pub mod test_runner;
 
-use crate::test_runner::TestRunner;
+use crate::test_runner::{CostTableGasMeterFactory, TestGasMeterFactory, TestRunner};
 use clap::*;
 use move_command_line_common::files::verify_and_create_named_address_mapping;
 use move_compiler::{
@@ -219,6 +219,27 @@ impl UnitTestingConfig {
         cost_table: Option<CostTable>,
         writer: W,
     ) -> Result<(W, bool)> {
+        self.run_and_report_unit_tests_with_gas_meter(
+            test_plan,
+            native_function_table,
+            genesis_state,
+            CostTableGasMeterFactory::new(cost_table),
+            writer,
+        )
+        .map(|(writer, ok, _)| (writer, ok))
+    }
+
+    /// Same as `run_and_report_unit_tests`, but meters the tests with the gas meters created by
+    /// `gas_meter_factory`, and additionally returns the gas used by each passing test, keyed by
+    /// its fully qualified name (`<addr>::<module_name>::<fn_name>`).
+    pub fn run_and_report_unit_tests_with_gas_meter<W: Write + Send, F: TestGasMeterFactory>(
+        &self,
+        test_plan: TestPlan,
+        native_function_table: Option<NativeFunctionTable>,
+        genesis_state: Option<ChangeSet>,
+        gas_meter_factory: F,
+        writer: W,
+    ) -> Result<(W, bool, BTreeMap<String, u64>)> {
         let shared_writer = Mutex::new(writer);
 
         if self.list {
@@ -232,7 +253,7 @@ impl UnitTestingConfig {
                     )?;
                 }
             }
-            return Ok((shared_writer.into_inner().unwrap(), true));
+            return Ok((shared_writer.into_inner().unwrap(), true, BTreeMap::new()));
         }
 
         writeln!(shared_writer.lock().unwrap(), "Running Move unit tests")?;
@@ -244,7 +265,7 @@ impl UnitTestingConfig {
             test_plan,
             native_function_table,
             genesis_state,
-            cost_table,
+            gas_meter_factory,
             self.verbose,
             #[cfg(feature = "evm-backend")]
             self.evm,
@@ -267,7 +288,7 @@ impl UnitTestingConfig {
         let ok = test_results.summarize(&shared_writer)?;
 
         let writer = shared_writer.into_inner().unwrap();
-        Ok((writer, ok))
+        Ok((writer, ok, test_results.gas_used()))
     }
 }
 
@@ -65,7 +65,7 @@ pub struct TestFailure {
 pub struct TestRunInfo {
     pub function_ident: String,
     pub elapsed_time: Duration,
-    pub instructions_executed: u64,
+    pub gas_used: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -82,11 +82,11 @@ pub struct TestResults {
 }
 
 impl TestRunInfo {
-    pub fn new(function_ident: String, elapsed_time: Duration, instructions_executed: u64) -> Self {
+    pub fn new(function_ident: String, elapsed_time: Duration, gas_used: u64) -> Self {
         Self {
             function_ident,
             elapsed_time,
-            instructions_executed,
+            gas_used,
         }
     }
 }
@@ -485,7 +485,7 @@ impl TestResults {
                 stats.push((
                     qualified_function_name,
                     test_result.elapsed_time.as_secs_f32(),
-                    test_result.instructions_executed,
+                    test_result.gas_used,
                 ))
             }
         }
@@ -502,7 +502,7 @@ impl TestResults {
                 stats.push((
                     qualified_function_name,
                     test_failure.test_run_info.elapsed_time.as_secs_f32(),
-                    test_failure.test_run_info.instructions_executed,
+                    test_failure.test_run_info.gas_used,
                 ));
             }
         }
@@ -557,8 +557,29 @@ impl TestResults {
         writeln!(writer.lock().unwrap())
     }
 
+    /// Returns the gas used by each passing test, keyed by its fully qualified name
+    /// (`<addr>::<module_name>::<fn_name>`).
+    pub fn gas_used(&self) -> BTreeMap<String, u64> {
+        self.final_statistics
+            .passed
+            .iter()
+            .flat_map(|(module_id, test_results)| {
+                test_results.iter().map(move |test_result| {
+                    (
+                        format!(
+                            "{}::{}",
+                            format_module_id(module_id),
+                            test_result.function_ident
+                        ),
+                        test_result.gas_used,
+                    )
+                })
+            })
+            .collect()
+    }
+
     /// Returns `true` if all tests passed, `false` if there was a test failure/timeout
-    pub fn summarize<W: Write>(self, writer: &Mutex<W>) -> Result<bool> {
+    pub fn summarize<W: Write>(&self, writer: &Mutex<W>) -> Result<bool> {
         let num_failed_tests = self
             .final_statistics
             .failed
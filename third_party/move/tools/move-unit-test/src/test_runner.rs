@@ -17,6 +17,7 @@ use move_core_types::{
     account_address::AccountAddress,
     effects::{ChangeSet, Op},
     identifier::IdentStr,
+    language_storage::ModuleId,
     value::serialize_values,
     vm_status::StatusCode,
 };
@@ -31,6 +32,7 @@ use move_vm_test_utils::{
     gas_schedule::{zero_cost_schedule, CostTable, Gas, GasCost, GasStatus},
     InMemoryStorage,
 };
+use move_vm_types::gas::GasMeter;
 use rayon::prelude::*;
 use std::{io::Write, marker::Send, sync::Mutex, time::Instant};
 #[cfg(feature = "evm-backend")]
@@ -44,11 +46,11 @@ use {
 };
 
 /// Test state common to all tests
-pub struct SharedTestingConfig {
+pub struct SharedTestingConfig<F> {
     save_storage_state_on_failure: bool,
     report_stacktrace_on_abort: bool,
     execution_bound: u64,
-    cost_table: CostTable,
+    gas_meter_factory: F,
     native_function_table: NativeFunctionTable,
     starting_storage_state: InMemoryStorage,
     #[allow(dead_code)] // used by some features
@@ -59,12 +61,37 @@ pub struct SharedTestingConfig {
     evm: bool,
 }
 
-pub struct TestRunner {
+pub struct TestRunner<F = CostTableGasMeterFactory> {
     num_threads: usize,
-    testing_config: SharedTestingConfig,
+    testing_config: SharedTestingConfig<F>,
     tests: TestPlan,
 }
 
+/// Creates the gas meter each test is run with, and measures the gas used by the test.
+pub trait TestGasMeterFactory: Send + Sync {
+    type GasMeter<'a>: GasMeter
+    where
+        Self: 'a;
+
+    /// Returns the gas meter to run `function_name` of `module_id` with, where `execution_bound`
+    /// is the amount of gas the test may use.
+    fn new_gas_meter(
+        &self,
+        module_id: &ModuleId,
+        function_name: &IdentStr,
+        execution_bound: u64,
+    ) -> Self::GasMeter<'_>;
+
+    /// Returns the gas used by a test, given its gas meter and (if the test executed
+    /// successfully) the changes it made to storage.
+    fn gas_used(
+        &self,
+        gas_meter: Self::GasMeter<'_>,
+        execution_bound: u64,
+        change_set: Option<&ChangeSet>,
+    ) -> u64;
+}
+
 /// A gas schedule where every instruction has a cost of "1". This is used to bound execution of a
 /// test to a certain number of ticks.
 fn unit_cost_table() -> CostTable {
@@ -75,6 +102,50 @@ fn unit_cost_table() -> CostTable {
     cost_schedule
 }
 
+/// Meters tests with a cost table, which is only used to bound their execution (by default, every
+/// instruction has a cost of "1"). Storage accesses are free.
+pub struct CostTableGasMeterFactory {
+    cost_table: CostTable,
+}
+
+impl CostTableGasMeterFactory {
+    pub fn new(cost_table: Option<CostTable>) -> Self {
+        Self {
+            // TODO: our current implementation uses a unit cost table to prevent programs from
+            // running indefinitely. This should probably be done in a different way, like halting
+            // after executing a certain number of instructions or setting a timer.
+            cost_table: cost_table.unwrap_or_else(unit_cost_table),
+        }
+    }
+}
+
+impl TestGasMeterFactory for CostTableGasMeterFactory {
+    type GasMeter<'a> = GasStatus<'a>;
+
+    fn new_gas_meter(
+        &self,
+        _module_id: &ModuleId,
+        _function_name: &IdentStr,
+        execution_bound: u64,
+    ) -> GasStatus<'_> {
+        GasStatus::new(&self.cost_table, Gas::new(execution_bound))
+    }
+
+    fn gas_used(
+        &self,
+        gas_meter: GasStatus<'_>,
+        execution_bound: u64,
+        _change_set: Option<&ChangeSet>,
+    ) -> u64 {
+        // TODO(Gas): This doesn't look quite right...
+        //            We're not computing the number of instructions executed even with a unit gas schedule.
+        Gas::new(execution_bound)
+            .checked_sub(gas_meter.remaining_gas())
+            .unwrap()
+            .into()
+    }
+}
+
 /// Setup storage state with the set of modules that will be needed for all tests
 fn setup_test_storage<'a>(
     modules: impl Iterator<Item = &'a CompiledModule>,
@@ -122,7 +193,7 @@ fn print_resources_and_extensions(
     Ok(buf)
 }
 
-impl TestRunner {
+impl<F: TestGasMeterFactory> TestRunner<F> {
     pub fn new(
         execution_bound: u64,
         num_threads: usize,
@@ -133,7 +204,7 @@ impl TestRunner {
         // we don't have to make assumptions about their gas parameters.
         native_function_table: Option<NativeFunctionTable>,
         genesis_state: Option<ChangeSet>,
-        cost_table: Option<CostTable>,
+        gas_meter_factory: F,
         record_writeset: bool,
         #[cfg(feature = "evm-backend")] evm: bool,
     ) -> Result<Self> {
@@ -160,12 +231,7 @@ impl TestRunner {
                 starting_storage_state,
                 execution_bound,
                 native_function_table,
-                // TODO: our current implementation uses a unit cost table to prevent programs from
-                // running indefinitely. This should probably be done in a different way, like halting
-                // after executing a certain number of instructions or setting a timer.
-                //
-                // From the API standpoint, we should let the client specify the cost table.
-                cost_table: cost_table.unwrap_or_else(unit_cost_table),
+                gas_meter_factory,
                 source_files,
                 record_writeset,
                 #[cfg(feature = "evm-backend")]
@@ -253,7 +319,7 @@ impl<'a, 'b, W: Write> TestOutput<'a, 'b, W> {
     }
 }
 
-impl SharedTestingConfig {
+impl<F: TestGasMeterFactory> SharedTestingConfig<F> {
     #[allow(clippy::field_reassign_with_default)]
     fn execute_via_move_vm(
         &self,
@@ -270,14 +336,19 @@ impl SharedTestingConfig {
         let extensions = extensions::new_extensions();
         let mut session =
             move_vm.new_session_with_extensions(&self.starting_storage_state, extensions);
-        let mut gas_meter = GasStatus::new(&self.cost_table, Gas::new(self.execution_bound));
+        let function_name = IdentStr::new(function_name).unwrap();
+        let mut gas_meter = self.gas_meter_factory.new_gas_meter(
+            &test_plan.module_id,
+            function_name,
+            self.execution_bound,
+        );
         // TODO: collect VM logs if the verbose flag (i.e, `self.verbose`) is set
 
         let now = Instant::now();
         let storage = TraversalStorage::new();
         let serialized_return_values_result = session.execute_function_bypass_visibility(
             &test_plan.module_id,
-            IdentStr::new(function_name).unwrap(),
+            function_name,
             vec![], // no ty args, at least for now
             serialize_values(test_info.arguments.iter()),
             &mut gas_meter,
@@ -294,17 +365,20 @@ impl SharedTestingConfig {
                 err.remove_exec_state();
             }
         }
-        let test_run_info = TestRunInfo::new(
-            function_name.to_string(),
-            now.elapsed(),
-            // TODO(Gas): This doesn't look quite right...
-            //            We're not computing the number of instructions executed even with a unit gas schedule.
-            Gas::new(self.execution_bound)
-                .checked_sub(gas_meter.remaining_gas())
-                .unwrap()
-                .into(),
-        );
-        match session.finish_with_extensions() {
+        let elapsed_time = now.elapsed();
+        let finish_result = session.finish_with_extensions();
+
+        // Only the changes of successfully executed tests are charged, as they are discarded
+        // otherwise
+        let change_set = match (&finish_result, &return_result) {
+            (Ok((cs, _)), Ok(_)) => Some(cs),
+            _ => None,
+        };
+        let gas_used = self
+            .gas_meter_factory
+            .gas_used(gas_meter, self.execution_bound, change_set);
+        let test_run_info = TestRunInfo::new(function_name.to_string(), elapsed_time, gas_used);
+        match finish_result {
             Ok((cs, extensions)) => (Ok(cs), Ok(extensions), return_result, test_run_info),
             Err(err) => (Err(err.clone()), Err(err), return_result, test_run_info),
         }
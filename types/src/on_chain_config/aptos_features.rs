@@ -93,6 +93,7 @@ pub enum FeatureFlag {
     ENABLE_RESOURCE_ACCESS_CONTROL = 75,
     REJECT_UNSTABLE_BYTECODE_FOR_SCRIPT = 76,
     FEDERATED_KEYLESS = 77,
+    SECP256R1_NATIVES = 78,
 }
 
 impl FeatureFlag {
@@ -169,6 +170,7 @@ impl FeatureFlag {
             FeatureFlag::ENABLE_ENUM_TYPES,
             FeatureFlag::ENABLE_RESOURCE_ACCESS_CONTROL,
             FeatureFlag::REJECT_UNSTABLE_BYTECODE_FOR_SCRIPT,
+            FeatureFlag::SECP256R1_NATIVES,
         ]
     }
 }